@@ -5,6 +5,7 @@
 pragma solidity ^0.8.24;
 
 import {IOABE} from "./interfaces/IOABE.sol";
+import {IPoseidon} from "./interfaces/IPoseidon.sol";
 import {ArrayUtils} from "./libraries/ArrayUtils.sol";
 // import "./libraries/ArrayUtils.sol";
 import "@openzeppelin/contracts/utils/Strings.sol";
@@ -15,6 +16,8 @@ import "@openzeppelin/contracts/utils/Strings.sol";
 // 2. DecryptionCloudServer(DCS)提交结果。submitResult【任何人可以在链上看到任务内容，然后到cloud上请求密文，之后就可以自己计算出结果。谁先算出结果，就提交到链上，这个任务就算被他领去了】
 // 3. Challenger质疑。challenge【质疑者需要质押一定的token，如果质疑成功，质疑者可以获得一定的奖励，否则质疑者的token会被处罚】
 // 4. DCS提交证明。proveState【DCS需要提交zkproof，证明自己的结果是正确的。如果zkproof验证通过，DCS可以获得一定的奖励，否则DCS的token会被处罚】
+// 【密文证明】用createTaskWithCiphertext发布的任务，需要先由加密者调用proveCiphertext证明密文是按访问策略正确生成的（ciphertext circuit），
+//  之后DCS才能提交结果、任务才能被challenge。否则恶意的DataUser可以发布畸形密文，让诚实的DCS解密失败并被罚没
 
 // DCS管理
 // 1. DCS注册。registerDCS
@@ -55,9 +58,33 @@ contract OABE is IOABE {
     uint constant MINIMAL_DCS_DEPOSIT = 1 ether;            // 注册成为外包计算员需要的质押金
     uint constant MINIMAL_CHALLENGE_DEPOSIT = 100000 wei;   // 发起challenge需要的质押金
 
+    // BN254标量域的模数。Poseidon承诺模式下，dataHash和result都是该域上的元素
+    uint constant SCALAR_FIELD_MODULUS = 21888242871839275222246405745257275088548364400416034343698204186575808495617;
+
+    // 两个元素的Poseidon哈希的domain，与电路中poseidon_commitment对两个元素的消息所用的相同：2 * 31 * 2^64
+    uint constant POSEIDON_PAIR_DOMAIN = 2 * 31 * 2**64;
+
     /// @notice The address of highly optimized plonk verifier contract.
     address public immutable PLONK_VERIFIER;
 
+    /// @notice The address of the plonk verifier contract of the pairing circuit in Poseidon commitment mode.
+    address public immutable POSEIDON_VERIFIER;
+
+    /// @notice The address of the poseidon hasher computing the public input in Poseidon commitment mode.
+    address public immutable POSEIDON_HASHER;
+
+    /// @notice The address of the plonk verifier contract of the ciphertext circuit.
+    address public immutable CIPHERTEXT_VERIFIER;
+
+    /// @notice The poseidon commitment to g^a of the public parameters the ciphertexts are proved under.
+    bytes32 public immutable PARAMS_HASH;
+
+    /// @notice The address of the plonk verifier contract of the batched pairing circuit.
+    address public immutable BATCH_VERIFIER;
+
+    /// @notice The number of tasks the batched pairing circuit of BATCH_VERIFIER proves at once.
+    uint256 public immutable BATCH_SIZE;
+
     mapping(bytes32 => Task) tasks;    // 任务id => 任务详情
     mapping(address => uint) DCSdeposits;   // 外包计算员地址 => 质押金额（单位wei）
     mapping(address => ArrayUtils.RandomRemoveableArray) pendingTasks;  // 外包计算员地址 => 正在处理的外包计算任务列表
@@ -66,9 +93,19 @@ contract OABE is IOABE {
     /***************
      * Constructor *
      ***************/
-     constructor(address _verifier) {
+     constructor(address _verifier, address _poseidonVerifier, address _poseidonHasher, address _ciphertextVerifier, bytes32 _paramsHash, address _batchVerifier, uint256 _batchSize) {
         // TODO:这是morph的constructor，要做一些改动吗？
         PLONK_VERIFIER = _verifier;
+        POSEIDON_VERIFIER = _poseidonVerifier;  // 为0表示不支持Poseidon承诺模式的证明
+        POSEIDON_HASHER = _poseidonHasher;
+        require(_poseidonVerifier == address(0) || _poseidonHasher != address(0), "Poseidon verifier needs a hasher");
+        require(_batchVerifier == address(0) || _poseidonHasher != address(0), "Batch verifier needs a hasher");
+        CIPHERTEXT_VERIFIER = _ciphertextVerifier;  // 为0表示不支持密文证明，否则所有任务都要有密文证明
+        PARAMS_HASH = _paramsHash;
+        require(_ciphertextVerifier == address(0) || _poseidonHasher != address(0), "Ciphertext verifier needs a hasher");
+        BATCH_VERIFIER = _batchVerifier;    // 为0表示不支持批量证明
+        BATCH_SIZE = _batchSize;            // 电路的instance个数固定，verifier只接受这么多个任务
+        require(_batchVerifier == address(0) || _batchSize > 0, "Batch verifier needs a batch size");
     }
 
     /* constructor和其他什么？？ */
@@ -81,41 +118,57 @@ contract OABE is IOABE {
     /* 流程 */
     // 1. DataUser发布任务
     function createTask(bytes32 _dataHash) external payable returns (bytes32) {
-        require(msg.value > 0, "Reward must be greater than 0");    // DataUser把发布任务时，需要把酬金转到合约账户
-        bytes32 taskId = keccak256(abi.encodePacked(block.timestamp, msg.sender, msg.value, _dataHash));  // 随机生成任务id
+        // 支持密文证明时，没有密文证明的任务会让恶意DataUser用畸形密文罚没诚实的DCS，只能用createTaskWithCiphertext发布
+        require(CIPHERTEXT_VERIFIER == address(0), "Tasks need a ciphertext proof");
+        return _createTask(_dataHash, 0, 0);
+    }
 
-        tasks[taskId] = Task({
-            id: taskId,
-            dataHash: _dataHash,
-            reward: msg.value,
-            // publishTime: block.timestamp,
-            // acceptTime: 0,      // 任务被接受的时间
-            // submitTime: 0,      // 提交外包计算结果的时间
+    // 1'. DataUser发布需要密文证明的任务（Poseidon承诺模式）。dataHash = Poseidon(密文承诺, TK承诺)
+    function createTaskWithCiphertext(bytes32 _ciphertextHash, bytes32 _policyHash, bytes32 _keyHash) external payable returns (bytes32) {
+        require(CIPHERTEXT_VERIFIER != address(0), "Ciphertext proofs are not supported");
+        require(_ciphertextHash != 0, "Ciphertext hash must not be 0");
+        require(_policyHash != 0, "Policy hash must not be 0");    // 电路要求w_i按访问策略重构秘密，没有策略的任务无法证明
+        bytes32 _dataHash = bytes32(_poseidonPair(uint256(_ciphertextHash), uint256(_keyHash)));
+        return _createTask(_dataHash, _ciphertextHash, _policyHash);
+    }
 
-            // acceptDeadline: block.timestamp + SUBMIT_PERIOD,  // 接受任务的截止时间
-            // submitDeadline: 0,
-            challengeDeadline: 0,
+    // 1''. DataUser发布带访问策略、可以用provePoseidon证明的任务。dataHash = Poseidon(密文承诺, TK承诺)
+    function createTaskWithPolicy(bytes32 _dataHash, bytes32 _policyHash) external payable returns (bytes32) {
+        require(CIPHERTEXT_VERIFIER == address(0), "Tasks need a ciphertext proof");
+        require(_policyHash != 0, "Policy hash must not be 0");
+        return _createTask(_dataHash, 0, _policyHash);
+    }
 
-            status: Status.CREATED,  // 任务状态
+    // 加密者证明密文是良构的。公开输入为 Poseidon(Poseidon(密文承诺, 访问策略承诺), 公开参数承诺)，由合约自己计算
+    function proveCiphertext(bytes32 taskId, bytes calldata _proof) external {
+        Task storage task = tasks[taskId];
+        require(CIPHERTEXT_VERIFIER != address(0), "Ciphertext proofs are not supported");
+        require(task.status == Status.CREATED, "Task is not created");
+        require(task.ciphertextHash != 0, "Task has no ciphertext to prove");
+        require(!task.ciphertextProved, "Ciphertext is already proved");
 
-            dataUser: msg.sender,       // 任务发起人地址
-            DCS: address(0),           // 外包计算员地址
+        uint256 publicInput = _poseidonPair(_poseidonPair(uint256(task.ciphertextHash), uint256(task.policyHash)), uint256(PARAMS_HASH));
+        require(_verifyProofWithCommitment(CIPHERTEXT_VERIFIER, _proof, publicInput), "Invalid ciphertext proof");
 
-            result: 0,          // 任务结果，即解密得到的CT'（图中步骤6），由Decryption Cloud Server提交
+        task.ciphertextProved = true;
 
-            challenge: Challenge({
-                challenger: address(0),     // 质疑者地址
-                // challengeTime: 0,     // 质疑时间
-                // challengeDeadline: 0, // 质疑截止时间
-                deposit: 0,            // 质疑者支付的质押金
+        emit ProveCiphertextEvent(taskId, msg.sender);
+    }
 
-                proveDeadline: 0     // zk proof提交截止时间
-                // proveTime: 0,          // zk proof提交时间
+    function _createTask(bytes32 _dataHash, bytes32 _ciphertextHash, bytes32 _policyHash) internal returns (bytes32) {
+        require(msg.value > 0, "Reward must be greater than 0");    // DataUser把发布任务时，需要把酬金转到合约账户
+        bytes32 taskId = keccak256(abi.encodePacked(block.timestamp, msg.sender, msg.value, _dataHash));  // 随机生成任务id
 
-                // proof: new bytes(0),    // zk proof
-                // publicInputHash: ""     // zk proof的hash
-            })    // 质疑(challenge)的详情
-        });
+        // 字段较多，逐个写入storage以免stack too deep。未写入的字段保持默认值：
+        // challengeDeadline、DCS、result为0，challenge为空（质疑者地址、质押金、zk proof提交截止时间均为0）
+        Task storage task = tasks[taskId];
+        task.id = taskId;
+        task.dataHash = _dataHash;
+        task.ciphertextHash = _ciphertextHash;   // 密文承诺，为0表示不需要密文证明
+        task.policyHash = _policyHash;           // 访问策略承诺
+        task.reward = msg.value;
+        task.status = Status.CREATED;  // 任务状态
+        task.dataUser = msg.sender;    // 任务发起人地址
 
         emit CreateTaskEvent(_dataHash, taskId, msg.sender, msg.value);
         return taskId;
@@ -130,6 +183,7 @@ contract OABE is IOABE {
         Task storage task = tasks[taskId];      // 指定storage，则task是指向tasks[taskId]的引用，可以通过task修改tasks[taskId]的值
         require(task.status == Status.CREATED, "Task is not created");
         require(DCSdeposits[msg.sender] >= MINIMAL_DCS_DEPOSIT, "DCS has no enough deposit");
+        require(task.ciphertextHash == 0 || task.ciphertextProved, "Ciphertext is not proved");  // 密文未经证明的任务不能被领取，也就不会进入challenge
 
         task.DCS = msg.sender;
         task.status = Status.SUBMITTED;
@@ -217,6 +271,64 @@ contract OABE is IOABE {
         emit ProveEvent(taskId, msg.sender, _proof, _publicInputHash);
     }
 
+    // Poseidon承诺模式（MyEccCircuit<F, XI_0, PoseidonCommitment>）下的证明。
+    // createTaskWithPolicy时传入的_dataHash是对CT||wi||TK的Poseidon承诺（data_commitment），_policyHash是对访问策略的承诺，
+    // submitResult时传入的_result是对结果T的Poseidon承诺（result_commitment），二者都由链下的Rust代码计算。
+    // 电路唯一的public input是Poseidon(Poseidon(dataHash, policyHash), result)，由合约自己计算，只需要展开成一个instance。
+    // POSEIDON_VERIFIER是为该模式的电路生成的verifier，与prove所用的PLONK_VERIFIER分开部署。
+    function provePoseidon(bytes32 taskId, bytes calldata _proof) external {
+        require(POSEIDON_VERIFIER != address(0), "Poseidon proofs are not supported");
+        Task storage task = tasks[taskId];
+        require(task.status == Status.CHALLENGED, "Task is not challenged");
+        require(task.DCS == msg.sender, "Only DCS can prove");
+        require(block.timestamp <= task.challenge.proveDeadline, "Prove deadline has passed");
+        require(task.policyHash != 0, "Task has no policy");   // 没有访问策略的普通任务只能用prove/newProve证明
+
+        uint256 publicInput = _poseidonPublicInput(task);
+
+        bool success = _verifyProofWithCommitment(POSEIDON_VERIFIER, _proof, publicInput);
+        if (success) {
+            _challengeFail(taskId);
+        } else {
+            _challengeSuccess(taskId);
+        }
+
+        task.status = Status.FINISHED;
+        pendingTasks[msg.sender].remove(taskId);
+
+        emit ProveEvent(taskId, msg.sender, _proof, bytes32(publicInput));
+    }
+
+    // 批量证明：同一个DCS用同一个转换密钥(L, R_i, R')解密的多个被challenge的任务，可以用一个批量电路的proof一起证明。
+    // 每个任务的公开输入与provePoseidon相同，按taskIds的顺序依次作为proof的instance。
+    // taskIds必须严格递增，保证每个任务只被结算一次。BATCH_VERIFIER对应的电路决定了一次批量证明的任务个数BATCH_SIZE。
+    // 验证失败时整笔交易回滚，不罚没任何任务：一个错误的任务不能连累同一批的其他任务，
+    // DCS仍可以在截止时间前单独或重新分批证明，过期未证明的任务由claimChallengeReward逐个结算
+    function provePoseidonBatch(bytes32[] calldata taskIds, bytes calldata _proof) external {
+        require(BATCH_VERIFIER != address(0), "Batch proofs are not supported");
+        require(taskIds.length == BATCH_SIZE, "Wrong number of tasks");
+
+        uint256[] memory publicInputs = new uint256[](taskIds.length);
+        for (uint i = 0; i < taskIds.length; i++) {
+            require(i == 0 || uint256(taskIds[i]) > uint256(taskIds[i - 1]), "Task ids must be sorted and distinct");
+            Task storage task = tasks[taskIds[i]];
+            require(task.status == Status.CHALLENGED, "Task is not challenged");
+            require(task.DCS == msg.sender, "Only DCS can prove");
+            require(block.timestamp <= task.challenge.proveDeadline, "Prove deadline has passed");
+            require(task.policyHash != 0, "Task has no policy");
+
+            publicInputs[i] = _poseidonPublicInput(task);
+        }
+
+        require(_verifyProofWithCommitments(BATCH_VERIFIER, _proof, publicInputs), "Invalid batch proof");
+        for (uint i = 0; i < taskIds.length; i++) {
+            // 结束任务并将其移出DCS的pendingTasks
+            _challengeFail(taskIds[i]);
+
+            emit ProveEvent(taskIds[i], msg.sender, _proof, bytes32(publicInputs[i]));
+        }
+    }
+
     /* DCS管理 */
     // 1. DCS注册
     function registerDCS() external payable {
@@ -324,4 +436,54 @@ contract OABE is IOABE {
 
         return success;
     }
+
+    // Poseidon承诺模式的公开输入 = Poseidon(Poseidon(dataHash, policyHash), result)，与电路中的poseidon_pi_commitment相同。
+    // 嵌套哈希使三个承诺之间不能互相挪用一部分。电路总是要求访问策略，policyHash为0的普通任务不能用这个模式证明
+    function _poseidonPublicInput(Task storage task) internal view returns (uint256) {
+        return _poseidonPair(_poseidonPair(uint256(task.dataHash), uint256(task.policyHash)), uint256(task.result));
+    }
+
+    // 两个标量域元素的Poseidon哈希，与电路中的poseidon_commitment(&[a, b])相同
+    function _poseidonPair(uint256 a, uint256 b) internal view returns (uint256) {
+        return IPoseidon(POSEIDON_HASHER).poseidon([a % SCALAR_FIELD_MODULUS, b % SCALAR_FIELD_MODULUS], POSEIDON_PAIR_DOMAIN);
+    }
+
+    // 验证Poseidon承诺模式的zk proof。与_verifyProof相同，只是accumulator之后只插入一个32字节的instance
+    function _verifyProofWithCommitment(address _verifier, bytes calldata proof, uint256 publicInput) internal view returns (bool) {
+        bool success;
+
+        assembly {
+            let p := mload(0x40)
+            calldatacopy(p, proof.offset, 0x180)
+            mstore(add(p, 0x180), publicInput)
+            calldatacopy(add(p, 0x1a0), add(proof.offset, 0x180), sub(proof.length, 0x180))
+
+            success := staticcall(gas(), _verifier, p, add(proof.length, 0x20), 0x00, 0x00)
+        }
+
+        return success;
+    }
+
+    // 验证批量电路的zk proof。accumulator之后依次插入每个任务的32字节instance
+    function _verifyProofWithCommitments(address _verifier, bytes calldata proof, uint256[] memory publicInputs) internal view returns (bool) {
+        bool success;
+
+        assembly {
+            let p := mload(0x40)
+            let n := mul(mload(publicInputs), 0x20)
+            calldatacopy(p, proof.offset, 0x180)
+            for {
+                let i := 0
+            } lt(i, n) {
+                i := add(i, 0x20)
+            } {
+                mstore(add(p, add(0x180, i)), mload(add(publicInputs, add(0x20, i))))
+            }
+            calldatacopy(add(p, add(0x180, n)), add(proof.offset, 0x180), sub(proof.length, 0x180))
+
+            success := staticcall(gas(), _verifier, p, add(proof.length, n), 0x00, 0x00)
+        }
+
+        return success;
+    }
 }
\ No newline at end of file
@@ -12,6 +12,9 @@ interface IOABE {
         bytes32 id;
 
         bytes32 dataHash;   // 任务的数据哈希
+        bytes32 ciphertextHash; // 密文承诺，为0表示任务不需要密文证明
+        bytes32 policyHash;     // 访问策略承诺
+        bool ciphertextProved;  // 密文证明是否已通过验证
         uint reward;
 
         uint challengeDeadline;
@@ -53,6 +56,7 @@ interface IOABE {
     event CreateTaskEvent(bytes32 indexed dataHash, bytes32 indexed taskId, address indexed dataUser, uint reward);
     event SubmitResultEvent(bytes32 indexed taskId, address indexed DCS, bytes32 result);
     event ChallengeEvent(bytes32 indexed taskId, address indexed challenger, uint deposit);
+    event ProveCiphertextEvent(bytes32 indexed taskId, address indexed prover);
     event ProveEvent(bytes32 indexed taskId, address indexed DCS, bytes proof, bytes32 publicInputHash);
 
     event RegisterDCSEvent(address indexed DCS);
@@ -73,12 +77,19 @@ interface IOABE {
     /* 流程 */
     // 1. DataUser发布任务
     function createTask(bytes32 _dataHash) external payable returns (bytes32) ;
+    // 1''. DataUser发布带访问策略的任务（Poseidon承诺模式）
+    function createTaskWithPolicy(bytes32 _dataHash, bytes32 _policyHash) external payable returns (bytes32) ;
+    // 1'. DataUser发布需要密文证明的任务，以及加密者提交密文证明
+    function createTaskWithCiphertext(bytes32 _ciphertextHash, bytes32 _policyHash, bytes32 _keyHash) external payable returns (bytes32) ;
+    function proveCiphertext(bytes32 taskId, bytes calldata _proof) external ;
     // 2. DecryptionCloudServer(DCS)提交结果
     function submitResult(bytes32 taskId, bytes32 _result) external ;
     // 3. Challenger质疑
     function challenge(bytes32 taskId) external payable ;
     // 4. DCS提交证明
     function prove(bytes32 taskId, bytes memory proof, bytes32 _publicInputHash) external ;
+    // 同一个转换密钥下的多个任务一起证明
+    function provePoseidonBatch(bytes32[] calldata taskIds, bytes calldata _proof) external ;
 
     /* DCS管理 */
     // 1. DCS注册
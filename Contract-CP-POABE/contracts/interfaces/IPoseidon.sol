@@ -0,0 +1,11 @@
+// SPDX-License-Identifier: MIT
+
+pragma solidity ^0.8.16;
+
+interface IPoseidon {
+    /// @notice Poseidon hash of two field elements, with `domain` as the capacity element, as
+    /// the `poseidon_commitment` of a two-element message in the circuits.
+    /// @param inputs The field elements to hash.
+    /// @param domain The domain of the hash.
+    function poseidon(uint256[2] memory inputs, uint256 domain) external view returns (uint256);
+}
@@ -0,0 +1,22 @@
+// SPDX-License-Identifier: MIT
+
+pragma solidity ^0.8.24;
+
+// 测试用的plonk verifier：不验证proof本身，只检查accumulator（0x180字节）之后的instance是否等于预设的值，
+// 用来测试OABE合约计算的公开输入和对验证结果的处理
+contract MockPlonkVerifier {
+    uint256[] public expectedInstances;
+
+    function setExpectedInstances(uint256[] calldata _instances) external {
+        expectedInstances = _instances;
+    }
+
+    fallback(bytes calldata input) external returns (bytes memory) {
+        require(input.length >= 0x180 + expectedInstances.length * 0x20, "Proof too short");
+        for (uint i = 0; i < expectedInstances.length; i++) {
+            uint256 instance = uint256(bytes32(input[0x180 + i * 0x20:0x1a0 + i * 0x20]));
+            require(instance == expectedInstances[i], "Wrong instance");
+        }
+        return "";
+    }
+}
@@ -0,0 +1,14 @@
+// SPDX-License-Identifier: MIT
+
+pragma solidity ^0.8.24;
+
+import {IPoseidon} from "../interfaces/IPoseidon.sol";
+
+// 测试用的poseidon hasher：用keccak代替poseidon，只用于测试OABE合约的流程，与电路的承诺不一致
+contract MockPoseidon is IPoseidon {
+    uint constant SCALAR_FIELD_MODULUS = 21888242871839275222246405745257275088548364400416034343698204186575808495617;
+
+    function poseidon(uint256[2] memory inputs, uint256 domain) external pure returns (uint256) {
+        return uint256(keccak256(abi.encodePacked(inputs[0], inputs[1], domain))) % SCALAR_FIELD_MODULUS;
+    }
+}
@@ -0,0 +1,131 @@
+import { expect } from "chai";
+import { BigNumber, Contract } from "ethers";
+import { hexlify } from "ethers/lib/utils";
+import { ethers } from "hardhat";
+import { SignerWithAddress } from "@nomiclabs/hardhat-ethers/signers";
+
+// 用provePoseidonBatch一起证明多个被challenge的任务。batch verifier和poseidon hasher都用测试合约代替
+describe("batch proof", async () => {
+    const POSEIDON_PAIR_DOMAIN = BigNumber.from(2 * 31).shl(64);
+    const BATCH_SIZE = 2;
+    const CHALLENGED = "2";
+    const FINISHED = "3";
+
+    const policyHash = ethers.utils.hexZeroPad("0xb1", 32);
+
+    let deployer: SignerWithAddress;
+    let DU: SignerWithAddress;
+    let DCS: SignerWithAddress;
+    let challenger: SignerWithAddress;
+    let OABEContract: Contract;
+    let batchVerifier: Contract;
+    let poseidon: Contract;
+
+    // accumulator之后是BATCH_SIZE个instance，这里的proof只需要足够长
+    const proof = hexlify(new Uint8Array(0x180 + 0x100));
+
+    beforeEach(async () => {
+        [deployer, DU, DCS, challenger] = await ethers.getSigners();
+
+        const ArrayUtils = await ethers.getContractFactory("ArrayUtils");
+        const arrayUtils = await ArrayUtils.deploy();
+        await arrayUtils.deployed();
+
+        batchVerifier = await (await ethers.getContractFactory("MockPlonkVerifier", deployer)).deploy();
+        await batchVerifier.deployed();
+        poseidon = await (await ethers.getContractFactory("MockPoseidon", deployer)).deploy();
+        await poseidon.deployed();
+
+        const OABEFactory = await ethers.getContractFactory("OABE", {
+            libraries: {
+                ArrayUtils: arrayUtils.address
+            },
+            signer: deployer});
+        OABEContract = await OABEFactory.deploy(
+            ethers.constants.AddressZero,
+            ethers.constants.AddressZero,
+            poseidon.address,
+            ethers.constants.AddressZero,
+            ethers.constants.HashZero,
+            batchVerifier.address,
+            BATCH_SIZE
+        );
+        await OABEContract.deployed();
+
+        await OABEContract.connect(DCS).registerDCS({value: ethers.utils.parseEther("5.0")});
+    });
+
+    // 发布、提交结果并challenge一个任务，返回taskId
+    async function challengedTask(i: number, _policyHash: string = policyHash) {
+        const dataHash = ethers.utils.hexZeroPad(hexlify(0xd0 + i), 32);
+        const tx = _policyHash == ethers.constants.HashZero
+            ? await OABEContract.connect(DU).createTask(dataHash, {value: ethers.utils.parseEther("1.0")})
+            : await OABEContract.connect(DU).createTaskWithPolicy(dataHash, _policyHash, {value: ethers.utils.parseEther("1.0")});
+        const receipt = await tx.wait();
+        const event = receipt.events?.find((e: any) => e.event === "CreateTaskEvent");
+        const taskId = event.args.taskId;
+
+        await OABEContract.connect(DCS).submitResult(taskId, ethers.utils.hexZeroPad(hexlify(0xe0 + i), 32));
+        await OABEContract.connect(challenger).challenge(taskId, {value: 100000});
+        return taskId;
+    }
+
+    // taskIds必须严格递增
+    async function challengedTasks(n: number) {
+        const taskIds = [];
+        for (let i = 0; i < n; i++) {
+            taskIds.push(await challengedTask(i));
+        }
+        return taskIds.sort((a, b) => BigNumber.from(a).lt(b) ? -1 : 1);
+    }
+
+    // Poseidon(Poseidon(dataHash, policyHash), result)
+    async function publicInput(taskId: string) {
+        const task = await OABEContract.getTask(taskId);
+        const inner = await poseidon.poseidon([task.dataHash, task.policyHash], POSEIDON_PAIR_DOMAIN);
+        return await poseidon.poseidon([inner, task.result], POSEIDON_PAIR_DOMAIN);
+    }
+
+    it ("batches of another size are rejected", async () => {
+        const taskIds = await challengedTasks(BATCH_SIZE + 1);
+        await expect(OABEContract.connect(DCS).provePoseidonBatch(taskIds.slice(0, 1), proof)).to.be.revertedWith("Wrong number of tasks");
+        await expect(OABEContract.connect(DCS).provePoseidonBatch(taskIds, proof)).to.be.revertedWith("Wrong number of tasks");
+        await expect(OABEContract.connect(DCS).provePoseidonBatch([taskIds[1], taskIds[0]], proof)).to.be.revertedWith("Task ids must be sorted and distinct");
+    });
+
+    it ("tasks without a policy are rejected", async () => {
+        const taskIds = [await challengedTask(0), await challengedTask(1, ethers.constants.HashZero)]
+            .sort((a, b) => BigNumber.from(a).lt(b) ? -1 : 1);
+        await expect(OABEContract.connect(DCS).provePoseidonBatch(taskIds, proof)).to.be.revertedWith("Task has no policy");
+    });
+
+    it ("a failed batch proof slashes no task", async () => {
+        const taskIds = await challengedTasks(BATCH_SIZE);
+        const deposit = await OABEContract.queryDCSdeposit(DCS.address);
+
+        // 第二个任务的instance不对，整个批量证明不能通过验证
+        await batchVerifier.setExpectedInstances([await publicInput(taskIds[0]), await publicInput(taskIds[0])]);
+        await expect(OABEContract.connect(DCS).provePoseidonBatch(taskIds, proof)).to.be.revertedWith("Invalid batch proof");
+
+        // 任务仍处于challenge状态，DCS的质押没有被罚没，可以在截止时间前重新证明
+        for (const taskId of taskIds) {
+            expect((await OABEContract.getTask(taskId)).status.toString()).to.equal(CHALLENGED);
+        }
+        expect(await OABEContract.queryDCSdeposit(DCS.address)).to.equal(deposit);
+    });
+
+    it ("provePoseidonBatch", async () => {
+        const taskIds = await challengedTasks(BATCH_SIZE);
+
+        await batchVerifier.setExpectedInstances([await publicInput(taskIds[0]), await publicInput(taskIds[1])]);
+        const tx = await OABEContract.connect(DCS).provePoseidonBatch(taskIds, proof);
+        const receipt = await tx.wait();
+        const events = receipt.events?.filter((e: any) => e.event === "ProveEvent");
+        expect(events.map((e: any) => e.args.taskId)).to.deep.equal(taskIds);
+
+        for (const taskId of taskIds) {
+            expect((await OABEContract.getTask(taskId)).status.toString()).to.equal(FINISHED);
+        }
+        await expect(OABEContract.connect(DCS).provePoseidonBatch(taskIds, proof)).to.be.revertedWith("Task is not challenged");
+    });
+});
@@ -0,0 +1,117 @@
+import { expect } from "chai";
+import { BigNumber, Contract } from "ethers";
+import { hexlify } from "ethers/lib/utils";
+import { ethers } from "hardhat";
+import { SignerWithAddress } from "@nomiclabs/hardhat-ethers/signers";
+
+// 用createTaskWithCiphertext发布、需要先证明密文良构的任务。verifier和poseidon hasher都用测试合约代替
+describe("ciphertext proof", async () => {
+    const POSEIDON_PAIR_DOMAIN = BigNumber.from(2 * 31).shl(64);
+
+    const ciphertextHash = ethers.utils.hexZeroPad("0xc1", 32);
+    const policyHash = ethers.utils.hexZeroPad("0xb1", 32);
+    const keyHash = ethers.utils.hexZeroPad("0xa1", 32);
+    const paramsHash = ethers.utils.hexZeroPad("0x9a", 32);
+
+    let deployer: SignerWithAddress;
+    let DU: SignerWithAddress;
+    let DCS: SignerWithAddress;
+    let OABEContract: Contract;
+    let ciphertextVerifier: Contract;
+    let poseidon: Contract;
+
+    // accumulator之后是instance，这里的proof只需要足够长
+    const proof = hexlify(new Uint8Array(0x180 + 0x100));
+
+    beforeEach(async () => {
+        [deployer, DU, DCS] = await ethers.getSigners();
+
+        const ArrayUtils = await ethers.getContractFactory("ArrayUtils");
+        const arrayUtils = await ArrayUtils.deploy();
+        await arrayUtils.deployed();
+
+        ciphertextVerifier = await (await ethers.getContractFactory("MockPlonkVerifier", deployer)).deploy();
+        await ciphertextVerifier.deployed();
+        poseidon = await (await ethers.getContractFactory("MockPoseidon", deployer)).deploy();
+        await poseidon.deployed();
+
+        const OABEFactory = await ethers.getContractFactory("OABE", {
+            libraries: {
+                ArrayUtils: arrayUtils.address
+            },
+            signer: deployer});
+        OABEContract = await OABEFactory.deploy(
+            ethers.constants.AddressZero,
+            ethers.constants.AddressZero,
+            poseidon.address,
+            ciphertextVerifier.address,
+            paramsHash,
+            ethers.constants.AddressZero,
+            0
+        );
+        await OABEContract.deployed();
+
+        await OABEContract.connect(DCS).registerDCS({value: ethers.utils.parseEther("5.0")});
+    });
+
+    async function createTask() {
+        const tx = await OABEContract.connect(DU).createTaskWithCiphertext(ciphertextHash, policyHash, keyHash, {value: ethers.utils.parseEther("1.0")});
+        const receipt = await tx.wait();
+        const event = receipt.events?.find((e: any) => e.event === "CreateTaskEvent");
+        return event.args.taskId;
+    }
+
+    // Poseidon(Poseidon(密文承诺, 访问策略承诺), 公开参数承诺)
+    async function ciphertextPublicInput() {
+        const inner = await poseidon.poseidon([ciphertextHash, policyHash], POSEIDON_PAIR_DOMAIN);
+        return await poseidon.poseidon([inner, paramsHash], POSEIDON_PAIR_DOMAIN);
+    }
+
+    it ("tasks without a ciphertext proof are rejected", async () => {
+        const _dataHash = ethers.utils.hexZeroPad("0x01", 32);
+        await expect(OABEContract.connect(DU).createTask(_dataHash, {value: ethers.utils.parseEther("1.0")})).to.be.revertedWith("Tasks need a ciphertext proof");
+        await expect(OABEContract.connect(DU).createTaskWithPolicy(_dataHash, policyHash, {value: ethers.utils.parseEther("1.0")})).to.be.revertedWith("Tasks need a ciphertext proof");
+    });
+
+    it ("createTaskWithCiphertext", async () => {
+        const taskId = await createTask();
+        const task = await OABEContract.getTask(taskId);
+        const dataHash = await poseidon.poseidon([ciphertextHash, keyHash], POSEIDON_PAIR_DOMAIN);
+        expect(BigNumber.from(task.dataHash)).to.equal(dataHash);
+        expect(task.ciphertextHash).to.equal(ciphertextHash);
+        expect(task.policyHash).to.equal(policyHash);
+        expect(task.ciphertextProved).to.equal(false);
+
+        // 密文未经证明，DCS不能提交结果
+        const result = ethers.utils.hexZeroPad("0xdd01", 32);
+        await expect(OABEContract.connect(DCS).submitResult(taskId, result)).to.be.revertedWith("Ciphertext is not proved");
+
+        await expect(OABEContract.connect(DU).createTaskWithCiphertext(ethers.constants.HashZero, policyHash, keyHash, {value: ethers.utils.parseEther("1.0")})).to.be.revertedWith("Ciphertext hash must not be 0");
+        // 电路总是要求访问策略
+        await expect(OABEContract.connect(DU).createTaskWithCiphertext(ciphertextHash, ethers.constants.HashZero, keyHash, {value: ethers.utils.parseEther("1.0")})).to.be.revertedWith("Policy hash must not be 0");
+    });
+
+    it ("proveCiphertext", async () => {
+        const taskId = await createTask();
+
+        // instance不是合约计算的公开输入，不能通过验证
+        await ciphertextVerifier.setExpectedInstances([BigNumber.from(ciphertextHash).add(policyHash)]);
+        await expect(OABEContract.connect(DU).proveCiphertext(taskId, proof)).to.be.revertedWith("Invalid ciphertext proof");
+
+        await ciphertextVerifier.setExpectedInstances([await ciphertextPublicInput()]);
+        const tx = await OABEContract.connect(DU).proveCiphertext(taskId, proof);
+        const receipt = await tx.wait();
+        const event = receipt.events?.find((e: any) => e.event === "ProveCiphertextEvent");
+        expect(event.args.taskId).to.equal(taskId);
+        expect(event.args.prover).to.equal(DU.address);
+        expect((await OABEContract.getTask(taskId)).ciphertextProved).to.equal(true);
+
+        await expect(OABEContract.connect(DU).proveCiphertext(taskId, proof)).to.be.revertedWith("Ciphertext is already proved");
+
+        // 密文证明之后，DCS可以提交结果
+        const result = ethers.utils.hexZeroPad("0xdd01", 32);
+        await OABEContract.connect(DCS).submitResult(taskId, result);
+        expect((await OABEContract.getTask(taskId)).status.toString()).to.equal("1");
+        await expect(OABEContract.connect(DU).proveCiphertext(taskId, proof)).to.be.revertedWith("Task is not created");
+    });
+});
@@ -46,7 +46,7 @@ describe("Gas Test. For each function.", async () => {
                 ArrayUtils: arrayUtils.address
             },
             signer: deployer});
-        OABEContract = await OABEFactory.deploy(receipt.contractAddress);
+        OABEContract = await OABEFactory.deploy(receipt.contractAddress, ethers.constants.AddressZero, ethers.constants.AddressZero, ethers.constants.AddressZero, ethers.constants.HashZero, ethers.constants.AddressZero, 0);
         await OABEContract.deployed();
     }
 
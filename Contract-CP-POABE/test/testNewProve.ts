@@ -47,7 +47,7 @@ describe("task and reward management", async () => {
                 ArrayUtils: arrayUtils.address
             },
             signer: deployer});
-        OABEContract = await OABEFactory.deploy(receipt.contractAddress);
+        OABEContract = await OABEFactory.deploy(receipt.contractAddress, ethers.constants.AddressZero, ethers.constants.AddressZero, ethers.constants.AddressZero, ethers.constants.HashZero, ethers.constants.AddressZero, 0);
         await OABEContract.deployed();
     });
 
@@ -129,6 +129,7 @@ impl ChunkInfo {
                     .chain(b_ctx.base_fee.to_be_bytes())
                     .chain(b_ctx.gas_limit.to_be_bytes())
                     .chain(num_txs.to_be_bytes())
+                    .chain(b_ctx.blob_base_fee.to_be_bytes())
             }))
             // Tx Hashes (excluding L2 txs)
             .chain(block.txs
@@ -1,6 +1,6 @@
 //! Circuit implementation for compression circuit.
 
-use std::fs::File;
+use std::marker::PhantomData;
 
 use ark_std::{end_timer, start_timer};
 use halo2_proofs::{
@@ -27,7 +27,11 @@ use snark_verifier::{
 };
 use snark_verifier_sdk::{aggregate, flatten_accumulator, types::Svk, Snark, SnarkWitness};
 
-use crate::{core::extract_proof_and_instances_with_pairing_check, param::ConfigParams, ACC_LEN};
+use crate::{
+    core::extract_proof_and_instances_with_pairing_check,
+    param::{CompressionEnvParams, CompressionParams},
+    ACC_LEN,
+};
 
 use super::config::CompressionConfig;
 
@@ -35,8 +39,10 @@ use super::config::CompressionConfig;
 ///
 /// It re-exposes same public inputs from the input snark.
 /// All this circuit does is to reduce the proof size.
+///
+/// `P` selects the [`ConfigParams`](crate::ConfigParams) the circuit is configured with.
 #[derive(Clone, Debug)]
-pub struct CompressionCircuit {
+pub struct CompressionCircuit<P: CompressionParams = CompressionEnvParams> {
     pub(crate) svk: KzgSuccinctVerifyingKey<G1Affine>,
     pub(crate) snark: SnarkWitness,
     /// whether this circuit compresses a fresh snark
@@ -48,9 +54,10 @@ pub struct CompressionCircuit {
     pub(crate) flattened_instances: Vec<Fr>,
     // accumulation scheme proof, private input
     pub(crate) as_proof: Value<Vec<u8>>,
+    _params: PhantomData<P>,
 }
 
-impl Circuit<Fr> for CompressionCircuit {
+impl<P: CompressionParams> Circuit<Fr> for CompressionCircuit<P> {
     type Config = CompressionConfig;
     type FloorPlanner = SimpleFloorPlanner;
 
@@ -68,18 +75,13 @@ impl Circuit<Fr> for CompressionCircuit {
             has_accumulator: false,
             flattened_instances,
             as_proof: Value::unknown(),
+            _params: PhantomData,
         }
     }
 
     fn configure(meta: &mut ConstraintSystem<Fr>) -> Self::Config {
-        // Too bad that configure function doesn't take additional input
-        // it would be nicer to load parameters from API rather than ENV
-        let path = std::env::var("COMPRESSION_CONFIG")
-            .unwrap_or_else(|_| "configs/compression_wide.config".to_owned());
-        let params: ConfigParams = serde_json::from_reader(
-            File::open(path.as_str()).unwrap_or_else(|_| panic!("{path:?} does not exist")),
-        )
-        .unwrap_or_else(|_| ConfigParams::default_compress_wide_param());
+        // configure function doesn't take additional input, so the params come from the type
+        let params = P::config_params();
 
         log::info!(
             "compression circuit configured with k = {} and {:?} advice columns",
@@ -163,7 +165,7 @@ impl Circuit<Fr> for CompressionCircuit {
     }
 }
 
-impl CompressionCircuit {
+impl<P: CompressionParams> CompressionCircuit<P> {
     /// Build a new circuit from a snark, with a flag whether this snark has been compressed before
     pub fn new(
         params: &ParamsKZG<Bn256>,
@@ -206,6 +208,7 @@ impl CompressionCircuit {
             has_accumulator,
             flattened_instances,
             as_proof: Value::known(as_proof),
+            _params: PhantomData,
         })
     }
 
@@ -3,11 +3,11 @@
 use halo2_proofs::{halo2curves::bn256::Fr, plonk::Selector};
 use snark_verifier_sdk::CircuitExt;
 
-use crate::ACC_LEN;
+use crate::{param::CompressionParams, ACC_LEN};
 
 use super::circuit::CompressionCircuit;
 
-impl CircuitExt<Fr> for CompressionCircuit {
+impl<P: CompressionParams> CircuitExt<Fr> for CompressionCircuit<P> {
     fn num_instance(&self) -> Vec<usize> {
         // re-expose inner public input
         let snark_pi_len: usize = self.snark.instances.iter().map(|x| x.len()).sum();
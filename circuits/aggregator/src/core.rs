@@ -3,6 +3,7 @@ use std::iter::repeat;
 use ark_std::{end_timer, start_timer};
 use ethers_core::utils::keccak256;
 use halo2_proofs::{
+    arithmetic::CurveAffine,
     circuit::{AssignedCell, Layouter, Region, Value},
     halo2curves::{
         bn256::{Bn256, Fq, Fr, G1Affine, G2Affine},
@@ -18,7 +19,7 @@ use snark_verifier::{
         kzg::{Bdfg21, Kzg, KzgAccumulator, KzgAs},
         AccumulationSchemeProver,
     },
-    util::arithmetic::fe_to_limbs,
+    util::arithmetic::{fe_from_limbs, fe_to_limbs},
     verifier::PlonkVerifier,
     Error,
 };
@@ -144,6 +145,29 @@ pub fn extract_proof_and_instances_with_pairing_check(
     Ok((as_proof, acc_instances))
 }
 
+/// Native decider of an accumulator exposed in limbs by the outer circuit, i.e. the first 12
+/// instances described above: checks e(lhs, g2) == e(rhs, s_g2).
+pub fn check_accumulator_limbs(params: &ParamsKZG<Bn256>, acc_instances: &[Fr]) -> bool {
+    if acc_instances.len() != 4 * LIMBS {
+        return false;
+    }
+
+    let [lhs_x, lhs_y, rhs_x, rhs_y]: [Fq; 4] = acc_instances
+        .chunks(LIMBS)
+        .map(|limbs| fe_from_limbs::<Fr, Fq, { LIMBS }, { BITS }>(limbs.try_into().unwrap()))
+        .collect_vec()
+        .try_into()
+        .unwrap();
+    let (Some(lhs), Some(rhs)) = (
+        Option::<G1Affine>::from(G1Affine::from_xy(lhs_x, lhs_y)),
+        Option::<G1Affine>::from(G1Affine::from_xy(rhs_x, rhs_y)),
+    ) else {
+        return false;
+    };
+
+    Bn256::pairing(&lhs, &params.g2()) == Bn256::pairing(&rhs, &params.s_g2())
+}
+
 /// Extracted hash cells. Including the padded ones so that the circuit is static.
 pub(crate) struct ExtractedHashCells<const N_SNARKS: usize> {
     inputs: Vec<Vec<AssignedCell<Fr, Fr>>>,
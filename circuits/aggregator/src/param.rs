@@ -1,3 +1,5 @@
+use std::{fmt::Debug, fs::File};
+
 use snark_verifier::loader::halo2::halo2_ecc::fields::fp::FpStrategy;
 
 use crate::{BITS, LIMBS};
@@ -56,3 +58,53 @@ impl ConfigParams {
         }
     }
 }
+
+/// Compile-time choice of the [`ConfigParams`] a compression circuit is configured with.
+///
+/// `Circuit::configure` takes no input besides the constraint system, so the params are carried by
+/// the circuit type instead. Circuits with different params can then be keyed and proven
+/// concurrently in one process.
+pub trait CompressionParams: Clone + Debug + Send + Sync + 'static {
+    fn config_params() -> ConfigParams;
+}
+
+/// Params read from the file at `COMPRESSION_CONFIG`, falling back to
+/// `configs/compression_wide.config`.
+///
+/// Kept for callers that still select the config through the environment; prefer a dedicated
+/// [`CompressionParams`] type since the environment is shared by all threads.
+#[derive(Clone, Debug)]
+pub struct CompressionEnvParams;
+
+impl CompressionParams for CompressionEnvParams {
+    fn config_params() -> ConfigParams {
+        let path = std::env::var("COMPRESSION_CONFIG")
+            .unwrap_or_else(|_| "configs/compression_wide.config".to_owned());
+        serde_json::from_reader(
+            File::open(path.as_str()).unwrap_or_else(|_| panic!("{path:?} does not exist")),
+        )
+        .unwrap_or_else(|_| ConfigParams::default_compress_wide_param())
+    }
+}
+
+/// Params of `configs/compression_wide.config`.
+#[derive(Clone, Debug)]
+pub struct CompressionWideParams;
+
+impl CompressionParams for CompressionWideParams {
+    fn config_params() -> ConfigParams {
+        serde_json::from_str(include_str!("../configs/compression_wide.config"))
+            .expect("valid compression_wide.config")
+    }
+}
+
+/// Params of `configs/compression_thin.config`.
+#[derive(Clone, Debug)]
+pub struct CompressionThinParams;
+
+impl CompressionParams for CompressionThinParams {
+    fn config_params() -> ConfigParams {
+        serde_json::from_str(include_str!("../configs/compression_thin.config"))
+            .expect("valid compression_thin.config")
+    }
+}
@@ -52,7 +52,7 @@ macro_rules! layer_0 {
 #[macro_export]
 macro_rules! compression_layer_snark {
     // generate a snark for compression layer
-    ($previous_snark: ident, $param: ident, $degree: ident, $path: ident, $layer_index: expr) => {{
+    ($previous_snark: ident, $param: ident, $degree: ident, $path: ident, $layer_index: expr, $compression_params: ty) => {{
         let timer = start_timer!(|| format!("gen layer {} snark", $layer_index));
 
         let param = {
@@ -64,8 +64,13 @@ macro_rules! compression_layer_snark {
         let mut rng = test_rng();
 
         let is_fresh = if $layer_index == 1 { true } else { false };
-        let compression_circuit =
-            CompressionCircuit::new(&$param, $previous_snark.clone(), is_fresh, &mut rng).unwrap();
+        let compression_circuit = CompressionCircuit::<$compression_params>::new(
+            &$param,
+            $previous_snark.clone(),
+            is_fresh,
+            &mut rng,
+        )
+        .unwrap();
 
         let pk = gen_pk(&$param, &compression_circuit, None);
         // build the snark for next layer
@@ -82,11 +87,9 @@ macro_rules! compression_layer_snark {
             $layer_index
         );
 
-        assert!(verify_snark_shplonk::<CompressionCircuit>(
-            &param,
-            snark.clone(),
-            pk.get_vk()
-        ));
+        assert!(verify_snark_shplonk::<
+            CompressionCircuit<$compression_params>,
+        >(&param, snark.clone(), pk.get_vk()));
 
         end_timer!(timer);
         snark
@@ -96,7 +99,7 @@ macro_rules! compression_layer_snark {
 #[macro_export]
 macro_rules! compression_layer_evm {
     // generate a evm proof and verify it for compression layer
-    ($previous_snark: ident, $param: ident, $degree: ident, $path: ident,$layer_index: expr) => {{
+    ($previous_snark: ident, $param: ident, $degree: ident, $path: ident,$layer_index: expr, $compression_params: ty) => {{
         let timer = start_timer!(|| format!("gen layer {} snark", $layer_index));
 
         let param = {
@@ -107,8 +110,13 @@ macro_rules! compression_layer_evm {
 
         let mut rng = test_rng();
 
-        let compression_circuit =
-            CompressionCircuit::new(&$param, $previous_snark, false, &mut rng).unwrap();
+        let compression_circuit = CompressionCircuit::<$compression_params>::new(
+            &$param,
+            $previous_snark,
+            false,
+            &mut rng,
+        )
+        .unwrap();
 
         let instances = compression_circuit.instances();
 
@@ -126,12 +134,13 @@ macro_rules! compression_layer_evm {
         log::trace!("proof size: {}", proof.len());
 
         // verify proof via EVM
-        let deployment_code = gen_evm_verifier::<CompressionCircuit, Kzg<Bn256, Bdfg21>>(
-            &param,
-            pk.get_vk(),
-            compression_circuit.num_instance(),
-            Some(&$path.join(Path::new("contract.sol"))),
-        );
+        let deployment_code =
+            gen_evm_verifier::<CompressionCircuit<$compression_params>, Kzg<Bn256, Bdfg21>>(
+                &param,
+                pk.get_vk(),
+                compression_circuit.num_instance(),
+                Some(&$path.join(Path::new("contract.sol"))),
+            );
         log::trace!("finished layer 4 bytecode generation");
 
         evm_verify(
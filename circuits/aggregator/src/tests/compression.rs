@@ -17,7 +17,7 @@ use snark_verifier_sdk::{
 
 use crate::{
     compression_layer_evm, compression_layer_snark, layer_0, tests::mock_chunk::MockChunkCircuit,
-    CompressionCircuit,
+    CompressionCircuit, CompressionThinParams, CompressionWideParams,
 };
 
 #[ignore = "it takes too much time"]
@@ -46,7 +46,6 @@ fn test_mock_compression() {
     let circuit = MockChunkCircuit::random(&mut rng, false, false);
     let layer_0_snark = layer_0!(circuit, MockChunkCircuit, params, k0, path);
 
-    std::env::set_var("COMPRESSION_CONFIG", "./configs/compression_wide.config");
     // layer 1 proof compression
     {
         let param = {
@@ -55,7 +54,8 @@ fn test_mock_compression() {
             param
         };
         let compression_circuit =
-            CompressionCircuit::new(&param, layer_0_snark, true, &mut rng).unwrap();
+            CompressionCircuit::<CompressionWideParams>::new(&param, layer_0_snark, true, &mut rng)
+                .unwrap();
         let instance = compression_circuit.instances();
         println!("instance length {:?}", instance.len());
 
@@ -92,9 +92,21 @@ fn test_two_layer_proof_compression() {
     let circuit = MockChunkCircuit::random(&mut rng, false, false);
     let layer_0_snark = layer_0!(circuit, MockChunkCircuit, layer_2_params, k0, path);
 
-    std::env::set_var("COMPRESSION_CONFIG", "./configs/compression_wide.config");
-    let layer_1_snark = compression_layer_snark!(layer_0_snark, layer_2_params, k1, path, 1);
-
-    std::env::set_var("COMPRESSION_CONFIG", "./configs/compression_thin.config");
-    compression_layer_evm!(layer_1_snark, layer_2_params, k2, path, 2);
+    let layer_1_snark = compression_layer_snark!(
+        layer_0_snark,
+        layer_2_params,
+        k1,
+        path,
+        1,
+        CompressionWideParams
+    );
+
+    compression_layer_evm!(
+        layer_1_snark,
+        layer_2_params,
+        k2,
+        path,
+        2,
+        CompressionThinParams
+    );
 }
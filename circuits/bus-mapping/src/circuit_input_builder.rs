@@ -28,28 +28,30 @@ pub use call::{Call, CallContext, CallKind};
 use core::fmt::Debug;
 use eth_types::{
     self,
-    evm_types::{GasCost, OpcodeId},
+    evm_types::{GasCost, OpcodeId, MAX_REFUND_QUOTIENT_OF_GAS_USED},
     sign_types::get_dummy_tx,
     state_db::{CodeDB, StateDB},
     EthBlock, GethExecTrace, Word, H256,
 };
 use ethers_core::utils::keccak256;
 pub use execution::{
-    BigModExp, CopyAccessList, CopyBytes, CopyDataType, CopyEvent, CopyEventStepsBuilder, CopyStep,
-    EcAddOp, EcMulOp, EcPairingOp, EcPairingPair, ExecState, ExecStep, ExpEvent, ExpStep,
-    NumberOrHash, PrecompileEvent, PrecompileEvents, N_BYTES_PER_PAIR, N_PAIRING_PER_OP, SHA256,
+    BigModExp, Bls12G1AddOp, CopyAccessList, CopyBytes, CopyDataType, CopyEvent,
+    CopyEventStepsBuilder, CopyStep, EcAddOp, EcMulOp, EcPairingOp, EcPairingPair, ExecState,
+    ExecStep, ExpEvent, ExpStep, NumberOrHash, PrecompileEvent, PrecompileEvents, N_BYTES_PER_PAIR,
+    N_PAIRING_PER_OP, SHA256,
 };
 pub use input_state_ref::CircuitInputStateRef;
 use itertools::Itertools;
 #[cfg(feature = "scroll")]
 use mpt_zktrie::state::ZktrieState;
+use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 pub use transaction::{
     Transaction, TransactionContext, TxL1Fee, TX_L1_COMMIT_EXTRA_COST, TX_L1_FEE_PRECISION,
 };
 
 /// Setup parameters for ECC-related precompile calls.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub struct PrecompileEcParams {
     /// Maximum number of EcAdd ops supported in one block.
     pub ec_add: usize,
@@ -57,6 +59,9 @@ pub struct PrecompileEcParams {
     pub ec_mul: usize,
     /// Maximum number of EcPairing ops supported in one block.
     pub ec_pairing: usize,
+    /// Maximum number of BLS12-381 G1 addition ops supported in one block.
+    #[serde(default = "PrecompileEcParams::default_bls12_g1_add")]
+    pub bls12_g1_add: usize,
 }
 
 impl Default for PrecompileEcParams {
@@ -65,12 +70,19 @@ impl Default for PrecompileEcParams {
             ec_add: 50,
             ec_mul: 50,
             ec_pairing: 2,
+            bls12_g1_add: Self::default_bls12_g1_add(),
         }
     }
 }
 
+impl PrecompileEcParams {
+    fn default_bls12_g1_add() -> usize {
+        10
+    }
+}
+
 /// Circuit Setup Parameters
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub struct CircuitsParams {
     /// Maximum number of rw operations in the state circuit (RwTable length /
     /// number of rows). This must be at least the number of rw operations
@@ -578,7 +590,13 @@ impl<'a> CircuitInputBuilder {
         {
             let steps_gas_cost: u64 = begin_tx_steps.iter().map(|st| st.gas_cost.0).sum();
             let real_gas_cost = if geth_trace.struct_logs.is_empty() {
-                GasCost(geth_trace.gas.0)
+                // geth reports the gas used after refunds, which EIP7702 authorizations can give
+                // before any code runs.
+                let refund = self
+                    .sdb
+                    .refund()
+                    .min(steps_gas_cost / MAX_REFUND_QUOTIENT_OF_GAS_USED as u64);
+                GasCost(geth_trace.gas.0 + refund)
             } else {
                 GasCost(tx.gas - geth_trace.struct_logs[0].gas.0)
             };
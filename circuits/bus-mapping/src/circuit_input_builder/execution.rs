@@ -10,7 +10,10 @@ use crate::{
     error::{ExecError, OogError},
     exec_trace::OperationRef,
     operation::RWCounter,
-    precompile::{PrecompileAuxData, PrecompileCalls},
+    precompile::{
+        execute_precompiled, PrecompileAuxData, PrecompileCalls, BLS12_FP_LEN,
+        BLS12_G1_ADD_INPUT_LEN,
+    },
 };
 use eth_types::{
     evm_types::{memory::MemoryWordRange, Gas, GasCost, MemoryAddress, OpcodeId, ProgramCounter},
@@ -149,6 +152,8 @@ pub enum ExecState {
     Precompile(PrecompileCalls),
     /// Virtual step Begin Tx
     BeginTx,
+    /// Virtual step applying one EIP7702 authorization of the tx
+    TxAuthorization,
     /// Virtual step End Tx
     EndTx,
     /// Virtual step End Block
@@ -988,6 +993,20 @@ impl PrecompileEvents {
             .cloned()
             .collect()
     }
+    /// Get all BLS12-381 G1 addition events.
+    pub fn get_bls12_g1_add_events(&self) -> Vec<Bls12G1AddOp> {
+        self.events
+            .iter()
+            .filter_map(|e| {
+                if let PrecompileEvent::Bls12G1Add(op) = e {
+                    Some(op)
+                } else {
+                    None
+                }
+            })
+            .cloned()
+            .collect()
+    }
 }
 
 /// I/O from a precompiled contract call.
@@ -1005,6 +1024,8 @@ pub enum PrecompileEvent {
     ModExp(BigModExp),
     /// Represents the I/O from SHA256 call.
     SHA256(SHA256),
+    /// Represents the I/O from BLS12-381 G1 addition call.
+    Bls12G1Add(Bls12G1AddOp),
 }
 
 impl Default for PrecompileEvent {
@@ -1115,6 +1136,81 @@ impl EcAddOp {
     }
 }
 
+/// BLS12-381 G1 addition operation (EIP-2537): P + Q = R
+///
+/// Field elements are kept in their EVM encoding, i.e. 64 bytes big-endian of which the first 16
+/// must be zero, as the BLS12-381 circuit decomposes them byte by byte.
+#[derive(Clone, Debug)]
+pub struct Bls12G1AddOp {
+    /// EVM input to the call: p_x | p_y | q_x | q_y.
+    pub input: Vec<u8>,
+    /// Encoding of R = P + Q: r_x | r_y. Empty if the input is invalid.
+    pub output: Vec<u8>,
+}
+
+impl Default for Bls12G1AddOp {
+    /// P = G1 generator, Q = point at infinity, R = P.
+    fn default() -> Self {
+        let generator = [
+            hex::decode("17f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb")
+                .expect("valid hex"),
+            hex::decode("08b3f481e3aaa0f1a09e30ed741d8ae4fcf5e095d5d00af600db18cb2c04b3edd03cc744a2888ae40caa232946c5e7e1")
+                .expect("valid hex"),
+        ]
+        .iter()
+        .flat_map(|coordinate| {
+            let mut fp = vec![0u8; BLS12_FP_LEN - coordinate.len()];
+            fp.extend_from_slice(coordinate);
+            fp
+        })
+        .collect::<Vec<u8>>();
+
+        let mut input = generator.clone();
+        input.resize(BLS12_G1_ADD_INPUT_LEN, 0u8);
+        Self {
+            input,
+            output: generator,
+        }
+    }
+}
+
+impl Bls12G1AddOp {
+    /// Creates a new G1 addition op given the input bytes of a precompile call, which must be
+    /// exactly [`BLS12_G1_ADD_INPUT_LEN`] bytes long.
+    pub fn new_from_bytes(input: &[u8]) -> Self {
+        assert_eq!(input.len(), BLS12_G1_ADD_INPUT_LEN);
+        // the output is recomputed instead of taken from the call, so that the op stays
+        // consistent with its input even if the call itself failed for another reason.
+        let (output, _, _) = execute_precompiled(
+            &PrecompileCalls::Bls12G1Add.into(),
+            input,
+            GasCost::PRECOMPILE_BLS12_G1ADD.as_u64(),
+        );
+        Self {
+            input: input.to_vec(),
+            output,
+        }
+    }
+
+    /// Whether the EVM inputs are valid or not, i.e. does the precompile succeed or fail.
+    pub fn is_valid(&self) -> bool {
+        !self.output.is_empty()
+    }
+
+    /// Coordinates of P and Q: [p_x, p_y, q_x, q_y], each in the EVM encoding.
+    pub fn input_coordinates(&self) -> [&[u8]; 4] {
+        [0, 1, 2, 3].map(|i| &self.input[i * BLS12_FP_LEN..(i + 1) * BLS12_FP_LEN])
+    }
+
+    /// Coordinates of R: [r_x, r_y], each in the EVM encoding. R is the point at infinity, i.e.
+    /// all zeroes, if the input is invalid.
+    pub fn output_coordinates(&self) -> [Vec<u8>; 2] {
+        let mut output = self.output.clone();
+        output.resize(2 * BLS12_FP_LEN, 0u8);
+        [0, 1].map(|i| output[i * BLS12_FP_LEN..(i + 1) * BLS12_FP_LEN].to_vec())
+    }
+}
+
 /// EcMul operation: s.P = R
 #[derive(Clone, Debug)]
 pub struct EcMulOp {
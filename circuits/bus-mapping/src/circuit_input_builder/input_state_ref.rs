@@ -25,10 +25,12 @@ use crate::{
 use eth_types::{
     bytecode::BytecodeElement,
     evm_types::{
+        delegation::parse_delegation,
         gas_utils::memory_expansion_gas_cost,
         memory::{MemoryRange, MemoryWordRange},
         Gas, GasCost, Memory, MemoryAddress, MemoryRef, OpcodeId, StackAddress, MAX_CODE_SIZE,
     },
+    forks::LAST_PRECOMPILE_ADDRESS,
     state_db::{CodeDB, StateDB},
     utils::is_precompiled,
     Address, Bytecode, GethExecStep, ToAddress, ToBigEndian, ToWord, Word, H256, U256,
@@ -1051,7 +1053,96 @@ impl<'a> CircuitInputStateRef<'a> {
 
     /// Check if address is a precompiled or not.
     pub fn is_precompiled(&self, address: &Address) -> bool {
-        address.0[0..19] == [0u8; 19] && (1..=9).contains(&address.0[19])
+        address.0[0..19] == [0u8; 19] && (1..=LAST_PRECOMPILE_ADDRESS).contains(&address.0[19])
+    }
+
+    /// Return the delegate of `address` if its code is an EIP7702 delegation designator.
+    pub fn delegation_target(&self, address: &Address) -> Option<Address> {
+        let (found, account) = self.sdb.get_account(address);
+        if !found || account.code_hash == CodeDB::empty_code_hash() {
+            return None;
+        }
+        self.code_db
+            .0
+            .get(&account.code_hash)
+            .and_then(|code| parse_delegation(code))
+    }
+
+    /// Return the hash of the code executed when calling `address`, following an EIP7702
+    /// delegation designator to the code of its delegate.
+    pub fn executed_code_hash(&self, address: &Address) -> H256 {
+        let address = self.delegation_target(address).unwrap_or(*address);
+        let (found, account) = self.sdb.get_account(&address);
+        if !found || is_precompiled(&address) {
+            CodeDB::empty_code_hash()
+        } else {
+            account.code_hash
+        }
+    }
+
+    /// Check whether the code of `address` is an EIP7702 delegation designator, which the
+    /// circuit hashes to recover the delegate. Under scroll, this reads the keccak code hash of
+    /// `address`.
+    #[cfg_attr(not(feature = "scroll"), allow(unused_variables))]
+    pub fn delegation_read(
+        &mut self,
+        step: &mut ExecStep,
+        address: Address,
+    ) -> Result<Option<Address>, Error> {
+        let Some(delegate) = self.delegation_target(&address) else {
+            return Ok(None);
+        };
+        let account = self.sdb.get_account(&address).1.clone();
+        #[cfg(feature = "scroll")]
+        self.account_read(
+            step,
+            address,
+            AccountField::KeccakCodeHash,
+            account.keccak_code_hash.to_word(),
+        )?;
+        let designator = self.code(account.code_hash)?;
+        self.block.sha3_inputs.push(designator);
+
+        Ok(Some(delegate))
+    }
+
+    /// Follow the EIP7702 delegation designator of `address`, if any: see
+    /// [`Self::delegation_read`], then add the delegate to the access list and read its code
+    /// hash. Return the delegate and whether it was warm before.
+    pub fn delegation_ops(
+        &mut self,
+        step: &mut ExecStep,
+        address: Address,
+        is_reversible: bool,
+    ) -> Result<Option<(Address, bool)>, Error> {
+        let Some(delegate) = self.delegation_read(step, address)? else {
+            return Ok(None);
+        };
+
+        let tx_id = self.tx_ctx.id();
+        let is_warm = self.sdb.check_account_in_access_list(&delegate);
+        let access_list_op = TxAccessListAccountOp {
+            tx_id,
+            address: delegate,
+            is_warm: true,
+            is_warm_prev: is_warm,
+        };
+        if is_reversible {
+            self.push_op_reversible(step, access_list_op)?;
+        } else {
+            self.sdb.add_account_to_access_list(delegate);
+            self.push_op(step, RW::WRITE, access_list_op)?;
+        }
+
+        let delegate_account = self.sdb.get_account(&delegate).1;
+        let delegate_code_hash = if delegate_account.is_empty() {
+            Word::zero()
+        } else {
+            delegate_account.code_hash.to_word()
+        };
+        self.account_read(step, delegate, AccountField::CodeHash, delegate_code_hash)?;
+
+        Ok(Some((delegate, is_warm)))
     }
 
     /// Parse [`Call`] from a *CALL*/CREATE* step without information about success and persistent.
@@ -1091,16 +1182,10 @@ impl<'a> CircuitInputStateRef<'a> {
                     CallKind::CallCode | CallKind::DelegateCall => stack.nth_last(1)?.to_address(),
                     _ => address,
                 };
-                if is_precompiled(&code_address) {
-                    (CodeSource::Address(code_address), CodeDB::empty_code_hash())
-                } else {
-                    let (found, account) = self.sdb.get_account(&code_address);
-                    if !found {
-                        (CodeSource::Address(code_address), CodeDB::empty_code_hash())
-                    } else {
-                        (CodeSource::Address(code_address), account.code_hash)
-                    }
-                }
+                (
+                    CodeSource::Address(code_address),
+                    self.executed_code_hash(&code_address),
+                )
             }
         };
 
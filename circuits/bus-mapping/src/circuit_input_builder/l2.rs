@@ -10,8 +10,8 @@ use eth_types::{
     Address, EthBlock, ToWord, Word, H256,
 };
 use ethers_core::types::Bytes;
-use mpt_zktrie::state::ZktrieState;
-use std::collections::hash_map::HashMap;
+use mpt_zktrie::state::{NodeStore, ZktrieState};
+use std::{collections::hash_map::HashMap, sync::Arc};
 
 fn dump_code_db(cdb: &CodeDB) {
     for (k, v) in &cdb.0 {
@@ -114,6 +114,17 @@ impl CircuitInputBuilder {
         circuits_params: CircuitsParams,
         l2_trace: BlockTrace,
         light_mode: bool,
+    ) -> Result<Self, Error> {
+        Self::new_from_l2_trace_with_node_store(circuits_params, l2_trace, light_mode, None)
+    }
+
+    /// Create a new CircuitInputBuilder from the given `l2_trace` and `circuits_params`, with the
+    /// zktrie nodes backed by `node_store` when it is given and not in light mode
+    pub fn new_from_l2_trace_with_node_store(
+        circuits_params: CircuitsParams,
+        l2_trace: BlockTrace,
+        light_mode: bool,
+        node_store: Option<Arc<dyn NodeStore>>,
     ) -> Result<Self, Error> {
         let chain_id = l2_trace.chain_id;
 
@@ -125,8 +136,12 @@ impl CircuitInputBuilder {
         );
 
         let mpt_init_state = if !light_mode {
-            let mpt_init_state = ZktrieState::from_trace_with_additional(
-                old_root,
+            let mut mpt_init_state = match node_store {
+                Some(store) => ZktrieState::construct_with_node_store(old_root, store),
+                None => ZktrieState::construct(old_root),
+            };
+            // a lot of poseidon computation, for the nodes missing from the store
+            mpt_init_state.update_from_trace(
                 Self::collect_account_proofs(&l2_trace.storage_trace),
                 Self::collect_storage_proofs(&l2_trace.storage_trace),
                 l2_trace
@@ -134,8 +149,7 @@ impl CircuitInputBuilder {
                     .deletion_proofs
                     .iter()
                     .map(Bytes::as_ref),
-            )
-            .map_err(Error::IoError)?;
+            );
 
             log::debug!(
                 "building partial statedb done, root {}",
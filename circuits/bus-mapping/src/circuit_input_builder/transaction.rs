@@ -5,11 +5,13 @@ use super::{
     ExecStep,
 };
 use crate::{l2_predeployed::l1_gas_price_oracle, Error};
-use eth_types::evm_types::gas_utils::tx_data_gas_cost;
 use eth_types::{
-    evm_types::OpcodeId,
+    evm_types::{block_utils::GAS_PER_BLOB, gas_utils::tx_data_gas_cost, OpcodeId},
     geth_types,
-    geth_types::{get_rlp_unsigned, TxType},
+    geth_types::{
+        get_authorization_list, get_blob_fields, get_rlp_signed, get_rlp_unsigned,
+        SetCodeAuthorization, TxType,
+    },
     state_db::{CodeDB, StateDB},
     AccessList, Address, GethExecTrace, Signature, Word, H256,
 };
@@ -204,6 +206,12 @@ pub struct Transaction {
     pub l1_fee_committed: TxL1Fee,
     /// EIP2930
     pub access_list: Option<AccessList>,
+    /// EIP4844 max fee per blob gas
+    pub max_fee_per_blob_gas: Word,
+    /// EIP4844 blob versioned hashes
+    pub blob_versioned_hashes: Vec<H256>,
+    /// EIP7702 authorization list
+    pub authorization_list: Vec<SetCodeAuthorization>,
     /// Calls made in the transaction
     pub(crate) calls: Vec<Call>,
     /// Execution steps
@@ -230,6 +238,9 @@ impl From<&Transaction> for geth_types::Transaction {
             //rlp_signed_bytes: tx.rlp_signed_bytes.clone(),
             rlp_bytes: tx.rlp_bytes.clone(),
             tx_type: tx.tx_type,
+            max_fee_per_blob_gas: tx.max_fee_per_blob_gas,
+            blob_versioned_hashes: tx.blob_versioned_hashes.clone(),
+            authorization_list: tx.authorization_list.clone(),
             ..Default::default()
         }
     }
@@ -265,6 +276,9 @@ impl Transaction {
             l1_fee: Default::default(),
             l1_fee_committed: Default::default(),
             access_list: None,
+            max_fee_per_blob_gas: Word::zero(),
+            blob_versioned_hashes: vec![],
+            authorization_list: vec![],
         }
     }
 
@@ -357,7 +371,8 @@ impl Transaction {
             l1_fee,
             l1_fee_committed
         );
-        let rlp_signed_bytes = eth_tx.rlp().to_vec();
+        let rlp_signed_bytes = get_rlp_signed(eth_tx);
+        let (max_fee_per_blob_gas, blob_versioned_hashes) = get_blob_fields(eth_tx);
         //debug_assert_eq!(H256(ethers_core::utils::keccak256(&bytes)), eth_tx.hash);
 
         Ok(Self {
@@ -365,7 +380,7 @@ impl Transaction {
             hash: eth_tx.hash,
             chain_id: tx_chain_id,
             tx_type,
-            rlp_bytes: eth_tx.rlp().to_vec(),
+            rlp_bytes: rlp_signed_bytes.clone(),
             rlp_unsigned_bytes: get_rlp_unsigned(eth_tx),
             rlp_signed_bytes,
             nonce: eth_tx.nonce.as_u64(),
@@ -387,6 +402,9 @@ impl Transaction {
             l1_fee,
             l1_fee_committed,
             access_list: eth_tx.access_list.clone(),
+            max_fee_per_blob_gas,
+            blob_versioned_hashes,
+            authorization_list: get_authorization_list(eth_tx),
         })
     }
 
@@ -444,6 +462,11 @@ impl Transaction {
             )
             .0
     }
+
+    /// Blob gas of this transaction, zero unless it is an EIP-4844 tx.
+    pub fn blob_gas(&self) -> u64 {
+        GAS_PER_BLOB * self.blob_versioned_hashes.len() as u64
+    }
 }
 
 #[cfg(feature = "test")]
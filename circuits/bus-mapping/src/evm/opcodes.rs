@@ -22,6 +22,7 @@ mod address;
 mod arithmetic;
 mod balance;
 mod begin_end_tx;
+mod blobhash;
 mod blockhash;
 mod calldatacopy;
 mod calldataload;
@@ -88,6 +89,7 @@ use address::Address;
 use arithmetic::ArithmeticOpcode;
 use balance::Balance;
 use begin_end_tx::{gen_begin_tx_steps, gen_end_tx_steps};
+use blobhash::Blobhash;
 use blockhash::Blockhash;
 use calldatacopy::Calldatacopy;
 use calldataload::Calldataload;
@@ -236,6 +238,10 @@ fn fn_gen_associated_ops(opcode_id: &OpcodeId) -> FnGenAssociatedOps {
         OpcodeId::CHAINID => GetBlockHeaderField::<{ OpcodeId::CHAINID }>::gen_associated_ops,
         OpcodeId::SELFBALANCE => Selfbalance::gen_associated_ops,
         OpcodeId::BASEFEE => GetBlockHeaderField::<{ OpcodeId::BASEFEE }>::gen_associated_ops,
+        OpcodeId::BLOBHASH => Blobhash::gen_associated_ops,
+        OpcodeId::BLOBBASEFEE => {
+            GetBlockHeaderField::<{ OpcodeId::BLOBBASEFEE }>::gen_associated_ops
+        }
         OpcodeId::POP => StackPopOnlyOpcode::<1>::gen_associated_ops,
         OpcodeId::MCOPY => MCopy::gen_associated_ops,
         OpcodeId::MLOAD => Mload::gen_associated_ops,
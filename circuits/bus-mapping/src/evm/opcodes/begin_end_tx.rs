@@ -5,25 +5,30 @@ use super::{
 use crate::{
     circuit_input_builder::{
         curie::is_curie_enabled, Call, CircuitInputStateRef, CopyAccessList, CopyBytes,
-        CopyDataType, CopyEvent, ExecStep, NumberOrHash,
+        CopyDataType, CopyEvent, ExecState, ExecStep, NumberOrHash,
     },
     l2_predeployed::l1_gas_price_oracle,
     operation::{
         AccountField, AccountOp, CallContextField, StorageOp, TxReceiptField, TxRefundOp, RW,
     },
-    precompile::{execute_precompiled, PrecompileCalls},
+    precompile::{check_precompile_supported, execute_precompiled, PrecompileCalls},
     Error,
 };
 use eth_types::{
     evm_types::{
+        delegation::delegation_designator,
         gas_utils::{tx_access_list_gas_cost, tx_data_gas_cost},
-        GasCost, MAX_REFUND_QUOTIENT_OF_GAS_USED,
+        GasCost, ProgramCounter, MAX_REFUND_QUOTIENT_OF_GAS_USED,
     },
+    forks::LAST_PRECOMPILE_ADDRESS,
+    geth_types::SetCodeAuthorization,
     state_db::CodeDB,
     utils::is_precompiled,
     Bytecode, ToWord, Word,
 };
 use ethers_core::utils::get_contract_address;
+#[cfg(feature = "scroll")]
+use ethers_core::utils::keccak256;
 
 // #[derive(Clone, Copy, Debug)]
 // pub(crate) struct BeginEndTx;
@@ -148,7 +153,7 @@ pub fn gen_begin_tx_steps(state: &mut CircuitInputStateRef) -> Result<Vec<ExecSt
     )?;
 
     // Add precompile contract address to access list
-    for address in 1..=9 {
+    for address in 1..=LAST_PRECOMPILE_ADDRESS as u64 {
         let address = eth_types::Address::from_low_u64_be(address);
         let is_warm_prev = !state.sdb.add_account_to_access_list(address);
         state.tx_access_list_account_write(
@@ -198,12 +203,14 @@ pub fn gen_begin_tx_steps(state: &mut CircuitInputStateRef) -> Result<Vec<ExecSt
         GasCost::TX.as_u64()
     } + call_data_gas_cost
         + access_list_gas_cost
-        + init_code_gas_cost;
+        + init_code_gas_cost
+        + state.tx.authorization_list.len() as u64 * GasCost::PER_EMPTY_ACCOUNT.as_u64();
     log::trace!("intrinsic_gas_cost {intrinsic_gas_cost}, call_data_gas_cost {call_data_gas_cost}, access_list_gas_cost {access_list_gas_cost}, init_code_gas_cost {init_code_gas_cost}, &mut exec_step.gas_cost {:?}", &mut exec_step.gas_cost);
     exec_step.gas_cost = GasCost(intrinsic_gas_cost);
 
     // Get code_hash of callee account
     let callee_account = &state.sdb.get_account(&call.address).1.clone();
+    check_precompile_supported(&call.address)?;
     let is_precompile = is_precompiled(&call.address);
     let callee_exists = !callee_account.is_empty();
     //if !callee_exists && call.value.is_zero() {
@@ -218,11 +225,6 @@ pub fn gen_begin_tx_steps(state: &mut CircuitInputStateRef) -> Result<Vec<ExecSt
     } else {
         Word::zero()
     };
-    // call_code is code being executed
-    let call_code_hash = call.code_hash.to_word();
-    if !state.tx.is_create() && !account_code_hash.is_zero() {
-        debug_assert_eq!(account_code_hash, call_code_hash);
-    }
     let account_code_hash_is_empty_or_zero =
         account_code_hash.is_zero() || account_code_hash == CodeDB::empty_code_hash().to_word();
 
@@ -233,6 +235,24 @@ pub fn gen_begin_tx_steps(state: &mut CircuitInputStateRef) -> Result<Vec<ExecSt
         account_code_hash,
     )?;
 
+    // EIP7702: the delegate of a delegated callee is warmed, and its code is run by the root
+    // call. With authorizations, this is only done once they are applied, by the last
+    // `TxAuthorization` step.
+    let has_authorizations = !state.tx.authorization_list.is_empty();
+    if has_authorizations && is_precompile {
+        return Err(Error::InternalError(
+            "EIP7702 tx to a precompile is not supported",
+        ));
+    }
+    // call_code is code being executed
+    let call_code_hash = if state.tx.is_create() || has_authorizations {
+        call.code_hash
+    } else {
+        state.delegation_ops(&mut exec_step, call.address, false)?;
+        state.executed_code_hash(&call.address)
+    };
+    state.call_mut()?.code_hash = call_code_hash;
+
     if state.tx.is_create()
         && ((!account_code_hash_is_empty_or_zero) || !callee_account.nonce.is_zero())
     {
@@ -257,11 +277,14 @@ pub fn gen_begin_tx_steps(state: &mut CircuitInputStateRef) -> Result<Vec<ExecSt
         }
     }
 
-    // Transfer with fee
+    // Transfer with fee. The blob fee of EIP-4844 txs is burned, as the base fee.
     let fee = if state.tx.tx_type.is_l1_msg() {
         0.into()
     } else {
-        state.tx.gas_price * state.tx.gas + state.tx_ctx.l1_fee
+        let blob_base_fee = state.block.blocks[&state.tx.block_num].blob_base_fee;
+        state.tx.gas_price * state.tx.gas
+            + state.tx_ctx.l1_fee
+            + blob_base_fee * state.tx.blob_gas()
     };
     state.transfer_with_fee(
         &mut exec_step,
@@ -323,15 +346,17 @@ pub fn gen_begin_tx_steps(state: &mut CircuitInputStateRef) -> Result<Vec<ExecSt
     }
 
     let mut precompile_step = None;
+    let mut authorization_steps = vec![];
 
-    // There are 4 branches from here.
+    // There are 5 branches from here.
     match (
         call.is_create(),
         is_precompile,
-        account_code_hash_is_empty_or_zero,
+        has_authorizations,
+        call_code_hash == CodeDB::empty_code_hash(),
     ) {
         // 1. Creation transaction.
-        (true, _, _) => {
+        (true, _, _, _) => {
             state.push_op_reversible(
                 &mut exec_step,
                 AccountOp {
@@ -377,7 +402,7 @@ pub fn gen_begin_tx_steps(state: &mut CircuitInputStateRef) -> Result<Vec<ExecSt
            + Prepare a precompile step to be returned, so in this case `gen_begin_tx_steps`
              would return 2 steps instead of 1
         */
-        (_, true, _) => {
+        (_, true, _, _) => {
             // some *pre-handling* for precompile address, like what we have done in callop
             // the generation of precompile step is in `handle_tx`, right after the generation
             // of begin_tx step
@@ -503,7 +528,40 @@ pub fn gen_begin_tx_steps(state: &mut CircuitInputStateRef) -> Result<Vec<ExecSt
             state.tx_ctx.pop_call_ctx(call_success);
             precompile_step.replace(next_step);
         }
-        (_, _, is_empty_code_hash) => {
+        // 5. EIP7702 tx with authorizations.
+        //
+        //   + Set up the root call's context except for its code hash
+        //   + Prepare one `TxAuthorization` step per authorization, the last of which resolves the
+        //     code of the root call
+        (_, _, true, _) => {
+            for (field, value) in [
+                (CallContextField::Depth, call.depth.into()),
+                (
+                    CallContextField::CallerAddress,
+                    call.caller_address.to_word(),
+                ),
+                (CallContextField::CalleeAddress, call.address.to_word()),
+                (
+                    CallContextField::CallDataOffset,
+                    call.call_data_offset.into(),
+                ),
+                (
+                    CallContextField::CallDataLength,
+                    call.call_data_length.into(),
+                ),
+                (CallContextField::Value, call.value),
+                (CallContextField::IsStatic, (call.is_static as usize).into()),
+                (CallContextField::LastCalleeId, 0.into()),
+                (CallContextField::LastCalleeReturnDataOffset, 0.into()),
+                (CallContextField::LastCalleeReturnDataLength, 0.into()),
+                (CallContextField::IsRoot, 1.into()),
+                (CallContextField::IsCreate, call.is_create().to_word()),
+            ] {
+                state.call_context_write(&mut exec_step, call.call_id, field, value)?;
+            }
+            authorization_steps = gen_tx_authorization_steps(state, &exec_step)?;
+        }
+        (_, _, _, is_empty_code_hash) => {
             // 3. Call to account with empty code (is_empty_code_hash == true).
             // 4. Call to account with non-empty code (is_empty_code_hash == false).
             if !is_empty_code_hash {
@@ -529,7 +587,7 @@ pub fn gen_begin_tx_steps(state: &mut CircuitInputStateRef) -> Result<Vec<ExecSt
                     (CallContextField::LastCalleeReturnDataLength, 0.into()),
                     (CallContextField::IsRoot, 1.into()),
                     (CallContextField::IsCreate, call.is_create().to_word()),
-                    (CallContextField::CodeHash, call_code_hash),
+                    (CallContextField::CodeHash, call_code_hash.to_word()),
                 ] {
                     state.call_context_write(&mut exec_step, call.call_id, field, value)?;
                 }
@@ -538,7 +596,196 @@ pub fn gen_begin_tx_steps(state: &mut CircuitInputStateRef) -> Result<Vec<ExecSt
     }
     log::trace!("begin_tx_step: {:?}, {:?}", exec_step, precompile_step);
 
-    Ok(std::iter::once(exec_step).chain(precompile_step).collect())
+    Ok(std::iter::once(exec_step)
+        .chain(precompile_step)
+        .chain(authorization_steps)
+        .collect())
+}
+
+/// Generate one `TxAuthorization` step per EIP7702 authorization of the tx, after the `BeginTx`
+/// step. The last one also resolves the code run by the root call, which the authorizations may
+/// have delegated.
+fn gen_tx_authorization_steps(
+    state: &mut CircuitInputStateRef,
+    begin_tx_step: &ExecStep,
+) -> Result<Vec<ExecStep>, Error> {
+    let call = state.call()?.clone();
+    let authorization_list = state.tx.authorization_list.clone();
+    let mut steps: Vec<ExecStep> = Vec::with_capacity(authorization_list.len());
+    for (index, authorization) in authorization_list.iter().enumerate() {
+        let mut exec_step = state.new_next_step(steps.last().unwrap_or(begin_tx_step))?;
+        exec_step.exec_state = ExecState::TxAuthorization;
+        // The program counter holds the index of the authorization.
+        exec_step.pc = ProgramCounter(index);
+
+        state.call_context_read(
+            &mut exec_step,
+            call.call_id,
+            CallContextField::TxId,
+            state.tx_ctx.id().into(),
+        )?;
+        apply_authorization(state, &mut exec_step, authorization)?;
+
+        if index + 1 == authorization_list.len() {
+            let account = state.sdb.get_account(&call.address).1;
+            let account_code_hash = if account.is_empty() {
+                Word::zero()
+            } else {
+                account.code_hash.to_word()
+            };
+            state.account_read(
+                &mut exec_step,
+                call.address,
+                AccountField::CodeHash,
+                account_code_hash,
+            )?;
+            state.delegation_ops(&mut exec_step, call.address, false)?;
+
+            let call_code_hash = state.executed_code_hash(&call.address);
+            state.call_mut()?.code_hash = call_code_hash;
+            if call_code_hash == CodeDB::empty_code_hash() {
+                // the tx ends here, like a tx to an account with empty code
+                state.call_context_read(
+                    &mut exec_step,
+                    call.call_id,
+                    CallContextField::IsPersistent,
+                    call.is_persistent.to_word(),
+                )?;
+            } else {
+                state.call_context_write(
+                    &mut exec_step,
+                    call.call_id,
+                    CallContextField::CodeHash,
+                    call_code_hash.to_word(),
+                )?;
+            }
+        }
+        steps.push(exec_step);
+    }
+
+    Ok(steps)
+}
+
+/// Apply an EIP7702 authorization, skipping it if it is invalid. The checks follow geth: chain
+/// id, nonce range and signature first, then the code and the nonce of the authority.
+fn apply_authorization(
+    state: &mut CircuitInputStateRef,
+    exec_step: &mut ExecStep,
+    authorization: &SetCodeAuthorization,
+) -> Result<(), Error> {
+    let is_chain_id_valid =
+        authorization.chain_id.is_zero() || authorization.chain_id == Word::from(state.tx.chain_id);
+    let is_nonce_valid = authorization.nonce.as_u64() != u64::MAX;
+    let authority = match authorization.authority() {
+        Some(authority) if is_chain_id_valid && is_nonce_valid => authority,
+        _ => return Ok(()),
+    };
+
+    let tx_id = state.tx_ctx.id();
+    let is_warm_prev = !state.sdb.add_account_to_access_list(authority);
+    state.tx_access_list_account_write(exec_step, tx_id, authority, true, is_warm_prev)?;
+
+    let account = state.sdb.get_account(&authority).1.clone();
+    let exists = !account.is_empty();
+    state.account_read(
+        exec_step,
+        authority,
+        AccountField::CodeHash,
+        if exists {
+            account.code_hash.to_word()
+        } else {
+            Word::zero()
+        },
+    )?;
+    let is_delegated = state.delegation_read(exec_step, authority)?.is_some();
+    if exists {
+        state.account_read(exec_step, authority, AccountField::Nonce, account.nonce)?;
+    }
+    let is_code_valid = account.code_hash == CodeDB::empty_code_hash() || is_delegated;
+    if !is_code_valid || account.nonce != authorization.nonce.as_u64().into() {
+        return Ok(());
+    }
+
+    if !exists {
+        state.account_write(
+            exec_step,
+            authority,
+            AccountField::CodeHash,
+            CodeDB::empty_code_hash().to_word(),
+            Word::zero(),
+        )?;
+        #[cfg(feature = "scroll")]
+        state.account_write(
+            exec_step,
+            authority,
+            AccountField::KeccakCodeHash,
+            crate::util::KECCAK_CODE_HASH_EMPTY.to_word(),
+            Word::zero(),
+        )?;
+    }
+    state.account_write(
+        exec_step,
+        authority,
+        AccountField::Nonce,
+        account.nonce + 1,
+        account.nonce,
+    )?;
+
+    // A zero address clears the delegation.
+    let code = if authorization.address.is_zero() {
+        vec![]
+    } else {
+        delegation_designator(&authorization.address)
+    };
+    let code_hash_prev = state.sdb.get_account(&authority).1.code_hash;
+    let code_hash = state.code_db.insert(code.clone());
+    state.account_write(
+        exec_step,
+        authority,
+        AccountField::CodeHash,
+        code_hash.to_word(),
+        code_hash_prev.to_word(),
+    )?;
+    #[cfg(feature = "scroll")]
+    {
+        let account = state.sdb.get_account(&authority).1.clone();
+        state.account_write(
+            exec_step,
+            authority,
+            AccountField::KeccakCodeHash,
+            Word::from(keccak256(&code)),
+            account.keccak_code_hash.to_word(),
+        )?;
+        state.account_write(
+            exec_step,
+            authority,
+            AccountField::CodeSize,
+            code.len().into(),
+            account.code_size,
+        )?;
+    }
+    // The installed designator is hashed in the circuit.
+    if !code.is_empty() {
+        state.block.sha3_inputs.push(code);
+    }
+
+    if exists {
+        let refund = state.sdb.refund();
+        let refund_next =
+            refund + GasCost::PER_EMPTY_ACCOUNT.as_u64() - GasCost::PER_AUTH_BASE.as_u64();
+        state.push_op(
+            exec_step,
+            RW::WRITE,
+            TxRefundOp {
+                tx_id,
+                value: refund_next,
+                value_prev: refund,
+            },
+        )?;
+        state.sdb.set_refund(refund_next);
+    }
+
+    Ok(())
 }
 
 pub fn gen_end_tx_steps(state: &mut CircuitInputStateRef) -> Result<ExecStep, Error> {
@@ -0,0 +1,103 @@
+use crate::{
+    circuit_input_builder::{CircuitInputStateRef, ExecStep},
+    operation::CallContextField,
+    Error,
+};
+use eth_types::{GethExecStep, Word};
+
+use super::Opcode;
+
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Blobhash;
+
+impl Opcode for Blobhash {
+    fn gen_associated_ops(
+        state: &mut CircuitInputStateRef,
+        geth_steps: &[GethExecStep],
+    ) -> Result<Vec<ExecStep>, Error> {
+        let geth_step = &geth_steps[0];
+        let mut exec_step = state.new_step(geth_step)?;
+
+        // CallContext read of the TxId
+        let tx_id = state.tx_ctx.id();
+        state.call_context_read(
+            &mut exec_step,
+            state.call()?.call_id,
+            CallContextField::TxId,
+            tx_id.into(),
+        )?;
+
+        let index = state.stack_pop(&mut exec_step)?;
+        #[cfg(feature = "enable-stack")]
+        assert_eq!(index, geth_step.stack.last()?);
+
+        // BLOBHASH returns 0 for an out of range index
+        let blob_hash = usize::try_from(index)
+            .ok()
+            .and_then(|index| state.tx.blob_versioned_hashes.get(index))
+            .map_or_else(Word::zero, |hash| Word::from_big_endian(hash.as_bytes()));
+        #[cfg(feature = "enable-stack")]
+        assert_eq!(blob_hash, geth_steps[1].stack.last()?);
+        state.stack_push(&mut exec_step, blob_hash)?;
+
+        Ok(vec![exec_step])
+    }
+}
+
+#[cfg(test)]
+mod blobhash_tests {
+    use crate::{
+        circuit_input_builder::ExecState,
+        mock::BlockData,
+        operation::{StackOp, RW},
+    };
+    use eth_types::{
+        bytecode,
+        evm_types::{OpcodeId, StackAddress},
+        geth_types::GethData,
+        Word,
+    };
+    use mock::test_ctx::{helpers::*, TestContext};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn blobhash_opcode_impl() {
+        let code = bytecode! {
+            PUSH1(0)
+            BLOBHASH
+            STOP
+        };
+
+        // Get the execution steps from the external tracer
+        let block: GethData = TestContext::<2, 1>::new(
+            None,
+            account_0_code_account_1_no_code(code),
+            tx_from_1_to_0,
+            |block, _tx| block,
+        )
+        .unwrap()
+        .into();
+
+        let mut builder = BlockData::new_from_geth_data(block.clone()).new_circuit_input_builder();
+        builder
+            .handle_block(&block.eth_block, &block.geth_traces)
+            .unwrap();
+
+        let step = builder.block.txs()[0]
+            .steps()
+            .iter()
+            .find(|step| step.exec_state == ExecState::Op(OpcodeId::BLOBHASH))
+            .unwrap();
+
+        // A non blob tx has no blob versioned hashes.
+        assert_eq!(
+            [1, 2]
+                .map(|idx| &builder.block.container.stack[step.bus_mapping_instance[idx].as_usize()])
+                .map(|operation| (operation.rw(), operation.op())),
+            [
+                (RW::READ, &StackOp::new(1, StackAddress::from(1023), Word::zero())),
+                (RW::WRITE, &StackOp::new(1, StackAddress::from(1023), Word::zero())),
+            ]
+        );
+    }
+}
@@ -9,7 +9,7 @@ use crate::{
         precompiles::gen_associated_ops as precompile_associated_ops,
     },
     operation::{AccountField, CallContextField, TxAccessListAccountOp},
-    precompile::{execute_precompiled, PrecompileCalls},
+    precompile::{check_precompile_supported, execute_precompiled, PrecompileCalls},
     Error,
 };
 use eth_types::{
@@ -137,12 +137,14 @@ impl<const N_ARGS: usize> Opcode for CallOpcode<N_ARGS> {
         }
         state.stack_push(&mut exec_step, (callee_call.is_success as u64).into())?;
 
+        // `callee_call.code_hash` is the hash of the executed code, which differs from the
+        // account code hash when the callee holds an EIP7702 delegation designator.
         let callee_code_hash = callee_call.code_hash;
         let callee_acc = state.sdb.get_account(&callee_address).1;
         let callee_exists = !callee_acc.is_empty();
         let (callee_code_hash_word, is_empty_code_hash) = if callee_exists {
             (
-                callee_code_hash.to_word(),
+                callee_acc.code_hash.to_word(),
                 callee_code_hash == CodeDB::empty_code_hash(),
             )
         } else {
@@ -166,6 +168,12 @@ impl<const N_ARGS: usize> Opcode for CallOpcode<N_ARGS> {
             },
         )?;
 
+        // The delegate of an EIP7702 delegated callee is warmed and charged like the callee.
+        let delegate_is_warm = state
+            .delegation_ops(&mut exec_step, callee_address, true)?
+            .map(|(_, is_warm)| is_warm);
+        let caller_reversible_writes = 1 + delegate_is_warm.is_some() as usize;
+
         // Switch to callee's call context
         state.push_call(callee_call.clone());
 
@@ -194,6 +202,9 @@ impl<const N_ARGS: usize> Opcode for CallOpcode<N_ARGS> {
         )?;
 
         let code_address = callee_call.code_address();
+        if let Some(code_address) = code_address {
+            check_precompile_supported(&code_address)?;
+        }
         let is_precompile = code_address
             .map(|ref addr| is_precompiled(addr))
             .unwrap_or(false);
@@ -239,6 +250,10 @@ impl<const N_ARGS: usize> Opcode for CallOpcode<N_ARGS> {
                 }
         } else {
             0
+        } + match delegate_is_warm {
+            Some(true) => GasCost::WARM_ACCESS.as_u64(),
+            Some(false) => GasCost::COLD_ACCOUNT_ACCESS.as_u64(),
+            None => 0,
         } + memory_expansion_gas_cost;
         let gas_specified = stack_inputs[0];
         debug_assert!(
@@ -369,7 +384,7 @@ impl<const N_ARGS: usize> Opcode for CallOpcode<N_ARGS> {
                     (CallContextField::MemorySize, next_memory_word_size.into()),
                     (
                         CallContextField::ReversibleWriteCounter,
-                        (exec_step.reversible_write_counter + 1).into(),
+                        (exec_step.reversible_write_counter + caller_reversible_writes).into(),
                     ),
                     (CallContextField::LastCalleeId, callee_call.call_id.into()),
                     (CallContextField::LastCalleeReturnDataOffset, 0.into()),
@@ -584,7 +599,7 @@ impl<const N_ARGS: usize> Opcode for CallOpcode<N_ARGS> {
                     (CallContextField::MemorySize, next_memory_word_size.into()),
                     (
                         CallContextField::ReversibleWriteCounter,
-                        (exec_step.reversible_write_counter + 1).into(),
+                        (exec_step.reversible_write_counter + caller_reversible_writes).into(),
                     ),
                 ] {
                     state.call_context_write(&mut exec_step, caller_call.call_id, field, value)?;
@@ -1076,4 +1091,45 @@ pub mod tests {
             }
         }
     }
+
+    #[cfg(not(feature = "scroll"))]
+    #[test]
+    fn test_unsupported_precompile_call() {
+        use crate::{
+            circuit_input_builder::CircuitsParams,
+            mock::BlockData,
+            precompile::{PrecompileCalls, UNSUPPORTED_BLS12_381_PRECOMPILES},
+        };
+        use eth_types::geth_types::GethData;
+        use mock::{
+            test_ctx::helpers::{account_0_code_account_1_no_code, tx_from_1_to_0},
+            TestContext,
+        };
+
+        let point_evaluation = PrecompileCalls::PointEvaluation as u8;
+        for address in std::iter::once(point_evaluation).chain(UNSUPPORTED_BLS12_381_PRECOMPILES) {
+            let code = PrecompileCallArgs {
+                name: "unsupported precompile",
+                call_data_length: Word::from(0xc0),
+                address: Word::from(address),
+                ..Default::default()
+            }
+            .with_call_op(OpcodeId::STATICCALL);
+            let block: GethData = TestContext::<2, 1>::new(
+                None,
+                account_0_code_account_1_no_code(code),
+                tx_from_1_to_0,
+                |block, _tx| block.number(0xcafeu64),
+            )
+            .unwrap()
+            .into();
+
+            let mut builder =
+                BlockData::new_from_geth_data_with_params(block.clone(), CircuitsParams::default())
+                    .new_circuit_input_builder();
+            assert!(builder
+                .handle_block(&block.eth_block, &block.geth_traces)
+                .is_err());
+        }
+    }
 }
@@ -54,6 +54,12 @@ impl BlockHeaderToField for GetBlockHeaderField<{ OpcodeId::BASEFEE }> {
     }
 }
 
+impl BlockHeaderToField for GetBlockHeaderField<{ OpcodeId::BLOBBASEFEE }> {
+    fn handle(block_head: &Block) -> Word {
+        block_head.blob_base_fee
+    }
+}
+
 impl<const OP: OpcodeId> Opcode for GetBlockHeaderField<OP>
 where
     Self: BlockHeaderToField,
@@ -173,4 +179,12 @@ mod tests {
             STOP
         });
     }
+
+    #[test]
+    fn blobbasefee_opcode_impl() {
+        test_trace(bytecode! {
+            BLOBBASEFEE
+            STOP
+        });
+    }
 }
@@ -85,6 +85,21 @@ impl Opcode for OOGCall {
             },
         )?;
 
+        // The delegate of an EIP7702 delegated callee is charged like the callee.
+        if let Some(delegate) = state.delegation_read(&mut exec_step, call_address)? {
+            let is_warm = state.sdb.check_account_in_access_list(&delegate);
+            state.push_op(
+                &mut exec_step,
+                RW::READ,
+                TxAccessListAccountOp {
+                    tx_id,
+                    address: delegate,
+                    is_warm,
+                    is_warm_prev: is_warm,
+                },
+            )?;
+        }
+
         state.handle_return((None, None), &mut [&mut exec_step], geth_steps, true)?;
         Ok(vec![exec_step])
     }
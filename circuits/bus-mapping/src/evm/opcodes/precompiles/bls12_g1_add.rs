@@ -0,0 +1,28 @@
+use crate::{
+    circuit_input_builder::{Bls12G1AddOp, PrecompileEvent},
+    precompile::{PrecompileAuxData, BLS12_G1_ADD_INPUT_LEN},
+};
+
+pub(crate) fn opt_data(
+    input_bytes: &[u8],
+    output_bytes: &[u8],
+    return_bytes: &[u8],
+) -> (Option<PrecompileEvent>, Option<PrecompileAuxData>) {
+    // inputs of any other length are rejected without a lookup to the BLS12-381 circuit.
+    let opt_event = if input_bytes.len() == BLS12_G1_ADD_INPUT_LEN {
+        Some(PrecompileEvent::Bls12G1Add(Bls12G1AddOp::new_from_bytes(
+            input_bytes,
+        )))
+    } else {
+        None
+    };
+
+    (
+        opt_event,
+        Some(PrecompileAuxData::Base {
+            input_bytes: input_bytes.to_vec(),
+            output_bytes: output_bytes.to_vec(),
+            return_bytes: return_bytes.to_vec(),
+        }),
+    )
+}
@@ -9,17 +9,21 @@ use crate::{
     Error,
 };
 
+mod bls12_g1_add;
 mod ec_add;
 mod ec_mul;
 mod ec_pairing;
 mod ecrecover;
 mod modexp;
+mod point_evaluation;
 
+use bls12_g1_add::opt_data as opt_data_bls12_g1_add;
 use ec_add::opt_data as opt_data_ec_add;
 use ec_mul::opt_data as opt_data_ec_mul;
 use ec_pairing::opt_data as opt_data_ec_pairing;
 use ecrecover::opt_data as opt_data_ecrecover;
 use modexp::opt_data as opt_data_modexp;
+use point_evaluation::opt_data as opt_data_point_evaluation;
 
 pub fn gen_associated_ops(
     state: &mut CircuitInputStateRef,
@@ -66,6 +70,12 @@ pub fn gen_ops(
             opt_data_ec_pairing(input_bytes, output_bytes, return_bytes)
         }
         PrecompileCalls::Modexp => opt_data_modexp(input_bytes, output_bytes, return_bytes),
+        PrecompileCalls::PointEvaluation => {
+            opt_data_point_evaluation(input_bytes, output_bytes, return_bytes)
+        }
+        PrecompileCalls::Bls12G1Add => {
+            opt_data_bls12_g1_add(input_bytes, output_bytes, return_bytes)
+        }
         PrecompileCalls::Identity => (
             None,
             Some(PrecompileAuxData::Identity {
@@ -0,0 +1,25 @@
+use crate::{
+    circuit_input_builder::{PrecompileEvent, SHA256},
+    precompile::{PointEvaluationAuxData, PrecompileAuxData, POINT_EVALUATION_INPUT_LEN},
+};
+
+pub(crate) fn opt_data(
+    input_bytes: &[u8],
+    output_bytes: &[u8],
+    return_bytes: &[u8],
+) -> (Option<PrecompileEvent>, Option<PrecompileAuxData>) {
+    let aux_data = PointEvaluationAuxData::new(input_bytes, output_bytes, return_bytes);
+
+    // The versioned hash of any well-sized input is checked against the sha256 of the commitment.
+    let sha256_event = (input_bytes.len() == POINT_EVALUATION_INPUT_LEN).then(|| {
+        PrecompileEvent::SHA256(SHA256 {
+            input: aux_data.commitment.clone(),
+            digest: aux_data.commitment_digest.0,
+        })
+    });
+
+    (
+        sha256_event,
+        Some(PrecompileAuxData::PointEvaluation(aux_data)),
+    )
+}
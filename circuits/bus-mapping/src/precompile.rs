@@ -1,24 +1,41 @@
 //! precompile helpers
 
-use eth_types::{evm_types::GasCost, Address, ToBigEndian, Word};
+use eth_types::{
+    evm_types::GasCost, forks::LAST_PRECOMPILE_ADDRESS, word, Address, ToBigEndian, Word, H256,
+};
 use revm_precompile::{Precompile, PrecompileError, Precompiles};
+use sha2::{Digest, Sha256};
+use std::{ops::RangeInclusive, sync::LazyLock};
 use strum_macros::EnumIter;
 
-use crate::circuit_input_builder::{EcMulOp, EcPairingOp};
+use crate::{
+    circuit_input_builder::{EcMulOp, EcPairingOp},
+    Error,
+};
 
 pub(crate) fn execute_precompiled(
     address: &Address,
     input: &[u8],
     gas: u64,
 ) -> (Vec<u8>, u64, bool) {
+    // revm only provides point evaluation as an env precompile, and not in every spec.
+    if *address == PrecompileCalls::PointEvaluation.into() {
+        return execute_point_evaluation(input, gas);
+    }
+
     #[cfg(feature = "scroll")]
     let precompiles = Precompiles::bernoulli();
     #[cfg(not(feature = "scroll"))]
     let precompiles = Precompiles::berlin();
 
-    let Some(Precompile::Standard(precompile_fn)) =
-        precompiles.get(address.as_fixed_bytes().into())
-    else {
+    // the BLS12-381 G1 addition (EIP-2537) is not part of the specs above either.
+    let bls12_381_precompile = revm_precompile::bls12_381::precompiles()
+        .find(|precompile| precompile.0.as_slice() == address.as_bytes());
+    let precompile = match &bls12_381_precompile {
+        Some(precompile) => Some(&precompile.1),
+        None => precompiles.get(address.as_fixed_bytes().into()),
+    };
+    let Some(Precompile::Standard(precompile_fn)) = precompile else {
         panic!("calling non-exist precompiled contract address")
     };
     log::trace!(
@@ -41,7 +58,116 @@ pub(crate) fn execute_precompiled(
     (return_data, gas_cost, is_oog)
 }
 
+/// Reject calls to the point evaluation precompile and to the
+/// [`UNSUPPORTED_BLS12_381_PRECOMPILES`] of forks which have them, rather than treating them as
+/// calls to empty accounts.
+///
+/// The EVM circuit cannot verify the KZG opening of a point evaluation call yet, as that needs a
+/// BLS12-381 pairing check, so its result would not be constrained.
+pub(crate) fn check_precompile_supported(address: &Address) -> Result<(), Error> {
+    if LAST_PRECOMPILE_ADDRESS >= PrecompileCalls::PointEvaluation as u8
+        && *address == PrecompileCalls::PointEvaluation.into()
+    {
+        return Err(Error::InternalError(
+            "the point evaluation precompile is not supported",
+        ));
+    }
+    if LAST_PRECOMPILE_ADDRESS >= PrecompileCalls::Bls12G1Add as u8
+        && address.0[0..19] == [0u8; 19]
+        && UNSUPPORTED_BLS12_381_PRECOMPILES.contains(&address.0[19])
+    {
+        return Err(Error::InternalError(
+            "BLS12-381 precompiles other than G1 addition are not supported",
+        ));
+    }
+    Ok(())
+}
+
+/// Version byte of versioned hashes of KZG commitments.
+pub const VERSIONED_HASH_VERSION_KZG: u8 = 0x01;
+/// Number of field elements in a blob, the first word returned by point evaluation.
+pub const FIELD_ELEMENTS_PER_BLOB: u64 = 4096;
+/// Modulus of the BLS12-381 scalar field, the second word returned by point evaluation.
+pub static BLS_MODULUS: LazyLock<Word> =
+    LazyLock::new(|| word!("0x73eda753299d7d483339d80809a1d80553bda402fffe5bfeffffffff00000001"));
+/// Size of the point evaluation input: versioned_hash | z | y | commitment | proof.
+pub const POINT_EVALUATION_INPUT_LEN: usize = 192;
+/// Size of a BLS12-381 base field element in the EIP-2537 encoding: 16 zero bytes followed by
+/// the 48 bytes of the big-endian value.
+pub const BLS12_FP_LEN: usize = 64;
+/// Size of the BLS12-381 G1 addition input: p_x | p_y | q_x | q_y.
+pub const BLS12_G1_ADD_INPUT_LEN: usize = 4 * BLS12_FP_LEN;
+/// Addresses of the BLS12-381 precompiles (EIP-2537) after G1 addition: G1 and G2
+/// multiplication and MSM, G2 addition, the pairing check and the map-to-curve operations. Only
+/// G1 addition is implemented so far; the others need Fp2 and pairing chips over BLS12-381 to be
+/// proven, so calls to them are rejected.
+pub const UNSUPPORTED_BLS12_381_PRECOMPILES: RangeInclusive<u8> = 0x0c..=0x13;
+
+static KZG_SETTINGS: LazyLock<c_kzg::KzgSettings> = LazyLock::new(|| {
+    c_kzg::KzgSettings::load_trusted_setup(
+        &revm_primitives::kzg::G1_POINTS.0,
+        &revm_primitives::kzg::G2_POINTS.0,
+    )
+    .expect("failed to load trusted setup")
+});
+
+/// Versioned hash of a KZG commitment, i.e. sha256(commitment) with the first byte replaced.
+pub fn kzg_to_versioned_hash(commitment: &[u8]) -> H256 {
+    let mut hash: [u8; 32] = Sha256::digest(commitment).into();
+    hash[0] = VERSIONED_HASH_VERSION_KZG;
+    H256(hash)
+}
+
+/// Output of a successful point evaluation call.
+pub fn point_evaluation_output() -> Vec<u8> {
+    Word::from(FIELD_ELEMENTS_PER_BLOB)
+        .to_be_bytes()
+        .into_iter()
+        .chain(BLS_MODULUS.to_be_bytes())
+        .collect()
+}
+
+/// Point evaluation precompile of EIP-4844: verify that the blob committed to by `commitment`,
+/// whose versioned hash is `versioned_hash`, evaluates to `y` at `z`.
+fn execute_point_evaluation(input: &[u8], gas: u64) -> (Vec<u8>, u64, bool) {
+    let gas_cost = GasCost::PRECOMPILE_POINT_EVALUATION.as_u64();
+    if gas < gas_cost {
+        return (vec![], gas, true);
+    }
+    if input.len() != POINT_EVALUATION_INPUT_LEN {
+        log::trace!("point evaluation with invalid input len {}", input.len());
+        return (vec![], gas, false);
+    }
+
+    let aux_data = PointEvaluationAuxData::new(input, &[], &[]);
+    if kzg_to_versioned_hash(&aux_data.commitment) != aux_data.versioned_hash {
+        log::trace!("point evaluation with mismatched versioned hash");
+        return (vec![], gas, false);
+    }
+
+    let is_valid = (|| -> Result<bool, c_kzg::Error> {
+        c_kzg::KzgProof::verify_kzg_proof(
+            &c_kzg::Bytes48::from_bytes(&aux_data.commitment)?,
+            &c_kzg::Bytes32::from_bytes(&aux_data.z.to_be_bytes())?,
+            &c_kzg::Bytes32::from_bytes(&aux_data.y.to_be_bytes())?,
+            &c_kzg::Bytes48::from_bytes(&aux_data.proof)?,
+            &KZG_SETTINGS,
+        )
+    })()
+    .unwrap_or(false);
+    if !is_valid {
+        log::trace!("point evaluation with invalid kzg proof");
+        return (vec![], gas, false);
+    }
+
+    (point_evaluation_output(), gas_cost, false)
+}
+
 /// Addresses of the precompiled contracts.
+///
+/// Of the BLS12-381 precompiles (EIP-2537), only G1 addition is supported, with the address and
+/// gas of the revision implemented by the pinned revm-precompile. The others (0x0c to 0x13) can't
+/// be proven yet, see [`UNSUPPORTED_BLS12_381_PRECOMPILES`].
 #[derive(Copy, Clone, Debug, Eq, PartialEq, EnumIter)]
 pub enum PrecompileCalls {
     /// Elliptic Curve Recovery
@@ -62,6 +188,10 @@ pub enum PrecompileCalls {
     Bn128Pairing = 0x08,
     /// Compression function
     Blake2F = 0x09,
+    /// KZG point evaluation (EIP-4844)
+    PointEvaluation = 0x0A,
+    /// BLS12-381 G1 point addition (EIP-2537)
+    Bls12G1Add = 0x0B,
 }
 
 impl Default for PrecompileCalls {
@@ -102,7 +232,9 @@ impl From<u8> for PrecompileCalls {
             0x07 => Self::Bn128Mul,
             0x08 => Self::Bn128Pairing,
             0x09 => Self::Blake2F,
-            _ => unreachable!("precompile contracts only from 0x01 to 0x09"),
+            0x0A => Self::PointEvaluation,
+            0x0B => Self::Bls12G1Add,
+            _ => unreachable!("precompile contracts only from 0x01 to 0x0b"),
         }
     }
 }
@@ -120,6 +252,8 @@ impl PrecompileCalls {
             Self::Bn128Mul => GasCost::PRECOMPILE_BN256MUL,
             Self::Bn128Pairing => GasCost::PRECOMPILE_BN256PAIRING,
             Self::Blake2F => GasCost::PRECOMPILE_BLAKE2F,
+            Self::PointEvaluation => GasCost::PRECOMPILE_POINT_EVALUATION,
+            Self::Bls12G1Add => GasCost::PRECOMPILE_BLS12_G1ADD,
         }
     }
 
@@ -134,6 +268,8 @@ impl PrecompileCalls {
             Self::Ecrecover | Self::Bn128Add => Some(128),
             Self::Bn128Mul => Some(96),
             Self::Modexp => Some(MODEXP_INPUT_LIMIT),
+            Self::PointEvaluation => Some(POINT_EVALUATION_INPUT_LEN),
+            Self::Bls12G1Add => Some(BLS12_G1_ADD_INPUT_LEN),
             _ => None,
         }
     }
@@ -394,6 +530,51 @@ impl EcMulAuxData {
     }
 }
 
+/// Auxiliary data for point evaluation, i.e. p(z) = y for the blob polynomial p committed to by
+/// `commitment`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PointEvaluationAuxData {
+    /// Versioned hash of the commitment.
+    pub versioned_hash: H256,
+    /// Evaluation point.
+    pub z: Word,
+    /// Claimed evaluation.
+    pub y: Word,
+    /// KZG commitment to the blob, 48 bytes.
+    pub commitment: Vec<u8>,
+    /// KZG opening proof, 48 bytes.
+    pub proof: Vec<u8>,
+    /// sha256 digest of the commitment, which the versioned hash is derived from.
+    pub commitment_digest: H256,
+    /// Input bytes to the point evaluation call.
+    pub input_bytes: Vec<u8>,
+    /// Output bytes from the point evaluation call.
+    pub output_bytes: Vec<u8>,
+    /// Bytes returned back to the caller from the point evaluation call.
+    pub return_bytes: Vec<u8>,
+}
+
+impl PointEvaluationAuxData {
+    /// Create a new instance of point evaluation auxiliary data.
+    pub fn new(input: &[u8], output: &[u8], return_bytes: &[u8]) -> Self {
+        let mut resized_input = input.to_vec();
+        resized_input.resize(POINT_EVALUATION_INPUT_LEN, 0u8);
+        let commitment = resized_input[0x60..0x90].to_vec();
+
+        Self {
+            versioned_hash: H256::from_slice(&resized_input[0x00..0x20]),
+            z: Word::from_big_endian(&resized_input[0x20..0x40]),
+            y: Word::from_big_endian(&resized_input[0x40..0x60]),
+            commitment_digest: H256(Sha256::digest(&commitment).into()),
+            commitment,
+            proof: resized_input[0x90..0xc0].to_vec(),
+            input_bytes: input.to_vec(),
+            output_bytes: output.to_vec(),
+            return_bytes: return_bytes.to_vec(),
+        }
+    }
+}
+
 /// Auxiliary data for EcPairing.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct EcPairingAuxData(pub EcPairingOp);
@@ -447,6 +628,8 @@ pub enum PrecompileAuxData {
     EcMul(EcMulAuxData),
     /// EcPairing.
     EcPairing(Box<Result<EcPairingAuxData, EcPairingError>>),
+    /// PointEvaluation.
+    PointEvaluation(PointEvaluationAuxData),
 }
 
 impl Default for PrecompileAuxData {
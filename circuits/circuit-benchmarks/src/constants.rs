@@ -1,5 +1,7 @@
 // Prefixes to be used in benchmarks' log messages for
 // consistent logging and log parsing
 pub const SETUP_PREFIX: &str = "[Setup generation]";
+pub const KEYGEN_PREFIX: &str = "[Key generation]";
 pub const PROOFGEN_PREFIX: &str = "[Proof generation]";
 pub const PROOFVER_PREFIX: &str = "[Proof verification]";
+pub const PROOFSIZE_PREFIX: &str = "[Proof size]";
@@ -0,0 +1,211 @@
+//! ECC circuit benchmarks
+
+#[cfg(test)]
+mod tests {
+    use ark_std::{end_timer, start_timer};
+    use bus_mapping::circuit_input_builder::{
+        CircuitsParams, EcAddOp, EcMulOp, EcPairingOp, EcPairingPair, PrecompileEcParams,
+        PrecompileEvent, PrecompileEvents,
+    };
+    use eth_types::U256;
+    use halo2_proofs::{
+        arithmetic::Field,
+        halo2curves::{
+            bn256::{Bn256, Fr, G1Affine, G2Affine, G1, G2},
+            group::Group,
+        },
+        plonk::{create_proof, keygen_pk, keygen_vk, verify_proof},
+        poly::{
+            commitment::ParamsProver,
+            kzg::{
+                commitment::{KZGCommitmentScheme, ParamsKZG, ParamsVerifierKZG},
+                multiopen::{ProverSHPLONK, VerifierSHPLONK},
+                strategy::SingleStrategy,
+            },
+        },
+        transcript::{
+            Blake2bRead, Blake2bWrite, Challenge255, TranscriptReadBuffer, TranscriptWriterBuffer,
+        },
+    };
+    use rand::{RngCore, SeedableRng};
+    use rand_xorshift::XorShiftRng;
+    use std::env::var;
+    use zkevm_circuits::{ecc_circuit::EccCircuit, evm_circuit::witness::Block, util::SubCircuit};
+
+    #[cfg_attr(not(feature = "benches"), ignore)]
+    #[cfg_attr(not(feature = "print-trace"), allow(unused_variables))] // FIXME: remove this after ark-std upgrade
+    #[test]
+    fn bench_ecc_circuit_prover() {
+        let setup_prfx = crate::constants::SETUP_PREFIX;
+        let keygen_prfx = crate::constants::KEYGEN_PREFIX;
+        let proof_gen_prfx = crate::constants::PROOFGEN_PREFIX;
+        let proof_ver_prfx = crate::constants::PROOFVER_PREFIX;
+        let proof_size_prfx = crate::constants::PROOFSIZE_PREFIX;
+        // Unique string used by bench results module for parsing the result
+        const BENCHMARK_ID: &str = "ECC Circuit";
+
+        let degree: u32 = var("DEGREE")
+            .unwrap_or_else(|_| "20".to_string())
+            .parse()
+            .expect("Cannot parse DEGREE env var as u32");
+        let add_ops: usize = var("ADD_OPS")
+            .unwrap_or_else(|_| "50".to_string())
+            .parse()
+            .expect("Cannot parse ADD_OPS env var as usize");
+        let mul_ops: usize = var("MUL_OPS")
+            .unwrap_or_else(|_| "50".to_string())
+            .parse()
+            .expect("Cannot parse MUL_OPS env var as usize");
+        let pairing_ops: usize = var("PAIRING_OPS")
+            .unwrap_or_else(|_| "2".to_string())
+            .parse()
+            .expect("Cannot parse PAIRING_OPS env var as usize");
+
+        // Initialize the polynomial commitment parameters
+        let mut rng = XorShiftRng::from_seed([
+            0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06,
+            0xbc, 0xe5,
+        ]);
+
+        // Create the circuit
+        let mut events = Vec::new();
+        events.extend((0..add_ops).map(|_| PrecompileEvent::EcAdd(gen_ec_add_op(&mut rng))));
+        events.extend((0..mul_ops).map(|_| PrecompileEvent::EcMul(gen_ec_mul_op(&mut rng))));
+        events.extend(
+            (0..pairing_ops)
+                .map(|_| PrecompileEvent::EcPairing(Box::new(gen_ec_pairing_op(&mut rng)))),
+        );
+        let block = Block {
+            circuits_params: CircuitsParams {
+                max_ec_ops: PrecompileEcParams {
+                    ec_add: add_ops,
+                    ec_mul: mul_ops,
+                    ec_pairing: pairing_ops,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            precompile_events: PrecompileEvents { events },
+            ..Default::default()
+        };
+        let circuit = EccCircuit::<Fr, 9>::new_from_block(&block);
+        let instance = circuit.instance();
+        let instance_refs: Vec<&[Fr]> = instance.iter().map(|v| &v[..]).collect();
+
+        // Bench setup generation
+        let setup_message = format!("{BENCHMARK_ID} {setup_prfx} with degree = {degree}");
+        let start1 = start_timer!(|| setup_message);
+        let general_params = ParamsKZG::<Bn256>::setup(degree, &mut rng);
+        let verifier_params: ParamsVerifierKZG<Bn256> = general_params.verifier_params().clone();
+        end_timer!(start1);
+
+        // Bench key generation
+        let keygen_message = format!("{BENCHMARK_ID} {keygen_prfx} with {add_ops} add, {mul_ops} mul and {pairing_ops} pairing ops");
+        let start2 = start_timer!(|| keygen_message);
+        let vk = keygen_vk(&general_params, &circuit).expect("keygen_vk should not fail");
+        let pk = keygen_pk(&general_params, vk, &circuit).expect("keygen_pk should not fail");
+        end_timer!(start2);
+
+        // Create a proof
+        let mut transcript = Blake2bWrite::<_, G1Affine, Challenge255<_>>::init(vec![]);
+
+        // Bench proof generation time
+        let proof_message =
+            format!("{BENCHMARK_ID} {proof_gen_prfx} with degree = {degree}, {add_ops} add, {mul_ops} mul and {pairing_ops} pairing ops");
+        let start3 = start_timer!(|| proof_message);
+        create_proof::<
+            KZGCommitmentScheme<Bn256>,
+            ProverSHPLONK<'_, Bn256>,
+            Challenge255<G1Affine>,
+            XorShiftRng,
+            Blake2bWrite<Vec<u8>, G1Affine, Challenge255<G1Affine>>,
+            EccCircuit<Fr, 9>,
+        >(
+            &general_params,
+            &pk,
+            &[circuit],
+            &[&instance_refs],
+            rng,
+            &mut transcript,
+        )
+        .expect("proof generation should not fail");
+        let proof = transcript.finalize();
+        end_timer!(start3);
+        println!("{BENCHMARK_ID} {proof_size_prfx} {} bytes", proof.len());
+
+        // Bench verification time
+        let start4 = start_timer!(|| format!("{BENCHMARK_ID} {proof_ver_prfx}"));
+        let mut verifier_transcript = Blake2bRead::<_, G1Affine, Challenge255<_>>::init(&proof[..]);
+        let strategy = SingleStrategy::new(&general_params);
+
+        verify_proof::<
+            KZGCommitmentScheme<Bn256>,
+            VerifierSHPLONK<'_, Bn256>,
+            Challenge255<G1Affine>,
+            Blake2bRead<&[u8], G1Affine, Challenge255<G1Affine>>,
+            SingleStrategy<'_, Bn256>,
+        >(
+            &verifier_params,
+            pk.get_vk(),
+            strategy,
+            &[&instance_refs],
+            &mut verifier_transcript,
+        )
+        .expect("failed to verify bench circuit");
+        end_timer!(start4);
+    }
+
+    fn g1_coords(point: &G1Affine) -> (U256, U256) {
+        (
+            U256::from_little_endian(&point.x.to_bytes()),
+            U256::from_little_endian(&point.y.to_bytes()),
+        )
+    }
+
+    fn gen_ec_add_op(rng: &mut impl RngCore) -> EcAddOp {
+        let p = G1Affine::from(G1::random(&mut *rng));
+        let q = G1Affine::from(G1::random(&mut *rng));
+        EcAddOp {
+            p: g1_coords(&p),
+            q: g1_coords(&q),
+            r: Some(G1Affine::from(p + q)),
+        }
+    }
+
+    fn gen_ec_mul_op(rng: &mut impl RngCore) -> EcMulOp {
+        let p = G1Affine::from(G1::random(&mut *rng));
+        let s = Fr::random(&mut *rng);
+        EcMulOp {
+            p: g1_coords(&p),
+            s,
+            r: Some(G1Affine::from(p * s)),
+        }
+    }
+
+    // e(-a.G1, b.G2) * e(ab.G1, G2) * e(-c.G1, d.G2) * e(cd.G1, G2) == 1
+    fn gen_ec_pairing_op(rng: &mut impl RngCore) -> EcPairingOp {
+        let [a, b, c, d] = [(); 4].map(|_| Fr::random(&mut *rng));
+        EcPairingOp {
+            pairs: [
+                EcPairingPair::new(
+                    -G1Affine::from(G1::generator() * a),
+                    G2Affine::from(G2::generator() * b),
+                ),
+                EcPairingPair::new(
+                    G1Affine::from(G1::generator() * (a * b)),
+                    G2Affine::from(G2::generator()),
+                ),
+                EcPairingPair::new(
+                    -G1Affine::from(G1::generator() * c),
+                    G2Affine::from(G2::generator() * d),
+                ),
+                EcPairingPair::new(
+                    G1Affine::from(G1::generator() * (c * d)),
+                    G2Affine::from(G2::generator()),
+                ),
+            ],
+            output: U256::one(),
+            ..Default::default()
+        }
+    }
+}
@@ -32,6 +32,34 @@ pub mod copy_circuit;
 #[cfg(feature = "benches")]
 pub mod exp_circuit;
 
+#[cfg(test)]
+#[cfg(feature = "benches")]
+pub mod ecc_circuit;
+
+#[cfg(test)]
+#[cfg(feature = "benches")]
+pub mod sig_circuit;
+
+#[cfg(test)]
+#[cfg(feature = "benches")]
+pub mod modexp_circuit;
+
+#[cfg(test)]
+#[cfg(feature = "benches")]
+pub mod sha256_circuit;
+
+#[cfg(test)]
+#[cfg(feature = "benches")]
+pub mod mpt_circuit;
+
+#[cfg(test)]
+#[cfg(feature = "benches")]
+pub mod pairing_circuit;
+
+#[cfg(test)]
+#[cfg(feature = "benches")]
+pub mod fraud_circuit;
+
 #[cfg(test)]
 #[cfg(feature = "benches")]
 pub mod constants;
@@ -0,0 +1,158 @@
+//! ModExp circuit benchmarks
+
+#[cfg(test)]
+mod tests {
+    use ark_std::{end_timer, start_timer};
+    use bus_mapping::circuit_input_builder::{
+        BigModExp, CircuitsParams, PrecompileEvent, PrecompileEvents,
+    };
+    use eth_types::{Word, U512};
+    use halo2_proofs::{
+        halo2curves::bn256::{Bn256, Fr, G1Affine},
+        plonk::{create_proof, keygen_pk, keygen_vk, verify_proof},
+        poly::{
+            commitment::ParamsProver,
+            kzg::{
+                commitment::{KZGCommitmentScheme, ParamsKZG, ParamsVerifierKZG},
+                multiopen::{ProverSHPLONK, VerifierSHPLONK},
+                strategy::SingleStrategy,
+            },
+        },
+        transcript::{
+            Blake2bRead, Blake2bWrite, Challenge255, TranscriptReadBuffer, TranscriptWriterBuffer,
+        },
+    };
+    use rand::{Rng, SeedableRng};
+    use rand_xorshift::XorShiftRng;
+    use std::env::var;
+    use zkevm_circuits::{
+        evm_circuit::witness::Block, modexp_circuit::ModExpCircuit, util::SubCircuit,
+    };
+
+    #[cfg_attr(not(feature = "benches"), ignore)]
+    #[cfg_attr(not(feature = "print-trace"), allow(unused_variables))] // FIXME: remove this after ark-std upgrade
+    #[test]
+    fn bench_modexp_circuit_prover() {
+        let setup_prfx = crate::constants::SETUP_PREFIX;
+        let keygen_prfx = crate::constants::KEYGEN_PREFIX;
+        let proof_gen_prfx = crate::constants::PROOFGEN_PREFIX;
+        let proof_ver_prfx = crate::constants::PROOFVER_PREFIX;
+        let proof_size_prfx = crate::constants::PROOFSIZE_PREFIX;
+        // Unique string used by bench results module for parsing the result
+        const BENCHMARK_ID: &str = "ModExp Circuit";
+
+        let degree: u32 = var("DEGREE")
+            .unwrap_or_else(|_| "18".to_string())
+            .parse()
+            .expect("Cannot parse DEGREE env var as u32");
+        let num_ops: usize = var("NUM_OPS")
+            .unwrap_or_else(|_| "4".to_string())
+            .parse()
+            .expect("Cannot parse NUM_OPS env var as usize");
+
+        // Initialize the polynomial commitment parameters
+        let mut rng = XorShiftRng::from_seed([
+            0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06,
+            0xbc, 0xe5,
+        ]);
+
+        // Create the circuit
+        let events = (0..num_ops)
+            .map(|_| PrecompileEvent::ModExp(gen_modexp(&mut rng)))
+            .collect();
+        let block = Block {
+            circuits_params: CircuitsParams {
+                max_keccak_rows: (1 << degree) - 64,
+                ..Default::default()
+            },
+            precompile_events: PrecompileEvents { events },
+            ..Default::default()
+        };
+        let circuit = ModExpCircuit::<Fr>::new_from_block(&block);
+        let instance = circuit.instance();
+        let instance_refs: Vec<&[Fr]> = instance.iter().map(|v| &v[..]).collect();
+
+        // Bench setup generation
+        let setup_message = format!("{BENCHMARK_ID} {setup_prfx} with degree = {degree}");
+        let start1 = start_timer!(|| setup_message);
+        let general_params = ParamsKZG::<Bn256>::setup(degree, &mut rng);
+        let verifier_params: ParamsVerifierKZG<Bn256> = general_params.verifier_params().clone();
+        end_timer!(start1);
+
+        // Bench key generation
+        let keygen_message = format!("{BENCHMARK_ID} {keygen_prfx} with {num_ops} ops");
+        let start2 = start_timer!(|| keygen_message);
+        let vk = keygen_vk(&general_params, &circuit).expect("keygen_vk should not fail");
+        let pk = keygen_pk(&general_params, vk, &circuit).expect("keygen_pk should not fail");
+        end_timer!(start2);
+
+        // Create a proof
+        let mut transcript = Blake2bWrite::<_, G1Affine, Challenge255<_>>::init(vec![]);
+
+        // Bench proof generation time
+        let proof_message =
+            format!("{BENCHMARK_ID} {proof_gen_prfx} with degree = {degree}, {num_ops} ops");
+        let start3 = start_timer!(|| proof_message);
+        create_proof::<
+            KZGCommitmentScheme<Bn256>,
+            ProverSHPLONK<'_, Bn256>,
+            Challenge255<G1Affine>,
+            XorShiftRng,
+            Blake2bWrite<Vec<u8>, G1Affine, Challenge255<G1Affine>>,
+            ModExpCircuit<Fr>,
+        >(
+            &general_params,
+            &pk,
+            &[circuit],
+            &[&instance_refs],
+            rng,
+            &mut transcript,
+        )
+        .expect("proof generation should not fail");
+        let proof = transcript.finalize();
+        end_timer!(start3);
+        println!("{BENCHMARK_ID} {proof_size_prfx} {} bytes", proof.len());
+
+        // Bench verification time
+        let start4 = start_timer!(|| format!("{BENCHMARK_ID} {proof_ver_prfx}"));
+        let mut verifier_transcript = Blake2bRead::<_, G1Affine, Challenge255<_>>::init(&proof[..]);
+        let strategy = SingleStrategy::new(&general_params);
+
+        verify_proof::<
+            KZGCommitmentScheme<Bn256>,
+            VerifierSHPLONK<'_, Bn256>,
+            Challenge255<G1Affine>,
+            Blake2bRead<&[u8], G1Affine, Challenge255<G1Affine>>,
+            SingleStrategy<'_, Bn256>,
+        >(
+            &verifier_params,
+            pk.get_vk(),
+            strategy,
+            &[&instance_refs],
+            &mut verifier_transcript,
+        )
+        .expect("failed to verify bench circuit");
+        end_timer!(start4);
+    }
+
+    fn gen_modexp(rng: &mut impl Rng) -> BigModExp {
+        let [base, exponent, modulus] =
+            [(); 3].map(|_| Word::from_big_endian(&rng.gen::<[u8; 32]>()));
+        let mul_mod =
+            |a: Word, b: Word| Word::try_from(a.full_mul(b) % U512::from(modulus)).unwrap();
+        let mut result = Word::one() % modulus;
+        let mut power = base % modulus;
+        for i in 0..exponent.bits() {
+            if exponent.bit(i) {
+                result = mul_mod(result, power);
+            }
+            power = mul_mod(power, power);
+        }
+        BigModExp {
+            base,
+            exponent,
+            modulus,
+            result,
+        }
+    }
+}
@@ -0,0 +1,168 @@
+//! MPT circuit benchmarks
+
+#[cfg(test)]
+mod tests {
+    use ark_std::{end_timer, start_timer};
+    use bus_mapping::{circuit_input_builder::CircuitsParams, mock::BlockData};
+    use eth_types::{geth_types::GethData, Bytecode, Word};
+    use halo2_proofs::{
+        halo2curves::bn256::{Bn256, Fr, G1Affine},
+        plonk::{create_proof, keygen_pk, keygen_vk, verify_proof},
+        poly::{
+            commitment::ParamsProver,
+            kzg::{
+                commitment::{KZGCommitmentScheme, ParamsKZG, ParamsVerifierKZG},
+                multiopen::{ProverSHPLONK, VerifierSHPLONK},
+                strategy::SingleStrategy,
+            },
+        },
+        transcript::{
+            Blake2bRead, Blake2bWrite, Challenge255, TranscriptReadBuffer, TranscriptWriterBuffer,
+        },
+    };
+    use mock::test_ctx::{helpers::*, TestContext};
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+    use std::env::var;
+    use zkevm_circuits::{
+        evm_circuit::witness::{block_convert, Block},
+        mpt_circuit::MptCircuit,
+        util::SubCircuit,
+    };
+
+    #[cfg_attr(not(feature = "benches"), ignore)]
+    #[cfg_attr(not(feature = "print-trace"), allow(unused_variables))] // FIXME: remove this after ark-std upgrade
+    #[test]
+    fn bench_mpt_circuit_prover() {
+        let setup_prfx = crate::constants::SETUP_PREFIX;
+        let keygen_prfx = crate::constants::KEYGEN_PREFIX;
+        let proof_gen_prfx = crate::constants::PROOFGEN_PREFIX;
+        let proof_ver_prfx = crate::constants::PROOFVER_PREFIX;
+        let proof_size_prfx = crate::constants::PROOFSIZE_PREFIX;
+        // Unique string used by bench results module for parsing the result
+        const BENCHMARK_ID: &str = "MPT Circuit";
+
+        let degree: u32 = var("DEGREE")
+            .unwrap_or_else(|_| "18".to_string())
+            .parse()
+            .expect("Cannot parse DEGREE env var as u32");
+        let num_ops: usize = var("NUM_OPS")
+            .unwrap_or_else(|_| "50".to_string())
+            .parse()
+            .expect("Cannot parse NUM_OPS env var as usize");
+
+        // Initialize the polynomial commitment parameters
+        let mut rng = XorShiftRng::from_seed([
+            0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06,
+            0xbc, 0xe5,
+        ]);
+
+        // Create the circuit
+        let block = generate_storage_writes_block(degree, num_ops);
+        let circuit = MptCircuit::<Fr>::new_from_block(&block);
+        let instance = circuit.instance();
+        let instance_refs: Vec<&[Fr]> = instance.iter().map(|v| &v[..]).collect();
+
+        // Bench setup generation
+        let setup_message = format!("{BENCHMARK_ID} {setup_prfx} with degree = {degree}");
+        let start1 = start_timer!(|| setup_message);
+        let general_params = ParamsKZG::<Bn256>::setup(degree, &mut rng);
+        let verifier_params: ParamsVerifierKZG<Bn256> = general_params.verifier_params().clone();
+        end_timer!(start1);
+
+        // Bench key generation
+        let keygen_message = format!("{BENCHMARK_ID} {keygen_prfx} with {num_ops} storage writes");
+        let start2 = start_timer!(|| keygen_message);
+        let vk = keygen_vk(&general_params, &circuit).expect("keygen_vk should not fail");
+        let pk = keygen_pk(&general_params, vk, &circuit).expect("keygen_pk should not fail");
+        end_timer!(start2);
+
+        // Create a proof
+        let mut transcript = Blake2bWrite::<_, G1Affine, Challenge255<_>>::init(vec![]);
+
+        // Bench proof generation time
+        let proof_message = format!(
+            "{BENCHMARK_ID} {proof_gen_prfx} with degree = {degree}, {num_ops} storage writes"
+        );
+        let start3 = start_timer!(|| proof_message);
+        create_proof::<
+            KZGCommitmentScheme<Bn256>,
+            ProverSHPLONK<'_, Bn256>,
+            Challenge255<G1Affine>,
+            XorShiftRng,
+            Blake2bWrite<Vec<u8>, G1Affine, Challenge255<G1Affine>>,
+            MptCircuit<Fr>,
+        >(
+            &general_params,
+            &pk,
+            &[circuit],
+            &[&instance_refs],
+            rng,
+            &mut transcript,
+        )
+        .expect("proof generation should not fail");
+        let proof = transcript.finalize();
+        end_timer!(start3);
+        println!("{BENCHMARK_ID} {proof_size_prfx} {} bytes", proof.len());
+
+        // Bench verification time
+        let start4 = start_timer!(|| format!("{BENCHMARK_ID} {proof_ver_prfx}"));
+        let mut verifier_transcript = Blake2bRead::<_, G1Affine, Challenge255<_>>::init(&proof[..]);
+        let strategy = SingleStrategy::new(&general_params);
+
+        verify_proof::<
+            KZGCommitmentScheme<Bn256>,
+            VerifierSHPLONK<'_, Bn256>,
+            Challenge255<G1Affine>,
+            Blake2bRead<&[u8], G1Affine, Challenge255<G1Affine>>,
+            SingleStrategy<'_, Bn256>,
+        >(
+            &verifier_params,
+            pk.get_vk(),
+            strategy,
+            &[&instance_refs],
+            &mut verifier_transcript,
+        )
+        .expect("failed to verify bench circuit");
+        end_timer!(start4);
+    }
+
+    fn generate_storage_writes_block(degree: u32, num_writes: usize) -> Block {
+        let mut code = Bytecode::default();
+        for slot in 0..num_writes {
+            code.op_sstore(Word::from(slot), Word::from(slot + 1));
+        }
+        code.op_stop();
+
+        let test_ctx = TestContext::<2, 1>::new(
+            None,
+            account_0_code_account_1_no_code(code),
+            |mut txs, accs| {
+                txs[0]
+                    .from(accs[1].address)
+                    .to(accs[0].address)
+                    .gas((1e16 as u64).into());
+            },
+            |block, _txs| block.number(0xcafeu64),
+        )
+        .unwrap();
+        let block: GethData = test_ctx.into();
+        let mut builder = BlockData::new_from_geth_data_with_params(
+            block.clone(),
+            CircuitsParams {
+                max_rws: 1 << (degree - 1),
+                max_mpt_rows: (1 << degree) - 64,
+                ..Default::default()
+            },
+        )
+        .new_circuit_input_builder();
+        builder
+            .handle_block(&block.eth_block, &block.geth_traces)
+            .unwrap();
+        let mut block = block_convert(&builder.block, &builder.code_db).unwrap();
+        // the mock block has no zktrie state, so the writes are replayed on a trie holding the
+        // old values
+        block.apply_mpt_updates_on_mock_prestate();
+        block
+    }
+}
@@ -0,0 +1,184 @@
+//! OABE pairing circuit benchmarks
+
+#[cfg(test)]
+mod tests {
+    use ark_std::{end_timer, start_timer};
+    use eth_types::U256;
+    use halo2_proofs::{
+        arithmetic::Field,
+        halo2curves::{
+            bn256::{pairing, Bn256, Fr, G1Affine, G2Affine, G1, G2},
+            group::Group,
+        },
+        plonk::{create_proof, keygen_pk, keygen_vk, verify_proof},
+        poly::{
+            commitment::ParamsProver,
+            kzg::{
+                commitment::{KZGCommitmentScheme, ParamsKZG, ParamsVerifierKZG},
+                multiopen::{ProverSHPLONK, VerifierSHPLONK},
+                strategy::SingleStrategy,
+            },
+        },
+        transcript::{
+            Blake2bRead, Blake2bWrite, Challenge255, TranscriptReadBuffer, TranscriptWriterBuffer,
+        },
+    };
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+    use std::{env::var, marker::PhantomData};
+    use zkevm_circuits::{
+        ciphertext_circuit::LsssPolicy, pairing_circuit::MyEccCircuit, util::SubCircuit,
+    };
+
+    #[cfg_attr(not(feature = "benches"), ignore)]
+    #[cfg_attr(not(feature = "print-trace"), allow(unused_variables))] // FIXME: remove this after ark-std upgrade
+    #[test]
+    fn bench_pairing_circuit_prover() {
+        let setup_prfx = crate::constants::SETUP_PREFIX;
+        let keygen_prfx = crate::constants::KEYGEN_PREFIX;
+        let proof_gen_prfx = crate::constants::PROOFGEN_PREFIX;
+        let proof_ver_prfx = crate::constants::PROOFVER_PREFIX;
+        let proof_size_prfx = crate::constants::PROOFSIZE_PREFIX;
+        // Unique string used by bench results module for parsing the result
+        const BENCHMARK_ID: &str = "Pairing Circuit";
+
+        let degree: u32 = var("DEGREE")
+            .unwrap_or_else(|_| "22".to_string())
+            .parse()
+            .expect("Cannot parse DEGREE env var as u32");
+        let num_attributes: usize = var("NUM_ATTRIBUTES")
+            .unwrap_or_else(|_| "2".to_string())
+            .parse()
+            .expect("Cannot parse NUM_ATTRIBUTES env var as usize");
+
+        // Initialize the polynomial commitment parameters
+        let mut rng = XorShiftRng::from_seed([
+            0x59, 0x62, 0xbe, 0x5d, 0x76, 0x3d, 0x31, 0x8d, 0x17, 0xdb, 0x37, 0x32, 0x54, 0x06,
+            0xbc, 0xe5,
+        ]);
+
+        // Create the circuit, over a policy requiring all the attributes, where every w_i is 1
+        let attributes: Vec<G1Affine> = (0..num_attributes)
+            .map(|_| G1Affine::from(G1::random(&mut rng)))
+            .collect();
+        let ws = vec![Fr::one(); num_attributes];
+        let c_points: Vec<G1Affine> = (0..num_attributes)
+            .map(|_| G1Affine::from(G1::random(&mut rng)))
+            .collect();
+        let d_points: Vec<G1Affine> = (0..num_attributes)
+            .map(|_| G1Affine::from(G1::random(&mut rng)))
+            .collect();
+        let r_points: Vec<G2Affine> = (0..num_attributes)
+            .map(|_| G2Affine::from(G2::random(&mut rng)))
+            .collect();
+        let l_point = G2Affine::from(G2::random(&mut rng));
+        let c_p_point = G1Affine::from(G1::random(&mut rng));
+        let r_p_point = G2Affine::from(G2::random(&mut rng));
+        // T = e(C', R') * prod_i e(c_i^w_i, L) * e(d_i^w_i, R_i)
+        let ti = ws
+            .iter()
+            .zip(c_points.iter().zip(&d_points).zip(&r_points))
+            .fold(pairing(&c_p_point, &r_p_point), |acc, (w, ((c, d), r))| {
+                acc + pairing(&G1Affine::from(c * w), &l_point) + pairing(&G1Affine::from(d * w), r)
+            });
+        let circuit = MyEccCircuit::<Fr, 9> {
+            p1s: c_points
+                .iter()
+                .zip(&d_points)
+                .flat_map(|(c, d)| [g1_coords(c), g1_coords(d)])
+                .collect(),
+            p2s: r_points.iter().map(g2_coords).collect(),
+            ws,
+            policy: LsssPolicy::and(attributes.clone()),
+            rows: (0..num_attributes).collect(),
+            key_attributes: attributes,
+            p4: g2_coords(&l_point),
+            ct: g1_coords(&c_p_point),
+            tk: g2_coords(&r_p_point),
+            ti: ti.0,
+            _marker: PhantomData,
+        };
+        let instance = circuit.instance();
+        let instance_refs: Vec<&[Fr]> = instance.iter().map(|v| &v[..]).collect();
+
+        // Bench setup generation
+        let setup_message = format!("{BENCHMARK_ID} {setup_prfx} with degree = {degree}");
+        let start1 = start_timer!(|| setup_message);
+        let general_params = ParamsKZG::<Bn256>::setup(degree, &mut rng);
+        let verifier_params: ParamsVerifierKZG<Bn256> = general_params.verifier_params().clone();
+        end_timer!(start1);
+
+        // Bench key generation
+        let keygen_message =
+            format!("{BENCHMARK_ID} {keygen_prfx} with {num_attributes} attributes");
+        let start2 = start_timer!(|| keygen_message);
+        let vk = keygen_vk(&general_params, &circuit).expect("keygen_vk should not fail");
+        let pk = keygen_pk(&general_params, vk, &circuit).expect("keygen_pk should not fail");
+        end_timer!(start2);
+
+        // Create a proof
+        let mut transcript = Blake2bWrite::<_, G1Affine, Challenge255<_>>::init(vec![]);
+
+        // Bench proof generation time
+        let proof_message = format!(
+            "{BENCHMARK_ID} {proof_gen_prfx} with degree = {degree}, {num_attributes} attributes"
+        );
+        let start3 = start_timer!(|| proof_message);
+        create_proof::<
+            KZGCommitmentScheme<Bn256>,
+            ProverSHPLONK<'_, Bn256>,
+            Challenge255<G1Affine>,
+            XorShiftRng,
+            Blake2bWrite<Vec<u8>, G1Affine, Challenge255<G1Affine>>,
+            MyEccCircuit<Fr, 9>,
+        >(
+            &general_params,
+            &pk,
+            &[circuit],
+            &[&instance_refs],
+            rng,
+            &mut transcript,
+        )
+        .expect("proof generation should not fail");
+        let proof = transcript.finalize();
+        end_timer!(start3);
+        println!("{BENCHMARK_ID} {proof_size_prfx} {} bytes", proof.len());
+
+        // Bench verification time
+        let start4 = start_timer!(|| format!("{BENCHMARK_ID} {proof_ver_prfx}"));
+        let mut verifier_transcript = Blake2bRead::<_, G1Affine, Challenge255<_>>::init(&proof[..]);
+        let strategy = SingleStrategy::new(&general_params);
+
+        verify_proof::<
+            KZGCommitmentScheme<Bn256>,
+            VerifierSHPLONK<'_, Bn256>,
+            Challenge255<G1Affine>,
+            Blake2bRead<&[u8], G1Affine, Challenge255<G1Affine>>,
+            SingleStrategy<'_, Bn256>,
+        >(
+            &verifier_params,
+            pk.get_vk(),
+            strategy,
+            &[&instance_refs],
+            &mut verifier_transcript,
+        )
+        .expect("failed to verify bench circuit");
+        end_timer!(start4);
+    }
+
+    fn g1_coords(point: &G1Affine) -> (U256, U256) {
+        (
+            U256::from_little_endian(&point.x.to_bytes()),
+            U256::from_little_endian(&point.y.to_bytes()),
+        )
+    }
+
+    fn g2_coords(point: &G2Affine) -> (U256, U256, U256, U256) {
+        (
+            U256::from_little_endian(&point.x.c1.to_bytes()),
+            U256::from_little_endian(&point.x.c0.to_bytes()),
+            U256::from_little_endian(&point.y.c1.to_bytes()),
+            U256::from_little_endian(&point.y.c0.to_bytes()),
+        )
+    }
+}
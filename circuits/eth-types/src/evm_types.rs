@@ -4,6 +4,7 @@ use serde::{Deserialize, Serialize};
 use std::{fmt, marker::ConstParamTy};
 
 pub mod block_utils;
+pub mod delegation;
 pub mod gas_utils;
 pub mod memory;
 pub mod opcode_ids;
@@ -196,10 +197,19 @@ impl GasCost {
     pub const PRECOMPILE_MODEXP_MIN: Self = Self(200);
     /// Base gas cost for precompile call: BLAKE2F
     pub const PRECOMPILE_BLAKE2F: Self = Self(0);
+    /// Gas cost for precompile call: KZG point evaluation (EIP-4844)
+    pub const PRECOMPILE_POINT_EVALUATION: Self = Self(50_000);
+    /// Gas cost for precompile call: BLS12-381 G1 addition (EIP-2537)
+    pub const PRECOMPILE_BLS12_G1ADD: Self = Self(500);
     /// Gas cost per address in tx access list (EIP 2930)
     pub const ACCESS_LIST_PER_ADDRESS: Self = Self(2400);
     /// Gas cost per storage key in tx access list (EIP 2930)
     pub const ACCESS_LIST_PER_STORAGE_KEY: Self = Self(1900);
+    /// Gas cost per authorization in a set-code tx (EIP 7702)
+    pub const PER_EMPTY_ACCOUNT: Self = Self(25000);
+    /// Base cost per authorization in a set-code tx. The difference to
+    /// `PER_EMPTY_ACCOUNT` is refunded if the authority already exists (EIP 7702)
+    pub const PER_AUTH_BASE: Self = Self(12500);
 }
 
 impl GasCost {
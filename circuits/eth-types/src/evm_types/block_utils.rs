@@ -6,6 +6,15 @@ use ethers_core::utils::keccak256;
 /// Maximum range of previous blocks allowed inside BLOCKHASH opcode
 pub const NUM_PREV_BLOCK_ALLOWED: u64 = 256;
 
+/// Minimum blob base fee, as in EIP-4844
+pub const MIN_BLOB_BASE_FEE: u64 = 1;
+
+/// Controls the maximum rate of change of the blob base fee, as in EIP-4844
+pub const BLOB_BASE_FEE_UPDATE_FRACTION: u64 = 3338477;
+
+/// Blob gas consumed by each blob of a tx, as in EIP-4844
+pub const GAS_PER_BLOB: u64 = 1 << 17;
+
 /// Calculate block hash by chain ID and block number (only for scroll).
 /// Return a pair of input and output.
 pub fn calculate_block_hash(chain_id: u64, block_number: U256) -> (Vec<u8>, U256) {
@@ -27,3 +36,25 @@ pub fn is_valid_block_number(block_number: U256, current_block_number: U256) ->
                 .checked_sub(NUM_PREV_BLOCK_ALLOWED.into())
                 .unwrap_or_default()
 }
+
+/// Calculate the blob base fee (returned by BLOBBASEFEE) from the excess blob gas of a block.
+pub fn calc_blob_base_fee(excess_blob_gas: u64) -> U256 {
+    fake_exponential(
+        MIN_BLOB_BASE_FEE.into(),
+        excess_blob_gas.into(),
+        BLOB_BASE_FEE_UPDATE_FRACTION.into(),
+    )
+}
+
+/// Approximate `factor * e ** (numerator / denominator)` using a Taylor expansion, as in EIP-4844.
+fn fake_exponential(factor: U256, numerator: U256, denominator: U256) -> U256 {
+    let mut i = U256::one();
+    let mut output = U256::zero();
+    let mut numerator_accum = factor * denominator;
+    while !numerator_accum.is_zero() {
+        output += numerator_accum;
+        numerator_accum = numerator_accum * numerator / (denominator * i);
+        i += U256::one();
+    }
+    output / denominator
+}
@@ -0,0 +1,35 @@
+//! Delegation designators of EIP-7702 set-code transactions.
+
+use crate::Address;
+
+/// Prefix of the code installed in an authority by an EIP-7702 authorization.
+pub const DELEGATION_PREFIX: [u8; 3] = [0xef, 0x01, 0x00];
+/// Size of a delegation designator, i.e. the prefix followed by the delegate address.
+pub const DELEGATION_CODE_SIZE: usize = 23;
+
+/// Return the delegation designator `0xef0100 || address` pointing to `address`.
+pub fn delegation_designator(address: &Address) -> Vec<u8> {
+    [&DELEGATION_PREFIX[..], address.as_bytes()].concat()
+}
+
+/// Return the delegate address if `code` is a delegation designator.
+pub fn parse_delegation(code: &[u8]) -> Option<Address> {
+    (code.len() == DELEGATION_CODE_SIZE && code.starts_with(&DELEGATION_PREFIX))
+        .then(|| Address::from_slice(&code[DELEGATION_PREFIX.len()..]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn delegation_designator_roundtrip() {
+        let address = Address::repeat_byte(0xab);
+        let code = delegation_designator(&address);
+        assert_eq!(code.len(), DELEGATION_CODE_SIZE);
+        assert_eq!(parse_delegation(&code), Some(address));
+        // contract code which is not a designator
+        assert_eq!(parse_delegation(&code[..22]), None);
+        assert_eq!(parse_delegation(&[0x60; DELEGATION_CODE_SIZE]), None);
+    }
+}
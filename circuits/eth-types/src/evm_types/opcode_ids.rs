@@ -284,6 +284,10 @@ pub enum OpcodeId {
     SELFBALANCE,
     /// `BASEFEE`
     BASEFEE,
+    /// `BLOBHASH`
+    BLOBHASH,
+    /// `BLOBBASEFEE`
+    BLOBBASEFEE,
     /// `SLOAD`
     SLOAD,
     /// `SSTORE`
@@ -516,6 +520,8 @@ impl OpcodeId {
             OpcodeId::CHAINID => 0x46u8,
             OpcodeId::SELFBALANCE => 0x47u8,
             OpcodeId::BASEFEE => 0x48u8,
+            OpcodeId::BLOBHASH => 0x49u8,
+            OpcodeId::BLOBBASEFEE => 0x4au8,
             OpcodeId::SLOAD => 0x54u8,
             OpcodeId::SSTORE => 0x55u8,
             OpcodeId::GAS => 0x5au8,
@@ -596,6 +602,8 @@ impl OpcodeId {
             OpcodeId::CHAINID => GasCost::QUICK,
             OpcodeId::SELFBALANCE => GasCost::FAST,
             OpcodeId::BASEFEE => GasCost::QUICK,
+            OpcodeId::BLOBHASH => GasCost::FASTEST,
+            OpcodeId::BLOBBASEFEE => GasCost::QUICK,
             OpcodeId::POP => GasCost::QUICK,
             OpcodeId::MLOAD => GasCost::FASTEST,
             OpcodeId::MSTORE => GasCost::FASTEST,
@@ -763,6 +771,8 @@ impl OpcodeId {
             OpcodeId::CHAINID => (1, 1024),
             OpcodeId::SELFBALANCE => (1, 1024),
             OpcodeId::BASEFEE => (1, 1024),
+            OpcodeId::BLOBHASH => (0, 1023),
+            OpcodeId::BLOBBASEFEE => (1, 1024),
             OpcodeId::POP => (0, 1023),
             OpcodeId::MLOAD => (0, 1023),
             OpcodeId::MSTORE => (0, 1022),
@@ -1072,6 +1082,8 @@ impl From<u8> for OpcodeId {
             0x46u8 => OpcodeId::CHAINID,
             0x47u8 => OpcodeId::SELFBALANCE,
             0x48u8 => OpcodeId::BASEFEE,
+            0x49u8 => OpcodeId::BLOBHASH,
+            0x4au8 => OpcodeId::BLOBBASEFEE,
             0x54u8 => OpcodeId::SLOAD,
             0x55u8 => OpcodeId::SSTORE,
             0x5au8 => OpcodeId::GAS,
@@ -1249,8 +1261,8 @@ impl FromStr for OpcodeId {
             "CHAINID" => OpcodeId::CHAINID,
             "opcode 0x48 not defined" => OpcodeId::BASEFEE,
             "BASEFEE" => OpcodeId::BASEFEE,
-            "BLOBHASH" => OpcodeId::INVALID(0x49),
-            "BLOBBASEFEE" => OpcodeId::INVALID(0x4a),
+            "BLOBHASH" => OpcodeId::BLOBHASH,
+            "BLOBBASEFEE" => OpcodeId::BLOBBASEFEE,
             "opcode 0x5c not defined" => OpcodeId::TLOAD,
             "TLOAD" => OpcodeId::TLOAD,
             "opcode 0x5d not defined" => OpcodeId::TSTORE,
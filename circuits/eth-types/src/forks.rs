@@ -7,6 +7,23 @@ pub enum HardforkId {
     Curie = 3,
 }
 
+impl HardforkId {
+    /// Name of the Ethereum fork whose EVM rules the hardfork follows, as used by the test
+    /// fixtures. Blob transactions and opcodes are not supported by any Scroll hardfork.
+    pub fn ethereum_fork(&self) -> &'static str {
+        match self {
+            HardforkId::Curie => "Cancun",
+        }
+    }
+}
+
+/// Address of the last precompiled contract. Scroll hardforks follow Cancun without blobs, so
+/// they stop at blake2f (0x09). Otherwise the Cancun point evaluation precompile (EIP-4844) at
+/// 0x0a and the BLS12-381 G1 addition (EIP-2537) at 0x0b are enabled as well. The point
+/// evaluation precompile and the other BLS12-381 precompiles are not supported yet: calls to them
+/// are rejected when building the circuit inputs.
+pub const LAST_PRECOMPILE_ADDRESS: u8 = if cfg!(feature = "scroll") { 0x09 } else { 0x0b };
+
 /// Scroll devnet chain id
 pub const SCROLL_DEVNET_CHAIN_ID: u64 = 222222;
 /// Scroll testnet chain id
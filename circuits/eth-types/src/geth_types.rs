@@ -7,14 +7,20 @@ use crate::{
     AccessList, Address, Block, Bytes, Error, GethExecTrace, Hash, ToBigEndian, ToLittleEndian,
     Word, U64,
 };
-use ethers_core::types::{
-    transaction::eip2718::TypedTransaction, Eip1559TransactionRequest, Eip2930TransactionRequest,
-    NameOrAddress, TransactionRequest, H256,
+use ethers_core::{
+    types::{
+        transaction::eip2718::TypedTransaction, Eip1559TransactionRequest,
+        Eip2930TransactionRequest, NameOrAddress, OtherFields, TransactionRequest, H256,
+    },
+    utils::rlp::{Encodable, RlpStream},
+};
+use halo2curves::{
+    group::{ff::PrimeField, prime::PrimeCurveAffine},
+    secp256k1::{Fq, Secp256k1Affine},
 };
-use halo2curves::{group::ff::PrimeField, secp256k1::Fq};
 use num::Integer;
 use num_bigint::BigUint;
-use serde::{Serialize, Serializer};
+use serde::{Deserialize, Serialize, Serializer};
 use serde_with::serde_as;
 use sha3::{Digest, Keccak256};
 use std::collections::HashMap;
@@ -34,6 +40,10 @@ pub enum TxType {
     Eip2930,
     /// L1 Message tx
     L1Msg,
+    /// EIP 4844 tx
+    Eip4844,
+    /// EIP 7702 tx
+    Eip7702,
 }
 
 impl From<TxType> for usize {
@@ -74,11 +84,23 @@ impl TxType {
         matches!(*self, TxType::Eip2930)
     }
 
+    /// If this type is Eip4844 or not
+    pub fn is_eip4844(&self) -> bool {
+        matches!(*self, TxType::Eip4844)
+    }
+
+    /// If this type is Eip7702 or not
+    pub fn is_eip7702(&self) -> bool {
+        matches!(*self, TxType::Eip7702)
+    }
+
     /// Get the type of transaction
     pub fn get_tx_type(tx: &crate::Transaction) -> Self {
         match tx.transaction_type {
             Some(x) if x == U64::from(1) => Self::Eip2930,
             Some(x) if x == U64::from(2) => Self::Eip1559,
+            Some(x) if x == U64::from(3) => Self::Eip4844,
+            Some(x) if x == U64::from(4) => Self::Eip7702,
             Some(x) if x == U64::from(0x7e) => Self::L1Msg,
             _ => {
                 if cfg!(feature = "scroll") {
@@ -116,6 +138,14 @@ impl TxType {
                 assert!(v <= 1);
                 v
             }
+            TxType::Eip4844 => {
+                assert!(v <= 1);
+                v
+            }
+            TxType::Eip7702 => {
+                assert!(v <= 1);
+                v
+            }
             TxType::L1Msg => {
                 unreachable!("L1 msg does not have signature")
             }
@@ -155,6 +185,183 @@ pub fn get_rlp_unsigned(tx: &crate::Transaction) -> Vec<u8> {
             // L1 msg does not have signature
             vec![]
         }
+        TxType::Eip4844 => eip4844_rlp(tx, false),
+        TxType::Eip7702 => eip7702_rlp(tx, false),
+    }
+}
+
+/// Get the RLP bytes of the signed tx
+pub fn get_rlp_signed(tx: &crate::Transaction) -> Vec<u8> {
+    match TxType::get_tx_type(tx) {
+        // ethers-rs encodes unknown typed txs as legacy ones
+        TxType::Eip4844 => eip4844_rlp(tx, true),
+        TxType::Eip7702 => eip7702_rlp(tx, true),
+        _ => tx.rlp().to_vec(),
+    }
+}
+
+/// Get the blob fields of an EIP 4844 tx, i.e. `max_fee_per_blob_gas` and
+/// `blob_versioned_hashes`. ethers-rs keeps them in the extra fields of the tx.
+pub fn get_blob_fields(tx: &crate::Transaction) -> (Word, Vec<H256>) {
+    let max_fee_per_blob_gas = tx
+        .other
+        .get("maxFeePerBlobGas")
+        .and_then(|v| serde_json::from_value(v.clone()).ok())
+        .unwrap_or_default();
+    let blob_versioned_hashes = tx
+        .other
+        .get("blobVersionedHashes")
+        .and_then(|v| serde_json::from_value(v.clone()).ok())
+        .unwrap_or_default();
+    (max_fee_per_blob_gas, blob_versioned_hashes)
+}
+
+/// `0x03 || rlp([chain_id, nonce, max_priority_fee_per_gas, max_fee_per_gas, gas_limit, to,
+/// value, data, access_list, max_fee_per_blob_gas, blob_versioned_hashes])`, followed by the
+/// signature fields `[v, r, s]` in the list if `signed`.
+fn eip4844_rlp(tx: &crate::Transaction, signed: bool) -> Vec<u8> {
+    let (max_fee_per_blob_gas, blob_versioned_hashes) = get_blob_fields(tx);
+    let mut stream = RlpStream::new();
+    stream.begin_list(if signed { 14 } else { 11 });
+    stream.append(&tx.chain_id.unwrap_or_default());
+    stream.append(&tx.nonce);
+    stream.append(&tx.max_priority_fee_per_gas.unwrap_or_default());
+    stream.append(&tx.max_fee_per_gas.unwrap_or_default());
+    stream.append(&tx.gas);
+    // blob txs can not create contracts
+    stream.append(&tx.to.unwrap_or_default());
+    stream.append(&tx.value);
+    stream.append(&tx.input);
+    stream.append(&tx.access_list.clone().unwrap_or_default());
+    stream.append(&max_fee_per_blob_gas);
+    stream.append_list(&blob_versioned_hashes);
+    if signed {
+        stream.append(&tx.v);
+        stream.append(&tx.r);
+        stream.append(&tx.s);
+    }
+    [&[0x03], stream.as_raw()].concat()
+}
+
+/// Get the authorization list of an EIP 7702 tx, which ethers-rs keeps in the extra fields of
+/// the tx.
+pub fn get_authorization_list(tx: &crate::Transaction) -> Vec<SetCodeAuthorization> {
+    tx.other
+        .get("authorizationList")
+        .and_then(|v| serde_json::from_value(v.clone()).ok())
+        .unwrap_or_default()
+}
+
+/// `0x04 || rlp([chain_id, nonce, max_priority_fee_per_gas, max_fee_per_gas, gas_limit, to,
+/// value, data, access_list, authorization_list])`, followed by the signature fields `[v, r,
+/// s]` in the list if `signed`.
+fn eip7702_rlp(tx: &crate::Transaction, signed: bool) -> Vec<u8> {
+    let mut stream = RlpStream::new();
+    stream.begin_list(if signed { 13 } else { 10 });
+    stream.append(&tx.chain_id.unwrap_or_default());
+    stream.append(&tx.nonce);
+    stream.append(&tx.max_priority_fee_per_gas.unwrap_or_default());
+    stream.append(&tx.max_fee_per_gas.unwrap_or_default());
+    stream.append(&tx.gas);
+    // set-code txs can not create contracts
+    stream.append(&tx.to.unwrap_or_default());
+    stream.append(&tx.value);
+    stream.append(&tx.input);
+    stream.append(&tx.access_list.clone().unwrap_or_default());
+    stream.append_list(&get_authorization_list(tx));
+    if signed {
+        stream.append(&tx.v);
+        stream.append(&tx.r);
+        stream.append(&tx.s);
+    }
+    [&[0x04], stream.as_raw()].concat()
+}
+
+/// Authorization tuple of an EIP 7702 tx, which installs a delegation designator to `address`
+/// in the code of the signer (the authority).
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetCodeAuthorization {
+    /// Chain id the authorization is valid on, 0 for any chain
+    pub chain_id: Word,
+    /// Address of the delegate, or zero to clear the delegation
+    pub address: Address,
+    /// Nonce of the authority
+    pub nonce: U64,
+    /// "y_parity" value of the signature
+    pub y_parity: U64,
+    /// "r" value of the signature
+    pub r: Word,
+    /// "s" value of the signature
+    pub s: Word,
+}
+
+impl Encodable for SetCodeAuthorization {
+    fn rlp_append(&self, s: &mut RlpStream) {
+        s.begin_list(6);
+        s.append(&self.chain_id);
+        s.append(&self.address);
+        s.append(&self.nonce);
+        s.append(&self.y_parity);
+        s.append(&self.r);
+        s.append(&self.s);
+    }
+}
+
+impl SetCodeAuthorization {
+    /// Message signed by the authority: `0x05 || rlp([chain_id, address, nonce])`
+    pub fn signing_message(&self) -> Vec<u8> {
+        let mut stream = RlpStream::new_list(3);
+        stream.append(&self.chain_id);
+        stream.append(&self.address);
+        stream.append(&self.nonce);
+        [&[0x05], stream.as_raw()].concat()
+    }
+
+    /// Return if the signature values are in the ranges accepted by EIP 7702, i.e. `y_parity`
+    /// is 0 or 1, `0 < r < n` and `0 < s <= n / 2`.
+    pub fn has_valid_signature_values(&self) -> bool {
+        let n = Word::from_little_endian(&SECP256K1_Q.to_bytes_le());
+        self.y_parity <= U64::one()
+            && !self.r.is_zero()
+            && self.r < n
+            && !self.s.is_zero()
+            && self.s <= n / 2
+    }
+
+    /// Return the SignData associated with this authorization, or None if the signature values
+    /// are out of range. The public key is the identity if it can not be recovered.
+    pub fn sign_data(&self) -> Option<SignData> {
+        if !self.has_valid_signature_values() {
+            return None;
+        }
+        let sig_r = Option::<Fq>::from(Fq::from_repr(self.r.to_le_bytes()))?;
+        let sig_s = Option::<Fq>::from(Fq::from_repr(self.s.to_le_bytes()))?;
+        let msg: Bytes = self.signing_message().into();
+        let msg_hash: [u8; 32] = Keccak256::digest(&msg)
+            .as_slice()
+            .to_vec()
+            .try_into()
+            .expect("hash length isn't 32 bytes");
+        let v = self.y_parity.as_u64() as u8;
+        let pk = recover_pk2(v, &self.r, &self.s, &msg_hash).unwrap_or(Secp256k1Affine::identity());
+        // msg_hash = msg_hash % q
+        let msg_hash = BigUint::from_bytes_be(msg_hash.as_slice());
+        let msg_hash = msg_hash.mod_floor(&*SECP256K1_Q);
+        let msg_hash = Option::<Fq>::from(Fq::from_repr(biguint_to_32bytes_le(msg_hash)))?;
+        Some(SignData {
+            signature: (sig_r, sig_s, v),
+            pk,
+            msg,
+            msg_hash,
+        })
+    }
+
+    /// Recover the authority of this authorization, or None if the signature is invalid.
+    pub fn authority(&self) -> Option<Address> {
+        self.sign_data()
+            .map(|sign_data| sign_data.get_addr())
+            .filter(|addr| !addr.is_zero())
     }
 }
 
@@ -277,6 +484,12 @@ pub struct Transaction {
     pub call_data: Bytes,
     /// Access list
     pub access_list: Option<AccessList>,
+    /// Blob gas fee cap (EIP 4844)
+    pub max_fee_per_blob_gas: Word,
+    /// Versioned hashes of the blobs (EIP 4844)
+    pub blob_versioned_hashes: Vec<H256>,
+    /// Authorization list (EIP 7702)
+    pub authorization_list: Vec<SetCodeAuthorization>,
 
     /// "v" value of the transaction signature
     pub v: u64,
@@ -296,6 +509,23 @@ pub struct Transaction {
 
 impl From<&Transaction> for crate::Transaction {
     fn from(tx: &Transaction) -> crate::Transaction {
+        let mut other = OtherFields::default();
+        if tx.tx_type.is_eip4844() {
+            other.insert(
+                "maxFeePerBlobGas".to_string(),
+                serde_json::to_value(tx.max_fee_per_blob_gas).unwrap(),
+            );
+            other.insert(
+                "blobVersionedHashes".to_string(),
+                serde_json::to_value(&tx.blob_versioned_hashes).unwrap(),
+            );
+        }
+        if tx.tx_type.is_eip7702() {
+            other.insert(
+                "authorizationList".to_string(),
+                serde_json::to_value(&tx.authorization_list).unwrap(),
+            );
+        }
         crate::Transaction {
             from: tx.from,
             to: tx.to,
@@ -311,6 +541,12 @@ impl From<&Transaction> for crate::Transaction {
             r: tx.r,
             s: tx.s,
             hash: tx.hash,
+            transaction_type: match tx.tx_type {
+                TxType::Eip4844 => Some(U64::from(3)),
+                TxType::Eip7702 => Some(U64::from(4)),
+                _ => None,
+            },
+            other,
             ..Default::default()
         }
     }
@@ -318,6 +554,7 @@ impl From<&Transaction> for crate::Transaction {
 
 impl From<&crate::Transaction> for Transaction {
     fn from(tx: &crate::Transaction) -> Transaction {
+        let (max_fee_per_blob_gas, blob_versioned_hashes) = get_blob_fields(tx);
         Transaction {
             tx_type: TxType::get_tx_type(tx),
             from: tx.from,
@@ -330,10 +567,13 @@ impl From<&crate::Transaction> for Transaction {
             gas_fee_cap: tx.max_fee_per_gas,
             call_data: tx.input.clone(),
             access_list: tx.access_list.clone(),
+            max_fee_per_blob_gas,
+            blob_versioned_hashes,
+            authorization_list: get_authorization_list(tx),
             v: tx.v.as_u64(),
             r: tx.r,
             s: tx.s,
-            rlp_bytes: tx.rlp().to_vec(),
+            rlp_bytes: get_rlp_signed(tx),
             rlp_unsigned_bytes: get_rlp_unsigned(tx),
             hash: tx.hash,
         }
@@ -438,3 +678,62 @@ pub fn access_list_size(access_list: &Option<AccessList>) -> (u64, u64) {
         },
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers_signers::{LocalWallet, Signer};
+
+    fn sign_authorization(
+        wallet: &LocalWallet,
+        mut authorization: SetCodeAuthorization,
+    ) -> SetCodeAuthorization {
+        let msg_hash = H256::from_slice(&Keccak256::digest(authorization.signing_message()));
+        let sig = wallet.sign_hash(msg_hash).unwrap();
+        authorization.y_parity = U64::from(sig.v - 27);
+        authorization.r = sig.r;
+        authorization.s = sig.s;
+        authorization
+    }
+
+    #[test]
+    fn set_code_authorization_authority() {
+        let wallet: LocalWallet =
+            "4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318"
+                .parse()
+                .unwrap();
+        let authorization = sign_authorization(
+            &wallet,
+            SetCodeAuthorization {
+                chain_id: Word::from(534352),
+                address: Address::repeat_byte(0xab),
+                nonce: U64::from(7),
+                ..Default::default()
+            },
+        );
+        assert_eq!(authorization.authority(), Some(wallet.address()));
+
+        // a tuple which is not the signed one recovers another authority
+        let tampered = SetCodeAuthorization {
+            nonce: U64::from(8),
+            ..authorization.clone()
+        };
+        assert_ne!(tampered.authority(), Some(wallet.address()));
+
+        // the equivalent signature with a high s is rejected
+        let n = Word::from_little_endian(&SECP256K1_Q.to_bytes_le());
+        let high_s = SetCodeAuthorization {
+            y_parity: U64::one() - authorization.y_parity,
+            s: n - authorization.s,
+            ..authorization.clone()
+        };
+        assert!(high_s.sign_data().is_none());
+        assert_eq!(high_s.authority(), None);
+
+        let bad_y_parity = SetCodeAuthorization {
+            y_parity: U64::from(2),
+            ..authorization
+        };
+        assert_eq!(bad_y_parity.authority(), None);
+    }
+}
@@ -2,18 +2,21 @@
 
 use crate::{
     evm_types::{Gas, GasCost, OpcodeId, ProgramCounter},
+    geth_types::SetCodeAuthorization,
     EthBlock, GethCallTrace, GethExecError, GethExecStep, GethExecTrace, GethPrestateTrace, Hash,
     ToBigEndian, Transaction, H256,
 };
 use ethers_core::types::{
     transaction::eip2930::{AccessList, AccessListItem},
-    Address, Bytes, U256, U64,
+    Address, Bytes, OtherFields, U256, U64,
 };
 use itertools::Itertools;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use trace::collect_codes;
 
+/// Compact binary encoding of block traces
+pub mod binary;
 /// Trace related helpers
 pub mod trace;
 
@@ -283,6 +286,15 @@ pub struct TransactionTrace {
     /// access list
     #[serde(rename = "accessList")]
     pub access_list: Option<Vec<AccessListItem>>,
+    /// blob gas fee cap, only for blob txs
+    #[serde(rename = "maxFeePerBlobGas", default)]
+    pub max_fee_per_blob_gas: Option<U256>,
+    /// blob versioned hashes, only for blob txs
+    #[serde(rename = "blobVersionedHashes", default)]
+    pub blob_versioned_hashes: Vec<H256>,
+    /// authorization list, only for set-code txs
+    #[serde(rename = "authorizationList", default)]
+    pub authorization_list: Vec<SetCodeAuthorization>,
     /// signature v
     pub v: U64,
     /// signature r
@@ -304,7 +316,7 @@ impl TransactionTrace {
         transaction_index: Option<U64>,
         base_fee_per_gas: Option<U256>,
     ) -> Transaction {
-        let gas_price = if self.type_ == 2 {
+        let gas_price = if self.type_ == 2 || self.type_ == 3 || self.type_ == 4 {
             let priority_fee_per_gas = std::cmp::min(
                 self.gas_tip_cap.unwrap(),
                 self.gas_fee_cap.unwrap() - base_fee_per_gas.unwrap(),
@@ -335,9 +347,30 @@ impl TransactionTrace {
             max_priority_fee_per_gas: self.gas_tip_cap,
             max_fee_per_gas: self.gas_fee_cap,
             chain_id: Some(self.chain_id),
-            other: Default::default(),
+            other: self.other_fields(),
         }
     }
+
+    fn other_fields(&self) -> OtherFields {
+        let mut other = OtherFields::default();
+        if let Some(max_fee_per_blob_gas) = self.max_fee_per_blob_gas {
+            other.insert(
+                "maxFeePerBlobGas".to_string(),
+                serde_json::to_value(max_fee_per_blob_gas).unwrap(),
+            );
+            other.insert(
+                "blobVersionedHashes".to_string(),
+                serde_json::to_value(&self.blob_versioned_hashes).unwrap(),
+            );
+        }
+        if self.type_ == 4 {
+            other.insert(
+                "authorizationList".to_string(),
+                serde_json::to_value(&self.authorization_list).unwrap(),
+            );
+        }
+        other
+    }
 }
 
 impl From<&TransactionTrace> for revm_primitives::TxEnv {
@@ -376,6 +409,14 @@ impl From<&TransactionTrace> for revm_primitives::TxEnv {
             gas_priority_fee: tx
                 .gas_tip_cap
                 .map(|g| revm_primitives::U256::from_be_bytes(g.to_be_bytes())),
+            blob_hashes: tx
+                .blob_versioned_hashes
+                .iter()
+                .map(|h| revm_primitives::B256::from(h.to_fixed_bytes()))
+                .collect(),
+            max_fee_per_blob_gas: tx
+                .max_fee_per_blob_gas
+                .map(|g| revm_primitives::U256::from_be_bytes(g.to_be_bytes())),
             ..Default::default()
         }
     }
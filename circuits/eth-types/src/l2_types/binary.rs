@@ -0,0 +1,721 @@
+//! Compact binary encoding of [`BlockTrace`], written and read back one block at a time.
+//!
+//! A stream starts with [`MAGIC`] and the format [`VERSION`] as u32 LE. Every block follows as a
+//! record: its length as u64 LE, then the encoded trace. A record of length zero ends the stream,
+//! so a truncated file is reported as an error instead of being read as a shorter chunk.
+//!
+//! Within a record, integers and lengths are LEB128 varints, words and hashes are big endian
+//! bytes of fixed size, and fields are laid out in declaration order. The block header and the
+//! call traces are embedded as JSON: their ethers and geth types carry flattened or private
+//! fields which can't be laid out field by field, and they are small next to the steps and
+//! the storage proofs.
+
+use super::{
+    AccountTrace, BlockTrace, BytecodeTrace, ExecStep, ExecutionResult, ExtraData, StorageTrace,
+    TransactionTrace,
+};
+use crate::{
+    evm_types::OpcodeId, geth_types::SetCodeAuthorization, EthBlock, GethCallTrace, GethExecError,
+    GethPrestateTrace,
+};
+use ethers_core::types::{transaction::eip2930::AccessListItem, Address, Bytes, H256, U256, U64};
+use serde::{de::DeserializeOwned, Serialize};
+use std::{
+    collections::HashMap,
+    fs::File,
+    hash::Hash,
+    io::{self, BufReader, BufWriter, Read, Write},
+    path::Path,
+};
+
+/// Bytes every stream of block traces starts with.
+pub const MAGIC: &[u8; 8] = b"L2BTRACE";
+/// Bump whenever the layout of a record changes.
+pub const VERSION: u32 = 3;
+/// Records announcing a larger length are rejected. The buffer of a record grows as it is read, so
+/// a bogus length below this bound is caught at the end of the stream rather than allocated.
+const MAX_RECORD_LEN: u64 = 1 << 30;
+
+fn invalid_data(msg: impl Into<String>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg.into())
+}
+
+/// Encode a single block trace, without the framing of a stream.
+pub fn encode_block_trace(trace: &BlockTrace) -> Vec<u8> {
+    let mut buf = Vec::new();
+    trace.encode(&mut buf);
+    buf
+}
+
+/// Decode a single block trace encoded by [`encode_block_trace`].
+pub fn decode_block_trace(buf: &[u8]) -> io::Result<BlockTrace> {
+    let mut de = Decoder { buf };
+    let trace = BlockTrace::decode(&mut de)?;
+    if !de.buf.is_empty() {
+        return Err(invalid_data(format!(
+            "{} trailing bytes after block trace",
+            de.buf.len()
+        )));
+    }
+    Ok(trace)
+}
+
+/// Writes block traces to a stream, one record per block. [`BlockTraceWriter::finish`] must be
+/// called to terminate the stream.
+pub struct BlockTraceWriter<W: Write> {
+    inner: W,
+    num_blocks: usize,
+}
+
+impl BlockTraceWriter<BufWriter<File>> {
+    /// Create the file at `path`, replacing any existing one.
+    pub fn create(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::new(BufWriter::new(File::create(path)?))
+    }
+}
+
+impl<W: Write> BlockTraceWriter<W> {
+    /// Write the header of the stream.
+    pub fn new(mut inner: W) -> io::Result<Self> {
+        inner.write_all(MAGIC)?;
+        inner.write_all(&VERSION.to_le_bytes())?;
+        Ok(Self {
+            inner,
+            num_blocks: 0,
+        })
+    }
+
+    pub fn write(&mut self, trace: &BlockTrace) -> io::Result<()> {
+        let record = encode_block_trace(trace);
+        self.inner.write_all(&(record.len() as u64).to_le_bytes())?;
+        self.inner.write_all(&record)?;
+        self.num_blocks += 1;
+        Ok(())
+    }
+
+    /// Number of blocks written so far.
+    pub fn num_blocks(&self) -> usize {
+        self.num_blocks
+    }
+
+    /// Terminate the stream and return the underlying writer, flushed.
+    pub fn finish(mut self) -> io::Result<W> {
+        self.inner.write_all(&0u64.to_le_bytes())?;
+        self.inner.flush()?;
+        Ok(self.inner)
+    }
+}
+
+/// Reads back the block traces of a stream, decoding one block at a time.
+pub struct BlockTraceReader<R: Read> {
+    inner: R,
+    done: bool,
+}
+
+impl BlockTraceReader<BufReader<File>> {
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::new(BufReader::new(File::open(path)?))
+    }
+}
+
+impl<R: Read> BlockTraceReader<R> {
+    /// Read and check the header of the stream.
+    pub fn new(mut inner: R) -> io::Result<Self> {
+        let mut magic = [0u8; 8];
+        inner.read_exact(&mut magic)?;
+        if &magic != MAGIC {
+            return Err(invalid_data("not a block trace stream"));
+        }
+        let mut version = [0u8; 4];
+        inner.read_exact(&mut version)?;
+        let version = u32::from_le_bytes(version);
+        if version != VERSION {
+            return Err(invalid_data(format!(
+                "block trace stream version {version}, expected {VERSION}"
+            )));
+        }
+        Ok(Self { inner, done: false })
+    }
+
+    fn read_record(&mut self) -> io::Result<Option<BlockTrace>> {
+        let mut len = [0u8; 8];
+        self.inner.read_exact(&mut len).map_err(truncated)?;
+        let len = u64::from_le_bytes(len);
+        if len == 0 {
+            return Ok(None);
+        }
+        if len > MAX_RECORD_LEN {
+            return Err(invalid_data(format!("block trace record of {len} bytes")));
+        }
+
+        let mut record = Vec::new();
+        (&mut self.inner).take(len).read_to_end(&mut record)?;
+        if record.len() as u64 != len {
+            return Err(invalid_data("truncated block trace stream"));
+        }
+        decode_block_trace(&record).map(Some)
+    }
+}
+
+fn truncated(err: io::Error) -> io::Error {
+    if err.kind() == io::ErrorKind::UnexpectedEof {
+        invalid_data("truncated block trace stream")
+    } else {
+        err
+    }
+}
+
+impl<R: Read> Iterator for BlockTraceReader<R> {
+    type Item = io::Result<BlockTrace>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let record = self.read_record();
+        // stop at the end of the stream, and after the first error as the framing is lost
+        self.done = !matches!(record, Ok(Some(_)));
+        record.transpose()
+    }
+}
+
+struct Decoder<'a> {
+    buf: &'a [u8],
+}
+
+impl<'a> Decoder<'a> {
+    fn take(&mut self, n: usize) -> io::Result<&'a [u8]> {
+        if self.buf.len() < n {
+            return Err(invalid_data("unexpected end of block trace record"));
+        }
+        let (head, tail) = self.buf.split_at(n);
+        self.buf = tail;
+        Ok(head)
+    }
+
+    /// Read the length of a sequence, every element taking at least one byte.
+    fn len(&mut self) -> io::Result<usize> {
+        let len = u64::decode(self)?;
+        if len > self.buf.len() as u64 {
+            return Err(invalid_data(format!(
+                "sequence of {len} elements overflows"
+            )));
+        }
+        Ok(len as usize)
+    }
+}
+
+trait Codec: Sized {
+    fn encode(&self, buf: &mut Vec<u8>);
+    fn decode(de: &mut Decoder<'_>) -> io::Result<Self>;
+}
+
+impl Codec for u8 {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        buf.push(*self);
+    }
+    fn decode(de: &mut Decoder<'_>) -> io::Result<Self> {
+        Ok(de.take(1)?[0])
+    }
+}
+
+impl Codec for bool {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        buf.push(*self as u8);
+    }
+    fn decode(de: &mut Decoder<'_>) -> io::Result<Self> {
+        match u8::decode(de)? {
+            0 => Ok(false),
+            1 => Ok(true),
+            b => Err(invalid_data(format!("invalid bool {b}"))),
+        }
+    }
+}
+
+impl Codec for u64 {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        let mut value = *self;
+        while value >= 0x80 {
+            buf.push(value as u8 | 0x80);
+            value >>= 7;
+        }
+        buf.push(value as u8);
+    }
+    fn decode(de: &mut Decoder<'_>) -> io::Result<Self> {
+        let mut value = 0u64;
+        for shift in (0..64).step_by(7) {
+            let b = u8::decode(de)?;
+            if shift == 63 && b > 1 {
+                break;
+            }
+            value |= u64::from(b & 0x7f) << shift;
+            if b & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err(invalid_data("varint overflows u64"))
+    }
+}
+
+/// Zigzag encoded, so that small negative values stay short.
+impl Codec for isize {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        let value = *self as i64;
+        (((value << 1) ^ (value >> 63)) as u64).encode(buf);
+    }
+    fn decode(de: &mut Decoder<'_>) -> io::Result<Self> {
+        let value = u64::decode(de)?;
+        let value = ((value >> 1) as i64) ^ -((value & 1) as i64);
+        isize::try_from(value).map_err(|_| invalid_data(format!("{value} overflows isize")))
+    }
+}
+
+impl Codec for U64 {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        self.as_u64().encode(buf);
+    }
+    fn decode(de: &mut Decoder<'_>) -> io::Result<Self> {
+        u64::decode(de).map(U64::from)
+    }
+}
+
+impl Codec for U256 {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        let mut bytes = [0u8; 32];
+        self.to_big_endian(&mut bytes);
+        buf.extend_from_slice(&bytes);
+    }
+    fn decode(de: &mut Decoder<'_>) -> io::Result<Self> {
+        Ok(U256::from_big_endian(de.take(32)?))
+    }
+}
+
+impl Codec for H256 {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        buf.extend_from_slice(self.as_bytes());
+    }
+    fn decode(de: &mut Decoder<'_>) -> io::Result<Self> {
+        Ok(H256::from_slice(de.take(32)?))
+    }
+}
+
+impl Codec for Address {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        buf.extend_from_slice(self.as_bytes());
+    }
+    fn decode(de: &mut Decoder<'_>) -> io::Result<Self> {
+        Ok(Address::from_slice(de.take(20)?))
+    }
+}
+
+impl Codec for Bytes {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        (self.len() as u64).encode(buf);
+        buf.extend_from_slice(self);
+    }
+    fn decode(de: &mut Decoder<'_>) -> io::Result<Self> {
+        let len = de.len()?;
+        Ok(Bytes::from(de.take(len)?.to_vec()))
+    }
+}
+
+impl Codec for String {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        (self.len() as u64).encode(buf);
+        buf.extend_from_slice(self.as_bytes());
+    }
+    fn decode(de: &mut Decoder<'_>) -> io::Result<Self> {
+        let len = de.len()?;
+        String::from_utf8(de.take(len)?.to_vec()).map_err(|err| invalid_data(err.to_string()))
+    }
+}
+
+impl<T: Codec> Codec for Option<T> {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        self.is_some().encode(buf);
+        if let Some(value) = self {
+            value.encode(buf);
+        }
+    }
+    fn decode(de: &mut Decoder<'_>) -> io::Result<Self> {
+        Ok(if bool::decode(de)? {
+            Some(T::decode(de)?)
+        } else {
+            None
+        })
+    }
+}
+
+impl<T: Codec> Codec for Vec<T> {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        (self.len() as u64).encode(buf);
+        for value in self {
+            value.encode(buf);
+        }
+    }
+    fn decode(de: &mut Decoder<'_>) -> io::Result<Self> {
+        let len = de.len()?;
+        (0..len).map(|_| T::decode(de)).collect()
+    }
+}
+
+/// Entries are sorted by their encoded key, so that the encoding of a trace is deterministic.
+impl<K: Codec + Eq + Hash, V: Codec> Codec for HashMap<K, V> {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        let mut entries: Vec<_> = self
+            .iter()
+            .map(|(key, value)| {
+                let mut key_buf = Vec::new();
+                key.encode(&mut key_buf);
+                (key_buf, value)
+            })
+            .collect();
+        entries.sort_unstable_by(|a, b| a.0.cmp(&b.0));
+
+        (entries.len() as u64).encode(buf);
+        for (key, value) in entries {
+            buf.extend_from_slice(&key);
+            value.encode(buf);
+        }
+    }
+    fn decode(de: &mut Decoder<'_>) -> io::Result<Self> {
+        let len = de.len()?;
+        (0..len)
+            .map(|_| Ok((K::decode(de)?, V::decode(de)?)))
+            .collect()
+    }
+}
+
+impl Codec for OpcodeId {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        self.as_u8().encode(buf);
+    }
+    fn decode(de: &mut Decoder<'_>) -> io::Result<Self> {
+        u8::decode(de).map(OpcodeId::from)
+    }
+}
+
+/// Same string constants as the struct logger of geth.
+impl Codec for GethExecError {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        self.error().to_string().encode(buf);
+    }
+    fn decode(de: &mut Decoder<'_>) -> io::Result<Self> {
+        let error = String::decode(de)?;
+        error
+            .parse()
+            .map_err(|_| invalid_data(format!("invalid exec error {error:?}")))
+    }
+}
+
+fn encode_json<T: Serialize>(value: &T, buf: &mut Vec<u8>) {
+    let json = serde_json::to_vec(value).expect("serialize to json");
+    (json.len() as u64).encode(buf);
+    buf.extend_from_slice(&json);
+}
+
+fn decode_json<T: DeserializeOwned>(de: &mut Decoder<'_>) -> io::Result<T> {
+    let len = de.len()?;
+    // call traces nest as deep as the calls
+    let mut deserializer = serde_json::Deserializer::from_slice(de.take(len)?);
+    deserializer.disable_recursion_limit();
+    let deserializer = serde_stacker::Deserializer::new(&mut deserializer);
+    Ok(serde::Deserialize::deserialize(deserializer)?)
+}
+
+impl Codec for EthBlock {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        encode_json(self, buf);
+    }
+    fn decode(de: &mut Decoder<'_>) -> io::Result<Self> {
+        decode_json(de)
+    }
+}
+
+impl Codec for GethCallTrace {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        encode_json(self, buf);
+    }
+    fn decode(de: &mut Decoder<'_>) -> io::Result<Self> {
+        decode_json(de)
+    }
+}
+
+/// Encode the listed fields of a struct in order.
+macro_rules! impl_codec {
+    ($ty:ident { $($field:ident),* $(,)? }) => {
+        impl Codec for $ty {
+            fn encode(&self, buf: &mut Vec<u8>) {
+                $(self.$field.encode(buf);)*
+            }
+            fn decode(de: &mut Decoder<'_>) -> io::Result<Self> {
+                // fields of a struct expression are evaluated in the order they are written
+                Ok(Self {
+                    $($field: Codec::decode(de)?,)*
+                })
+            }
+        }
+    };
+}
+
+impl_codec!(AccessListItem {
+    address,
+    storage_keys,
+});
+
+impl_codec!(AccountTrace {
+    address,
+    nonce,
+    balance,
+    keccak_code_hash,
+    poseidon_code_hash,
+    code_size,
+});
+
+impl_codec!(BytecodeTrace { hash, code });
+
+impl_codec!(StorageTrace {
+    root_before,
+    root_after,
+    proofs,
+    storage_proofs,
+    deletion_proofs,
+});
+
+impl_codec!(ExtraData { code_list });
+
+impl_codec!(GethPrestateTrace {
+    balance,
+    nonce,
+    code,
+    storage,
+});
+
+impl_codec!(TransactionTrace {
+    tx_hash,
+    type_,
+    nonce,
+    gas,
+    gas_price,
+    gas_tip_cap,
+    gas_fee_cap,
+    from,
+    to,
+    chain_id,
+    value,
+    data,
+    is_create,
+    access_list,
+    max_fee_per_blob_gas,
+    blob_versioned_hashes,
+    authorization_list,
+    v,
+    r,
+    s,
+});
+
+impl_codec!(SetCodeAuthorization {
+    chain_id,
+    address,
+    nonce,
+    y_parity,
+    r,
+    s,
+});
+
+impl_codec!(ExecutionResult {
+    l1_fee,
+    gas,
+    failed,
+    return_value,
+    from,
+    to,
+    account_after,
+    account_created,
+    code_hash,
+    byte_code,
+    exec_steps,
+    call_trace,
+    prestate,
+});
+
+/// Stack, memory and storage are always part of the layout, so that traces can be exchanged
+/// between builds with different features. They are written empty and skipped on read when
+/// their feature is disabled.
+impl Codec for ExecStep {
+    fn encode(&self, buf: &mut Vec<u8>) {
+        self.pc.encode(buf);
+        self.op.encode(buf);
+        self.gas.encode(buf);
+        self.gas_cost.encode(buf);
+        self.refund.encode(buf);
+        self.depth.encode(buf);
+        self.error.encode(buf);
+        #[cfg(feature = "enable-stack")]
+        self.stack.encode(buf);
+        #[cfg(not(feature = "enable-stack"))]
+        None::<Vec<U256>>.encode(buf);
+        #[cfg(feature = "enable-memory")]
+        self.memory.encode(buf);
+        #[cfg(not(feature = "enable-memory"))]
+        None::<Vec<U256>>.encode(buf);
+        #[cfg(feature = "enable-storage")]
+        self.storage.encode(buf);
+        #[cfg(not(feature = "enable-storage"))]
+        None::<HashMap<U256, U256>>.encode(buf);
+        self.extra_data.encode(buf);
+    }
+
+    fn decode(de: &mut Decoder<'_>) -> io::Result<Self> {
+        let pc = Codec::decode(de)?;
+        let op = Codec::decode(de)?;
+        let gas = Codec::decode(de)?;
+        let gas_cost = Codec::decode(de)?;
+        let refund = Codec::decode(de)?;
+        let depth = Codec::decode(de)?;
+        let error = Codec::decode(de)?;
+        #[cfg_attr(not(feature = "enable-stack"), allow(unused_variables))]
+        let stack = Option::<Vec<U256>>::decode(de)?;
+        #[cfg_attr(not(feature = "enable-memory"), allow(unused_variables))]
+        let memory = Option::<Vec<U256>>::decode(de)?;
+        #[cfg_attr(not(feature = "enable-storage"), allow(unused_variables))]
+        let storage = Option::<HashMap<U256, U256>>::decode(de)?;
+        let extra_data = Codec::decode(de)?;
+
+        Ok(Self {
+            pc,
+            op,
+            gas,
+            gas_cost,
+            refund,
+            depth,
+            error,
+            #[cfg(feature = "enable-stack")]
+            stack,
+            #[cfg(feature = "enable-memory")]
+            memory,
+            #[cfg(feature = "enable-storage")]
+            storage,
+            extra_data,
+        })
+    }
+}
+
+impl_codec!(BlockTrace {
+    chain_id,
+    coinbase,
+    header,
+    transactions,
+    execution_results,
+    codes,
+    storage_trace,
+    tx_storage_trace,
+    start_l1_queue_index,
+    withdraw_trie_root,
+});
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn block_trace() -> BlockTrace {
+        let address = Address::repeat_byte(0x11);
+        let exec_step = ExecStep {
+            pc: 3,
+            op: OpcodeId::SLOAD,
+            gas: 30_000,
+            gas_cost: 2100,
+            refund: 0,
+            depth: 1,
+            error: Some(GethExecError::OutOfGas),
+            #[cfg(feature = "enable-stack")]
+            stack: Some(vec![U256::from(7)]),
+            #[cfg(feature = "enable-memory")]
+            memory: None,
+            #[cfg(feature = "enable-storage")]
+            storage: Some(HashMap::from([(U256::one(), U256::MAX)])),
+            extra_data: Some(ExtraData {
+                code_list: Some(vec![Bytes::from(vec![0x60, 0x00])]),
+            }),
+        };
+        let execution_result = ExecutionResult {
+            l1_fee: U256::from(100),
+            gas: 21_000,
+            failed: true,
+            return_value: "0x".to_string(),
+            from: None,
+            to: None,
+            account_after: vec![AccountTrace::default()],
+            account_created: None,
+            code_hash: Some(H256::repeat_byte(0x22)),
+            byte_code: Some("0x6000".to_string()),
+            exec_steps: vec![exec_step],
+            call_trace: GethCallTrace::new(
+                "CALL".to_string(),
+                address,
+                None,
+                U256::from(21_000),
+                None,
+                Some("out of gas".to_string()),
+                vec![],
+            ),
+            prestate: HashMap::from([(
+                address,
+                GethPrestateTrace {
+                    balance: Some(U256::from(1)),
+                    nonce: Some(2),
+                    code: None,
+                    storage: Some(HashMap::from([(U256::zero(), U256::one())])),
+                },
+            )]),
+        };
+
+        let mut trace = BlockTrace {
+            chain_id: 534352,
+            execution_results: vec![execution_result],
+            start_l1_queue_index: 5,
+            withdraw_trie_root: H256::repeat_byte(0x33),
+            ..Default::default()
+        };
+        trace.header.number = Some(U64::from(42));
+        trace.storage_trace.proofs =
+            Some(HashMap::from([(address, vec![Bytes::from(vec![1, 2, 3])])]));
+        trace
+    }
+
+    #[test]
+    fn test_block_trace_stream_roundtrip() -> io::Result<()> {
+        let traces = [block_trace(), BlockTrace::default()];
+        let mut writer = BlockTraceWriter::new(Vec::new())?;
+        for trace in &traces {
+            writer.write(trace)?;
+        }
+        let buf = writer.finish()?;
+
+        let decoded = BlockTraceReader::new(buf.as_slice())?.collect::<io::Result<Vec<_>>>()?;
+        assert_eq!(decoded.len(), traces.len());
+        for (decoded, trace) in decoded.iter().zip(&traces) {
+            assert_eq!(
+                serde_json::to_value(decoded).unwrap(),
+                serde_json::to_value(trace).unwrap()
+            );
+        }
+
+        // a stream cut before its end marker is an error, not a shorter chunk
+        let truncated = BlockTraceReader::new(&buf[..buf.len() - 8])?;
+        assert!(truncated.last().unwrap().is_err());
+
+        // neither is a record longer than the rest of the stream
+        let read_all = |buf: &[u8]| {
+            BlockTraceReader::new(buf).and_then(|reader| reader.collect::<io::Result<Vec<_>>>())
+        };
+        for len in [MAX_RECORD_LEN - 1, u64::MAX] {
+            let mut bogus = buf[..buf.len() - 8].to_vec();
+            bogus.extend_from_slice(&len.to_le_bytes());
+            bogus.extend_from_slice(&[0u8; 16]);
+            assert!(read_all(&bogus).is_err());
+        }
+
+        Ok(())
+    }
+}
@@ -757,6 +757,27 @@ pub struct FlatGethCallTrace {
 }
 
 impl GethCallTrace {
+    /// Create a call trace frame, as returned by the geth callTracer.
+    pub fn new(
+        call_type: String,
+        from: Address,
+        to: Option<Address>,
+        gas_used: U256,
+        output: Option<Bytes>,
+        error: Option<String>,
+        calls: Vec<GethCallTrace>,
+    ) -> Self {
+        Self {
+            calls,
+            error,
+            from,
+            gas_used,
+            output,
+            to,
+            call_type,
+        }
+    }
+
     fn is_precheck_failed(&self) -> bool {
         self.error
             .as_ref()
@@ -1,6 +1,6 @@
 //! Some handy helpers
 
-use crate::{Address, Hash};
+use crate::{forks::LAST_PRECOMPILE_ADDRESS, Address, Hash};
 use revm_precompile::Precompiles;
 
 /// Check if address is a precompiled or not.
@@ -9,7 +9,11 @@ pub fn is_precompiled(address: &Address) -> bool {
     let precompiles = Precompiles::bernoulli();
     #[cfg(not(feature = "scroll"))]
     let precompiles = Precompiles::berlin();
+    // point evaluation (EIP-4844) and the BLS12-381 G1 addition (EIP-2537) are dispatched by
+    // bus-mapping itself, whatever the revm spec, when the fork has them
     precompiles.get(address.as_fixed_bytes().into()).is_some()
+        || (address.0[0..19] == [0u8; 19]
+            && (0x0a..=LAST_PRECOMPILE_ADDRESS).contains(&address.0[19]))
 }
 
 /// Default number of bytes to pack into a field element.
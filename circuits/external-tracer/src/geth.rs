@@ -0,0 +1,53 @@
+//! Trace backend calling into the Go tracer of `geth-utils`.
+
+use crate::TraceConfig;
+#[cfg(feature = "scroll")]
+use eth_types::l2_types::BlockTrace;
+use eth_types::{Error, GethExecTrace};
+
+#[cfg(not(feature = "scroll"))]
+pub fn trace(config: &TraceConfig) -> Result<Vec<GethExecTrace>, Error> {
+    let trace_config = &serde_json::to_string_pretty(&config).unwrap();
+    log::trace!("trace config: {}", trace_config);
+    // Get the trace
+    let trace_string = geth_utils::trace(trace_config).map_err(|error| match error {
+        geth_utils::Error::TracingError(error) => Error::TracingError(error),
+    })?;
+
+    log::trace!("trace: {}", trace_string);
+
+    let mut deserializer = serde_json::Deserializer::from_str(&trace_string);
+    deserializer.disable_recursion_limit();
+    let deserializer = serde_stacker::Deserializer::new(&mut deserializer);
+    serde::Deserialize::deserialize(deserializer).map_err(Error::SerdeError)
+}
+
+/// Creates a l2-trace for the specified config
+#[cfg(feature = "scroll")]
+pub fn l2trace(config: &TraceConfig) -> Result<BlockTrace, Error> {
+    let l2_config = config.clone();
+    let trace_config = &serde_json::to_string_pretty(&l2_config).unwrap();
+    log::trace!("trace config: {}", trace_config);
+    // Get the trace
+    let trace_string = geth_utils::trace(trace_config).map_err(|error| match error {
+        geth_utils::Error::TracingError(error) => Error::TracingError(error),
+    })?;
+
+    log::trace!("trace: {}", trace_string);
+
+    let mut deserializer = serde_json::Deserializer::from_str(&trace_string);
+    deserializer.disable_recursion_limit();
+    let deserializer = serde_stacker::Deserializer::new(&mut deserializer);
+    serde::Deserialize::deserialize(deserializer).map_err(Error::SerdeError)
+}
+
+#[cfg(feature = "scroll")]
+pub fn trace(config: &TraceConfig) -> Result<Vec<GethExecTrace>, Error> {
+    let block_trace = l2trace(config)?;
+
+    Ok(block_trace
+        .execution_results
+        .into_iter()
+        .map(From::from)
+        .collect::<Vec<_>>())
+}
@@ -1,10 +1,31 @@
 //! This module generates traces by connecting to an external tracer
+//!
+//! Two backends are available behind the same [`TraceConfig`] / [`trace`] API:
+//! - `geth-tracer` (default) runs the Go tracer of `geth-utils` through cgo.
+//! - `revm-tracer` runs the transactions in-process with revm, which avoids the Go toolchain.
+//!
+//! When both are enabled, [`trace`] uses the revm backend.
+
+#[cfg(not(any(feature = "geth-tracer", feature = "revm-tracer")))]
+compile_error!("one of the `geth-tracer` or `revm-tracer` features must be enabled");
+
+#[cfg(feature = "geth-tracer")]
+pub mod geth;
+#[cfg(feature = "revm-tracer")]
+pub mod revm_tracer;
+
+#[cfg(all(feature = "geth-tracer", not(feature = "revm-tracer")))]
+use geth as backend;
+#[cfg(feature = "revm-tracer")]
+use revm_tracer as backend;
 
 #[cfg(feature = "scroll")]
-use eth_types::l2_types::BlockTrace;
+pub use backend::l2trace;
+pub use backend::trace;
+
 use eth_types::{
     geth_types::{Account, BlockConstants, Transaction},
-    Address, Error, GethExecTrace, Word,
+    Address, Word,
 };
 use serde::Serialize;
 use std::collections::BTreeMap;
@@ -78,50 +99,3 @@ impl LoggerConfig {
 #[derive(Clone, Debug, Default, Serialize)]
 #[serde(rename_all = "PascalCase")]
 pub struct ChainConfig {}
-
-#[cfg(not(feature = "scroll"))]
-pub fn trace(config: &TraceConfig) -> Result<Vec<GethExecTrace>, Error> {
-    let trace_config = &serde_json::to_string_pretty(&config).unwrap();
-    log::trace!("trace config: {}", trace_config);
-    // Get the trace
-    let trace_string = geth_utils::trace(trace_config).map_err(|error| match error {
-        geth_utils::Error::TracingError(error) => Error::TracingError(error),
-    })?;
-
-    log::trace!("trace: {}", trace_string);
-
-    let mut deserializer = serde_json::Deserializer::from_str(&trace_string);
-    deserializer.disable_recursion_limit();
-    let deserializer = serde_stacker::Deserializer::new(&mut deserializer);
-    serde::Deserialize::deserialize(deserializer).map_err(Error::SerdeError)
-}
-
-/// Creates a l2-trace for the specified config
-#[cfg(feature = "scroll")]
-pub fn l2trace(config: &TraceConfig) -> Result<BlockTrace, Error> {
-    let l2_config = config.clone();
-    let trace_config = &serde_json::to_string_pretty(&l2_config).unwrap();
-    log::trace!("trace config: {}", trace_config);
-    // Get the trace
-    let trace_string = geth_utils::trace(trace_config).map_err(|error| match error {
-        geth_utils::Error::TracingError(error) => Error::TracingError(error),
-    })?;
-
-    log::trace!("trace: {}", trace_string);
-
-    let mut deserializer = serde_json::Deserializer::from_str(&trace_string);
-    deserializer.disable_recursion_limit();
-    let deserializer = serde_stacker::Deserializer::new(&mut deserializer);
-    serde::Deserialize::deserialize(deserializer).map_err(Error::SerdeError)
-}
-
-#[cfg(feature = "scroll")]
-pub fn trace(config: &TraceConfig) -> Result<Vec<GethExecTrace>, Error> {
-    let block_trace = l2trace(config)?;
-
-    Ok(block_trace
-        .execution_results
-        .into_iter()
-        .map(From::from)
-        .collect::<Vec<_>>())
-}
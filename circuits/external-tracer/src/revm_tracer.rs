@@ -0,0 +1,613 @@
+//! Trace backend running the transactions in-process with revm.
+//!
+//! The struct-logs, call traces and prestates follow the output of the Go tracer of
+//! `geth-utils`. In scroll mode, the storage trace of the [`BlockTrace`] is produced from an
+//! in-memory zktrie of the traced state. Per-tx storage traces, which are only used by the
+//! capacity checker, are left empty.
+
+mod inspector;
+#[cfg(all(test, feature = "geth-tracer"))]
+mod test;
+#[cfg(feature = "scroll")]
+mod zktrie;
+
+use crate::TraceConfig;
+#[cfg(not(feature = "scroll"))]
+use eth_types::evm_types::Gas;
+use eth_types::{
+    geth_types::Transaction, Address, Error, GethExecTrace, GethPrestateTrace, ToBigEndian, Word,
+};
+#[cfg(feature = "scroll")]
+use eth_types::{
+    l2_predeployed::{l1_gas_price_oracle, message_queue},
+    l2_types::{
+        self, AccountTrace, BlockTrace, BytecodeTrace, ExecStep, StorageTrace, TransactionTrace,
+    },
+    utils::{hash_code, hash_code_keccak, hash_code_poseidon},
+    EthBlock, GethExecStep, TxType, H256,
+};
+use inspector::StructLogger;
+use revm::{
+    db::{CacheDB, EmptyDB},
+    inspector_handle_register,
+    primitives::{
+        self, AccountInfo, BlobExcessGasAndPrice, BlockEnv, Bytecode, ExecutionResult,
+        ResultAndState, SpecId, TransactTo, TxEnv, B256,
+    },
+    DatabaseCommit, Evm,
+};
+#[cfg(feature = "scroll")]
+use revm::{
+    primitives::{EvmState, Output},
+    DatabaseRef,
+};
+use std::collections::HashMap;
+#[cfg(feature = "scroll")]
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Hardfork of the chain config used by `geth-utils`.
+#[cfg(not(feature = "scroll"))]
+const SPEC_ID: SpecId = SpecId::CANCUN;
+#[cfg(feature = "scroll")]
+const SPEC_ID: SpecId = SpecId::CURIE;
+
+pub(crate) fn to_revm_address(address: Address) -> primitives::Address {
+    address.0.into()
+}
+
+pub(crate) fn from_revm_address(address: primitives::Address) -> Address {
+    Address::from(address.0 .0)
+}
+
+pub(crate) fn to_revm_u256(value: Word) -> primitives::U256 {
+    primitives::U256::from_be_bytes(value.to_be_bytes())
+}
+
+pub(crate) fn from_revm_u256(value: primitives::U256) -> Word {
+    Word::from_big_endian(&value.to_be_bytes::<32>())
+}
+
+/// Code of an account in the state db.
+pub(crate) fn code(db: &CacheDB<EmptyDB>, info: &AccountInfo) -> primitives::Bytes {
+    info.code
+        .clone()
+        .or_else(|| db.contracts.get(&info.code_hash).cloned())
+        .map(|code| code.original_bytes())
+        .unwrap_or_default()
+}
+
+/// Transactions without signature are handled as l1 messages by `geth-utils`.
+#[cfg(feature = "scroll")]
+fn is_l1_msg(tx: &Transaction) -> bool {
+    tx.tx_type.is_l1_msg() || tx.r.is_zero()
+}
+
+fn state_db(config: &TraceConfig) -> CacheDB<EmptyDB> {
+    let mut db = CacheDB::new(EmptyDB::default());
+    for (address, account) in &config.accounts {
+        let address = to_revm_address(*address);
+        let code = Bytecode::new_raw(account.code.to_vec().into());
+        let info = AccountInfo {
+            balance: to_revm_u256(account.balance),
+            nonce: account.nonce.as_u64(),
+            ..AccountInfo::from_bytecode(code)
+        };
+        db.insert_account_info(address, info);
+        for (key, value) in &account.storage {
+            db.insert_account_storage(address, to_revm_u256(*key), to_revm_u256(*value))
+                .expect("EmptyDB is infallible");
+        }
+    }
+
+    // The hash of the blocks not covered by the history hashes is zero, as in `geth-utils`.
+    let number = config.block_constants.number.as_u64();
+    for n in number.saturating_sub(256)..number {
+        db.block_hashes
+            .insert(primitives::U256::from(n), B256::ZERO);
+    }
+    for (n, hash) in (0..number).rev().zip(config.history_hashes.iter().rev()) {
+        db.block_hashes
+            .insert(primitives::U256::from(n), B256::from(hash.to_be_bytes()));
+    }
+    db
+}
+
+fn block_env(config: &TraceConfig) -> BlockEnv {
+    let block = &config.block_constants;
+    BlockEnv {
+        number: primitives::U256::from(block.number.as_u64()),
+        coinbase: to_revm_address(block.coinbase),
+        timestamp: to_revm_u256(block.timestamp),
+        gas_limit: to_revm_u256(block.gas_limit),
+        basefee: to_revm_u256(block.base_fee),
+        difficulty: to_revm_u256(block.difficulty),
+        // `geth-utils` uses the difficulty as randao for PREVRANDAO.
+        prevrandao: Some(B256::from(block.difficulty.to_be_bytes())),
+        blob_excess_gas_and_price: (!cfg!(feature = "scroll"))
+            .then(|| BlobExcessGasAndPrice::new(0)),
+    }
+}
+
+fn tx_env(tx: &Transaction) -> TxEnv {
+    #[allow(unused_mut)]
+    let mut tx_env = TxEnv {
+        caller: to_revm_address(tx.from),
+        gas_limit: tx.gas_limit.as_u64(),
+        gas_price: to_revm_u256(tx.gas_fee_cap.or(tx.gas_price).unwrap_or_default()),
+        transact_to: match tx.to {
+            Some(to) => TransactTo::Call(to_revm_address(to)),
+            None => TransactTo::Create,
+        },
+        value: to_revm_u256(tx.value),
+        data: tx.call_data.to_vec().into(),
+        nonce: Some(tx.nonce.as_u64()),
+        access_list: tx
+            .access_list
+            .as_ref()
+            .map(|access_list| {
+                access_list
+                    .0
+                    .iter()
+                    .map(|item| {
+                        (
+                            to_revm_address(item.address),
+                            item.storage_keys
+                                .iter()
+                                .map(|key| primitives::U256::from_be_bytes(key.0))
+                                .collect(),
+                        )
+                    })
+                    .collect()
+            })
+            .unwrap_or_default(),
+        gas_priority_fee: tx.gas_tip_cap.map(to_revm_u256),
+        blob_hashes: tx
+            .blob_versioned_hashes
+            .iter()
+            .map(|hash| B256::from(hash.0))
+            .collect(),
+        max_fee_per_blob_gas: tx
+            .tx_type
+            .is_eip4844()
+            .then(|| to_revm_u256(tx.max_fee_per_blob_gas)),
+        ..Default::default()
+    };
+    #[cfg(feature = "scroll")]
+    if is_l1_msg(tx) {
+        // The nonce of a l1 message is its queue index.
+        tx_env.nonce = None;
+        tx_env.scroll.is_l1_msg = true;
+    } else {
+        tx_env.scroll.rlp_bytes = Some(tx.rlp_bytes.clone().into());
+    }
+    tx_env
+}
+
+/// Trace of a transaction executed by revm.
+struct TxTrace {
+    result: ExecutionResult,
+    logger: StructLogger,
+    prestate: HashMap<Address, GethPrestateTrace>,
+    /// Accounts touched by the transaction, after its execution.
+    #[cfg(feature = "scroll")]
+    state: EvmState,
+    #[cfg(feature = "scroll")]
+    l1_fee: primitives::U256,
+}
+
+impl TxTrace {
+    fn failed(&self) -> bool {
+        !self.result.is_success()
+    }
+
+    /// Hex encoded return value, which is empty when the transaction failed without reverting.
+    fn return_value(&self) -> String {
+        match &self.result {
+            ExecutionResult::Success { output, .. } => hex::encode(output.data()),
+            ExecutionResult::Revert { output, .. } => hex::encode(output),
+            ExecutionResult::Halt { .. } => String::new(),
+        }
+    }
+
+    /// Call trace, whose outermost frame uses the gas of the whole transaction, like geth.
+    fn call_trace(&mut self) -> eth_types::GethCallTrace {
+        let mut frame = self
+            .logger
+            .call_trace
+            .take()
+            .expect("the outermost frame is traced");
+        frame.gas_used = self.result.gas_used();
+        frame.into()
+    }
+}
+
+/// Accounts of the state db touched by a transaction, before its execution.
+fn prestate(
+    db: &CacheDB<EmptyDB>,
+    state: &primitives::EvmState,
+) -> HashMap<Address, GethPrestateTrace> {
+    state
+        .iter()
+        .map(|(address, account)| {
+            let info = db
+                .accounts
+                .get(address)
+                .map(|account| account.info.clone())
+                .unwrap_or_default();
+            let code = code(db, &info);
+            let storage = account
+                .storage
+                .iter()
+                .map(|(key, slot)| (from_revm_u256(*key), from_revm_u256(slot.original_value())))
+                .collect::<HashMap<_, _>>();
+            let prestate = GethPrestateTrace {
+                balance: Some(from_revm_u256(info.balance)),
+                nonce: (info.nonce > 0).then_some(info.nonce),
+                code: (!code.is_empty()).then(|| code.to_vec().into()),
+                storage: (!storage.is_empty()).then_some(storage),
+            };
+            (from_revm_address(*address), prestate)
+        })
+        .collect()
+}
+
+#[cfg(feature = "scroll")]
+fn l1_fee(db: &mut CacheDB<EmptyDB>, tx: &Transaction) -> Result<primitives::U256, Error> {
+    if is_l1_msg(tx) {
+        return Ok(primitives::U256::ZERO);
+    }
+    let l1_block_info = revm::L1BlockInfo::try_fetch(db, SPEC_ID)
+        .map_err(|error| Error::TracingError(format!("{error:?}")))?;
+    Ok(l1_block_info.calculate_tx_l1_cost(&tx.rlp_bytes, SPEC_ID))
+}
+
+/// Execute the transactions of the config on top of `db`.
+fn execute(config: &TraceConfig, db: &mut CacheDB<EmptyDB>) -> Result<Vec<TxTrace>, Error> {
+    let block_gas_limit = config.block_constants.gas_limit.low_u64();
+    let txs_gas_limit = config
+        .transactions
+        .iter()
+        .map(|tx| tx.gas_limit.low_u64())
+        .sum::<u64>();
+    if txs_gas_limit > block_gas_limit {
+        return Err(Error::TracingError(format!(
+            "txs total gas: {txs_gas_limit} Exceeds block gas limit: {block_gas_limit}"
+        )));
+    }
+
+    let mut evm = Evm::builder()
+        .with_db(db)
+        .with_external_context(StructLogger::default())
+        .modify_cfg_env(|cfg| {
+            cfg.chain_id = config.chain_id;
+            // `geth-utils` runs the l1 transactions with `NoBaseFee`.
+            cfg.disable_base_fee = !cfg!(feature = "scroll");
+        })
+        .modify_block_env(|block| *block = block_env(config))
+        .with_spec_id(SPEC_ID)
+        .append_handler_register(inspector_handle_register)
+        .build();
+
+    config
+        .transactions
+        .iter()
+        .enumerate()
+        .map(|(idx, tx)| {
+            *evm.tx_mut() = tx_env(tx);
+            evm.context.external = StructLogger::new(config.logger_config.clone());
+            #[cfg(feature = "scroll")]
+            let l1_fee = l1_fee(evm.db_mut(), tx)?;
+
+            let ResultAndState { result, state } = evm.transact().map_err(|error| {
+                Error::TracingError(format!(
+                    "Failed to apply config.Transactions[{idx}]: {error:?}"
+                ))
+            })?;
+            let prestate = prestate(evm.db_mut(), &state);
+            #[cfg(feature = "scroll")]
+            evm.db_mut().commit(state.clone());
+            #[cfg(not(feature = "scroll"))]
+            evm.db_mut().commit(state);
+
+            Ok(TxTrace {
+                result,
+                logger: std::mem::take(&mut evm.context.external),
+                prestate,
+                #[cfg(feature = "scroll")]
+                state,
+                #[cfg(feature = "scroll")]
+                l1_fee,
+            })
+        })
+        .collect()
+}
+
+/// Creates a trace for the specified config
+#[cfg(not(feature = "scroll"))]
+pub fn trace(config: &TraceConfig) -> Result<Vec<GethExecTrace>, Error> {
+    let mut db = state_db(config);
+    Ok(execute(config, &mut db)?
+        .into_iter()
+        .map(|mut tx| GethExecTrace {
+            l1_fee: 0,
+            gas: Gas(tx.result.gas_used()),
+            failed: tx.failed(),
+            return_value: tx.return_value(),
+            call_trace: tx.call_trace(),
+            struct_logs: std::mem::take(&mut tx.logger.steps),
+            account_after: vec![],
+            prestate: tx.prestate,
+        })
+        .collect())
+}
+
+/// Creates a trace for the specified config
+#[cfg(feature = "scroll")]
+pub fn trace(config: &TraceConfig) -> Result<Vec<GethExecTrace>, Error> {
+    let block_trace = l2trace(config)?;
+
+    Ok(block_trace
+        .execution_results
+        .into_iter()
+        .map(From::from)
+        .collect::<Vec<_>>())
+}
+
+/// Creates a l2-trace for the specified config
+#[cfg(feature = "scroll")]
+pub fn l2trace(config: &TraceConfig) -> Result<BlockTrace, Error> {
+    let mut db = state_db(config);
+    let mut state_trie = zktrie::StateTrie::new(&config.accounts)?;
+    let txs = execute(config, &mut db)?;
+
+    // Accounts and slots accessed by the block, plus the ones read by the circuits for the l1
+    // fee and the withdraw root.
+    let coinbase = config.block_constants.coinbase;
+    let mut accessed = BTreeMap::<Address, BTreeSet<Word>>::new();
+    accessed.entry(coinbase).or_default();
+    accessed
+        .entry(*message_queue::ADDRESS)
+        .or_default()
+        .insert(message_queue::WITHDRAW_TRIE_ROOT_SLOT);
+    accessed
+        .entry(*l1_gas_price_oracle::ADDRESS)
+        .or_default()
+        .extend([
+            *l1_gas_price_oracle::BASE_FEE_SLOT,
+            *l1_gas_price_oracle::OVERHEAD_SLOT,
+            *l1_gas_price_oracle::SCALAR_SLOT,
+            *l1_gas_price_oracle::L1_BLOB_BASEFEE_SLOT,
+            *l1_gas_price_oracle::COMMIT_SCALAR_SLOT,
+            *l1_gas_price_oracle::BLOB_SCALAR_SLOT,
+        ]);
+    for tx in &txs {
+        for (address, account) in &tx.state {
+            accessed
+                .entry(from_revm_address(*address))
+                .or_default()
+                .extend(account.storage.keys().copied().map(from_revm_u256));
+        }
+    }
+
+    let root_before = state_trie.root();
+    let (proofs, storage_proofs) = state_trie.proofs(&accessed)?;
+    for tx in &txs {
+        state_trie.apply(&db, &tx.state)?;
+    }
+    let storage_trace = StorageTrace {
+        root_before,
+        root_after: state_trie.root(),
+        proofs: Some(proofs),
+        storage_proofs,
+        deletion_proofs: state_trie.into_deletion_proofs(),
+    };
+
+    let block = &config.block_constants;
+    let header = EthBlock {
+        parent_hash: config
+            .history_hashes
+            .last()
+            .map(|hash| H256(hash.to_be_bytes()))
+            .unwrap_or_else(|| H256(block.difficulty.to_be_bytes())),
+        number: Some(block.number),
+        author: Some(coinbase),
+        gas_limit: block.gas_limit,
+        timestamp: block.timestamp,
+        difficulty: block.difficulty,
+        base_fee_per_gas: Some(block.base_fee),
+        state_root: storage_trace.root_after,
+        ..Default::default()
+    };
+
+    let mut codes = db
+        .contracts
+        .values()
+        .map(Bytecode::original_bytes)
+        .filter(|code| !code.is_empty())
+        .map(|code| BytecodeTrace {
+            hash: hash_code(&code),
+            code: code.to_vec().into(),
+        })
+        .collect::<Vec<_>>();
+    codes.sort_by_key(|code| code.hash);
+
+    let withdraw_trie_root = db
+        .storage_ref(
+            to_revm_address(*message_queue::ADDRESS),
+            to_revm_u256(message_queue::WITHDRAW_TRIE_ROOT_SLOT),
+        )
+        .expect("EmptyDB is infallible");
+    let coinbase_info = db
+        .accounts
+        .get(&to_revm_address(coinbase))
+        .map(|account| &account.info);
+
+    Ok(BlockTrace {
+        chain_id: config.chain_id,
+        coinbase: account_trace(&db, coinbase, coinbase_info),
+        header,
+        transactions: config
+            .transactions
+            .iter()
+            .map(|tx| transaction_trace(tx, config.chain_id))
+            .collect(),
+        execution_results: config
+            .transactions
+            .iter()
+            .zip(txs)
+            .map(|(tx, tx_trace)| execution_result(&db, tx, tx_trace, coinbase))
+            .collect(),
+        codes,
+        storage_trace,
+        tx_storage_trace: vec![],
+        start_l1_queue_index: config.l1_queue_index,
+        withdraw_trie_root: H256(from_revm_u256(withdraw_trie_root).to_be_bytes()),
+    })
+}
+
+/// Status of an account, which is zero for non-existing accounts.
+#[cfg(feature = "scroll")]
+fn account_trace(
+    db: &CacheDB<EmptyDB>,
+    address: Address,
+    info: Option<&AccountInfo>,
+) -> AccountTrace {
+    match info.filter(|info| !info.is_empty()) {
+        Some(info) => {
+            let code = code(db, info);
+            AccountTrace {
+                address,
+                nonce: info.nonce,
+                balance: from_revm_u256(info.balance),
+                keccak_code_hash: hash_code_keccak(&code),
+                poseidon_code_hash: hash_code_poseidon(&code),
+                code_size: code.len() as u64,
+            }
+        }
+        None => AccountTrace {
+            address,
+            ..Default::default()
+        },
+    }
+}
+
+#[cfg(feature = "scroll")]
+fn transaction_trace(tx: &Transaction, chain_id: u64) -> TransactionTrace {
+    let type_ = if is_l1_msg(tx) {
+        0x7e
+    } else {
+        match tx.tx_type {
+            TxType::Eip2930 => 1,
+            TxType::Eip1559 => 2,
+            TxType::Eip4844 => 3,
+            TxType::Eip7702 => 4,
+            _ => 0,
+        }
+    };
+    TransactionTrace {
+        tx_hash: tx.hash,
+        type_,
+        nonce: tx.nonce.as_u64(),
+        gas: tx.gas_limit.as_u64(),
+        gas_price: tx.gas_fee_cap.or(tx.gas_price).unwrap_or_default(),
+        gas_tip_cap: tx.gas_tip_cap,
+        gas_fee_cap: tx.gas_fee_cap,
+        from: tx.from,
+        to: tx.to,
+        chain_id: chain_id.into(),
+        value: tx.value,
+        data: tx.call_data.clone(),
+        is_create: tx.to.is_none(),
+        access_list: tx
+            .access_list
+            .as_ref()
+            .map(|access_list| access_list.0.clone()),
+        max_fee_per_blob_gas: tx.tx_type.is_eip4844().then_some(tx.max_fee_per_blob_gas),
+        blob_versioned_hashes: tx.blob_versioned_hashes.clone(),
+        authorization_list: tx.authorization_list.clone(),
+        v: tx.v.into(),
+        r: tx.r,
+        s: tx.s,
+    }
+}
+
+#[cfg(feature = "scroll")]
+fn execution_result(
+    db: &CacheDB<EmptyDB>,
+    tx: &Transaction,
+    mut tx_trace: TxTrace,
+    coinbase: Address,
+) -> l2_types::ExecutionResult {
+    let state = std::mem::take(&mut tx_trace.state);
+    let account_after = |address: Address| {
+        let info = state
+            .get(&to_revm_address(address))
+            .map(|account| &account.info);
+        account_trace(db, address, info)
+    };
+    let from = account_after(tx.from);
+    let to = tx.to.map(account_after);
+    let account_created = match &tx_trace.result {
+        ExecutionResult::Success {
+            output: Output::Create(_, address),
+            ..
+        } => address.map(from_revm_address).map(account_after),
+        _ => None,
+    };
+    let callee_code = tx
+        .to
+        .and_then(|to| tx_trace.prestate.get(&to))
+        .and_then(|prestate| prestate.code.clone());
+
+    l2_types::ExecutionResult {
+        l1_fee: from_revm_u256(tx_trace.l1_fee),
+        gas: tx_trace.result.gas_used(),
+        failed: tx_trace.failed(),
+        return_value: tx_trace.return_value(),
+        account_after: [
+            Some(from.clone()),
+            to.clone().or_else(|| account_created.clone()),
+            Some(account_after(coinbase)),
+        ]
+        .into_iter()
+        .flatten()
+        .collect(),
+        from: Some(from),
+        to,
+        account_created,
+        code_hash: callee_code.as_ref().map(|code| hash_code_poseidon(code)),
+        byte_code: callee_code.map(|code| format!("0x{}", hex::encode(code))),
+        call_trace: tx_trace.call_trace(),
+        exec_steps: std::mem::take(&mut tx_trace.logger.steps)
+            .into_iter()
+            .map(exec_step)
+            .collect(),
+        prestate: tx_trace.prestate,
+    }
+}
+
+#[cfg(feature = "scroll")]
+fn exec_step(step: GethExecStep) -> ExecStep {
+    ExecStep {
+        pc: step.pc.0 as u64,
+        op: step.op,
+        gas: step.gas.0,
+        gas_cost: step.gas_cost.0,
+        refund: step.refund.0,
+        depth: step.depth as isize,
+        error: step.error,
+        #[cfg(feature = "enable-stack")]
+        stack: Some(step.stack.0),
+        #[cfg(feature = "enable-memory")]
+        memory: Some(
+            step.memory
+                .0
+                .chunks(32)
+                .map(Word::from_big_endian)
+                .collect(),
+        ),
+        #[cfg(feature = "enable-storage")]
+        storage: Some(step.storage.0),
+        extra_data: None,
+    }
+}
@@ -0,0 +1,361 @@
+//! revm inspector recording the struct-logs and the call frames of a transaction, in the same
+//! shape as the geth `structLogger` and `callTracer`.
+
+use super::from_revm_address;
+#[cfg(any(feature = "enable-stack", feature = "enable-storage"))]
+use super::from_revm_u256;
+use crate::LoggerConfig;
+#[cfg(feature = "enable-memory")]
+use eth_types::evm_types::Memory;
+#[cfg(feature = "enable-stack")]
+use eth_types::evm_types::Stack;
+#[cfg(feature = "enable-storage")]
+use eth_types::{evm_types::Storage, Word};
+use eth_types::{
+    evm_types::{Gas, GasCost, OpcodeId, ProgramCounter},
+    Address, Bytes, GethCallTrace, GethExecError, GethExecStep, U256,
+};
+use revm::{
+    interpreter::{
+        CallInputs, CallOutcome, CallScheme, CreateInputs, CreateOutcome, InstructionResult,
+        Interpreter, InterpreterResult,
+    },
+    primitives::CreateScheme,
+    Database, EvmContext, Inspector,
+};
+#[cfg(feature = "enable-storage")]
+use std::collections::HashMap;
+
+/// A frame of the call trace, converted into a [`GethCallTrace`] once the transaction is done.
+#[derive(Debug)]
+pub(crate) struct CallFrame {
+    call_type: &'static str,
+    from: Address,
+    to: Option<Address>,
+    gas: u64,
+    pub(crate) gas_used: u64,
+    output: Bytes,
+    error: Option<GethExecError>,
+    calls: Vec<CallFrame>,
+}
+
+impl From<CallFrame> for GethCallTrace {
+    fn from(frame: CallFrame) -> Self {
+        GethCallTrace::new(
+            frame.call_type.to_string(),
+            frame.from,
+            frame.to,
+            U256::from(frame.gas_used),
+            (!frame.output.is_empty()).then_some(frame.output),
+            frame.error.map(|error| error.to_string()),
+            frame.calls.into_iter().map(From::from).collect(),
+        )
+    }
+}
+
+/// Inspector reproducing the output of the geth tracers used by `geth-utils`.
+#[derive(Debug, Default)]
+pub(crate) struct StructLogger {
+    config: LoggerConfig,
+    /// Recorded steps.
+    pub(crate) steps: Vec<GethExecStep>,
+    /// Gas left before the step being executed.
+    gas_before: u64,
+    /// Refund counters of the frames in the call stack, indexed by depth - 1. Their sum is the
+    /// global refund counter reported by geth.
+    refunds: Vec<i64>,
+    /// Storage accessed by SLOAD and SSTORE so far, per contract.
+    #[cfg(feature = "enable-storage")]
+    storage: HashMap<Address, HashMap<Word, Word>>,
+    /// Error of the last step that failed before execution.
+    last_error: Option<GethExecError>,
+    /// Call frames that have been entered but not exited yet.
+    frames: Vec<CallFrame>,
+    /// The outermost call frame, once the transaction is done.
+    pub(crate) call_trace: Option<CallFrame>,
+}
+
+impl StructLogger {
+    pub(crate) fn new(config: LoggerConfig) -> Self {
+        Self {
+            config,
+            ..Default::default()
+        }
+    }
+
+    fn enter_frame(
+        &mut self,
+        call_type: &'static str,
+        from: Address,
+        to: Option<Address>,
+        gas: u64,
+    ) {
+        self.frames.push(CallFrame {
+            call_type,
+            from,
+            to,
+            gas,
+            gas_used: 0,
+            output: Bytes::default(),
+            error: None,
+            calls: vec![],
+        });
+    }
+
+    fn exit_frame(&mut self, result: &InterpreterResult, error: Option<GethExecError>) {
+        let mut frame = self
+            .frames
+            .pop()
+            .expect("frame entered before being exited");
+        frame.gas_used = frame.gas.saturating_sub(result.gas.remaining());
+        if error.is_none() || error == Some(GethExecError::ExecutionReverted) {
+            frame.output = result.output.to_vec().into();
+        }
+        frame.error = error;
+        match self.frames.last_mut() {
+            Some(parent) => parent.calls.push(frame),
+            None => self.call_trace = Some(frame),
+        }
+    }
+
+    /// Error of a frame that returned with `result`.
+    fn frame_error(&self, result: InstructionResult) -> Option<GethExecError> {
+        if result.is_ok() {
+            return None;
+        }
+        Some(match result {
+            InstructionResult::Revert => GethExecError::ExecutionReverted,
+            InstructionResult::CallTooDeep => GethExecError::Depth,
+            InstructionResult::OutOfFunds => GethExecError::InsufficientBalance,
+            InstructionResult::CreateCollision => GethExecError::ContractAddressCollision,
+            InstructionResult::NonceOverflow => GethExecError::NonceUintOverflow,
+            InstructionResult::InvalidJump => GethExecError::InvalidJump,
+            InstructionResult::StateChangeDuringStaticCall => GethExecError::WriteProtection,
+            InstructionResult::OutOfOffset => GethExecError::ReturnDataOutOfBounds,
+            InstructionResult::CreateContractSizeLimit => GethExecError::MaxCodeSizeExceeded,
+            InstructionResult::CreateInitCodeSizeLimit => GethExecError::MaxInitCodeSizeExceeded,
+            InstructionResult::CreateContractStartingWithEF => GethExecError::InvalidCode,
+            InstructionResult::OverflowPayment => GethExecError::GasUintOverflow,
+            InstructionResult::StackUnderflow
+            | InstructionResult::StackOverflow
+            | InstructionResult::OpcodeNotFound
+            | InstructionResult::InvalidFEOpcode
+            | InstructionResult::NotActivated => self
+                .last_error
+                .unwrap_or(GethExecError::InvalidOpcode(OpcodeId::INVALID(0xfe))),
+            // geth fails precompiles with their own error messages, which all consume the
+            // whole gas like an out of gas error.
+            _ => GethExecError::OutOfGas,
+        })
+    }
+}
+
+/// Error of a step that fails before being executed, which geth reports in the struct-log.
+fn step_error(result: InstructionResult, op: OpcodeId, stack_len: usize) -> Option<GethExecError> {
+    let (min_stack_ptr, max_stack_ptr) = op.valid_stack_ptr_range();
+    match result {
+        InstructionResult::OutOfGas
+        | InstructionResult::MemoryOOG
+        | InstructionResult::MemoryLimitOOG
+        | InstructionResult::InvalidOperandOOG => Some(GethExecError::OutOfGas),
+        InstructionResult::StackUnderflow => Some(GethExecError::StackUnderflow {
+            stack_len: stack_len as u64,
+            required: 1024 - max_stack_ptr as u64,
+        }),
+        InstructionResult::StackOverflow => Some(GethExecError::StackOverflow {
+            stack_len: stack_len as u64,
+            limit: 1024 - min_stack_ptr as u64,
+        }),
+        InstructionResult::OpcodeNotFound
+        | InstructionResult::InvalidFEOpcode
+        | InstructionResult::NotActivated => Some(GethExecError::InvalidOpcode(op)),
+        _ => None,
+    }
+}
+
+impl<DB: Database> Inspector<DB> for StructLogger {
+    fn step(&mut self, interp: &mut Interpreter, context: &mut EvmContext<DB>) {
+        let depth = context.journaled_state.depth() as usize;
+        self.refunds.resize(depth, 0);
+        self.refunds[depth - 1] = interp.gas.refunded();
+        let refund = self.refunds.iter().sum::<i64>().max(0) as u64;
+
+        let op = OpcodeId::from(interp.current_opcode());
+        #[cfg(feature = "enable-stack")]
+        let stack = if self.config.disable_stack {
+            Stack::new()
+        } else {
+            Stack(
+                interp
+                    .stack
+                    .data()
+                    .iter()
+                    .copied()
+                    .map(from_revm_u256)
+                    .collect(),
+            )
+        };
+        #[cfg(feature = "enable-memory")]
+        let memory = if self.config.enable_memory {
+            Memory(interp.shared_memory.context_memory().to_vec())
+        } else {
+            Memory::default()
+        };
+        #[cfg(feature = "enable-storage")]
+        let storage = if self.config.disable_storage {
+            Storage::empty()
+        } else {
+            self.capture_storage(interp, context, op)
+        };
+
+        self.gas_before = interp.gas.remaining();
+        self.steps.push(GethExecStep {
+            pc: ProgramCounter(interp.program_counter()),
+            op,
+            gas: Gas(self.gas_before),
+            gas_cost: GasCost(0),
+            refund: Gas(refund),
+            depth: depth as u16,
+            error: None,
+            #[cfg(feature = "enable-stack")]
+            stack,
+            #[cfg(feature = "enable-memory")]
+            memory,
+            #[cfg(feature = "enable-storage")]
+            storage,
+        });
+    }
+
+    fn step_end(&mut self, interp: &mut Interpreter, _context: &mut EvmContext<DB>) {
+        let step = self
+            .steps
+            .last_mut()
+            .expect("step_end is called after step");
+        match step_error(interp.instruction_result, step.op, interp.stack.len()) {
+            // geth only charges the constant gas of a step that fails before execution.
+            Some(error) => {
+                step.error = Some(error);
+                step.gas_cost = step.op.constant_gas_cost();
+                self.last_error = Some(error);
+            }
+            None => {
+                step.gas_cost = GasCost(self.gas_before.saturating_sub(interp.gas.remaining()));
+            }
+        }
+    }
+
+    fn call(
+        &mut self,
+        _context: &mut EvmContext<DB>,
+        inputs: &mut CallInputs,
+    ) -> Option<CallOutcome> {
+        let (call_type, from) = match inputs.scheme {
+            CallScheme::Call => ("CALL", inputs.caller),
+            CallScheme::CallCode => ("CALLCODE", inputs.caller),
+            CallScheme::DelegateCall => ("DELEGATECALL", inputs.target_address),
+            CallScheme::StaticCall => ("STATICCALL", inputs.caller),
+        };
+        self.enter_frame(
+            call_type,
+            from_revm_address(from),
+            Some(from_revm_address(inputs.bytecode_address)),
+            inputs.gas_limit,
+        );
+        None
+    }
+
+    fn call_end(
+        &mut self,
+        _context: &mut EvmContext<DB>,
+        _inputs: &CallInputs,
+        outcome: CallOutcome,
+    ) -> CallOutcome {
+        let error = self.frame_error(outcome.result.result);
+        self.exit_frame(&outcome.result, error);
+        outcome
+    }
+
+    fn create(
+        &mut self,
+        context: &mut EvmContext<DB>,
+        inputs: &mut CreateInputs,
+    ) -> Option<CreateOutcome> {
+        // Unlike CALL, geth doesn't include the gas forwarded to the init code in the cost of
+        // the CREATE step.
+        if context.journaled_state.depth() > 0 {
+            if let Some(step) = self.steps.last_mut().filter(|step| step.op.is_create()) {
+                step.gas_cost.0 = step.gas_cost.0.saturating_sub(inputs.gas_limit);
+            }
+        }
+        let call_type = match inputs.scheme {
+            CreateScheme::Create => "CREATE",
+            CreateScheme::Create2 { .. } => "CREATE2",
+        };
+        self.enter_frame(
+            call_type,
+            from_revm_address(inputs.caller),
+            None,
+            inputs.gas_limit,
+        );
+        None
+    }
+
+    fn create_end(
+        &mut self,
+        context: &mut EvmContext<DB>,
+        _inputs: &CreateInputs,
+        outcome: CreateOutcome,
+    ) -> CreateOutcome {
+        let mut error = self.frame_error(outcome.result.result);
+        // The init code returned successfully but there is not enough gas left to store the
+        // deployed code.
+        let child_depth = context.journaled_state.depth() as u16 + 1;
+        if error == Some(GethExecError::OutOfGas)
+            && self.steps.last().map_or(false, |step| {
+                step.depth == child_depth && step.op == OpcodeId::RETURN && step.error.is_none()
+            })
+        {
+            error = Some(GethExecError::CodeStoreOutOfGas);
+        }
+        if let Some(frame) = self.frames.last_mut() {
+            frame.to = outcome.address.map(from_revm_address);
+        }
+        self.exit_frame(&outcome.result, error);
+        outcome
+    }
+}
+
+#[cfg(feature = "enable-storage")]
+impl StructLogger {
+    /// Storage of the current contract, captured by geth on SLOAD and SSTORE steps only.
+    fn capture_storage<DB: Database>(
+        &mut self,
+        interp: &Interpreter,
+        context: &mut EvmContext<DB>,
+        op: OpcodeId,
+    ) -> Storage {
+        let stack = interp.stack.data();
+        let address = interp.contract.target_address;
+        let (key, value) = match op {
+            OpcodeId::SLOAD if !stack.is_empty() => {
+                let key = stack[stack.len() - 1];
+                let value = context
+                    .journaled_state
+                    .state
+                    .get(&address)
+                    .and_then(|account| account.storage.get(&key))
+                    .map(|slot| slot.present_value())
+                    .or_else(|| context.db.storage(address, key).ok())
+                    .unwrap_or_default();
+                (key, value)
+            }
+            OpcodeId::SSTORE if stack.len() >= 2 => {
+                (stack[stack.len() - 1], stack[stack.len() - 2])
+            }
+            _ => return Storage::empty(),
+        };
+        let storage = self.storage.entry(from_revm_address(address)).or_default();
+        storage.insert(from_revm_u256(key), from_revm_u256(value));
+        Storage::new(storage.clone())
+    }
+}
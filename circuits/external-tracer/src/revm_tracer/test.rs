@@ -0,0 +1,181 @@
+//! Differential tests against the traces of `geth-utils`.
+
+use crate::{geth, revm_tracer, LoggerConfig, TraceConfig};
+use eth_types::{
+    address, bytecode,
+    geth_types::{Account, BlockConstants, Transaction},
+    Bytecode, Word,
+};
+use std::collections::HashMap;
+
+fn account(address: &str, balance: u64, code: Bytecode, storage: HashMap<Word, Word>) -> Account {
+    Account {
+        address: address!(address),
+        nonce: Word::one(),
+        balance: Word::from(balance),
+        code: code.to_vec().into(),
+        storage,
+    }
+}
+
+fn trace_config(accounts: Vec<Account>, to: Option<&str>, call_data: Vec<u8>) -> TraceConfig {
+    let from = address!("0x000000000000000000000000000000000000cafe");
+    let tx = Transaction {
+        from,
+        to: to.map(|to| address!(to)),
+        nonce: Word::one(),
+        gas_limit: Word::from(1_000_000u64),
+        gas_price: Some(Word::one()),
+        call_data: call_data.into(),
+        ..Default::default()
+    };
+    TraceConfig {
+        chain_id: 1338,
+        history_hashes: vec![Word::from(0xbeefu64)],
+        block_constants: BlockConstants {
+            coinbase: address!("0x00000000000000000000000000000000c014ba5e"),
+            timestamp: Word::from(0x10u64),
+            number: 0xcafeu64.into(),
+            difficulty: Word::from(0x200000u64),
+            gas_limit: Word::from(10_000_000u64),
+            base_fee: Word::zero(),
+        },
+        accounts: accounts
+            .into_iter()
+            .chain([account(
+                "0x000000000000000000000000000000000000cafe",
+                1 << 40,
+                Bytecode::default(),
+                HashMap::new(),
+            )])
+            .map(|account| (account.address, account))
+            .collect(),
+        transactions: vec![tx],
+        logger_config: LoggerConfig {
+            enable_memory: true,
+            ..Default::default()
+        },
+        chain_config: None,
+        #[cfg(feature = "scroll")]
+        l1_queue_index: 0,
+    }
+}
+
+fn assert_same_traces(config: &TraceConfig) {
+    let expected = geth::trace(config).unwrap();
+    let traces = revm_tracer::trace(config).unwrap();
+    assert_eq!(traces, expected);
+
+    #[cfg(feature = "scroll")]
+    {
+        let expected = geth::l2trace(config).unwrap().storage_trace;
+        let storage_trace = revm_tracer::l2trace(config).unwrap().storage_trace;
+        assert_eq!(storage_trace.root_before, expected.root_before);
+        assert_eq!(storage_trace.root_after, expected.root_after);
+    }
+}
+
+#[test]
+fn call_with_storage_and_memory() {
+    let callee = bytecode! {
+        PUSH1(0x2a)
+        PUSH1(0x00)
+        MSTORE
+        PUSH1(0x20)
+        PUSH1(0x00)
+        RETURN
+    };
+    let caller = bytecode! {
+        PUSH1(0x00)
+        SLOAD
+        PUSH1(0x01)
+        ADD
+        PUSH1(0x00)
+        SSTORE
+        PUSH1(0x20)
+        PUSH1(0x00)
+        PUSH1(0x00)
+        PUSH1(0x00)
+        PUSH1(0x00)
+        PUSH20(address!("0x00000000000000000000000000000000000000b0"))
+        GAS
+        CALL
+        PUSH1(0x00)
+        MLOAD
+        PUSH1(0x01)
+        SSTORE
+        STOP
+    };
+    let config = trace_config(
+        vec![
+            account(
+                "0x00000000000000000000000000000000000000a0",
+                0,
+                caller,
+                HashMap::from([
+                    (Word::zero(), Word::from(7u64)),
+                    (Word::from(2u64), Word::one()),
+                ]),
+            ),
+            account(
+                "0x00000000000000000000000000000000000000b0",
+                0,
+                callee,
+                HashMap::new(),
+            ),
+        ],
+        Some("0x00000000000000000000000000000000000000a0"),
+        vec![],
+    );
+    assert_same_traces(&config);
+}
+
+#[test]
+fn revert_and_out_of_gas() {
+    let code = bytecode! {
+        PUSH1(0x00)
+        CALLDATALOAD
+        PUSH1(0x10)
+        JUMPI
+        PUSH1(0x01)
+        PUSH1(0x00)
+        SSTORE
+        PUSH1(0x00)
+        PUSH1(0x00)
+        REVERT
+        JUMPDEST
+        PUSH1(0x10)
+        JUMP
+    };
+    let account = account(
+        "0x00000000000000000000000000000000000000a0",
+        0,
+        code,
+        HashMap::new(),
+    );
+    for call_data in [vec![], vec![1; 32]] {
+        let config = trace_config(
+            vec![account.clone()],
+            Some("0x00000000000000000000000000000000000000a0"),
+            call_data,
+        );
+        assert_same_traces(&config);
+    }
+}
+
+#[test]
+fn create() {
+    let init_code = bytecode! {
+        PUSH1(0x01)
+        PUSH1(0x00)
+        SSTORE
+        PUSH2(0x6001)
+        PUSH1(0x00)
+        MSTORE
+        PUSH1(0x02)
+        PUSH1(0x1e)
+        RETURN
+    };
+    let config = trace_config(vec![], None, init_code.to_vec());
+    assert_same_traces(&config);
+}
@@ -0,0 +1,187 @@
+//! In-memory zktrie of the traced state, producing the storage trace of a block.
+
+use super::{code, from_revm_address, from_revm_u256};
+use eth_types::{
+    geth_types::Account,
+    l2_types::{AccountTrieProofs, StorageTrieProofs},
+    utils::{hash_code_keccak, hash_code_poseidon},
+    Address, Bytes, Error, ToBigEndian, Word, H256,
+};
+use mpt_zktrie::{builder::init_hash_scheme, state::ZkMemoryDb, AccountData, ZkTrie};
+use revm::{
+    db::{CacheDB, EmptyDB},
+    primitives::EvmState,
+};
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    fmt::Debug,
+};
+
+fn trie_error(err: impl Debug) -> Error {
+    Error::TracingError(format!("zktrie: {err:?}"))
+}
+
+/// State trie with the storage tries of its accounts.
+pub(crate) struct StateTrie {
+    trie: ZkTrie,
+    storage_tries: HashMap<Address, ZkTrie>,
+    deletion_proofs: Vec<Bytes>,
+}
+
+impl StateTrie {
+    /// Build the trie of the accounts before the block.
+    pub(crate) fn new(accounts: &BTreeMap<Address, Account>) -> Result<Self, Error> {
+        init_hash_scheme();
+        let trie = ZkMemoryDb::new()
+            .new_trie(&[0; 32])
+            .ok_or_else(|| trie_error("empty trie"))?;
+        let mut state_trie = Self {
+            trie,
+            storage_tries: HashMap::new(),
+            deletion_proofs: vec![],
+        };
+        for (address, account) in accounts {
+            for (key, value) in &account.storage {
+                state_trie.set_storage(*address, *key, *value)?;
+            }
+            if !account.is_empty() {
+                state_trie.update_account(
+                    *address,
+                    account.nonce.as_u64(),
+                    account.balance,
+                    &account.code,
+                )?;
+            }
+        }
+        Ok(state_trie)
+    }
+
+    pub(crate) fn root(&self) -> H256 {
+        H256(self.trie.root())
+    }
+
+    fn storage_trie(&mut self, address: Address) -> Result<&mut ZkTrie, Error> {
+        if !self.storage_tries.contains_key(&address) {
+            let trie = self
+                .trie
+                .get_db()
+                .new_trie(&[0; 32])
+                .ok_or_else(|| trie_error("empty trie"))?;
+            self.storage_tries.insert(address, trie);
+        }
+        Ok(self
+            .storage_tries
+            .get_mut(&address)
+            .expect("inserted above"))
+    }
+
+    fn set_storage(&mut self, address: Address, key: Word, value: Word) -> Result<(), Error> {
+        if value.is_zero() {
+            return self.delete_storage(address, key);
+        }
+        self.storage_trie(address)?
+            .update_store(&key.to_be_bytes(), &value.to_be_bytes())
+            .map_err(trie_error)
+    }
+
+    /// Delete a slot, recording the proof of the sibling which is needed to replay the
+    /// deletion on a partial trie.
+    fn delete_storage(&mut self, address: Address, key: Word) -> Result<(), Error> {
+        let key = key.to_be_bytes();
+        let trie = self.storage_trie(address)?;
+        if trie.get_store(&key).is_none() {
+            return Ok(());
+        }
+        trie.delete(&key);
+        let proof = trie.prove(&key).map_err(trie_error)?;
+        self.deletion_proofs
+            .extend(proof.into_iter().map(Bytes::from));
+        Ok(())
+    }
+
+    fn update_account(
+        &mut self,
+        address: Address,
+        nonce: u64,
+        balance: Word,
+        code: &[u8],
+    ) -> Result<(), Error> {
+        let storage_root = H256(self.storage_trie(address)?.root());
+        let data = AccountData {
+            nonce,
+            balance,
+            keccak_code_hash: hash_code_keccak(code),
+            poseidon_code_hash: hash_code_poseidon(code),
+            code_size: code.len() as u64,
+            storage_root,
+        };
+        self.trie
+            .update_account(address.as_bytes(), &data.into())
+            .map_err(trie_error)
+    }
+
+    fn delete_account(&mut self, address: Address) {
+        self.storage_tries.remove(&address);
+        if self.trie.get_account(address.as_bytes()).is_some() {
+            self.trie.delete(address.as_bytes());
+        }
+    }
+
+    /// Proofs of the accessed accounts and slots in the current state.
+    pub(crate) fn proofs(
+        &mut self,
+        accessed: &BTreeMap<Address, BTreeSet<Word>>,
+    ) -> Result<(AccountTrieProofs, StorageTrieProofs), Error> {
+        let mut proofs = AccountTrieProofs::new();
+        let mut storage_proofs = StorageTrieProofs::new();
+        for (address, keys) in accessed {
+            let proof = self.trie.prove(address.as_bytes()).map_err(trie_error)?;
+            proofs.insert(*address, proof.into_iter().map(Bytes::from).collect());
+            if keys.is_empty() {
+                continue;
+            }
+            let storage_trie = self.storage_trie(*address)?;
+            let address_proofs = storage_proofs.entry(*address).or_default();
+            for key in keys {
+                let key = key.to_be_bytes();
+                let proof = storage_trie.prove(&key).map_err(trie_error)?;
+                address_proofs.insert(H256(key), proof.into_iter().map(Bytes::from).collect());
+            }
+        }
+        Ok((proofs, storage_proofs))
+    }
+
+    /// Apply the accounts touched by a transaction.
+    pub(crate) fn apply(&mut self, db: &CacheDB<EmptyDB>, state: &EvmState) -> Result<(), Error> {
+        for (address, account) in state.iter().filter(|(_, account)| account.is_touched()) {
+            let address = from_revm_address(*address);
+            if account.is_selfdestructed() {
+                self.delete_account(address);
+                continue;
+            }
+            for (key, slot) in account.changed_storage_slots() {
+                self.set_storage(
+                    address,
+                    from_revm_u256(*key),
+                    from_revm_u256(slot.present_value()),
+                )?;
+            }
+            if account.info.is_empty() {
+                self.delete_account(address);
+            } else {
+                let code = code(db, &account.info);
+                self.update_account(
+                    address,
+                    account.info.nonce,
+                    from_revm_u256(account.info.balance),
+                    &code,
+                )?;
+            }
+        }
+        Ok(())
+    }
+
+    pub(crate) fn into_deletion_proofs(self) -> Vec<Bytes> {
+        self.deletion_proofs
+    }
+}
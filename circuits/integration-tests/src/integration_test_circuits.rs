@@ -78,6 +78,8 @@ const MAX_EC_ADD: usize = 10;
 const MAX_EC_MUL: usize = 10;
 /// Max number of EcPairing ops.
 const MAX_EC_PAIRING: usize = 4;
+/// Max number of BLS12-381 G1 addition ops.
+const MAX_BLS12_G1_ADD: usize = 10;
 
 const CIRCUITS_PARAMS: CircuitsParams = CircuitsParams {
     max_rws: MAX_RWS,
@@ -97,6 +99,7 @@ const CIRCUITS_PARAMS: CircuitsParams = CircuitsParams {
         ec_add: MAX_EC_ADD,
         ec_mul: MAX_EC_MUL,
         ec_pairing: MAX_EC_PAIRING,
+        bls12_g1_add: MAX_BLS12_G1_ADD,
     },
 };
 
@@ -1,8 +1,12 @@
 #![feature(lazy_cell)]
 //! Mock types and functions to generate GethData used for tests
 
-use eth_types::{address, bytecode, bytecode::Bytecode, word, Address, Bytes, Word};
-use ethers_signers::LocalWallet;
+use eth_types::{
+    address, bytecode, bytecode::Bytecode, geth_types::SetCodeAuthorization, word, Address, Bytes,
+    Word, U64,
+};
+use ethers_core::utils::keccak256;
+use ethers_signers::{LocalWallet, Signer};
 use rand::SeedableRng;
 use rand_chacha::ChaCha20Rng;
 use std::sync::LazyLock;
@@ -99,6 +103,29 @@ pub fn gwei(x: u64) -> Word {
     Word::from(x) * Word::from(10u64.pow(9))
 }
 
+/// Generate an EIP7702 authorization of `wallet` delegating to `address`.
+pub fn sign_authorization(
+    wallet: &LocalWallet,
+    chain_id: u64,
+    address: Address,
+    nonce: u64,
+) -> SetCodeAuthorization {
+    let mut authorization = SetCodeAuthorization {
+        chain_id: Word::from(chain_id),
+        address,
+        nonce: U64::from(nonce),
+        ..Default::default()
+    };
+    let sig = wallet
+        .sign_hash(keccak256(authorization.signing_message()).into())
+        .expect("sign mock authorization");
+    // `sign_hash` returns an electrum style v, convert it to [0, 1]
+    authorization.y_parity = U64::from(sig.v - 27);
+    authorization.r = sig.r;
+    authorization.s = sig.s;
+    authorization
+}
+
 /// Holds the parameters for generating mock EVM bytecode for a contract call
 pub struct MockCallBytecodeParams {
     /// The address to call with the generated bytecode
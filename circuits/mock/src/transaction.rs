@@ -2,14 +2,17 @@
 
 use super::{MOCK_ACCOUNTS, MOCK_CHAIN_ID};
 use eth_types::{
-    geth_types::Transaction as GethTransaction, word, AccessList, Address, Bytes, Hash,
-    Transaction, Word, U64,
+    geth_types::{
+        get_rlp_signed, get_rlp_unsigned, SetCodeAuthorization, Transaction as GethTransaction,
+    },
+    word, AccessList, Address, Bytes, Hash, Transaction, Word, H256, U64,
 };
 use ethers_core::{
     rand::{CryptoRng, RngCore},
     types::{
         Eip1559TransactionRequest, Eip2930TransactionRequest, OtherFields, TransactionRequest,
     },
+    utils::keccak256,
 };
 use ethers_signers::{LocalWallet, Signer};
 use rand::SeedableRng;
@@ -161,6 +164,9 @@ pub struct MockTransaction {
     pub access_list: AccessList,
     pub max_priority_fee_per_gas: Word,
     pub max_fee_per_gas: Word,
+    pub max_fee_per_blob_gas: Word,
+    pub blob_versioned_hashes: Vec<H256>,
+    pub authorization_list: Vec<SetCodeAuthorization>,
     pub chain_id: u64,
 }
 
@@ -186,6 +192,9 @@ impl Default for MockTransaction {
             access_list: AccessList::default(),
             max_priority_fee_per_gas: Word::zero(),
             max_fee_per_gas: Word::zero(),
+            max_fee_per_blob_gas: Word::zero(),
+            blob_versioned_hashes: vec![],
+            authorization_list: vec![],
             chain_id: MOCK_CHAIN_ID,
         }
     }
@@ -193,6 +202,24 @@ impl Default for MockTransaction {
 
 impl From<MockTransaction> for Transaction {
     fn from(mock: MockTransaction) -> Self {
+        // ethers-rs has no EIP 4844 and 7702 txs, so their fields go into the extra fields.
+        let mut other = OtherFields::default();
+        if mock.transaction_type == U64::from(3) {
+            other.insert(
+                "maxFeePerBlobGas".to_string(),
+                serde_json::to_value(mock.max_fee_per_blob_gas).unwrap(),
+            );
+            other.insert(
+                "blobVersionedHashes".to_string(),
+                serde_json::to_value(&mock.blob_versioned_hashes).unwrap(),
+            );
+        }
+        if mock.transaction_type == U64::from(4) {
+            other.insert(
+                "authorizationList".to_string(),
+                serde_json::to_value(&mock.authorization_list).unwrap(),
+            );
+        }
         Transaction {
             hash: mock.hash.unwrap_or_default(),
             nonce: mock.nonce,
@@ -213,7 +240,7 @@ impl From<MockTransaction> for Transaction {
             max_priority_fee_per_gas: Some(mock.max_priority_fee_per_gas),
             max_fee_per_gas: Some(mock.max_fee_per_gas),
             chain_id: Some(mock.chain_id.into()),
-            other: OtherFields::default(),
+            other,
         }
     }
 }
@@ -324,6 +351,27 @@ impl MockTransaction {
         self
     }
 
+    /// Set max_fee_per_blob_gas field for the MockTransaction.
+    pub fn max_fee_per_blob_gas(&mut self, max_fee_per_blob_gas: Word) -> &mut Self {
+        self.max_fee_per_blob_gas = max_fee_per_blob_gas;
+        self
+    }
+
+    /// Set blob_versioned_hashes field for the MockTransaction.
+    pub fn blob_versioned_hashes(&mut self, blob_versioned_hashes: Vec<H256>) -> &mut Self {
+        self.blob_versioned_hashes = blob_versioned_hashes;
+        self
+    }
+
+    /// Set authorization_list field for the MockTransaction.
+    pub fn authorization_list(
+        &mut self,
+        authorization_list: Vec<SetCodeAuthorization>,
+    ) -> &mut Self {
+        self.authorization_list = authorization_list;
+        self
+    }
+
     /// Set chain_id field for the MockTransaction.
     pub fn chain_id(&mut self, chain_id: u64) -> &mut Self {
         self.chain_id = chain_id;
@@ -333,7 +381,11 @@ impl MockTransaction {
     /// Consumes the mutable ref to the MockTransaction returning the structure
     /// by value.
     pub fn build(&mut self) -> Self {
-        if self.transaction_type == U64::from(2) {
+        if self.transaction_type == U64::from(4) {
+            return self.build_7702();
+        } else if self.transaction_type == U64::from(3) {
+            return self.build_4844();
+        } else if self.transaction_type == U64::from(2) {
             return self.build_1559();
         } else if self.transaction_type == U64::from(1) {
             return self.build_2930();
@@ -501,6 +553,48 @@ impl MockTransaction {
 
     // helper `sign_transaction_sync` in ethers-rs lib compute V using legacy tx pattern(V =
     // recover_id + 2 * chain_id + 35), this method converts above V value to origin recover_id.
+    /// build eip 4844 type tx
+    pub fn build_4844(&mut self) -> Self {
+        self.build_sighash_signed("4844")
+    }
+
+    /// build eip 7702 type tx
+    pub fn build_7702(&mut self) -> Self {
+        self.build_sighash_signed("7702")
+    }
+
+    /// build a typed tx which ethers-rs can not sign, by signing its sighash directly
+    fn build_sighash_signed(&mut self, eip: &str) -> Self {
+        match (self.v, self.r, self.s) {
+            (None, None, None) => {
+                // Compute sig params and set them in case we have a wallet as `from` attr.
+                if self.from.is_wallet() && self.hash.is_none() {
+                    let sighash = keccak256(get_rlp_unsigned(&Transaction::from(self.to_owned())));
+                    let sig = self
+                        .from
+                        .as_wallet()
+                        .sign_hash(sighash.into())
+                        .unwrap_or_else(|_| panic!("sign mock eip {eip} tx"));
+
+                    // `sign_hash` returns an electrum style v, convert it to [0, 1]
+                    self.sig_data((sig.v - 27, sig.r, sig.s));
+                } else {
+                    #[cfg(feature = "scroll")]
+                    panic!("{eip} type tx must have signature data, otherwise will be treated as L1Msg type in trace.go of l2geth");
+                }
+            }
+            _ => panic!("Either all or none of the SigData params have to be set"),
+        }
+
+        // Compute tx hash in case is not already set
+        if self.hash.is_none() {
+            let tmp_tx = Transaction::from(self.to_owned());
+            self.hash(keccak256(get_rlp_signed(&tmp_tx)).into());
+        }
+
+        self.to_owned()
+    }
+
     pub(crate) fn normalize_v(v: u64, chain_id: u64) -> u64 {
         if v > 1 {
             v - chain_id * 2 - 35
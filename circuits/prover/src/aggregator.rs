@@ -3,4 +3,4 @@ mod verifier;
 
 pub use self::prover::{check_chunk_hashes, Prover};
 pub use aggregator::{BatchData, BatchHash, MAX_AGG_SNARKS};
-pub use verifier::Verifier;
+pub use verifier::{BatchVerifier, FinalVerifier, OabeVerifier, Verifier, VerifyMode};
@@ -3,7 +3,7 @@ use crate::{
     config::{LayerId, AGG_DEGREES},
     consts::{AGG_KECCAK_ROW, AGG_VK_FILENAME, CHUNK_PROTOCOL_FILENAME},
     io::{force_to_read, try_to_read},
-    BatchProof, BatchProvingTask, ChunkProof,
+    metrics, BatchProof, BatchProvingTask, ChunkProof,
 };
 use aggregator::{ChunkInfo, MAX_AGG_SNARKS};
 use anyhow::{bail, Result};
@@ -67,6 +67,7 @@ impl Prover {
     }
 
     // Return the EVM proof for verification.
+    // The metrics of proving the batch are written as `metrics_batch_{name}`.
     pub fn gen_agg_evm_proof(
         &mut self,
         batch: BatchProvingTask,
@@ -75,11 +76,23 @@ impl Prover {
     ) -> Result<BatchProof> {
         let name = name.map_or_else(|| batch.identifier(), |name| name.to_string());
 
-        let layer3_snark = self.load_or_gen_last_agg_snark(&name, batch, output_dir)?;
+        metrics::set_label("batch", &name);
+        let result = self.gen_named_agg_evm_proof(batch, &name, output_dir);
+        metrics::take_and_dump(&format!("batch_{name}"));
+        result
+    }
+
+    fn gen_named_agg_evm_proof(
+        &mut self,
+        batch: BatchProvingTask,
+        name: &str,
+        output_dir: Option<&str>,
+    ) -> Result<BatchProof> {
+        let layer3_snark = self.load_or_gen_last_agg_snark(name, batch, output_dir)?;
 
         // Load or generate final compression thin EVM proof (layer-4).
         let evm_proof = self.prover_impl.load_or_gen_comp_evm_proof(
-            &name,
+            name,
             LayerId::Layer4.id(),
             true,
             LayerId::Layer4.degree(),
@@ -1,54 +1,319 @@
 use crate::{
     common,
-    config::{LAYER4_CONFIG_PATH, LAYER4_DEGREE},
+    config::{Layer4Params, LAYER4_DEGREE, VERIFY_MODE},
     consts::{agg_vk_filename, DEPLOYMENT_CODE_FILENAME},
-    io::force_to_read,
+    io::{force_to_read, try_to_read},
     BatchProof,
 };
-use aggregator::CompressionCircuit;
+use aggregator::{CompressionCircuit, CompressionWideParams};
+use anyhow::{bail, Error};
 use halo2_proofs::{
-    halo2curves::bn256::{Bn256, G1Affine},
+    halo2curves::bn256::{Bn256, Fr, G1Affine},
     plonk::VerifyingKey,
     poly::kzg::commitment::ParamsKZG,
 };
-use snark_verifier_sdk::verify_evm_calldata;
-use std::env;
+use snark_verifier_sdk::{verify_evm_calldata, CircuitExt};
+use std::str::FromStr;
 
+/// How the final proofs are verified.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum VerifyMode {
+    /// Run the verifier contract, which needs its deployment code.
+    #[default]
+    Evm,
+    /// Check the proof against the vk and the KZG pairing, no EVM involved.
+    Native,
+    /// Run both and reject the proof if their verdicts differ.
+    CrossCheck,
+}
+
+impl FromStr for VerifyMode {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "evm" => Self::Evm,
+            "native" => Self::Native,
+            "cross-check" => Self::CrossCheck,
+            _ => bail!("verify mode should be one of evm, native or cross-check, got {s}"),
+        })
+    }
+}
+
+/// Verifier of the final compressed proofs sent on-chain.
 #[derive(Debug)]
-pub struct Verifier {
+pub struct FinalVerifier<C: CircuitExt<Fr>> {
     // Make it public for testing with inner functions (unnecessary for FFI).
-    pub inner: common::Verifier<CompressionCircuit>,
-    deployment_code: Vec<u8>,
+    pub inner: common::Verifier<C>,
+    deployment_code: Option<Vec<u8>>,
+    mode: VerifyMode,
 }
 
-impl Verifier {
+/// Verifier of batch proofs, compressed by layer4.
+pub type BatchVerifier = FinalVerifier<CompressionCircuit<Layer4Params>>;
+/// Verifier of OABE decryption proofs, compressed by the wide compression circuit.
+pub type OabeVerifier = FinalVerifier<CompressionCircuit<CompressionWideParams>>;
+pub type Verifier = BatchVerifier;
+
+impl<C: CircuitExt<Fr>> FinalVerifier<C> {
     pub fn new(
         params: ParamsKZG<Bn256>,
         vk: VerifyingKey<G1Affine>,
         deployment_code: Vec<u8>,
+    ) -> Self {
+        Self::with_mode(params, vk, Some(deployment_code), *VERIFY_MODE)
+    }
+
+    /// Without deployment code, only the native mode can be used.
+    pub fn with_mode(
+        params: ParamsKZG<Bn256>,
+        vk: VerifyingKey<G1Affine>,
+        deployment_code: Option<Vec<u8>>,
+        mode: VerifyMode,
     ) -> Self {
         let inner = common::Verifier::new(params, vk);
+        Self::from_inner(inner, deployment_code, mode)
+    }
+
+    fn from_inner(
+        inner: common::Verifier<C>,
+        deployment_code: Option<Vec<u8>>,
+        mode: VerifyMode,
+    ) -> Self {
+        if mode != VerifyMode::Native && deployment_code.is_none() {
+            log::warn!("No deployment code of the verifier contract, verify proofs natively");
+        }
 
         Self {
             inner,
             deployment_code,
+            mode,
         }
     }
 
+    pub fn from_params_dir(
+        params_dir: &str,
+        degree: u32,
+        raw_vk: &[u8],
+        deployment_code: Option<Vec<u8>>,
+    ) -> Self {
+        let inner = common::Verifier::from_params_dir(params_dir, degree, raw_vk);
+        Self::from_inner(inner, deployment_code, *VERIFY_MODE)
+    }
+
+    pub fn mode(&self) -> VerifyMode {
+        match self.deployment_code {
+            Some(_) => self.mode,
+            None => VerifyMode::Native,
+        }
+    }
+
+    pub fn verify_agg_evm_proof(&self, batch_proof: BatchProof) -> bool {
+        match (self.mode(), self.deployment_code.as_ref()) {
+            (VerifyMode::Evm, Some(deployment_code)) => {
+                verify_evm_calldata(deployment_code.clone(), batch_proof.calldata())
+            }
+            (VerifyMode::CrossCheck, Some(deployment_code)) => {
+                let native = self.verify_natively(batch_proof.clone());
+                let evm = verify_evm_calldata(deployment_code.clone(), batch_proof.calldata());
+                if native != evm {
+                    log::error!("Native verdict {native} differs from the EVM verdict {evm}");
+                }
+                native && evm
+            }
+            _ => self.verify_natively(batch_proof),
+        }
+    }
+
+    /// Verify directly against the vk, whatever the mode.
+    pub fn verify_natively(&self, batch_proof: BatchProof) -> bool {
+        self.inner
+            .verify_evm_proof_natively(&batch_proof.proof_to_verify())
+    }
+}
+
+impl BatchVerifier {
+    /// The deployment code is optional, the proofs are verified natively without it.
     pub fn from_dirs(params_dir: &str, assets_dir: &str) -> Self {
         let raw_vk = force_to_read(assets_dir, &agg_vk_filename());
-        let deployment_code = force_to_read(assets_dir, &DEPLOYMENT_CODE_FILENAME);
+        let deployment_code = try_to_read(assets_dir, &DEPLOYMENT_CODE_FILENAME);
+
+        Self::from_params_dir(params_dir, *LAYER4_DEGREE, &raw_vk, deployment_code)
+    }
+}
 
-        env::set_var("COMPRESSION_CONFIG", &*LAYER4_CONFIG_PATH);
-        let inner = common::Verifier::from_params_dir(params_dir, *LAYER4_DEGREE, &raw_vk);
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Proof;
+    use halo2_proofs::{
+        circuit::{Layouter, SimpleFloorPlanner, Value},
+        plonk::{
+            keygen_pk, keygen_vk, Advice, Circuit, Column, ConstraintSystem, Error, Instance,
+            ProvingKey,
+        },
+    };
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+    use snark_verifier_sdk::gen_evm_proof_shplonk;
 
-        Self {
-            inner,
-            deployment_code,
+    /// Accumulator and PI of a batch proof.
+    const NUM_INSTANCES: usize = 12 + 32;
+    /// Deploys a contract without code, which accepts any calldata.
+    const ACCEPT_ALL: &[u8] = &[0x00];
+    /// Deploys a contract which reverts on any calldata.
+    const REJECT_ALL: &[u8] = &[
+        0x64, 0x60, 0x00, 0x60, 0x00, 0xfd, 0x60, 0x00, 0x52, 0x60, 0x05, 0x60, 0x1b, 0xf3,
+    ];
+
+    /// Circuit exposing its advice cells as instances, shaped like a final proof without
+    /// accumulator.
+    #[derive(Clone)]
+    struct TestCircuit(Vec<Fr>);
+
+    impl Circuit<Fr> for TestCircuit {
+        type Config = (Column<Advice>, Column<Instance>);
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            Self(vec![Fr::zero(); NUM_INSTANCES])
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fr>) -> Self::Config {
+            let advice = meta.advice_column();
+            let instance = meta.instance_column();
+            meta.enable_equality(advice);
+            meta.enable_equality(instance);
+            (advice, instance)
+        }
+
+        fn synthesize(
+            &self,
+            (advice, instance): Self::Config,
+            mut layouter: impl Layouter<Fr>,
+        ) -> Result<(), Error> {
+            let cells = layouter.assign_region(
+                || "instances",
+                |mut region| {
+                    self.0
+                        .iter()
+                        .enumerate()
+                        .map(|(row, value)| {
+                            region.assign_advice(|| "value", advice, row, || Value::known(*value))
+                        })
+                        .collect::<Result<Vec<_>, _>>()
+                },
+            )?;
+            for (row, cell) in cells.iter().enumerate() {
+                layouter.constrain_instance(cell.cell(), instance, row)?;
+            }
+            Ok(())
         }
     }
 
-    pub fn verify_agg_evm_proof(&self, batch_proof: BatchProof) -> bool {
-        verify_evm_calldata(self.deployment_code.clone(), batch_proof.calldata())
+    impl CircuitExt<Fr> for TestCircuit {
+        fn num_instance(&self) -> Vec<usize> {
+            vec![NUM_INSTANCES]
+        }
+
+        fn instances(&self) -> Vec<Vec<Fr>> {
+            vec![self.0.clone()]
+        }
+    }
+
+    struct Fixture {
+        params: ParamsKZG<Bn256>,
+        pk: ProvingKey<G1Affine>,
+        proof: Vec<u8>,
+        instances: Vec<Vec<Fr>>,
+    }
+
+    impl Fixture {
+        fn new() -> Self {
+            let params = ParamsKZG::<Bn256>::setup(6, XorShiftRng::seed_from_u64(0));
+            let circuit = TestCircuit((0..NUM_INSTANCES as u64).map(Fr::from).collect());
+            let vk = keygen_vk(&params, &circuit).unwrap();
+            let pk = keygen_pk(&params, vk, &circuit).unwrap();
+            let instances = circuit.instances();
+            let proof = gen_evm_proof_shplonk(
+                &params,
+                &pk,
+                circuit,
+                instances.clone(),
+                &mut XorShiftRng::seed_from_u64(1),
+            );
+            Self {
+                params,
+                pk,
+                proof,
+                instances,
+            }
+        }
+
+        fn verifier(
+            &self,
+            deployment_code: Option<&[u8]>,
+            mode: VerifyMode,
+        ) -> FinalVerifier<TestCircuit> {
+            FinalVerifier::with_mode(
+                self.params.clone(),
+                self.pk.get_vk().clone(),
+                deployment_code.map(<[u8]>::to_vec),
+                mode,
+            )
+        }
+
+        fn batch_proof(&self, proof: &[u8], instances: &[Vec<Fr>]) -> BatchProof {
+            BatchProof::from(Proof::new(proof.to_vec(), instances, Some(&self.pk)))
+        }
+
+        fn valid(&self) -> BatchProof {
+            self.batch_proof(&self.proof, &self.instances)
+        }
+
+        fn tampered_proof(&self) -> BatchProof {
+            let mut proof = self.proof.clone();
+            *proof.last_mut().unwrap() ^= 1;
+            self.batch_proof(&proof, &self.instances)
+        }
+
+        fn tampered_instances(&self) -> BatchProof {
+            let mut instances = self.instances.clone();
+            instances[0][NUM_INSTANCES - 1] += Fr::one();
+            self.batch_proof(&self.proof, &instances)
+        }
+    }
+
+    #[test]
+    fn test_verify_natively() {
+        let fixture = Fixture::new();
+        let verifier = fixture.verifier(None, VerifyMode::Native);
+
+        assert!(verifier.verify_agg_evm_proof(fixture.valid()));
+        assert!(!verifier.verify_agg_evm_proof(fixture.tampered_proof()));
+        assert!(!verifier.verify_agg_evm_proof(fixture.tampered_instances()));
+    }
+
+    // The verifier contract needs solc, so stub contracts stand in for it to exercise how the
+    // verdicts are combined.
+    #[test]
+    fn test_cross_check() {
+        let fixture = Fixture::new();
+
+        let verifier = fixture.verifier(Some(ACCEPT_ALL), VerifyMode::CrossCheck);
+        assert!(verifier.verify_agg_evm_proof(fixture.valid()));
+        assert!(!verifier.verify_agg_evm_proof(fixture.tampered_proof()));
+        assert!(!verifier.verify_agg_evm_proof(fixture.tampered_instances()));
+
+        // a verdict of the EVM differing from the native one rejects the proof
+        let verifier = fixture.verifier(Some(REJECT_ALL), VerifyMode::CrossCheck);
+        assert!(!verifier.verify_agg_evm_proof(fixture.valid()));
+        let verifier = fixture.verifier(Some(REJECT_ALL), VerifyMode::Native);
+        assert!(verifier.verify_agg_evm_proof(fixture.valid()));
+
+        // without deployment code, proofs are only verified natively
+        let verifier = fixture.verifier(None, VerifyMode::CrossCheck);
+        assert_eq!(verifier.mode(), VerifyMode::Native);
+        assert!(verifier.verify_agg_evm_proof(fixture.valid()));
     }
 }
@@ -0,0 +1,178 @@
+use aggregator::{
+    CompressionCircuit, CompressionParams, CompressionThinParams, CompressionWideParams,
+};
+use anyhow::{anyhow, bail, Result};
+use clap::{Parser, ValueEnum};
+use halo2_proofs::{halo2curves::bn256::Fr, plonk::Circuit};
+use prover::{utils::chunk_trace_file_to_witness_block, zkevm::circuit::dummy_witness_block};
+use std::path::{Path, PathBuf};
+use zkevm_circuits::{
+    bls12_381_circuit::Bls12381Circuit,
+    bytecode_circuit::BytecodeCircuit,
+    copy_circuit::CopyCircuit,
+    ecc_circuit::EccCircuit,
+    evm_circuit::EvmCircuit,
+    exp_circuit::ExpCircuit,
+    fraud_circuit::MyFraudCircuit,
+    keccak_circuit::KeccakCircuit,
+    modexp_circuit::ModExpCircuit,
+    mpt_circuit::MptCircuit,
+    pairing_circuit::MyEccCircuit,
+    poseidon_circuit::PoseidonCircuit,
+    rlp_circuit_fsm::RlpCircuit,
+    sha256_circuit::SHA256Circuit,
+    sig_circuit::SigCircuit,
+    state_circuit::StateCircuit,
+    super_circuit::params::ScrollSuperCircuit,
+    tx_circuit::TestTxCircuit,
+    util::{circuit_report::CircuitReport, SubCircuit},
+    witness::{Block, Transaction},
+};
+
+/// Layout and cost report of a circuit: columns, gates, lookups, permutation and regions
+#[derive(Parser, Debug)]
+#[clap(author, version, about, long_about = None)]
+struct Args {
+    /// Circuit to report on
+    #[clap(long, value_enum)]
+    circuit: CircuitKind,
+
+    /// Chunk trace file to build the circuit from, the dummy witness block by default
+    #[clap(long)]
+    trace: Option<PathBuf>,
+
+    /// Print the report as JSON instead of markdown
+    #[clap(long)]
+    json: bool,
+
+    /// Render the column and region layout to this image, with the `dev-graph` feature
+    #[clap(long)]
+    layout: Option<PathBuf>,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum CircuitKind {
+    Super,
+    Evm,
+    State,
+    Tx,
+    Bytecode,
+    Copy,
+    Keccak,
+    Exp,
+    Rlp,
+    Mpt,
+    Poseidon,
+    Sig,
+    Ecc,
+    Modexp,
+    Sha256,
+    Bls12381,
+    Pairing,
+    Fraud,
+    CompressionWide,
+    CompressionThin,
+}
+
+fn sub_circuit_report<C: SubCircuit<Fr> + Circuit<Fr>>(
+    name: &str,
+    block: &Block,
+    layout: Option<&Path>,
+) -> Result<CircuitReport> {
+    let report = CircuitReport::from_block::<Fr, C>(name, block)?;
+    if let Some(path) = layout {
+        render_layout(&C::new_from_block(block), &report, path)?;
+    }
+    Ok(report)
+}
+
+/// The compression circuit is only built from a snark, so only its configuration is reported
+fn compression_report<P: CompressionParams>(
+    name: &str,
+    layout: Option<&Path>,
+) -> Result<CircuitReport> {
+    if layout.is_some() {
+        bail!("the layout of {name} needs a snark to compress");
+    }
+    Ok(CircuitReport::configure::<Fr, CompressionCircuit<P>>(name))
+}
+
+#[cfg(feature = "dev-graph")]
+fn render_layout<C: Circuit<Fr>>(circuit: &C, report: &CircuitReport, path: &Path) -> Result<()> {
+    let k = report.k.expect("k of a synthesized circuit");
+    zkevm_circuits::util::circuit_report::render_layout(
+        k,
+        circuit,
+        &report.name,
+        path,
+        (1024, 3480),
+    )
+    .map_err(|e| anyhow!("render layout of {}: {e}", report.name))
+}
+
+#[cfg(not(feature = "dev-graph"))]
+fn render_layout<C: Circuit<Fr>>(_: &C, report: &CircuitReport, _: &Path) -> Result<()> {
+    Err(anyhow!(
+        "rendering the layout of {} requires the dev-graph feature",
+        report.name
+    ))
+}
+
+fn main() -> Result<()> {
+    env_logger::init();
+
+    let args = Args::parse();
+    let name = args
+        .circuit
+        .to_possible_value()
+        .expect("no skipped variant")
+        .get_name()
+        .to_string();
+    let layout = args.layout.as_deref();
+
+    let block = || match &args.trace {
+        Some(path) => chunk_trace_file_to_witness_block(path),
+        None => dummy_witness_block(),
+    };
+    let report = match args.circuit {
+        CircuitKind::Super => sub_circuit_report::<ScrollSuperCircuit>(&name, &block()?, layout),
+        CircuitKind::Evm => sub_circuit_report::<EvmCircuit<Fr>>(&name, &block()?, layout),
+        CircuitKind::State => sub_circuit_report::<StateCircuit<Fr>>(&name, &block()?, layout),
+        CircuitKind::Tx => sub_circuit_report::<TestTxCircuit<Fr>>(&name, &block()?, layout),
+        CircuitKind::Bytecode => {
+            sub_circuit_report::<BytecodeCircuit<Fr>>(&name, &block()?, layout)
+        }
+        CircuitKind::Copy => sub_circuit_report::<CopyCircuit<Fr>>(&name, &block()?, layout),
+        CircuitKind::Keccak => sub_circuit_report::<KeccakCircuit<Fr>>(&name, &block()?, layout),
+        CircuitKind::Exp => sub_circuit_report::<ExpCircuit<Fr>>(&name, &block()?, layout),
+        CircuitKind::Rlp => {
+            sub_circuit_report::<RlpCircuit<Fr, Transaction>>(&name, &block()?, layout)
+        }
+        CircuitKind::Mpt => sub_circuit_report::<MptCircuit<Fr>>(&name, &block()?, layout),
+        CircuitKind::Poseidon => {
+            sub_circuit_report::<PoseidonCircuit<Fr>>(&name, &block()?, layout)
+        }
+        CircuitKind::Sig => sub_circuit_report::<SigCircuit<Fr>>(&name, &block()?, layout),
+        CircuitKind::Ecc => sub_circuit_report::<EccCircuit<Fr, 9>>(&name, &block()?, layout),
+        CircuitKind::Modexp => sub_circuit_report::<ModExpCircuit<Fr>>(&name, &block()?, layout),
+        CircuitKind::Sha256 => sub_circuit_report::<SHA256Circuit<Fr>>(&name, &block()?, layout),
+        CircuitKind::Bls12381 => {
+            sub_circuit_report::<Bls12381Circuit<Fr>>(&name, &block()?, layout)
+        }
+        CircuitKind::Pairing => sub_circuit_report::<MyEccCircuit<Fr, 9>>(&name, &block()?, layout),
+        CircuitKind::Fraud => sub_circuit_report::<MyFraudCircuit<Fr, 9>>(&name, &block()?, layout),
+        CircuitKind::CompressionWide => compression_report::<CompressionWideParams>(&name, layout),
+        CircuitKind::CompressionThin => compression_report::<CompressionThinParams>(&name, layout),
+    }?;
+
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        println!("{report}");
+    }
+    if let Some(path) = layout {
+        println!("Rendered the layout of {} to {path:?}", report.name);
+    }
+
+    Ok(())
+}
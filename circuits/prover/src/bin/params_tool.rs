@@ -0,0 +1,48 @@
+use anyhow::Result;
+use clap::{Parser, Subcommand};
+use prover::setup::{import_params, write_imported_params};
+use std::path::PathBuf;
+
+/// KZG params utility
+#[derive(Parser, Debug)]
+#[clap(author, version, about, long_about = None)]
+struct Args {
+    #[clap(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Convert a ptau (or halo2 params) transcript to the params file `load_params` expects
+    Import {
+        /// Ceremony transcript to import
+        #[clap(long)]
+        transcript: PathBuf,
+
+        /// Degree of the params to emit
+        #[clap(long)]
+        degree: u32,
+
+        /// Directory to write `params{degree}` to
+        #[clap(long, default_value = "./params")]
+        params_dir: String,
+    },
+}
+
+fn main() -> Result<()> {
+    env_logger::init();
+
+    match Args::parse().command {
+        Command::Import {
+            transcript,
+            degree,
+            params_dir,
+        } => {
+            let params = import_params(&transcript, degree)?;
+            let params_path = write_imported_params(&params, &params_dir)?;
+            println!("Imported params of degree {degree} from {transcript:?} to {params_path}");
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,39 @@
+use anyhow::Result;
+use clap::{Parser, Subcommand};
+use prover::utils::convert_block_traces_to_binary;
+use std::path::PathBuf;
+
+/// Block trace utility
+#[derive(Parser, Debug)]
+#[clap(author, version, about, long_about = None)]
+struct Args {
+    #[clap(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Convert JSON block traces, plain or JSON RPC results, to a binary chunk trace file
+    Convert {
+        /// JSON block traces, in the order of the chunk
+        #[clap(required = true)]
+        inputs: Vec<PathBuf>,
+
+        /// Chunk trace file to write
+        #[clap(long)]
+        output: PathBuf,
+    },
+}
+
+fn main() -> Result<()> {
+    env_logger::init();
+
+    match Args::parse().command {
+        Command::Convert { inputs, output } => {
+            let num_blocks = convert_block_traces_to_binary(&inputs, &output)?;
+            println!("Converted {num_blocks} block traces to {output:?}");
+        }
+    }
+
+    Ok(())
+}
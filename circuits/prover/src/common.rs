@@ -1,5 +1,12 @@
+mod pk_store;
+mod proof_cache;
 mod prover;
 mod verifier;
 
-pub use self::{prover::Prover, verifier::Verifier};
+pub use self::{
+    pk_store::PkStore,
+    proof_cache::{ProofCache, ProofCacheKey},
+    prover::Prover,
+    verifier::Verifier,
+};
 pub use aggregator::{ChunkInfo, CompressionCircuit};
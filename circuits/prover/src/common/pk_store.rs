@@ -0,0 +1,284 @@
+use crate::{
+    config::PK_DIR,
+    io::serialize_vk,
+    utils::{short_git_version, GIT_VERSION},
+};
+use anyhow::{bail, Result};
+use halo2_proofs::{
+    halo2curves::bn256::{Fr, G1Affine},
+    plonk::{Circuit, ProvingKey, VerifyingKey},
+    SerdeFormat,
+};
+use sha2::{Digest, Sha256};
+use std::{
+    fs::{self, File},
+    io::{BufReader, BufWriter, Read, Write},
+    path::PathBuf,
+};
+use tempfile::NamedTempFile;
+
+/// Magic bytes at the start of every proving key file.
+const PK_MAGIC: &[u8; 8] = b"SCRLPKEY";
+/// Bump whenever the header layout or the serde format of the key changes.
+pub const PK_FORMAT_VERSION: u32 = 1;
+const PK_SERDE_FORMAT: SerdeFormat = SerdeFormat::RawBytesUnchecked;
+
+/// Header written in front of the serialized proving key:
+///   magic: 8 bytes
+///   version: u32 LE
+///   degree: u32 LE
+///   vk_hash: 32 bytes, sha256 of the serialized vk
+///   git_version: u32 LE length followed by utf8 bytes
+#[derive(Debug, PartialEq, Eq)]
+struct PkHeader {
+    version: u32,
+    degree: u32,
+    vk_hash: [u8; 32],
+    git_version: String,
+}
+
+impl PkHeader {
+    fn write(&self, writer: &mut impl Write) -> Result<()> {
+        writer.write_all(PK_MAGIC)?;
+        writer.write_all(&self.version.to_le_bytes())?;
+        writer.write_all(&self.degree.to_le_bytes())?;
+        writer.write_all(&self.vk_hash)?;
+        writer.write_all(&(self.git_version.len() as u32).to_le_bytes())?;
+        writer.write_all(self.git_version.as_bytes())?;
+        Ok(())
+    }
+
+    fn read(reader: &mut impl Read) -> Result<Self> {
+        let mut magic = [0u8; 8];
+        reader.read_exact(&mut magic)?;
+        if &magic != PK_MAGIC {
+            bail!("not a proving key file");
+        }
+
+        let mut u32_bytes = [0u8; 4];
+        reader.read_exact(&mut u32_bytes)?;
+        let version = u32::from_le_bytes(u32_bytes);
+        reader.read_exact(&mut u32_bytes)?;
+        let degree = u32::from_le_bytes(u32_bytes);
+
+        let mut vk_hash = [0u8; 32];
+        reader.read_exact(&mut vk_hash)?;
+
+        reader.read_exact(&mut u32_bytes)?;
+        let mut git_version = vec![0u8; u32::from_le_bytes(u32_bytes) as usize];
+        reader.read_exact(&mut git_version)?;
+
+        Ok(Self {
+            version,
+            degree,
+            vk_hash,
+            git_version: String::from_utf8(git_version)?,
+        })
+    }
+}
+
+pub fn vk_hash(vk: &VerifyingKey<G1Affine>) -> [u8; 32] {
+    Sha256::digest(serialize_vk(vk)).into()
+}
+
+/// Directory of proving keys, one file per circuit id and git version. A key is only reused when
+/// the vk regenerated from the circuit still hashes to the one recorded in the file header.
+#[derive(Clone, Debug)]
+pub struct PkStore {
+    dir: PathBuf,
+}
+
+impl PkStore {
+    pub fn new(dir: impl Into<PathBuf>) -> Result<Self> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir)?;
+        Ok(Self { dir })
+    }
+
+    /// Build from `SCROLL_PROVER_PK_DIR`, or return `None` if it is unset.
+    pub fn from_env() -> Option<Self> {
+        if PK_DIR.is_empty() {
+            return None;
+        }
+
+        Self::new(PK_DIR.as_str())
+            .map_err(|err| log::warn!("Failed to use pk dir {}: {err}", *PK_DIR))
+            .ok()
+    }
+
+    pub fn path(&self, id: &str) -> PathBuf {
+        self.dir.join(format!("{id}_{}.pk", short_git_version()))
+    }
+
+    /// Load the proving key of `id` if it exists and still matches `vk`. Stale keys are reported
+    /// as `Ok(None)` so that the caller regenerates and overwrites them.
+    pub fn load<C: Circuit<Fr>>(
+        &self,
+        id: &str,
+        degree: u32,
+        vk: &VerifyingKey<G1Affine>,
+    ) -> Result<Option<ProvingKey<G1Affine>>> {
+        let path = self.path(id);
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        // `ProvingKey::read` deserializes into owned buffers, so the file is streamed rather than
+        // mapped.
+        let mut reader = BufReader::new(File::open(&path)?);
+
+        let header = PkHeader::read(&mut reader)?;
+        if header.version != PK_FORMAT_VERSION {
+            log::warn!(
+                "Stale pk {path:?}: format version {} != {PK_FORMAT_VERSION}",
+                header.version
+            );
+            return Ok(None);
+        }
+        if header.degree != degree || header.vk_hash != vk_hash(vk) {
+            log::warn!(
+                "Stale pk {path:?}: written by {} for degree {}, vk changed since",
+                header.git_version,
+                header.degree
+            );
+            return Ok(None);
+        }
+
+        log::info!("Load pk of {id} from {path:?}");
+        let pk = ProvingKey::<G1Affine>::read::<_, C>(&mut reader, PK_SERDE_FORMAT)?;
+        // The key is read unchecked, so make sure its vk is the one the header was trusted for.
+        if vk_hash(pk.get_vk()) != header.vk_hash {
+            log::warn!("Stale pk {path:?}: vk of the key doesn't match the header");
+            return Ok(None);
+        }
+        Ok(Some(pk))
+    }
+
+    pub fn store(&self, id: &str, degree: u32, pk: &ProvingKey<G1Affine>) -> Result<()> {
+        let path = self.path(id);
+
+        let header = PkHeader {
+            version: PK_FORMAT_VERSION,
+            degree,
+            vk_hash: vk_hash(pk.get_vk()),
+            git_version: GIT_VERSION.to_string(),
+        };
+
+        // Each writer gets its own temp file in the store directory, so concurrent writers of the
+        // same key never interleave and the last rename wins with a complete file.
+        let tmp_file = NamedTempFile::new_in(&self.dir)?;
+        let mut writer = BufWriter::new(tmp_file);
+        header.write(&mut writer)?;
+        pk.write(&mut writer, PK_SERDE_FORMAT)?;
+        let tmp_file = writer.into_inner().map_err(|err| err.into_error())?;
+        tmp_file.as_file().sync_all()?;
+
+        tmp_file.persist(&path)?;
+        log::info!("Stored pk of {id} to {path:?}");
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use halo2_proofs::{
+        circuit::{Layouter, SimpleFloorPlanner, Value},
+        halo2curves::bn256::Bn256,
+        plonk::{keygen_pk, keygen_vk, Advice, Column, ConstraintSystem, Error, Fixed},
+        poly::{kzg::commitment::ParamsKZG, Rotation},
+    };
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    /// Circuit with a single gate, advice = fixed.
+    #[derive(Clone, Default)]
+    struct TestCircuit;
+
+    impl Circuit<Fr> for TestCircuit {
+        type Config = (Column<Advice>, Column<Fixed>);
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            Self
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fr>) -> Self::Config {
+            let advice = meta.advice_column();
+            let fixed = meta.fixed_column();
+            meta.create_gate("advice = fixed", |meta| {
+                vec![
+                    meta.query_advice(advice, Rotation::cur())
+                        - meta.query_fixed(fixed, Rotation::cur()),
+                ]
+            });
+            (advice, fixed)
+        }
+
+        fn synthesize(
+            &self,
+            (advice, fixed): Self::Config,
+            mut layouter: impl Layouter<Fr>,
+        ) -> Result<(), Error> {
+            layouter.assign_region(
+                || "advice = fixed",
+                |mut region| {
+                    region.assign_fixed(|| "fixed", fixed, 0, || Value::known(Fr::one()))?;
+                    region.assign_advice(|| "advice", advice, 0, || Value::known(Fr::one()))?;
+                    Ok(())
+                },
+            )
+        }
+    }
+
+    #[test]
+    fn test_pk_store_roundtrip() -> Result<()> {
+        let dir = std::env::temp_dir().join(format!("pk-store-{}", std::process::id()));
+        let store = PkStore::new(&dir)?;
+
+        let degree = 4;
+        let params = ParamsKZG::<Bn256>::setup(degree, XorShiftRng::seed_from_u64(0));
+        let vk = keygen_vk(&params, &TestCircuit)?;
+        let pk = keygen_pk(&params, vk.clone(), &TestCircuit)?;
+
+        assert!(store.load::<TestCircuit>("test", degree, &vk)?.is_none());
+        store.store("test", degree, &pk)?;
+        let loaded = store
+            .load::<TestCircuit>("test", degree, &vk)?
+            .expect("stored pk is loaded");
+        assert_eq!(vk_hash(loaded.get_vk()), vk_hash(&vk));
+
+        let mut stored = vec![];
+        pk.write(&mut stored, PK_SERDE_FORMAT)?;
+        let mut reloaded = vec![];
+        loaded.write(&mut reloaded, PK_SERDE_FORMAT)?;
+        assert_eq!(stored, reloaded);
+
+        // A key for another degree is stale, and no temp file is left behind.
+        assert!(store
+            .load::<TestCircuit>("test", degree + 1, &vk)?
+            .is_none());
+        assert_eq!(fs::read_dir(&dir)?.count(), 1);
+
+        // A key whose vk doesn't match its header is stale.
+        let other_params = ParamsKZG::<Bn256>::setup(degree, XorShiftRng::seed_from_u64(1));
+        let other_vk = keygen_vk(&other_params, &TestCircuit)?;
+        let other_pk = keygen_pk(&other_params, other_vk, &TestCircuit)?;
+        let mut writer = BufWriter::new(File::create(store.path("test"))?);
+        PkHeader {
+            version: PK_FORMAT_VERSION,
+            degree,
+            vk_hash: vk_hash(&vk),
+            git_version: GIT_VERSION.to_string(),
+        }
+        .write(&mut writer)?;
+        other_pk.write(&mut writer, PK_SERDE_FORMAT)?;
+        writer.flush()?;
+        drop(writer);
+        assert!(store.load::<TestCircuit>("test", degree, &vk)?.is_none());
+
+        fs::remove_dir_all(dir)?;
+        Ok(())
+    }
+}
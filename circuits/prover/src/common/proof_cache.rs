@@ -0,0 +1,278 @@
+use crate::config::{PROOF_CACHE_DIR, PROOF_CACHE_MAX_SIZE};
+use anyhow::Result;
+use serde_derive::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::{
+    collections::BTreeMap,
+    fmt,
+    fs::{self, File},
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+/// Bump whenever the key derivation or the layout of the cache changes.
+pub const PROOF_CACHE_VERSION: u32 = 2;
+const INDEX_FILENAME: &str = "index.json";
+
+/// Content key of a cached proof, the sha256 of everything the proof depends on:
+///   version: u32 LE
+///   id: u32 LE length followed by utf8 bytes
+///   degree: u32 LE
+///   vk_hash: 32 bytes, sha256 of the serialized vk, or of the git version and the params g2 and
+///     s_g2 for the circuits keyed by their params
+///   config: u32 LE length followed by the serialized circuit config
+///   input: u32 LE length followed by the serialized input of the circuit
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ProofCacheKey(String);
+
+impl ProofCacheKey {
+    pub fn new(id: &str, degree: u32, vk_hash: &[u8; 32], config: &[u8], input: &[u8]) -> Self {
+        let update_prefixed = |hasher: &mut Sha256, bytes: &[u8]| {
+            hasher.update((bytes.len() as u32).to_le_bytes());
+            hasher.update(bytes);
+        };
+
+        let mut hasher = Sha256::new();
+        hasher.update(PROOF_CACHE_VERSION.to_le_bytes());
+        update_prefixed(&mut hasher, id.as_bytes());
+        hasher.update(degree.to_le_bytes());
+        hasher.update(vk_hash);
+        update_prefixed(&mut hasher, config);
+        update_prefixed(&mut hasher, input);
+        Self(hex::encode(hasher.finalize()))
+    }
+}
+
+impl fmt::Display for ProofCacheKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+struct IndexEntry {
+    /// Name of the task the proof was generated for, only informative.
+    name: String,
+    size: u64,
+    /// Hex sha256 of the file content, checked on load.
+    sha256: String,
+    /// Increasing counter of the last load or store, the smallest is evicted first.
+    last_used: u64,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize)]
+struct Index {
+    version: u32,
+    entries: BTreeMap<String, IndexEntry>,
+}
+
+/// Directory of proofs addressed by content, with an index of the entries. The least recently
+/// used entries are evicted once the total size exceeds `max_size`. A directory should not be
+/// shared by provers running concurrently, as each of them rewrites the index.
+#[derive(Debug)]
+pub struct ProofCache {
+    dir: PathBuf,
+    max_size: Option<u64>,
+    index: Index,
+}
+
+impl ProofCache {
+    pub fn new(dir: impl Into<PathBuf>, max_size: Option<u64>) -> Result<Self> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir)?;
+
+        let index_path = dir.join(INDEX_FILENAME);
+        let index = match fs::read(&index_path) {
+            Ok(buf) => match serde_json::from_slice::<Index>(&buf) {
+                Ok(index) if index.version == PROOF_CACHE_VERSION => index,
+                Ok(index) => {
+                    log::warn!(
+                        "Reset proof cache {dir:?}: version {} != {PROOF_CACHE_VERSION}",
+                        index.version
+                    );
+                    Self::remove_entries(&dir, index.entries.keys());
+                    Index::default()
+                }
+                Err(err) => {
+                    log::warn!("Reset proof cache {dir:?}: invalid index: {err}");
+                    Index::default()
+                }
+            },
+            Err(_) => Index::default(),
+        };
+
+        Ok(Self {
+            dir,
+            max_size,
+            index: Index {
+                version: PROOF_CACHE_VERSION,
+                ..index
+            },
+        })
+    }
+
+    /// Build from `SCROLL_PROVER_PROOF_CACHE_DIR`, or return `None` if it is unset. The size
+    /// limit in bytes is read from `SCROLL_PROVER_PROOF_CACHE_MAX_SIZE`, 0 meaning unlimited.
+    pub fn from_env() -> Option<Self> {
+        if PROOF_CACHE_DIR.is_empty() {
+            return None;
+        }
+
+        let max_size = (*PROOF_CACHE_MAX_SIZE > 0).then_some(*PROOF_CACHE_MAX_SIZE);
+        Self::new(PROOF_CACHE_DIR.as_str(), max_size)
+            .map_err(|err| log::warn!("Failed to use proof cache {}: {err}", *PROOF_CACHE_DIR))
+            .ok()
+    }
+
+    fn entry_path(dir: &Path, key: &str) -> PathBuf {
+        dir.join(format!("{key}.proof"))
+    }
+
+    fn remove_entries<'a>(dir: &Path, keys: impl Iterator<Item = &'a String>) {
+        for key in keys {
+            let _ = fs::remove_file(Self::entry_path(dir, key));
+        }
+    }
+
+    fn next_use(&self) -> u64 {
+        self.index
+            .entries
+            .values()
+            .map(|entry| entry.last_used + 1)
+            .max()
+            .unwrap_or_default()
+    }
+
+    pub fn size(&self) -> u64 {
+        self.index.entries.values().map(|entry| entry.size).sum()
+    }
+
+    /// Load the proof of `key`. Entries which are missing or fail the integrity check are
+    /// dropped from the cache and reported as `Ok(None)`.
+    pub fn load(&mut self, key: &ProofCacheKey) -> Result<Option<Vec<u8>>> {
+        let Some(entry) = self.index.entries.get(&key.0) else {
+            return Ok(None);
+        };
+
+        let path = Self::entry_path(&self.dir, &key.0);
+        let buf = match fs::read(&path) {
+            Ok(buf) => buf,
+            Err(err) => {
+                log::warn!("Drop cached proof {key}: {err}");
+                self.remove(key)?;
+                return Ok(None);
+            }
+        };
+        if buf.len() as u64 != entry.size || hex::encode(Sha256::digest(&buf)) != entry.sha256 {
+            log::warn!("Drop cached proof {key} of {}: corrupted", entry.name);
+            self.remove(key)?;
+            return Ok(None);
+        }
+
+        log::info!("Load cached proof of {} from {path:?}", entry.name);
+        let last_used = self.next_use();
+        if let Some(entry) = self.index.entries.get_mut(&key.0) {
+            entry.last_used = last_used;
+        }
+        self.write_index()?;
+
+        Ok(Some(buf))
+    }
+
+    pub fn store(&mut self, key: &ProofCacheKey, name: &str, buf: &[u8]) -> Result<()> {
+        let path = Self::entry_path(&self.dir, &key.0);
+        let tmp_path = path.with_extension("proof.tmp");
+
+        let mut file = File::create(&tmp_path)?;
+        file.write_all(buf)?;
+        file.sync_all()?;
+        drop(file);
+        fs::rename(&tmp_path, &path)?;
+
+        let entry = IndexEntry {
+            name: name.to_string(),
+            size: buf.len() as u64,
+            sha256: hex::encode(Sha256::digest(buf)),
+            last_used: self.next_use(),
+        };
+        self.index.entries.insert(key.0.clone(), entry);
+        self.evict(key);
+        self.write_index()?;
+        log::info!("Stored proof of {name} to {path:?}");
+
+        Ok(())
+    }
+
+    fn remove(&mut self, key: &ProofCacheKey) -> Result<()> {
+        self.index.entries.remove(&key.0);
+        Self::remove_entries(&self.dir, [&key.0].into_iter());
+        self.write_index()
+    }
+
+    /// Evict the least recently used entries until the cache fits in its size, keeping `keep`.
+    fn evict(&mut self, keep: &ProofCacheKey) {
+        let Some(max_size) = self.max_size else {
+            return;
+        };
+
+        let mut size = self.size();
+        while size > max_size {
+            let Some((key, entry)) = self
+                .index
+                .entries
+                .iter()
+                .filter(|(key, _)| **key != keep.0)
+                .min_by_key(|(_, entry)| entry.last_used)
+                .map(|(key, entry)| (key.clone(), entry.clone()))
+            else {
+                break;
+            };
+            log::info!("Evict cached proof {key} of {}", entry.name);
+            self.index.entries.remove(&key);
+            Self::remove_entries(&self.dir, [&key].into_iter());
+            size -= entry.size;
+        }
+    }
+
+    fn write_index(&self) -> Result<()> {
+        let path = self.dir.join(INDEX_FILENAME);
+        let tmp_path = path.with_extension("json.tmp");
+        fs::write(&tmp_path, serde_json::to_vec(&self.index)?)?;
+        fs::rename(&tmp_path, &path)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(input: &[u8]) -> ProofCacheKey {
+        ProofCacheKey::new("layer1", 21, &[0; 32], b"{}", input)
+    }
+
+    #[test]
+    fn test_proof_cache() -> Result<()> {
+        let dir = std::env::temp_dir().join(format!("proof-cache-{}", std::process::id()));
+        let mut cache = ProofCache::new(&dir, Some(8))?;
+
+        assert_ne!(key(b"chunk-1"), key(b"chunk-2"));
+        cache.store(&key(b"chunk-1"), "1", b"proof-1")?;
+        assert_eq!(cache.load(&key(b"chunk-1"))?, Some(b"proof-1".to_vec()));
+        assert_eq!(cache.load(&key(b"chunk-2"))?, None);
+
+        // Evicted by size, and the index survives reopening the cache.
+        cache.store(&key(b"chunk-2"), "2", b"proof-2")?;
+        let mut cache = ProofCache::new(&dir, Some(8))?;
+        assert_eq!(cache.load(&key(b"chunk-1"))?, None);
+        assert_eq!(cache.load(&key(b"chunk-2"))?, Some(b"proof-2".to_vec()));
+
+        // Corrupted entries are dropped.
+        fs::write(ProofCache::entry_path(&dir, &key(b"chunk-2").0), b"proof-x")?;
+        assert_eq!(cache.load(&key(b"chunk-2"))?, None);
+        assert_eq!(cache.size(), 0);
+
+        fs::remove_dir_all(dir)?;
+        Ok(())
+    }
+}
@@ -1,3 +1,4 @@
+use super::{PkStore, ProofCache};
 use crate::utils::{load_params, param_path_for_degree};
 use halo2_proofs::{
     halo2curves::bn256::{Bn256, G1Affine},
@@ -12,6 +13,7 @@ mod compression;
 mod evm;
 mod inner;
 mod mock;
+mod oabe;
 mod utils;
 
 #[derive(Debug)]
@@ -20,6 +22,10 @@ pub struct Prover {
     params_map: BTreeMap<u32, ParamsKZG<Bn256>>,
     // Cached id -> pk
     pk_map: HashMap<String, ProvingKey<G1Affine>>,
+    // On-disk pk store, consulted before running keygen
+    pk_store: Option<PkStore>,
+    // Content-addressed snarks, consulted before proving
+    proof_cache: Option<ProofCache>,
 }
 
 impl Prover {
@@ -27,6 +33,8 @@ impl Prover {
         Self {
             params_map,
             pk_map: HashMap::new(),
+            pk_store: PkStore::from_env(),
+            proof_cache: ProofCache::from_env(),
         }
     }
 
@@ -65,6 +73,18 @@ impl Prover {
         Self {
             params_map,
             pk_map: HashMap::new(),
+            pk_store: PkStore::from_env(),
+            proof_cache: ProofCache::from_env(),
         }
     }
+
+    pub fn with_pk_store(mut self, pk_store: PkStore) -> Self {
+        self.pk_store = Some(pk_store);
+        self
+    }
+
+    pub fn with_proof_cache(mut self, proof_cache: ProofCache) -> Self {
+        self.proof_cache = Some(proof_cache);
+        self
+    }
 }
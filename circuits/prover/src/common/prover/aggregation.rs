@@ -2,6 +2,7 @@ use super::Prover;
 use crate::{
     config::layer_config_path,
     io::{load_snark, write_snark},
+    metrics::{start_phase, Phase},
     utils::gen_rng,
 };
 use aggregator::{AggregationCircuit, BatchHash, ChunkInfo, MAX_AGG_SNARKS};
@@ -19,15 +20,54 @@ impl Prover {
         chunk_hashes: &[ChunkInfo],
         previous_snarks: &[Snark],
     ) -> Result<Snark> {
+        let circuit = self.agg_circuit(id, degree, &mut rng, chunk_hashes, previous_snarks)?;
+        self.gen_snark(id, degree, &mut rng, circuit, "gen_agg_snark")
+    }
+
+    fn agg_circuit(
+        &mut self,
+        id: &str,
+        degree: u32,
+        rng: &mut (impl Rng + Send),
+        chunk_hashes: &[ChunkInfo],
+        previous_snarks: &[Snark],
+    ) -> Result<AggregationCircuit<MAX_AGG_SNARKS>> {
         env::set_var("AGGREGATION_CONFIG", layer_config_path(id));
 
         let batch_hash = BatchHash::construct(chunk_hashes);
 
-        let circuit: AggregationCircuit<MAX_AGG_SNARKS> =
-            AggregationCircuit::new(self.params(degree), previous_snarks, &mut rng, batch_hash)
-                .map_err(|err| anyhow!("Failed to construct aggregation circuit: {err:?}"))?;
+        let _timer = start_phase(Phase::CircuitBuild, id);
+        AggregationCircuit::new(self.params(degree), previous_snarks, rng, batch_hash)
+            .map_err(|err| anyhow!("Failed to construct aggregation circuit: {err:?}"))
+    }
 
-        self.gen_snark(id, degree, &mut rng, circuit, "gen_agg_snark")
+    /// Load the snark from the proof cache, keyed by the chunks and their snarks, or generate
+    /// and cache it.
+    fn load_cached_or_gen_agg_snark(
+        &mut self,
+        name: &str,
+        id: &str,
+        degree: u32,
+        chunk_hashes: &[ChunkInfo],
+        previous_snarks: &[Snark],
+    ) -> Result<Snark> {
+        let mut rng = gen_rng();
+        let circuit = self.agg_circuit(id, degree, &mut rng, chunk_hashes, previous_snarks)?;
+
+        let key = if self.proof_cache.is_some() {
+            let input = serde_json::to_vec(&(chunk_hashes, previous_snarks))?;
+            let config = std::fs::read(layer_config_path(id))?;
+            self.proof_cache_key(id, degree, &circuit, &config, &input)?
+        } else {
+            None
+        };
+        if let Some(snark) = self.load_cached_snark(key.as_ref()) {
+            return Ok(snark);
+        }
+
+        let snark = self.gen_snark(id, degree, &mut rng, circuit, "gen_agg_snark")?;
+        self.store_cached_snark(key.as_ref(), name, &snark);
+        Ok(snark)
     }
 
     pub fn load_or_gen_agg_snark(
@@ -49,8 +89,13 @@ impl Prover {
         match output_dir.and_then(|_| load_snark(&file_path).ok().flatten()) {
             Some(snark) => Ok(snark),
             None => {
-                let rng = gen_rng();
-                let result = self.gen_agg_snark(id, degree, rng, chunk_hashes, previous_snarks);
+                let result = self.load_cached_or_gen_agg_snark(
+                    name,
+                    id,
+                    degree,
+                    chunk_hashes,
+                    previous_snarks,
+                );
                 if let (Some(_), Ok(snark)) = (output_dir, &result) {
                     write_snark(&file_path, snark);
                 }
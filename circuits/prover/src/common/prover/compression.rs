@@ -1,14 +1,14 @@
 use super::Prover;
 use crate::{
-    config::layer_config_path,
+    config::{Layer1Params, Layer2Params, Layer4Params, LayerId},
     io::{load_snark, write_snark},
+    metrics::{start_phase, Phase},
     utils::gen_rng,
 };
-use aggregator::CompressionCircuit;
-use anyhow::{anyhow, Result};
+use aggregator::{CompressionCircuit, CompressionParams};
+use anyhow::{anyhow, bail, Result};
 use rand::Rng;
 use snark_verifier_sdk::Snark;
-use std::env;
 
 impl Prover {
     pub fn gen_comp_snark(
@@ -16,15 +16,23 @@ impl Prover {
         id: &str,
         has_accumulator: bool,
         degree: u32,
-        mut rng: impl Rng + Send,
+        rng: impl Rng + Send,
         prev_snark: Snark,
     ) -> Result<Snark> {
-        env::set_var("COMPRESSION_CONFIG", layer_config_path(id));
+        self.comp_snark_of_layer(None, id, has_accumulator, degree, rng, prev_snark)
+    }
 
-        let circuit =
-            CompressionCircuit::new(self.params(degree), prev_snark, has_accumulator, &mut rng)
-                .map_err(|err| anyhow!("Failed to construct compression circuit: {err:?}"))?;
-        self.gen_snark(id, degree, &mut rng, circuit, "gen_comp_snark")
+    /// Generate a compression snark configured with `P`, independently of the config other
+    /// threads compress with.
+    pub fn gen_comp_snark_with<P: CompressionParams>(
+        &mut self,
+        id: &str,
+        has_accumulator: bool,
+        degree: u32,
+        rng: impl Rng + Send,
+        prev_snark: Snark,
+    ) -> Result<Snark> {
+        self.load_cached_or_gen_comp_snark::<P>(None, id, has_accumulator, degree, rng, prev_snark)
     }
 
     pub fn load_or_gen_comp_snark(
@@ -46,8 +54,14 @@ impl Prover {
         match output_dir.and_then(|_| load_snark(&file_path).ok().flatten()) {
             Some(snark) => Ok(snark),
             None => {
-                let rng = gen_rng();
-                let result = self.gen_comp_snark(id, has_accumulator, degree, rng, prev_snark);
+                let result = self.comp_snark_of_layer(
+                    Some(name),
+                    id,
+                    has_accumulator,
+                    degree,
+                    gen_rng(),
+                    prev_snark,
+                );
                 if let (Some(_), Ok(snark)) = (output_dir, &result) {
                     write_snark(&file_path, snark);
                 }
@@ -56,4 +70,88 @@ impl Prover {
             }
         }
     }
+
+    /// Compress `prev_snark` with the params of the layer `id`, going through the proof cache
+    /// when the snark is `name`d.
+    fn comp_snark_of_layer(
+        &mut self,
+        name: Option<&str>,
+        id: &str,
+        has_accumulator: bool,
+        degree: u32,
+        rng: impl Rng + Send,
+        prev_snark: Snark,
+    ) -> Result<Snark> {
+        if id == LayerId::Layer1.id() {
+            self.load_cached_or_gen_comp_snark::<Layer1Params>(
+                name,
+                id,
+                has_accumulator,
+                degree,
+                rng,
+                prev_snark,
+            )
+        } else if id == LayerId::Layer2.id() {
+            self.load_cached_or_gen_comp_snark::<Layer2Params>(
+                name,
+                id,
+                has_accumulator,
+                degree,
+                rng,
+                prev_snark,
+            )
+        } else if id == LayerId::Layer4.id() {
+            self.load_cached_or_gen_comp_snark::<Layer4Params>(
+                name,
+                id,
+                has_accumulator,
+                degree,
+                rng,
+                prev_snark,
+            )
+        } else {
+            bail!("Wrong id-{id} for compression layer")
+        }
+    }
+
+    /// Load the snark from the proof cache, keyed by the previous snark and the compression
+    /// config, or generate and cache it. The cache is skipped for unnamed snarks.
+    fn load_cached_or_gen_comp_snark<P: CompressionParams>(
+        &mut self,
+        name: Option<&str>,
+        id: &str,
+        has_accumulator: bool,
+        degree: u32,
+        mut rng: impl Rng + Send,
+        prev_snark: Snark,
+    ) -> Result<Snark> {
+        let input = match (name, &self.proof_cache) {
+            (Some(_), Some(_)) => Some(serde_json::to_vec(&(has_accumulator, &prev_snark))?),
+            _ => None,
+        };
+        let timer = start_phase(Phase::CircuitBuild, id);
+        let circuit = CompressionCircuit::<P>::new(
+            self.params(degree),
+            prev_snark,
+            has_accumulator,
+            &mut rng,
+        )
+        .map_err(|err| anyhow!("Failed to construct compression circuit: {err:?}"))?;
+        drop(timer);
+
+        let key = match input {
+            Some(input) => {
+                let config = serde_json::to_vec(&P::config_params())?;
+                self.proof_cache_key(id, degree, &circuit, &config, &input)?
+            }
+            None => None,
+        };
+        if let Some(snark) = self.load_cached_snark(key.as_ref()) {
+            return Ok(snark);
+        }
+
+        let snark = self.gen_snark(id, degree, &mut rng, circuit, "gen_comp_snark")?;
+        self.store_cached_snark(key.as_ref(), name.unwrap_or(id), &snark);
+        Ok(snark)
+    }
 }
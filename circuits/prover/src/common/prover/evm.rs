@@ -1,15 +1,15 @@
 use super::Prover;
 use crate::{
-    config::layer_config_path,
+    config::{Layer1Params, Layer2Params, Layer4Params, LayerId},
+    metrics::{start_phase, Phase, Timed},
     utils::{gen_rng, read_env_var},
     EvmProof,
 };
-use aggregator::CompressionCircuit;
-use anyhow::{anyhow, Result};
+use aggregator::{CompressionCircuit, CompressionParams};
+use anyhow::{anyhow, bail, Result};
 use halo2_proofs::halo2curves::bn256::Fr;
 use rand::Rng;
 use snark_verifier_sdk::{gen_evm_proof_shplonk, CircuitExt, Snark};
-use std::env;
 
 impl Prover {
     pub fn load_or_gen_comp_evm_proof(
@@ -25,18 +25,33 @@ impl Prover {
         match output_dir.and_then(|output_dir| EvmProof::from_json_file(output_dir, &name).ok()) {
             Some(proof) => Ok(proof),
             None => {
-                env::set_var("COMPRESSION_CONFIG", layer_config_path(id));
-
-                let mut rng = gen_rng();
-                let circuit = CompressionCircuit::new(
-                    self.params(degree),
-                    prev_snark,
-                    has_accumulator,
-                    &mut rng,
-                )
-                .map_err(|err| anyhow!("Failed to construct compression circuit: {err:?}"))?;
-
-                let result = self.gen_evm_proof(id, degree, &mut rng, circuit, output_dir);
+                let result = if id == LayerId::Layer1.id() {
+                    self.gen_comp_evm_proof::<Layer1Params>(
+                        id,
+                        has_accumulator,
+                        degree,
+                        prev_snark,
+                        output_dir,
+                    )
+                } else if id == LayerId::Layer2.id() {
+                    self.gen_comp_evm_proof::<Layer2Params>(
+                        id,
+                        has_accumulator,
+                        degree,
+                        prev_snark,
+                        output_dir,
+                    )
+                } else if id == LayerId::Layer4.id() {
+                    self.gen_comp_evm_proof::<Layer4Params>(
+                        id,
+                        has_accumulator,
+                        degree,
+                        prev_snark,
+                        output_dir,
+                    )
+                } else {
+                    bail!("Wrong id-{id} for compression layer")
+                };
 
                 if let (Some(output_dir), Ok(proof)) = (output_dir, &result) {
                     proof.dump(output_dir, &name)?;
@@ -47,6 +62,29 @@ impl Prover {
         }
     }
 
+    /// Generate a compression EVM proof configured with `P`.
+    pub fn gen_comp_evm_proof<P: CompressionParams>(
+        &mut self,
+        id: &str,
+        has_accumulator: bool,
+        degree: u32,
+        prev_snark: Snark,
+        output_dir: Option<&str>,
+    ) -> Result<EvmProof> {
+        let mut rng = gen_rng();
+        let timer = start_phase(Phase::CircuitBuild, id);
+        let circuit = CompressionCircuit::<P>::new(
+            self.params(degree),
+            prev_snark,
+            has_accumulator,
+            &mut rng,
+        )
+        .map_err(|err| anyhow!("Failed to construct compression circuit: {err:?}"))?;
+        drop(timer);
+
+        self.gen_evm_proof(id, degree, &mut rng, circuit, output_dir)
+    }
+
     fn gen_evm_proof<C: CircuitExt<Fr>>(
         &mut self,
         id: &str,
@@ -64,7 +102,10 @@ impl Prover {
         );
         let instances = circuit.instances();
         let num_instance = circuit.num_instance();
+        let timer = start_phase(Phase::EvmProve, id);
+        let circuit = Timed::new(circuit, id);
         let proof = gen_evm_proof_shplonk(params, pk, circuit, instances.clone(), rng);
+        drop(timer);
         let evm_proof = EvmProof::new(proof, &instances, num_instance, Some(pk))?;
 
         if read_env_var("SCROLL_PROVER_DUMP_YUL", false) {
@@ -1,20 +1,21 @@
 use super::Prover;
 use crate::{
-    config::INNER_DEGREE,
-    io::{load_snark, write_snark},
+    config::{CIRCUITS_PROFILE, INNER_DEGREE},
+    io::{load_snark, serialize_instance, write_snark},
+    metrics::{start_phase, Phase, Timed},
     utils::{gen_rng, metric_of_witness_block},
     zkevm::circuit::{SuperCircuit, TargetCircuit},
 };
 use anyhow::Result;
 use rand::Rng;
-use snark_verifier_sdk::{gen_snark_shplonk, Snark};
+use snark_verifier_sdk::{gen_snark_shplonk, CircuitExt, Snark};
 use zkevm_circuits::evm_circuit::witness::Block;
 
 impl Prover {
     pub fn gen_inner_snark<C: TargetCircuit>(
         &mut self,
         id: &str,
-        mut rng: impl Rng + Send,
+        rng: impl Rng + Send,
         witness_block: &Block,
     ) -> Result<Snark> {
         log::info!(
@@ -22,9 +23,21 @@ impl Prover {
             metric_of_witness_block(witness_block)
         );
 
-        let degree = *INNER_DEGREE;
-
+        let timer = start_phase(Phase::CircuitBuild, id);
         let circuit = C::from_witness_block(witness_block)?;
+        drop(timer);
+
+        self.prove_inner_circuit::<C>(id, rng, circuit)
+    }
+
+    /// Prove an inner circuit already built from its witness block.
+    fn prove_inner_circuit<C: TargetCircuit>(
+        &mut self,
+        id: &str,
+        mut rng: impl Rng + Send,
+        circuit: C::Inner,
+    ) -> Result<Snark> {
+        let degree = *INNER_DEGREE;
 
         Self::assert_if_mock_prover(id, degree, &circuit);
 
@@ -33,6 +46,8 @@ impl Prover {
             "gen_inner_snark vk transcript_repr {:?}",
             pk.get_vk().transcript_repr()
         );
+        let _timer = start_phase(Phase::Prove, id);
+        let circuit = Timed::new(circuit, id);
         let snark = gen_snark_shplonk(params, pk, circuit, &mut rng, None::<String>)?;
 
         Ok(snark)
@@ -55,8 +70,7 @@ impl Prover {
         match output_dir.and_then(|_| load_snark(&file_path).ok().flatten()) {
             Some(snark) => Ok(snark),
             None => {
-                let rng = gen_rng();
-                let result = self.gen_inner_snark::<SuperCircuit>(id, rng, witness_block);
+                let result = self.load_cached_or_gen_inner_snark(name, id, witness_block);
                 if let (Some(_), Ok(snark)) = (output_dir, &result) {
                     write_snark(&file_path, snark);
                 }
@@ -65,4 +79,36 @@ impl Prover {
             }
         }
     }
+
+    /// Load the snark from the proof cache, keyed by the params and the instances of the super
+    /// circuit which commit to the whole chunk, or generate and cache it.
+    fn load_cached_or_gen_inner_snark(
+        &mut self,
+        name: &str,
+        id: &str,
+        witness_block: &Block,
+    ) -> Result<Snark> {
+        let timer = start_phase(Phase::CircuitBuild, id);
+        let circuit = SuperCircuit::from_witness_block(witness_block)?;
+        drop(timer);
+
+        let key = if self.proof_cache.is_some() {
+            let input = serialize_instance(&circuit.instances());
+            let config = serde_json::to_vec(&*CIRCUITS_PROFILE)?;
+            self.params_proof_cache_key(id, *INNER_DEGREE, &config, &input)
+        } else {
+            None
+        };
+        if let Some(snark) = self.load_cached_snark(key.as_ref()) {
+            return Ok(snark);
+        }
+
+        log::info!(
+            "Proving the chunk: {:?}",
+            metric_of_witness_block(witness_block)
+        );
+        let snark = self.prove_inner_circuit::<SuperCircuit>(id, gen_rng(), circuit)?;
+        self.store_cached_snark(key.as_ref(), name, &snark);
+        Ok(snark)
+    }
 }
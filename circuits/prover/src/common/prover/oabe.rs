@@ -0,0 +1,22 @@
+use super::Prover;
+use crate::metrics;
+use anyhow::Result;
+use halo2_proofs::halo2curves::bn256::Fr;
+use rand::Rng;
+use snark_verifier_sdk::Snark;
+use zkevm_circuits::pairing_circuit::{MyEccCircuit, PiCommitment};
+
+impl Prover {
+    /// Generate the snark of an OABE decryption. The metrics are labelled with the number of
+    /// attributes of the ciphertext, as the size of the circuit grows with it.
+    pub fn gen_oabe_snark<const XI_0: i64, P: PiCommitment>(
+        &mut self,
+        id: &str,
+        degree: u32,
+        mut rng: impl Rng + Send,
+        circuit: MyEccCircuit<Fr, XI_0, P>,
+    ) -> Result<Snark> {
+        metrics::set_label("attributes", circuit.p2s.len());
+        self.gen_snark(id, degree, &mut rng, circuit, "gen_oabe_snark")
+    }
+}
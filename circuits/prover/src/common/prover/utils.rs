@@ -1,12 +1,18 @@
 use super::Prover;
-use crate::io::serialize_vk;
+use crate::{
+    common::{pk_store::vk_hash, PkStore, ProofCacheKey},
+    io::{read_snark, serialize_vk},
+    metrics::{start_phase, Phase, Timed},
+    utils::GIT_VERSION,
+};
 use anyhow::Result;
 use halo2_proofs::{
     halo2curves::bn256::{Bn256, Fr, G1Affine},
-    plonk::{keygen_pk2, Circuit, ProvingKey},
+    plonk::{keygen_pk, keygen_pk2, keygen_vk, Circuit, ProvingKey},
     poly::{commitment::Params, kzg::commitment::ParamsKZG},
 };
 use rand::Rng;
+use sha2::{Digest, Sha256};
 use snark_verifier_sdk::{gen_snark_shplonk, CircuitExt, Snark};
 
 impl Prover {
@@ -28,6 +34,8 @@ impl Prover {
             desc,
             pk.get_vk().transcript_repr()
         );
+        let _timer = start_phase(Phase::Prove, id);
+        let circuit = Timed::new(circuit, id);
         let snark = gen_snark_shplonk(params, pk, circuit, rng, None::<String>)?;
         Ok(snark)
     }
@@ -69,15 +77,121 @@ impl Prover {
             return Ok((&self.params_map[&degree], &self.pk_map[id]));
         }
 
-        log::info!("Before generate pk of {}", &id);
-        let pk = keygen_pk2(self.params(degree), circuit)?;
-        log::info!("After generate pk of {}", &id);
+        let timer = start_phase(Phase::Keygen, id);
+        let pk = match self.pk_store.clone() {
+            Some(pk_store) => self.load_or_gen_pk(&pk_store, id, degree, circuit)?,
+            None => {
+                log::info!("Before generate pk of {}", &id);
+                let pk = keygen_pk2(self.params(degree), circuit)?;
+                log::info!("After generate pk of {}", &id);
+                pk
+            }
+        };
+        drop(timer);
 
         self.pk_map.insert(id.to_string(), pk);
 
         Ok((&self.params_map[&degree], &self.pk_map[id]))
     }
 
+    fn load_or_gen_pk<C: Circuit<Fr>>(
+        &mut self,
+        pk_store: &PkStore,
+        id: &str,
+        degree: u32,
+        circuit: &C,
+    ) -> Result<ProvingKey<G1Affine>> {
+        let params = self.params(degree);
+
+        // The vk is cheap compared to the pk, and tells whether the stored pk is stale.
+        let vk = keygen_vk(params, circuit)?;
+        match pk_store.load::<C>(id, degree, &vk) {
+            Ok(Some(pk)) => return Ok(pk),
+            Ok(None) => (),
+            Err(err) => log::warn!("Failed to load pk of {id}: {err}"),
+        }
+
+        log::info!("Before generate pk of {}", &id);
+        let pk = keygen_pk(params, vk, circuit)?;
+        log::info!("After generate pk of {}", &id);
+
+        if let Err(err) = pk_store.store(id, degree, &pk) {
+            log::warn!("Failed to store pk of {id}: {err}");
+        }
+
+        Ok(pk)
+    }
+
+    /// Key of the snark of `id` in the proof cache, `None` when no cache is used. `circuit` is
+    /// only used to generate the vk if the pk is not loaded yet.
+    pub fn proof_cache_key<C: Circuit<Fr>>(
+        &mut self,
+        id: &str,
+        degree: u32,
+        circuit: &C,
+        config: &[u8],
+        input: &[u8],
+    ) -> Result<Option<ProofCacheKey>> {
+        if self.proof_cache.is_none() {
+            return Ok(None);
+        }
+
+        let vk_hash = match self.pk_map.get(id) {
+            Some(pk) => vk_hash(pk.get_vk()),
+            None => vk_hash(&keygen_vk(self.params(degree), circuit)?),
+        };
+        Ok(Some(ProofCacheKey::new(
+            id, degree, &vk_hash, config, input,
+        )))
+    }
+
+    /// Key of the snark of `id` in the proof cache, with the vk hash replaced by the hash of the
+    /// code version and the params. Only for circuits whose vk is fully determined by these and
+    /// `config`, so that no vk has to be generated to look the snark up.
+    pub fn params_proof_cache_key(
+        &mut self,
+        id: &str,
+        degree: u32,
+        config: &[u8],
+        input: &[u8],
+    ) -> Option<ProofCacheKey> {
+        self.proof_cache.as_ref()?;
+
+        let params = self.params(degree);
+        let mut hasher = Sha256::new();
+        hasher.update(GIT_VERSION.as_bytes());
+        hasher.update(format!("{:?}{:?}", params.g2(), params.s_g2()).as_bytes());
+        Some(ProofCacheKey::new(
+            id,
+            degree,
+            &hasher.finalize().into(),
+            config,
+            input,
+        ))
+    }
+
+    pub fn load_cached_snark(&mut self, key: Option<&ProofCacheKey>) -> Option<Snark> {
+        let (proof_cache, key) = (self.proof_cache.as_mut()?, key?);
+        let buf = proof_cache.load(key).unwrap_or_else(|err| {
+            log::warn!("Failed to load cached snark {key}: {err}");
+            None
+        })?;
+        read_snark(buf.as_slice())
+            .map_err(|err| log::warn!("Failed to read cached snark {key}: {err}"))
+            .ok()
+    }
+
+    pub fn store_cached_snark(&mut self, key: Option<&ProofCacheKey>, name: &str, snark: &Snark) {
+        if let (Some(proof_cache), Some(key)) = (self.proof_cache.as_mut(), key) {
+            let result = serde_json::to_vec(snark)
+                .map_err(Into::into)
+                .and_then(|buf| proof_cache.store(key, name, &buf));
+            if let Err(err) = result {
+                log::warn!("Failed to store snark of {name}: {err}");
+            }
+        }
+    }
+
     pub fn raw_vk(&self, id: &str) -> Option<Vec<u8>> {
         self.pk_map.get(id).map(|pk| serialize_vk(pk.get_vk()))
     }
@@ -8,6 +8,7 @@ use snark_verifier_sdk::{verify_snark_shplonk, CircuitExt, Snark};
 use std::marker::PhantomData;
 
 mod evm;
+mod native;
 mod utils;
 
 #[derive(Debug)]
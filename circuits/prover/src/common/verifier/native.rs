@@ -0,0 +1,65 @@
+use super::Verifier;
+use crate::Proof;
+use aggregator::check_accumulator_limbs;
+use halo2_proofs::{
+    halo2curves::bn256::{Bn256, Fr, G1Affine},
+    plonk::verify_proof,
+    poly::{
+        commitment::ParamsProver,
+        kzg::{
+            commitment::KZGCommitmentScheme, multiopen::VerifierSHPLONK,
+            strategy::AccumulatorStrategy,
+        },
+        VerificationStrategy,
+    },
+    transcript::TranscriptReadBuffer,
+};
+use snark_verifier::system::halo2::transcript::evm::EvmTranscript;
+use snark_verifier_sdk::CircuitExt;
+
+impl<C: CircuitExt<Fr>> Verifier<C> {
+    /// Verify a proof generated for the EVM, i.e. with the keccak transcript, without the EVM:
+    /// the proof is checked against the vk, then the accumulator it carries in its instances, if
+    /// the circuit has one, with the KZG pairing check. This is what the verifier contract does.
+    pub fn verify_evm_proof_natively(&self, proof: &Proof) -> bool {
+        let instances = proof.instances();
+        let instances: Vec<&[Fr]> = instances.iter().map(Vec::as_slice).collect();
+
+        let mut transcript = TranscriptReadBuffer::<_, G1Affine, _>::init(proof.proof());
+        let proof_ok = match verify_proof::<
+            KZGCommitmentScheme<Bn256>,
+            VerifierSHPLONK<'_, Bn256>,
+            _,
+            EvmTranscript<_, _, _, _>,
+            _,
+        >(
+            self.params.verifier_params(),
+            &self.vk,
+            AccumulatorStrategy::new(self.params.verifier_params()),
+            &[instances.as_slice()],
+            &mut transcript,
+        ) {
+            Ok(strategy) => {
+                VerificationStrategy::<_, VerifierSHPLONK<'_, Bn256>>::finalize(strategy)
+            }
+            Err(err) => {
+                log::warn!("Native verification of EVM proof failed: {err:?}");
+                false
+            }
+        };
+        if !proof_ok {
+            return false;
+        }
+
+        match C::accumulator_indices() {
+            Some(indices) => indices
+                .iter()
+                .map(|&(column, row)| instances.get(column)?.get(row).copied())
+                .collect::<Option<Vec<_>>>()
+                .map_or(false, |acc_instances| {
+                    check_accumulator_limbs(&self.params, &acc_instances)
+                }),
+            None => true,
+        }
+    }
+}
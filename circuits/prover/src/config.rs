@@ -1,10 +1,55 @@
-use crate::utils::read_env_var;
-use aggregator::ConfigParams;
+use crate::{aggregator::VerifyMode, utils::read_env_var};
+use aggregator::{CompressionParams, ConfigParams};
 use std::{collections::HashSet, fmt, fs::File, path::Path, sync::LazyLock};
+use zkevm_circuits::super_circuit::profile::{CircuitsProfile, MAINNET_CHUNK};
 
 pub static INNER_DEGREE: LazyLock<u32> =
     LazyLock::new(|| read_env_var("SCROLL_PROVER_INNER_DEGREE", 20));
 
+/// Capacity profile of the super (inner) circuit, either a built-in profile name or the path of a
+/// TOML/JSON profile file. The profile must be sized for `INNER_DEGREE`.
+pub static CIRCUITS_PROFILE: LazyLock<CircuitsProfile> = LazyLock::new(|| {
+    let profile = read_env_var("SCROLL_PROVER_CIRCUITS_PROFILE", MAINNET_CHUNK.to_string());
+    let circuits_profile = CircuitsProfile::load(&profile)
+        .unwrap_or_else(|err| panic!("Failed to load circuits profile {profile}: {err}"));
+    assert_eq!(
+        circuits_profile.degree, *INNER_DEGREE,
+        "Circuits profile {profile} is sized for degree {}, but the inner degree is {}",
+        circuits_profile.degree, *INNER_DEGREE
+    );
+    circuits_profile
+});
+
+/// INSECURE: generate params from a public seed instead of loading the trusted setup. Only meant
+/// for CI and tests running without the downloaded setup files, and only honored by builds with
+/// the `insecure-test-setup` feature.
+pub static INSECURE_TEST_SETUP: LazyLock<bool> =
+    LazyLock::new(|| read_env_var("SCROLL_PROVER_INSECURE_TEST_SETUP", false));
+
+/// Extra list of trusted `[tau]_2`, in the format of `trusted_s_g2.txt`, disabled when empty.
+pub static TRUSTED_S_G2_PATH: LazyLock<String> =
+    LazyLock::new(|| read_env_var("SCROLL_PROVER_TRUSTED_S_G2_PATH", String::new()));
+
+/// Directory to persist proving keys in, disabled when empty.
+pub static PK_DIR: LazyLock<String> =
+    LazyLock::new(|| read_env_var("SCROLL_PROVER_PK_DIR", String::new()));
+
+/// Directory of the content-addressed proof cache, disabled when empty.
+pub static PROOF_CACHE_DIR: LazyLock<String> =
+    LazyLock::new(|| read_env_var("SCROLL_PROVER_PROOF_CACHE_DIR", String::new()));
+
+/// Size limit in bytes of the proof cache, unlimited when 0.
+pub static PROOF_CACHE_MAX_SIZE: LazyLock<u64> =
+    LazyLock::new(|| read_env_var("SCROLL_PROVER_PROOF_CACHE_MAX_SIZE", 0));
+
+/// How the final proofs are verified: `evm`, `native` or `cross-check`.
+pub static VERIFY_MODE: LazyLock<VerifyMode> =
+    LazyLock::new(|| read_env_var("SCROLL_PROVER_VERIFY_MODE", VerifyMode::Evm));
+
+/// Directory to write the metrics of every chunk and batch proved to, disabled when empty.
+pub static METRICS_DIR: LazyLock<String> =
+    LazyLock::new(|| read_env_var("SCROLL_PROVER_METRICS_DIR", String::new()));
+
 pub static ASSETS_DIR: LazyLock<String> =
     LazyLock::new(|| read_env_var("SCROLL_PROVER_ASSETS_DIR", "configs".to_string()));
 
@@ -17,10 +62,19 @@ pub static LAYER3_CONFIG_PATH: LazyLock<String> =
 pub static LAYER4_CONFIG_PATH: LazyLock<String> =
     LazyLock::new(|| asset_file_path("layer4.config"));
 
-pub static LAYER1_DEGREE: LazyLock<u32> = LazyLock::new(|| layer_degree(&LAYER1_CONFIG_PATH));
-pub static LAYER2_DEGREE: LazyLock<u32> = LazyLock::new(|| layer_degree(&LAYER2_CONFIG_PATH));
-pub static LAYER3_DEGREE: LazyLock<u32> = LazyLock::new(|| layer_degree(&LAYER3_CONFIG_PATH));
-pub static LAYER4_DEGREE: LazyLock<u32> = LazyLock::new(|| layer_degree(&LAYER4_CONFIG_PATH));
+pub static LAYER1_CONFIG: LazyLock<ConfigParams> =
+    LazyLock::new(|| layer_config(&LAYER1_CONFIG_PATH));
+pub static LAYER2_CONFIG: LazyLock<ConfigParams> =
+    LazyLock::new(|| layer_config(&LAYER2_CONFIG_PATH));
+pub static LAYER3_CONFIG: LazyLock<ConfigParams> =
+    LazyLock::new(|| layer_config(&LAYER3_CONFIG_PATH));
+pub static LAYER4_CONFIG: LazyLock<ConfigParams> =
+    LazyLock::new(|| layer_config(&LAYER4_CONFIG_PATH));
+
+pub static LAYER1_DEGREE: LazyLock<u32> = LazyLock::new(|| LAYER1_CONFIG.degree);
+pub static LAYER2_DEGREE: LazyLock<u32> = LazyLock::new(|| LAYER2_CONFIG.degree);
+pub static LAYER3_DEGREE: LazyLock<u32> = LazyLock::new(|| LAYER3_CONFIG.degree);
+pub static LAYER4_DEGREE: LazyLock<u32> = LazyLock::new(|| LAYER4_CONFIG.degree);
 
 pub static ZKEVM_DEGREES: LazyLock<Vec<u32>> = LazyLock::new(|| {
     Vec::from_iter(HashSet::from([
@@ -102,11 +156,38 @@ pub fn layer_config_path(id: &str) -> &str {
     }
 }
 
-fn layer_degree(config_file: &str) -> u32 {
-    let f = File::open(config_file).unwrap_or_else(|_| panic!("Failed to open {config_file}"));
+/// Compression params of layer1, read from `LAYER1_CONFIG_PATH`.
+#[derive(Clone, Debug)]
+pub struct Layer1Params;
 
-    let params: ConfigParams =
-        serde_json::from_reader(f).unwrap_or_else(|_| panic!("Failed to parse {config_file}"));
+impl CompressionParams for Layer1Params {
+    fn config_params() -> ConfigParams {
+        LAYER1_CONFIG.clone()
+    }
+}
+
+/// Compression params of layer2, read from `LAYER2_CONFIG_PATH`.
+#[derive(Clone, Debug)]
+pub struct Layer2Params;
+
+impl CompressionParams for Layer2Params {
+    fn config_params() -> ConfigParams {
+        LAYER2_CONFIG.clone()
+    }
+}
+
+/// Compression params of layer4, read from `LAYER4_CONFIG_PATH`.
+#[derive(Clone, Debug)]
+pub struct Layer4Params;
+
+impl CompressionParams for Layer4Params {
+    fn config_params() -> ConfigParams {
+        LAYER4_CONFIG.clone()
+    }
+}
+
+fn layer_config(config_file: &str) -> ConfigParams {
+    let f = File::open(config_file).unwrap_or_else(|_| panic!("Failed to open {config_file}"));
 
-    params.degree
+    serde_json::from_reader(f).unwrap_or_else(|_| panic!("Failed to parse {config_file}"))
 }
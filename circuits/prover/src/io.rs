@@ -153,11 +153,15 @@ pub fn load_snark(file_path: &str) -> anyhow::Result<Option<Snark>> {
     }
 
     let fd = File::open(file_path)?;
-    let mut deserializer = serde_json::Deserializer::from_reader(fd);
+    Ok(Some(read_snark(fd)?))
+}
+
+pub fn read_snark(reader: impl Read) -> anyhow::Result<Snark> {
+    let mut deserializer = serde_json::Deserializer::from_reader(reader);
     deserializer.disable_recursion_limit();
     let deserializer = serde_stacker::Deserializer::new(&mut deserializer);
     let snark = serde::Deserialize::deserialize(deserializer)?;
-    Ok(Some(snark))
+    Ok(snark)
 }
 
 pub fn load_instances(buf: &[u8]) -> Vec<Vec<Vec<Fr>>> {
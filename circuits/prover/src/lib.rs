@@ -16,7 +16,9 @@ pub mod consts;
 mod evm;
 pub mod inner;
 pub mod io;
+pub mod metrics;
 pub mod proof;
+pub mod setup;
 pub mod test;
 pub mod types;
 pub mod utils;
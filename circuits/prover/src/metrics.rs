@@ -0,0 +1,320 @@
+//! Metrics of proving: durations of the phases, peak memory and row usage of the sub-circuits.
+//!
+//! Phases are recorded into a registry of the process by the guards of [`start_phase`], and
+//! [`take`] returns what was recorded since the last call. Provers running concurrently in one
+//! process share the registry, their phases are told apart by the `id` of the circuit only.
+//!
+//! Synthesis and the FFTs and MSMs of the commitments all run within `create_proof` of halo2, so
+//! they are recorded together as [`Phase::Prove`]. Circuits proved through [`Timed`] also record
+//! the time spent in their synthesis alone as [`Phase::Synthesis`].
+
+use crate::{config::METRICS_DIR, zkevm::SubCircuitRowUsage};
+use anyhow::Result;
+use halo2_proofs::{
+    circuit::Layouter,
+    halo2curves::bn256::Fr,
+    plonk::{Circuit, ConstraintSystem, Error, Selector},
+};
+use serde_derive::{Deserialize, Serialize};
+use snark_verifier_sdk::CircuitExt;
+use std::{
+    collections::BTreeMap,
+    fmt::Write,
+    fs,
+    path::Path,
+    sync::{LazyLock, Mutex},
+    time::Instant,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Phase {
+    /// Build the witness block from the block traces.
+    WitnessGen,
+    /// Construct a circuit from its witness, including the native accumulation of the snarks
+    /// aggregated or compressed.
+    CircuitBuild,
+    /// Generate or load the proving key.
+    Keygen,
+    /// Generate a snark: synthesis, then the FFTs and MSMs of the commitments.
+    Prove,
+    /// Synthesize the witness of a circuit, part of [`Phase::Prove`].
+    Synthesis,
+    /// Generate a proof to be verified in the EVM.
+    EvmProve,
+}
+
+impl Phase {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::WitnessGen => "witness_gen",
+            Self::CircuitBuild => "circuit_build",
+            Self::Keygen => "keygen",
+            Self::Prove => "prove",
+            Self::Synthesis => "synthesis",
+            Self::EvmProve => "evm_prove",
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct PhaseRecord {
+    pub phase: Phase,
+    /// Id of the circuit, as in `LayerId`.
+    pub id: String,
+    pub seconds: f64,
+    /// Peak resident memory of the process when the phase ended.
+    pub peak_memory_bytes: Option<u64>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct Metrics {
+    /// Labels of the task proved, e.g. the chunk or the attribute count, added to every sample.
+    pub labels: BTreeMap<String, String>,
+    pub phases: Vec<PhaseRecord>,
+    pub row_usage: Vec<SubCircuitRowUsage>,
+    /// Peak resident memory of the process when the metrics were taken.
+    pub peak_memory_bytes: Option<u64>,
+}
+
+static METRICS: LazyLock<Mutex<Metrics>> = LazyLock::new(Default::default);
+
+/// Guard recording the duration of a phase when dropped.
+#[must_use = "the phase ends when the guard is dropped"]
+pub struct PhaseTimer {
+    phase: Phase,
+    id: String,
+    start: Instant,
+}
+
+pub fn start_phase(phase: Phase, id: &str) -> PhaseTimer {
+    log::debug!("Start {} of {id}", phase.name());
+    PhaseTimer {
+        phase,
+        id: id.to_string(),
+        start: Instant::now(),
+    }
+}
+
+impl Drop for PhaseTimer {
+    fn drop(&mut self) {
+        let seconds = self.start.elapsed().as_secs_f64();
+        log::info!("{} of {} took {seconds:.3}s", self.phase.name(), self.id);
+        METRICS.lock().unwrap().phases.push(PhaseRecord {
+            phase: self.phase,
+            id: std::mem::take(&mut self.id),
+            seconds,
+            peak_memory_bytes: peak_memory(),
+        });
+    }
+}
+
+/// Circuit recording the duration of its synthesis as [`Phase::Synthesis`], to be proved in place
+/// of the circuit it wraps.
+pub struct Timed<C> {
+    circuit: C,
+    id: String,
+}
+
+impl<C> Timed<C> {
+    pub fn new(circuit: C, id: &str) -> Self {
+        Self {
+            circuit,
+            id: id.to_string(),
+        }
+    }
+}
+
+impl<C: Circuit<Fr>> Circuit<Fr> for Timed<C> {
+    type Config = C::Config;
+    type FloorPlanner = C::FloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            circuit: self.circuit.without_witnesses(),
+            id: self.id.clone(),
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Fr>) -> Self::Config {
+        C::configure(meta)
+    }
+
+    fn synthesize(&self, config: Self::Config, layouter: impl Layouter<Fr>) -> Result<(), Error> {
+        let _timer = start_phase(Phase::Synthesis, &self.id);
+        self.circuit.synthesize(config, layouter)
+    }
+}
+
+impl<C: CircuitExt<Fr>> CircuitExt<Fr> for Timed<C> {
+    fn num_instance(&self) -> Vec<usize> {
+        self.circuit.num_instance()
+    }
+
+    fn instances(&self) -> Vec<Vec<Fr>> {
+        self.circuit.instances()
+    }
+
+    fn accumulator_indices() -> Option<Vec<(usize, usize)>> {
+        C::accumulator_indices()
+    }
+
+    fn selectors(config: &Self::Config) -> Vec<Selector> {
+        C::selectors(config)
+    }
+}
+
+pub fn set_label(key: &str, value: impl ToString) {
+    METRICS
+        .lock()
+        .unwrap()
+        .labels
+        .insert(key.to_string(), value.to_string());
+}
+
+pub fn record_row_usage(row_usage: &[SubCircuitRowUsage]) {
+    METRICS.lock().unwrap().row_usage = row_usage.to_vec();
+}
+
+/// Return the metrics recorded so far and reset the registry.
+pub fn take() -> Metrics {
+    let mut metrics = std::mem::take(&mut *METRICS.lock().unwrap());
+    metrics.peak_memory_bytes = peak_memory();
+    metrics
+}
+
+/// Take the metrics of a task, and write them to `SCROLL_PROVER_METRICS_DIR` if it is set, as
+/// `metrics_{name}.json` and `metrics_{name}.prom`.
+pub fn take_and_dump(name: &str) {
+    let metrics = take();
+    if METRICS_DIR.is_empty() {
+        return;
+    }
+    if let Err(err) = metrics.dump(METRICS_DIR.as_str(), name) {
+        log::warn!("Failed to dump metrics of {name}: {err}");
+    }
+}
+
+/// Peak resident set size of the process, read from procfs on Linux.
+pub fn peak_memory() -> Option<u64> {
+    let status = fs::read_to_string("/proc/self/status").ok()?;
+    let kib = status
+        .lines()
+        .find_map(|line| line.strip_prefix("VmHWM:"))?
+        .trim()
+        .strip_suffix("kB")?
+        .trim()
+        .parse::<u64>()
+        .ok()?;
+    Some(kib * 1024)
+}
+
+impl Metrics {
+    pub fn dump(&self, dir: impl AsRef<Path>, name: &str) -> Result<()> {
+        let dir = dir.as_ref();
+        fs::create_dir_all(dir)?;
+        fs::write(
+            dir.join(format!("metrics_{name}.json")),
+            serde_json::to_vec_pretty(self)?,
+        )?;
+        fs::write(
+            dir.join(format!("metrics_{name}.prom")),
+            self.to_prometheus(),
+        )?;
+        Ok(())
+    }
+
+    /// Render in the Prometheus text format. The durations of a phase repeated for the same
+    /// circuit are summed, as a sample can't be repeated.
+    pub fn to_prometheus(&self) -> String {
+        let mut seconds = BTreeMap::<_, f64>::new();
+        for record in &self.phases {
+            *seconds
+                .entry((record.phase.name(), record.id.as_str()))
+                .or_default() += record.seconds;
+        }
+
+        let mut out = String::new();
+        out.push_str("# HELP prover_phase_seconds Duration of a phase of proving.\n");
+        out.push_str("# TYPE prover_phase_seconds gauge\n");
+        for ((phase, id), seconds) in seconds {
+            let labels = self.labels_with(&[("phase", phase), ("id", id)]);
+            writeln!(out, "prover_phase_seconds{labels} {seconds}").unwrap();
+        }
+
+        if let Some(bytes) = self.peak_memory_bytes {
+            out.push_str("# HELP prover_peak_memory_bytes Peak resident memory of the prover.\n");
+            out.push_str("# TYPE prover_peak_memory_bytes gauge\n");
+            let labels = self.labels_with(&[]);
+            writeln!(out, "prover_peak_memory_bytes{labels} {bytes}").unwrap();
+        }
+
+        if !self.row_usage.is_empty() {
+            out.push_str("# HELP prover_row_usage Rows used by a sub-circuit.\n");
+            out.push_str("# TYPE prover_row_usage gauge\n");
+            for usage in &self.row_usage {
+                let labels = self.labels_with(&[("circuit", &usage.name)]);
+                writeln!(out, "prover_row_usage{labels} {}", usage.row_number).unwrap();
+            }
+        }
+
+        out
+    }
+
+    fn labels_with(&self, extra: &[(&str, &str)]) -> String {
+        let labels = self
+            .labels
+            .iter()
+            .map(|(key, value)| (key.as_str(), value.as_str()))
+            .chain(extra.iter().copied())
+            .map(|(key, value)| format!("{key}=\"{}\"", escape_label_value(value)))
+            .collect::<Vec<_>>();
+        if labels.is_empty() {
+            String::new()
+        } else {
+            format!("{{{}}}", labels.join(","))
+        }
+    }
+}
+
+fn escape_label_value(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prometheus_text() {
+        let record = |phase, id: &str, seconds| PhaseRecord {
+            phase,
+            id: id.to_string(),
+            seconds,
+            peak_memory_bytes: None,
+        };
+        let metrics = Metrics {
+            labels: BTreeMap::from([("chunk".to_string(), "12_\"ab\"".to_string())]),
+            phases: vec![
+                record(Phase::Keygen, "layer1", 1.5),
+                record(Phase::Prove, "layer1", 2.0),
+                record(Phase::Prove, "layer1", 0.5),
+            ],
+            row_usage: vec![SubCircuitRowUsage {
+                name: "evm".to_string(),
+                row_number: 42,
+            }],
+            peak_memory_bytes: Some(1024),
+        };
+
+        let text = metrics.to_prometheus();
+        assert!(text.contains(
+            "prover_phase_seconds{chunk=\"12_\\\"ab\\\"\",phase=\"prove\",id=\"layer1\"} 2.5\n"
+        ));
+        assert!(text.contains("prover_peak_memory_bytes{chunk=\"12_\\\"ab\\\"\"} 1024\n"));
+        assert!(text.contains("prover_row_usage{chunk=\"12_\\\"ab\\\"\",circuit=\"evm\"} 42\n"));
+    }
+}
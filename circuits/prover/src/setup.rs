@@ -0,0 +1,441 @@
+//! Import KZG params from public ceremony transcripts.
+//!
+//! Supported sources:
+//!   - snarkjs / Perpetual Powers of Tau `.ptau` files, recognized by their `ptau` magic.
+//!   - Any other file (`.params`, `.ph2`, ...) is read as a halo2 `ParamsKZG` file, in whichever
+//!     serde format its length matches.
+//!
+//! The tau powers are checked for consistency with pairings before being downsized to the
+//! requested degree and converted to the `RawBytesUnchecked` format `load_params` expects.
+//! Consistency doesn't make a transcript trusted, so its `[tau]_2` must also be one of the
+//! ceremony values listed in [`TRUSTED_S_G2`], which `load_params` checks again.
+use crate::{
+    config::TRUSTED_S_G2_PATH,
+    utils::{param_path_for_degree, DEFAULT_SERDE_FORMAT, PARAMS_G2_SECRET_POWER},
+};
+use anyhow::{bail, ensure, Context, Result};
+use halo2_proofs::{
+    arithmetic::{best_multiexp, g_to_lagrange, Field},
+    halo2curves::{
+        bn256::{pairing, Bn256, Fq, Fq2, Fr, G1Affine, G2Affine, G1},
+        group::Curve,
+        serde::SerdeObject,
+        CurveAffine,
+    },
+    poly::{commitment::Params, kzg::commitment::ParamsKZG},
+    SerdeFormat,
+};
+use rand::rngs::OsRng;
+use std::{
+    collections::BTreeMap,
+    fs::{self, File},
+    io::{BufReader, Read, Seek, SeekFrom},
+    path::Path,
+    sync::LazyLock,
+};
+
+const PTAU_MAGIC: &[u8; 4] = b"ptau";
+const PTAU_SECTION_HEADER: u32 = 1;
+const PTAU_SECTION_TAU_G1: u32 = 2;
+const PTAU_SECTION_TAU_G2: u32 = 3;
+/// Bytes of a base field element in a ptau file.
+const PTAU_N8: u32 = 32;
+
+/// Reviewed list of the ceremonies params may come from, see `trusted_s_g2.txt`.
+const TRUSTED_S_G2_LIST: &str = include_str!("../trusted_s_g2.txt");
+
+/// `[tau]_2` of the ceremonies params may come from, as printed by `Debug`: the pinned
+/// `PARAMS_G2_SECRET_POWER`, the reviewed `trusted_s_g2.txt` and the list at
+/// `SCROLL_PROVER_TRUSTED_S_G2_PATH` if set.
+pub static TRUSTED_S_G2: LazyLock<Vec<String>> = LazyLock::new(|| {
+    let mut trusted = vec![PARAMS_G2_SECRET_POWER.to_string()];
+    trusted.extend(parse_trusted_s_g2(TRUSTED_S_G2_LIST));
+    if !TRUSTED_S_G2_PATH.is_empty() {
+        let list = fs::read_to_string(&*TRUSTED_S_G2_PATH)
+            .unwrap_or_else(|err| panic!("Failed to read {}: {err}", *TRUSTED_S_G2_PATH));
+        trusted.extend(parse_trusted_s_g2(&list));
+    }
+    trusted
+});
+
+/// One `[tau]_2` per line, skipping blank lines and `#` comments.
+fn parse_trusted_s_g2(list: &str) -> impl Iterator<Item = String> + '_ {
+    list.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(str::to_string)
+}
+
+/// Whether `s_g2` is the `[tau]_2` of a trusted ceremony.
+pub fn is_trusted_s_g2(s_g2: &G2Affine) -> bool {
+    let s_g2 = format!("{s_g2:?}");
+    TRUSTED_S_G2.iter().any(|trusted| *trusted == s_g2)
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TranscriptFormat {
+    /// snarkjs / Perpetual Powers of Tau transcript
+    Ptau,
+    /// halo2 `ParamsKZG` file produced by other halo2 toolchains
+    Halo2Params,
+}
+
+impl TranscriptFormat {
+    pub fn detect(path: &Path) -> Result<Self> {
+        let mut magic = [0u8; 4];
+        File::open(path)?.read_exact(&mut magic)?;
+
+        Ok(if &magic == PTAU_MAGIC {
+            Self::Ptau
+        } else {
+            Self::Halo2Params
+        })
+    }
+}
+
+/// The part of a powers-of-tau transcript the KZG params are built from.
+struct TauPowers {
+    /// [tau^i]_1 for i in 0..2^degree
+    g: Vec<G1Affine>,
+    /// [1]_2
+    g2: G2Affine,
+    /// [tau]_2
+    s_g2: G2Affine,
+}
+
+/// Read a transcript, verify its tau powers and convert them to params of `degree`.
+pub fn import_params(transcript: impl AsRef<Path>, degree: u32) -> Result<ParamsKZG<Bn256>> {
+    let transcript = transcript.as_ref();
+    ensure!(degree > 0, "degree must be positive");
+
+    let format = TranscriptFormat::detect(transcript)?;
+    log::info!("Import params of degree {degree} from {transcript:?} as {format:?}");
+
+    let powers = match format {
+        TranscriptFormat::Ptau => read_ptau(transcript, degree)?,
+        TranscriptFormat::Halo2Params => read_halo2_params(transcript, degree)?,
+    };
+    verify_tau_powers(&powers)?;
+    log::info!("Tau powers of {transcript:?} are consistent");
+    ensure!(
+        is_trusted_s_g2(&powers.s_g2),
+        "[tau]_2 of {transcript:?} is not one of TRUSTED_S_G2"
+    );
+
+    build_params(powers, degree)
+}
+
+/// Write imported params to `params_dir` for `load_params`. Return the path of the params file.
+pub fn write_imported_params(params: &ParamsKZG<Bn256>, params_dir: &str) -> Result<String> {
+    fs::create_dir_all(params_dir)?;
+
+    let params_path = param_path_for_degree(params_dir, params.k());
+    params.write_custom(&mut File::create(&params_path)?, DEFAULT_SERDE_FORMAT)?;
+
+    Ok(params_path)
+}
+
+fn read_u32(reader: &mut impl Read) -> Result<u32> {
+    let mut bytes = [0u8; 4];
+    reader.read_exact(&mut bytes)?;
+    Ok(u32::from_le_bytes(bytes))
+}
+
+fn read_u64(reader: &mut impl Read) -> Result<u64> {
+    let mut bytes = [0u8; 8];
+    reader.read_exact(&mut bytes)?;
+    Ok(u64::from_le_bytes(bytes))
+}
+
+/// ptau field elements are stored as little-endian Montgomery limbs, which is also the raw
+/// representation of halo2curves.
+fn read_ptau_fq(reader: &mut impl Read) -> Result<Fq> {
+    let mut bytes = [0u8; PTAU_N8 as usize];
+    reader.read_exact(&mut bytes)?;
+    Fq::from_raw_bytes(&bytes).context("ptau field element out of range")
+}
+
+fn read_ptau_g1(reader: &mut impl Read) -> Result<G1Affine> {
+    let x = read_ptau_fq(reader)?;
+    let y = read_ptau_fq(reader)?;
+    Option::from(G1Affine::from_xy(x, y)).context("ptau G1 point not on curve")
+}
+
+fn read_ptau_g2(reader: &mut impl Read) -> Result<G2Affine> {
+    let x = Fq2 {
+        c0: read_ptau_fq(reader)?,
+        c1: read_ptau_fq(reader)?,
+    };
+    let y = Fq2 {
+        c0: read_ptau_fq(reader)?,
+        c1: read_ptau_fq(reader)?,
+    };
+    Option::from(G2Affine::from_xy(x, y)).context("ptau G2 point not on curve")
+}
+
+fn read_ptau(path: &Path, degree: u32) -> Result<TauPowers> {
+    let mut reader = BufReader::new(File::open(path)?);
+    let file_size = reader.get_ref().metadata()?.len();
+
+    let mut magic = [0u8; 4];
+    reader.read_exact(&mut magic)?;
+    ensure!(&magic == PTAU_MAGIC, "not a ptau file");
+    let _version = read_u32(&mut reader)?;
+
+    // section type -> (offset, size)
+    let mut sections = BTreeMap::new();
+    for _ in 0..read_u32(&mut reader)? {
+        let section_type = read_u32(&mut reader)?;
+        let size = read_u64(&mut reader)?;
+        let offset = reader.stream_position()?;
+        let end = offset
+            .checked_add(size)
+            .filter(|end| *end <= file_size)
+            .with_context(|| format!("ptau section {section_type} runs past the end of file"))?;
+        sections.insert(section_type, (offset, size));
+        reader.seek(SeekFrom::Start(end))?;
+    }
+    let seek_section = |reader: &mut BufReader<File>, section_type| -> Result<u64> {
+        let (offset, size) = sections
+            .get(&section_type)
+            .with_context(|| format!("ptau section {section_type} missing"))?;
+        reader.seek(SeekFrom::Start(*offset))?;
+        Ok(*size)
+    };
+
+    seek_section(&mut reader, PTAU_SECTION_HEADER)?;
+    let n8 = read_u32(&mut reader)?;
+    ensure!(n8 == PTAU_N8, "ptau over a {n8}-byte field, expected bn254");
+    let mut modulus = [0u8; PTAU_N8 as usize];
+    reader.read_exact(&mut modulus)?;
+    let power = read_u32(&mut reader)?;
+    ensure!(
+        degree <= power,
+        "ptau only has 2^{power} powers, degree {degree} requested"
+    );
+
+    let size = seek_section(&mut reader, PTAU_SECTION_TAU_G1)?;
+    ensure!(
+        size >= (1u64 << degree) * 2 * PTAU_N8 as u64,
+        "ptau tau_g1 section truncated"
+    );
+    let g = (0..1 << degree)
+        .map(|_| read_ptau_g1(&mut reader))
+        .collect::<Result<Vec<_>>>()?;
+
+    seek_section(&mut reader, PTAU_SECTION_TAU_G2)?;
+    let g2 = read_ptau_g2(&mut reader)?;
+    let s_g2 = read_ptau_g2(&mut reader)?;
+
+    Ok(TauPowers { g, g2, s_g2 })
+}
+
+fn read_halo2_params(path: &Path, degree: u32) -> Result<TauPowers> {
+    let mut reader = BufReader::new(File::open(path)?);
+    let file_size = reader.get_ref().metadata()?.len();
+    let k = read_u32(&mut reader)?;
+    ensure!(
+        degree <= k,
+        "params only have degree {k}, degree {degree} requested"
+    );
+    reader.rewind()?;
+
+    // Same layout as checked in `load_params`: k, g, g_lagrange, g2, s_g2.
+    let g1_num = 2 * (1u64 << k);
+    let serde_fmt = [(SerdeFormat::Processed, 32), (SerdeFormat::RawBytes, 64)]
+        .into_iter()
+        .find(|(_, g1_bytes_len)| file_size == 4 + g1_num * g1_bytes_len + 2 * 2 * g1_bytes_len)
+        .map(|(serde_fmt, _)| serde_fmt);
+    let Some(serde_fmt) = serde_fmt else {
+        bail!("invalid params file len {file_size} for degree {k}");
+    };
+
+    let mut params = ParamsKZG::<Bn256>::read_custom(&mut reader, serde_fmt)?;
+    params.downsize(degree);
+
+    Ok(TauPowers {
+        g: params.get_g().to_vec(),
+        g2: params.g2(),
+        s_g2: params.s_g2(),
+    })
+}
+
+/// Check that the powers start at the generators, that tau is the same in G1 and G2, and that
+/// the G1 powers are consecutive, i.e. `tau * [tau^i]_1 == [tau^(i+1)]_1`. The latter is checked
+/// for all i at once on a random linear combination.
+fn verify_tau_powers(powers: &TauPowers) -> Result<()> {
+    let n = powers.g.len();
+    ensure!(n >= 2, "need at least 2 tau powers");
+    ensure!(
+        powers.g[0] == G1Affine::generator(),
+        "first G1 power is not the generator"
+    );
+    ensure!(
+        powers.g2 == G2Affine::generator(),
+        "G2 power is not the generator"
+    );
+    ensure!(
+        pairing(&powers.g[1], &powers.g2) == pairing(&powers.g[0], &powers.s_g2),
+        "tau differs between G1 and G2"
+    );
+
+    let coeffs = (0..n - 1).map(|_| Fr::random(OsRng)).collect::<Vec<_>>();
+    let lhs = best_multiexp(&coeffs, &powers.g[1..]).to_affine();
+    let rhs = best_multiexp(&coeffs, &powers.g[..n - 1]).to_affine();
+    ensure!(
+        pairing(&lhs, &powers.g2) == pairing(&rhs, &powers.s_g2),
+        "G1 powers are not consecutive powers of tau"
+    );
+
+    Ok(())
+}
+
+fn build_params(powers: TauPowers, degree: u32) -> Result<ParamsKZG<Bn256>> {
+    let g_projective = powers.g.iter().map(|p| G1::from(*p)).collect::<Vec<_>>();
+    let g_lagrange: Vec<G1Affine> = g_to_lagrange(g_projective, degree);
+
+    // Serialize as `ParamsKZG::write_custom` does with raw bytes, and read it back.
+    let mut buf = Vec::with_capacity(4 + (powers.g.len() + g_lagrange.len()) * 64 + 2 * 128);
+    buf.extend_from_slice(&degree.to_le_bytes());
+    for point in powers.g.iter().chain(g_lagrange.iter()) {
+        point.write_raw(&mut buf)?;
+    }
+    powers.g2.write_raw(&mut buf)?;
+    powers.s_g2.write_raw(&mut buf)?;
+
+    Ok(ParamsKZG::<Bn256>::read_custom(
+        &mut buf.as_slice(),
+        SerdeFormat::RawBytesUnchecked,
+    )?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    /// A ptau transcript of 2^`power` G1 powers of `tau`.
+    fn ptau_bytes(power: u32, tau: Fr) -> Vec<u8> {
+        let write_fq = |buf: &mut Vec<u8>, x: &Fq| buf.extend_from_slice(&x.to_raw_bytes());
+
+        let mut header = vec![];
+        header.extend_from_slice(&PTAU_N8.to_le_bytes());
+        header.extend_from_slice(&[0; PTAU_N8 as usize]); // modulus, not read
+        header.extend_from_slice(&power.to_le_bytes());
+
+        let mut tau_g1 = vec![];
+        let mut tau_i = Fr::one();
+        for _ in 0..1 << power {
+            let point = (G1Affine::generator() * tau_i).to_affine();
+            write_fq(&mut tau_g1, &point.x);
+            write_fq(&mut tau_g1, &point.y);
+            tau_i *= tau;
+        }
+
+        let mut tau_g2 = vec![];
+        for point in [
+            G2Affine::generator(),
+            (G2Affine::generator() * tau).to_affine(),
+        ] {
+            for x in [&point.x.c0, &point.x.c1, &point.y.c0, &point.y.c1] {
+                write_fq(&mut tau_g2, x);
+            }
+        }
+
+        let mut bytes = PTAU_MAGIC.to_vec();
+        bytes.extend_from_slice(&1u32.to_le_bytes()); // version
+        bytes.extend_from_slice(&3u32.to_le_bytes()); // number of sections
+        for (section_type, section) in [
+            (PTAU_SECTION_HEADER, header),
+            (PTAU_SECTION_TAU_G1, tau_g1),
+            (PTAU_SECTION_TAU_G2, tau_g2),
+        ] {
+            bytes.extend_from_slice(&section_type.to_le_bytes());
+            bytes.extend_from_slice(&(section.len() as u64).to_le_bytes());
+            bytes.extend(section);
+        }
+        bytes
+    }
+
+    fn write_transcript(name: &str, bytes: &[u8]) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("{name}-{}.ptau", std::process::id()));
+        fs::write(&path, bytes).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_read_ptau() -> Result<()> {
+        let tau = Fr::from(7);
+        let path = write_transcript("ptau", &ptau_bytes(3, tau));
+        assert_eq!(TranscriptFormat::detect(&path)?, TranscriptFormat::Ptau);
+
+        let powers = read_ptau(&path, 2)?;
+        assert_eq!(powers.g.len(), 4);
+        assert_eq!(powers.s_g2, (G2Affine::generator() * tau).to_affine());
+        verify_tau_powers(&powers)?;
+        let params = build_params(powers, 2)?;
+        assert_eq!(params.k(), 2);
+        assert_eq!(params.s_g2(), (G2Affine::generator() * tau).to_affine());
+
+        // more powers than the transcript has
+        assert!(read_ptau(&path, 4).is_err());
+        // consistent, but not from a pinned ceremony
+        assert!(import_params(&path, 2).is_err());
+
+        fs::remove_file(path)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_ptau_truncated() {
+        let bytes = ptau_bytes(2, Fr::from(7));
+        for len in [0, 3, 12, 40, bytes.len() / 2, bytes.len() - 1] {
+            let path = write_transcript(&format!("ptau-truncated-{len}"), &bytes[..len]);
+            assert!(read_ptau(&path, 2).is_err(), "truncated to {len} bytes");
+            fs::remove_file(path).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_read_ptau_corrupt() -> Result<()> {
+        let mut bytes = ptau_bytes(2, Fr::from(7));
+
+        // a G1 power that is not the next power of tau
+        let path = write_transcript("ptau-swapped", &bytes);
+        let mut powers = read_ptau(&path, 2)?;
+        powers.g.swap(2, 3);
+        assert!(verify_tau_powers(&powers).is_err());
+        fs::remove_file(path)?;
+
+        // [tau]_2 off the curve
+        let len = bytes.len();
+        bytes[len - PTAU_N8 as usize] ^= 1;
+        let path = write_transcript("ptau-corrupt", &bytes);
+        assert!(read_ptau(&path, 2).is_err());
+        fs::remove_file(path)?;
+
+        // a section size past the end of the file
+        let mut bytes = ptau_bytes(2, Fr::from(7));
+        bytes[16..24].copy_from_slice(&u64::MAX.to_le_bytes());
+        let path = write_transcript("ptau-section", &bytes);
+        assert!(read_ptau(&path, 2).is_err());
+        fs::remove_file(path)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_pinned_s_g2() {
+        let reviewed: Vec<_> = parse_trusted_s_g2(TRUSTED_S_G2_LIST).collect();
+        assert_eq!(reviewed, vec![PARAMS_G2_SECRET_POWER]);
+        assert_eq!(
+            parse_trusted_s_g2("# comment\n\n  a  \nb\n").collect::<Vec<_>>(),
+            vec!["a", "b"]
+        );
+
+        assert!(!is_trusted_s_g2(&G2Affine::generator()));
+        assert!(!is_trusted_s_g2(
+            &(G2Affine::generator() * Fr::from(7)).to_affine()
+        ));
+    }
+}
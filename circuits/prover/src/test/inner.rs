@@ -1,20 +1,22 @@
 use crate::{
+    aggregator::{OabeVerifier, VerifyMode},
     common::{Prover, Verifier},
     config::{LayerId, INNER_DEGREE},
     utils::{gen_rng, read_env_var, load_params},
     zkevm::circuit::{SuperCircuit, TargetCircuit, },
     WitnessBlock, EvmProof, proof::dump_as_json, io::{serialize_vk, write_file}, BatchProof,
+    metrics,
 };
-use std::{sync::{LazyLock, Mutex}, ops::Mul, marker::PhantomData, fs::File, path::{Path, PathBuf}, env};
+use std::{collections::BTreeMap, sync::{LazyLock, Mutex}, ops::Mul, marker::PhantomData, fs::File, path::{Path, PathBuf}, env};
 
-use aggregator::{CompressionCircuit, extract_proof_and_instances_with_pairing_check};
+use aggregator::{CompressionCircuit, CompressionWideParams, extract_proof_and_instances_with_pairing_check};
 use ark_std::{start_timer, end_timer};
 use eth_types::U256;
 use ethers_core::k256::elliptic_curve::Group;
 use halo2_proofs::{arithmetic::Field, dev::MockProver, halo2curves::bn256::{pairing, Bn256, Fq12, Fr, G1Affine, G2Affine, G2}, plonk::{keygen_pk, keygen_vk}, poly::{commitment::Params, kzg::commitment::ParamsKZG}};
 use rand::rngs::OsRng;
-use snark_verifier_sdk::{gen_snark_shplonk, CircuitExt, gen_evm_proof_shplonk};
-use zkevm_circuits::{fraud_circuit::MyFraudCircuit, pairing_circuit::MyEccCircuit, util::SubCircuit};
+use snark_verifier_sdk::{CircuitExt, gen_evm_proof_shplonk};
+use zkevm_circuits::{ciphertext_circuit::LsssPolicy, fraud_circuit::MyFraudCircuit, pairing_circuit::MyEccCircuit, util::SubCircuit};
 
 static INNER_PROVER: LazyLock<Mutex<Prover>> = LazyLock::new(|| {
     let params_dir = read_env_var("SCROLL_PROVER_PARAMS_DIR", "./test_params".to_string());
@@ -63,12 +65,15 @@ pub fn oabe_prove () {
     let n = 5;
 
     let mut ws = vec![];
+    let mut attributes = vec![];
     let mut c_points = vec![];
     let mut d_points = vec![];
     let mut r_points = vec![];
 
+    // a policy requiring all n attributes, under which every w_i is 1
     for i in 0..n {
-        ws.push(Fr::random(&mut OsRng));
+        ws.push(Fr::one());
+        attributes.push(G1Affine::random(&mut OsRng));
         c_points.push(G1Affine::random(&mut OsRng));
         d_points.push(G1Affine::random(&mut OsRng));
         r_points.push(G2Affine::from(G2::random(&mut OsRng)));
@@ -142,6 +147,9 @@ pub fn oabe_prove () {
         p1s,
         p2s,
         ws,
+        policy: LsssPolicy::and(attributes.clone()),
+        rows: (0..n).collect(),
+        key_attributes: attributes,
         p4,
         ct,
         tk,
@@ -157,8 +165,6 @@ pub fn oabe_prove () {
 
     // println!("finish mock prove");
 
-    std::env::set_var("COMPRESSION_CONFIG", "../aggregator/configs/compression_wide.config");
-
     let inner_k = 19;
 
     let outer_k = 23;
@@ -180,25 +186,18 @@ pub fn oabe_prove () {
     // params_inner.downsize(22);
     // end_timer!(timer);
 
-    let timer = start_timer!(|| "build vk");
-    let vk = keygen_vk(&params_inner, &circuit).expect("keygen_vk should not fail");
-
-    let current_dir = env::current_dir().unwrap();
-    let mut path = PathBuf::from(current_dir);
-    write_file(&mut path, "vk", &serialize_vk(&vk));
-    end_timer!(timer);
-
-    let timer = start_timer!(|| "build pk");
-    let pk = keygen_pk(&params_inner, vk, &circuit).expect("keygen_pk should not fail");
-
-    end_timer!(timer);
+    let mut prover = Prover::from_params(BTreeMap::from([(inner_k, params_inner.clone())]));
 
     let timer = start_timer!(|| "generate inner snark");
-    let prev_snark = gen_snark_shplonk(&params_inner, &pk, circuit, &mut OsRng, None::<String>).unwrap();
+    let prev_snark = prover.gen_oabe_snark("oabe", inner_k, OsRng, circuit).unwrap();
 
     println!("generate oabe snark");
     end_timer!(timer);
 
+    let current_dir = env::current_dir().unwrap();
+    let mut path = PathBuf::from(current_dir);
+    write_file(&mut path, "vk", &serialize_vk(prover.pk("oabe").unwrap().get_vk()));
+
 
     // extract_proof_and_instances_with_pairing_check(
     //     &params_outer,
@@ -209,7 +208,7 @@ pub fn oabe_prove () {
     println!("finish out pairing check");
 
     //compression
-    let comp_circuit = CompressionCircuit::new(
+    let comp_circuit = CompressionCircuit::<CompressionWideParams>::new(
         &params_outer,
         prev_snark,
         false,
@@ -250,15 +249,27 @@ pub fn oabe_prove () {
     batch_proof.dump("./attr5_no_outer", "agg").unwrap();
     end_timer!(timer);
 
+    let verifier = OabeVerifier::with_mode(
+        params_outer.clone(),
+        pk1.get_vk().clone(),
+        None,
+        VerifyMode::Native,
+    );
+    assert!(
+        verifier.verify_agg_evm_proof(batch_proof),
+        "failed to verify oabe proof natively"
+    );
+
 
     // if read_env_var("SCROLL_PROVER_DUMP_YUL", false) {
     //     println!("gen_evm_verifier");
     //     crate::evm::gen_evm_verifier::<CompressionCircuit>(&params, pk1.get_vk(), &evm_proof, Some("./test/evm_verifier"));
     // }
     let timer = start_timer!(|| "gen_evm_verifier");
-    crate::evm::gen_evm_verifier::<CompressionCircuit>(&params_outer, pk1.get_vk(), &evm_proof, Some("./attr5_no_outer"));
+    crate::evm::gen_evm_verifier::<CompressionCircuit<CompressionWideParams>>(&params_outer, pk1.get_vk(), &evm_proof, Some("./attr5_no_outer"));
     end_timer!(timer);
 
+    metrics::take_and_dump(&format!("oabe_{n}"));
     println!("finish prove of attr number:{:?}", n);
 }
 
@@ -1,6 +1,7 @@
 use aggregator::ChunkInfo;
 use eth_types::l2_types::BlockTrace;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use zkevm_circuits::evm_circuit::witness::Block;
 
 pub type WitnessBlock = Block;
@@ -30,14 +31,20 @@ impl ChunkProvingTask {
     pub fn is_empty(&self) -> bool {
         self.block_traces.is_empty()
     }
-    /// Used for cache/load proof from disk
+    /// Sha256 of the block traces, distinguishing different traces of the same blocks.
+    pub fn content_hash(&self) -> [u8; 32] {
+        let mut hasher = Sha256::new();
+        serde_json::to_writer(&mut hasher, &self.block_traces).expect("serialize block traces");
+        hasher.finalize().into()
+    }
+
+    /// Used for cache/load proof from disk: the first block number followed by the start of the
+    /// content hash, so that re-executed blocks don't reuse stale proofs.
     pub fn identifier(&self) -> String {
-        self.block_traces
-            .first()
-            .map_or(0, |trace: &BlockTrace| {
-                trace.header.number.expect("block num").low_u64()
-            })
-            .to_string()
+        let block_number = self.block_traces.first().map_or(0, |trace: &BlockTrace| {
+            trace.header.number.expect("block num").low_u64()
+        });
+        format!("{block_number}_{}", hex::encode(&self.content_hash()[..8]))
     }
 }
 
@@ -47,14 +54,23 @@ pub struct BatchProvingTask {
 }
 
 impl BatchProvingTask {
-    /// Used for cache/load proof from disk
+    /// Sha256 of the chunk proofs.
+    pub fn content_hash(&self) -> [u8; 32] {
+        let mut hasher = Sha256::new();
+        serde_json::to_writer(&mut hasher, &self.chunk_proofs).expect("serialize chunk proofs");
+        hasher.finalize().into()
+    }
+
+    /// Used for cache/load proof from disk: the public input hash of the last chunk followed by
+    /// the start of the content hash.
     pub fn identifier(&self) -> String {
-        self.chunk_proofs
+        let pi_hash = self
+            .chunk_proofs
             .last()
             .unwrap()
             .chunk_info
             .public_input_hash()
-            .to_low_u64_le()
-            .to_string()
+            .to_low_u64_le();
+        format!("{pi_hash}_{}", hex::encode(&self.content_hash()[..8]))
     }
 }
@@ -1,13 +1,24 @@
 #![allow(deprecated)]
 use crate::{
+    config::INSECURE_TEST_SETUP,
+    setup::is_trusted_s_g2,
     types::BlockTraceJsonRpcResult,
-    zkevm::circuit::{block_traces_to_witness_block, print_chunk_stats},
+    zkevm::circuit::{
+        block_trace_stream_to_witness_block, block_traces_to_witness_block, print_chunk_stats,
+    },
 };
 use anyhow::{bail, Result};
 use chrono::Utc;
-use eth_types::l2_types::BlockTrace;
+use eth_types::l2_types::{
+    binary::{BlockTraceReader, BlockTraceWriter},
+    BlockTrace,
+};
 use git_version::git_version;
-use halo2_proofs::{halo2curves::bn256::Bn256, poly::kzg::commitment::ParamsKZG, SerdeFormat};
+use halo2_proofs::{
+    halo2curves::bn256::Bn256,
+    poly::{commitment::Params, kzg::commitment::ParamsKZG},
+    SerdeFormat,
+};
 use log::LevelFilter;
 use log4rs::{
     append::{
@@ -18,8 +29,9 @@ use log4rs::{
 };
 use rand::{Rng, SeedableRng};
 use rand_xorshift::XorShiftRng;
-use std::fmt::Debug;
+use sha2::{Digest, Sha256};
 use std::{
+    fmt::Debug,
     fs::{self, metadata, File},
     io::{BufReader, Read},
     path::{Path, PathBuf},
@@ -36,11 +48,31 @@ pub const GIT_VERSION: &str = git_version!(args = ["--abbrev=7", "--always"]);
 pub const PARAMS_G2_SECRET_POWER: &str = "(Fq2 { c0: 0x17944351223333f260ddc3b4af45191b856689eda9eab5cbcddbbe570ce860d2, c1: 0x186282957db913abd99f91db59fe69922e95040603ef44c0bd7aa3adeef8f5ac }, Fq2 { c0: 0x297772d34bc9aa8ae56162486363ffe417b02dc7e8c207fc2cc20203e67a02ad, c1: 0x298adc7396bd3865cbf6d6df91bae406694e6d2215baa893bdeadb63052895f4 })";
 
 /// Load setup params from a file.
+///
+/// When `SCROLL_PROVER_INSECURE_TEST_SETUP` is on and the prover is built with the
+/// `insecure-test-setup` feature, the params are instead generated from a public seed (see
+/// `load_or_gen_insecure_test_params`), so that tests can run without downloading the setup files.
+/// Without the feature, the variable is an error rather than silently ignored.
 pub fn load_params(
     params_dir: &str,
     degree: u32,
     serde_fmt: Option<SerdeFormat>,
 ) -> Result<ParamsKZG<Bn256>> {
+    if *INSECURE_TEST_SETUP {
+        #[cfg(feature = "insecure-test-setup")]
+        {
+            let params_path = Path::new(params_dir);
+            let cache_dir = if params_path.is_dir() {
+                params_path
+            } else {
+                params_path.parent().unwrap_or(Path::new("."))
+            };
+            return load_or_gen_insecure_test_params(&cache_dir.to_string_lossy(), degree);
+        }
+        #[cfg(not(feature = "insecure-test-setup"))]
+        bail!("SCROLL_PROVER_INSECURE_TEST_SETUP is set, but the prover is built without the insecure-test-setup feature");
+    }
+
     log::info!("Start loading params with degree {}", degree);
     let params_path = if metadata(params_dir)?.is_dir() {
         // auto load
@@ -51,7 +83,7 @@ pub fn load_params(
     if !Path::new(&params_path).exists() {
         bail!("Need to download params by `make download-setup -e degree={degree}`");
     }
-    let f = File::open(params_path)?;
+    let f = File::open(&params_path)?;
 
     // check params file length:
     //   len: 4 bytes
@@ -75,7 +107,7 @@ pub fn load_params(
     }
 
     let p = ParamsKZG::<Bn256>::read_custom::<_>(&mut BufReader::new(f), serde_fmt)?;
-    if format!("{:?}", p.s_g2()) != PARAMS_G2_SECRET_POWER {
+    if !is_trusted_s_g2(&p.s_g2()) {
         bail!("Wrong params file of degree {}", degree);
     }
 
@@ -83,6 +115,93 @@ pub fn load_params(
     Ok(p)
 }
 
+#[cfg(feature = "insecure-test-setup")]
+pub use insecure::{insecure_param_path_for_degree, load_or_gen_insecure_test_params};
+
+/// Insecure params for tests, only built with the `insecure-test-setup` feature.
+#[cfg(feature = "insecure-test-setup")]
+mod insecure {
+    use super::DEFAULT_SERDE_FORMAT;
+    use anyhow::{bail, Result};
+    use halo2_proofs::{
+        halo2curves::bn256::Bn256,
+        poly::{commitment::Params, kzg::commitment::ParamsKZG},
+    };
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+    use std::{
+        collections::BTreeMap,
+        fs::{self, File},
+        io::BufReader,
+        sync::{LazyLock, Mutex},
+    };
+
+    /// Seed of the insecure test setup. Anyone knowing it can recover the toxic waste.
+    const INSECURE_TEST_SETUP_SEED: [u8; 16] = *b"insecure-setup!!";
+
+    /// In-process cache of the insecure test params, keyed by degree.
+    static INSECURE_TEST_PARAMS: LazyLock<Mutex<BTreeMap<u32, ParamsKZG<Bn256>>>> =
+        LazyLock::new(|| Mutex::new(BTreeMap::new()));
+
+    /// INSECURE: deterministically generate params of `degree` from a public seed, for tests only.
+    ///
+    /// Generated params are cached in memory and written to `params_dir` as
+    /// `insecure_params{degree}`, next to (and never in place of) the real setup files. A cached
+    /// file is only accepted if its `s_g2` matches the one derived from the seed.
+    pub fn load_or_gen_insecure_test_params(
+        params_dir: &str,
+        degree: u32,
+    ) -> Result<ParamsKZG<Bn256>> {
+        log::warn!(
+            "INSECURE test setup in use for params of degree {degree}, never use in production"
+        );
+
+        let mut cache = INSECURE_TEST_PARAMS
+            .lock()
+            .expect("poisoned insecure test params");
+        if let Some(params) = cache.get(&degree) {
+            return Ok(params.clone());
+        }
+
+        let params_path = insecure_param_path_for_degree(params_dir, degree);
+        let expected_s_g2 = insecure_test_params_setup(1).s_g2();
+        let params = match File::open(&params_path) {
+            Ok(f) => {
+                let params =
+                    ParamsKZG::<Bn256>::read_custom(&mut BufReader::new(f), DEFAULT_SERDE_FORMAT)?;
+                if params.s_g2() != expected_s_g2 || params.k() != degree {
+                    bail!("Wrong insecure test params file {params_path}, remove it to regenerate");
+                }
+                params
+            }
+            Err(_) => {
+                log::info!("Generate insecure test params of degree {degree}");
+                let params = insecure_test_params_setup(degree);
+
+                let written = fs::create_dir_all(params_dir).and_then(|_| {
+                    let mut f = File::create(&params_path)?;
+                    params.write_custom(&mut f, DEFAULT_SERDE_FORMAT)
+                });
+                if let Err(err) = written {
+                    log::warn!("Failed to cache insecure test params to {params_path}: {err}");
+                }
+                params
+            }
+        };
+
+        cache.insert(degree, params.clone());
+        Ok(params)
+    }
+
+    fn insecure_test_params_setup(degree: u32) -> ParamsKZG<Bn256> {
+        ParamsKZG::<Bn256>::setup(degree, XorShiftRng::from_seed(INSECURE_TEST_SETUP_SEED))
+    }
+
+    pub fn insecure_param_path_for_degree(params_dir: &str, degree: u32) -> String {
+        format!("{params_dir}/insecure_params{degree}")
+    }
+}
+
 #[deprecated]
 fn post_process_tx_storage_proof(trace: &mut BlockTrace) {
     // fill intrinsicStorageProofs into tx storage proof
@@ -114,25 +233,29 @@ fn post_process_tx_storage_proof(trace: &mut BlockTrace) {
 
 /// get a block-result from file
 pub fn get_block_trace_from_file<P: AsRef<Path>>(path: P) -> BlockTrace {
+    read_block_trace_from_file(path).unwrap()
+}
+
+/// Read a block trace from JSON, plain or wrapped in a JSON RPC result.
+pub fn read_block_trace_from_file<P: AsRef<Path>>(path: P) -> Result<BlockTrace> {
     let mut buffer = Vec::new();
-    let mut f = File::open(&path).unwrap();
-    f.read_to_end(&mut buffer).unwrap();
-
-    let mut trace = serde_json::from_slice::<BlockTrace>(&buffer).unwrap_or_else(|e1| {
-        serde_json::from_slice::<BlockTraceJsonRpcResult>(&buffer)
-            .map_err(|e2| {
-                panic!(
-                    "unable to load BlockTrace from {:?}, {:?}, {:?}",
-                    path.as_ref(),
-                    e1,
-                    e2
-                )
-            })
-            .unwrap()
-            .result
-    });
+    let mut f = File::open(&path)?;
+    f.read_to_end(&mut buffer)?;
+
+    let mut trace = match serde_json::from_slice::<BlockTrace>(&buffer) {
+        Ok(trace) => trace,
+        Err(e1) => match serde_json::from_slice::<BlockTraceJsonRpcResult>(&buffer) {
+            Ok(result) => result.result,
+            Err(e2) => bail!(
+                "unable to load BlockTrace from {:?}, {:?}, {:?}",
+                path.as_ref(),
+                e1,
+                e2
+            ),
+        },
+    };
     post_process_tx_storage_proof(&mut trace);
-    trace
+    Ok(trace)
 }
 
 pub fn read_env_var<T: Debug + Clone + FromStr>(var_name: &'static str, default: T) -> T {
@@ -166,6 +289,48 @@ pub fn chunk_trace_to_witness_block(chunk_trace: Vec<BlockTrace>) -> Result<Bloc
     block_traces_to_witness_block(chunk_trace)
 }
 
+/// Build the witness block of a chunk streamed from a file in the binary format of
+/// [`eth_types::l2_types::binary`], decoding one block at a time.
+pub fn chunk_trace_file_to_witness_block<P: AsRef<Path>>(path: P) -> Result<Block> {
+    log::info!("stream block traces from {:?}", path.as_ref());
+    let block_traces = BlockTraceReader::open(path)?;
+    block_trace_stream_to_witness_block(block_traces.map(|trace| Ok(trace?)))
+}
+
+/// Identifier of a chunk trace file, in the format of `ChunkProvingTask::identifier`: the first
+/// block number followed by the start of the sha256 of the file. It differs from the identifier
+/// of the same traces in JSON.
+pub fn chunk_trace_file_identifier<P: AsRef<Path>>(path: P) -> Result<String> {
+    let mut hasher = Sha256::new();
+    std::io::copy(&mut File::open(&path)?, &mut hasher)?;
+
+    let block_number = BlockTraceReader::open(&path)?
+        .next()
+        .transpose()?
+        .map_or(0, |trace| trace.header.number.expect("block num").low_u64());
+    Ok(format!(
+        "{block_number}_{}",
+        hex::encode(&hasher.finalize()[..8])
+    ))
+}
+
+/// Convert block traces from JSON, plain or wrapped in a JSON RPC result, to a chunk trace file
+/// in the binary format. Blocks are converted one at a time in the given order. Return the number
+/// of blocks written.
+pub fn convert_block_traces_to_binary<P: AsRef<Path>>(
+    inputs: &[P],
+    output: impl AsRef<Path>,
+) -> Result<usize> {
+    let mut writer = BlockTraceWriter::create(output)?;
+    for input in inputs {
+        writer.write(&read_block_trace_from_file(input)?)?;
+    }
+
+    let num_blocks = writer.num_blocks();
+    writer.finish()?;
+    Ok(num_blocks)
+}
+
 // Return the output dir.
 pub fn init_env_and_log(id: &str) -> String {
     dotenvy::dotenv().ok();
@@ -1,4 +1,5 @@
 use super::circuit::{calculate_row_usage_of_witness_block, finalize_builder};
+use crate::config::CIRCUITS_PROFILE;
 use bus_mapping::circuit_input_builder::{self, CircuitInputBuilder};
 use eth_types::{
     l2_types::BlockTrace,
@@ -6,11 +7,9 @@ use eth_types::{
     ToWord, H256,
 };
 use itertools::Itertools;
-use mpt_zktrie::state::ZktrieState;
+use mpt_zktrie::state::{NodeStore, ZktrieState};
 use serde_derive::{Deserialize, Serialize};
-use zkevm_circuits::super_circuit::params::{
-    get_sub_circuit_limit_and_confidence, get_super_circuit_params,
-};
+use std::sync::Arc;
 
 pub use super::SubCircuitRowUsage;
 
@@ -37,9 +36,11 @@ impl RowUsage {
             row_usage_details: Vec::new(),
         }
     }
-    // We treat 1M as 100%
+    // We treat 1M as 100%. Dynamic limits are resolved by the profile, and a limit that still
+    // comes out as zero rows is skipped rather than divided by.
     pub fn normalize(&self) -> Self {
-        let real_available_rows: Vec<_> = get_sub_circuit_limit_and_confidence()
+        let real_available_rows: Vec<_> = CIRCUITS_PROFILE
+            .sub_circuit_limit_and_confidence()
             .into_iter()
             .map(|(limit, confidence)| (limit as f64 * confidence) as usize)
             .collect();
@@ -49,7 +50,10 @@ impl RowUsage {
             .zip_eq(real_available_rows.iter())
             .map(|(x, limit)| SubCircuitRowUsage {
                 name: x.name.clone(),
-                row_number: (1_000_000u64 * (x.row_number as u64) / (*limit as u64)) as usize,
+                row_number: match *limit {
+                    0 => 0,
+                    limit => (1_000_000u64 * (x.row_number as u64) / (limit as u64)) as usize,
+                },
             })
             .collect_vec();
         log::trace!(
@@ -99,6 +103,9 @@ pub struct CircuitCapacityChecker {
     pub acc_row_usage: RowUsage,
     pub row_usages: Vec<RowUsage>,
     pub builder_ctx: Option<(CodeDB, StateDB, Option<ZktrieState>)>,
+    /// Store keeping the zktrie nodes across chunks when not in light mode, so that only the
+    /// nodes missing from it are hashed from the traces.
+    pub node_store: Option<Arc<dyn NodeStore>>,
 }
 
 impl Default for CircuitCapacityChecker {
@@ -115,6 +122,7 @@ impl CircuitCapacityChecker {
             row_usages: Vec::new(),
             light_mode: true,
             builder_ctx: None,
+            node_store: None,
         }
     }
     pub fn reset(&mut self) {
@@ -125,6 +133,9 @@ impl CircuitCapacityChecker {
     pub fn set_light_mode(&mut self, light_mode: bool) {
         self.light_mode = light_mode;
     }
+    pub fn set_node_store(&mut self, node_store: Arc<dyn NodeStore>) {
+        self.node_store = Some(node_store);
+    }
     pub fn get_tx_num(&self) -> usize {
         self.row_usages.len()
     }
@@ -147,7 +158,7 @@ impl CircuitCapacityChecker {
                 // notice the prev_root in current builder may be not invalid (since the state has
                 // changed but we may not update it in light mode)
                 let mut builder_block =
-                    circuit_input_builder::Blocks::init(trace.chain_id, get_super_circuit_params());
+                    circuit_input_builder::Blocks::init(trace.chain_id, CIRCUITS_PROFILE.params);
                 builder_block.start_l1_queue_index = trace.start_l1_queue_index;
                 builder_block.prev_state_root = mpt_state
                     .as_ref()
@@ -172,15 +183,19 @@ impl CircuitCapacityChecker {
                 (builder, Some(code_db))
             } else {
                 (
-                    CircuitInputBuilder::new_from_l2_trace(
-                        get_super_circuit_params(),
+                    CircuitInputBuilder::new_from_l2_trace_with_node_store(
+                        CIRCUITS_PROFILE.params,
                         trace,
                         self.light_mode,
+                        self.node_store.clone(),
                     )?,
                     None,
                 )
             };
         let witness_block = finalize_builder(&mut estimate_builder)?;
+        if let Some(mpt_state) = &estimate_builder.mpt_init_state {
+            mpt_state.persist_nodes()?;
+        }
         let mut rows = calculate_row_usage_of_witness_block(&witness_block)?;
 
         let mut code_db = codedb_prev.unwrap_or_else(CodeDB::new);
@@ -1,12 +1,12 @@
-use builder::dummy_witness_block;
 use halo2_proofs::halo2curves::bn256::Fr;
 use snark_verifier_sdk::CircuitExt;
 use zkevm_circuits::{super_circuit::params::ScrollSuperCircuit, util::SubCircuit, witness};
 
 mod builder;
 pub use self::builder::{
-    block_traces_to_witness_block, calculate_row_usage_of_witness_block, finalize_builder,
-    print_chunk_stats, validite_block_traces,
+    block_trace_stream_to_witness_block, block_traces_to_witness_block,
+    calculate_row_usage_of_witness_block, dummy_witness_block, finalize_builder, print_chunk_stats,
+    validite_block_traces,
 };
 
 pub use zkevm_circuits::super_circuit::params::{MAX_CALLDATA, MAX_INNER_BLOCKS, MAX_TXS};
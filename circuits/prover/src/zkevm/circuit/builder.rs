@@ -1,4 +1,4 @@
-use crate::{utils::read_env_var, zkevm::SubCircuitRowUsage};
+use crate::{config::CIRCUITS_PROFILE, utils::read_env_var, zkevm::SubCircuitRowUsage};
 use anyhow::{bail, Result};
 use bus_mapping::circuit_input_builder::{self, CircuitInputBuilder};
 use eth_types::{
@@ -10,9 +10,7 @@ use itertools::Itertools;
 use mpt_zktrie::state::ZkTrieHash;
 use std::sync::LazyLock;
 use zkevm_circuits::{
-    evm_circuit::witness::Block,
-    super_circuit::params::{get_super_circuit_params, ScrollSuperCircuit, MAX_TXS},
-    witness::block_convert,
+    evm_circuit::witness::Block, super_circuit::params::ScrollSuperCircuit, witness::block_convert,
 };
 
 static CHAIN_ID: LazyLock<u64> = LazyLock::new(|| read_env_var("CHAIN_ID", 534352));
@@ -103,7 +101,7 @@ pub fn validite_block_traces(block_traces: &[BlockTrace]) -> Result<()> {
 
 pub fn dummy_witness_block() -> Result<Block> {
     log::debug!("generate dummy witness block");
-    let builder_block = circuit_input_builder::Blocks::init(*CHAIN_ID, get_super_circuit_params());
+    let builder_block = circuit_input_builder::Blocks::init(*CHAIN_ID, CIRCUITS_PROFILE.params);
     let mut builder: CircuitInputBuilder =
         CircuitInputBuilder::new(StateDB::new(), CodeDB::new(), &builder_block);
     builder.finalize_building()?;
@@ -122,7 +120,7 @@ pub fn block_traces_to_witness_block(block_traces: Vec<BlockTrace>) -> Result<Bl
         .iter()
         .map(|b| b.transactions.len())
         .sum::<usize>();
-    if total_tx_num > MAX_TXS {
+    if total_tx_num > CIRCUITS_PROFILE.params.max_txs {
         bail!(
             "tx num overflow {}, block range {} to {}",
             total_tx_num,
@@ -135,17 +133,42 @@ pub fn block_traces_to_witness_block(block_traces: Vec<BlockTrace>) -> Result<Bl
         block_num,
         total_tx_num,
     );
-    for block_trace in block_traces.iter() {
+    block_trace_stream_to_witness_block(block_traces.into_iter().map(Ok))
+}
+
+/// Build the witness block of a chunk from its block traces, consumed one at a time so that only
+/// the block being added is held next to the builder. The traces are checked as they come.
+pub fn block_trace_stream_to_witness_block(
+    block_traces: impl IntoIterator<Item = Result<BlockTrace>>,
+) -> Result<Block> {
+    let mut block_traces = block_traces.into_iter();
+    let Some(first) = block_traces.next().transpose()? else {
+        bail!("use dummy_witness_block instead");
+    };
+
+    let mut block_num = 0;
+    let mut total_tx_num = 0;
+    let mut check = |block_trace: &BlockTrace| -> Result<()> {
+        validite_block_traces(std::slice::from_ref(block_trace))?;
+        block_num += 1;
+        total_tx_num += block_trace.transactions.len();
+        if total_tx_num > CIRCUITS_PROFILE.params.max_txs {
+            bail!(
+                "tx num overflow {}, at block {:?}",
+                total_tx_num,
+                block_trace.header.number
+            );
+        }
         log::debug!("start_l1_queue_index: {}", block_trace.start_l1_queue_index);
-    }
+        Ok(())
+    };
 
-    let mut traces = block_traces.into_iter();
-    let mut builder = CircuitInputBuilder::new_from_l2_trace(
-        get_super_circuit_params(),
-        traces.next().unwrap(),
-        false,
-    )?;
-    for (idx, block_trace) in traces.enumerate() {
+    check(&first)?;
+    let mut builder =
+        CircuitInputBuilder::new_from_l2_trace(CIRCUITS_PROFILE.params, first, false)?;
+    for (idx, block_trace) in block_traces.enumerate() {
+        let block_trace = block_trace?;
+        check(&block_trace)?;
         log::debug!(
             "add_more_l2_trace idx {}, block num {:?}",
             idx + 1,
@@ -153,6 +176,8 @@ pub fn block_traces_to_witness_block(block_traces: Vec<BlockTrace>) -> Result<Bl
         );
         builder.add_more_l2_trace(block_trace)?;
     }
+    log::debug!("added {block_num} blocks, {total_tx_num} txs to the builder");
+
     let witness_block = finalize_builder(&mut builder)?;
     // send to other thread to drop
     std::thread::spawn(move || drop(builder.block));
@@ -3,14 +3,19 @@ use crate::{
     config::{LayerId, ZKEVM_DEGREES},
     consts::CHUNK_VK_FILENAME,
     io::try_to_read,
+    metrics::{self, start_phase, Phase},
     proof::compare_chunk_info,
-    types::ChunkProvingTask,
-    utils::chunk_trace_to_witness_block,
+    types::{ChunkProvingTask, WitnessBlock},
+    utils::{
+        chunk_trace_file_identifier, chunk_trace_file_to_witness_block,
+        chunk_trace_to_witness_block,
+    },
     zkevm::circuit::calculate_row_usage_of_witness_block,
     ChunkProof,
 };
 use aggregator::ChunkInfo;
 use anyhow::Result;
+use std::path::Path;
 
 #[derive(Debug)]
 pub struct Prover {
@@ -52,14 +57,16 @@ impl Prover {
     /// Generate proof for a chunk. This method usually takes ~10minutes.
     /// Meaning of each parameter:
     ///   output_dir:
-    ///     If `output_dir` is not none, the dir will be used to save/load proof or intermediate results.
+    ///     If `output_dir` is not none, the dir will be used to save/load proof or intermediate
+    ///     results.
     ///     If proof or intermediate results can be loaded from `output_dir`,
     ///     then they will not be computed again.
     ///     If `output_dir` is not none, computed intermediate results and proof will be written
     ///     into this dir.
     ///   chunk_identifier:
     ///     used to distinguish different chunk files located in output_dir.
-    ///     If it is not set, default value(first block number of this chuk) will be used.
+    ///     If it is not set, default value(first block number and content hash of this chunk)
+    ///     will be used.
     ///   id:
     ///     TODO(zzhang). clean this. I think it can only be None or Some(0)...
     pub fn gen_chunk_proof(
@@ -73,18 +80,89 @@ impl Prover {
 
         let chunk_identifier =
             chunk_identifier.map_or_else(|| chunk.identifier(), |name| name.to_string());
+        let ChunkProvingTask {
+            chunk_info,
+            block_traces,
+        } = chunk;
+
+        self.gen_chunk_proof_with(
+            &chunk_identifier,
+            chunk_info.as_ref(),
+            || chunk_trace_to_witness_block(block_traces),
+            inner_id,
+            output_dir,
+        )
+    }
+
+    /// Generate proof for a chunk streamed from a trace file in the binary format of
+    /// `eth_types::l2_types::binary`, holding a single block trace in memory at a time. The
+    /// parameters are those of `gen_chunk_proof`, `chunk_info` standing for the one of the task.
+    /// `chunk_identifier` defaults to the first block number and the hash of the file.
+    pub fn gen_chunk_proof_from_trace_file(
+        &mut self,
+        trace_path: &Path,
+        chunk_info: Option<&ChunkInfo>,
+        chunk_identifier: Option<&str>,
+        inner_id: Option<&str>,
+        output_dir: Option<&str>,
+    ) -> Result<ChunkProof> {
+        let chunk_identifier = match chunk_identifier {
+            Some(name) => name.to_string(),
+            None => chunk_trace_file_identifier(trace_path)?,
+        };
+
+        self.gen_chunk_proof_with(
+            &chunk_identifier,
+            chunk_info,
+            || chunk_trace_file_to_witness_block(trace_path),
+            inner_id,
+            output_dir,
+        )
+    }
 
+    /// Record the metrics of proving the chunk, written as `metrics_chunk_{chunk_identifier}`.
+    fn gen_chunk_proof_with(
+        &mut self,
+        chunk_identifier: &str,
+        chunk_info_input: Option<&ChunkInfo>,
+        build_witness_block: impl FnOnce() -> Result<WitnessBlock>,
+        inner_id: Option<&str>,
+        output_dir: Option<&str>,
+    ) -> Result<ChunkProof> {
+        metrics::set_label("chunk", chunk_identifier);
+        let result = self.gen_chunk_proof_inner(
+            chunk_identifier,
+            chunk_info_input,
+            build_witness_block,
+            inner_id,
+            output_dir,
+        );
+        metrics::take_and_dump(&format!("chunk_{chunk_identifier}"));
+        result
+    }
+
+    fn gen_chunk_proof_inner(
+        &mut self,
+        chunk_identifier: &str,
+        chunk_info_input: Option<&ChunkInfo>,
+        build_witness_block: impl FnOnce() -> Result<WitnessBlock>,
+        inner_id: Option<&str>,
+        output_dir: Option<&str>,
+    ) -> Result<ChunkProof> {
         let chunk_proof = match output_dir
-            .and_then(|output_dir| ChunkProof::from_json_file(output_dir, &chunk_identifier).ok())
+            .and_then(|output_dir| ChunkProof::from_json_file(output_dir, chunk_identifier).ok())
         {
             Some(proof) => Ok(proof),
             None => {
-                let witness_block = chunk_trace_to_witness_block(chunk.block_traces)?;
+                let timer = start_phase(Phase::WitnessGen, "chunk");
+                let witness_block = build_witness_block()?;
+                drop(timer);
                 let row_usage = calculate_row_usage_of_witness_block(&witness_block)?;
+                metrics::record_row_usage(&row_usage);
                 log::info!("Got witness block");
 
                 let chunk_info = ChunkInfo::from_witness_block(&witness_block, false);
-                if let Some(chunk_info_input) = chunk.chunk_info.as_ref() {
+                if let Some(chunk_info_input) = chunk_info_input {
                     compare_chunk_info(
                         &format!("gen_chunk_proof {chunk_identifier:?}"),
                         &chunk_info,
@@ -92,7 +170,7 @@ impl Prover {
                     )?;
                 }
                 let snark = self.prover_impl.load_or_gen_final_chunk_snark(
-                    &chunk_identifier,
+                    chunk_identifier,
                     &witness_block,
                     inner_id,
                     output_dir,
@@ -108,7 +186,7 @@ impl Prover {
                 );
 
                 if let (Some(output_dir), Ok(proof)) = (output_dir, &result) {
-                    proof.dump(output_dir, &chunk_identifier)?;
+                    proof.dump(output_dir, chunk_identifier)?;
                 }
 
                 result
@@ -1,6 +1,6 @@
 use crate::{
     common,
-    config::{LAYER2_CONFIG_PATH, LAYER2_DEGREE},
+    config::{Layer2Params, LAYER2_DEGREE},
     consts::chunk_vk_filename,
     io::force_to_read,
     ChunkProof,
@@ -11,16 +11,15 @@ use halo2_proofs::{
     plonk::VerifyingKey,
     poly::kzg::commitment::ParamsKZG,
 };
-use std::env;
 
 #[derive(Debug)]
 pub struct Verifier {
     // Make it public for testing with inner functions (unnecessary for FFI).
-    pub inner: common::Verifier<CompressionCircuit>,
+    pub inner: common::Verifier<CompressionCircuit<Layer2Params>>,
 }
 
-impl From<common::Verifier<CompressionCircuit>> for Verifier {
-    fn from(inner: common::Verifier<CompressionCircuit>) -> Self {
+impl From<common::Verifier<CompressionCircuit<Layer2Params>>> for Verifier {
+    fn from(inner: common::Verifier<CompressionCircuit<Layer2Params>>) -> Self {
         Self { inner }
     }
 }
@@ -33,7 +32,6 @@ impl Verifier {
     pub fn from_dirs(params_dir: &str, assets_dir: &str) -> Self {
         let raw_vk = force_to_read(assets_dir, &chunk_vk_filename());
 
-        env::set_var("COMPRESSION_CONFIG", &*LAYER2_CONFIG_PATH);
         common::Verifier::from_params_dir(params_dir, *LAYER2_DEGREE, &raw_vk).into()
     }
 
@@ -1,5 +1,8 @@
 #![allow(clippy::map_entry)]
 
+mod yul;
+
+use crate::config::{CompilerBackend, CompilerConfig};
 use anyhow::{bail, Context, Result};
 use eth_types::{bytecode, Bytecode, Bytes, H256};
 use serde::{Deserialize, Serialize};
@@ -7,34 +10,60 @@ use sha3::Digest;
 use std::{
     collections::HashMap,
     io::{Read, Write},
-    path::PathBuf,
+    path::{Path, PathBuf},
     process::{Command, Stdio},
     str::FromStr,
     sync::Mutex,
 };
 
+#[derive(Default)]
 struct Cache {
     entries: HashMap<H256, Bytes>,
-    path: PathBuf,
+    /// file the new entries are appended to, if any
+    path: Option<PathBuf>,
 }
 
 impl Cache {
     pub fn new(path: PathBuf) -> Result<Self> {
         let entries = if let Ok(mut file) = std::fs::File::open(&path) {
-            let h256 = |s| H256::from_slice(&hex::decode(s).expect("cache load h256"));
-            let bytes = |s| Bytes::from(hex::decode(s).expect("cache load value"));
-
-            let mut buf = String::new();
-            file.read_to_string(&mut buf)?;
-            buf.lines()
-                .filter(|l| l.len() > 1)
-                .map(|l| l.split_once('=').unwrap())
-                .map(|(k, v)| (h256(k), bytes(v)))
-                .collect()
+            Self::load(&mut file)?
         } else {
             HashMap::new()
         };
-        Ok(Self { path, entries })
+        Ok(Self {
+            path: Some(path),
+            entries,
+        })
+    }
+
+    fn load(file: &mut std::fs::File) -> Result<HashMap<H256, Bytes>> {
+        let h256 = |s| H256::from_slice(&hex::decode(s).expect("cache load h256"));
+        let bytes = |s| Bytes::from(hex::decode(s).expect("cache load value"));
+
+        let mut buf = String::new();
+        file.read_to_string(&mut buf)?;
+        Ok(buf
+            .lines()
+            .filter(|l| l.len() > 1)
+            .map(|l| l.split_once('=').unwrap())
+            .map(|(k, v)| (h256(k), bytes(v)))
+            .collect())
+    }
+
+    /// Add the entries of a pre-built bundle, which are not written back to the cache file.
+    pub fn seed(&mut self, bundle: &Path) -> Result<()> {
+        let mut file = std::fs::File::open(bundle)
+            .with_context(|| format!("Unable to open bytecode bundle {}", bundle.display()))?;
+        let entries = Self::load(&mut file)?;
+        log::info!(
+            "{} bytecodes loaded from {}",
+            entries.len(),
+            bundle.display()
+        );
+        for (code_hash, bytecode) in entries {
+            self.entries.entry(code_hash).or_insert(bytecode);
+        }
+        Ok(())
     }
 
     pub fn get(&self, src: &str) -> Option<&Bytes> {
@@ -45,14 +74,16 @@ impl Cache {
         let code_hash = Self::hash(src);
 
         if !self.entries.contains_key(&code_hash) {
-            let entry = format!("{}={}\n", hex::encode(code_hash), hex::encode(&bytecode));
-            std::fs::OpenOptions::new()
-                .read(true)
-                .write(true)
-                .create(true)
-                .append(true)
-                .open(&self.path)?
-                .write_all(entry.as_bytes())?;
+            if let Some(path) = &self.path {
+                let entry = format!("{}={}\n", hex::encode(code_hash), hex::encode(&bytecode));
+                std::fs::OpenOptions::new()
+                    .read(true)
+                    .write(true)
+                    .create(true)
+                    .append(true)
+                    .open(path)?
+                    .write_all(entry.as_bytes())?;
+            }
 
             self.entries.insert(code_hash, bytecode);
         }
@@ -185,21 +216,99 @@ struct SourceLocation {
     start: i32,
 }
 
+#[derive(Debug, Clone, Copy)]
+enum Tool {
+    Solc,
+    Lllc,
+}
+
+impl Tool {
+    fn name(&self) -> &'static str {
+        match self {
+            Tool::Solc => "solc",
+            Tool::Lllc => "lllc",
+        }
+    }
+}
+
+/// Path of the local `solc`, following the layout of `svm` when a version is configured.
+fn local_solc(config: &CompilerConfig) -> PathBuf {
+    let Some(version) = &config.solc_version else {
+        return PathBuf::from("solc");
+    };
+    let solc_dir = config
+        .solc_dir
+        .clone()
+        .or_else(|| std::env::var_os("SVM_HOME").map(PathBuf::from))
+        .or_else(|| std::env::var_os("HOME").map(|home| Path::new(&home).join(".svm")));
+    let binary = format!("solc-{version}");
+    solc_dir
+        .into_iter()
+        .flat_map(|dir| [dir.join(version).join(&binary), dir.join(&binary)])
+        .find(|path| path.is_file())
+        // otherwise, let it be resolved from the PATH
+        .unwrap_or_else(|| PathBuf::from(binary))
+}
+
 #[derive(Default)]
 pub struct Compiler {
     cache: Option<Mutex<Cache>>,
     compile: bool,
+    backend: CompilerBackend,
+    solc: Option<PathBuf>,
 }
 
 impl Compiler {
     pub fn new(compile: bool, cache_path: Option<PathBuf>) -> Result<Self> {
         let cache = cache_path.map(Cache::new).transpose()?.map(Mutex::new);
-        Ok(Compiler { compile, cache })
+        Ok(Compiler {
+            compile,
+            cache,
+            ..Default::default()
+        })
+    }
+
+    /// Compiler with the backend and bytecode bundle of the `[compiler]` config
+    pub fn from_config(
+        compile: bool,
+        cache_path: Option<PathBuf>,
+        config: &CompilerConfig,
+    ) -> Result<Self> {
+        let mut cache = cache_path.map(Cache::new).transpose()?;
+        if let Some(bundle) = &config.bundle {
+            cache.get_or_insert_with(Cache::default).seed(bundle)?;
+        }
+        let solc = (config.backend == CompilerBackend::Local).then(|| local_solc(config));
+        log::info!("Compiler backend {:?} {:?}", config.backend, solc);
+        Ok(Compiler {
+            cache: cache.map(Mutex::new),
+            compile,
+            backend: config.backend,
+            solc,
+        })
+    }
+
+    fn command(&self, tool: Tool) -> Result<Command> {
+        Ok(match self.backend {
+            CompilerBackend::Docker => {
+                let mut command = Command::new("docker");
+                command.args(["run", "-i", "--rm", tool.name()]);
+                command
+            }
+            CompilerBackend::Local => match (tool, &self.solc) {
+                (Tool::Solc, Some(solc)) => Command::new(solc),
+                _ => Command::new(tool.name()),
+            },
+            CompilerBackend::Native => {
+                bail!("{} is not available with the native backend", tool.name())
+            }
+        })
     }
 
     /// the concurrency level of the exec is controlled by rayon parallelism
-    fn exec(args: &[&str], stdin: &str) -> Result<String> {
-        let mut child = Command::new("docker")
+    fn exec(&self, tool: Tool, args: &[&str], stdin: &str) -> Result<String> {
+        let mut child = self
+            .command(tool)?
             .args(args)
             .stdin(Stdio::piped())
             .stderr(Stdio::piped())
@@ -220,7 +329,8 @@ impl Compiler {
         } else {
             let err = String::from_utf8(output.stderr)?;
             bail!(
-                "docker {:?} failed {:?} when compiling >>>{:?}<<<",
+                "{:?} {:?} failed {:?} when compiling >>>{:?}<<<",
+                self.backend,
                 args,
                 err,
                 stdin
@@ -255,7 +365,7 @@ impl Compiler {
             bail!("No way to compile LLLC for '{}'", src)
         }
 
-        let stdout = Self::exec(&["run", "-i", "--rm", "lllc"], src)?;
+        let stdout = self.exec(Tool::Lllc, &[], src)?;
         let bytecode = Bytes::from(hex::decode(stdout.trim())?);
 
         if let Some(ref cache) = self.cache {
@@ -274,7 +384,8 @@ impl Compiler {
         self.solc(Language::Yul, src, evm_version)
     }
 
-    /// compiles YUL code, this is the exact script used in retesteth
+    /// compiles YUL code, this is the exact script used in retesteth. With the native backend,
+    /// the code is neither optimized nor depends on the evm version.
     pub fn yul(&self, src: &str, optimize_level: u32, evm_version: Option<&str>) -> Result<Bytes> {
         if let Some(bytecode) = self
             .cache
@@ -287,7 +398,14 @@ impl Compiler {
         if !self.compile {
             bail!("No way to compile Yul for '{}'", src)
         }
-        let mut cmd = vec!["run", "-i", "--rm", "solc", "--strict-assembly"];
+        if self.backend == CompilerBackend::Native {
+            // the cache is keyed by the source only and shared with solc, so the unoptimized
+            // output of the native backend is not persisted
+            return Ok(Bytes::from(
+                yul::compile(src).with_context(|| format!("Unable to compile: {src}"))?,
+            ));
+        }
+        let mut cmd = vec!["--strict-assembly"];
         if optimize_level == 1 {
             cmd.push("--optimize");
             cmd.push("--yul-optimizations=:");
@@ -298,7 +416,7 @@ impl Compiler {
         }
         cmd.push("-");
 
-        let stdout = Self::exec(&cmd, src)?;
+        let stdout = self.exec(Tool::Solc, &cmd, src)?;
         let placeholder = "Binary representation:\n";
         let from_pos = stdout.find(placeholder);
         let len = from_pos.and_then(|pos| stdout[pos + placeholder.len()..].find('\n'));
@@ -333,10 +451,7 @@ impl Compiler {
         }
         let compiler_input = CompilerInput::new_default(language, src, evm_version);
         let stdin = serde_json::to_string(&compiler_input).unwrap();
-        let stdout = Self::exec(
-            &["run", "-i", "--rm", "solc", "--standard-json", "-"],
-            stdin.as_str(),
-        )?;
+        let stdout = self.exec(Tool::Solc, &["--standard-json", "-"], stdin.as_str())?;
         let mut compilation_result: CompilationResult = serde_json::from_str(&stdout)
             .map_err(|e| {
                 println!(
@@ -368,6 +483,103 @@ impl Compiler {
 
 #[cfg(test)]
 mod test {
+    use crate::config::{CompilerBackend, CompilerConfig};
+
+    fn native() -> super::Compiler {
+        let config = CompilerConfig {
+            backend: CompilerBackend::Native,
+            ..Default::default()
+        };
+        super::Compiler::from_config(true, None, &config).unwrap()
+    }
+
+    #[test]
+    fn test_native_yul() -> anyhow::Result<()> {
+        let out = native().yul("{ sstore(0, add(1, 2)) }", 1, None)?;
+        assert_eq!(hex::encode(out), "600260010160005500");
+        Ok(())
+    }
+
+    #[test]
+    fn test_native_yul_functions() -> anyhow::Result<()> {
+        let out = native().yul(
+            r#"
+{
+    function power(base, exponent) -> result
+    {
+        result := 1
+        for { let i := 0 } lt(i, exponent) { i := add(i, 1) }
+        {
+            result := mul(result, base)
+        }
+    }
+    function pick(x) -> y {
+        switch x
+        case 0 { y := 10 }
+        case 1 { y := 20 leave }
+        default { y := 30 }
+        y := add(y, 1)
+    }
+    mstore(0, power(3, 4))
+    mstore(32, add(add(pick(0), pick(1)), pick(5)))
+    return(0, 64)
+}
+            "#,
+            1,
+            None,
+        )?;
+        let ctx = mock::TestContext::<2, 1>::simple_ctx_with_bytecode(out.to_vec().into())?;
+        assert_eq!(
+            ctx.geth_traces[0].return_value,
+            format!("{:064x}{:064x}", 81, 11 + 20 + 31)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_native_yul_object() -> anyhow::Result<()> {
+        let compiler = native();
+        let out = compiler.yul(
+            r#"
+object "C" {
+    code {
+        datacopy(0, dataoffset("runtime"), datasize("runtime"))
+        return(0, datasize("runtime"))
+    }
+    object "runtime" {
+        code { sstore(0, 1) }
+    }
+}
+            "#,
+            1,
+            None,
+        )?;
+        let runtime = compiler.yul("{ sstore(0, 1) }", 1, None)?;
+        assert!(out.ends_with(&runtime));
+        Ok(())
+    }
+
+    #[test]
+    fn test_native_yul_not_cached() -> anyhow::Result<()> {
+        let path =
+            std::env::temp_dir().join(format!("testool-codehash-{}.txt", std::process::id()));
+        let config = CompilerConfig {
+            backend: CompilerBackend::Native,
+            ..Default::default()
+        };
+        let compiler = super::Compiler::from_config(true, Some(path.clone()), &config)?;
+        compiler.yul("{ sstore(0, 1) }", 1, None)?;
+        let persisted = std::fs::read_to_string(&path).unwrap_or_default();
+        let _ = std::fs::remove_file(&path);
+        assert!(persisted.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_native_lll() {
+        assert!(native().lll("[[0]] 1").is_err());
+    }
+
     #[test]
     #[cfg(not(feature = "ignore-test-docker"))]
     fn test_docker_lll() -> anyhow::Result<()> {
@@ -0,0 +1,1044 @@
+//! In-process compiler for the Yul sources of the test fillers.
+//!
+//! This is a straightforward, non optimizing code generator for the EVM dialect of Yul: every
+//! variable lives in a stack slot, functions are called through a return label pushed below
+//! their arguments, and all the jump targets are pushed with `PUSH2`. The bytecode differs from
+//! the one of `solc --strict-assembly`: besides the size and hash of the code, it uses more gas,
+//! which changes the outcome of the tests that depend on the gas used or on the balances it is
+//! paid from. The results of runs with this backend are tagged as `native` in the report.
+
+use anyhow::{bail, Context, Result};
+use eth_types::{evm_types::OpcodeId, U256};
+use std::collections::HashMap;
+
+/// Compile a Yul block or object.
+pub fn compile(src: &str) -> Result<Vec<u8>> {
+    let mut parser = Parser::new(src)?;
+    let object = if parser.peek_ident("object") {
+        parser.object()?
+    } else {
+        Object {
+            name: String::new(),
+            code: parser.block()?,
+            subs: vec![],
+        }
+    };
+    if let Some(token) = parser.tokens.get(parser.pos) {
+        bail!("unexpected {token:?} after the end of the yul code");
+    }
+    assemble(&object)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    LBrace,
+    RBrace,
+    LParen,
+    RParen,
+    Comma,
+    Colon,
+    Assign,
+    Arrow,
+    Ident(String),
+    Number(U256),
+    Str(Vec<u8>),
+}
+
+fn tokenize(src: &str) -> Result<Vec<Token>> {
+    let chars = src.chars().collect::<Vec<_>>();
+    let mut tokens = vec![];
+    let mut pos = 0;
+    while let Some(&c) = chars.get(pos) {
+        let next = chars.get(pos + 1).copied();
+        pos += 1;
+        let token = match (c, next) {
+            (c, _) if c.is_whitespace() => continue,
+            ('/', Some('/')) => {
+                while chars.get(pos).is_some_and(|c| *c != '\n') {
+                    pos += 1;
+                }
+                continue;
+            }
+            ('/', Some('*')) => {
+                let end = (pos + 1..chars.len().saturating_sub(1))
+                    .find(|&i| chars[i] == '*' && chars[i + 1] == '/')
+                    .context("unterminated comment")?;
+                pos = end + 2;
+                continue;
+            }
+            ('{', _) => Token::LBrace,
+            ('}', _) => Token::RBrace,
+            ('(', _) => Token::LParen,
+            (')', _) => Token::RParen,
+            (',', _) => Token::Comma,
+            (':', Some('=')) => {
+                pos += 1;
+                Token::Assign
+            }
+            (':', _) => Token::Colon,
+            ('-', Some('>')) => {
+                pos += 1;
+                Token::Arrow
+            }
+            ('"' | '\'', _) => {
+                let (bytes, end) = string_literal(&chars, pos, c)?;
+                pos = end;
+                Token::Str(bytes)
+            }
+            (c, _) if c.is_ascii_digit() => {
+                let start = pos - 1;
+                while chars.get(pos).is_some_and(|c| c.is_ascii_alphanumeric()) {
+                    pos += 1;
+                }
+                let literal = chars[start..pos].iter().collect::<String>();
+                let number = match literal.strip_prefix("0x") {
+                    Some(hex) => U256::from_str_radix(hex, 16).ok(),
+                    None => U256::from_dec_str(&literal).ok(),
+                };
+                Token::Number(number.with_context(|| format!("bad number {literal}"))?)
+            }
+            (c, _) if c.is_ascii_alphabetic() || c == '_' || c == '$' => {
+                let start = pos - 1;
+                while chars
+                    .get(pos)
+                    .is_some_and(|c| c.is_ascii_alphanumeric() || "_$.".contains(*c))
+                {
+                    pos += 1;
+                }
+                let ident = chars[start..pos].iter().collect::<String>();
+                match chars.get(pos) {
+                    Some(quote @ ('"' | '\'')) if ident == "hex" => {
+                        let (digits, end) = string_literal(&chars, pos + 1, *quote)?;
+                        pos = end;
+                        let digits = String::from_utf8(digits)?.replace('_', "");
+                        Token::Str(hex::decode(digits)?)
+                    }
+                    _ => Token::Ident(ident),
+                }
+            }
+            (c, _) => bail!("unexpected character '{c}' in yul code"),
+        };
+        tokens.push(token);
+    }
+    Ok(tokens)
+}
+
+/// Parse the string literal starting at `pos`, right after its opening `quote`.
+fn string_literal(chars: &[char], mut pos: usize, quote: char) -> Result<(Vec<u8>, usize)> {
+    let mut bytes = vec![];
+    loop {
+        let c = *chars.get(pos).context("unterminated string")?;
+        pos += 1;
+        if c == quote {
+            return Ok((bytes, pos));
+        }
+        if c != '\\' {
+            let mut buf = [0; 4];
+            bytes.extend_from_slice(c.encode_utf8(&mut buf).as_bytes());
+            continue;
+        }
+        let escaped = *chars.get(pos).context("unterminated string")?;
+        pos += 1;
+        match escaped {
+            'n' => bytes.push(b'\n'),
+            'r' => bytes.push(b'\r'),
+            't' => bytes.push(b'\t'),
+            'x' => {
+                let digits = chars
+                    .get(pos..pos + 2)
+                    .context("bad escape")?
+                    .iter()
+                    .collect::<String>();
+                bytes.push(u8::from_str_radix(&digits, 16)?);
+                pos += 2;
+            }
+            c => bytes.push(c as u8),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Expr {
+    Number(U256),
+    Str(Vec<u8>),
+    Ident(String),
+    Call(String, Vec<Expr>),
+}
+
+#[derive(Debug, Clone)]
+enum Stmt {
+    Block(Vec<Stmt>),
+    Function {
+        name: String,
+        params: Vec<String>,
+        rets: Vec<String>,
+        body: Vec<Stmt>,
+    },
+    Let(Vec<String>, Option<Expr>),
+    Assign(Vec<String>, Expr),
+    If(Expr, Vec<Stmt>),
+    Switch(Expr, Vec<(Option<Expr>, Vec<Stmt>)>),
+    For {
+        init: Vec<Stmt>,
+        cond: Expr,
+        post: Vec<Stmt>,
+        body: Vec<Stmt>,
+    },
+    Break,
+    Continue,
+    Leave,
+    Expr(Expr),
+}
+
+#[derive(Debug)]
+struct Object {
+    name: String,
+    code: Vec<Stmt>,
+    subs: Vec<Sub>,
+}
+
+/// Sub-object or data appended to the code of an object.
+#[derive(Debug)]
+enum Sub {
+    Object(Object),
+    Data(String, Vec<u8>),
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn new(src: &str) -> Result<Self> {
+        Ok(Self {
+            tokens: tokenize(src)?,
+            pos: 0,
+        })
+    }
+
+    fn next(&mut self) -> Result<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token.context("unexpected end of yul code")
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn peek_ident(&self, keyword: &str) -> bool {
+        matches!(self.peek(), Some(Token::Ident(ident)) if ident == keyword)
+    }
+
+    fn eat(&mut self, token: Token) -> bool {
+        let found = self.peek() == Some(&token);
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+
+    fn expect(&mut self, token: Token) -> Result<()> {
+        let found = self.next()?;
+        if found != token {
+            bail!("expected {token:?}, found {found:?}");
+        }
+        Ok(())
+    }
+
+    fn ident(&mut self) -> Result<String> {
+        match self.next()? {
+            Token::Ident(ident) => {
+                // type annotations are ignored, everything is u256
+                if self.eat(Token::Colon) {
+                    self.ident()?;
+                }
+                Ok(ident)
+            }
+            token => bail!("expected an identifier, found {token:?}"),
+        }
+    }
+
+    fn string(&mut self) -> Result<String> {
+        match self.next()? {
+            Token::Str(bytes) => Ok(String::from_utf8(bytes)?),
+            token => bail!("expected a string, found {token:?}"),
+        }
+    }
+
+    fn idents(&mut self) -> Result<Vec<String>> {
+        let mut idents = vec![self.ident()?];
+        while self.eat(Token::Comma) {
+            idents.push(self.ident()?);
+        }
+        Ok(idents)
+    }
+
+    fn object(&mut self) -> Result<Object> {
+        self.ident()?;
+        let name = self.string()?;
+        self.expect(Token::LBrace)?;
+        if self.ident()? != "code" {
+            bail!("expected the code of object {name}");
+        }
+        let code = self.block()?;
+        let mut subs = vec![];
+        while !self.eat(Token::RBrace) {
+            match self.ident()?.as_str() {
+                "object" => {
+                    self.pos -= 1;
+                    subs.push(Sub::Object(self.object()?));
+                }
+                "data" => {
+                    let name = self.string()?;
+                    let data = match self.next()? {
+                        Token::Str(data) => data,
+                        token => bail!("expected data, found {token:?}"),
+                    };
+                    subs.push(Sub::Data(name, data));
+                }
+                // metadata is not part of the bytecode
+                "metadata" => {}
+                ident => bail!("unexpected {ident} in object {name}"),
+            }
+        }
+        Ok(Object { name, code, subs })
+    }
+
+    fn block(&mut self) -> Result<Vec<Stmt>> {
+        self.expect(Token::LBrace)?;
+        let mut stmts = vec![];
+        while !self.eat(Token::RBrace) {
+            stmts.push(self.stmt()?);
+        }
+        Ok(stmts)
+    }
+
+    fn stmt(&mut self) -> Result<Stmt> {
+        if self.peek() == Some(&Token::LBrace) {
+            return Ok(Stmt::Block(self.block()?));
+        }
+        let Some(Token::Ident(keyword)) = self.peek().cloned() else {
+            bail!("unexpected {:?}", self.peek());
+        };
+        let stmt = match keyword.as_str() {
+            "function" => {
+                self.pos += 1;
+                let name = self.ident()?;
+                self.expect(Token::LParen)?;
+                let params = if self.eat(Token::RParen) {
+                    vec![]
+                } else {
+                    let params = self.idents()?;
+                    self.expect(Token::RParen)?;
+                    params
+                };
+                let rets = if self.eat(Token::Arrow) {
+                    self.idents()?
+                } else {
+                    vec![]
+                };
+                let body = self.block()?;
+                Stmt::Function {
+                    name,
+                    params,
+                    rets,
+                    body,
+                }
+            }
+            "let" => {
+                self.pos += 1;
+                let names = self.idents()?;
+                let value = if self.eat(Token::Assign) {
+                    Some(self.expr()?)
+                } else {
+                    None
+                };
+                Stmt::Let(names, value)
+            }
+            "if" => {
+                self.pos += 1;
+                Stmt::If(self.expr()?, self.block()?)
+            }
+            "switch" => {
+                self.pos += 1;
+                let value = self.expr()?;
+                let mut cases = vec![];
+                loop {
+                    if self.peek_ident("case") {
+                        self.pos += 1;
+                        let literal = self.expr()?;
+                        cases.push((Some(literal), self.block()?));
+                    } else if self.peek_ident("default") {
+                        self.pos += 1;
+                        cases.push((None, self.block()?));
+                    } else {
+                        break;
+                    }
+                }
+                Stmt::Switch(value, cases)
+            }
+            "for" => {
+                self.pos += 1;
+                Stmt::For {
+                    init: self.block()?,
+                    cond: self.expr()?,
+                    post: self.block()?,
+                    body: self.block()?,
+                }
+            }
+            "break" | "continue" | "leave" => {
+                self.pos += 1;
+                match keyword.as_str() {
+                    "break" => Stmt::Break,
+                    "continue" => Stmt::Continue,
+                    _ => Stmt::Leave,
+                }
+            }
+            _ => {
+                let expr = self.expr()?;
+                match (expr, self.peek()) {
+                    (Expr::Ident(name), Some(Token::Comma | Token::Assign)) => {
+                        let mut names = vec![name];
+                        while self.eat(Token::Comma) {
+                            names.push(self.ident()?);
+                        }
+                        self.expect(Token::Assign)?;
+                        Stmt::Assign(names, self.expr()?)
+                    }
+                    (expr, _) => Stmt::Expr(expr),
+                }
+            }
+        };
+        Ok(stmt)
+    }
+
+    fn expr(&mut self) -> Result<Expr> {
+        let expr = match self.next()? {
+            Token::Number(number) => Expr::Number(number),
+            Token::Str(bytes) => Expr::Str(bytes),
+            Token::Ident(ident) if ident == "true" => Expr::Number(U256::one()),
+            Token::Ident(ident) if ident == "false" => Expr::Number(U256::zero()),
+            Token::Ident(ident) => {
+                if self.eat(Token::LParen) {
+                    let mut args = vec![];
+                    if !self.eat(Token::RParen) {
+                        args.push(self.expr()?);
+                        while self.eat(Token::Comma) {
+                            args.push(self.expr()?);
+                        }
+                        self.expect(Token::RParen)?;
+                    }
+                    return Ok(Expr::Call(ident, args));
+                }
+                Expr::Ident(ident)
+            }
+            token => bail!("expected an expression, found {token:?}"),
+        };
+        // type annotations are ignored, everything is u256
+        if matches!(expr, Expr::Number(_) | Expr::Str(_)) && self.eat(Token::Colon) {
+            self.ident()?;
+        }
+        Ok(expr)
+    }
+}
+
+/// Opcodes callable from Yul, with their number of arguments and of returned values.
+const BUILTINS: &[(&str, OpcodeId, usize, usize)] = &[
+    ("stop", OpcodeId::STOP, 0, 0),
+    ("add", OpcodeId::ADD, 2, 1),
+    ("sub", OpcodeId::SUB, 2, 1),
+    ("mul", OpcodeId::MUL, 2, 1),
+    ("div", OpcodeId::DIV, 2, 1),
+    ("sdiv", OpcodeId::SDIV, 2, 1),
+    ("mod", OpcodeId::MOD, 2, 1),
+    ("smod", OpcodeId::SMOD, 2, 1),
+    ("exp", OpcodeId::EXP, 2, 1),
+    ("not", OpcodeId::NOT, 1, 1),
+    ("lt", OpcodeId::LT, 2, 1),
+    ("gt", OpcodeId::GT, 2, 1),
+    ("slt", OpcodeId::SLT, 2, 1),
+    ("sgt", OpcodeId::SGT, 2, 1),
+    ("eq", OpcodeId::EQ, 2, 1),
+    ("iszero", OpcodeId::ISZERO, 1, 1),
+    ("and", OpcodeId::AND, 2, 1),
+    ("or", OpcodeId::OR, 2, 1),
+    ("xor", OpcodeId::XOR, 2, 1),
+    ("byte", OpcodeId::BYTE, 2, 1),
+    ("shl", OpcodeId::SHL, 2, 1),
+    ("shr", OpcodeId::SHR, 2, 1),
+    ("sar", OpcodeId::SAR, 2, 1),
+    ("addmod", OpcodeId::ADDMOD, 3, 1),
+    ("mulmod", OpcodeId::MULMOD, 3, 1),
+    ("signextend", OpcodeId::SIGNEXTEND, 2, 1),
+    ("keccak256", OpcodeId::SHA3, 2, 1),
+    ("pc", OpcodeId::PC, 0, 1),
+    ("pop", OpcodeId::POP, 1, 0),
+    ("mload", OpcodeId::MLOAD, 1, 1),
+    ("mstore", OpcodeId::MSTORE, 2, 0),
+    ("mstore8", OpcodeId::MSTORE8, 2, 0),
+    ("sload", OpcodeId::SLOAD, 1, 1),
+    ("sstore", OpcodeId::SSTORE, 2, 0),
+    ("tload", OpcodeId::TLOAD, 1, 1),
+    ("tstore", OpcodeId::TSTORE, 2, 0),
+    ("msize", OpcodeId::MSIZE, 0, 1),
+    ("gas", OpcodeId::GAS, 0, 1),
+    ("address", OpcodeId::ADDRESS, 0, 1),
+    ("balance", OpcodeId::BALANCE, 1, 1),
+    ("selfbalance", OpcodeId::SELFBALANCE, 0, 1),
+    ("caller", OpcodeId::CALLER, 0, 1),
+    ("callvalue", OpcodeId::CALLVALUE, 0, 1),
+    ("calldataload", OpcodeId::CALLDATALOAD, 1, 1),
+    ("calldatasize", OpcodeId::CALLDATASIZE, 0, 1),
+    ("calldatacopy", OpcodeId::CALLDATACOPY, 3, 0),
+    ("codesize", OpcodeId::CODESIZE, 0, 1),
+    ("codecopy", OpcodeId::CODECOPY, 3, 0),
+    ("datacopy", OpcodeId::CODECOPY, 3, 0),
+    ("extcodesize", OpcodeId::EXTCODESIZE, 1, 1),
+    ("extcodecopy", OpcodeId::EXTCODECOPY, 4, 0),
+    ("extcodehash", OpcodeId::EXTCODEHASH, 1, 1),
+    ("returndatasize", OpcodeId::RETURNDATASIZE, 0, 1),
+    ("returndatacopy", OpcodeId::RETURNDATACOPY, 3, 0),
+    ("mcopy", OpcodeId::MCOPY, 3, 0),
+    ("create", OpcodeId::CREATE, 3, 1),
+    ("create2", OpcodeId::CREATE2, 4, 1),
+    ("call", OpcodeId::CALL, 7, 1),
+    ("callcode", OpcodeId::CALLCODE, 7, 1),
+    ("delegatecall", OpcodeId::DELEGATECALL, 6, 1),
+    ("staticcall", OpcodeId::STATICCALL, 6, 1),
+    ("return", OpcodeId::RETURN, 2, 0),
+    ("revert", OpcodeId::REVERT, 2, 0),
+    ("selfdestruct", OpcodeId::SELFDESTRUCT, 1, 0),
+    ("invalid", OpcodeId::INVALID(0xfe), 0, 0),
+    ("log0", OpcodeId::LOG0, 2, 0),
+    ("log1", OpcodeId::LOG1, 3, 0),
+    ("log2", OpcodeId::LOG2, 4, 0),
+    ("log3", OpcodeId::LOG3, 5, 0),
+    ("log4", OpcodeId::LOG4, 6, 0),
+    ("chainid", OpcodeId::CHAINID, 0, 1),
+    ("basefee", OpcodeId::BASEFEE, 0, 1),
+    ("origin", OpcodeId::ORIGIN, 0, 1),
+    ("gasprice", OpcodeId::GASPRICE, 0, 1),
+    ("blockhash", OpcodeId::BLOCKHASH, 1, 1),
+    ("coinbase", OpcodeId::COINBASE, 0, 1),
+    ("timestamp", OpcodeId::TIMESTAMP, 0, 1),
+    ("number", OpcodeId::NUMBER, 0, 1),
+    ("difficulty", OpcodeId::DIFFICULTY, 0, 1),
+    ("prevrandao", OpcodeId::DIFFICULTY, 0, 1),
+    ("gaslimit", OpcodeId::GASLIMIT, 0, 1),
+];
+
+/// Assembly item, all the jump targets and data offsets are pushed with `PUSH2`.
+#[derive(Debug)]
+enum Item {
+    Op(OpcodeId),
+    Push(U256),
+    PushLabel(usize),
+    Label(usize),
+    PushDataOffset(String),
+    PushDataSize(String),
+}
+
+impl Item {
+    fn size(&self) -> usize {
+        match self {
+            Item::Op(_) | Item::Label(_) => 1,
+            Item::Push(value) => 1 + push_len(value),
+            Item::PushLabel(_) | Item::PushDataOffset(_) | Item::PushDataSize(_) => 3,
+        }
+    }
+}
+
+fn push_len(value: &U256) -> usize {
+    ((value.bits() + 7) / 8).max(1)
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Function {
+    label: usize,
+    params: usize,
+    rets: usize,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Loop {
+    height: usize,
+    post: usize,
+    end: usize,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Frame {
+    height: usize,
+    exit: usize,
+}
+
+#[derive(Default)]
+struct Codegen {
+    items: Vec<Item>,
+    labels: usize,
+    /// Simulated stack height, relative to the current function frame.
+    height: usize,
+    /// Stack slots of the variables in scope.
+    vars: Vec<Vec<(String, usize)>>,
+    functions: Vec<HashMap<String, Function>>,
+    loops: Vec<Loop>,
+    frame: Option<Frame>,
+}
+
+impl Codegen {
+    fn label(&mut self) -> usize {
+        self.labels += 1;
+        self.labels - 1
+    }
+
+    fn op(&mut self, op: OpcodeId) {
+        self.items.push(Item::Op(op));
+    }
+
+    fn push(&mut self, value: U256) {
+        self.items.push(Item::Push(value));
+        self.height += 1;
+    }
+
+    fn jump(&mut self, label: usize) {
+        self.items.push(Item::PushLabel(label));
+        self.op(OpcodeId::JUMP);
+    }
+
+    fn pop(&mut self, n: usize) {
+        for _ in 0..n {
+            self.op(OpcodeId::POP);
+        }
+        self.height -= n;
+    }
+
+    fn dup(&mut self, n: usize) -> Result<()> {
+        if !(1..=16).contains(&n) {
+            bail!("stack too deep");
+        }
+        self.op(OpcodeId::from(OpcodeId::DUP1.as_u8() + n as u8 - 1));
+        self.height += 1;
+        Ok(())
+    }
+
+    fn swap(&mut self, n: usize) -> Result<()> {
+        if !(1..=16).contains(&n) {
+            bail!("stack too deep");
+        }
+        self.op(OpcodeId::from(OpcodeId::SWAP1.as_u8() + n as u8 - 1));
+        Ok(())
+    }
+
+    fn var(&self, name: &str) -> Result<usize> {
+        self.vars
+            .iter()
+            .rev()
+            .flatten()
+            .find(|(var, _)| var == name)
+            .map(|(_, slot)| *slot)
+            .with_context(|| format!("undeclared variable {name}"))
+    }
+
+    fn function(&self, name: &str) -> Option<Function> {
+        self.functions
+            .iter()
+            .rev()
+            .find_map(|functions| functions.get(name))
+            .copied()
+    }
+
+    fn declare(&mut self, names: &[String]) {
+        let first = self.height - names.len();
+        let scope = self.vars.last_mut().expect("in a block");
+        scope.extend(names.iter().cloned().zip(first..));
+    }
+
+    /// Open the scope of a block, where its functions are visible from the start.
+    fn open_scope(&mut self, stmts: &[Stmt]) {
+        let mut functions = HashMap::new();
+        for stmt in stmts {
+            if let Stmt::Function {
+                name, params, rets, ..
+            } = stmt
+            {
+                let function = Function {
+                    label: self.label(),
+                    params: params.len(),
+                    rets: rets.len(),
+                };
+                functions.insert(name.clone(), function);
+            }
+        }
+        self.functions.push(functions);
+        self.vars.push(vec![]);
+    }
+
+    fn close_scope(&mut self) {
+        self.functions.pop();
+        let vars = self.vars.pop().expect("in a block").len();
+        self.pop(vars);
+    }
+
+    fn block(&mut self, stmts: &[Stmt]) -> Result<()> {
+        self.open_scope(stmts);
+        for stmt in stmts {
+            self.stmt(stmt)?;
+        }
+        self.close_scope();
+        Ok(())
+    }
+
+    fn stmt(&mut self, stmt: &Stmt) -> Result<()> {
+        match stmt {
+            Stmt::Block(stmts) => self.block(stmts)?,
+            Stmt::Function {
+                name,
+                params,
+                rets,
+                body,
+            } => {
+                let function = self.function(name).expect("registered by its block");
+                let skip = self.label();
+                self.jump(skip);
+                self.function_body(function, params, rets, body)?;
+                self.items.push(Item::Label(skip));
+            }
+            Stmt::Let(names, value) => {
+                match value {
+                    Some(value) => self.expr_values(value, names.len())?,
+                    None => names.iter().for_each(|_| self.push(U256::zero())),
+                }
+                self.declare(names);
+            }
+            Stmt::Assign(names, value) => {
+                self.expr_values(value, names.len())?;
+                for name in names.iter().rev() {
+                    let slot = self.var(name)?;
+                    self.swap(self.height - 1 - slot)?;
+                    self.pop(1);
+                }
+            }
+            Stmt::If(cond, body) => {
+                let end = self.label();
+                self.expr(cond)?;
+                self.op(OpcodeId::ISZERO);
+                self.items.push(Item::PushLabel(end));
+                self.op(OpcodeId::JUMPI);
+                self.height -= 1;
+                self.block(body)?;
+                self.items.push(Item::Label(end));
+            }
+            Stmt::Switch(value, cases) => {
+                self.expr(value)?;
+                let labels = cases.iter().map(|_| self.label()).collect::<Vec<_>>();
+                let explicit_default = cases
+                    .iter()
+                    .zip(&labels)
+                    .find_map(|((case, _), label)| case.is_none().then_some(*label));
+                let default = explicit_default.unwrap_or_else(|| self.label());
+                let end = self.label();
+                for ((case, _), label) in cases.iter().zip(&labels) {
+                    if let Some(case) = case {
+                        self.dup(1)?;
+                        self.expr(case)?;
+                        self.op(OpcodeId::EQ);
+                        self.items.push(Item::PushLabel(*label));
+                        self.op(OpcodeId::JUMPI);
+                        self.height -= 2;
+                    }
+                }
+                self.jump(default);
+                // every case starts by popping the switch value
+                for ((_, body), label) in cases.iter().zip(labels) {
+                    self.items.push(Item::Label(label));
+                    self.pop(1);
+                    self.block(body)?;
+                    self.jump(end);
+                    self.height += 1;
+                }
+                if explicit_default.is_none() {
+                    self.items.push(Item::Label(default));
+                    self.pop(1);
+                } else {
+                    self.height -= 1;
+                }
+                self.items.push(Item::Label(end));
+            }
+            Stmt::For {
+                init,
+                cond,
+                post,
+                body,
+            } => {
+                self.open_scope(init);
+                for stmt in init {
+                    self.stmt(stmt)?;
+                }
+                let (start, post_label, end) = (self.label(), self.label(), self.label());
+                self.items.push(Item::Label(start));
+                self.expr(cond)?;
+                self.op(OpcodeId::ISZERO);
+                self.items.push(Item::PushLabel(end));
+                self.op(OpcodeId::JUMPI);
+                self.height -= 1;
+                self.loops.push(Loop {
+                    height: self.height,
+                    post: post_label,
+                    end,
+                });
+                self.block(body)?;
+                self.loops.pop();
+                self.items.push(Item::Label(post_label));
+                self.block(post)?;
+                self.jump(start);
+                self.items.push(Item::Label(end));
+                self.close_scope();
+            }
+            Stmt::Break | Stmt::Continue => {
+                let lp = *self
+                    .loops
+                    .last()
+                    .context("break or continue outside of a loop")?;
+                let target = if matches!(stmt, Stmt::Break) {
+                    lp.end
+                } else {
+                    lp.post
+                };
+                self.unwind(lp.height, target);
+            }
+            Stmt::Leave => {
+                let frame = self.frame.context("leave outside of a function")?;
+                self.unwind(frame.height, frame.exit);
+            }
+            Stmt::Expr(expr) => {
+                if self.expr_count(expr)? != 0 {
+                    bail!("value of {expr:?} is discarded");
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Jump to `target` with the stack popped down to `height`, leaving the simulated height
+    /// unchanged for the unreachable code which follows.
+    fn unwind(&mut self, height: usize, target: usize) {
+        for _ in height..self.height {
+            self.op(OpcodeId::POP);
+        }
+        self.jump(target);
+    }
+
+    /// Emit the function, which is entered with its return label below the arguments, the
+    /// first one on top, and exits with its return values, the last one on top.
+    fn function_body(
+        &mut self,
+        function: Function,
+        params: &[String],
+        rets: &[String],
+        body: &[Stmt],
+    ) -> Result<()> {
+        let outer_height = std::mem::replace(&mut self.height, 1 + params.len());
+        let outer_vars = std::mem::replace(
+            &mut self.vars,
+            vec![params.iter().cloned().rev().zip(1..).collect()],
+        );
+        let outer_loops = std::mem::take(&mut self.loops);
+        let exit = self.label();
+        let outer_frame = self.frame.replace(Frame {
+            height: 1 + params.len() + rets.len(),
+            exit,
+        });
+
+        self.items.push(Item::Label(function.label));
+        rets.iter().for_each(|_| self.push(U256::zero()));
+        self.declare(rets);
+        self.block(body)?;
+        self.items.push(Item::Label(exit));
+
+        // [ret label, params.., rets..] -> [rets.., ret label]
+        let mut stack = (0..self.height).collect::<Vec<_>>();
+        let target = (1 + params.len()..self.height)
+            .chain([0])
+            .collect::<Vec<_>>();
+        for (pos, slot) in target.iter().enumerate() {
+            let top = stack.len() - 1;
+            let from = stack.iter().position(|s| s == slot).expect("in the stack");
+            if from == pos {
+                continue;
+            }
+            if from != top {
+                self.swap(top - from)?;
+                stack.swap(top, from);
+            }
+            if pos != top {
+                self.swap(top - pos)?;
+                stack.swap(top, pos);
+            }
+        }
+        self.pop(stack.len() - target.len());
+        self.op(OpcodeId::JUMP);
+
+        self.height = outer_height;
+        self.vars = outer_vars;
+        self.loops = outer_loops;
+        self.frame = outer_frame;
+        Ok(())
+    }
+
+    fn expr(&mut self, expr: &Expr) -> Result<()> {
+        self.expr_values(expr, 1)
+    }
+
+    fn expr_values(&mut self, expr: &Expr, count: usize) -> Result<()> {
+        let values = self.expr_count(expr)?;
+        if values != count {
+            bail!("{expr:?} returns {values} values instead of {count}");
+        }
+        Ok(())
+    }
+
+    /// Emit the expression, returning the number of values it pushed.
+    fn expr_count(&mut self, expr: &Expr) -> Result<usize> {
+        match expr {
+            Expr::Number(value) => self.push(*value),
+            Expr::Str(bytes) => {
+                if bytes.len() > 32 {
+                    bail!("string literal longer than 32 bytes");
+                }
+                let mut word = [0; 32];
+                word[..bytes.len()].copy_from_slice(bytes);
+                self.push(U256::from_big_endian(&word));
+            }
+            Expr::Ident(name) => {
+                let slot = self.var(name)?;
+                self.dup(self.height - slot)?;
+            }
+            Expr::Call(name, args) => return self.call(name, args),
+        }
+        Ok(1)
+    }
+
+    fn call(&mut self, name: &str, args: &[Expr]) -> Result<usize> {
+        let check_args = |expected: usize| {
+            if args.len() != expected {
+                bail!("{name} expects {expected} arguments, got {}", args.len());
+            }
+            Ok(())
+        };
+        if let Some(function) = self.function(name) {
+            check_args(function.params)?;
+            let ret = self.label();
+            self.items.push(Item::PushLabel(ret));
+            self.height += 1;
+            for arg in args.iter().rev() {
+                self.expr(arg)?;
+            }
+            self.jump(function.label);
+            self.items.push(Item::Label(ret));
+            self.height = self.height - 1 - args.len() + function.rets;
+            return Ok(function.rets);
+        }
+        match name {
+            "dataoffset" | "datasize" => {
+                check_args(1)?;
+                let Expr::Str(data) = &args[0] else {
+                    bail!("{name} expects a string literal");
+                };
+                let data = String::from_utf8(data.clone())?;
+                self.items.push(if name == "dataoffset" {
+                    Item::PushDataOffset(data)
+                } else {
+                    Item::PushDataSize(data)
+                });
+                self.height += 1;
+                return Ok(1);
+            }
+            "memoryguard" => {
+                check_args(1)?;
+                self.expr(&args[0])?;
+                return Ok(1);
+            }
+            _ => {}
+        }
+        let (_, op, inputs, outputs) = BUILTINS
+            .iter()
+            .find(|(builtin, ..)| *builtin == name)
+            .with_context(|| format!("unknown function {name}"))?;
+        check_args(*inputs)?;
+        for arg in args.iter().rev() {
+            self.expr(arg)?;
+        }
+        self.op(*op);
+        self.height = self.height - inputs + outputs;
+        Ok(*outputs)
+    }
+}
+
+/// Assemble an object, its code being followed by its sub-objects and data.
+fn assemble(object: &Object) -> Result<Vec<u8>> {
+    let mut codegen = Codegen::default();
+    codegen.block(&object.code)?;
+    codegen.op(OpcodeId::STOP);
+
+    let subs = object
+        .subs
+        .iter()
+        .map(|sub| match sub {
+            Sub::Object(sub) => Ok((sub.name.as_str(), assemble(sub)?)),
+            Sub::Data(name, data) => Ok((name.as_str(), data.clone())),
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut labels = vec![0; codegen.labels];
+    let mut code_size = 0;
+    for item in &codegen.items {
+        if let Item::Label(label) = item {
+            labels[*label] = code_size;
+        }
+        code_size += item.size();
+    }
+    let total_size = code_size + subs.iter().map(|(_, data)| data.len()).sum::<usize>();
+    let data_range = |name: &str| {
+        if name == object.name {
+            return Ok((0, total_size));
+        }
+        let mut offset = code_size;
+        for (sub, data) in &subs {
+            if *sub == name {
+                return Ok((offset, data.len()));
+            }
+            offset += data.len();
+        }
+        bail!("unknown object or data {name}")
+    };
+
+    let mut code = Vec::with_capacity(total_size);
+    let push2 = |code: &mut Vec<u8>, value: usize| -> Result<()> {
+        let value = u16::try_from(value).context("code too large")?;
+        code.push(OpcodeId::PUSH2.as_u8());
+        code.extend_from_slice(&value.to_be_bytes());
+        Ok(())
+    };
+    for item in &codegen.items {
+        match item {
+            Item::Op(op) => code.push(op.as_u8()),
+            Item::Label(_) => code.push(OpcodeId::JUMPDEST.as_u8()),
+            Item::Push(value) => {
+                let len = push_len(value);
+                let mut word = [0; 32];
+                value.to_big_endian(&mut word);
+                code.push(OpcodeId::PUSH1.as_u8() + len as u8 - 1);
+                code.extend_from_slice(&word[32 - len..]);
+            }
+            Item::PushLabel(label) => push2(&mut code, labels[*label])?,
+            Item::PushDataOffset(name) => push2(&mut code, data_range(name)?.0)?,
+            Item::PushDataSize(name) => push2(&mut code, data_range(name)?.1)?,
+        }
+    }
+    for (_, data) in subs {
+        code.extend(data);
+    }
+    Ok(code)
+}
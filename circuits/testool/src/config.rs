@@ -1,5 +1,6 @@
 use anyhow::{anyhow, Context, Result};
 use serde::Deserialize;
+use std::path::PathBuf;
 
 const CONFIG_FILE: &str = "Config.toml";
 
@@ -11,6 +12,35 @@ pub struct Config {
     pub skip_paths: Vec<SkipPaths>,
     #[serde(default)]
     pub skip_tests: Vec<SkipTests>,
+    #[serde(default)]
+    pub compiler: CompilerConfig,
+}
+
+/// How the sources of the tests are compiled
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CompilerBackend {
+    /// `solc` and `lllc` docker images, built from `docker/`
+    #[default]
+    Docker,
+    /// `solc` and `lllc` binaries of the host
+    Local,
+    /// in-process Yul compiler, LLL and Solidity sources can only come from the cache. The code is
+    /// not optimized, so the results are tagged as `native`
+    Native,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct CompilerConfig {
+    #[serde(default)]
+    pub backend: CompilerBackend,
+    /// version of the local `solc`, e.g. "0.8.20"
+    pub solc_version: Option<String>,
+    /// directory of the local `solc` binaries, in the layout of `svm`
+    /// (`<dir>/<version>/solc-<version>`). Defaults to `$SVM_HOME` or `~/.svm`
+    pub solc_dir: Option<PathBuf>,
+    /// pre-built bytecodes, in the format of the codehash cache, seeding the cache
+    pub bundle: Option<PathBuf>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -14,7 +14,8 @@ use compiler::Compiler;
 use config::Config;
 use log::info;
 use statetest::{
-    load_statetests_suite, run_statetests_suite, run_test, CircuitsConfig, Results, StateTest,
+    load_statetests_suite, run_statetests_suite, run_test, CircuitsConfig, PoolConfig, Results,
+    Shard, StateTest,
 };
 use std::{
     collections::{HashMap, HashSet},
@@ -88,6 +89,27 @@ struct Args {
     #[clap(long)]
     exclude_test_ids: Option<String>,
 
+    /// Run only the shard `index/count` of the tests, `index` starting from 0
+    #[clap(long)]
+    shard: Option<Shard>,
+
+    /// Number of tests executed concurrently
+    #[clap(long)]
+    jobs: Option<usize>,
+
+    /// Memory in GiB shared by the tests executed concurrently
+    #[clap(long)]
+    memory: Option<u64>,
+
+    /// Memory in GiB reserved by each test, defaults to an estimation for the circuits
+    #[clap(long)]
+    test_memory: Option<u64>,
+
+    /// Do not execute any test, merge these result files (like the caches of the shards) into
+    /// a report
+    #[clap(long, value_delimiter = ',')]
+    merge: Vec<PathBuf>,
+
     /// Verbose
     #[clap(short, long)]
     v: bool,
@@ -147,6 +169,53 @@ fn run_single_test(
     Ok(())
 }
 
+/// returns the git hash, and the csv and html files of a new report
+fn report_filenames(suite: &str) -> Result<(String, String, String)> {
+    let git_hash = utils::current_git_commit()?;
+    let timestamp = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+
+    std::fs::create_dir_all(REPORT_FOLDER)?;
+    let csv_filename = format!("{REPORT_FOLDER}/{suite}.{timestamp}.{git_hash}.csv");
+    let html_filename = format!("{REPORT_FOLDER}/{suite}.{timestamp}.{git_hash}.html");
+    Ok((git_hash, csv_filename, html_filename))
+}
+
+/// generates the html report of the results, compared with the latest report of another commit
+fn write_report(suite: &str, git_hash: &str, html_filename: &str, results: Results) -> Result<()> {
+    let git_submodule_tests_hash = utils::current_submodule_git_commit()?;
+
+    // filter non-csv files and files from the same commit
+    let mut files: Vec<_> = std::fs::read_dir(REPORT_FOLDER)
+        .unwrap()
+        .filter_map(|f| {
+            let filename = f.unwrap().file_name().to_str().unwrap().to_string();
+            (filename.starts_with(&format!("{suite}."))
+                && filename.ends_with(".csv")
+                && !filename.contains(&format!(".{git_hash}.")))
+            .then_some(filename)
+        })
+        .collect();
+
+    files.sort_by(|f, s| s.cmp(f));
+    let previous = if !files.is_empty() {
+        let file = files.remove(0);
+        let path = format!("{REPORT_FOLDER}/{file}");
+        info!("Comparing with previous results in {path}");
+        Some((file, Results::from_file(PathBuf::from(path))?))
+    } else {
+        None
+    };
+    let report = results.report(previous);
+    std::fs::write(html_filename, report.gen_html(git_submodule_tests_hash)?)?;
+
+    report.print_tty()?;
+    info!("{}", html_filename);
+    Ok(())
+}
+
 fn go() -> Result<()> {
     //  RAYON_NUM_THREADS=1 RUST_BACKTRACE=1 cargo run -- --path
     // "tests/src/GeneralStateTestsFiller/**/" --skip-state-circuit
@@ -166,12 +235,24 @@ fn go() -> Result<()> {
         return Ok(());
     }
 
+    if !args.merge.is_empty() {
+        let mut results = Results::merge(&args.merge)?;
+        info!("merged {} test results", results.tests.len());
+        let (git_hash, csv_filename, html_filename) = report_filenames(&args.suite)?;
+        results.set_cache(PathBuf::from(csv_filename));
+        results.write_cache()?;
+        write_report(&args.suite, &git_hash, &html_filename, results)?;
+        return Ok(());
+    }
+
     let config = Config::load()?;
 
     log::info!("Using suite '{}'", args.suite);
     log::info!("Parsing and compiling tests...");
-    let compiler = Compiler::new(true, Some(PathBuf::from(CODEHASH_FILE)))?;
+    let compiler =
+        Compiler::from_config(true, Some(PathBuf::from(CODEHASH_FILE)), &config.compiler)?;
     let suite = config.suite(&args.suite)?.clone();
+    let compiler_backend = config.compiler.backend;
     let mut state_tests = load_statetests_suite(&suite, config, compiler)?;
     log::info!(
         "{} tests collected in {}",
@@ -239,23 +320,24 @@ fn go() -> Result<()> {
         state_tests.sort_by_key(|t| t.id.chars().rev().collect::<String>());
     }
 
-    if args.report {
-        let git_hash = utils::current_git_commit()?;
-        let git_submodule_tests_hash = utils::current_submodule_git_commit()?;
-        let timestamp = SystemTime::now()
-            .duration_since(SystemTime::UNIX_EPOCH)
-            .unwrap()
-            .as_secs();
-
-        std::fs::create_dir_all(REPORT_FOLDER)?;
-        let csv_filename = format!(
-            "{}/{}.{}.{}.csv",
-            REPORT_FOLDER, args.suite, timestamp, git_hash
-        );
-        let html_filename = format!(
-            "{}/{}.{}.{}.html",
-            REPORT_FOLDER, args.suite, timestamp, git_hash
+    if let Some(shard) = args.shard {
+        state_tests.retain(|t| shard.contains(&t.id));
+        info!(
+            "{} tests in shard {}/{}",
+            state_tests.len(),
+            shard.index,
+            shard.count
         );
+    }
+
+    let pool_config = PoolConfig {
+        jobs: args.jobs,
+        memory: args.memory.map(|gib| gib << 30),
+        test_memory: args.test_memory.map(|gib| gib << 30),
+    };
+
+    if args.report {
+        let (git_hash, csv_filename, html_filename) = report_filenames(&args.suite)?;
 
         let cache_file_name = if !args.use_cache {
             None
@@ -305,34 +387,15 @@ fn go() -> Result<()> {
 
         previous_results.set_cache(PathBuf::from(csv_filename));
         previous_results.write_cache()?;
-        run_statetests_suite(state_tests, &circuits_config, &suite, &mut previous_results)?;
-
-        // filter non-csv files and files from the same commit
-        let mut files: Vec<_> = std::fs::read_dir(REPORT_FOLDER)
-            .unwrap()
-            .filter_map(|f| {
-                let filename = f.unwrap().file_name().to_str().unwrap().to_string();
-                (filename.starts_with(&format!("{}.", args.suite))
-                    && filename.ends_with(".csv")
-                    && !filename.contains(&format!(".{git_hash}.")))
-                .then_some(filename)
-            })
-            .collect();
-
-        files.sort_by(|f, s| s.cmp(f));
-        let previous = if !files.is_empty() {
-            let file = files.remove(0);
-            let path = format!("{REPORT_FOLDER}/{file}");
-            info!("Comparing with previous results in {path}");
-            Some((file, Results::from_file(PathBuf::from(path))?))
-        } else {
-            None
-        };
-        let report = previous_results.report(previous);
-        std::fs::write(&html_filename, report.gen_html(git_submodule_tests_hash)?)?;
-
-        report.print_tty()?;
-        info!("{}", html_filename);
+        run_statetests_suite(
+            state_tests,
+            &circuits_config,
+            &pool_config,
+            &suite,
+            compiler_backend,
+            &mut previous_results,
+        )?;
+        write_report(&args.suite, &git_hash, &html_filename, previous_results)?;
     } else {
         let mut results = if let Some(cache_filename) = args.cache {
             Results::with_cache(cache_filename)?
@@ -341,7 +404,14 @@ fn go() -> Result<()> {
         };
 
         log::info!("Executing...");
-        run_statetests_suite(state_tests, &circuits_config, &suite, &mut results)?;
+        run_statetests_suite(
+            state_tests,
+            &circuits_config,
+            &pool_config,
+            &suite,
+            compiler_backend,
+            &mut results,
+        )?;
         let success = results.success();
 
         log::info!("Generating report...");
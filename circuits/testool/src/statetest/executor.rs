@@ -13,6 +13,7 @@ use itertools::Itertools;
 use std::{collections::BTreeMap, env, str::FromStr, sync::LazyLock};
 use thiserror::Error;
 use zkevm_circuits::{
+    bls12_381_circuit::Bls12381Circuit,
     bytecode_circuit::circuit::BytecodeCircuit,
     ecc_circuit::EccCircuit,
     modexp_circuit::ModExpCircuit,
@@ -198,6 +199,9 @@ fn into_traceconfig(st: StateTest) -> (String, TraceConfig, StateTestResult) {
                 gas_tip_cap: st.max_priority_fee_per_gas,
                 call_data: st.data,
                 access_list: st.access_list,
+                max_fee_per_blob_gas: Default::default(),
+                blob_versioned_hashes: vec![],
+                authorization_list: vec![],
                 v,
                 r: sig.r,
                 s: sig.s,
@@ -323,7 +327,7 @@ fn trace_config_to_witness_block_l2(
         .expect("could not finalize building block");
     let mut block =
         zkevm_circuits::witness::block_convert(&builder.block, &builder.code_db).unwrap();
-    block.apply_mpt_updates(builder.mpt_init_state.as_ref().unwrap());
+    block.apply_mpt_updates(builder.mpt_init_state.as_mut().unwrap());
     // as mentioned above, we cannot fit the trace into circuit
     // stop here
     if exceed_max_steps != 0 {
@@ -458,6 +462,7 @@ fn get_params_for_super_circuit_test() -> CircuitsParams {
             ec_add: 50,
             ec_mul: 50,
             ec_pairing: 2,
+            bls12_g1_add: 10,
         },
     }
 }
@@ -482,6 +487,7 @@ fn get_params_for_sub_circuit_test() -> CircuitsParams {
             ec_add: 50,
             ec_mul: 50,
             ec_pairing: 2,
+            bls12_g1_add: 10,
         },
     }
 }
@@ -615,6 +621,7 @@ pub fn run_test(
                 "modexp" => test_with::<ModExpCircuit<Fr>>(&witness_block),
                 "bytecode" => test_with::<BytecodeCircuit<Fr>>(&witness_block),
                 "ecc" => test_with::<EccCircuit<Fr, 9>>(&witness_block),
+                "bls12_381" => test_with::<Bls12381Circuit<Fr>>(&witness_block),
                 "sig" => {
                     if !witness_block
                         .precompile_events
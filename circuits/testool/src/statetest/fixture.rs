@@ -0,0 +1,639 @@
+//! Loader of the fixtures filled by the execution-spec-tests, in the `state_test` and
+//! `blockchain_test` formats.
+
+use super::{
+    parse,
+    spec::{AccountMatch, Env, StateTest, DEFAULT_BASE_FEE},
+};
+use crate::{compiler::Compiler, utils::MainnetFork};
+use anyhow::{bail, Context, Result};
+use eth_types::{geth_types::Account, Address, Bytes, U256};
+use ethers_core::{k256::ecdsa::SigningKey, utils::secret_key_to_address};
+use serde::{de::IgnoredAny, Deserialize};
+use std::{
+    collections::{BTreeMap, HashMap},
+    str::FromStr,
+};
+
+/// Key of the default sender of the fixtures, which blockchain tests only reference by address.
+const DEFAULT_SECRET_KEY: &str =
+    "0x45a915e4d060149eb4365960e6a7a45f334393093061116b197e3240065ff2d8";
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FixtureEnv {
+    current_coinbase: String,
+    current_gas_limit: String,
+    current_number: String,
+    current_timestamp: String,
+    current_difficulty: Option<String>,
+    current_random: Option<String>,
+    current_base_fee: Option<String>,
+    previous_hash: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct FixtureAccount {
+    balance: String,
+    code: String,
+    nonce: String,
+    storage: HashMap<String, String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct StateFixtureTransaction {
+    nonce: String,
+    gas_price: Option<String>,
+    max_priority_fee_per_gas: Option<String>,
+    max_fee_per_gas: Option<String>,
+    max_fee_per_blob_gas: Option<String>,
+    gas_limit: Vec<String>,
+    to: String,
+    value: Vec<String>,
+    data: Vec<String>,
+    access_lists: Option<Vec<Option<parse::RawAccessList>>>,
+    secret_key: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct Indexes {
+    data: usize,
+    gas: usize,
+    value: usize,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PostEntry {
+    indexes: Indexes,
+    state: Option<HashMap<String, FixtureAccount>>,
+    expect_exception: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct StateFixture {
+    env: FixtureEnv,
+    pre: HashMap<String, FixtureAccount>,
+    transaction: StateFixtureTransaction,
+    post: HashMap<String, Vec<PostEntry>>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BlockHeader {
+    parent_hash: String,
+    coinbase: String,
+    difficulty: String,
+    number: String,
+    gas_limit: String,
+    timestamp: String,
+    mix_hash: String,
+    base_fee_per_gas: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BlockTransaction {
+    nonce: String,
+    gas_price: Option<String>,
+    max_priority_fee_per_gas: Option<String>,
+    max_fee_per_gas: Option<String>,
+    max_fee_per_blob_gas: Option<String>,
+    gas_limit: String,
+    to: String,
+    value: String,
+    data: String,
+    access_list: Option<parse::RawAccessList>,
+    sender: String,
+    secret_key: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Block {
+    block_header: Option<BlockHeader>,
+    #[serde(default)]
+    transactions: Vec<BlockTransaction>,
+    expect_exception: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BlockchainFixture {
+    network: String,
+    pre: HashMap<String, FixtureAccount>,
+    post_state: Option<HashMap<String, FixtureAccount>>,
+    blocks: Vec<Block>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+enum Fixture {
+    State(Box<StateFixture>),
+    Blockchain(Box<BlockchainFixture>),
+}
+
+/// Fields of a transaction shared by both fixture formats.
+struct FixtureTx<'a> {
+    nonce: &'a str,
+    gas_price: &'a Option<String>,
+    max_priority_fee_per_gas: &'a Option<String>,
+    max_fee_per_gas: &'a Option<String>,
+    max_fee_per_blob_gas: &'a Option<String>,
+    to: &'a str,
+    secret_key: &'a str,
+}
+
+/// Fork whose post-states are checked: the Ethereum fork followed by the latest Scroll
+/// hardfork in scroll mode, the tested mainnet fork otherwise.
+fn selected_fork() -> Result<MainnetFork> {
+    #[cfg(feature = "scroll")]
+    let fork = MainnetFork::from_str(eth_types::forks::HardforkId::Curie.ethereum_fork())?;
+    #[cfg(not(feature = "scroll"))]
+    let fork = crate::utils::TEST_FORK;
+    Ok(fork)
+}
+
+fn is_selected_fork(network: &str) -> Result<bool> {
+    let selected = selected_fork()?;
+    // transition networks like `ShanghaiToCancunAtTime15k` are not tested
+    Ok(MainnetFork::from_str(network).map_or(false, |fork| fork == selected))
+}
+
+/// returns true if the json source contains fixtures instead of legacy fillers
+pub fn is_fixture(source: &str) -> bool {
+    #[derive(Deserialize)]
+    struct Probe {
+        post: Option<IgnoredAny>,
+        blocks: Option<IgnoredAny>,
+    }
+    serde_json::from_str::<HashMap<String, Probe>>(source).map_or(false, |tests| {
+        tests
+            .values()
+            .any(|test| test.post.is_some() || test.blocks.is_some())
+    })
+}
+
+pub struct FixtureStateTestBuilder<'a> {
+    compiler: &'a Compiler,
+}
+
+impl<'a> FixtureStateTestBuilder<'a> {
+    pub fn new(compiler: &'a Compiler) -> Self {
+        Self { compiler }
+    }
+
+    /// generates `StateTest` vectors from execution-spec-tests fixtures
+    pub fn load_fixtures(&mut self, path: &str, source: &str) -> Result<Vec<StateTest>> {
+        let fixtures: HashMap<String, Fixture> =
+            serde_json::from_str(source).context("unable to parse fixtures")?;
+
+        let mut state_tests = Vec::new();
+        for (test_name, fixture) in fixtures {
+            let tests = match fixture {
+                Fixture::State(fixture) => self.load_state_fixture(path, &test_name, &fixture)?,
+                Fixture::Blockchain(fixture) => {
+                    self.load_blockchain_fixture(path, &test_name, &fixture)?
+                }
+            };
+            state_tests.extend(tests);
+        }
+
+        Ok(state_tests)
+    }
+
+    fn load_state_fixture(
+        &mut self,
+        path: &str,
+        test_name: &str,
+        fixture: &StateFixture,
+    ) -> Result<Vec<StateTest>> {
+        let tx = &fixture.transaction;
+        let Some(template) = self.parse_tx(
+            test_name,
+            &fixture.env,
+            &fixture.pre,
+            FixtureTx {
+                nonce: &tx.nonce,
+                gas_price: &tx.gas_price,
+                max_priority_fee_per_gas: &tx.max_priority_fee_per_gas,
+                max_fee_per_gas: &tx.max_fee_per_gas,
+                max_fee_per_blob_gas: &tx.max_fee_per_blob_gas,
+                to: &tx.to,
+                secret_key: &tx.secret_key,
+            },
+        )?
+        else {
+            return Ok(vec![]);
+        };
+
+        let mut state_tests = Vec::new();
+        for (network, entries) in &fixture.post {
+            if !is_selected_fork(network)? {
+                continue;
+            }
+            for entry in entries {
+                let Indexes { data, gas, value } = entry.indexes;
+                let access_list = tx
+                    .access_lists
+                    .as_ref()
+                    .and_then(|access_lists| access_lists.get(data).cloned().flatten());
+                let calldata = parse::parse_calldata(
+                    self.compiler,
+                    tx.data.get(data).context("data index out of range")?,
+                    &access_list,
+                )?;
+                let mut access_list = calldata.access_list;
+                if template.max_fee_per_gas.is_some() && access_list.is_none() {
+                    access_list = Some(Default::default());
+                }
+                let result = match &entry.state {
+                    Some(state) => self.parse_accounts_post(state)?,
+                    None => BTreeMap::new(),
+                };
+
+                state_tests.push(StateTest {
+                    path: path.to_string(),
+                    id: format!("{test_name}_d{data}_g{gas}_v{value}"),
+                    gas_limit: parse::parse_u64(
+                        tx.gas_limit.get(gas).context("gas index out of range")?,
+                    )?,
+                    value: parse::parse_u256(
+                        tx.value.get(value).context("value index out of range")?,
+                    )?,
+                    data: calldata.data,
+                    access_list,
+                    result,
+                    exception: entry.expect_exception.is_some(),
+                    ..template.clone()
+                });
+            }
+        }
+
+        Ok(state_tests)
+    }
+
+    /// blockchain fixtures are supported when they consist of a single block with a single valid
+    /// transaction, which is then checked against the post state.
+    fn load_blockchain_fixture(
+        &mut self,
+        path: &str,
+        test_name: &str,
+        fixture: &BlockchainFixture,
+    ) -> Result<Vec<StateTest>> {
+        if !is_selected_fork(&fixture.network)? {
+            return Ok(vec![]);
+        }
+        let (header, tx) = match fixture.blocks.as_slice() {
+            [Block {
+                block_header: Some(header),
+                transactions,
+                expect_exception: None,
+            }] if transactions.len() == 1 => (header, &transactions[0]),
+            _ => {
+                log::warn!("{test_name}: only single transaction blocks are supported, skip");
+                return Ok(vec![]);
+            }
+        };
+
+        let secret_key = match &tx.secret_key {
+            Some(secret_key) => secret_key.as_str(),
+            None if parse::parse_address(&tx.sender)? == Self::address_of(DEFAULT_SECRET_KEY)? => {
+                DEFAULT_SECRET_KEY
+            }
+            None => {
+                log::warn!("{test_name}: unknown key of sender {}, skip", tx.sender);
+                return Ok(vec![]);
+            }
+        };
+
+        // post-merge blocks carry the randomness in `mixHash`
+        let difficulty = parse::parse_u256(&header.difficulty)?;
+        let env = FixtureEnv {
+            current_coinbase: header.coinbase.clone(),
+            current_gas_limit: header.gas_limit.clone(),
+            current_number: header.number.clone(),
+            current_timestamp: header.timestamp.clone(),
+            current_difficulty: Some(header.difficulty.clone()),
+            current_random: difficulty.is_zero().then(|| header.mix_hash.clone()),
+            current_base_fee: header.base_fee_per_gas.clone(),
+            previous_hash: Some(header.parent_hash.clone()),
+        };
+        let Some(template) = self.parse_tx(
+            test_name,
+            &env,
+            &fixture.pre,
+            FixtureTx {
+                nonce: &tx.nonce,
+                gas_price: &tx.gas_price,
+                max_priority_fee_per_gas: &tx.max_priority_fee_per_gas,
+                max_fee_per_gas: &tx.max_fee_per_gas,
+                max_fee_per_blob_gas: &tx.max_fee_per_blob_gas,
+                to: &tx.to,
+                secret_key,
+            },
+        )?
+        else {
+            return Ok(vec![]);
+        };
+
+        let calldata = parse::parse_calldata(self.compiler, &tx.data, &tx.access_list)?;
+        let mut access_list = calldata.access_list;
+        if template.max_fee_per_gas.is_some() && access_list.is_none() {
+            access_list = Some(Default::default());
+        }
+        let result = match &fixture.post_state {
+            Some(state) => self.parse_accounts_post(state)?,
+            None => BTreeMap::new(),
+        };
+
+        Ok(vec![StateTest {
+            path: path.to_string(),
+            id: test_name.to_string(),
+            gas_limit: parse::parse_u64(&tx.gas_limit)?,
+            value: parse::parse_u256(&tx.value)?,
+            data: calldata.data,
+            access_list,
+            result,
+            ..template
+        }])
+    }
+
+    /// parse the env, pre-state and transaction shared by all the vectors of a fixture, returns
+    /// None if the transaction type is not supported.
+    fn parse_tx(
+        &mut self,
+        test_name: &str,
+        env: &FixtureEnv,
+        pre: &HashMap<String, FixtureAccount>,
+        tx: FixtureTx,
+    ) -> Result<Option<StateTest>> {
+        if tx.max_fee_per_blob_gas.is_some() {
+            log::warn!("{test_name}: blob transactions are not supported, skip");
+            return Ok(None);
+        }
+
+        let env = Self::parse_env(env)?;
+        let secret_key = parse::parse_bytes(tx.secret_key)?;
+        let max_priority_fee_per_gas = tx
+            .max_priority_fee_per_gas
+            .as_deref()
+            .map(parse::parse_u256)
+            .transpose()?;
+        let max_fee_per_gas = tx
+            .max_fee_per_gas
+            .as_deref()
+            .map(parse::parse_u256)
+            .transpose()?;
+        // Set gas price to `min(max_priority_fee_per_gas + base_fee, max_fee_per_gas)` for
+        // EIP-1559 transaction.
+        let gas_price = match (tx.gas_price, max_priority_fee_per_gas, max_fee_per_gas) {
+            (Some(gas_price), _, _) => parse::parse_u256(gas_price)?,
+            (None, Some(max_priority_fee_per_gas), Some(max_fee_per_gas)) => {
+                max_fee_per_gas.min(max_priority_fee_per_gas + env.current_base_fee)
+            }
+            _ => bail!("{test_name}: missing gas price"),
+        };
+
+        Ok(Some(StateTest {
+            path: String::new(),
+            id: String::new(),
+            from: Self::address_of(tx.secret_key)?,
+            to: parse::parse_to_address(tx.to)?,
+            secret_key,
+            nonce: parse::parse_u256(tx.nonce)?,
+            max_priority_fee_per_gas,
+            max_fee_per_gas,
+            gas_price,
+            gas_limit: 0,
+            value: U256::zero(),
+            data: Bytes::default(),
+            access_list: None,
+            pre: self.parse_accounts_pre(pre)?,
+            result: BTreeMap::new(),
+            exception: false,
+            env,
+        }))
+    }
+
+    fn address_of(secret_key: &str) -> Result<Address> {
+        let secret_key = parse::parse_bytes(secret_key)?;
+        Ok(secret_key_to_address(&SigningKey::from_slice(&secret_key)?))
+    }
+
+    /// parse env section
+    fn parse_env(env: &FixtureEnv) -> Result<Env> {
+        let difficulty = env
+            .current_random
+            .as_ref()
+            .or(env.current_difficulty.as_ref());
+        Ok(Env {
+            current_base_fee: env
+                .current_base_fee
+                .as_deref()
+                .map(parse::parse_u256)
+                .transpose()?
+                .unwrap_or_else(|| U256::from(DEFAULT_BASE_FEE)),
+            current_coinbase: parse::parse_address(&env.current_coinbase)?,
+            current_difficulty: difficulty
+                .map(|v| parse::parse_u256(v))
+                .transpose()?
+                .unwrap_or_default(),
+            current_gas_limit: parse::parse_u64(&env.current_gas_limit)?,
+            current_number: parse::parse_u64(&env.current_number)?,
+            current_timestamp: parse::parse_u64(&env.current_timestamp)?,
+            previous_hash: env
+                .previous_hash
+                .as_deref()
+                .map(parse::parse_hash)
+                .transpose()?
+                .unwrap_or_default(),
+        })
+    }
+
+    fn parse_storage(storage: &HashMap<String, String>) -> Result<HashMap<U256, U256>> {
+        storage
+            .iter()
+            .map(|(k, v)| Ok((parse::parse_u256(k)?, parse::parse_u256(v)?)))
+            .collect()
+    }
+
+    /// parse a vector of address=>(storage,balance,code,nonce) entry
+    fn parse_accounts_pre(
+        &mut self,
+        accounts_pre: &HashMap<String, FixtureAccount>,
+    ) -> Result<BTreeMap<Address, Account>> {
+        let mut accounts = BTreeMap::new();
+        for (address, acc) in accounts_pre {
+            let address = parse::parse_address(address)?;
+            let account = Account {
+                address,
+                balance: parse::parse_u256(&acc.balance)?,
+                nonce: parse::parse_u256(&acc.nonce)?,
+                code: parse::parse_code(self.compiler, &acc.code)?,
+                storage: Self::parse_storage(&acc.storage)?,
+            };
+            accounts.insert(address, account);
+        }
+        Ok(accounts)
+    }
+
+    /// parse the full post state, accounts missing from it are not checked
+    fn parse_accounts_post(
+        &mut self,
+        accounts_post: &HashMap<String, FixtureAccount>,
+    ) -> Result<BTreeMap<Address, AccountMatch>> {
+        let mut accounts = BTreeMap::new();
+        for (address, acc) in accounts_post {
+            let address = parse::parse_address(address)?;
+            let account = AccountMatch {
+                address,
+                balance: Some(parse::parse_u256(&acc.balance)?),
+                code: Some(parse::parse_code(self.compiler, &acc.code)?),
+                nonce: Some(parse::parse_u256(&acc.nonce)?),
+                storage: Self::parse_storage(&acc.storage)?,
+            };
+            accounts.insert(address, account);
+        }
+        Ok(accounts)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use eth_types::H256;
+
+    const STATE_FIXTURE: &str = r#"
+{
+    "tests/cancun/eip1153_tstore/test_tstore.py::test_tload_after_tstore[fork_Cancun-state_test]": {
+        "env": {
+            "currentCoinbase": "0x2adc25665018aa1fe0e6bc666dac8fc2697ff9ba",
+            "currentGasLimit": "0x016345785d8a0000",
+            "currentNumber": "0x01",
+            "currentTimestamp": "0x03e8",
+            "currentRandom": "0x0000000000000000000000000000000000000000000000000000000000000000",
+            "currentDifficulty": "0x00",
+            "currentBaseFee": "0x07"
+        },
+        "pre": {
+            "0x0000000000000000000000000000000000001000": {
+                "nonce": "0x01",
+                "balance": "0x00",
+                "code": "0x600260015d60015c60005500",
+                "storage": {}
+            },
+            "0xa94f5374fce5edbc8e2a8697c15331677e6ebf0b": {
+                "nonce": "0x00",
+                "balance": "0x3635c9adc5dea00000",
+                "code": "0x",
+                "storage": {}
+            }
+        },
+        "transaction": {
+            "nonce": "0x00",
+            "maxPriorityFeePerGas": "0x00",
+            "maxFeePerGas": "0x07",
+            "gasLimit": ["0x0186a0"],
+            "to": "0x0000000000000000000000000000000000001000",
+            "value": ["0x00"],
+            "data": ["0x"],
+            "sender": "0xa94f5374fce5edbc8e2a8697c15331677e6ebf0b",
+            "secretKey": "0x45a915e4d060149eb4365960e6a7a45f334393093061116b197e3240065ff2d8"
+        },
+        "post": {
+            "Shanghai": [
+                {
+                    "hash": "0x0000000000000000000000000000000000000000000000000000000000000000",
+                    "logs": "0x1dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347",
+                    "txbytes": "0x",
+                    "indexes": {"data": 0, "gas": 0, "value": 0},
+                    "expectException": "TransactionException.INTRINSIC_GAS_TOO_LOW"
+                }
+            ],
+            "Cancun": [
+                {
+                    "hash": "0x0000000000000000000000000000000000000000000000000000000000000000",
+                    "logs": "0x1dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347",
+                    "txbytes": "0x",
+                    "indexes": {"data": 0, "gas": 0, "value": 0},
+                    "state": {
+                        "0x0000000000000000000000000000000000001000": {
+                            "nonce": "0x01",
+                            "balance": "0x00",
+                            "code": "0x600260015d60015c60005500",
+                            "storage": {"0x00": "0x02"}
+                        }
+                    }
+                }
+            ]
+        }
+    }
+}
+"#;
+
+    #[test]
+    fn test_state_fixture_parse() -> Result<()> {
+        assert!(is_fixture(STATE_FIXTURE));
+
+        let compiler = Compiler::new(true, None)?;
+        let mut builder = FixtureStateTestBuilder::new(&compiler);
+        let mut tests = builder.load_fixtures("test_path", STATE_FIXTURE)?;
+        assert_eq!(tests.len(), 1);
+        let test = tests.remove(0);
+
+        let contract = Address::from_str("0x0000000000000000000000000000000000001000")?;
+        let code = Bytes::from(hex::decode("600260015d60015c60005500")?);
+
+        assert_eq!(
+            test.id,
+            "tests/cancun/eip1153_tstore/test_tstore.py::test_tload_after_tstore[fork_Cancun-state_test]_d0_g0_v0"
+        );
+        assert_eq!(
+            test.env,
+            Env {
+                current_base_fee: U256::from(7u64),
+                current_coinbase: Address::from_str("0x2adc25665018aa1fe0e6bc666dac8fc2697ff9ba")?,
+                current_difficulty: U256::zero(),
+                current_gas_limit: 0x016345785d8a0000,
+                current_number: 1,
+                current_timestamp: 1000,
+                previous_hash: H256::zero(),
+            }
+        );
+        assert_eq!(
+            test.from,
+            Address::from_str("0xa94f5374fce5edbc8e2a8697c15331677e6ebf0b")?
+        );
+        assert_eq!(test.to, Some(contract));
+        assert_eq!(test.gas_limit, 100000);
+        assert_eq!(test.gas_price, U256::from(7u64));
+        assert_eq!(test.max_fee_per_gas, Some(U256::from(7u64)));
+        assert_eq!(test.access_list, Some(Default::default()));
+        assert_eq!(test.pre.len(), 2);
+        assert_eq!(
+            test.result,
+            BTreeMap::from([(
+                contract,
+                AccountMatch {
+                    address: contract,
+                    nonce: Some(U256::one()),
+                    balance: Some(U256::zero()),
+                    code: Some(code),
+                    storage: HashMap::from([(U256::zero(), U256::from(2u64))]),
+                },
+            )])
+        );
+        assert!(!test.exception);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_legacy_filler_is_not_fixture() {
+        assert!(!is_fixture(
+            r#"{"add11": {"env": {}, "expect": [], "pre": {}, "transaction": {}}}"#
+        ));
+    }
+}
@@ -1,14 +1,18 @@
 mod executor;
+mod fixture;
 mod json;
 mod parse;
 mod results;
+mod scheduler;
 pub mod spec;
 mod suite;
 mod yaml;
 
 pub use executor::{run_test, CircuitsConfig};
+pub use fixture::FixtureStateTestBuilder;
 pub use json::JsonStateTestBuilder;
 pub use results::{ResultLevel, Results};
+pub use scheduler::{PoolConfig, Shard};
 pub use spec::{AccountMatch, StateTest, StateTestResult};
 pub use suite::{load_statetests_suite, run_statetests_suite};
 pub use yaml::YamlStateTestBuilder;
@@ -34,6 +34,8 @@ pub struct ResultInfo {
     pub level: ResultLevel,
     pub details: String,
     pub path: String,
+    /// compiled with the native yul backend, whose unoptimized code uses more gas than solc's
+    pub native: bool,
 }
 
 impl ResultLevel {
@@ -140,7 +142,11 @@ impl Report {
         files_diff.print_tty(false)?;
         let mut num_succ = 0f32;
         let mut num_fail = 0f32;
+        let mut num_native = 0;
         for (test_id, info) in &self.tests {
+            if info.native {
+                num_native += 1;
+            }
             if info.level == ResultLevel::Success {
                 num_succ += 1.0;
             }
@@ -153,6 +159,12 @@ impl Report {
             "success rate: {:.1}%",
             100f32 * num_succ / (num_succ + num_fail)
         );
+        if num_native > 0 {
+            log::warn!(
+                "{num_native} results are from the native yul backend, tests depending on the gas \
+                 used can differ from solc"
+            );
+        }
         Ok(())
     }
     pub fn gen_html(&self, githash: String) -> Result<String> {
@@ -226,10 +238,17 @@ impl Results {
         let mut file = std::fs::File::open(&path)?;
         let mut buf = String::new();
         file.read_to_string(&mut buf)?;
+        // an interrupted run can leave the last entry partially written
+        if !buf.is_empty() && !buf.ends_with('\n') {
+            let complete = buf.rfind('\n').map_or(0, |pos| pos + 1);
+            log::warn!("dropping incomplete entry {:?}", &buf[complete..]);
+            buf.truncate(complete);
+        }
         let mut tests = HashMap::new();
         for line in buf.lines().filter(|l| l.len() > 1) {
-            let split: Vec<&str> = line.splitn(4, ';').collect();
-            if split.len() != 4 {
+            // the `native` tag is only written for results of the native yul backend
+            let split: Vec<&str> = line.splitn(5, ';').collect();
+            if split.len() != 4 && split.len() != 5 {
                 log::warn!("un-supported line {:?}", line);
                 return Ok(Self { cache: None, tests });
             }
@@ -241,6 +260,7 @@ impl Results {
                 .expect("should be urldecodeable")
                 .to_string();
             let path = split.next().unwrap().to_string();
+            let native = split.next() == Some(&"native");
             let id = format!("{test_id}#{path}");
             tests.insert(
                 id,
@@ -249,6 +269,7 @@ impl Results {
                     level,
                     details,
                     path,
+                    native,
                 },
             );
         }
@@ -256,21 +277,37 @@ impl Results {
     }
 
     pub fn with_cache(path: PathBuf) -> Result<Self> {
-        let tests = if path.exists() {
-            Self::from_file(path.clone())?.tests
+        let results = if path.exists() {
+            let results = Self {
+                tests: Self::from_file(path.clone())?.tests,
+                cache: Some(path),
+            };
+            // rewrite the loaded entries, without an incomplete one new entries would follow
+            results.write_cache()?;
+            results
         } else {
-            HashMap::new()
+            Self {
+                tests: HashMap::new(),
+                cache: Some(path),
+            }
         };
-        Ok(Self {
-            tests,
-            cache: Some(path),
-        })
+        Ok(results)
     }
 
     pub fn set_cache(&mut self, path: PathBuf) {
         self.cache = Some(path);
     }
 
+    /// merges the results of several runs, like the shards of a suite. Later files take
+    /// precedence for the tests found in several of them.
+    pub fn merge(paths: &[PathBuf]) -> Result<Self> {
+        let mut results = Self::default();
+        for path in paths {
+            results.tests.extend(Self::from_file(path.clone())?.tests);
+        }
+        Ok(results)
+    }
+
     pub fn report(self, previous: Option<(String, Results)>) -> Report {
         // collect data
         let mut folders = HashSet::new();
@@ -293,7 +330,10 @@ impl Results {
             let filename = &file_path.rsplit_terminator('/').next().unwrap();
             let folder = &file_path[..file_path.len() - filename.len() - 1];
 
-            let result = format!("{:?}_{}", info.level, info.details);
+            let mut result = format!("{:?}_{}", info.level, info.details);
+            if info.native {
+                result.push_str(" [native yul]");
+            }
 
             folders.insert(folder);
             results.insert(result.to_string());
@@ -399,31 +439,37 @@ impl Results {
         self.tests.contains_key(test)
     }
 
+    fn cache_entry(result: &ResultInfo) -> String {
+        format!(
+            "{:?};{};{};{}{}\n",
+            result.level,
+            result.test_id,
+            urlencoding::encode(&result.details),
+            result.path,
+            if result.native { ";native" } else { "" },
+        )
+    }
+
+    /// writes all the results to the cache file, replacing it atomically
     pub fn write_cache(&self) -> Result<()> {
         if let Some(path) = &self.cache {
-            let mut file = std::fs::OpenOptions::new()
-                .read(true)
-                .write(true)
-                .create(true)
-                .append(true)
-                .open(path)?;
+            let mut tmp = path.clone().into_os_string();
+            tmp.push(".tmp");
+            let tmp = PathBuf::from(tmp);
+            let mut file = std::fs::File::create(&tmp)?;
             for result in self.tests.values() {
-                let entry = format!(
-                    "{:?};{};{};{}\n",
-                    result.level,
-                    result.test_id,
-                    urlencoding::encode(&result.details),
-                    result.path,
-                );
-                file.write_all(entry.as_bytes())?;
+                file.write_all(Self::cache_entry(result).as_bytes())?;
             }
+            file.sync_all()?;
+            std::fs::rename(tmp, path)?;
         }
         Ok(())
     }
 
     #[allow(clippy::map_entry)]
     pub fn insert(&mut self, result: ResultInfo) -> Result<()> {
-        if !self.tests.contains_key(&result.test_id) {
+        let id = format!("{}#{}", result.test_id, result.path);
+        if !self.tests.contains_key(&id) {
             if result.level == ResultLevel::Ignored {
                 log::debug!(
                     target : "testool",
@@ -442,26 +488,68 @@ impl Results {
                     result.path,
                 );
             }
-            let entry = format!(
-                "{:?};{};{};{}\n",
-                result.level,
-                result.test_id,
-                urlencoding::encode(&result.details),
-                result.path,
-            );
+            // each entry is appended with a single write and synced, so that an interrupted run
+            // loses at most the entry being written, which is dropped when loading the cache
             if let Some(path) = &self.cache {
-                std::fs::OpenOptions::new()
-                    .read(true)
-                    .write(true)
+                let mut file = std::fs::OpenOptions::new()
                     .create(true)
                     .append(true)
-                    .open(path)?
-                    .write_all(entry.as_bytes())?;
+                    .open(path)?;
+                file.write_all(Self::cache_entry(&result).as_bytes())?;
+                file.sync_data()?;
             }
-            let id = format!("{}#{}", result.test_id, result.path);
             self.tests.insert(id, result);
         }
 
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn result(test_id: &str, level: ResultLevel) -> ResultInfo {
+        ResultInfo {
+            test_id: test_id.to_string(),
+            level,
+            details: "some details; with separator".to_string(),
+            path: "tests/src/GeneralStateTestsFiller/stExample/add11Filler.json".to_string(),
+            native: false,
+        }
+    }
+
+    #[test]
+    fn test_resume_and_merge() -> Result<()> {
+        let dir = std::env::temp_dir().join(format!("testool-results-{}", std::process::id()));
+        std::fs::create_dir_all(&dir)?;
+        let (shard0, shard1) = (dir.join("shard0.csv"), dir.join("shard1.csv"));
+
+        let mut results = Results::with_cache(shard0.clone())?;
+        results.insert(result("add11_d0_g0_v0", ResultLevel::Success))?;
+        results.insert(result("add11_d1_g0_v0", ResultLevel::Fail))?;
+        // interrupted while writing an entry
+        std::fs::OpenOptions::new()
+            .append(true)
+            .open(&shard0)?
+            .write_all(b"Success;add11_d2")?;
+
+        let resumed = Results::with_cache(shard0.clone())?;
+        assert_eq!(resumed.tests, results.tests);
+
+        let mut results = Results::with_cache(shard1.clone())?;
+        results.insert(result("add11_d1_g0_v0", ResultLevel::Success))?;
+        results.insert(ResultInfo {
+            native: true,
+            ..result("add11_d2_g0_v0", ResultLevel::Ignored)
+        })?;
+
+        let merged = Results::merge(&[shard0, shard1])?;
+        assert_eq!(merged.tests.len(), 3);
+        assert!(merged.success());
+        assert_eq!(merged.tests.values().filter(|t| t.native).count(), 1);
+
+        std::fs::remove_dir_all(dir)?;
+        Ok(())
+    }
+}
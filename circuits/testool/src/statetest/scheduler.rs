@@ -0,0 +1,175 @@
+use anyhow::{bail, Context};
+use ethers_core::utils::keccak256;
+use std::{
+    str::FromStr,
+    sync::{Condvar, Mutex},
+};
+
+const GIB: u64 = 1 << 30;
+/// Memory reserved by default for a test executed with the evm circuit.
+const BASIC_TEST_MEMORY: u64 = 4 * GIB;
+/// Memory reserved by default for a test executed with the super circuit.
+const SUPER_CIRCUIT_TEST_MEMORY: u64 = 48 * GIB;
+
+/// Subset `index` of the tests split in `count` shards, `index` starting from 0.
+/// Tests are assigned by the hash of their id, so the assignment does not depend on the order
+/// or on the other tests collected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Shard {
+    pub index: usize,
+    pub count: usize,
+}
+
+impl FromStr for Shard {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (index, count) = s.split_once('/').context("shard should be `index/count`")?;
+        let shard = Self {
+            index: index.trim().parse().context("invalid shard index")?,
+            count: count.trim().parse().context("invalid shard count")?,
+        };
+        if shard.index >= shard.count {
+            bail!("shard index {} out of 0..{}", shard.index, shard.count);
+        }
+        Ok(shard)
+    }
+}
+
+impl Shard {
+    pub fn contains(&self, test_id: &str) -> bool {
+        let hash = keccak256(test_id.as_bytes());
+        let hash = u64::from_be_bytes(hash[..8].try_into().unwrap());
+        hash % self.count as u64 == self.index as u64
+    }
+}
+
+/// Limits of the pool executing the tests.
+#[derive(Debug, Clone, Default)]
+pub struct PoolConfig {
+    /// Number of tests executed concurrently, defaults to the number of cpus, or to one test
+    /// when running the super circuit without a memory limit.
+    pub jobs: Option<usize>,
+    /// Memory in bytes shared by the tests being executed, unlimited if not set.
+    pub memory: Option<u64>,
+    /// Memory in bytes reserved by each test, defaults to an estimation for the circuits.
+    pub test_memory: Option<u64>,
+}
+
+impl PoolConfig {
+    pub fn jobs(&self, super_circuit: bool) -> usize {
+        self.jobs.unwrap_or_else(|| {
+            if super_circuit && self.memory.is_none() {
+                1
+            } else {
+                std::thread::available_parallelism().map_or(1, |n| n.get())
+            }
+        })
+    }
+
+    pub fn test_memory(&self, super_circuit: bool) -> u64 {
+        self.test_memory.unwrap_or(if super_circuit {
+            SUPER_CIRCUIT_TEST_MEMORY
+        } else {
+            BASIC_TEST_MEMORY
+        })
+    }
+}
+
+/// Memory shared by the running tests. Tests larger than the whole budget are executed alone.
+pub struct MemoryBudget {
+    total: Option<u64>,
+    available: Mutex<u64>,
+    released: Condvar,
+}
+
+pub struct Reservation<'a> {
+    budget: &'a MemoryBudget,
+    amount: u64,
+}
+
+impl MemoryBudget {
+    pub fn new(total: Option<u64>) -> Self {
+        Self {
+            total,
+            available: Mutex::new(total.unwrap_or_default()),
+            released: Condvar::new(),
+        }
+    }
+
+    /// Wait until `amount` bytes are available and reserve them until the returned reservation
+    /// is dropped.
+    pub fn reserve(&self, amount: u64) -> Reservation<'_> {
+        let Some(total) = self.total else {
+            return Reservation {
+                budget: self,
+                amount: 0,
+            };
+        };
+        let amount = amount.min(total);
+        let mut available = self
+            .released
+            .wait_while(self.available.lock().unwrap(), |available| {
+                *available < amount
+            })
+            .unwrap();
+        *available -= amount;
+        Reservation {
+            budget: self,
+            amount,
+        }
+    }
+}
+
+impl Drop for Reservation<'_> {
+    fn drop(&mut self) {
+        if self.amount > 0 {
+            *self.budget.available.lock().unwrap() += self.amount;
+            self.budget.released.notify_all();
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::{sync::Arc, thread, time::Duration};
+
+    #[test]
+    fn test_shard_parse() {
+        assert_eq!(
+            Shard::from_str("1/4").unwrap(),
+            Shard { index: 1, count: 4 }
+        );
+        assert!(Shard::from_str("4/4").is_err());
+        assert!(Shard::from_str("1").is_err());
+    }
+
+    #[test]
+    fn test_shards_partition_tests() {
+        let shards: Vec<_> = (0..3).map(|index| Shard { index, count: 3 }).collect();
+        for i in 0..100 {
+            let id = format!("add11_d{i}_g0_v0");
+            assert_eq!(shards.iter().filter(|s| s.contains(&id)).count(), 1);
+        }
+    }
+
+    #[test]
+    fn test_memory_budget() {
+        let budget = Arc::new(MemoryBudget::new(Some(10)));
+        let first = budget.reserve(6);
+        // larger than the budget, clamped to run alone
+        let waiting = {
+            let budget = budget.clone();
+            thread::spawn(move || {
+                let reservation = budget.reserve(100);
+                assert_eq!(reservation.amount, 10);
+            })
+        };
+        thread::sleep(Duration::from_millis(50));
+        assert!(!waiting.is_finished());
+        drop(first);
+        waiting.join().unwrap();
+        assert_eq!(*budget.available.lock().unwrap(), 10);
+    }
+}
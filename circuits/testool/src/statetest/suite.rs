@@ -1,10 +1,15 @@
-use super::{executor::run_test, CircuitsConfig, JsonStateTestBuilder, Results, StateTest};
+use super::{
+    executor::run_test,
+    scheduler::{MemoryBudget, PoolConfig},
+    CircuitsConfig, JsonStateTestBuilder, Results, StateTest,
+};
 use crate::{
     compiler::Compiler,
-    config::{Config, TestSuite},
+    config::{CompilerBackend, Config, TestSuite},
     statetest::{
+        fixture::is_fixture,
         results::{ResultInfo, ResultLevel},
-        YamlStateTestBuilder,
+        FixtureStateTestBuilder, YamlStateTestBuilder,
     },
 };
 use anyhow::{Context, Result};
@@ -49,6 +54,9 @@ pub fn load_statetests_suite(
                     //log::debug!(target: "testool", "Reading file {:?}", file);
                     let tcs = match ext {
                         "yml" => YamlStateTestBuilder::new(&compiler).load_yaml(&path, &src),
+                        "json" if is_fixture(&src) => {
+                            FixtureStateTestBuilder::new(&compiler).load_fixtures(&path, &src)
+                        }
                         "json" => JsonStateTestBuilder::new(&compiler).load_json(&path, &src),
                         _ => unreachable!(),
                     };
@@ -76,9 +84,13 @@ pub fn load_statetests_suite(
 pub fn run_statetests_suite(
     tcs: Vec<StateTest>,
     circuits_config: &CircuitsConfig,
+    pool_config: &PoolConfig,
     suite: &TestSuite,
+    compiler_backend: CompilerBackend,
     results: &mut Results,
 ) -> Result<()> {
+    let native = compiler_backend == CompilerBackend::Native;
+
     // Filter already cached entries
     let all_test_count = tcs.len();
     let tcs: Vec<StateTest> = tcs
@@ -107,6 +119,7 @@ pub fn run_statetests_suite(
                     level: ResultLevel::Ignored,
                     details: "Ignored in config file".to_string(),
                     path,
+                    native,
                 })
                 .unwrap();
             return;
@@ -153,6 +166,7 @@ pub fn run_statetests_suite(
                         level,
                         details: panic_err,
                         path,
+                        native,
                     })
                     .unwrap();
                 return;
@@ -173,6 +187,7 @@ pub fn run_statetests_suite(
                     },
                     details: err.to_string(),
                     path,
+                    native,
                 })
                 .unwrap();
             return;
@@ -186,22 +201,34 @@ pub fn run_statetests_suite(
                 level: ResultLevel::Success,
                 details: String::default(),
                 path,
+                native,
             })
             .unwrap();
     };
 
-    if circuits_config.super_circuit {
-        tcs.into_iter().for_each(|ref tc| run_state_test(tc));
-    } else {
-        const PARALLELISM: usize = 20;
-        let mut groups =
-            [(); PARALLELISM].map(|_| Vec::with_capacity((tcs.len() / PARALLELISM) + 1));
-        tcs.into_iter().enumerate().for_each(|(i, tc)| {
-            groups[i % PARALLELISM].push(tc);
-        });
-        groups
-            .into_par_iter()
-            .for_each(|chunk| chunk.into_iter().for_each(|ref tc| run_state_test(tc)));
-    }
+    // Memory is reserved before spawning each test, so that waiting for memory never blocks
+    // a worker of the pool, which could be in the middle of a test.
+    let jobs = pool_config.jobs(circuits_config.super_circuit);
+    let test_memory = pool_config.test_memory(circuits_config.super_circuit);
+    let budget = MemoryBudget::new(pool_config.memory);
+    log::info!(
+        "running with {jobs} jobs, {test_memory} bytes per test, memory budget {:?}",
+        pool_config.memory
+    );
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(jobs)
+        .build()
+        .context("failed to build thread pool")?;
+    let run_state_test = &run_state_test;
+    let budget = &budget;
+    pool.in_place_scope(|scope| {
+        for tc in tcs {
+            let reservation = budget.reserve(test_memory);
+            scope.spawn(move |_| {
+                run_state_test(&tc);
+                drop(reservation);
+            });
+        }
+    });
     Ok(())
 }
@@ -14,6 +14,7 @@ pub const ETH_CHAIN_ID: u64 = 1;
 
 #[derive(Debug, Eq, PartialEq, PartialOrd)]
 pub enum MainnetFork {
+    Prague = 17,
     Cancun = 16,
     Shanghai = 15,
     Paris = 14,
@@ -39,9 +40,10 @@ impl FromStr for MainnetFork {
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         Ok(match s {
+            "Prague" => Self::Prague,
             "Cancun" => Self::Cancun,
             "Shanghai" => Self::Shanghai,
-            "Paris" => Self::Paris,
+            "Paris" | "Merge" => Self::Paris,
             "Gray Glacier" => Self::GrayGlacier,
             "Arrow Glacier" => Self::ArrowGlacier,
             "Altair" => Self::Altair,
@@ -0,0 +1,550 @@
+//! The BLS12-381 circuit is responsible for verifying BLS12-381 operations from precompiled
+//! contract calls (EIP-2537), namely, G1Add.
+//!
+//! EIP-2537 support is partial: G1Add is the only operation proven. The remaining BLS12-381
+//! precompiles (G1 multiplication and MSM, the G2 operations, the pairing check and the
+//! map-to-curve operations) need Fp2 and pairing chips over BLS12-381 which halo2-ecc does not
+//! provide, so they are not supported: bus-mapping rejects calls to them.
+
+use std::marker::PhantomData;
+
+use crate::util::Field;
+use bus_mapping::{
+    circuit_input_builder::Bls12G1AddOp,
+    precompile::{PrecompileCalls, BLS12_FP_LEN, BLS12_G1_ADD_INPUT_LEN},
+};
+use halo2_base::{
+    gates::{GateInstructions, RangeInstructions},
+    utils::modulus,
+    AssignedValue, Context, QuantumCell, SKIP_FIRST_PASS,
+};
+use halo2_ecc::{
+    bigint::{big_is_zero, CRTInteger, OverflowInteger},
+    ecc::{EcPoint, EccChip},
+    fields::{
+        fp::{FpConfig, FpStrategy},
+        FieldChip,
+    },
+};
+use halo2_proofs::{
+    circuit::{Layouter, Value},
+    halo2curves::{
+        bls12_381::{Fq, G1Affine},
+        CurveAffine,
+    },
+    plonk::{ConstraintSystem, Error, Expression},
+};
+use itertools::Itertools;
+use log::error;
+
+use crate::{
+    evm_circuit::EvmCircuit,
+    keccak_circuit::KeccakCircuit,
+    table::{Bls12381Table, LookupTable},
+    util::{Challenges, SubCircuit, SubCircuitConfig},
+    witness::Block,
+};
+
+#[cfg(any(feature = "test", test, feature = "test-circuits"))]
+mod dev;
+#[cfg(any(feature = "test", test, feature = "test-circuits"))]
+mod test;
+mod util;
+
+use util::{
+    FqDecomposed, G1AddAssigned, G1AddDecomposed, G1Decomposed, LOG_TOTAL_NUM_ROWS, N_BYTES_FQ,
+    N_BYTES_LIMB,
+};
+
+/// Arguments accepted to configure the Bls12381CircuitConfig.
+#[derive(Clone, Debug)]
+pub struct Bls12381CircuitConfigArgs<F: Field> {
+    /// BLS12-381 table that is connected to the BLS12-381 circuit.
+    pub bls12_381_table: Bls12381Table,
+    /// zkEVM challenge API.
+    pub challenges: Challenges<Expression<F>>,
+}
+
+/// Config for the BLS12-381 circuit.
+#[derive(Clone, Debug)]
+pub struct Bls12381CircuitConfig<F: Field> {
+    /// Field config for halo2_proofs::halo2curves::bls12_381::Fq.
+    fp_config: FpConfig<F, Fq>,
+    /// Lookup table for I/Os to the G1Add operations.
+    bls12_381_table: Bls12381Table,
+
+    _marker: PhantomData<F>,
+}
+
+impl<F: Field> SubCircuitConfig<F> for Bls12381CircuitConfig<F> {
+    type ConfigArgs = Bls12381CircuitConfigArgs<F>;
+
+    fn new(
+        meta: &mut ConstraintSystem<F>,
+        Self::ConfigArgs {
+            bls12_381_table,
+            challenges: _,
+        }: Self::ConfigArgs,
+    ) -> Self {
+        // 4 limbs of 104 bits, i.e. 13 bytes, hold the 381 bits of Fq.
+        let num_limbs = 4;
+        let limb_bits = N_BYTES_LIMB * 8;
+        #[cfg(feature = "onephase")]
+        let num_advice = [6];
+        #[cfg(not(feature = "onephase"))]
+        let num_advice = [6, 1];
+
+        let fp_config = FpConfig::configure(
+            meta,
+            FpStrategy::Simple,
+            &num_advice,
+            &[2], // num lookup advice
+            1,    // num fixed
+            13,   // lookup bits
+            limb_bits,
+            num_limbs,
+            modulus::<Fq>(),
+            0,
+            LOG_TOTAL_NUM_ROWS as usize, // k
+        );
+
+        for column in <Bls12381Table as LookupTable<F>>::advice_columns(&bls12_381_table) {
+            meta.enable_equality(column);
+        }
+
+        Self {
+            fp_config,
+            bls12_381_table,
+            _marker: PhantomData,
+        }
+    }
+}
+
+/// The BLS12-381 Circuit is a sub-circuit of the super circuit, responsible for verifying the
+/// G1 point additions (R = P + Q) of the BLS12-381 precompiles.
+///
+/// Like the ECC circuit, we pre-allocate the maximum number of cells for the G1Add operations,
+/// which means a witness with more operations than `max_g1_add_ops` is invalid.
+#[derive(Clone, Debug, Default)]
+pub struct Bls12381Circuit<F: Field> {
+    /// Maximum number of G1Add operations supported in one instance of the BLS12-381 Circuit.
+    pub max_g1_add_ops: usize,
+
+    /// G1Add operations provided as witness data to the BLS12-381 circuit.
+    pub g1_add_ops: Vec<Bls12G1AddOp>,
+
+    _marker: PhantomData<F>,
+}
+
+impl<F: Field> Bls12381Circuit<F> {
+    /// Return the minimum number of rows required to prove an input of a
+    /// particular size.
+    pub fn min_num_rows() -> usize {
+        // Bls12381Circuit can't determine usable rows independently, see EccCircuit.
+        let max_blinding_factor = Self::unusable_rows() - 1;
+
+        // same formula as halo2-lib's FlexGate
+        (1 << LOG_TOTAL_NUM_ROWS) - (max_blinding_factor + 3)
+    }
+
+    /// Assign witness from the BLS12-381 ops to the circuit.
+    pub(crate) fn assign(
+        &self,
+        layouter: &mut impl Layouter<F>,
+        config: &<Self as SubCircuit<F>>::Config,
+        challenges: &Challenges<Value<F>>,
+    ) -> Result<(), Error> {
+        if self.g1_add_ops.len() > self.max_g1_add_ops {
+            error!(
+                "g1 add ops = {} > max g1 add ops = {}",
+                self.g1_add_ops.len(),
+                self.max_g1_add_ops,
+            );
+            return Err(Error::Synthesis);
+        }
+
+        // keccak powers of randomness.
+        let keccak_powers = std::iter::successors(Some(Value::known(F::one())), |coeff| {
+            Some(challenges.keccak_input() * coeff)
+        })
+        .take(BLS12_G1_ADD_INPUT_LEN)
+        .map(|x| QuantumCell::Witness(x))
+        .collect_vec();
+
+        let powers_of_256 =
+            std::iter::successors(Some(F::one()), |coeff| Some(F::from(256) * coeff))
+                .take(N_BYTES_LIMB)
+                .map(|x| QuantumCell::Constant(x))
+                .collect_vec();
+
+        let ecc_chip = EccChip::<F, FpConfig<F, Fq>>::construct(config.fp_config.clone());
+
+        let mut first_pass = SKIP_FIRST_PASS;
+
+        let g1_adds_assigned = layouter.assign_region(
+            || "bls12-381 circuit",
+            |region| {
+                if first_pass {
+                    first_pass = false;
+                    return Ok(vec![]);
+                }
+
+                let mut ctx = config.fp_config.new_context(region);
+
+                // P + Q == R
+                let g1_adds_decomposed = self
+                    .g1_add_ops
+                    .iter()
+                    .chain(std::iter::repeat(&Bls12G1AddOp::default()))
+                    .take(self.max_g1_add_ops)
+                    .map(|op| self.decompose_g1_add_op(&mut ctx, &ecc_chip, &powers_of_256, op))
+                    .collect_vec();
+
+                #[cfg(not(feature = "onephase"))]
+                {
+                    // finalize after first phase.
+                    config.fp_config.finalize(&mut ctx);
+                    ctx.next_phase();
+                }
+
+                let g1_adds_assigned = g1_adds_decomposed
+                    .iter()
+                    .map(|decomposed_op| {
+                        self.assign_g1_add(&mut ctx, decomposed_op, &ecc_chip, &keccak_powers)
+                    })
+                    .collect_vec();
+
+                // Finalize the Fp config always at the end of assignment.
+                let lookup_cells = config.fp_config.finalize(&mut ctx);
+                log::info!("total number of lookup cells: {}", lookup_cells);
+                ctx.print_stats(&["Bls12381Circuit: FpConfig context"]);
+
+                Ok(g1_adds_assigned)
+            },
+        )?;
+
+        layouter.assign_region(
+            || "expose bls12-381 table",
+            |mut region| {
+                for (idx, g1_add_assigned) in g1_adds_assigned.iter().enumerate() {
+                    region.assign_fixed(
+                        || "assign bls12_381_table op_type",
+                        config.bls12_381_table.op_type,
+                        idx,
+                        || Value::known(F::from(u64::from(PrecompileCalls::Bls12G1Add))),
+                    )?;
+                    g1_add_assigned.is_valid.copy_advice(
+                        &mut region,
+                        config.bls12_381_table.is_valid,
+                        idx,
+                    );
+                    g1_add_assigned.input_rlc.copy_advice(
+                        &mut region,
+                        config.bls12_381_table.input_rlc,
+                        idx,
+                    );
+                    g1_add_assigned.output_rlc.copy_advice(
+                        &mut region,
+                        config.bls12_381_table.output_rlc,
+                        idx,
+                    );
+                }
+
+                Ok(())
+            },
+        )?;
+
+        Ok(())
+    }
+
+    /// Decomposes a G1Add operation into the cells of its input and output bytes, and checks
+    /// that the output is P + Q if the input is valid, or the point at infinity otherwise.
+    fn decompose_g1_add_op(
+        &self,
+        ctx: &mut Context<F>,
+        ecc_chip: &EccChip<F, FpConfig<F, Fq>>,
+        powers_of_256: &[QuantumCell<F>],
+        op: &Bls12G1AddOp,
+    ) -> G1AddDecomposed<F> {
+        let gate = ecc_chip.field_chip().range().gate();
+
+        let [px, py, qx, qy] = op
+            .input_coordinates()
+            .map(|bytes| self.decompose_fq(ctx, ecc_chip, bytes, powers_of_256));
+        let p_is_on_curve_or_infinity = self.is_on_curveg1_or_infinity(ctx, ecc_chip, &px, &py);
+        let q_is_on_curve_or_infinity = self.is_on_curveg1_or_infinity(ctx, ecc_chip, &qx, &qy);
+
+        // EIP-2537 does not require a subgroup check for G1Add.
+        let inputs_valid = gate.and_many(
+            ctx,
+            vec![
+                QuantumCell::Existing(px.is_valid),
+                QuantumCell::Existing(py.is_valid),
+                QuantumCell::Existing(p_is_on_curve_or_infinity),
+                QuantumCell::Existing(qx.is_valid),
+                QuantumCell::Existing(qy.is_valid),
+                QuantumCell::Existing(q_is_on_curve_or_infinity),
+            ],
+        );
+        let inputs_invalid = gate.not(ctx, QuantumCell::Existing(inputs_valid));
+
+        // The output must be canonically encoded: zero padding and both co-ordinates < p.
+        let [rx, ry] = op
+            .output_coordinates()
+            .map(|bytes| self.decompose_fq(ctx, ecc_chip, &bytes, powers_of_256));
+        gate.assert_is_const(ctx, &rx.is_valid, F::one());
+        gate.assert_is_const(ctx, &ry.is_valid, F::one());
+        let r_is_infinity = gate.and(
+            ctx,
+            QuantumCell::Existing(rx.is_zero),
+            QuantumCell::Existing(ry.is_zero),
+        );
+        // invalid inputs imply R == (0, 0), so that the output bytes RLC is zero.
+        let invalid_implies_infinity = gate.or(
+            ctx,
+            QuantumCell::Existing(inputs_invalid),
+            QuantumCell::Existing(r_is_infinity),
+        );
+        gate.assert_equal(
+            ctx,
+            QuantumCell::Existing(invalid_implies_infinity),
+            QuantumCell::Existing(r_is_infinity),
+        );
+
+        let point_p = G1Decomposed {
+            cells: [px.cells, py.cells].concat(),
+            ec_point: EcPoint::construct(px.crt_int, py.crt_int),
+        };
+        let point_q = G1Decomposed {
+            cells: [qx.cells, qy.cells].concat(),
+            ec_point: EcPoint::construct(qx.crt_int, qy.crt_int),
+        };
+        let point_r = G1Decomposed {
+            cells: [rx.cells, ry.cells].concat(),
+            ec_point: EcPoint::construct(rx.crt_int, ry.crt_int),
+        };
+
+        // Same approach as for EcAdd in the ECC circuit, to check P + Q == R, we check:
+        // r + P + Q - R == r
+        // where r is a random point on the curve, and invalid inputs are treated as (0, 0).
+        let rand_point = ecc_chip.load_random_point::<G1Affine>(ctx);
+        let point_p_is_zero = gate.or_and(
+            ctx,
+            QuantumCell::Existing(inputs_invalid),
+            QuantumCell::Existing(px.is_zero),
+            QuantumCell::Existing(py.is_zero),
+        );
+        let point_q_is_zero = gate.or_and(
+            ctx,
+            QuantumCell::Existing(inputs_invalid),
+            QuantumCell::Existing(qx.is_zero),
+            QuantumCell::Existing(qy.is_zero),
+        );
+
+        // sum1 = if P == (0, 0) then r else r + P
+        let sum1 = ecc_chip.add_unequal(ctx, &rand_point, &point_p.ec_point, true);
+        let sum1 = ecc_chip.select(ctx, &rand_point, &sum1, &point_p_is_zero);
+
+        // sum2 = if Q == (0, 0) then sum1 else sum1 + Q
+        let sum2 = ecc_chip.add_unequal(ctx, &sum1, &point_q.ec_point, true);
+        let sum2 = ecc_chip.select(ctx, &sum1, &sum2, &point_q_is_zero);
+
+        // sum3 = if R == (0, 0) then sum2 else sum2 - R
+        let sum3 = ecc_chip.sub_unequal(ctx, &sum2, &point_r.ec_point, true);
+        let sum3 = ecc_chip.select(ctx, &sum2, &sum3, &r_is_infinity);
+
+        ecc_chip.assert_equal(ctx, &rand_point, &sum3);
+
+        G1AddDecomposed {
+            is_valid: inputs_valid,
+            input_cells: [point_p.cells, point_q.cells].concat(),
+            output_cells: point_r.cells,
+        }
+    }
+
+    /// Handles Phase2 for G1Add operation and returns the RLC'd input and output bytes.
+    fn assign_g1_add(
+        &self,
+        ctx: &mut Context<F>,
+        g1_add_decomposed: &G1AddDecomposed<F>,
+        ecc_chip: &EccChip<F, FpConfig<F, Fq>>,
+        keccak_powers: &[QuantumCell<F>],
+    ) -> G1AddAssigned<F> {
+        let gate = ecc_chip.field_chip().range().gate();
+        let mut rlc = |cells: &[AssignedValue<F>]| {
+            gate.inner_product(
+                ctx,
+                cells.iter().rev().map(|&cell| QuantumCell::Existing(cell)),
+                keccak_powers.iter().cloned(),
+            )
+        };
+        G1AddAssigned {
+            is_valid: g1_add_decomposed.is_valid,
+            input_rlc: rlc(&g1_add_decomposed.input_cells),
+            output_rlc: rlc(&g1_add_decomposed.output_cells),
+        }
+    }
+
+    /// Decompose a 64-bytes big-endian EVM encoding of a field element, supposed to be
+    /// bls12_381::Fq, into range checked byte cells and return its CRT integer representation. We
+    /// also return assigned values to indicate whether the encoding is valid, i.e. the 16 bytes
+    /// padding is zero and the value is within Fq::MODULUS, and whether or not it is zero.
+    fn decompose_fq(
+        &self,
+        ctx: &mut Context<F>,
+        ecc_chip: &EccChip<F, FpConfig<F, Fq>>,
+        bytes: &[u8],
+        powers_of_256: &[QuantumCell<F>],
+    ) -> FqDecomposed<F> {
+        debug_assert_eq!(bytes.len(), BLS12_FP_LEN);
+        let range = ecc_chip.field_chip().range();
+        let gate = range.gate();
+
+        let cells = gate.assign_witnesses(
+            ctx,
+            bytes
+                .iter()
+                .map(|&byte| Value::known(F::from(u64::from(byte))))
+                .collect_vec(),
+        );
+        for cell in cells.iter() {
+            range.range_check(ctx, cell, 8);
+        }
+        let (padding_cells, value_cells) = cells.split_at(BLS12_FP_LEN - N_BYTES_FQ);
+
+        // bytes are range checked, so their sum is zero iff they are all zero.
+        let padding_sum = gate.sum(
+            ctx,
+            padding_cells
+                .iter()
+                .map(|&cell| QuantumCell::Existing(cell)),
+        );
+        let padding_is_zero = gate.is_zero(ctx, &padding_sum);
+
+        // limbs in LE order, each from 13 bytes (the last one from the remaining 9 bytes).
+        let value_cells_le = value_cells.iter().rev().copied().collect_vec();
+        let limbs = value_cells_le
+            .chunks(N_BYTES_LIMB)
+            .map(|limb_bytes| {
+                gate.inner_product(
+                    ctx,
+                    limb_bytes.iter().map(|&cell| QuantumCell::Existing(cell)),
+                    powers_of_256[0..limb_bytes.len()].to_vec(),
+                )
+            })
+            .collect_vec();
+        let native_value = OverflowInteger::evaluate(
+            gate,
+            ctx,
+            &limbs,
+            ecc_chip.field_chip.limb_bases.iter().cloned(),
+        );
+        let overflow_int = OverflowInteger::construct(limbs, ecc_chip.field_chip.limb_bits);
+        let value = Value::known(num_bigint::BigInt::from(
+            num_bigint::BigUint::from_bytes_be(&bytes[BLS12_FP_LEN - N_BYTES_FQ..]),
+        ));
+        let crt_int = CRTInteger::construct(overflow_int, native_value, value);
+
+        let is_lt_mod = ecc_chip.field_chip().is_less_than_p(ctx, &crt_int);
+        let is_zero = big_is_zero::positive(gate, ctx, &crt_int.truncation);
+        let is_valid = gate.and(
+            ctx,
+            QuantumCell::Existing(padding_is_zero),
+            QuantumCell::Existing(is_lt_mod),
+        );
+
+        FqDecomposed {
+            crt_int,
+            cells,
+            is_valid,
+            is_zero,
+        }
+    }
+
+    /// Return an assigned value that indicates whether the given point is on curve G1, i.e.
+    /// y^2 = x^3 + 4, or is the point at infinity (0, 0).
+    fn is_on_curveg1_or_infinity(
+        &self,
+        ctx: &mut Context<F>,
+        ecc_chip: &EccChip<F, FpConfig<F, Fq>>,
+        x: &FqDecomposed<F>,
+        y: &FqDecomposed<F>,
+    ) -> AssignedValue<F> {
+        let lhs = ecc_chip
+            .field_chip()
+            .mul_no_carry(ctx, &y.crt_int, &y.crt_int);
+        let mut rhs = ecc_chip.field_chip().mul(ctx, &x.crt_int, &x.crt_int);
+        rhs = ecc_chip.field_chip().mul_no_carry(ctx, &rhs, &x.crt_int);
+
+        let b = FpConfig::<F, Fq>::fe_to_constant(G1Affine::b());
+        rhs = ecc_chip.field_chip().add_constant_no_carry(ctx, &rhs, b);
+        let mut diff = ecc_chip.field_chip().sub_no_carry(ctx, &lhs, &rhs);
+        diff = ecc_chip.field_chip().carry_mod(ctx, &diff);
+
+        let is_on_curve = ecc_chip.field_chip().is_zero(ctx, &diff);
+
+        ecc_chip.field_chip().range().gate().or_and(
+            ctx,
+            QuantumCell::Existing(is_on_curve),
+            QuantumCell::Existing(x.is_zero),
+            QuantumCell::Existing(y.is_zero),
+        )
+    }
+}
+
+impl<F: Field> SubCircuit<F> for Bls12381Circuit<F> {
+    type Config = Bls12381CircuitConfig<F>;
+
+    fn new_from_block(block: &Block) -> Self {
+        Self {
+            max_g1_add_ops: block.circuits_params.max_ec_ops.bls12_g1_add,
+            g1_add_ops: block.get_bls12_g1_add_ops(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns number of unusable rows of the SubCircuit, which should be
+    /// `meta.blinding_factors() + 1`.
+    fn unusable_rows() -> usize {
+        [
+            KeccakCircuit::<F>::unusable_rows(),
+            EvmCircuit::<F>::unusable_rows(),
+            // may include additional subcircuits here
+        ]
+        .into_iter()
+        .max()
+        .unwrap()
+    }
+
+    fn synthesize_sub(
+        &self,
+        config: &Self::Config,
+        challenges: &Challenges<Value<F>>,
+        layouter: &mut impl Layouter<F>,
+    ) -> Result<(), Error> {
+        config.fp_config.range.load_lookup_table(layouter)?;
+        self.assign(layouter, config, challenges)?;
+        Ok(())
+    }
+
+    fn min_num_rows_block(block: &Block) -> (usize, usize) {
+        let row_num = if block.circuits_params.max_vertical_circuit_rows == 0 {
+            Self::min_num_rows()
+        } else {
+            block.circuits_params.max_vertical_circuit_rows
+        };
+
+        let g1_adds = block.get_bls12_g1_add_ops().len();
+        let max_g1_adds = block.circuits_params.max_ec_ops.bls12_g1_add;
+        log::debug!("bls12-381 circuit row usage: g1add {g1_adds}/{max_g1_adds}");
+
+        // As for the ECC circuit, min_row_num represents a percentage of total-used capacity.
+        let min_row_num = if g1_adds == 0 {
+            0
+        } else {
+            (row_num / max_g1_adds.max(1)) * g1_adds
+        };
+
+        (min_row_num, row_num)
+    }
+}
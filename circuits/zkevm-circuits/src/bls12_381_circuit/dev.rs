@@ -0,0 +1,46 @@
+use crate::util::Field;
+use halo2_proofs::{
+    circuit::{Layouter, SimpleFloorPlanner},
+    plonk::{Challenge, Circuit, ConstraintSystem, Error},
+};
+
+use crate::{
+    table::Bls12381Table,
+    util::{Challenges, SubCircuit, SubCircuitConfig},
+};
+
+use super::{Bls12381Circuit, Bls12381CircuitConfig, Bls12381CircuitConfigArgs};
+
+impl<F: Field> Circuit<F> for Bls12381Circuit<F> {
+    type Config = (Bls12381CircuitConfig<F>, Challenges<Challenge>);
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self::default()
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        let bls12_381_table = Bls12381Table::construct(meta);
+        let challenges = Challenges::construct(meta);
+        let challenge_exprs = challenges.exprs(meta);
+        (
+            Bls12381CircuitConfig::new(
+                meta,
+                Bls12381CircuitConfigArgs {
+                    bls12_381_table,
+                    challenges: challenge_exprs,
+                },
+            ),
+            challenges,
+        )
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let challenge_values = config.1.values(&layouter);
+        self.synthesize_sub(&config.0, &challenge_values, &mut layouter)
+    }
+}
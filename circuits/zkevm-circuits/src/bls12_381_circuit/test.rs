@@ -0,0 +1,120 @@
+use std::{marker::PhantomData, sync::LazyLock};
+
+use crate::util::Field;
+use bus_mapping::{
+    circuit_input_builder::Bls12G1AddOp,
+    precompile::{BLS12_FP_LEN, BLS12_G1_ADD_INPUT_LEN},
+};
+use halo2_proofs::{dev::MockProver, halo2curves::bn256::Fr};
+
+use crate::bls12_381_circuit::{util::LOG_TOTAL_NUM_ROWS, Bls12381Circuit};
+
+fn run<F: Field, const MUST_FAIL: bool>(
+    k: u32,
+    max_g1_add_ops: usize,
+    g1_add_ops: Vec<Bls12G1AddOp>,
+) {
+    let circuit = Bls12381Circuit::<F> {
+        max_g1_add_ops,
+        g1_add_ops,
+        _marker: PhantomData,
+    };
+
+    let prover = match MockProver::run(k, &circuit, vec![]) {
+        Ok(prover) => prover,
+        Err(e) => panic!("{e:#?}"),
+    };
+
+    if MUST_FAIL {
+        if let Ok(()) = prover.verify() {
+            panic!("expected failure, found success");
+        }
+    } else if let Err(e) = prover.verify() {
+        panic!("{e:#?}");
+    }
+}
+
+/// EIP-2537 encoding of a field element given as big-endian hex, left-padded to 64 bytes.
+fn fp(hex: &str) -> Vec<u8> {
+    let bytes = hex::decode(hex).expect("valid hex");
+    let mut encoded = vec![0u8; BLS12_FP_LEN - bytes.len()];
+    encoded.extend(bytes);
+    encoded
+}
+
+/// Build a G1Add op from the EIP-2537 encodings of p_x, p_y, q_x and q_y.
+fn g1_add_op(coordinates: [Vec<u8>; 4]) -> Bls12G1AddOp {
+    let input = coordinates.concat();
+    assert_eq!(input.len(), BLS12_G1_ADD_INPUT_LEN);
+    Bls12G1AddOp::new_from_bytes(&input)
+}
+
+const G_X: &str = "17f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb";
+const G_Y: &str = "08b3f481e3aaa0f1a09e30ed741d8ae4fcf5e095d5d00af600db18cb2c04b3edd03cc744a2888ae40caa232946c5e7e1";
+const NEG_G_Y: &str = "114d1d6855d545a8aa7d76c8cf2e21f267816aef1db507c96655b9d5caac42364e6f38ba0ecb751bad54dcd6b939c2ca";
+const P_PLUS_ONE: &str = "1a0111ea397fe69a4b1ba7b6434bacd764774b84f38512bf6730d2a0f6b0f6241eabfffeb153ffffb9feffffffffaaac";
+
+static G1_ADD_OPS: LazyLock<Vec<Bls12G1AddOp>> = LazyLock::new(|| {
+    let zero = || vec![0u8; BLS12_FP_LEN];
+    vec![
+        // 1. valid: G + infinity
+        g1_add_op([fp(G_X), fp(G_Y), zero(), zero()]),
+        // 2. valid: G + G
+        g1_add_op([fp(G_X), fp(G_Y), fp(G_X), fp(G_Y)]),
+        // 3. valid: G + (-G)
+        g1_add_op([fp(G_X), fp(G_Y), fp(G_X), fp(NEG_G_Y)]),
+        // 4. valid: all zeroes
+        g1_add_op([zero(), zero(), zero(), zero()]),
+        // 5. invalid: P not on curve
+        g1_add_op([fp("01"), fp("02"), fp(G_X), fp(G_Y)]),
+        // 6. invalid: P_x > Fq::MODULUS
+        g1_add_op([fp(P_PLUS_ONE), fp(G_Y), zero(), zero()]),
+        // 7. invalid: non-zero padding of Q_y
+        g1_add_op([fp(G_X), fp(G_Y), zero(), {
+            let mut q_y = zero();
+            q_y[0] = 1;
+            q_y
+        }]),
+    ]
+});
+
+#[test]
+fn test_bls12_381_circuit_valid_invalid() {
+    assert!(G1_ADD_OPS[0..4].iter().all(|op| op.is_valid()));
+    assert!(G1_ADD_OPS[4..].iter().all(|op| !op.is_valid()));
+
+    run::<Fr, false>(LOG_TOTAL_NUM_ROWS, 10, G1_ADD_OPS.clone());
+}
+
+#[test]
+fn test_bls12_381_circuit_padding_only() {
+    run::<Fr, false>(LOG_TOTAL_NUM_ROWS, 2, vec![]);
+}
+
+#[test]
+fn test_bls12_381_circuit_negative() {
+    // R != P + Q
+    let mut op = G1_ADD_OPS[1].clone();
+    op.output = G1_ADD_OPS[0].output.clone();
+    run::<Fr, true>(LOG_TOTAL_NUM_ROWS, 1, vec![op]);
+
+    // R != (0, 0) for invalid inputs
+    let mut op = G1_ADD_OPS[4].clone();
+    op.output = G1_ADD_OPS[0].output.clone();
+    run::<Fr, true>(LOG_TOTAL_NUM_ROWS, 1, vec![op]);
+
+    // R_x > Fq::MODULUS
+    let mut op = G1_ADD_OPS[0].clone();
+    op.output = [fp(P_PLUS_ONE), fp(G_Y)].concat();
+    run::<Fr, true>(LOG_TOTAL_NUM_ROWS, 1, vec![op]);
+}
+
+#[test]
+fn test_bls12_381_circuit_overflow() {
+    let circuit = Bls12381Circuit::<Fr> {
+        max_g1_add_ops: 1,
+        g1_add_ops: G1_ADD_OPS[0..2].to_vec(),
+        _marker: PhantomData,
+    };
+    assert!(MockProver::run(LOG_TOTAL_NUM_ROWS, &circuit, vec![]).is_err());
+}
@@ -0,0 +1,51 @@
+use crate::util::Field;
+use halo2_base::AssignedValue;
+use halo2_ecc::{bigint::CRTInteger, ecc::EcPoint};
+
+// Total number of rows allowable for BLS12-381 circuit
+pub const LOG_TOTAL_NUM_ROWS: u32 = 20;
+
+/// Number of bytes of a BLS12-381 base field element.
+pub(super) const N_BYTES_FQ: usize = 48;
+
+/// Number of bytes per limb of the CRT representation of a BLS12-381 base field element, with
+/// 104-bits limbs.
+pub(super) const N_BYTES_LIMB: usize = 13;
+
+/// Decomposed state of a field element in its EIP-2537 encoding.
+pub(super) struct FqDecomposed<F: Field> {
+    /// CRT representation of the 48 bytes value.
+    pub crt_int: CRTInteger<F>,
+    /// Cells for the 64 bytes of the encoding in BE format, including the zero padding.
+    pub cells: Vec<AssignedValue<F>>,
+    /// Whether the padding is zero and the value is less than Fq::MODULUS.
+    pub is_valid: AssignedValue<F>,
+    /// Whether the value is zero.
+    pub is_zero: AssignedValue<F>,
+}
+
+/// Decomposed state of a G1 curve point.
+pub(super) struct G1Decomposed<F: Field> {
+    /// EcPoint on G1.
+    pub ec_point: EcPoint<F, CRTInteger<F>>,
+    /// Cells for the encoding of the x and y co-ordinates, i.e. 128 bytes in BE format.
+    pub cells: Vec<AssignedValue<F>>,
+}
+
+/// State of G1Add operation post first phase.
+pub(super) struct G1AddDecomposed<F: Field> {
+    pub is_valid: AssignedValue<F>,
+    /// Cells for the 256 input bytes in BE format.
+    pub input_cells: Vec<AssignedValue<F>>,
+    /// Cells for the 128 output bytes in BE format.
+    pub output_cells: Vec<AssignedValue<F>>,
+}
+
+/// State of G1Add operation post second phase.
+pub(super) struct G1AddAssigned<F: Field> {
+    pub is_valid: AssignedValue<F>,
+    /// RLC of input bytes that will be copied to the BLS12-381 table.
+    pub input_rlc: AssignedValue<F>,
+    /// RLC of output bytes that will be copied to the BLS12-381 table.
+    pub output_rlc: AssignedValue<F>,
+}
@@ -0,0 +1,517 @@
+//! The ciphertext circuit is a circuit for the encryptor to prove an OABE ciphertext is
+//! well-formed, that is C_i, D_i and C' all come from one secret s shared under the access policy
+//! (M, ρ) of the ciphertext:
+//!
+//! - C' = g^s
+//! - C_i = (g^a)^λ_i · H(ρ(i))^(-r_i), with the shares λ_i = M_i · (s, y_2, ..., y_n)
+//! - D_i = g^r_i
+//!
+//! C = m · e(g, g)^(αs) is consistent with any message m, so it is not part of the relation.
+//!
+//! The single instance is Poseidon(Poseidon(ciphertext, policy), params), hashing the poseidon
+//! commitments to the ciphertext, to the policy and to g^a of the public parameters like the
+//! public input of the pairing circuit. The ciphertext commitment is the one the pairing circuit
+//! computes over the same points, which lets the contract check both proofs are about the same
+//! ciphertext. g^a is a witness bound by the params commitment the contract is deployed with, so
+//! the verifying key does not depend on the public parameters.
+use std::{iter, marker::PhantomData};
+
+use halo2_base::{
+    gates::RangeInstructions, utils::modulus, AssignedValue, Context, SKIP_FIRST_PASS,
+};
+use halo2_ecc::{
+    bigint::CRTInteger,
+    ecc::{fixed_base, EcPoint, EccChip},
+    fields::{
+        fp::{FpConfig, FpStrategy},
+        FieldChip,
+    },
+};
+use halo2_proofs::{
+    circuit::{Layouter, Value},
+    halo2curves::{
+        bn256::{Fq, Fr, G1Affine},
+        group::prime::PrimeCurveAffine,
+    },
+    plonk::{Column, ConstraintSystem, Error, Expression, Instance},
+};
+use itertools::Itertools;
+use snark_verifier_sdk::CircuitExt;
+
+use crate::{
+    evm_circuit::EvmCircuit,
+    keccak_circuit::KeccakCircuit,
+    pairing_circuit::{
+        commitment::{
+            assign_messages, assign_pi_messages, g1_words, poseidon_commitment,
+            poseidon_pi_commitment, word_limbs, PoseidonCommitmentConfig,
+        },
+        LIMB_BITS, NUM_LIMBS,
+    },
+    util::{Challenges, Field, SubCircuit, SubCircuitConfig},
+    witness::Block,
+};
+
+mod dev;
+mod policy;
+#[cfg(test)]
+mod test;
+
+pub use policy::LsssPolicy;
+
+/// Window bits of the scalar multiplications by a fixed base.
+const FIXED_WINDOW_BITS: usize = 4;
+/// Window bits of the scalar multiplications by a variable base.
+const VAR_WINDOW_BITS: usize = 4;
+
+/// Arguments accepted to configure the CiphertextCircuitConfig.
+#[derive(Clone, Debug)]
+pub struct CiphertextCircuitConfigArgs<F: Field> {
+    /// zkEVM challenge API.
+    pub challenges: Challenges<Expression<F>>,
+}
+
+/// Config for the ciphertext circuit.
+#[derive(Clone, Debug)]
+pub struct CiphertextCircuitConfig<F: Field> {
+    /// Field config for halo2_proofs::halo2curves::bn256::Fq.
+    fp_config: FpConfig<F, Fq>,
+    /// Number of limbs to represent Fp.
+    num_limbs: usize,
+    /// Number of bits per limb.
+    limb_bits: usize,
+
+    /// Poseidon table lookups of the ciphertext, policy and params commitments.
+    commitment: PoseidonCommitmentConfig<F>,
+    instance: Column<Instance>,
+
+    _marker: PhantomData<F>,
+}
+
+impl<F: Field> SubCircuitConfig<F> for CiphertextCircuitConfig<F> {
+    type ConfigArgs = CiphertextCircuitConfigArgs<F>;
+
+    fn new(
+        meta: &mut ConstraintSystem<F>,
+        Self::ConfigArgs { challenges: _ }: Self::ConfigArgs,
+    ) -> Self {
+        let num_limbs = NUM_LIMBS;
+        let limb_bits = LIMB_BITS;
+        let num_advice = [35];
+
+        let fp_config = FpConfig::configure(
+            meta,
+            FpStrategy::Simple,
+            &num_advice,
+            &[17], // num lookup advice
+            1,     // num fixed
+            13,    // lookup bits
+            limb_bits,
+            num_limbs,
+            modulus::<Fq>(),
+            0,
+            19, // k
+        );
+
+        let hash = meta.advice_column();
+        let instance = meta.instance_column();
+
+        meta.enable_equality(hash);
+        meta.enable_equality(instance);
+
+        let commitment = PoseidonCommitmentConfig::configure(meta, hash);
+
+        Self {
+            fp_config,
+            num_limbs,
+            limb_bits,
+            commitment,
+            instance,
+            _marker: PhantomData,
+        }
+    }
+}
+
+/// Ciphertext Circuit proving an OABE ciphertext is well-formed under its access policy.
+#[derive(Clone, Debug, Default)]
+pub struct CiphertextCircuit<F: Field> {
+    /// g^a of the public parameters, a witness bound by the params commitment.
+    pub g_a: G1Affine,
+    /// Access policy the ciphertext is encrypted under.
+    pub policy: LsssPolicy,
+    /// C_i of each row of the policy.
+    pub cs: Vec<G1Affine>,
+    /// D_i of each row of the policy.
+    pub ds: Vec<G1Affine>,
+    /// C'
+    pub c_p: G1Affine,
+    /// v = (s, y_2, ..., y_n), the secret s and the randomness it is shared with.
+    pub v: Vec<Fr>,
+    /// r_i of each row of the policy.
+    pub rs: Vec<Fr>,
+    /// Marker
+    pub _marker: PhantomData<F>,
+}
+
+impl<F: Field> CiphertextCircuit<F> {
+    /// Encrypt under `policy`, sharing `v[0]` with `v` and blinding row i with `rs[i]`, and
+    /// return the circuit proving the ciphertext is well-formed.
+    pub fn encrypt(
+        g_a: G1Affine,
+        policy: LsssPolicy,
+        v: Vec<Fr>,
+        rs: Vec<Fr>,
+    ) -> Result<Self, Error> {
+        Self::check_policy(&policy, &v, &rs)?;
+        let g = G1Affine::generator();
+        let (cs, ds) = policy
+            .shares(&v)
+            .iter()
+            .zip_eq(&policy.attributes)
+            .zip_eq(&rs)
+            .map(|((lambda, h), r)| ((g_a * lambda - h * r).into(), (g * r).into()))
+            .unzip();
+
+        Ok(Self {
+            g_a,
+            cs,
+            ds,
+            c_p: (g * v[0]).into(),
+            policy,
+            v,
+            rs,
+            _marker: PhantomData,
+        })
+    }
+
+    /// Check that the policy has at least one row and column, with an H(ρ(i)) and an r_i for
+    /// each row and an entry of v for each column.
+    fn check_policy(policy: &LsssPolicy, v: &[Fr], rs: &[Fr]) -> Result<(), Error> {
+        let (num_rows, num_columns) = (policy.num_rows(), policy.num_columns());
+        if num_rows == 0 || num_columns == 0 {
+            log::error!("policy has {num_rows} rows and {num_columns} columns");
+            return Err(Error::Synthesis);
+        }
+        if policy.matrix.iter().any(|row| row.len() != num_columns)
+            || policy.attributes.len() != num_rows
+            || rs.len() != num_rows
+        {
+            log::error!(
+                "policy rows are not all of {num_columns} entries with an attribute and r_i"
+            );
+            return Err(Error::Synthesis);
+        }
+        if v.len() != num_columns {
+            log::error!("v has {} entries for {num_columns} columns", v.len());
+            return Err(Error::Synthesis);
+        }
+        Ok(())
+    }
+
+    /// Check that the ciphertext is well formed: its policy, v and r_i as in
+    /// [`Self::check_policy`], and a C_i and D_i for each row.
+    fn check(&self) -> Result<(), Error> {
+        Self::check_policy(&self.policy, &self.v, &self.rs)?;
+        let num_rows = self.policy.num_rows();
+        if self.cs.len() != num_rows || self.ds.len() != num_rows {
+            log::error!(
+                "{} C_i and {} D_i for {num_rows} rows",
+                self.cs.len(),
+                self.ds.len()
+            );
+            return Err(Error::Synthesis);
+        }
+        Ok(())
+    }
+
+    /// Return the minimum number of rows required to prove an input of a
+    /// particular size.
+    pub fn min_num_rows() -> usize {
+        let max_blinding_factor = Self::unusable_rows() - 1;
+
+        // same formula as halo2-lib's FlexGate
+        (1 << 20) - (max_blinding_factor + 3)
+    }
+
+    /// Field elements of the ciphertext committed to: the CRT limbs of C_i and D_i row by row,
+    /// followed by those of C'. This is the ciphertext message of the pairing circuit.
+    pub fn ciphertext_message(&self) -> Vec<Fr> {
+        // a C_i without its D_i is rejected by `Self::check` before assignment
+        self.cs
+            .iter()
+            .zip(&self.ds)
+            .flat_map(|(c, d)| [c, d])
+            .chain(iter::once(&self.c_p))
+            .flat_map(g1_words)
+            .flat_map(word_limbs)
+            .collect()
+    }
+
+    /// Commitment to the ciphertext, which the contract stores when the task is created.
+    pub fn ciphertext_commitment(&self) -> Fr {
+        poseidon_commitment(&self.ciphertext_message())
+    }
+
+    /// Field elements of the public parameters committed to: the CRT limbs of g^a.
+    pub fn params_message(&self) -> Vec<Fr> {
+        g1_words(&self.g_a)
+            .into_iter()
+            .flat_map(word_limbs)
+            .collect()
+    }
+
+    /// Commitment to the public parameters, which the contract is deployed with.
+    pub fn params_commitment(&self) -> Fr {
+        poseidon_commitment(&self.params_message())
+    }
+
+    /// Assign the ciphertext, the policy and their commitments to the circuit.
+    pub(crate) fn assign(
+        &self,
+        layouter: &mut impl Layouter<F>,
+        config: &<Self as SubCircuit<F>>::Config,
+    ) -> Result<(), Error> {
+        self.check()?;
+        let ecc_chip = EccChip::<F, FpConfig<F, Fq>>::construct(config.fp_config.clone());
+        let fr_chip = FpConfig::<F, Fr>::construct(
+            config.fp_config.range.clone(),
+            config.limb_bits,
+            config.num_limbs,
+            modulus::<Fr>(),
+        );
+        let g = G1Affine::generator();
+
+        let mut first_pass = SKIP_FIRST_PASS;
+
+        let pi_commitment = layouter.assign_region(
+            || "ciphertext circuit",
+            |region| {
+                if first_pass {
+                    first_pass = false;
+                    return Ok(None);
+                }
+
+                let mut ctx = config.fp_config.new_context(region);
+
+                // v = (s, y_2, ..., y_n), and the policy (M, ρ)
+                let v = self
+                    .v
+                    .iter()
+                    .map(|v| self.load_scalar(&mut ctx, &fr_chip, v))
+                    .collect_vec();
+                let matrix = self
+                    .policy
+                    .matrix
+                    .iter()
+                    .map(|row| {
+                        row.iter()
+                            .map(|m| self.load_scalar(&mut ctx, &fr_chip, m))
+                            .collect_vec()
+                    })
+                    .collect_vec();
+                let attributes = self
+                    .policy
+                    .attributes
+                    .iter()
+                    .map(|h| self.load_point(&mut ctx, &ecc_chip, h))
+                    .collect_vec();
+                // CRT limbs of the policy, in the order of `LsssPolicy::message`
+                let policy_limbs = matrix
+                    .iter()
+                    .flatten()
+                    .flat_map(|m| m.truncation.limbs.iter().cloned())
+                    .chain(attributes.iter().flat_map(point_limbs))
+                    .collect_vec();
+
+                let g_a = self.load_point(&mut ctx, &ecc_chip, &self.g_a);
+
+                // C' = g^s
+                let g_s = fixed_base::scalar_multiply::<F, _, _>(
+                    ecc_chip.field_chip(),
+                    &mut ctx,
+                    &g,
+                    &v[0].truncation.limbs,
+                    fr_chip.limb_bits,
+                    FIXED_WINDOW_BITS,
+                );
+                let c_p = self.load_point(&mut ctx, &ecc_chip, &self.c_p);
+                ecc_chip.assert_equal(&mut ctx, &g_s, &c_p);
+
+                // CRT limbs of the ciphertext, in the order of `Self::ciphertext_message`
+                let mut ciphertext_limbs = vec![];
+                for (i, (row, h)) in matrix.iter().zip_eq(&attributes).enumerate() {
+                    // λ_i = M_i · v
+                    let mut lambda = fr_chip.mul(&mut ctx, &row[0], &v[0]);
+                    for (m, v) in row.iter().zip_eq(&v).skip(1) {
+                        let term = fr_chip.mul(&mut ctx, m, v);
+                        lambda = fr_chip.add_no_carry(&mut ctx, &lambda, &term);
+                    }
+                    let lambda = fr_chip.carry_mod(&mut ctx, &lambda);
+                    let r = self.load_scalar(&mut ctx, &fr_chip, &self.rs[i]);
+
+                    // D_i = g^r_i
+                    let g_r = fixed_base::scalar_multiply::<F, _, _>(
+                        ecc_chip.field_chip(),
+                        &mut ctx,
+                        &g,
+                        &r.truncation.limbs,
+                        fr_chip.limb_bits,
+                        FIXED_WINDOW_BITS,
+                    );
+                    let d = self.load_point(&mut ctx, &ecc_chip, &self.ds[i]);
+                    ecc_chip.assert_equal(&mut ctx, &g_r, &d);
+
+                    // C_i = (g^a)^λ_i · H(ρ(i))^(-r_i)
+                    let g_a_lambda = ecc_chip.scalar_mult(
+                        &mut ctx,
+                        &g_a,
+                        &lambda.truncation.limbs,
+                        fr_chip.limb_bits,
+                        VAR_WINDOW_BITS,
+                    );
+                    let h_r = ecc_chip.scalar_mult(
+                        &mut ctx,
+                        h,
+                        &r.truncation.limbs,
+                        fr_chip.limb_bits,
+                        VAR_WINDOW_BITS,
+                    );
+                    let h_r_inv = EcPoint::<F, CRTInteger<F>>::construct(
+                        h_r.x.clone(),
+                        ecc_chip.field_chip().negate(&mut ctx, &h_r.y),
+                    );
+                    let c_expected = ecc_chip.add_unequal(&mut ctx, &g_a_lambda, &h_r_inv, true);
+                    let c = self.load_point(&mut ctx, &ecc_chip, &self.cs[i]);
+                    ecc_chip.assert_equal(&mut ctx, &c_expected, &c);
+
+                    ciphertext_limbs.extend(point_limbs(&c).chain(point_limbs(&d)));
+                }
+                ciphertext_limbs.extend(point_limbs(&c_p));
+
+                let gate = ecc_chip.field_chip().range().gate();
+                let mut messages = assign_messages(
+                    &mut ctx,
+                    gate,
+                    vec![
+                        (ciphertext_limbs, self.ciphertext_message()),
+                        (policy_limbs, self.policy.message()),
+                        (point_limbs(&g_a).collect(), self.params_message()),
+                    ],
+                );
+                let (pi_messages, pi) = assign_pi_messages(
+                    &mut ctx,
+                    gate,
+                    (messages[0].commitment.clone(), self.ciphertext_commitment()),
+                    (messages[1].commitment.clone(), self.policy.commitment()),
+                    (messages[2].commitment.clone(), self.params_commitment()),
+                );
+                messages.extend(pi_messages);
+                let pi_commitment = (messages, pi);
+
+                // Finalize the Fp config always at the end of assignment.
+                let lookup_cells = config.fp_config.finalize(&mut ctx);
+                log::info!("total number of lookup cells: {}", lookup_cells);
+                ctx.print_stats(&["CiphertextCircuit: FpConfig context"]);
+
+                Ok(Some(pi_commitment))
+            },
+        )?;
+
+        if let Some((messages, pi)) = pi_commitment {
+            config.commitment.assign(layouter, &messages)?;
+            layouter.constrain_instance(pi.cell, config.instance, 0)?;
+        }
+        Ok(())
+    }
+
+    /// Load a G1 point, whose coordinates are range checked into CRT limbs.
+    fn load_point(
+        &self,
+        ctx: &mut Context<F>,
+        ecc_chip: &EccChip<F, FpConfig<F, Fq>>,
+        point: &G1Affine,
+    ) -> EcPoint<F, CRTInteger<F>> {
+        ecc_chip.load_private(ctx, (Value::known(point.x), Value::known(point.y)))
+    }
+
+    /// Load a scalar field element, range checked into CRT limbs.
+    fn load_scalar(
+        &self,
+        ctx: &mut Context<F>,
+        fr_chip: &FpConfig<F, Fr>,
+        s: &Fr,
+    ) -> CRTInteger<F> {
+        fr_chip.load_private(ctx, FpConfig::<F, Fr>::fe_to_witness(&Value::known(*s)))
+    }
+}
+
+/// CRT limbs of the coordinates of an assigned point.
+fn point_limbs<F: Field>(
+    point: &EcPoint<F, CRTInteger<F>>,
+) -> impl Iterator<Item = AssignedValue<F>> + '_ {
+    point
+        .x
+        .truncation
+        .limbs
+        .iter()
+        .chain(&point.y.truncation.limbs)
+        .cloned()
+}
+
+impl<F: Field> SubCircuit<F> for CiphertextCircuit<F> {
+    type Config = CiphertextCircuitConfig<F>;
+
+    fn new_from_block(_block: &Block) -> Self {
+        unimplemented!("the ciphertext circuit is built by the encryptor, not from a block")
+    }
+
+    /// Returns number of unusable rows of the SubCircuit, which should be
+    /// `meta.blinding_factors() + 1`.
+    fn unusable_rows() -> usize {
+        [
+            KeccakCircuit::<F>::unusable_rows(),
+            EvmCircuit::<F>::unusable_rows(),
+            // may include additional subcircuits here
+        ]
+        .into_iter()
+        .max()
+        .unwrap()
+    }
+
+    /// Compute the public inputs for this circuit.
+    fn instance(&self) -> Vec<Vec<F>> {
+        // hashed like the public input of the pairing circuit, with the params in place of the
+        // result
+        let pi = poseidon_pi_commitment(
+            self.ciphertext_commitment(),
+            self.policy.commitment(),
+            self.params_commitment(),
+        );
+        vec![vec![F::from(pi)]]
+    }
+
+    fn synthesize_sub(
+        &self,
+        config: &Self::Config,
+        _challenges: &Challenges<Value<F>>,
+        layouter: &mut impl Layouter<F>,
+    ) -> Result<(), Error> {
+        config.fp_config.range.load_lookup_table(layouter)?;
+        self.assign(layouter, config)
+    }
+
+    fn min_num_rows_block(_block: &Block) -> (usize, usize) {
+        (Self::min_num_rows(), Self::min_num_rows())
+    }
+}
+
+impl CircuitExt<Fr> for CiphertextCircuit<Fr> {
+    /// The hash of the ciphertext, policy and params commitments
+    fn num_instance(&self) -> Vec<usize> {
+        vec![1]
+    }
+
+    fn instances(&self) -> Vec<Vec<Fr>> {
+        self.instance()
+    }
+}
@@ -0,0 +1,41 @@
+use crate::util::Field;
+use halo2_proofs::{
+    circuit::{Layouter, SimpleFloorPlanner},
+    plonk::{Challenge, Circuit, ConstraintSystem, Error},
+};
+
+use crate::util::{Challenges, SubCircuit, SubCircuitConfig};
+
+use super::{CiphertextCircuit, CiphertextCircuitConfig, CiphertextCircuitConfigArgs};
+
+impl<F: Field> Circuit<F> for CiphertextCircuit<F> {
+    type Config = (CiphertextCircuitConfig<F>, Challenges<Challenge>);
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self::default()
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        let challenges = Challenges::construct(meta);
+        let challenge_exprs = challenges.exprs(meta);
+        (
+            CiphertextCircuitConfig::new(
+                meta,
+                CiphertextCircuitConfigArgs {
+                    challenges: challenge_exprs,
+                },
+            ),
+            challenges,
+        )
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let challenge_values = config.1.values(&layouter);
+        self.synthesize_sub(&config.0, &challenge_values, &mut layouter)
+    }
+}
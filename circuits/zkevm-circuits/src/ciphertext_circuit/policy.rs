@@ -0,0 +1,75 @@
+use eth_types::U256;
+use halo2_proofs::halo2curves::bn256::{Fr, G1Affine};
+use itertools::Itertools;
+
+use crate::pairing_circuit::commitment::{g1_words, poseidon_commitment, word_limbs};
+
+/// An LSSS access policy (M, ρ): row i of the share-generating matrix M is labelled by the
+/// attribute ρ(i), given by its hash H(ρ(i)) in G1.
+#[derive(Clone, Debug, Default)]
+pub struct LsssPolicy {
+    /// Share-generating matrix M, one row per attribute.
+    pub matrix: Vec<Vec<Fr>>,
+    /// H(ρ(i)) of each row of M.
+    pub attributes: Vec<G1Affine>,
+}
+
+impl LsssPolicy {
+    /// The policy requiring every one of the given attributes: M_0 = (1, 1, 0, ..., 0), and the
+    /// other rows M_i have -1 in column i and 1 in column i + 1, so that Σ M_i = (1, 0, ..., 0)
+    /// and every w_i is 1.
+    pub fn and(attributes: Vec<G1Affine>) -> Self {
+        let n = attributes.len();
+        let matrix = (0..n)
+            .map(|i| {
+                (0..n)
+                    .map(|j| {
+                        if j == i + 1 || (i == 0 && j == 0) {
+                            Fr::one()
+                        } else if j == i {
+                            -Fr::one()
+                        } else {
+                            Fr::zero()
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
+        Self { matrix, attributes }
+    }
+
+    /// Number of rows of M.
+    pub fn num_rows(&self) -> usize {
+        self.matrix.len()
+    }
+
+    /// Number of columns of M.
+    pub fn num_columns(&self) -> usize {
+        self.matrix.first().map_or(0, Vec::len)
+    }
+
+    /// Shares λ_i = M_i · v of the vector v = (s, y_2, ..., y_n).
+    pub fn shares(&self, v: &[Fr]) -> Vec<Fr> {
+        self.matrix
+            .iter()
+            .map(|row| row.iter().zip_eq(v).map(|(m, v)| m * v).sum())
+            .collect()
+    }
+
+    /// Field elements of the policy committed to: the CRT limbs of the entries of M row by row,
+    /// followed by those of the coordinates of each H(ρ(i)).
+    pub fn message(&self) -> Vec<Fr> {
+        self.matrix
+            .iter()
+            .flatten()
+            .map(|m| U256::from_little_endian(&m.to_bytes()))
+            .chain(self.attributes.iter().flat_map(g1_words))
+            .flat_map(word_limbs)
+            .collect()
+    }
+
+    /// Poseidon commitment to the policy.
+    pub fn commitment(&self) -> Fr {
+        poseidon_commitment(&self.message())
+    }
+}
@@ -0,0 +1,114 @@
+use eth_types::U256;
+use ff::Field;
+use halo2_proofs::{
+    dev::MockProver,
+    halo2curves::bn256::{Fr, G1Affine},
+};
+use rand_chacha::rand_core::OsRng;
+
+use crate::{
+    pairing_circuit::{poseidon_commitment, MyEccCircuit},
+    util::SubCircuit,
+};
+
+use super::{CiphertextCircuit, LsssPolicy};
+
+/// A ciphertext under the policy "A and B", with M = [[1, 1], [0, -1]].
+fn and_circuit() -> CiphertextCircuit<Fr> {
+    let policy = LsssPolicy {
+        matrix: vec![vec![Fr::one(), Fr::one()], vec![Fr::zero(), -Fr::one()]],
+        attributes: (0..2).map(|_| G1Affine::random(&mut OsRng)).collect(),
+    };
+    let v = (0..policy.num_columns())
+        .map(|_| Fr::random(&mut OsRng))
+        .collect();
+    let rs = (0..policy.num_rows())
+        .map(|_| Fr::random(&mut OsRng))
+        .collect();
+
+    CiphertextCircuit::encrypt(G1Affine::random(&mut OsRng), policy, v, rs).unwrap()
+}
+
+#[test]
+fn test_ciphertext_circuit() {
+    let circuit = and_circuit();
+
+    let instance = circuit.instance();
+    assert_eq!(
+        instance,
+        vec![vec![poseidon_commitment(&[
+            poseidon_commitment(&[circuit.ciphertext_commitment(), circuit.policy.commitment()]),
+            circuit.params_commitment()
+        ])]]
+    );
+
+    let prover = match MockProver::<Fr>::run(20, &circuit, instance) {
+        Ok(prover) => prover,
+        Err(e) => panic!("{e:#?}"),
+    };
+    assert_eq!(prover.verify(), Ok(()));
+}
+
+#[test]
+fn test_ciphertext_circuit_malformed() {
+    let circuit = and_circuit();
+
+    // C_1 that is not blinded with the share of the secret
+    let mut malformed = circuit.clone();
+    malformed.cs[0] = G1Affine::random(&mut OsRng);
+    let prover = MockProver::<Fr>::run(20, &malformed, malformed.instance()).unwrap();
+    assert!(prover.verify().is_err());
+
+    // C' for another secret
+    let mut malformed = circuit.clone();
+    malformed.c_p = G1Affine::random(&mut OsRng);
+    let prover = MockProver::<Fr>::run(20, &malformed, malformed.instance()).unwrap();
+    assert!(prover.verify().is_err());
+
+    // a ciphertext under other public parameters
+    let mut other_params = circuit.clone();
+    other_params.g_a = G1Affine::random(&mut OsRng);
+    let prover = MockProver::<Fr>::run(20, &other_params, circuit.instance()).unwrap();
+    assert!(prover.verify().is_err());
+
+    // a missing D_i, r_i or entry of v is an error rather than a panic
+    let mut missing_d = circuit.clone();
+    missing_d.ds.pop();
+    assert!(MockProver::<Fr>::run(20, &missing_d, circuit.instance()).is_err());
+    let mut missing_r = circuit.clone();
+    missing_r.rs.pop();
+    assert!(MockProver::<Fr>::run(20, &missing_r, circuit.instance()).is_err());
+    assert!(CiphertextCircuit::<Fr>::encrypt(
+        circuit.g_a,
+        circuit.policy.clone(),
+        circuit.v[1..].to_vec(),
+        circuit.rs.clone(),
+    )
+    .is_err());
+}
+
+#[test]
+fn test_ciphertext_commitment_matches_pairing_circuit() {
+    let circuit = and_circuit();
+    let g1_words = |p: &G1Affine| {
+        (
+            U256::from_little_endian(&p.x.to_bytes()),
+            U256::from_little_endian(&p.y.to_bytes()),
+        )
+    };
+
+    let pairing_circuit = MyEccCircuit::<Fr, 9> {
+        p1s: circuit
+            .cs
+            .iter()
+            .zip(circuit.ds.iter())
+            .flat_map(|(c, d)| [g1_words(c), g1_words(d)])
+            .collect(),
+        ct: g1_words(&circuit.c_p),
+        ..Default::default()
+    };
+    assert_eq!(
+        pairing_circuit.ciphertext_commitment(),
+        circuit.ciphertext_commitment()
+    );
+}
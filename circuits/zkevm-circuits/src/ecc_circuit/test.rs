@@ -415,6 +415,7 @@ fn test_ecc_circuit_valid_invalid() {
             ec_add: 0,
             ec_mul: 0,
             ec_pairing: 2,
+            bls12_g1_add: 0,
         },
         vec![],
         vec![],
@@ -427,6 +428,7 @@ fn test_ecc_circuit_valid_invalid() {
             ec_add: 0,
             ec_mul: 0,
             ec_pairing: 2,
+            bls12_g1_add: 0,
         },
         vec![],
         vec![],
@@ -439,6 +441,7 @@ fn test_ecc_circuit_valid_invalid() {
             ec_add: 0,
             ec_mul: 0,
             ec_pairing: 2,
+            bls12_g1_add: 0,
         },
         vec![],
         vec![],
@@ -494,6 +497,7 @@ fn test_invalid_ec_add() {
             ec_add: ec_adds.len(),
             ec_mul: 0,
             ec_pairing: 0,
+            bls12_g1_add: 0,
         },
         ec_adds,
         vec![],
@@ -537,6 +541,7 @@ fn test_invalid_ec_add() {
             ec_add: ec_adds.len(),
             ec_mul: 0,
             ec_pairing: 0,
+            bls12_g1_add: 0,
         },
         ec_adds,
         vec![],
@@ -22,8 +22,8 @@ pub use crate::witness;
 use crate::{
     evm_circuit::param::{MAX_STEP_HEIGHT, STEP_STATE_HEIGHT},
     table::{
-        BlockTable, BytecodeTable, CopyTable, EccTable, ExpTable, KeccakTable, LookupTable,
-        ModExpTable, PowOfRandTable, RwTable, SHA256Table, SigTable, TxTable,
+        BlockTable, Bls12381Table, BytecodeTable, CopyTable, EccTable, ExpTable, KeccakTable,
+        LookupTable, ModExpTable, PowOfRandTable, RwTable, SHA256Table, SigTable, TxTable,
     },
     util::{Field, SubCircuit, SubCircuitConfig},
 };
@@ -53,6 +53,7 @@ pub struct EvmCircuitConfig<F> {
     sig_table: SigTable,
     modexp_table: ModExpTable,
     ecc_table: EccTable,
+    bls12_381_table: Bls12381Table,
     pub(crate) pow_of_rand_table: PowOfRandTable,
 }
 
@@ -82,6 +83,8 @@ pub struct EvmCircuitConfigArgs<F: Field> {
     pub modexp_table: ModExpTable,
     /// Ecc Table.
     pub ecc_table: EccTable,
+    /// BLS12-381 Table.
+    pub bls12_381_table: Bls12381Table,
     // Power of Randomness Table.
     pub pow_of_rand_table: PowOfRandTable,
 }
@@ -113,6 +116,7 @@ impl<F: Field> SubCircuitConfig<F> for EvmCircuitConfig<F> {
             sig_table,
             modexp_table,
             ecc_table,
+            bls12_381_table,
             pow_of_rand_table,
         }: Self::ConfigArgs,
     ) -> Self {
@@ -134,6 +138,7 @@ impl<F: Field> SubCircuitConfig<F> for EvmCircuitConfig<F> {
             &sig_table,
             &modexp_table,
             &ecc_table,
+            &bls12_381_table,
             &pow_of_rand_table,
         ));
 
@@ -151,6 +156,7 @@ impl<F: Field> SubCircuitConfig<F> for EvmCircuitConfig<F> {
         sig_table.annotate_columns(meta);
         modexp_table.annotate_columns(meta);
         ecc_table.annotate_columns(meta);
+        bls12_381_table.annotate_columns(meta);
         pow_of_rand_table.annotate_columns(meta);
 
         Self {
@@ -168,6 +174,7 @@ impl<F: Field> SubCircuitConfig<F> for EvmCircuitConfig<F> {
             sig_table,
             modexp_table,
             ecc_table,
+            bls12_381_table,
             pow_of_rand_table,
         }
     }
@@ -456,6 +463,7 @@ impl<F: Field> Circuit<F> for EvmCircuit<F> {
         let sig_table = SigTable::construct(meta);
         let modexp_table = ModExpTable::construct(meta);
         let ecc_table = EccTable::construct(meta);
+        let bls12_381_table = Bls12381Table::construct(meta);
         let pow_of_rand_table = PowOfRandTable::construct(meta, &challenges_expr);
         (
             EvmCircuitConfig::new(
@@ -473,6 +481,7 @@ impl<F: Field> Circuit<F> for EvmCircuit<F> {
                     sig_table,
                     modexp_table,
                     ecc_table,
+                    bls12_381_table,
                     pow_of_rand_table,
                 },
             ),
@@ -540,6 +549,12 @@ impl<F: Field> Circuit<F> for EvmCircuit<F> {
             &block.get_ec_pairing_ops(),
             &challenges,
         )?;
+        config.bls12_381_table.dev_load(
+            &mut layouter,
+            block.circuits_params.max_ec_ops,
+            &block.get_bls12_g1_add_ops(),
+            &challenges,
+        )?;
         config
             .pow_of_rand_table
             .assign(&mut layouter, &challenges, 2048)?;
@@ -1,9 +1,10 @@
 use super::{
     param::{
-        BLOCK_TABLE_LOOKUPS, BYTECODE_TABLE_LOOKUPS, COPY_TABLE_LOOKUPS, ECC_TABLE_LOOKUPS,
-        EXP_TABLE_LOOKUPS, FIXED_TABLE_LOOKUPS, KECCAK_TABLE_LOOKUPS, MODEXP_TABLE_LOOKUPS,
-        N_BYTE_LOOKUPS, N_COPY_COLUMNS, N_PHASE1_COLUMNS, POW_OF_RAND_TABLE_LOOKUPS,
-        RW_TABLE_LOOKUPS, SHA256_TABLE_LOOKUPS, SIG_TABLE_LOOKUPS, TX_TABLE_LOOKUPS,
+        BLOCK_TABLE_LOOKUPS, BLS12_381_TABLE_LOOKUPS, BYTECODE_TABLE_LOOKUPS, COPY_TABLE_LOOKUPS,
+        ECC_TABLE_LOOKUPS, EXP_TABLE_LOOKUPS, FIXED_TABLE_LOOKUPS, KECCAK_TABLE_LOOKUPS,
+        MODEXP_TABLE_LOOKUPS, N_BYTE_LOOKUPS, N_COPY_COLUMNS, N_PHASE1_COLUMNS,
+        POW_OF_RAND_TABLE_LOOKUPS, RW_TABLE_LOOKUPS, SHA256_TABLE_LOOKUPS, SIG_TABLE_LOOKUPS,
+        TX_TABLE_LOOKUPS,
     },
     util::{instrumentation::Instrument, CachedRegion, CellManager, Inverter, StoredExpression},
     EvmCircuitExports,
@@ -61,6 +62,8 @@ mod address;
 mod balance;
 mod begin_tx;
 mod bitwise;
+mod blobbasefee;
+mod blobhash;
 mod block_ctx;
 mod blockhash;
 mod byte;
@@ -141,6 +144,7 @@ mod stop;
 mod swap;
 mod tload;
 mod tstore;
+mod tx_authorization;
 
 use add_sub::AddSubGadget;
 use addmod::AddModGadget;
@@ -148,6 +152,8 @@ use address::AddressGadget;
 use balance::BalanceGadget;
 use begin_tx::BeginTxGadget;
 use bitwise::BitwiseGadget;
+use blobbasefee::BlobBaseFeeGadget;
+use blobhash::BlobHashGadget;
 #[cfg(feature = "scroll")]
 use block_ctx::DifficultyGadget;
 use block_ctx::{BlockCtxU160Gadget, BlockCtxU256Gadget, BlockCtxU64Gadget};
@@ -212,8 +218,8 @@ use padding::PaddingGadget;
 use pc::PcGadget;
 use pop::PopGadget;
 use precompiles::{
-    BasePrecompileGadget, EcAddGadget, EcMulGadget, EcPairingGadget, EcrecoverGadget,
-    IdentityGadget, ModExpGadget, SHA256Gadget,
+    BasePrecompileGadget, Bls12G1AddGadget, EcAddGadget, EcMulGadget, EcPairingGadget,
+    EcrecoverGadget, IdentityGadget, ModExpGadget, PointEvaluationGadget, SHA256Gadget,
 };
 use push::PushGadget;
 use return_revert::ReturnRevertGadget;
@@ -232,6 +238,7 @@ use stop::StopGadget;
 use swap::SwapGadget;
 use tload::TloadGadget;
 use tstore::TstoreGadget;
+use tx_authorization::TxAuthorizationGadget;
 
 pub(crate) trait ExecutionGadget<F: Field> {
     const NAME: &'static str;
@@ -275,6 +282,7 @@ pub(crate) struct ExecutionConfig<F> {
     instrument: Instrument,
     // internal state gadgets
     begin_tx_gadget: Box<BeginTxGadget<F>>,
+    tx_authorization_gadget: Box<TxAuthorizationGadget<F>>,
     end_block_gadget: Box<EndBlockGadget<F>>,
     padding_gadget: Box<PaddingGadget<F>>,
     end_inner_block_gadget: Box<EndInnerBlockGadget<F>>,
@@ -339,6 +347,8 @@ pub(crate) struct ExecutionConfig<F> {
     stop_gadget: Box<StopGadget<F>>,
     swap_gadget: Box<SwapGadget<F>>,
     blockhash_gadget: Box<BlockHashGadget<F>>,
+    blobhash_gadget: Box<BlobHashGadget<F>>,
+    blobbasefee_gadget: Box<BlobBaseFeeGadget<F>>,
     block_ctx_u64_gadget: Box<BlockCtxU64Gadget<F>>,
     block_ctx_u160_gadget: Box<BlockCtxU160Gadget<F>>,
     block_ctx_u256_gadget: Box<BlockCtxU256Gadget<F>>,
@@ -378,6 +388,8 @@ pub(crate) struct ExecutionConfig<F> {
     precompile_bn128mul_gadget: Box<EcMulGadget<F>>,
     precompile_bn128pairing_gadget: Box<EcPairingGadget<F>>,
     precompile_blake2f_gadget: Box<BasePrecompileGadget<F, { ExecutionState::PrecompileBlake2f }>>,
+    precompile_point_evaluation_gadget: Box<PointEvaluationGadget<F>>,
+    precompile_bls12_g1add_gadget: Box<Bls12G1AddGadget<F>>,
 }
 
 impl<F: Field> ExecutionConfig<F> {
@@ -399,6 +411,7 @@ impl<F: Field> ExecutionConfig<F> {
         sig_table: &dyn LookupTable<F>,
         modexp_table: &dyn LookupTable<F>,
         ecc_table: &dyn LookupTable<F>,
+        bls12_381_table: &dyn LookupTable<F>,
         pow_of_rand_table: &dyn LookupTable<F>,
     ) -> Self {
         let mut instrument = Instrument::default();
@@ -571,6 +584,7 @@ impl<F: Field> ExecutionConfig<F> {
             advices,
             // internal states
             begin_tx_gadget: configure_gadget!(),
+            tx_authorization_gadget: configure_gadget!(),
             end_block_gadget: configure_gadget!(),
             end_inner_block_gadget: configure_gadget!(),
             end_tx_gadget: configure_gadget!(),
@@ -617,6 +631,8 @@ impl<F: Field> ExecutionConfig<F> {
             address_gadget: configure_gadget!(),
             balance_gadget: configure_gadget!(),
             blockhash_gadget: configure_gadget!(),
+            blobhash_gadget: configure_gadget!(),
+            blobbasefee_gadget: configure_gadget!(),
             exp_gadget: configure_gadget!(),
             sar_gadget: configure_gadget!(),
             extcodecopy_gadget: configure_gadget!(),
@@ -673,6 +689,8 @@ impl<F: Field> ExecutionConfig<F> {
             precompile_bn128mul_gadget: configure_gadget!(),
             precompile_bn128pairing_gadget: configure_gadget!(),
             precompile_blake2f_gadget: configure_gadget!(),
+            precompile_point_evaluation_gadget: configure_gadget!(),
+            precompile_bls12_g1add_gadget: configure_gadget!(),
             // step and presets
             step: step_curr,
             height_map,
@@ -695,6 +713,7 @@ impl<F: Field> ExecutionConfig<F> {
             sig_table,
             modexp_table,
             ecc_table,
+            bls12_381_table,
             pow_of_rand_table,
             &challenges,
             &cell_manager,
@@ -883,14 +902,19 @@ impl<F: Field> ExecutionConfig<F> {
                             vec![ExecutionState::EndTx, ExecutionState::EndInnerBlock],
                         ),
                         (
-                            "Only ExecutionState which halts / precompile or BeginTx can transit to EndTx",
+                            "Only ExecutionState which halts / precompile or BeginTx / TxAuthorization can transit to EndTx",
                             ExecutionState::EndTx,
                             ExecutionState::iter()
                                 .filter(ExecutionState::halts)
                                 .chain(ExecutionState::iter().filter(ExecutionState::is_precompiled))
-                                .chain(iter::once(ExecutionState::BeginTx))
+                                .chain([ExecutionState::BeginTx, ExecutionState::TxAuthorization])
                                 .collect(),
                         ),
+                        (
+                            "Only BeginTx or TxAuthorization can transit to TxAuthorization",
+                            ExecutionState::TxAuthorization,
+                            vec![ExecutionState::BeginTx, ExecutionState::TxAuthorization],
+                        ),
                         (
                             "Only Padding can transit to EndBlock",
                             ExecutionState::EndBlock,
@@ -964,6 +988,7 @@ impl<F: Field> ExecutionConfig<F> {
         sig_table: &dyn LookupTable<F>,
         modexp_table: &dyn LookupTable<F>,
         ecc_table: &dyn LookupTable<F>,
+        bls12_381_table: &dyn LookupTable<F>,
         pow_of_rand_table: &dyn LookupTable<F>,
         challenges: &Challenges<Expression<F>>,
         cell_manager: &CellManager<F>,
@@ -985,6 +1010,7 @@ impl<F: Field> ExecutionConfig<F> {
                         Table::Sig => sig_table,
                         Table::ModExp => modexp_table,
                         Table::Ecc => ecc_table,
+                        Table::Bls12381 => bls12_381_table,
                         Table::PowOfRand => pow_of_rand_table,
                     }
                     .table_exprs(meta);
@@ -1426,6 +1452,7 @@ impl<F: Field> ExecutionConfig<F> {
             ("EVM_lookup_sig", SIG_TABLE_LOOKUPS),
             ("EVM_lookup_modexp", MODEXP_TABLE_LOOKUPS),
             ("EVM_lookup_ecc", ECC_TABLE_LOOKUPS),
+            ("EVM_lookup_bls12_381", BLS12_381_TABLE_LOOKUPS),
             ("EVM_lookup_pow_of_rand", POW_OF_RAND_TABLE_LOOKUPS),
             ("EVM_adv_phase2", N_PHASE2_COLUMNS),
             ("EVM_copy", N_COPY_COLUMNS),
@@ -1570,6 +1597,7 @@ impl<F: Field> ExecutionConfig<F> {
         match step.execution_state {
             // internal states
             ExecutionState::BeginTx => assign_exec_step!(self.begin_tx_gadget),
+            ExecutionState::TxAuthorization => assign_exec_step!(self.tx_authorization_gadget),
             ExecutionState::EndTx => assign_exec_step!(self.end_tx_gadget),
             ExecutionState::EndInnerBlock => assign_exec_step!(self.end_inner_block_gadget),
             ExecutionState::EndBlock => assign_exec_step!(self.end_block_gadget),
@@ -1624,6 +1652,8 @@ impl<F: Field> ExecutionConfig<F> {
             #[cfg(feature = "scroll")]
             ExecutionState::DIFFICULTY => assign_exec_step!(self.difficulty_gadget),
             ExecutionState::BLOCKHASH => assign_exec_step!(self.blockhash_gadget),
+            ExecutionState::BLOBHASH => assign_exec_step!(self.blobhash_gadget),
+            ExecutionState::BLOBBASEFEE => assign_exec_step!(self.blobbasefee_gadget),
             ExecutionState::SELFBALANCE => assign_exec_step!(self.selfbalance_gadget),
             ExecutionState::CREATE => assign_exec_step!(self.create_gadget),
             ExecutionState::CREATE2 => assign_exec_step!(self.create2_gadget),
@@ -1735,6 +1765,12 @@ impl<F: Field> ExecutionConfig<F> {
             ExecutionState::PrecompileBlake2f => {
                 assign_exec_step!(self.precompile_blake2f_gadget)
             }
+            ExecutionState::PrecompilePointEvaluation => {
+                assign_exec_step!(self.precompile_point_evaluation_gadget)
+            }
+            ExecutionState::PrecompileBls12G1Add => {
+                assign_exec_step!(self.precompile_bls12_g1add_gadget)
+            }
         }
 
         // Fill in the witness values for stored expressions
@@ -6,8 +6,8 @@ use crate::{
         util::{
             and,
             common_gadget::{
-                CurieGadget, TransferGadgetInfo, TransferWithGasFeeGadget, TxAccessListGadget,
-                TxEip1559Gadget, TxL1FeeGadget, TxL1MsgGadget,
+                CurieGadget, DelegationGadget, TransferGadgetInfo, TransferWithGasFeeGadget,
+                TxAccessListGadget, TxEip1559Gadget, TxL1FeeGadget, TxL1MsgGadget,
             },
             constraint_builder::{
                 ConstrainBuilderCommon, EVMConstraintBuilder, ReversionInfo, StepStateTransition,
@@ -31,13 +31,16 @@ use crate::{
 };
 use array_init::array_init;
 use bus_mapping::{circuit_input_builder::CopyDataType, precompile::PrecompileCalls};
-use eth_types::{utils::is_precompiled, Address, ToLittleEndian, U256};
+use eth_types::{
+    evm_types::block_utils::GAS_PER_BLOB, forks::LAST_PRECOMPILE_ADDRESS, utils::is_precompiled,
+    Address, ToLittleEndian, U256,
+};
 use ethers_core::utils::{get_contract_address, keccak256, rlp::RlpStream};
 use gadgets::util::{expr_from_bytes, not, select, Expr};
 use gadgets::ToScalar;
 use halo2_proofs::{circuit::Value, plonk::Error};
 
-const PRECOMPILE_COUNT: usize = 9;
+const PRECOMPILE_COUNT: usize = LAST_PRECOMPILE_ADDRESS as usize;
 
 #[derive(Clone, Debug)]
 pub(crate) struct BeginTxGadget<F> {
@@ -48,6 +51,12 @@ pub(crate) struct BeginTxGadget<F> {
     tx_gas: Cell<F>,
     tx_gas_price: Word<F>,
     mul_gas_fee_by_gas: MulWordByU64Gadget<F>,
+    // Number of blob versioned hashes, zero unless EIP-4844 tx
+    tx_blob_hashes_len: Cell<F>,
+    // Blob base fee from block context
+    blob_base_fee: Word<F>,
+    // Blob fee burned by EIP-4844 tx, blob_base_fee * blob_gas
+    mul_blob_fee_by_blob_gas: MulWordByU64Gadget<F>,
     tx_fee: Word<F>,
     tx_caller_address: Cell<F>,
     tx_caller_address_is_zero: IsZeroGadget<F>,
@@ -73,6 +82,14 @@ pub(crate) struct BeginTxGadget<F> {
     account_code_hash_is_zero: IsZeroGadget<F>,
     #[cfg(feature = "scroll")]
     account_keccak_code_hash: Cell<F>,
+    // EIP7702
+    authorization_list_len: Cell<F>,
+    has_no_authorizations: IsZeroGadget<F>,
+    callee_delegation: DelegationGadget<F>,
+    is_delegate_warm: Cell<F>,
+    delegate_code_hash: Cell<F>,
+    executed_code_hash_is_empty: IsEqualGadget<F>,
+    executed_code_hash_is_zero: IsZeroGadget<F>,
     call_code_hash: Cell<F>,
     call_code_hash_is_empty: IsEqualGadget<F>,
     call_code_hash_is_zero: IsZeroGadget<F>,
@@ -87,7 +104,7 @@ pub(crate) struct BeginTxGadget<F> {
     init_code_rlc: Cell<F>,
     /// RLP gadget for CREATE address.
     create: ContractCreateGadget<F, false>,
-    // Caller, callee, coinbase, precompile addresses (9) and optional
+    // Caller, callee, coinbase, precompile addresses (10) and optional
     // access-list addresses are added to the access list.
     // <https://github.com/ethereum/go-ethereum/blob/604e215d1bb070dff98fb76aa965064c74e3633f/core/state/statedb.go#L1098>
     is_caller_warm: Cell<F>,
@@ -137,6 +154,10 @@ impl<F: Field> ExecutionGadget<F> for BeginTxGadget<F> {
             .map(|field_tag| cb.tx_context(tx_id.expr(), field_tag, None));
 
         let tx_signed_length = cb.tx_context(tx_id.expr(), TxContextFieldTag::TxHashLength, None);
+        let authorization_list_len =
+            cb.tx_context(tx_id.expr(), TxContextFieldTag::AuthorizationListLen, None);
+        let has_no_authorizations = IsZeroGadget::construct(cb, authorization_list_len.expr());
+        let has_authorizations = not::expr(has_no_authorizations.expr());
         let tx_access_list = TxAccessListGadget::construct(cb, tx_id.expr(), tx_type.expr());
         let is_call_data_empty = IsZeroGadget::construct(cb, tx_call_data_length.expr());
 
@@ -235,20 +256,42 @@ impl<F: Field> ExecutionGadget<F> for BeginTxGadget<F> {
         // Calculate transaction gas fee
         let mul_gas_fee_by_gas =
             MulWordByU64Gadget::construct(cb, tx_gas_price.clone(), tx_gas.expr());
+        // Calculate blob fee of EIP-4844 tx, which is burned as the base fee.
+        // BlobVersionedHashesLen is constrained to zero for other tx types in tx circuit.
+        let tx_blob_hashes_len = cb.tx_context(
+            tx_id.expr(),
+            TxContextFieldTag::BlobVersionedHashesLen,
+            None,
+        );
+        let tx_blob_gas = tx_blob_hashes_len.expr() * GAS_PER_BLOB.expr();
+        let blob_base_fee = cb.query_word_rlc();
+        cb.block_lookup(
+            BlockContextFieldTag::BlobBaseFee.expr(),
+            cb.curr.state.block_number.expr(),
+            blob_base_fee.expr(),
+        );
+        let mul_blob_fee_by_blob_gas =
+            MulWordByU64Gadget::construct(cb, blob_base_fee.clone(), tx_blob_gas.clone());
         let tx_fee = cb.query_word_rlc();
         let l2_fee = select::expr(
             tx_l1_msg.is_l1_msg(),
             0.expr(),
             from_bytes::expr(&mul_gas_fee_by_gas.product().cells[..16]),
         );
+        let blob_fee = select::expr(
+            tx_l1_msg.is_l1_msg(),
+            0.expr(),
+            from_bytes::expr(&mul_blob_fee_by_blob_gas.product().cells[..16]),
+        );
         cb.require_equal(
-            "tx_fee == l1_fee + l2_fee",
-            l1_fee_cost + l2_fee,
+            "tx_fee == l1_fee + l2_fee + blob_fee",
+            l1_fee_cost + l2_fee + blob_fee,
             from_bytes::expr(&tx_fee.cells[..16]),
         );
 
-        // a valid precompile address is: 1 <= addr <= 9 (addr != 0 && addr < 0xA)
-        let is_precompile_lt = LtGadget::construct(cb, tx_callee_address.expr(), 0xA.expr());
+        // a valid precompile address is: 1 <= addr <= PRECOMPILE_COUNT
+        let is_precompile_lt =
+            LtGadget::construct(cb, tx_callee_address.expr(), (PRECOMPILE_COUNT + 1).expr());
         let is_precompile = and::expr([
             not::expr(tx_callee_address_is_zero.expr()),
             is_precompile_lt.expr(),
@@ -280,7 +323,9 @@ impl<F: Field> ExecutionGadget<F> for BeginTxGadget<F> {
                     eth_types::evm_types::GasCost::TX.expr(),
                 ) + tx_call_data_gas_cost.expr()
                     + tx_access_list.gas_cost()
-                    + init_code_gas_cost,
+                    + init_code_gas_cost
+                    + authorization_list_len.expr()
+                        * eth_types::evm_types::GasCost::PER_EMPTY_ACCOUNT.expr(),
             )
         });
         // Check gas_left is sufficient
@@ -350,6 +395,47 @@ impl<F: Field> ExecutionGadget<F> for BeginTxGadget<F> {
             account_code_hash.expr(),
         ); // rwc_delta += 1
 
+        // EIP7702: a delegated callee runs the code of its delegate, which is warmed. With
+        // authorizations, this is done by the last `TxAuthorization` step instead.
+        let callee_delegation = DelegationGadget::construct(
+            cb,
+            call_callee_address.expr(),
+            account_code_hash.expr(),
+            and::expr([
+                not::expr(tx_is_create.expr()),
+                has_no_authorizations.expr(),
+                not::expr(account_code_hash_is_empty_or_zero.expr()),
+            ]),
+        ); // rwc_delta += callee_delegation.rw_delta()
+        let is_delegate_warm = cb.query_bool();
+        let delegate_code_hash = cb.query_cell_phase2();
+        cb.condition(callee_delegation.is_delegated(), |cb| {
+            cb.account_access_list_write(
+                tx_id.expr(),
+                callee_delegation.delegate(),
+                1.expr(),
+                is_delegate_warm.expr(),
+                None,
+            );
+            cb.account_read(
+                callee_delegation.delegate(),
+                AccountFieldTag::CodeHash,
+                delegate_code_hash.expr(),
+            );
+        }); // rwc_delta += 2 * is_delegated
+        let delegation_rw_delta =
+            callee_delegation.rw_delta() + 2.expr() * callee_delegation.is_delegated();
+        let executed_code_hash = select::expr(
+            callee_delegation.is_delegated(),
+            delegate_code_hash.expr(),
+            account_code_hash.expr(),
+        );
+        let executed_code_hash_is_empty =
+            IsEqualGadget::construct(cb, executed_code_hash.clone(), cb.empty_code_hash_rlc());
+        let executed_code_hash_is_zero = IsZeroGadget::construct(cb, executed_code_hash.clone());
+        let executed_code_hash_is_empty_or_zero =
+            executed_code_hash_is_empty.expr() + executed_code_hash_is_zero.expr();
+
         // Transfer value from caller to callee, creating account if necessary.
         let transfer_with_gas_fee = TransferWithGasFeeGadget::construct(
             cb,
@@ -374,6 +460,8 @@ impl<F: Field> ExecutionGadget<F> for BeginTxGadget<F> {
             &tx_gas_price,
             tx_l1_fee.tx_l1_fee_word(),
             &tx_value,
+            &blob_base_fee,
+            tx_blob_gas,
             transfer_with_gas_fee.sender_balance_prev(),
         );
 
@@ -553,6 +641,10 @@ impl<F: Field> ExecutionGadget<F> for BeginTxGadget<F> {
                     account_code_hash_is_empty_or_zero.expr(),
                     true.expr(),
                 );
+                cb.require_zero(
+                    "EIP7702 tx to a precompile is not supported",
+                    has_authorizations.expr(),
+                );
                 // cb.require_equal(
                 //     "Go to EndTx when Tx to precompile",
                 //     cb.next.execution_state_selector([ExecutionState::EndTx]),
@@ -673,7 +765,8 @@ impl<F: Field> ExecutionGadget<F> for BeginTxGadget<F> {
         cb.condition(
             and::expr([
                 not::expr(tx_is_create.expr()),
-                account_code_hash_is_empty_or_zero.expr(),
+                has_no_authorizations.expr(),
+                executed_code_hash_is_empty_or_zero.expr(),
                 not::expr(is_precompile.expr()),
             ]),
             |cb| {
@@ -700,10 +793,14 @@ impl<F: Field> ExecutionGadget<F> for BeginTxGadget<F> {
                     //   - Write TxAccessListAccount (Callee)
                     //   - Write TxAccessListAccount (Coinbase) only for Shanghai
                     //   - Read Account CodeHash
+                    //   - a DelegationGadget
+                    //   - Write TxAccessListAccount (Delegate) if delegated
+                    //   - Read Account (Delegate) CodeHash if delegated
                     //   - a TxL1FeeGadget
                     //   - a TransferWithGasFeeGadget
                     rw_counter: Delta(
                         9.expr()
+                            + delegation_rw_delta.clone()
                             + l1_rw_delta.expr()
                             + transfer_with_gas_fee.rw_delta()
                             + tx_access_list.rw_delta_expr()
@@ -720,7 +817,8 @@ impl<F: Field> ExecutionGadget<F> for BeginTxGadget<F> {
         cb.condition(
             and::expr([
                 not::expr(tx_is_create.expr()),
-                not::expr(account_code_hash_is_empty_or_zero),
+                has_no_authorizations.expr(),
+                not::expr(executed_code_hash_is_empty_or_zero),
             ]),
             |cb| {
                 // Setup first call's context.
@@ -743,7 +841,7 @@ impl<F: Field> ExecutionGadget<F> for BeginTxGadget<F> {
                     (CallContextFieldTag::LastCalleeReturnDataLength, 0.expr()),
                     (CallContextFieldTag::IsRoot, 1.expr()),
                     (CallContextFieldTag::IsCreate, tx_is_create.expr()),
-                    (CallContextFieldTag::CodeHash, account_code_hash.expr()),
+                    (CallContextFieldTag::CodeHash, executed_code_hash.clone()),
                 ] {
                     cb.call_context_lookup(true.expr(), Some(call_id.expr()), field_tag, value);
                 }
@@ -761,6 +859,9 @@ impl<F: Field> ExecutionGadget<F> for BeginTxGadget<F> {
                     //   - Write TxAccessListAccount (Callee)
                     //   - Write TxAccessListAccount (Coinbase) only for Shanghai
                     //   - Read Account CodeHash
+                    //   - a DelegationGadget
+                    //   - Write TxAccessListAccount (Delegate) if delegated
+                    //   - Read Account (Delegate) CodeHash if delegated
                     //   - a TransferWithGasFeeGadget
                     //   - Write CallContext Depth
                     //   - Write CallContext CallerAddress
@@ -777,6 +878,7 @@ impl<F: Field> ExecutionGadget<F> for BeginTxGadget<F> {
                     //   - Write CallContext CodeHash
                     rw_counter: Delta(
                         22.expr()
+                            + delegation_rw_delta
                             + l1_rw_delta.expr()
                             + transfer_with_gas_fee.rw_delta()
                             + tx_access_list.rw_delta_expr()
@@ -785,8 +887,8 @@ impl<F: Field> ExecutionGadget<F> for BeginTxGadget<F> {
                     call_id: To(call_id.expr()),
                     is_root: To(true.expr()),
                     is_create: To(tx_is_create.expr()),
-                    code_hash: To(account_code_hash.expr()),
-                    gas_left: To(gas_left),
+                    code_hash: To(executed_code_hash),
+                    gas_left: To(gas_left.clone()),
                     reversible_write_counter: To(transfer_with_gas_fee.reversible_w_delta()),
                     log_id: To(0.expr()),
                     ..StepStateTransition::new_context()
@@ -794,6 +896,65 @@ impl<F: Field> ExecutionGadget<F> for BeginTxGadget<F> {
             },
         );
 
+        // 5. EIP7702 tx with authorizations, which are applied by the next `TxAuthorization`
+        // steps. The last of them sets the code hash of the root call.
+        cb.condition(
+            and::expr([not::expr(tx_is_create.expr()), has_authorizations]),
+            |cb| {
+                // Setup first call's context, except for its code hash.
+                for (field_tag, value) in [
+                    (CallContextFieldTag::Depth, 1.expr()),
+                    (CallContextFieldTag::CallerAddress, tx_caller_address.expr()),
+                    (
+                        CallContextFieldTag::CalleeAddress,
+                        call_callee_address.expr(),
+                    ),
+                    (CallContextFieldTag::CallDataOffset, 0.expr()),
+                    (
+                        CallContextFieldTag::CallDataLength,
+                        tx_call_data_length.expr(),
+                    ),
+                    (CallContextFieldTag::Value, tx_value.expr()),
+                    (CallContextFieldTag::IsStatic, 0.expr()),
+                    (CallContextFieldTag::LastCalleeId, 0.expr()),
+                    (CallContextFieldTag::LastCalleeReturnDataOffset, 0.expr()),
+                    (CallContextFieldTag::LastCalleeReturnDataLength, 0.expr()),
+                    (CallContextFieldTag::IsRoot, 1.expr()),
+                    (CallContextFieldTag::IsCreate, tx_is_create.expr()),
+                ] {
+                    cb.call_context_lookup(true.expr(), Some(call_id.expr()), field_tag, value);
+                }
+
+                cb.require_equal(
+                    "Go to TxAuthorization when Tx has authorizations",
+                    cb.next
+                        .execution_state_selector([ExecutionState::TxAuthorization]),
+                    1.expr(),
+                );
+
+                cb.require_step_state_transition(StepStateTransition {
+                    // 21 reads and writes, the ones of the call to account with non-empty code
+                    // except for the CallContext CodeHash write.
+                    rw_counter: Delta(
+                        21.expr()
+                            + l1_rw_delta.expr()
+                            + transfer_with_gas_fee.rw_delta()
+                            + tx_access_list.rw_delta_expr()
+                            + PRECOMPILE_COUNT.expr(),
+                    ),
+                    call_id: To(call_id.expr()),
+                    is_root: To(true.expr()),
+                    is_create: To(tx_is_create.expr()),
+                    gas_left: To(gas_left),
+                    reversible_write_counter: To(transfer_with_gas_fee.reversible_w_delta()),
+                    log_id: To(0.expr()),
+                    // The program counter holds the index of the authorization.
+                    program_counter: To(0.expr()),
+                    ..StepStateTransition::any()
+                });
+            },
+        );
+
         Self {
             tx_id,
             tx_type,
@@ -802,6 +963,9 @@ impl<F: Field> ExecutionGadget<F> for BeginTxGadget<F> {
             tx_gas,
             tx_gas_price,
             mul_gas_fee_by_gas,
+            tx_blob_hashes_len,
+            blob_base_fee,
+            mul_blob_fee_by_blob_gas,
             tx_fee,
             tx_caller_address,
             tx_caller_address_is_zero,
@@ -825,6 +989,13 @@ impl<F: Field> ExecutionGadget<F> for BeginTxGadget<F> {
             account_code_hash_is_zero,
             #[cfg(feature = "scroll")]
             account_keccak_code_hash,
+            authorization_list_len,
+            has_no_authorizations,
+            callee_delegation,
+            is_delegate_warm,
+            delegate_code_hash,
+            executed_code_hash_is_empty,
+            executed_code_hash_is_zero,
             call_code_hash,
             call_code_hash_is_empty,
             call_code_hash_is_zero,
@@ -952,6 +1123,58 @@ impl<F: Field> ExecutionGadget<F> for BeginTxGadget<F> {
         let is_coinbase_warm = rws.next().tx_access_list_value_pair().1;
 
         let account_code_hash = rws.next().account_codehash_pair().1;
+
+        let has_authorizations = !tx.authorization_list.is_empty();
+        let delegate = self.callee_delegation.assign_from_rws(
+            region,
+            offset,
+            block,
+            if tx.is_create || has_authorizations {
+                U256::zero()
+            } else {
+                account_code_hash
+            },
+            &mut rws,
+        )?;
+        let (is_delegate_warm, delegate_code_hash) = if delegate.is_some() {
+            (
+                rws.next().tx_access_list_value_pair().1,
+                rws.next().account_codehash_pair().0,
+            )
+        } else {
+            (false, U256::zero())
+        };
+        let executed_code_hash = if delegate.is_some() {
+            delegate_code_hash
+        } else {
+            account_code_hash
+        };
+        self.authorization_list_len.assign(
+            region,
+            offset,
+            Value::known(F::from(tx.authorization_list.len() as u64)),
+        )?;
+        self.has_no_authorizations.assign(
+            region,
+            offset,
+            F::from(tx.authorization_list.len() as u64),
+        )?;
+        self.is_delegate_warm
+            .assign(region, offset, Value::known(F::from(is_delegate_warm)))?;
+        self.delegate_code_hash
+            .assign(region, offset, region.code_hash(delegate_code_hash))?;
+        self.executed_code_hash_is_empty.assign_value(
+            region,
+            offset,
+            region.code_hash(executed_code_hash),
+            region.empty_code_hash_rlc(),
+        )?;
+        self.executed_code_hash_is_zero.assign_value(
+            region,
+            offset,
+            region.code_hash(executed_code_hash),
+        )?;
+
         let transfer_assign_result = self.transfer_with_gas_fee.assign_from_rws(
             region,
             offset,
@@ -1014,6 +1237,27 @@ impl<F: Field> ExecutionGadget<F> for BeginTxGadget<F> {
             tx.gas,
             tx.gas_price * tx.gas,
         )?;
+        // get blob_base_fee from block context
+        let blob_base_fee = block
+            .context
+            .ctxs
+            .get(&tx.block_number)
+            .expect("could not find block with number = {tx.block_number}")
+            .blob_base_fee;
+        self.tx_blob_hashes_len.assign(
+            region,
+            offset,
+            Value::known(F::from(tx.blob_versioned_hashes.len() as u64)),
+        )?;
+        self.blob_base_fee
+            .assign(region, offset, Some(blob_base_fee.to_le_bytes()))?;
+        self.mul_blob_fee_by_blob_gas.assign(
+            region,
+            offset,
+            blob_base_fee,
+            tx.blob_gas(),
+            blob_base_fee * tx.blob_gas(),
+        )?;
         let caller_address = tx
             .caller_address
             .to_scalar()
@@ -1031,8 +1275,12 @@ impl<F: Field> ExecutionGadget<F> for BeginTxGadget<F> {
             .assign(region, offset, Value::known(callee_address))?;
         self.tx_callee_address_is_zero
             .assign(region, offset, callee_address)?;
-        self.is_precompile_lt
-            .assign(region, offset, callee_address, F::from(0xA))?;
+        self.is_precompile_lt.assign(
+            region,
+            offset,
+            callee_address,
+            F::from(PRECOMPILE_COUNT as u64 + 1),
+        )?;
         // precompile related assignment.
         let (precompile_input_len, precompile_input_bytes_rlc) = if tx
             .callee_address
@@ -1218,9 +1466,9 @@ impl<F: Field> ExecutionGadget<F> for BeginTxGadget<F> {
             ),
         )?;
 
-        let (tx_l1_fee, tx_l2_fee) = if tx.tx_type.is_l1_msg() {
+        let (tx_l1_fee, tx_l2_fee, tx_blob_fee) = if tx.tx_type.is_l1_msg() {
             log::trace!("tx is l1msg and l1 fee is 0");
-            (U256::zero(), U256::zero())
+            (U256::zero(), U256::zero(), U256::zero())
         } else {
             (
                 tx.l1_fee
@@ -1228,14 +1476,16 @@ impl<F: Field> ExecutionGadget<F> for BeginTxGadget<F> {
                     .0
                     .into(),
                 tx.gas_price * tx.gas,
+                blob_base_fee * tx.blob_gas(),
             )
         };
-        if tx_fee != tx_l2_fee + tx_l1_fee {
+        if tx_fee != tx_l2_fee + tx_l1_fee + tx_blob_fee {
             log::error!(
-                "begin_tx assign: tx_fee ({}) != tx_l1_fee ({}) + tx_l2_fee ({})",
+                "begin_tx assign: tx_fee ({}) != tx_l1_fee ({}) + tx_l2_fee ({}) + tx_blob_fee ({})",
                 tx_fee,
                 tx_l1_fee,
-                tx_l2_fee
+                tx_l2_fee,
+                tx_blob_fee
             );
         }
 
@@ -1266,6 +1516,7 @@ impl<F: Field> ExecutionGadget<F> for BeginTxGadget<F> {
                 .unwrap()
                 .1,
             base_fee,
+            blob_base_fee,
         )
     }
 }
@@ -1277,7 +1528,9 @@ mod test {
     use crate::{evm_circuit::test::rand_bytes, test_util::CircuitTestBuilder};
     use bus_mapping::evm::OpcodeId;
     use eth_types::{
-        self, address, bytecode, evm_types::GasCost, word, Address, Bytecode, Hash, Word, U256,
+        self, address, bytecode,
+        evm_types::{delegation::delegation_designator, GasCost},
+        word, Address, Bytecode, Hash, Word, U256,
     };
     use ethers_core::{types::Bytes, utils::get_contract_address};
     use mock::{eth, gwei, MockTransaction, TestContext, MOCK_ACCOUNTS};
@@ -1725,6 +1978,43 @@ mod test {
         CircuitTestBuilder::new_from_test_ctx(ctx).run();
     }
 
+    #[test]
+    fn begin_tx_delegated_callee() {
+        // the callee delegates to MOCK_ACCOUNTS[2], whose code runs in the callee's context
+        for delegate_code in [code_with_return(), code_with_revert(), bytecode! {}] {
+            let ctx = TestContext::<3, 1>::new(
+                None,
+                |accs| {
+                    accs[0]
+                        .address(MOCK_ACCOUNTS[0])
+                        .balance(eth(10))
+                        .code(delegation_designator(&MOCK_ACCOUNTS[2]));
+                    accs[1].address(MOCK_ACCOUNTS[1]).balance(eth(10));
+                    accs[2].address(MOCK_ACCOUNTS[2]).code(delegate_code);
+                },
+                |mut txs, accs| {
+                    txs[0]
+                        .from(accs[1].address)
+                        .to(accs[0].address)
+                        .value(eth(1));
+                },
+                |block, _tx| block.number(0xcafeu64),
+            )
+            .unwrap();
+
+            CircuitTestBuilder::new_from_test_ctx(ctx).run();
+        }
+    }
+
+    #[test]
+    fn begin_tx_designator_sized_code() {
+        // code of the size of a delegation designator which is not one runs as is
+        test_ok(
+            mock_tx(eth(1), gwei(2), vec![]),
+            Some(Bytecode::from(vec![OpcodeId::JUMPDEST.as_u8(); 23])),
+        );
+    }
+
     // Test that we handle the case where account creation tx happens for an account that already
     // has a non-zero balance and codehash.
     // This is not possible in real world.
@@ -0,0 +1,186 @@
+use crate::{
+    evm_circuit::{
+        execution::ExecutionGadget,
+        param::N_BYTES_U64,
+        step::ExecutionState,
+        util::{
+            common_gadget::{SameContextGadget, WordByteCapGadget},
+            constraint_builder::{
+                ConstrainBuilderCommon, EVMConstraintBuilder, StepStateTransition,
+                Transition::Delta,
+            },
+            CachedRegion, Cell,
+        },
+        witness::{Block, Call, ExecStep, Transaction},
+    },
+    table::{CallContextFieldTag, TxContextFieldTag},
+    util::{Expr, Field},
+};
+use bus_mapping::evm::OpcodeId;
+use gadgets::util::not;
+use halo2_proofs::{circuit::Value, plonk::Error};
+
+#[derive(Clone, Debug)]
+pub(crate) struct BlobHashGadget<F> {
+    same_context: SameContextGadget<F>,
+    tx_id: Cell<F>,
+    blob_hashes_len: Cell<F>,
+    index: WordByteCapGadget<F, N_BYTES_U64>,
+    blob_hash: Cell<F>,
+}
+
+impl<F: Field> ExecutionGadget<F> for BlobHashGadget<F> {
+    const NAME: &'static str = "BLOBHASH";
+
+    const EXECUTION_STATE: ExecutionState = ExecutionState::BLOBHASH;
+
+    fn configure(cb: &mut EVMConstraintBuilder<F>) -> Self {
+        let tx_id = cb.call_context(None, CallContextFieldTag::TxId);
+        let blob_hashes_len = cb.tx_context(
+            tx_id.expr(),
+            TxContextFieldTag::BlobVersionedHashesLen,
+            None,
+        );
+
+        let index = WordByteCapGadget::construct(cb, blob_hashes_len.expr());
+        cb.stack_pop(index.original_word());
+
+        // The blob versioned hashes of a tx are 1-indexed in the tx table.
+        let blob_hash = cb.query_cell_phase2();
+        cb.condition(index.lt_cap(), |cb| {
+            cb.tx_context_lookup(
+                tx_id.expr(),
+                TxContextFieldTag::BlobVersionedHash,
+                Some(index.valid_value() + 1.expr()),
+                blob_hash.expr(),
+            );
+        });
+        cb.condition(not::expr(index.lt_cap()), |cb| {
+            cb.require_zero(
+                "BLOBHASH returns 0 for an out of range index",
+                blob_hash.expr(),
+            );
+        });
+
+        cb.stack_push(blob_hash.expr());
+
+        let step_state_transition = StepStateTransition {
+            rw_counter: Delta(3.expr()),
+            program_counter: Delta(1.expr()),
+            gas_left: Delta(-OpcodeId::BLOBHASH.constant_gas_cost().expr()),
+            ..Default::default()
+        };
+        let opcode = cb.query_cell();
+        let same_context = SameContextGadget::construct(cb, opcode, step_state_transition);
+
+        Self {
+            same_context,
+            tx_id,
+            blob_hashes_len,
+            index,
+            blob_hash,
+        }
+    }
+
+    fn assign_exec_step(
+        &self,
+        region: &mut CachedRegion<'_, '_, F>,
+        offset: usize,
+        block: &Block,
+        tx: &Transaction,
+        _: &Call,
+        step: &ExecStep,
+    ) -> Result<(), Error> {
+        self.same_context.assign_exec_step(region, offset, step)?;
+
+        let index = block.rws[step.rw_indices[1]].stack_value();
+        let blob_hash = block.rws[step.rw_indices[2]].stack_value();
+        let blob_hashes_len = F::from(tx.blob_versioned_hashes.len() as u64);
+
+        self.tx_id
+            .assign(region, offset, Value::known(F::from(tx.id as u64)))?;
+        self.blob_hashes_len
+            .assign(region, offset, Value::known(blob_hashes_len))?;
+        self.index.assign(region, offset, index, blob_hashes_len)?;
+        self.blob_hash
+            .assign(region, offset, region.word_rlc(blob_hash))?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::test_util::CircuitTestBuilder;
+    use eth_types::{bytecode, Word, H256};
+    use ethers_signers::Signer;
+    use mock::{eth, gwei, TestContext, MOCK_ACCOUNTS, MOCK_WALLETS};
+
+    fn test_ok(index: Word, blob_hashes: Vec<H256>) {
+        let bytecode = bytecode! {
+            PUSH32(index)
+            BLOBHASH
+            STOP
+        };
+
+        let ctx = TestContext::<2, 1>::new(
+            None,
+            |accs| {
+                accs[0].address(MOCK_ACCOUNTS[0]).code(bytecode);
+                accs[1].address(MOCK_WALLETS[0].address()).balance(eth(10));
+            },
+            |mut txs, _accs| {
+                txs[0]
+                    .from(MOCK_WALLETS[0].clone())
+                    .to(MOCK_ACCOUNTS[0])
+                    .max_fee_per_gas(gwei(2))
+                    .max_priority_fee_per_gas(gwei(2))
+                    .max_fee_per_blob_gas(gwei(1))
+                    .blob_versioned_hashes(blob_hashes)
+                    .transaction_type(3); // Set tx type to EIP-4844.
+            },
+            |block, _txs| block,
+        )
+        .unwrap();
+
+        CircuitTestBuilder::new_from_test_ctx(ctx).run();
+    }
+
+    fn blob_hashes() -> Vec<H256> {
+        // versioned hashes start with the KZG version byte 0x01
+        (1..=2u8)
+            .map(|i| {
+                let mut hash = [0u8; 32];
+                hash[0] = 0x01;
+                hash[31] = i;
+                H256(hash)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn blobhash_gadget_in_range() {
+        test_ok(Word::zero(), blob_hashes());
+        test_ok(Word::one(), blob_hashes());
+    }
+
+    #[test]
+    fn blobhash_gadget_out_of_range() {
+        test_ok(2.into(), blob_hashes());
+        test_ok(Word::MAX, blob_hashes());
+    }
+
+    #[test]
+    fn blobhash_gadget_non_blob_tx() {
+        let bytecode = bytecode! {
+            PUSH1(0)
+            BLOBHASH
+            STOP
+        };
+
+        CircuitTestBuilder::new_from_test_ctx(
+            TestContext::<2, 1>::simple_ctx_with_bytecode(bytecode).unwrap(),
+        )
+        .run();
+    }
+}
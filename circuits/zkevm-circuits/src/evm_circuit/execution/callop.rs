@@ -5,13 +5,16 @@ use crate::{
         step::ExecutionState,
         util::{
             and,
-            common_gadget::{CommonCallGadget, TransferGadget, TransferGadgetInfo},
+            common_gadget::{
+                CommonCallGadget, DelegationGadget, TransferGadget, TransferGadgetInfo,
+            },
             constraint_builder::{
                 ConstrainBuilderCommon, EVMConstraintBuilder, ReversionInfo, StepStateTransition,
                 Transition::{Delta, To},
             },
             math_gadget::{
-                ConstantDivisionGadget, IsZeroGadget, LtGadget, LtWordGadget, MinMaxGadget,
+                ConstantDivisionGadget, IsEqualGadget, IsZeroGadget, LtGadget, LtWordGadget,
+                MinMaxGadget,
             },
             memory_gadget::{CommonMemoryAddressGadget, MemoryAddressGadget},
             not, or,
@@ -27,7 +30,8 @@ use bus_mapping::{
     circuit_input_builder::CopyDataType, evm::OpcodeId, precompile::PrecompileCalls,
 };
 use eth_types::{
-    evm_types::{memory::MemoryWordRange, GAS_STIPEND_CALL_WITH_VALUE},
+    evm_types::{memory::MemoryWordRange, GasCost, GAS_STIPEND_CALL_WITH_VALUE},
+    forks::LAST_PRECOMPILE_ADDRESS,
     utils::is_precompiled,
     ToAddress, ToBigEndian, ToLittleEndian, U256,
 };
@@ -57,6 +61,12 @@ pub(crate) struct CallOpGadget<F> {
     current_value: Word<F>,
     is_warm: Cell<F>,
     is_warm_prev: Cell<F>,
+    // EIP7702
+    callee_delegation: DelegationGadget<F>,
+    is_delegate_warm_prev: Cell<F>,
+    delegate_code_hash: Cell<F>,
+    executed_code_hash_is_empty: IsEqualGadget<F>,
+    executed_code_hash_is_zero: IsZeroGadget<F>,
     callee_reversion_info: ReversionInfo<F>,
     transfer: TransferGadget<F>,
     code_hash_previous: Cell<F>,
@@ -163,6 +173,42 @@ impl<F: Field> ExecutionGadget<F> for CallOpGadget<F> {
         );
         // rwc_delta = 7 + is_delegatecall * 2 + call_gadget.rw_delta()
 
+        // EIP7702: a delegated callee runs the code of its delegate, which is warmed and charged
+        // like the callee.
+        let callee_delegation = DelegationGadget::construct(
+            cb,
+            call_gadget.callee_address_expr(),
+            call_gadget.phase2_callee_code_hash.expr(),
+            not::expr(call_gadget.is_empty_code_hash.expr() + call_gadget.callee_not_exists.expr()),
+        );
+        let is_delegate_warm_prev = cb.query_bool();
+        let delegate_code_hash = cb.query_cell_phase2();
+        cb.condition(callee_delegation.is_delegated(), |cb| {
+            cb.account_access_list_write(
+                tx_id.expr(),
+                callee_delegation.delegate(),
+                1.expr(),
+                is_delegate_warm_prev.expr(),
+                Some(&mut reversion_info),
+            );
+            cb.account_read(
+                callee_delegation.delegate(),
+                AccountFieldTag::CodeHash,
+                delegate_code_hash.expr(),
+            );
+        });
+        let delegation_rw_delta =
+            callee_delegation.rw_delta() + 2.expr() * callee_delegation.is_delegated();
+        // rwc_delta = 7 + is_delegatecall * 2 + call_gadget.rw_delta() + delegation_rw_delta
+        let executed_code_hash = select::expr(
+            callee_delegation.is_delegated(),
+            delegate_code_hash.expr(),
+            call_gadget.phase2_callee_code_hash.expr(),
+        );
+        let executed_code_hash_is_empty =
+            IsEqualGadget::construct(cb, executed_code_hash.clone(), cb.empty_code_hash_rlc());
+        let executed_code_hash_is_zero = IsZeroGadget::construct(cb, executed_code_hash.clone());
+
         // Propagate rw_counter_end_of_reversion and is_persistent
         let mut callee_reversion_info = cb.reversion_info_write(Some(callee_call_id.expr()));
         // rwc_delta = 7 + is_delegatecall * 2 + call_gadget.rw_delta() +
@@ -214,10 +260,13 @@ impl<F: Field> ExecutionGadget<F> for CallOpGadget<F> {
         });
 
         // whether the call is to a precompiled contract.
-        // precompile contracts are stored from address 0x01 to 0x09.
+        // precompile contracts are stored from address 0x01 to LAST_PRECOMPILE_ADDRESS.
         let is_code_address_zero = IsZeroGadget::construct(cb, call_gadget.callee_address_expr());
-        let is_precompile_lt =
-            LtGadget::construct(cb, call_gadget.callee_address_expr(), 0x0A.expr());
+        let is_precompile_lt = LtGadget::construct(
+            cb,
+            call_gadget.callee_address_expr(),
+            (LAST_PRECOMPILE_ADDRESS + 1).expr(),
+        );
         let is_precompile = and::expr([
             not::expr(is_code_address_zero.expr()),
             is_precompile_lt.expr(),
@@ -270,15 +319,19 @@ impl<F: Field> ExecutionGadget<F> for CallOpGadget<F> {
             );
         });
 
-        // no_callee_code is true when the account exists and has empty
-        // code hash, or when the account doesn't exist (which we encode with
-        // code_hash = 0).
-        let no_callee_code =
-            call_gadget.is_empty_code_hash.expr() + call_gadget.callee_not_exists.expr();
+        // no_callee_code is true when the executed code is empty, or when the account doesn't
+        // exist (which we encode with code_hash = 0).
+        let no_callee_code = executed_code_hash_is_empty.expr() + executed_code_hash_is_zero.expr();
 
         // Sum up and verify gas cost.
         // Only CALL opcode could invoke transfer to make empty account into non-empty.
-        let gas_cost = call_gadget.gas_cost_expr(is_warm_prev.expr(), is_call.expr());
+        let gas_cost = call_gadget.gas_cost_expr(is_warm_prev.expr(), is_call.expr())
+            + callee_delegation.is_delegated()
+                * select::expr(
+                    is_delegate_warm_prev.expr(),
+                    GasCost::WARM_ACCESS.expr(),
+                    GasCost::COLD_ACCOUNT_ACCESS.expr(),
+                );
         // Apply EIP 150
         let gas_available = cb.curr.state.gas_left.expr() - gas_cost.clone();
         let one_64th_gas = cb.annotation("one_64th_gas", |cb| {
@@ -301,9 +354,11 @@ impl<F: Field> ExecutionGadget<F> for CallOpGadget<F> {
         let rw_counter_delta = 8.expr()
             + is_delegatecall.expr() * 2.expr()
             + call_gadget.rw_delta()
+            + delegation_rw_delta
             + callee_reversion_info.rw_delta()
             + transfer_rwc_delta.expr();
-        let caller_reversible_rwc_delta = 1.expr(); // AccessList
+        // AccessList of the callee, and of its delegate if any
+        let caller_reversible_rwc_delta = 1.expr() + callee_delegation.is_delegated();
         let callee_reversible_rwc_delta = is_call.expr() * transfer.reversible_w_delta();
 
         // 1. handle precompile calls.
@@ -384,7 +439,8 @@ impl<F: Field> ExecutionGadget<F> for CallOpGadget<F> {
                     ),
                     (
                         CallContextFieldTag::ReversibleWriteCounter,
-                        cb.curr.state.reversible_write_counter.expr() + 1.expr(),
+                        cb.curr.state.reversible_write_counter.expr()
+                            + caller_reversible_rwc_delta.expr(),
                     ),
                     (CallContextFieldTag::LastCalleeId, callee_call_id.expr()),
                     (CallContextFieldTag::LastCalleeReturnDataOffset, 0.expr()),
@@ -624,7 +680,8 @@ impl<F: Field> ExecutionGadget<F> for CallOpGadget<F> {
                     ),
                     (
                         CallContextFieldTag::ReversibleWriteCounter,
-                        cb.curr.state.reversible_write_counter.expr() + 1.expr(),
+                        cb.curr.state.reversible_write_counter.expr()
+                            + caller_reversible_rwc_delta.expr(),
                     ),
                 ] {
                     cb.call_context_lookup(true.expr(), None, field_tag, value);
@@ -665,10 +722,7 @@ impl<F: Field> ExecutionGadget<F> for CallOpGadget<F> {
                     (CallContextFieldTag::LastCalleeReturnDataLength, 0.expr()),
                     (CallContextFieldTag::IsRoot, 0.expr()),
                     (CallContextFieldTag::IsCreate, 0.expr()),
-                    (
-                        CallContextFieldTag::CodeHash,
-                        call_gadget.phase2_callee_code_hash.expr(),
-                    ),
+                    (CallContextFieldTag::CodeHash, executed_code_hash.clone()),
                 ] {
                     cb.call_context_lookup(
                         true.expr(),
@@ -699,7 +753,7 @@ impl<F: Field> ExecutionGadget<F> for CallOpGadget<F> {
                     call_id: To(callee_call_id.expr()),
                     is_root: To(false.expr()),
                     is_create: To(false.expr()),
-                    code_hash: To(call_gadget.phase2_callee_code_hash.expr()),
+                    code_hash: To(executed_code_hash),
                     gas_left: To(callee_gas_left),
                     reversible_write_counter: To(callee_reversible_rwc_delta.expr()),
                     ..StepStateTransition::new_context()
@@ -723,6 +777,11 @@ impl<F: Field> ExecutionGadget<F> for CallOpGadget<F> {
             call: call_gadget,
             is_warm,
             is_warm_prev,
+            callee_delegation,
+            is_delegate_warm_prev,
+            delegate_code_hash,
+            executed_code_hash_is_empty,
+            executed_code_hash_is_zero,
             callee_reversion_info,
             transfer,
             code_hash_previous,
@@ -798,6 +857,45 @@ impl<F: Field> ExecutionGadget<F> for CallOpGadget<F> {
 
         let (is_warm, is_warm_prev) = rws.next().tx_access_list_value_pair();
 
+        let delegate = self.callee_delegation.assign_from_rws(
+            region,
+            offset,
+            block,
+            callee_code_hash,
+            &mut rws,
+        )?;
+        let (is_delegate_warm_prev, delegate_code_hash) = if delegate.is_some() {
+            (
+                rws.next().tx_access_list_value_pair().1,
+                rws.next().account_codehash_pair().0,
+            )
+        } else {
+            (false, U256::zero())
+        };
+        let executed_code_hash = if delegate.is_some() {
+            delegate_code_hash
+        } else {
+            callee_code_hash
+        };
+        self.is_delegate_warm_prev.assign(
+            region,
+            offset,
+            Value::known(F::from(is_delegate_warm_prev as u64)),
+        )?;
+        self.delegate_code_hash
+            .assign(region, offset, region.code_hash(delegate_code_hash))?;
+        self.executed_code_hash_is_empty.assign_value(
+            region,
+            offset,
+            region.code_hash(executed_code_hash),
+            region.empty_code_hash_rlc(),
+        )?;
+        self.executed_code_hash_is_zero.assign_value(
+            region,
+            offset,
+            region.code_hash(executed_code_hash),
+        )?;
+
         let [callee_rw_counter_end_of_reversion, callee_is_persistent] =
             [(); 2].map(|_| rws.next().call_context_value());
 
@@ -928,7 +1026,11 @@ impl<F: Field> ExecutionGadget<F> for CallOpGadget<F> {
             is_call,
             has_value,
             !callee_exists,
-        )?;
+        )? + match (delegate, is_delegate_warm_prev) {
+            (None, _) => 0,
+            (Some(_), true) => GasCost::WARM_ACCESS.as_u64(),
+            (Some(_), false) => GasCost::COLD_ACCOUNT_ACCESS.as_u64(),
+        };
         let gas_available = step.gas_left - gas_cost;
         self.one_64th_gas
             .assign(region, offset, gas_available.into())?;
@@ -948,8 +1050,12 @@ impl<F: Field> ExecutionGadget<F> for CallOpGadget<F> {
         let code_address: F = callee_address.to_address().to_scalar().unwrap();
         self.is_code_address_zero
             .assign(region, offset, code_address)?;
-        self.is_precompile_lt
-            .assign(region, offset, code_address, 0x0Au64.into())?;
+        self.is_precompile_lt.assign(
+            region,
+            offset,
+            code_address,
+            (LAST_PRECOMPILE_ADDRESS as u64 + 1).into(),
+        )?;
         log::trace!("callop is precompile call {}", is_precompile_call);
         let precompile_return_length = if is_precompile_call && is_precheck_ok {
             rws.offset_add(15); // skip
@@ -1153,7 +1259,10 @@ mod test {
     use crate::test_util::CircuitTestBuilder;
     use bus_mapping::circuit_input_builder::CircuitsParams;
     use eth_types::{
-        address, bytecode, evm_types::OpcodeId, geth_types::Account, word, Address, ToWord, Word,
+        address, bytecode,
+        evm_types::{delegation::delegation_designator, OpcodeId},
+        geth_types::Account,
+        word, Address, ToWord, Word,
     };
     use itertools::Itertools;
     use mock::{
@@ -1421,6 +1530,58 @@ mod test {
         }
     }
 
+    #[test]
+    fn callop_delegated_callee() {
+        // the callee delegates to 0xfd, whose code runs in the callee's context
+        let delegate = Address::repeat_byte(0xfd);
+        for opcode in TEST_CALL_OPCODES {
+            let caller = caller(
+                opcode,
+                Stack {
+                    gas: 100000,
+                    ..Default::default()
+                },
+                true,
+            );
+            let ctx = TestContext::<4, 1>::new(
+                None,
+                |accs| {
+                    accs[0]
+                        .address(address!("0x000000000000000000000000000000000000cafe"))
+                        .balance(Word::from(10u64.pow(19)));
+                    accs[1]
+                        .address(caller.address)
+                        .code(caller.code.clone())
+                        .balance(caller.balance);
+                    accs[2]
+                        .address(Address::repeat_byte(0xff))
+                        .code(delegation_designator(&delegate))
+                        .nonce(1.into());
+                    accs[3].address(delegate).code(bytecode! {
+                        PUSH1(0)
+                        PUSH1(0)
+                        RETURN
+                    });
+                },
+                |mut txs, accs| {
+                    txs[0]
+                        .from(accs[0].address)
+                        .to(accs[1].address)
+                        .gas(200000.into());
+                },
+                |block, _tx| block.number(0xcafeu64),
+            )
+            .unwrap();
+
+            CircuitTestBuilder::new_from_test_ctx(ctx)
+                .params(CircuitsParams {
+                    max_rws: 1000,
+                    ..Default::default()
+                })
+                .run();
+        }
+    }
+
     #[test]
     fn callop_base() {
         test_ok(
@@ -4,11 +4,11 @@ use crate::{
         param::N_BYTES_GAS,
         step::ExecutionState,
         util::{
-            common_gadget::{CommonCallGadget, CommonErrorGadget},
+            common_gadget::{CommonCallGadget, CommonErrorGadget, DelegationGadget},
             constraint_builder::{ConstrainBuilderCommon, EVMConstraintBuilder},
             math_gadget::{IsZeroGadget, LtGadget},
             memory_gadget::MemoryExpandedAddressGadget,
-            or, CachedRegion, Cell, StepRws,
+            not, or, select, CachedRegion, Cell, StepRws,
         },
     },
     table::CallContextFieldTag,
@@ -16,7 +16,7 @@ use crate::{
     witness::{Block, Call, ExecStep, Transaction},
 };
 use bus_mapping::evm::OpcodeId;
-use eth_types::U256;
+use eth_types::{evm_types::GasCost, U256};
 use halo2_proofs::{circuit::Value, plonk::Error};
 
 /// Gadget to implement the corresponding out of gas errors for
@@ -33,6 +33,8 @@ pub(crate) struct ErrorOOGCallGadget<F> {
     is_static: Cell<F>,
     is_warm: Cell<F>,
     call: CommonCallGadget<F, MemoryExpandedAddressGadget<F>, false>,
+    callee_delegation: DelegationGadget<F>,
+    is_delegate_warm: Cell<F>,
     insufficient_gas: LtGadget<F, N_BYTES_GAS>,
     common_error_gadget: CommonErrorGadget<F>,
 }
@@ -72,6 +74,22 @@ impl<F: Field> ExecutionGadget<F> for ErrorOOGCallGadget<F> {
             is_warm.expr(),
         );
 
+        // EIP7702: the delegate of a delegated callee is charged like the callee
+        let callee_delegation = DelegationGadget::construct(
+            cb,
+            call_gadget.callee_address_expr(),
+            call_gadget.phase2_callee_code_hash.expr(),
+            not::expr(call_gadget.is_empty_code_hash.expr() + call_gadget.callee_not_exists.expr()),
+        );
+        let is_delegate_warm = cb.query_bool();
+        cb.condition(callee_delegation.is_delegated(), |cb| {
+            cb.account_access_list_read(
+                tx_id.expr(),
+                callee_delegation.delegate(),
+                is_delegate_warm.expr(),
+            );
+        });
+
         cb.condition(is_call.expr() * call_gadget.has_value.expr(), |cb| {
             cb.require_zero(
                 "CALL with value must not be in static call stack",
@@ -80,7 +98,13 @@ impl<F: Field> ExecutionGadget<F> for ErrorOOGCallGadget<F> {
         });
 
         // Verify gas cost
-        let gas_cost = call_gadget.gas_cost_expr(is_warm.expr(), is_call.expr());
+        let gas_cost = call_gadget.gas_cost_expr(is_warm.expr(), is_call.expr())
+            + callee_delegation.is_delegated()
+                * select::expr(
+                    is_delegate_warm.expr(),
+                    GasCost::WARM_ACCESS.expr(),
+                    GasCost::COLD_ACCOUNT_ACCESS.expr(),
+                );
 
         // Check if the amount of gas available is less than the amount of gas required
         let insufficient_gas = LtGadget::construct(cb, cb.curr.state.gas_left.expr(), gas_cost);
@@ -96,11 +120,15 @@ impl<F: Field> ExecutionGadget<F> for ErrorOOGCallGadget<F> {
         );
 
         // Both CALL and CALLCODE opcodes have an extra stack pop `value` relative to
-        // DELEGATECALL and STATICCALL.
+        // DELEGATECALL and STATICCALL. A delegated callee reads the access list of its delegate.
         let common_error_gadget = CommonErrorGadget::construct(
             cb,
             opcode.expr(),
-            13.expr() + is_call.expr() + is_callcode.expr(),
+            13.expr()
+                + is_call.expr()
+                + is_callcode.expr()
+                + callee_delegation.rw_delta()
+                + callee_delegation.is_delegated(),
         );
 
         Self {
@@ -113,6 +141,8 @@ impl<F: Field> ExecutionGadget<F> for ErrorOOGCallGadget<F> {
             is_static,
             is_warm,
             call: call_gadget,
+            callee_delegation,
+            is_delegate_warm,
             insufficient_gas,
             common_error_gadget,
         }
@@ -151,6 +181,14 @@ impl<F: Field> ExecutionGadget<F> for ErrorOOGCallGadget<F> {
         let callee_code_hash = rws.next().account_value_pair().0;
         let callee_exists = !callee_code_hash.is_zero();
         let (is_warm, is_warm_prev) = rws.next().tx_access_list_value_pair();
+        let delegate = self.callee_delegation.assign_from_rws(
+            region,
+            offset,
+            block,
+            callee_code_hash,
+            &mut rws,
+        )?;
+        let is_delegate_warm = delegate.is_some() && rws.next().tx_access_list_value_pair().0;
 
         let memory_expansion_gas_cost = self.call.assign(
             region,
@@ -199,6 +237,11 @@ impl<F: Field> ExecutionGadget<F> for ErrorOOGCallGadget<F> {
 
         self.is_warm
             .assign(region, offset, Value::known(F::from(is_warm as u64)))?;
+        self.is_delegate_warm.assign(
+            region,
+            offset,
+            Value::known(F::from(is_delegate_warm as u64)),
+        )?;
 
         let has_value = !value.is_zero();
         let gas_cost = self.call.cal_gas_cost_for_assignment(
@@ -207,7 +250,11 @@ impl<F: Field> ExecutionGadget<F> for ErrorOOGCallGadget<F> {
             is_call,
             has_value,
             !callee_exists,
-        )?;
+        )? + match (delegate, is_delegate_warm) {
+            (None, _) => 0,
+            (Some(_), true) => GasCost::WARM_ACCESS.as_u64(),
+            (Some(_), false) => GasCost::COLD_ACCOUNT_ACCESS.as_u64(),
+        };
 
         self.insufficient_gas.assign_value(
             region,
@@ -224,7 +271,12 @@ impl<F: Field> ExecutionGadget<F> for ErrorOOGCallGadget<F> {
             block,
             call,
             step,
-            13 + if is_call || is_callcode { 1 } else { 0 },
+            13 + if is_call || is_callcode { 1 } else { 0 }
+                + if delegate.is_some() {
+                    1 + cfg!(feature = "scroll") as usize
+                } else {
+                    0
+                },
         )?;
         Ok(())
     }
@@ -234,8 +286,11 @@ impl<F: Field> ExecutionGadget<F> for ErrorOOGCallGadget<F> {
 mod test {
     use crate::test_util::CircuitTestBuilder;
     use eth_types::{
-        address, bytecode, bytecode::Bytecode, evm_types::OpcodeId, geth_types::Account, Address,
-        ToWord, Word,
+        address, bytecode,
+        bytecode::Bytecode,
+        evm_types::{delegation::delegation_designator, OpcodeId},
+        geth_types::Account,
+        Address, ToWord, Word,
     };
     use mock::TestContext;
     use std::default::Default;
@@ -303,7 +358,10 @@ mod test {
     }
 
     fn test_oog(caller: &Account, callee: &Account, is_root: bool) {
-        let tx_gas = if is_root { 21100 } else { 25000 };
+        test_oog_with_gas(caller, callee, if is_root { 21100 } else { 25000 });
+    }
+
+    fn test_oog_with_gas(caller: &Account, callee: &Account, tx_gas: u64) {
         let ctx = TestContext::<3, 1>::new(
             None,
             |accs| {
@@ -384,6 +442,28 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_oog_call_delegated_callee() {
+        let stack = Stack {
+            gas: 100.into(),
+            cd_offset: 64,
+            cd_length: 320,
+            rd_offset: 0,
+            rd_length: 32,
+            ..Default::default()
+        };
+        let callee = Account {
+            address: Address::repeat_byte(0xff),
+            code: delegation_designator(&Address::repeat_byte(0xfd)).into(),
+            nonce: 1.into(),
+            ..Default::default()
+        };
+        // enough gas to access the cold callee, but not its cold delegate
+        for opcode in TEST_CALL_OPCODES {
+            test_oog_with_gas(&caller(*opcode, stack), &callee, 24000);
+        }
+    }
+
     #[test]
     fn test_oog_call_with_overflow_gas() {
         let stack = Stack {
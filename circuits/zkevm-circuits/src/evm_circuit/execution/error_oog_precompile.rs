@@ -101,6 +101,14 @@ impl<F: Field> ExecutionGadget<F> for ErrorOOGPrecompileGadget<F> {
                 GasCost::PRECOMPILE_BN256PAIRING.expr()
                     + n_pairs.quotient() * GasCost::PRECOMPILE_BN256PAIRING_PER_PAIR.expr(),
             ),
+            (
+                addr_bits.value_equals(PrecompileCalls::PointEvaluation),
+                GasCost::PRECOMPILE_POINT_EVALUATION.expr(),
+            ),
+            (
+                addr_bits.value_equals(PrecompileCalls::Bls12G1Add),
+                GasCost::PRECOMPILE_BLS12_G1ADD.expr(),
+            ),
         ];
 
         cb.require_equal(
@@ -209,9 +217,11 @@ impl<F: Field> ExecutionGadget<F> for ErrorOOGPrecompileGadget<F> {
                 precompile_call.base_gas_cost().as_u64()
                     + n_words * GasCost::PRECOMPILE_SHA256_PER_WORD.as_u64()
             }
-            PrecompileCalls::Bn128Add | PrecompileCalls::Bn128Mul | PrecompileCalls::Ecrecover => {
-                precompile_call.base_gas_cost().as_u64()
-            }
+            PrecompileCalls::Bn128Add
+            | PrecompileCalls::Bn128Mul
+            | PrecompileCalls::Ecrecover
+            | PrecompileCalls::PointEvaluation
+            | PrecompileCalls::Bls12G1Add => precompile_call.base_gas_cost().as_u64(),
             _ => unreachable!(),
         };
 
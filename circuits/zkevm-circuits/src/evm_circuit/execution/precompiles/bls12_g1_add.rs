@@ -0,0 +1,367 @@
+use crate::util::Field;
+use bus_mapping::precompile::{PrecompileAuxData, PrecompileCalls, BLS12_G1_ADD_INPUT_LEN};
+use eth_types::evm_types::GasCost;
+use gadgets::{
+    util::{not, select, Expr},
+    ToScalar,
+};
+use halo2_proofs::{circuit::Value, plonk::Error};
+
+use crate::{
+    evm_circuit::{
+        execution::ExecutionGadget,
+        step::ExecutionState,
+        util::{
+            common_gadget::RestoreContextGadget,
+            constraint_builder::{ConstrainBuilderCommon, EVMConstraintBuilder},
+            math_gadget::IsEqualGadget,
+            rlc, CachedRegion, Cell,
+        },
+    },
+    table::CallContextFieldTag,
+    witness::{Block, Call, ExecStep, Transaction},
+};
+
+/// Gadget for the BLS12-381 G1 addition precompile of EIP-2537.
+///
+/// The input is `p_x | p_y | q_x | q_y`, each coordinate a 64 bytes big-endian field element. The
+/// call fails unless the input is exactly 256 bytes long. For inputs of the right length, the
+/// validity of the points and the resulting point `R = P + Q` are checked by a lookup to the
+/// BLS12-381 table.
+#[derive(Clone, Debug)]
+pub struct Bls12G1AddGadget<F> {
+    input_bytes_rlc: Cell<F>,
+    output_bytes_rlc: Cell<F>,
+    return_bytes_rlc: Cell<F>,
+
+    input_len_is_valid: IsEqualGadget<F>,
+
+    is_success: Cell<F>,
+    callee_address: Cell<F>,
+    is_root: Cell<F>,
+    call_data_offset: Cell<F>,
+    call_data_length: Cell<F>,
+    return_data_offset: Cell<F>,
+    return_data_length: Cell<F>,
+    restore_context: RestoreContextGadget<F>,
+}
+
+impl<F: Field> ExecutionGadget<F> for Bls12G1AddGadget<F> {
+    const EXECUTION_STATE: ExecutionState = ExecutionState::PrecompileBls12G1Add;
+
+    const NAME: &'static str = "BLS12_G1ADD";
+
+    fn configure(cb: &mut EVMConstraintBuilder<F>) -> Self {
+        let (input_bytes_rlc, output_bytes_rlc, return_bytes_rlc) = (
+            cb.query_cell_phase2(),
+            cb.query_cell_phase2(),
+            cb.query_cell_phase2(),
+        );
+
+        let [is_success, callee_address, is_root, call_data_offset, call_data_length, return_data_offset, return_data_length] =
+            [
+                CallContextFieldTag::IsSuccess,
+                CallContextFieldTag::CalleeAddress,
+                CallContextFieldTag::IsRoot,
+                CallContextFieldTag::CallDataOffset,
+                CallContextFieldTag::CallDataLength,
+                CallContextFieldTag::ReturnDataOffset,
+                CallContextFieldTag::ReturnDataLength,
+            ]
+            .map(|tag| cb.call_context(None, tag));
+
+        // all gas sent to this call will be consumed if `is_success == false`.
+        let gas_cost = select::expr(
+            is_success.expr(),
+            GasCost::PRECOMPILE_BLS12_G1ADD.expr(),
+            cb.curr.state.gas_left.expr(),
+        );
+
+        cb.precompile_info_lookup(
+            cb.execution_state().as_u64().expr(),
+            callee_address.expr(),
+            cb.execution_state().precompile_base_gas_cost().expr(),
+        );
+
+        let input_len_is_valid =
+            IsEqualGadget::construct(cb, call_data_length.expr(), BLS12_G1_ADD_INPUT_LEN.expr());
+        cb.condition(input_len_is_valid.expr(), |cb| {
+            cb.bls12_381_table_lookup(
+                u64::from(PrecompileCalls::Bls12G1Add).expr(),
+                is_success.expr(),
+                input_bytes_rlc.expr(),
+                output_bytes_rlc.expr(),
+            );
+        });
+        cb.condition(not::expr(input_len_is_valid.expr()), |cb| {
+            cb.require_zero(
+                "input of invalid length implies call failure",
+                is_success.expr(),
+            );
+        });
+        cb.condition(not::expr(is_success.expr()), |cb| {
+            cb.require_zero("no output on failure", output_bytes_rlc.expr());
+        });
+
+        let restore_context = super::gen_restore_context(
+            cb,
+            is_root.expr(),
+            is_success.expr(),
+            gas_cost.expr(),
+            select::expr(is_success.expr(), 0x80.expr(), 0x00.expr()), // ReturnDataLength
+        );
+
+        Self {
+            input_bytes_rlc,
+            output_bytes_rlc,
+            return_bytes_rlc,
+
+            input_len_is_valid,
+
+            is_success,
+            callee_address,
+            is_root,
+            call_data_offset,
+            call_data_length,
+            return_data_offset,
+            return_data_length,
+            restore_context,
+        }
+    }
+
+    fn assign_exec_step(
+        &self,
+        region: &mut CachedRegion<'_, '_, F>,
+        offset: usize,
+        block: &Block,
+        _tx: &Transaction,
+        call: &Call,
+        step: &ExecStep,
+    ) -> Result<(), Error> {
+        if let Some(PrecompileAuxData::Base {
+            input_bytes,
+            output_bytes,
+            return_bytes,
+        }) = &step.aux_data
+        {
+            let keccak_rand = region.challenges().keccak_input();
+            for (col, bytes) in [
+                (&self.input_bytes_rlc, input_bytes),
+                (&self.output_bytes_rlc, output_bytes),
+                (&self.return_bytes_rlc, return_bytes),
+            ] {
+                col.assign(
+                    region,
+                    offset,
+                    keccak_rand.map(|r| rlc::value(bytes.iter().rev(), r)),
+                )?;
+            }
+        } else {
+            log::error!("unexpected aux_data {:?} for bls12 g1add", step.aux_data);
+            return Err(Error::Synthesis);
+        }
+
+        self.input_len_is_valid.assign(
+            region,
+            offset,
+            F::from(call.call_data_length),
+            F::from(BLS12_G1_ADD_INPUT_LEN as u64),
+        )?;
+
+        self.is_success.assign(
+            region,
+            offset,
+            Value::known(F::from(u64::from(call.is_success))),
+        )?;
+        self.callee_address.assign(
+            region,
+            offset,
+            Value::known(call.code_address.unwrap().to_scalar().unwrap()),
+        )?;
+        self.is_root
+            .assign(region, offset, Value::known(F::from(call.is_root as u64)))?;
+        self.call_data_offset.assign(
+            region,
+            offset,
+            Value::known(F::from(call.call_data_offset)),
+        )?;
+        self.call_data_length.assign(
+            region,
+            offset,
+            Value::known(F::from(call.call_data_length)),
+        )?;
+        self.return_data_offset.assign(
+            region,
+            offset,
+            Value::known(F::from(call.return_data_offset)),
+        )?;
+        self.return_data_length.assign(
+            region,
+            offset,
+            Value::known(F::from(call.return_data_length)),
+        )?;
+
+        self.restore_context
+            .assign(region, offset, block, call, step, 7)
+    }
+}
+
+#[cfg(all(test, not(feature = "scroll")))]
+mod test {
+    use bus_mapping::{
+        evm::{OpcodeId, PrecompileCallArgs},
+        precompile::PrecompileCalls,
+    };
+    use eth_types::{bytecode, word, Bytecode, ToWord};
+    use itertools::Itertools;
+    use mock::TestContext;
+    use std::sync::LazyLock;
+
+    use crate::test_util::CircuitTestBuilder;
+
+    /// Place the generator `G` of G1 as point P in memory, i.e. `[0x00, 0x80)`.
+    fn generator_as_p() -> Bytecode {
+        bytecode! {
+            // p_x
+            PUSH16(word!("0x17f1d3a73197d7942695638c4fa9ac0f"))
+            PUSH1(0x00)
+            MSTORE
+            PUSH32(word!("0xc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb"))
+            PUSH1(0x20)
+            MSTORE
+            // p_y
+            PUSH16(word!("0x08b3f481e3aaa0f1a09e30ed741d8ae4"))
+            PUSH1(0x40)
+            MSTORE
+            PUSH32(word!("0xfcf5e095d5d00af600db18cb2c04b3edd03cc744a2888ae40caa232946c5e7e1"))
+            PUSH1(0x60)
+            MSTORE
+        }
+    }
+
+    static TEST_VECTOR: LazyLock<Vec<PrecompileCallArgs>> = LazyLock::new(|| {
+        vec![
+            PrecompileCallArgs {
+                name: "bls12 g1add (valid inputs: P = G, Q = infinity)",
+                setup_code: generator_as_p(),
+                call_data_offset: 0x00.into(),
+                call_data_length: 0x100.into(),
+                ret_offset: 0x100.into(),
+                ret_size: 0x80.into(),
+                address: PrecompileCalls::Bls12G1Add.address().to_word(),
+                ..Default::default()
+            },
+            PrecompileCallArgs {
+                name: "bls12 g1add (valid inputs: P = G, Q = -G)",
+                setup_code: {
+                    let mut code = generator_as_p();
+                    code.append(&bytecode! {
+                        // q_x = p_x
+                        PUSH16(word!("0x17f1d3a73197d7942695638c4fa9ac0f"))
+                        PUSH1(0x80)
+                        MSTORE
+                        PUSH32(word!("0xc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb"))
+                        PUSH1(0xa0)
+                        MSTORE
+                        // q_y = p - p_y
+                        PUSH16(word!("0x114d1d6855d545a8aa7d76c8cf2e21f2"))
+                        PUSH1(0xc0)
+                        MSTORE
+                        PUSH32(word!("0x67816aef1db507c96655b9d5caac42364e6f38ba0ecb751bad54dcd6b939c2ca"))
+                        PUSH1(0xe0)
+                        MSTORE
+                    });
+                    code
+                },
+                call_data_offset: 0x00.into(),
+                call_data_length: 0x100.into(),
+                ret_offset: 0x100.into(),
+                ret_size: 0x80.into(),
+                address: PrecompileCalls::Bls12G1Add.address().to_word(),
+                ..Default::default()
+            },
+            PrecompileCallArgs {
+                name: "bls12 g1add (invalid input: point not on curve)",
+                // P = (1, 2), Q = infinity
+                setup_code: bytecode! {
+                    PUSH1(0x01)
+                    PUSH1(0x20)
+                    MSTORE
+                    PUSH1(0x02)
+                    PUSH1(0x60)
+                    MSTORE
+                },
+                call_data_offset: 0x00.into(),
+                call_data_length: 0x100.into(),
+                ret_offset: 0x100.into(),
+                ret_size: 0x80.into(),
+                gas: 1000.into(),
+                address: PrecompileCalls::Bls12G1Add.address().to_word(),
+                ..Default::default()
+            },
+            PrecompileCallArgs {
+                name: "bls12 g1add (invalid input: truncated input)",
+                setup_code: generator_as_p(),
+                call_data_offset: 0x00.into(),
+                call_data_length: 0xff.into(),
+                ret_offset: 0x100.into(),
+                ret_size: 0x80.into(),
+                gas: 1000.into(),
+                address: PrecompileCalls::Bls12G1Add.address().to_word(),
+                ..Default::default()
+            },
+        ]
+    });
+
+    static OOG_TEST_VECTOR: LazyLock<Vec<PrecompileCallArgs>> = LazyLock::new(|| {
+        vec![PrecompileCallArgs {
+            name: "bls12 g1add OOG (valid inputs: P = G, Q = infinity)",
+            setup_code: generator_as_p(),
+            call_data_offset: 0x00.into(),
+            call_data_length: 0x100.into(),
+            ret_offset: 0x100.into(),
+            ret_size: 0x80.into(),
+            address: PrecompileCalls::Bls12G1Add.address().to_word(),
+            gas: 499.into(),
+            ..Default::default()
+        }]
+    });
+
+    #[test]
+    fn precompile_bls12_g1_add_test() {
+        let call_kinds = vec![
+            OpcodeId::CALL,
+            OpcodeId::STATICCALL,
+            OpcodeId::DELEGATECALL,
+            OpcodeId::CALLCODE,
+        ];
+
+        TEST_VECTOR
+            .iter()
+            .cartesian_product(&call_kinds)
+            .for_each(|(test_vector, &call_kind)| {
+                let bytecode = test_vector.with_call_op(call_kind);
+                log::info!("TESTING {}", test_vector.name);
+
+                CircuitTestBuilder::new_from_test_ctx(
+                    TestContext::<2, 1>::simple_ctx_with_bytecode(bytecode).unwrap(),
+                )
+                .run();
+            })
+    }
+
+    #[test]
+    fn precompile_bls12_g1_add_oog_test() {
+        OOG_TEST_VECTOR
+            .iter()
+            .cartesian_product(&[OpcodeId::CALL, OpcodeId::STATICCALL])
+            .for_each(|(test_vector, &call_kind)| {
+                let bytecode = test_vector.with_call_op(call_kind);
+
+                CircuitTestBuilder::new_from_test_ctx(
+                    TestContext::<2, 1>::simple_ctx_with_bytecode(bytecode).unwrap(),
+                )
+                .run();
+            })
+    }
+}
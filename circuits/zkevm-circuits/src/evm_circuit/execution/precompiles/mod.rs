@@ -40,6 +40,12 @@ pub use identity::IdentityGadget;
 mod sha256;
 pub use sha256::SHA256Gadget;
 
+mod point_evaluation;
+pub use point_evaluation::PointEvaluationGadget;
+
+mod bls12_g1_add;
+pub use bls12_g1_add::Bls12G1AddGadget;
+
 /// build RestoreContextGadget with consideration for root calling
 /// MUST be called after all rw has completed since we use `rw_counter_offset``
 pub fn gen_restore_context<F: Field>(
@@ -0,0 +1,520 @@
+use crate::util::Field;
+use bus_mapping::precompile::{
+    point_evaluation_output, PrecompileAuxData, POINT_EVALUATION_INPUT_LEN,
+    VERSIONED_HASH_VERSION_KZG,
+};
+use eth_types::{evm_types::GasCost, ToBigEndian};
+use gadgets::{
+    util::{and, select, Expr},
+    ToScalar,
+};
+use halo2_proofs::{circuit::Value, plonk::Error};
+
+use crate::{
+    evm_circuit::{
+        execution::ExecutionGadget,
+        step::ExecutionState,
+        util::{
+            common_gadget::RestoreContextGadget,
+            constraint_builder::{ConstrainBuilderCommon, EVMConstraintBuilder},
+            math_gadget::IsEqualGadget,
+            rlc, CachedRegion, Cell,
+        },
+    },
+    table::CallContextFieldTag,
+    witness::{Block, Call, ExecStep, Transaction},
+};
+
+/// Bytes of a KZG commitment or proof.
+const N_BYTES_G1_COMPRESSED: usize = 48;
+
+/// Gadget for the point evaluation precompile of EIP-4844.
+///
+/// The input is `versioned_hash | z | y | commitment | proof`. The call succeeds iff the input is
+/// 192 bytes, the versioned hash has the KZG version and matches the sha256 of the commitment (via
+/// the sha256 table), and the KZG proof verifies. The output of a successful call is the constant
+/// `FIELD_ELEMENTS_PER_BLOB | BLS_MODULUS`.
+///
+/// The KZG opening itself (`p(z) == y`) is witnessed by `is_kzg_valid`: it needs a BLS12-381
+/// pairing check, which neither the ECC circuit nor the BLS12-381 circuit supports. Until it is
+/// constrained, bus-mapping rejects calls to the precompile and this gadget is never assigned.
+#[derive(Clone, Debug)]
+pub struct PointEvaluationGadget<F> {
+    input_bytes_rlc: Cell<F>,
+    output_bytes_rlc: Cell<F>,
+    return_bytes_rlc: Cell<F>,
+
+    version: Cell<F>,
+    versioned_hash_tail_rlc: Cell<F>,
+    commitment_digest_first_byte: Cell<F>,
+    commitment_digest_tail_rlc: Cell<F>,
+    z_rlc: Cell<F>,
+    y_rlc: Cell<F>,
+    commitment_rlc: Cell<F>,
+    proof_rlc: Cell<F>,
+
+    is_valid_length: IsEqualGadget<F>,
+    is_valid_version: IsEqualGadget<F>,
+    is_valid_hash: IsEqualGadget<F>,
+    is_kzg_valid: Cell<F>,
+
+    is_success: Cell<F>,
+    callee_address: Cell<F>,
+    is_root: Cell<F>,
+    call_data_offset: Cell<F>,
+    call_data_length: Cell<F>,
+    return_data_offset: Cell<F>,
+    return_data_length: Cell<F>,
+    restore_context: RestoreContextGadget<F>,
+}
+
+impl<F: Field> ExecutionGadget<F> for PointEvaluationGadget<F> {
+    const EXECUTION_STATE: ExecutionState = ExecutionState::PrecompilePointEvaluation;
+
+    const NAME: &'static str = "POINT_EVALUATION";
+
+    fn configure(cb: &mut EVMConstraintBuilder<F>) -> Self {
+        let (input_bytes_rlc, output_bytes_rlc, return_bytes_rlc) = (
+            cb.query_cell_phase2(),
+            cb.query_cell_phase2(),
+            cb.query_cell_phase2(),
+        );
+        let (
+            version,
+            versioned_hash_tail_rlc,
+            commitment_digest_first_byte,
+            commitment_digest_tail_rlc,
+        ) = (
+            cb.query_byte(),
+            cb.query_cell_phase2(),
+            cb.query_byte(),
+            cb.query_cell_phase2(),
+        );
+        let (z_rlc, y_rlc, commitment_rlc, proof_rlc) = (
+            cb.query_cell_phase2(),
+            cb.query_cell_phase2(),
+            cb.query_cell_phase2(),
+            cb.query_cell_phase2(),
+        );
+
+        let [is_success, callee_address, is_root, call_data_offset, call_data_length, return_data_offset, return_data_length] =
+            [
+                CallContextFieldTag::IsSuccess,
+                CallContextFieldTag::CalleeAddress,
+                CallContextFieldTag::IsRoot,
+                CallContextFieldTag::CallDataOffset,
+                CallContextFieldTag::CallDataLength,
+                CallContextFieldTag::ReturnDataOffset,
+                CallContextFieldTag::ReturnDataLength,
+            ]
+            .map(|tag| cb.call_context(None, tag));
+
+        let gas_cost = select::expr(
+            is_success.expr(),
+            GasCost::PRECOMPILE_POINT_EVALUATION.expr(),
+            cb.curr.state.gas_left.expr(),
+        );
+
+        cb.precompile_info_lookup(
+            cb.execution_state().as_u64().expr(),
+            callee_address.expr(),
+            cb.execution_state().precompile_base_gas_cost().expr(),
+        );
+
+        let (r_pow_31, r_pow_48, r_pow_96, r_pow_128, r_pow_160) = {
+            let challenges = cb.challenges().keccak_powers_of_randomness::<16>();
+            let r_pow_16 = challenges[15].clone();
+            let r_pow_31 = challenges[14].clone() * r_pow_16.expr();
+            let r_pow_32 = r_pow_16.clone().square();
+            let r_pow_48 = r_pow_32.expr() * r_pow_16;
+            let r_pow_96 = r_pow_48.expr().square();
+            let r_pow_128 = r_pow_96.expr() * r_pow_32.expr();
+            let r_pow_160 = r_pow_128.expr() * r_pow_32;
+            (r_pow_31, r_pow_48, r_pow_96, r_pow_128, r_pow_160)
+        };
+
+        let is_valid_length = IsEqualGadget::construct(
+            cb,
+            call_data_length.expr(),
+            POINT_EVALUATION_INPUT_LEN.expr(),
+        );
+        cb.condition(is_valid_length.expr(), |cb| {
+            cb.require_equal(
+                "input bytes (RLC) = [versioned_hash | z | y | commitment | proof]",
+                input_bytes_rlc.expr(),
+                (version.expr() * r_pow_31.expr() + versioned_hash_tail_rlc.expr()) * r_pow_160
+                    + z_rlc.expr() * r_pow_128
+                    + y_rlc.expr() * r_pow_96
+                    + commitment_rlc.expr() * r_pow_48
+                    + proof_rlc.expr(),
+            );
+            cb.sha256_table_lookup(
+                commitment_rlc.expr(),
+                N_BYTES_G1_COMPRESSED.expr(),
+                commitment_digest_first_byte.expr() * r_pow_31.expr()
+                    + commitment_digest_tail_rlc.expr(),
+            );
+        });
+
+        // versioned_hash == VERSIONED_HASH_VERSION_KZG | sha256(commitment)[1..]
+        let is_valid_version =
+            IsEqualGadget::construct(cb, version.expr(), VERSIONED_HASH_VERSION_KZG.expr());
+        let is_valid_hash = IsEqualGadget::construct(
+            cb,
+            versioned_hash_tail_rlc.expr(),
+            commitment_digest_tail_rlc.expr(),
+        );
+        let is_kzg_valid = cb.query_bool();
+        cb.require_equal(
+            "is_success == input is valid and the KZG proof verifies",
+            is_success.expr(),
+            and::expr([
+                is_valid_length.expr(),
+                is_valid_version.expr(),
+                is_valid_hash.expr(),
+                is_kzg_valid.expr(),
+            ]),
+        );
+
+        cb.condition(is_success.expr(), |cb| {
+            let mut output: [u8; 64] = point_evaluation_output()
+                .try_into()
+                .expect("point evaluation output is 64 bytes");
+            output.reverse();
+            let output_rlc = cb.keccak_rlc::<64>(output.map(|byte| byte.expr()));
+            cb.require_equal(
+                "output bytes (RLC) = [FIELD_ELEMENTS_PER_BLOB | BLS_MODULUS]",
+                output_bytes_rlc.expr(),
+                output_rlc,
+            );
+        });
+
+        let restore_context = super::gen_restore_context(
+            cb,
+            is_root.expr(),
+            is_success.expr(),
+            gas_cost.expr(),
+            select::expr(is_success.expr(), 0x40.expr(), 0x00.expr()), // ReturnDataLength
+        );
+
+        Self {
+            input_bytes_rlc,
+            output_bytes_rlc,
+            return_bytes_rlc,
+
+            version,
+            versioned_hash_tail_rlc,
+            commitment_digest_first_byte,
+            commitment_digest_tail_rlc,
+            z_rlc,
+            y_rlc,
+            commitment_rlc,
+            proof_rlc,
+
+            is_valid_length,
+            is_valid_version,
+            is_valid_hash,
+            is_kzg_valid,
+
+            is_success,
+            callee_address,
+            is_root,
+            call_data_offset,
+            call_data_length,
+            return_data_offset,
+            return_data_length,
+            restore_context,
+        }
+    }
+
+    fn assign_exec_step(
+        &self,
+        region: &mut CachedRegion<'_, '_, F>,
+        offset: usize,
+        block: &Block,
+        _tx: &Transaction,
+        call: &Call,
+        step: &ExecStep,
+    ) -> Result<(), Error> {
+        if let Some(PrecompileAuxData::PointEvaluation(aux_data)) = &step.aux_data {
+            let z = aux_data.z.to_be_bytes();
+            let y = aux_data.y.to_be_bytes();
+            for (col, bytes) in [
+                (&self.input_bytes_rlc, aux_data.input_bytes.as_slice()),
+                (&self.output_bytes_rlc, aux_data.output_bytes.as_slice()),
+                (&self.return_bytes_rlc, aux_data.return_bytes.as_slice()),
+                (
+                    &self.versioned_hash_tail_rlc,
+                    &aux_data.versioned_hash.as_bytes()[1..],
+                ),
+                (
+                    &self.commitment_digest_tail_rlc,
+                    &aux_data.commitment_digest.as_bytes()[1..],
+                ),
+                (&self.z_rlc, z.as_slice()),
+                (&self.y_rlc, y.as_slice()),
+                (&self.commitment_rlc, aux_data.commitment.as_slice()),
+                (&self.proof_rlc, aux_data.proof.as_slice()),
+            ] {
+                col.assign(
+                    region,
+                    offset,
+                    region
+                        .challenges()
+                        .keccak_input()
+                        .map(|r| rlc::value(bytes.iter().rev(), r)),
+                )?;
+            }
+            let version = F::from(aux_data.versioned_hash[0] as u64);
+            self.version.assign(region, offset, Value::known(version))?;
+            self.is_valid_version.assign(
+                region,
+                offset,
+                version,
+                F::from(VERSIONED_HASH_VERSION_KZG as u64),
+            )?;
+            let keccak_input = region.challenges().keccak_input();
+            self.is_valid_hash.assign_value(
+                region,
+                offset,
+                keccak_input
+                    .map(|r| rlc::value(aux_data.versioned_hash.as_bytes()[1..].iter().rev(), r)),
+                keccak_input.map(|r| {
+                    rlc::value(aux_data.commitment_digest.as_bytes()[1..].iter().rev(), r)
+                }),
+            )?;
+            self.commitment_digest_first_byte.assign(
+                region,
+                offset,
+                Value::known(F::from(aux_data.commitment_digest[0] as u64)),
+            )?;
+        } else {
+            log::error!(
+                "unexpected aux_data {:?} for point evaluation",
+                step.aux_data
+            );
+            return Err(Error::Synthesis);
+        }
+
+        self.is_valid_length.assign(
+            region,
+            offset,
+            F::from(call.call_data_length),
+            F::from(POINT_EVALUATION_INPUT_LEN as u64),
+        )?;
+        // a valid input fails only on the KZG proof
+        self.is_kzg_valid.assign(
+            region,
+            offset,
+            Value::known(F::from(u64::from(call.is_success))),
+        )?;
+        self.is_success.assign(
+            region,
+            offset,
+            Value::known(F::from(u64::from(call.is_success))),
+        )?;
+        self.callee_address.assign(
+            region,
+            offset,
+            Value::known(call.code_address.unwrap().to_scalar().unwrap()),
+        )?;
+        self.is_root
+            .assign(region, offset, Value::known(F::from(call.is_root as u64)))?;
+        self.call_data_offset.assign(
+            region,
+            offset,
+            Value::known(F::from(call.call_data_offset)),
+        )?;
+        self.call_data_length.assign(
+            region,
+            offset,
+            Value::known(F::from(call.call_data_length)),
+        )?;
+        self.return_data_offset.assign(
+            region,
+            offset,
+            Value::known(F::from(call.return_data_offset)),
+        )?;
+        self.return_data_length.assign(
+            region,
+            offset,
+            Value::known(F::from(call.return_data_length)),
+        )?;
+        self.restore_context
+            .assign(region, offset, block, call, step, 7)
+    }
+}
+
+#[cfg(all(test, not(feature = "scroll")))]
+mod test {
+    use bus_mapping::{
+        evm::{OpcodeId, PrecompileCallArgs},
+        precompile::{kzg_to_versioned_hash, PrecompileCalls},
+    };
+    use eth_types::{bytecode, evm_types::GasCost, word, ToWord, Word};
+    use itertools::Itertools;
+    use mock::TestContext;
+    use std::sync::LazyLock;
+
+    use crate::test_util::CircuitTestBuilder;
+
+    /// Commitment to (and proof for) the zero polynomial, i.e. the compressed point at infinity.
+    static ZERO_COMMITMENT: LazyLock<Vec<u8>> = LazyLock::new(|| {
+        let mut commitment = vec![0u8; 48];
+        commitment[0] = 0xc0;
+        commitment
+    });
+
+    /// Place `versioned_hash(0) | z = 0 | y | commitment(0) | proof(0)` in memory, a valid opening
+    /// for y = 0.
+    fn zero_polynomial_setup(versioned_hash: Word, y: Word) -> eth_types::Bytecode {
+        bytecode! {
+            PUSH32(versioned_hash)
+            PUSH1(0x00)
+            MSTORE
+            PUSH32(y)
+            PUSH1(0x40)
+            MSTORE
+            // commitment, from 0x60
+            PUSH32(word!("0xc000000000000000000000000000000000000000000000000000000000000000"))
+            PUSH1(0x60)
+            MSTORE
+            // proof, from 0x90
+            PUSH16(word!("0xc0000000000000000000000000000000"))
+            PUSH1(0x80)
+            MSTORE
+        }
+    }
+
+    static TEST_VECTOR: LazyLock<Vec<PrecompileCallArgs>> = LazyLock::new(|| {
+        let versioned_hash =
+            Word::from_big_endian(kzg_to_versioned_hash(&ZERO_COMMITMENT).as_bytes());
+        vec![
+            PrecompileCallArgs {
+                name: "zero polynomial",
+                setup_code: zero_polynomial_setup(versioned_hash, Word::zero()),
+                call_data_offset: 0x00.into(),
+                call_data_length: 0xc0.into(),
+                ret_offset: 0xc0.into(),
+                ret_size: 0x40.into(),
+                address: PrecompileCalls::PointEvaluation.address().to_word(),
+                ..Default::default()
+            },
+            PrecompileCallArgs {
+                name: "zero polynomial, truncated return",
+                setup_code: zero_polynomial_setup(versioned_hash, Word::zero()),
+                call_data_offset: 0x00.into(),
+                call_data_length: 0xc0.into(),
+                ret_offset: 0xc0.into(),
+                ret_size: 0x20.into(),
+                address: PrecompileCalls::PointEvaluation.address().to_word(),
+                ..Default::default()
+            },
+            PrecompileCallArgs {
+                name: "invalid input length",
+                setup_code: zero_polynomial_setup(versioned_hash, Word::zero()),
+                call_data_offset: 0x00.into(),
+                call_data_length: 0xbf.into(),
+                ret_offset: 0xc0.into(),
+                ret_size: 0x40.into(),
+                address: PrecompileCalls::PointEvaluation.address().to_word(),
+                ..Default::default()
+            },
+            PrecompileCallArgs {
+                name: "invalid kzg proof",
+                setup_code: zero_polynomial_setup(versioned_hash, Word::one()),
+                call_data_offset: 0x00.into(),
+                call_data_length: 0xc0.into(),
+                ret_offset: 0xc0.into(),
+                ret_size: 0x40.into(),
+                address: PrecompileCalls::PointEvaluation.address().to_word(),
+                ..Default::default()
+            },
+            PrecompileCallArgs {
+                name: "invalid versioned hash",
+                setup_code: zero_polynomial_setup(Word::one(), Word::zero()),
+                call_data_offset: 0x00.into(),
+                call_data_length: 0xc0.into(),
+                ret_offset: 0xc0.into(),
+                ret_size: 0x40.into(),
+                address: PrecompileCalls::PointEvaluation.address().to_word(),
+                ..Default::default()
+            },
+        ]
+    });
+
+    static OOG_TEST_VECTOR: LazyLock<Vec<PrecompileCallArgs>> = LazyLock::new(|| {
+        let versioned_hash =
+            Word::from_big_endian(kzg_to_versioned_hash(&ZERO_COMMITMENT).as_bytes());
+        vec![PrecompileCallArgs {
+            name: "oog",
+            setup_code: zero_polynomial_setup(versioned_hash, Word::zero()),
+            call_data_offset: 0x00.into(),
+            call_data_length: 0xc0.into(),
+            ret_offset: 0xc0.into(),
+            ret_size: 0x40.into(),
+            address: PrecompileCalls::PointEvaluation.address().to_word(),
+            gas: (GasCost::PRECOMPILE_POINT_EVALUATION.as_u64() - 1).into(),
+            ..Default::default()
+        }]
+    });
+
+    #[test]
+    #[ignore = "bus-mapping rejects point evaluation calls until the KZG opening is constrained"]
+    fn precompile_point_evaluation_test() {
+        let call_kinds = vec![
+            OpcodeId::CALL,
+            OpcodeId::STATICCALL,
+            OpcodeId::DELEGATECALL,
+            OpcodeId::CALLCODE,
+        ];
+
+        for (test_vector, &call_kind) in TEST_VECTOR.iter().cartesian_product(&call_kinds) {
+            let bytecode = test_vector.with_call_op(call_kind);
+
+            CircuitTestBuilder::new_from_test_ctx(
+                TestContext::<2, 1>::simple_ctx_with_bytecode(bytecode).unwrap(),
+            )
+            .run();
+        }
+    }
+
+    #[test]
+    #[ignore = "bus-mapping rejects point evaluation calls until the KZG opening is constrained"]
+    fn precompile_point_evaluation_sha256_event_test() {
+        let bytecode = TEST_VECTOR[0].with_call_op(OpcodeId::STATICCALL);
+
+        CircuitTestBuilder::new_from_test_ctx(
+            TestContext::<2, 1>::simple_ctx_with_bytecode(bytecode).unwrap(),
+        )
+        .block_modifier(Box::new(|blk| {
+            let evts = blk.get_sha256();
+            assert_eq!(evts.len(), 1);
+            assert_eq!(evts[0].input, *ZERO_COMMITMENT);
+        }))
+        .run();
+    }
+
+    #[test]
+    #[ignore = "bus-mapping rejects point evaluation calls until the KZG opening is constrained"]
+    fn precompile_point_evaluation_oog_test() {
+        let call_kinds = vec![
+            OpcodeId::CALL,
+            OpcodeId::STATICCALL,
+            OpcodeId::DELEGATECALL,
+            OpcodeId::CALLCODE,
+        ];
+
+        for (test_vector, &call_kind) in OOG_TEST_VECTOR.iter().cartesian_product(&call_kinds) {
+            let bytecode = test_vector.with_call_op(call_kind);
+
+            CircuitTestBuilder::new_from_test_ctx(
+                TestContext::<2, 1>::simple_ctx_with_bytecode(bytecode).unwrap(),
+            )
+            .block_modifier(Box::new(|blk| {
+                assert_eq!(blk.get_sha256().len(), 0);
+            }))
+            .run();
+        }
+    }
+}
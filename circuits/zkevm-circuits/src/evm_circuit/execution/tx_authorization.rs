@@ -0,0 +1,901 @@
+use crate::{
+    evm_circuit::{
+        execution::ExecutionGadget,
+        param::N_BYTES_ACCOUNT_ADDRESS,
+        step::ExecutionState,
+        util::{
+            and,
+            common_gadget::DelegationGadget,
+            constraint_builder::{
+                ConstrainBuilderCommon, EVMConstraintBuilder, StepStateTransition,
+                Transition::{Delta, Same, To},
+            },
+            from_bytes,
+            math_gadget::{
+                AuthorizationMessageGadget, IsEqualGadget, IsZeroGadget, LtWordGadget, ModGadget,
+            },
+            not, select, sum, CachedRegion, Cell, RandomLinearCombination, StepRws, Word,
+        },
+        witness::{Block, Call, ExecStep, Transaction},
+    },
+    table::{AccountFieldTag, CallContextFieldTag, TxFieldTag as TxContextFieldTag},
+    util::{Expr, Field},
+};
+use eth_types::{
+    evm_types::{delegation::DELEGATION_CODE_SIZE, GasCost},
+    state_db::CodeDB,
+    word, ToLittleEndian, ToWord, U256,
+};
+use ethers_core::utils::keccak256;
+use gadgets::ToScalar;
+use halo2_proofs::{circuit::Value, plonk::Error};
+use std::sync::LazyLock;
+
+static FQ_MODULUS: LazyLock<U256> =
+    LazyLock::new(|| word!("0xfffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141"));
+/// Exclusive upper bound of the `s` value of an authorization signature, i.e. `n / 2 + 1`.
+static SIG_S_BOUND: LazyLock<U256> = LazyLock::new(|| *FQ_MODULUS / 2 + 1);
+
+/// Gadget applying one EIP7702 authorization of a set-code tx, after its `BeginTx` step. The
+/// authority is recovered through the sig table, and the authorization is skipped if it is
+/// invalid. The last authorization step also resolves the code run by the root call, which the
+/// authorizations may have delegated.
+#[derive(Clone, Debug)]
+pub(crate) struct TxAuthorizationGadget<F> {
+    tx_id: Cell<F>,
+    tx_chain_id: Cell<F>,
+    authorization_list_len: Cell<F>,
+    is_last: IsEqualGadget<F>,
+    message: AuthorizationMessageGadget<F>,
+    chain_id_is_zero: IsZeroGadget<F>,
+    chain_id_is_tx: IsEqualGadget<F>,
+    nonce_is_max: IsEqualGadget<F>,
+    msg_hash_raw: Word<F>,
+    msg_hash: Word<F>,
+    fq_modulus: Word<F>,
+    msg_hash_mod: ModGadget<F, true>,
+    sig_v: Cell<F>,
+    sig_v_is_zero: IsZeroGadget<F>,
+    sig_v_is_one: IsEqualGadget<F>,
+    sig_r: Word<F>,
+    sig_r_is_zero: IsZeroGadget<F>,
+    sig_r_canonical: LtWordGadget<F>,
+    sig_s: Word<F>,
+    sig_s_is_zero: IsZeroGadget<F>,
+    sig_s_bound: Word<F>,
+    sig_s_low: LtWordGadget<F>,
+    recovered: Cell<F>,
+    authority: RandomLinearCombination<F, N_BYTES_ACCOUNT_ADDRESS>,
+    // Authority's state, read when the authority is recovered.
+    is_authority_warm_prev: Cell<F>,
+    authority_code_hash: Cell<F>,
+    authority_code_hash_is_zero: IsZeroGadget<F>,
+    authority_code_hash_is_empty: IsEqualGadget<F>,
+    authority_delegation: DelegationGadget<F>,
+    authority_nonce: Cell<F>,
+    authority_nonce_matches: IsEqualGadget<F>,
+    // Code installed in the authority, written when the authorization is valid.
+    address_is_zero: IsZeroGadget<F>,
+    code_hash: Cell<F>,
+    #[cfg(feature = "scroll")]
+    keccak_code_hash: Cell<F>,
+    #[cfg(feature = "scroll")]
+    keccak_code_hash_prev: Cell<F>,
+    #[cfg(feature = "scroll")]
+    code_size_prev: Cell<F>,
+    refund: Cell<F>,
+    // Code run by the root call, resolved by the last authorization step.
+    callee_address: Cell<F>,
+    callee_code_hash: Cell<F>,
+    callee_delegation: DelegationGadget<F>,
+    is_delegate_warm_prev: Cell<F>,
+    delegate_code_hash: Cell<F>,
+    call_code_hash_is_zero: IsZeroGadget<F>,
+    call_code_hash_is_empty: IsEqualGadget<F>,
+    is_persistent: Cell<F>,
+}
+
+impl<F: Field> ExecutionGadget<F> for TxAuthorizationGadget<F> {
+    const NAME: &'static str = "TxAuthorization";
+
+    const EXECUTION_STATE: ExecutionState = ExecutionState::TxAuthorization;
+
+    fn configure(cb: &mut EVMConstraintBuilder<F>) -> Self {
+        let tx_id = cb.call_context(None, CallContextFieldTag::TxId);
+        let [tx_chain_id, authorization_list_len, callee_address] = [
+            TxContextFieldTag::ChainID,
+            TxContextFieldTag::AuthorizationListLen,
+            TxContextFieldTag::CalleeAddress,
+        ]
+        .map(|field_tag| cb.tx_context(tx_id.expr(), field_tag, None));
+
+        // The program counter holds the index of the authorization.
+        let is_last = IsEqualGadget::construct(
+            cb,
+            cb.curr.state.program_counter.expr() + 1.expr(),
+            authorization_list_len.expr(),
+        );
+
+        let message = AuthorizationMessageGadget::construct(cb);
+        let chain_id_is_zero = IsZeroGadget::construct(cb, message.chain_id());
+        let chain_id_is_tx = IsEqualGadget::construct(cb, message.chain_id(), tx_chain_id.expr());
+        let nonce_is_max = IsEqualGadget::construct(cb, message.nonce(), u64::MAX.expr());
+        let is_chain_id_valid = chain_id_is_zero.expr() + chain_id_is_tx.expr()
+            - chain_id_is_zero.expr() * chain_id_is_tx.expr();
+
+        let msg_hash_raw = cb.query_word_rlc();
+        let msg_hash = cb.query_word_rlc();
+        let fq_modulus = cb.query_word_rlc();
+        let msg_hash_mod = ModGadget::construct(cb, [&msg_hash_raw, &fq_modulus, &msg_hash]);
+        cb.require_equal(
+            "Secp256k1::Fq modulus assigned correctly",
+            fq_modulus.expr(),
+            cb.word_rlc::<32>(FQ_MODULUS.to_le_bytes().map(|b| b.expr())),
+        );
+
+        // y_parity is 0 or 1, 0 < r < n and 0 < s <= n / 2
+        let sig_v = cb.query_cell();
+        let sig_v_is_zero = IsZeroGadget::construct(cb, sig_v.expr());
+        let sig_v_is_one = IsEqualGadget::construct(cb, sig_v.expr(), 1.expr());
+        let sig_r = cb.query_word_rlc();
+        let sig_r_is_zero = IsZeroGadget::construct(cb, sum::expr(&sig_r.cells));
+        let sig_r_canonical = LtWordGadget::construct(cb, &sig_r, &fq_modulus);
+        let sig_s = cb.query_word_rlc();
+        let sig_s_is_zero = IsZeroGadget::construct(cb, sum::expr(&sig_s.cells));
+        let sig_s_bound = cb.query_word_rlc();
+        cb.require_equal(
+            "signature s bound assigned correctly",
+            sig_s_bound.expr(),
+            cb.word_rlc::<32>(SIG_S_BOUND.to_le_bytes().map(|b| b.expr())),
+        );
+        let sig_s_low = LtWordGadget::construct(cb, &sig_s, &sig_s_bound);
+
+        // The authorization is read from the tx table, where its index is pc + 1.
+        for (field_tag, value) in [
+            (TxContextFieldTag::AuthorizationChainId, message.chain_id()),
+            (TxContextFieldTag::AuthorizationAddress, message.address()),
+            (TxContextFieldTag::AuthorizationNonce, message.nonce()),
+            (TxContextFieldTag::AuthorizationYParity, sig_v.expr()),
+            (TxContextFieldTag::AuthorizationR, sig_r.expr()),
+            (TxContextFieldTag::AuthorizationS, sig_s.expr()),
+        ] {
+            cb.tx_context_lookup(
+                tx_id.expr(),
+                field_tag,
+                Some(cb.curr.state.program_counter.expr() + 1.expr()),
+                value,
+            );
+        }
+        let is_signature_valid = and::expr([
+            sig_v_is_zero.expr() + sig_v_is_one.expr(),
+            not::expr(sig_r_is_zero.expr()),
+            sig_r_canonical.expr(),
+            not::expr(sig_s_is_zero.expr()),
+            sig_s_low.expr(),
+        ]);
+
+        // lookup to the sign_verify table:
+        //
+        // || msg_hash | v | r | s | authority | recovered ||
+        let recovered = cb.query_bool();
+        let authority = cb.query_keccak_rlc();
+        cb.condition(is_signature_valid.expr(), |cb| {
+            cb.keccak_table_lookup(
+                message.input_rlc(cb),
+                message.input_length(),
+                msg_hash_raw.expr(),
+            );
+            cb.sig_table_lookup(
+                msg_hash.expr(),
+                sig_v.expr(),
+                sig_r.expr(),
+                sig_s.expr(),
+                select::expr(
+                    recovered.expr(),
+                    from_bytes::expr(&authority.cells),
+                    0.expr(),
+                ),
+                recovered.expr(),
+            );
+        });
+        cb.condition(not::expr(is_signature_valid), |cb| {
+            cb.require_zero(
+                "recovered == false if the signature values are out of range",
+                recovered.expr(),
+            );
+        });
+        cb.condition(not::expr(recovered.expr()), |cb| {
+            cb.require_zero(
+                "authority == 0 if it could not be recovered",
+                authority.expr(),
+            );
+        });
+
+        // 1. Read the state of the authority, which is warmed.
+        let is_recovered = and::expr([
+            is_chain_id_valid,
+            not::expr(nonce_is_max.expr()),
+            recovered.expr(),
+        ]);
+        let authority_address = from_bytes::expr(&authority.cells);
+        let is_authority_warm_prev = cb.query_bool();
+        let authority_code_hash = cb.query_cell_phase2();
+        let authority_code_hash_is_zero = IsZeroGadget::construct(cb, authority_code_hash.expr());
+        let authority_code_hash_is_empty =
+            IsEqualGadget::construct(cb, authority_code_hash.expr(), cb.empty_code_hash_rlc());
+        let authority_exists = not::expr(authority_code_hash_is_zero.expr());
+        let authority_nonce = cb.query_cell();
+        let authority_delegation = cb.condition(is_recovered.expr(), |cb| {
+            cb.account_access_list_write(
+                tx_id.expr(),
+                authority_address.expr(),
+                1.expr(),
+                is_authority_warm_prev.expr(),
+                None,
+            );
+            cb.account_read(
+                authority_address.expr(),
+                AccountFieldTag::CodeHash,
+                authority_code_hash.expr(),
+            );
+            let authority_delegation = DelegationGadget::construct(
+                cb,
+                authority_address.expr(),
+                authority_code_hash.expr(),
+                and::expr([
+                    authority_exists.expr(),
+                    not::expr(authority_code_hash_is_empty.expr()),
+                ]),
+            );
+            cb.condition(authority_exists.expr(), |cb| {
+                cb.account_read(
+                    authority_address.expr(),
+                    AccountFieldTag::Nonce,
+                    authority_nonce.expr(),
+                );
+            });
+            cb.condition(not::expr(authority_exists.expr()), |cb| {
+                cb.require_zero(
+                    "nonce of a non-existing authority is 0",
+                    authority_nonce.expr(),
+                );
+            });
+            authority_delegation
+        });
+        let authority_nonce_matches =
+            IsEqualGadget::construct(cb, authority_nonce.expr(), message.nonce());
+
+        // 2. Install the delegation designator if the authority has no code, or is already
+        // delegated, and its nonce matches.
+        let is_valid = and::expr([
+            is_recovered,
+            authority_code_hash_is_zero.expr()
+                + authority_code_hash_is_empty.expr()
+                + authority_delegation.is_delegated(),
+            authority_nonce_matches.expr(),
+        ]);
+        let address_is_zero = IsZeroGadget::construct(cb, message.address());
+        let code_hash = cb.query_cell_phase2();
+        #[cfg(feature = "scroll")]
+        let keccak_code_hash = cb.query_cell_phase2();
+        #[cfg(feature = "scroll")]
+        let keccak_code_hash_prev = cb.query_cell_phase2();
+        #[cfg(feature = "scroll")]
+        let code_size_prev = cb.query_cell();
+        let refund = cb.query_cell();
+        cb.condition(is_valid, |cb| {
+            cb.condition(not::expr(authority_exists.expr()), |cb| {
+                cb.account_write(
+                    authority_address.expr(),
+                    AccountFieldTag::CodeHash,
+                    cb.empty_code_hash_rlc(),
+                    0.expr(),
+                    None,
+                );
+                #[cfg(feature = "scroll")]
+                cb.account_write(
+                    authority_address.expr(),
+                    AccountFieldTag::KeccakCodeHash,
+                    cb.empty_keccak_hash_rlc(),
+                    0.expr(),
+                    None,
+                );
+            });
+            cb.account_write(
+                authority_address.expr(),
+                AccountFieldTag::Nonce,
+                authority_nonce.expr() + 1.expr(),
+                authority_nonce.expr(),
+                None,
+            );
+
+            // A zero address clears the delegation.
+            cb.condition(address_is_zero.expr(), |cb| {
+                cb.require_equal(
+                    "code hash is empty when clearing the delegation",
+                    code_hash.expr(),
+                    cb.empty_code_hash_rlc(),
+                );
+                #[cfg(feature = "scroll")]
+                cb.require_equal(
+                    "keccak code hash is empty when clearing the delegation",
+                    keccak_code_hash.expr(),
+                    cb.empty_keccak_hash_rlc(),
+                );
+            });
+            cb.condition(not::expr(address_is_zero.expr()), |cb| {
+                cb.keccak_table_lookup(
+                    DelegationGadget::designator_keccak_rlc(cb, message.address_keccak_rlc()),
+                    DELEGATION_CODE_SIZE.expr(),
+                    #[cfg(feature = "scroll")]
+                    keccak_code_hash.expr(),
+                    #[cfg(not(feature = "scroll"))]
+                    code_hash.expr(),
+                );
+            });
+            cb.account_write(
+                authority_address.expr(),
+                AccountFieldTag::CodeHash,
+                code_hash.expr(),
+                select::expr(
+                    authority_exists.expr(),
+                    authority_code_hash.expr(),
+                    cb.empty_code_hash_rlc(),
+                ),
+                None,
+            );
+            #[cfg(feature = "scroll")]
+            {
+                cb.account_write(
+                    authority_address.expr(),
+                    AccountFieldTag::KeccakCodeHash,
+                    keccak_code_hash.expr(),
+                    keccak_code_hash_prev.expr(),
+                    None,
+                );
+                cb.account_write(
+                    authority_address.expr(),
+                    AccountFieldTag::CodeSize,
+                    select::expr(
+                        address_is_zero.expr(),
+                        0.expr(),
+                        DELEGATION_CODE_SIZE.expr(),
+                    ),
+                    code_size_prev.expr(),
+                    None,
+                );
+            }
+
+            // The intrinsic gas charged the cost of a new account, which is refunded in part
+            // when the authority exists.
+            cb.condition(authority_exists.expr(), |cb| {
+                cb.tx_refund_write(
+                    tx_id.expr(),
+                    refund.expr() + GasCost::PER_EMPTY_ACCOUNT.expr()
+                        - GasCost::PER_AUTH_BASE.expr(),
+                    refund.expr(),
+                    None,
+                );
+            });
+        });
+
+        // 3. The last step resolves the code run by the root call, following the delegation of
+        // the callee.
+        let callee_code_hash = cb.query_cell_phase2();
+        let callee_code_hash_is_zero = IsZeroGadget::construct(cb, callee_code_hash.expr());
+        let callee_code_hash_is_empty =
+            IsEqualGadget::construct(cb, callee_code_hash.expr(), cb.empty_code_hash_rlc());
+        let is_delegate_warm_prev = cb.query_bool();
+        let delegate_code_hash = cb.query_cell_phase2();
+        let callee_delegation = cb.condition(is_last.expr(), |cb| {
+            cb.account_read(
+                callee_address.expr(),
+                AccountFieldTag::CodeHash,
+                callee_code_hash.expr(),
+            );
+            let callee_delegation = DelegationGadget::construct(
+                cb,
+                callee_address.expr(),
+                callee_code_hash.expr(),
+                not::expr(callee_code_hash_is_zero.expr() + callee_code_hash_is_empty.expr()),
+            );
+            cb.condition(callee_delegation.is_delegated(), |cb| {
+                cb.account_access_list_write(
+                    tx_id.expr(),
+                    callee_delegation.delegate(),
+                    1.expr(),
+                    is_delegate_warm_prev.expr(),
+                    None,
+                );
+                cb.account_read(
+                    callee_delegation.delegate(),
+                    AccountFieldTag::CodeHash,
+                    delegate_code_hash.expr(),
+                );
+            });
+            callee_delegation
+        });
+        let call_code_hash = select::expr(
+            callee_delegation.is_delegated(),
+            delegate_code_hash.expr(),
+            callee_code_hash.expr(),
+        );
+        let call_code_hash_is_zero = IsZeroGadget::construct(cb, call_code_hash.expr());
+        let call_code_hash_is_empty =
+            IsEqualGadget::construct(cb, call_code_hash.expr(), cb.empty_code_hash_rlc());
+        let call_code_hash_is_empty_or_zero =
+            call_code_hash_is_zero.expr() + call_code_hash_is_empty.expr();
+
+        let is_persistent = cb.query_bool();
+        cb.condition(
+            and::expr([is_last.expr(), call_code_hash_is_empty_or_zero.expr()]),
+            |cb| {
+                cb.call_context_lookup(
+                    false.expr(),
+                    None,
+                    CallContextFieldTag::IsPersistent,
+                    is_persistent.expr(),
+                );
+                cb.require_equal(
+                    "Tx to account with empty code should be persistent",
+                    is_persistent.expr(),
+                    1.expr(),
+                );
+                cb.require_equal(
+                    "Go to EndTx when Tx to account with empty code",
+                    cb.next.execution_state_selector([ExecutionState::EndTx]),
+                    1.expr(),
+                );
+                cb.require_step_state_transition(StepStateTransition {
+                    rw_counter: Delta(cb.rw_counter_offset()),
+                    gas_left: Same,
+                    end_tx: To(1.expr()),
+                    ..StepStateTransition::any()
+                });
+            },
+        );
+        cb.condition(
+            and::expr([
+                is_last.expr(),
+                not::expr(call_code_hash_is_empty_or_zero.expr()),
+            ]),
+            |cb| {
+                cb.call_context_lookup(
+                    true.expr(),
+                    None,
+                    CallContextFieldTag::CodeHash,
+                    call_code_hash.expr(),
+                );
+                cb.require_step_state_transition(StepStateTransition {
+                    rw_counter: Delta(cb.rw_counter_offset()),
+                    code_hash: To(call_code_hash.expr()),
+                    ..StepStateTransition::new_context()
+                });
+            },
+        );
+        cb.condition(not::expr(is_last.expr()), |cb| {
+            cb.require_equal(
+                "Go to the next TxAuthorization when authorizations are left",
+                cb.next
+                    .execution_state_selector([ExecutionState::TxAuthorization]),
+                1.expr(),
+            );
+            cb.require_step_state_transition(StepStateTransition {
+                rw_counter: Delta(cb.rw_counter_offset()),
+                program_counter: Delta(1.expr()),
+                ..Default::default()
+            });
+        });
+
+        Self {
+            tx_id,
+            tx_chain_id,
+            authorization_list_len,
+            is_last,
+            message,
+            chain_id_is_zero,
+            chain_id_is_tx,
+            nonce_is_max,
+            msg_hash_raw,
+            msg_hash,
+            fq_modulus,
+            msg_hash_mod,
+            sig_v,
+            sig_v_is_zero,
+            sig_v_is_one,
+            sig_r,
+            sig_r_is_zero,
+            sig_r_canonical,
+            sig_s,
+            sig_s_is_zero,
+            sig_s_bound,
+            sig_s_low,
+            recovered,
+            authority,
+            is_authority_warm_prev,
+            authority_code_hash,
+            authority_code_hash_is_zero,
+            authority_code_hash_is_empty,
+            authority_delegation,
+            authority_nonce,
+            authority_nonce_matches,
+            address_is_zero,
+            code_hash,
+            #[cfg(feature = "scroll")]
+            keccak_code_hash,
+            #[cfg(feature = "scroll")]
+            keccak_code_hash_prev,
+            #[cfg(feature = "scroll")]
+            code_size_prev,
+            refund,
+            callee_address,
+            callee_code_hash,
+            callee_delegation,
+            is_delegate_warm_prev,
+            delegate_code_hash,
+            call_code_hash_is_zero,
+            call_code_hash_is_empty,
+            is_persistent,
+        }
+    }
+
+    fn assign_exec_step(
+        &self,
+        region: &mut CachedRegion<'_, '_, F>,
+        offset: usize,
+        block: &Block,
+        tx: &Transaction,
+        _call: &Call,
+        step: &ExecStep,
+    ) -> Result<(), Error> {
+        let index = step.program_counter as usize;
+        let authorization = &tx.authorization_list[index];
+        let authorization_list_len = tx.authorization_list.len() as u64;
+
+        let mut rws = StepRws::new(block, step);
+        // TxId
+        rws.next();
+
+        self.tx_id
+            .assign(region, offset, Value::known(F::from(tx.id as u64)))?;
+        self.tx_chain_id
+            .assign(region, offset, Value::known(F::from(tx.chain_id)))?;
+        self.authorization_list_len.assign(
+            region,
+            offset,
+            Value::known(F::from(authorization_list_len)),
+        )?;
+        self.is_last.assign(
+            region,
+            offset,
+            F::from(index as u64 + 1),
+            F::from(authorization_list_len),
+        )?;
+
+        // the RLP circuit limits the chain id to 8 bytes
+        let chain_id = authorization.chain_id.low_u64();
+        let nonce = authorization.nonce.as_u64();
+        self.message
+            .assign(region, offset, chain_id, authorization.address, nonce)?;
+        self.chain_id_is_zero
+            .assign(region, offset, F::from(chain_id))?;
+        self.chain_id_is_tx
+            .assign(region, offset, F::from(chain_id), F::from(tx.chain_id))?;
+        self.nonce_is_max
+            .assign(region, offset, F::from(nonce), F::from(u64::MAX))?;
+
+        let msg_hash = U256::from(keccak256(authorization.signing_message()));
+        let (quotient, remainder) = msg_hash.div_mod(*FQ_MODULUS);
+        self.msg_hash_raw
+            .assign(region, offset, Some(msg_hash.to_le_bytes()))?;
+        self.msg_hash
+            .assign(region, offset, Some(remainder.to_le_bytes()))?;
+        self.fq_modulus
+            .assign(region, offset, Some(FQ_MODULUS.to_le_bytes()))?;
+        self.msg_hash_mod
+            .assign(region, offset, msg_hash, *FQ_MODULUS, remainder, quotient)?;
+
+        let sig_v = authorization.y_parity.as_u64();
+        self.sig_v
+            .assign(region, offset, Value::known(F::from(sig_v)))?;
+        self.sig_v_is_zero.assign(region, offset, F::from(sig_v))?;
+        self.sig_v_is_one
+            .assign(region, offset, F::from(sig_v), F::one())?;
+        for (word, is_zero, value) in [
+            (&self.sig_r, &self.sig_r_is_zero, authorization.r),
+            (&self.sig_s, &self.sig_s_is_zero, authorization.s),
+        ] {
+            word.assign(region, offset, Some(value.to_le_bytes()))?;
+            is_zero.assign(
+                region,
+                offset,
+                F::from(
+                    value
+                        .to_le_bytes()
+                        .into_iter()
+                        .map(|byte| byte as u64)
+                        .sum::<u64>(),
+                ),
+            )?;
+        }
+        self.sig_r_canonical
+            .assign(region, offset, authorization.r, *FQ_MODULUS)?;
+        self.sig_s_bound
+            .assign(region, offset, Some(SIG_S_BOUND.to_le_bytes()))?;
+        self.sig_s_low
+            .assign(region, offset, authorization.s, *SIG_S_BOUND)?;
+
+        let authority = authorization.authority();
+        self.recovered.assign(
+            region,
+            offset,
+            Value::known(F::from(authority.is_some() as u64)),
+        )?;
+        let mut authority_bytes = authority.unwrap_or_default().to_fixed_bytes();
+        authority_bytes.reverse();
+        self.authority
+            .assign(region, offset, Some(authority_bytes))?;
+
+        // The same checks as `apply_authorization` in bus-mapping.
+        let is_recovered =
+            (chain_id == 0 || chain_id == tx.chain_id) && nonce != u64::MAX && authority.is_some();
+        let (is_authority_warm_prev, authority_code_hash) = if is_recovered {
+            (
+                rws.next().tx_access_list_value_pair().1,
+                rws.next().account_codehash_pair().0,
+            )
+        } else {
+            (false, U256::zero())
+        };
+        self.is_authority_warm_prev.assign(
+            region,
+            offset,
+            Value::known(F::from(is_authority_warm_prev as u64)),
+        )?;
+        self.authority_code_hash
+            .assign(region, offset, region.code_hash(authority_code_hash))?;
+        self.authority_code_hash_is_zero.assign_value(
+            region,
+            offset,
+            region.code_hash(authority_code_hash),
+        )?;
+        self.authority_code_hash_is_empty.assign_value(
+            region,
+            offset,
+            region.code_hash(authority_code_hash),
+            region.empty_code_hash_rlc(),
+        )?;
+        let authority_delegate = self.authority_delegation.assign_from_rws(
+            region,
+            offset,
+            block,
+            authority_code_hash,
+            &mut rws,
+        )?;
+        let authority_exists = !authority_code_hash.is_zero();
+        let authority_nonce = if is_recovered && authority_exists {
+            rws.next().account_nonce_pair().0.as_u64()
+        } else {
+            0
+        };
+        self.authority_nonce
+            .assign(region, offset, Value::known(F::from(authority_nonce)))?;
+        self.authority_nonce_matches.assign(
+            region,
+            offset,
+            F::from(authority_nonce),
+            F::from(nonce),
+        )?;
+
+        let is_code_valid = !authority_exists
+            || authority_code_hash == CodeDB::empty_code_hash().to_word()
+            || authority_delegate.is_some();
+        let is_valid = is_recovered && is_code_valid && authority_nonce == nonce;
+        self.address_is_zero.assign(
+            region,
+            offset,
+            authorization
+                .address
+                .to_scalar()
+                .expect("unexpected Address -> Scalar conversion failure"),
+        )?;
+        let (code_hash, refund) = if is_valid {
+            if !authority_exists {
+                rws.offset_add(if cfg!(feature = "scroll") { 2 } else { 1 });
+            }
+            // Nonce
+            rws.next();
+            let code_hash = rws.next().account_codehash_pair().0;
+            #[cfg(feature = "scroll")]
+            {
+                let (keccak_code_hash, keccak_code_hash_prev) =
+                    rws.next().account_keccak_codehash_pair();
+                self.keccak_code_hash
+                    .assign(region, offset, region.word_rlc(keccak_code_hash))?;
+                self.keccak_code_hash_prev.assign(
+                    region,
+                    offset,
+                    region.word_rlc(keccak_code_hash_prev),
+                )?;
+                let code_size_prev = rws
+                    .next()
+                    .account_value_pair_field_tag(AccountFieldTag::CodeSize)
+                    .1;
+                self.code_size_prev.assign(
+                    region,
+                    offset,
+                    Value::known(F::from(code_size_prev.as_u64())),
+                )?;
+            }
+            let refund = if authority_exists {
+                rws.next().tx_refund_value_pair().1
+            } else {
+                0
+            };
+            (code_hash, refund)
+        } else {
+            (U256::zero(), 0)
+        };
+        self.code_hash
+            .assign(region, offset, region.code_hash(code_hash))?;
+        self.refund
+            .assign(region, offset, Value::known(F::from(refund)))?;
+
+        let callee_address = tx.callee_address.unwrap_or_default();
+        self.callee_address.assign(
+            region,
+            offset,
+            Value::known(
+                callee_address
+                    .to_scalar()
+                    .expect("unexpected Address -> Scalar conversion failure"),
+            ),
+        )?;
+        let callee_code_hash = if index as u64 + 1 == authorization_list_len {
+            rws.next().account_codehash_pair().0
+        } else {
+            U256::zero()
+        };
+        self.callee_code_hash
+            .assign(region, offset, region.code_hash(callee_code_hash))?;
+        let callee_delegate = self.callee_delegation.assign_from_rws(
+            region,
+            offset,
+            block,
+            callee_code_hash,
+            &mut rws,
+        )?;
+        let (is_delegate_warm_prev, delegate_code_hash) = if callee_delegate.is_some() {
+            (
+                rws.next().tx_access_list_value_pair().1,
+                rws.next().account_codehash_pair().0,
+            )
+        } else {
+            (false, U256::zero())
+        };
+        self.is_delegate_warm_prev.assign(
+            region,
+            offset,
+            Value::known(F::from(is_delegate_warm_prev as u64)),
+        )?;
+        self.delegate_code_hash
+            .assign(region, offset, region.code_hash(delegate_code_hash))?;
+        let call_code_hash = if callee_delegate.is_some() {
+            delegate_code_hash
+        } else {
+            callee_code_hash
+        };
+        self.call_code_hash_is_zero.assign_value(
+            region,
+            offset,
+            region.code_hash(call_code_hash),
+        )?;
+        self.call_code_hash_is_empty.assign_value(
+            region,
+            offset,
+            region.code_hash(call_code_hash),
+            region.empty_code_hash_rlc(),
+        )?;
+        let is_persistent = if index as u64 + 1 == authorization_list_len
+            && (call_code_hash.is_zero() || call_code_hash == CodeDB::empty_code_hash().to_word())
+        {
+            rws.next().call_context_value()
+        } else {
+            U256::zero()
+        };
+        self.is_persistent.assign(
+            region,
+            offset,
+            Value::known(F::from(is_persistent.as_u64())),
+        )?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::test_util::CircuitTestBuilder;
+    use eth_types::{bytecode, geth_types::SetCodeAuthorization, Address, Word};
+    use ethers_signers::Signer;
+    use mock::{
+        eth, gwei, sign_authorization, TestContext, MOCK_ACCOUNTS, MOCK_CHAIN_ID, MOCK_WALLETS,
+    };
+
+    /// Send an EIP7702 tx from MOCK_WALLETS[0] to the authority MOCK_WALLETS[1], which runs the
+    /// code of MOCK_ACCOUNTS[0] if one of the authorizations is applied.
+    fn test_ok(authorizations: Vec<SetCodeAuthorization>) {
+        let ctx = TestContext::<3, 1>::new(
+            None,
+            |accs| {
+                accs[0].address(MOCK_ACCOUNTS[0]).code(bytecode! {
+                    PUSH1(0x2a)
+                    PUSH1(0)
+                    SSTORE
+                    STOP
+                });
+                accs[1].address(MOCK_WALLETS[0].address()).balance(eth(10));
+                accs[2].address(MOCK_WALLETS[1].address()).balance(eth(1));
+            },
+            |mut txs, _accs| {
+                txs[0]
+                    .from(MOCK_WALLETS[0].clone())
+                    .to(MOCK_WALLETS[1].address())
+                    .max_fee_per_gas(gwei(2))
+                    .max_priority_fee_per_gas(gwei(2))
+                    .authorization_list(authorizations)
+                    .transaction_type(4); // Set tx type to EIP-7702.
+            },
+            |block, _txs| block,
+        )
+        .unwrap();
+
+        CircuitTestBuilder::new_from_test_ctx(ctx).run();
+    }
+
+    fn authorization(chain_id: u64, address: Address, nonce: u64) -> SetCodeAuthorization {
+        sign_authorization(&MOCK_WALLETS[1], chain_id, address, nonce)
+    }
+
+    #[test]
+    fn tx_authorization_delegate() {
+        test_ok(vec![authorization(MOCK_CHAIN_ID, MOCK_ACCOUNTS[0], 0)]);
+    }
+
+    #[test]
+    fn tx_authorization_any_chain() {
+        test_ok(vec![authorization(0, MOCK_ACCOUNTS[0], 0)]);
+    }
+
+    #[test]
+    fn tx_authorization_invalid_chain_id() {
+        test_ok(vec![authorization(MOCK_CHAIN_ID + 1, MOCK_ACCOUNTS[0], 0)]);
+    }
+
+    #[test]
+    fn tx_authorization_invalid_nonce() {
+        test_ok(vec![authorization(MOCK_CHAIN_ID, MOCK_ACCOUNTS[0], 1)]);
+    }
+
+    #[test]
+    fn tx_authorization_invalid_signature() {
+        let mut invalid = authorization(MOCK_CHAIN_ID, MOCK_ACCOUNTS[0], 0);
+        invalid.s = Word::MAX;
+        test_ok(vec![invalid]);
+    }
+
+    #[test]
+    fn tx_authorization_multiple() {
+        // the second authorization applies to the nonce bumped by the first one, and the third
+        // one clears the delegation
+        test_ok(vec![
+            authorization(MOCK_CHAIN_ID, MOCK_ACCOUNTS[1], 0),
+            authorization(MOCK_CHAIN_ID, MOCK_ACCOUNTS[0], 1),
+            authorization(MOCK_CHAIN_ID, MOCK_ACCOUNTS[0], 0),
+        ]);
+        test_ok(vec![
+            authorization(MOCK_CHAIN_ID, MOCK_ACCOUNTS[0], 0),
+            authorization(MOCK_CHAIN_ID, Address::zero(), 1),
+        ]);
+    }
+}
@@ -42,6 +42,7 @@ pub(crate) const EVM_LOOKUP_COLS: usize = FIXED_TABLE_LOOKUPS
     + SIG_TABLE_LOOKUPS
     + MODEXP_TABLE_LOOKUPS
     + ECC_TABLE_LOOKUPS
+    + BLS12_381_TABLE_LOOKUPS
     + POW_OF_RAND_TABLE_LOOKUPS;
 
 /// Lookups done per row.
@@ -58,6 +59,7 @@ pub(crate) const LOOKUP_CONFIG: &[(Table, usize)] = &[
     (Table::Sig, SIG_TABLE_LOOKUPS),
     (Table::ModExp, MODEXP_TABLE_LOOKUPS),
     (Table::Ecc, ECC_TABLE_LOOKUPS),
+    (Table::Bls12381, BLS12_381_TABLE_LOOKUPS),
     (Table::PowOfRand, POW_OF_RAND_TABLE_LOOKUPS),
 ];
 
@@ -96,6 +98,9 @@ pub const MODEXP_TABLE_LOOKUPS: usize = 1;
 /// Ecc Table lookups done in EVMCircuit
 pub const ECC_TABLE_LOOKUPS: usize = 1;
 
+/// BLS12-381 Table lookups done in EVMCircuit
+pub const BLS12_381_TABLE_LOOKUPS: usize = 1;
+
 /// Power of Randomness lookups done from EVM Circuit.
 pub const POW_OF_RAND_TABLE_LOOKUPS: usize = 1;
 
@@ -30,6 +30,8 @@ impl From<PrecompileCalls> for ExecutionState {
             PrecompileCalls::Bn128Mul => ExecutionState::PrecompileBn256ScalarMul,
             PrecompileCalls::Bn128Pairing => ExecutionState::PrecompileBn256Pairing,
             PrecompileCalls::Blake2F => ExecutionState::PrecompileBlake2f,
+            PrecompileCalls::PointEvaluation => ExecutionState::PrecompilePointEvaluation,
+            PrecompileCalls::Bls12G1Add => ExecutionState::PrecompileBls12G1Add,
         }
     }
 }
@@ -39,6 +41,7 @@ impl From<PrecompileCalls> for ExecutionState {
 pub enum ExecutionState {
     // Internal state
     BeginTx,
+    TxAuthorization,
     EndTx,
     EndInnerBlock,
     EndBlock,
@@ -85,6 +88,8 @@ pub enum ExecutionState {
     DIFFICULTY, // DIFFICULTY
     CHAINID,
     SELFBALANCE,
+    BLOBHASH,
+    BLOBBASEFEE,
     POP,
     MEMORY, // MLOAD, MSTORE, MSTORE8
     MCOPY,
@@ -140,6 +145,8 @@ pub enum ExecutionState {
     PrecompileBn256ScalarMul,
     PrecompileBn256Pairing,
     PrecompileBlake2f,
+    PrecompilePointEvaluation,
+    PrecompileBls12G1Add,
 }
 
 impl Default for ExecutionState {
@@ -175,6 +182,8 @@ impl ExecutionState {
                 | Self::PrecompileBn256ScalarMul
                 | Self::PrecompileBn256Pairing
                 | Self::PrecompileBlake2f
+                | Self::PrecompilePointEvaluation
+                | Self::PrecompileBls12G1Add
                 | Self::ErrorOutOfGasPrecompile
                 | Self::ErrorPrecompileFailed
         )
@@ -191,6 +200,8 @@ impl ExecutionState {
             Self::PrecompileBn256ScalarMul => PrecompileCalls::Bn128Mul,
             Self::PrecompileBn256Pairing => PrecompileCalls::Bn128Pairing,
             Self::PrecompileBlake2f => PrecompileCalls::Blake2F,
+            Self::PrecompilePointEvaluation => PrecompileCalls::PointEvaluation,
+            Self::PrecompileBls12G1Add => PrecompileCalls::Bls12G1Add,
             _ => return GasCost(0),
         })
         .base_gas_cost()
@@ -286,6 +297,8 @@ impl ExecutionState {
             Self::DIFFICULTY => vec![OpcodeId::DIFFICULTY],
             Self::CHAINID => vec![OpcodeId::CHAINID],
             Self::SELFBALANCE => vec![OpcodeId::SELFBALANCE],
+            Self::BLOBHASH => vec![OpcodeId::BLOBHASH],
+            Self::BLOBBASEFEE => vec![OpcodeId::BLOBBASEFEE],
             Self::POP => vec![OpcodeId::POP],
             Self::MEMORY => {
                 vec![OpcodeId::MLOAD, OpcodeId::MSTORE, OpcodeId::MSTORE8]
@@ -170,6 +170,7 @@ pub(crate) enum Table {
     Sig,
     ModExp,
     Ecc,
+    Bls12381,
     PowOfRand,
 }
 
@@ -356,6 +357,12 @@ pub(crate) enum Lookup<F> {
         output1_rlc: Expression<F>,
         output2_rlc: Expression<F>,
     },
+    Bls12381Table {
+        op_type: Expression<F>,
+        is_valid: Expression<F>,
+        input_rlc: Expression<F>,
+        output_rlc: Expression<F>,
+    },
     PowOfRandTable {
         exponent: Expression<F>,
         pow_of_rand: Expression<F>,
@@ -383,6 +390,7 @@ impl<F: Field> Lookup<F> {
             Self::SigTable { .. } => Table::Sig,
             Self::ModExpTable { .. } => Table::ModExp,
             Self::EccTable { .. } => Table::Ecc,
+            Self::Bls12381Table { .. } => Table::Bls12381,
             Self::PowOfRandTable { .. } => Table::PowOfRand,
             Self::Conditional(_, lookup) => lookup.table(),
         }
@@ -572,6 +580,17 @@ impl<F: Field> Lookup<F> {
                 output1_rlc.expr(),
                 output2_rlc.expr(),
             ],
+            Self::Bls12381Table {
+                op_type,
+                is_valid,
+                input_rlc,
+                output_rlc,
+            } => vec![
+                op_type.expr(),
+                is_valid.expr(),
+                input_rlc.expr(),
+                output_rlc.expr(),
+            ],
             Self::PowOfRandTable {
                 exponent,
                 pow_of_rand,
@@ -10,7 +10,9 @@ use crate::{
     util::{query_expression, Challenges, Expr, Field},
     witness::{Block, ExecStep, Rw, RwMap},
 };
-use eth_types::{state_db::CodeDB, Address, ToLittleEndian, ToWord, U256};
+use eth_types::{
+    forks::LAST_PRECOMPILE_ADDRESS, state_db::CodeDB, Address, ToLittleEndian, ToWord, U256,
+};
 use halo2_proofs::{
     circuit::{AssignedCell, Region, Value},
     halo2curves::group::ff::BatchInvert,
@@ -720,7 +722,7 @@ pub(crate) fn transpose_val_ret<F, E>(value: Value<Result<F, E>>) -> Result<Valu
 }
 
 pub(crate) fn is_precompiled(address: &Address) -> bool {
-    address.0[0..19] == [0u8; 19] && (1..=9).contains(&address.0[19])
+    address.0[0..19] == [0u8; 19] && (1..=LAST_PRECOMPILE_ADDRESS).contains(&address.0[19])
 }
 
 /// Helper struct to read rw operations from a step sequentially.
@@ -33,12 +33,14 @@ use halo2_proofs::{
 };
 
 mod curie;
+mod delegation;
 mod tx_access_list;
 mod tx_eip1559;
 mod tx_l1_fee;
 mod tx_l1_msg;
 
 pub(crate) use curie::CurieGadget;
+pub(crate) use delegation::DelegationGadget;
 pub(crate) use tx_access_list::TxAccessListGadget;
 pub(crate) use tx_eip1559::TxEip1559Gadget;
 pub(crate) use tx_l1_fee::TxL1FeeGadget;
@@ -0,0 +1,257 @@
+use super::{CachedRegion, Cell, StepRws};
+#[cfg(feature = "scroll")]
+use crate::table::AccountFieldTag;
+use crate::{
+    evm_circuit::{
+        param::N_BYTES_ACCOUNT_ADDRESS,
+        util::{
+            and,
+            constraint_builder::{ConstrainBuilderCommon, EVMConstraintBuilder},
+            from_bytes,
+            math_gadget::IsEqualGadget,
+            not, RandomLinearCombination,
+        },
+    },
+    util::{Expr, Field},
+    witness::Block,
+};
+use eth_types::{
+    evm_types::delegation::{parse_delegation, DELEGATION_CODE_SIZE, DELEGATION_PREFIX},
+    Address, U256,
+};
+use halo2_proofs::{
+    circuit::Value,
+    plonk::{Error, Expression},
+};
+
+/// Gadget checking whether the code of an account is an EIP7702 delegation designator
+/// `0xef0100 || delegate`, and recovering the delegate if so.
+///
+/// The size and the prefix of the code are looked up in the bytecode table, and the delegate is
+/// bound to the code by hashing the designator. Under scroll the keccak code hash of the account
+/// is read for that.
+#[derive(Clone, Debug)]
+pub(crate) struct DelegationGadget<F> {
+    code_size: Cell<F>,
+    is_designator_size: IsEqualGadget<F>,
+    prefix: [Cell<F>; 3],
+    prefix_is_code: [Cell<F>; 3],
+    prefix_push_rlc: [Cell<F>; 3],
+    is_designator_prefix: IsEqualGadget<F>,
+    is_delegated: Cell<F>,
+    delegate: RandomLinearCombination<F, N_BYTES_ACCOUNT_ADDRESS>,
+    #[cfg(feature = "scroll")]
+    keccak_code_hash: Cell<F>,
+}
+
+impl<F: Field> DelegationGadget<F> {
+    /// `code_hash` is the code hash of the account at `address`, and `has_code` whether it is
+    /// neither zero nor the empty code hash.
+    #[cfg_attr(not(feature = "scroll"), allow(unused_variables))]
+    pub(crate) fn construct(
+        cb: &mut EVMConstraintBuilder<F>,
+        address: Expression<F>,
+        code_hash: Expression<F>,
+        has_code: Expression<F>,
+    ) -> Self {
+        let code_size = cb.query_cell();
+        let prefix = array_init::array_init(|_| cb.query_byte());
+        let prefix_is_code = array_init::array_init(|_| cb.query_bool());
+        let prefix_push_rlc = array_init::array_init(|_| cb.query_cell_phase2());
+        let is_delegated = cb.query_bool();
+        let delegate = cb.query_keccak_rlc();
+
+        let is_designator_size =
+            IsEqualGadget::construct(cb, code_size.expr(), DELEGATION_CODE_SIZE.expr());
+        let is_designator_prefix = IsEqualGadget::construct(
+            cb,
+            from_bytes::expr(&[prefix[2].expr(), prefix[1].expr(), prefix[0].expr()]),
+            from_bytes::expr(&[
+                DELEGATION_PREFIX[2],
+                DELEGATION_PREFIX[1],
+                DELEGATION_PREFIX[0],
+            ]),
+        );
+
+        cb.condition(has_code.expr(), |cb| {
+            cb.bytecode_length(code_hash.expr(), code_size.expr());
+            cb.condition(is_designator_size.expr(), |cb| {
+                for (index, ((byte, is_code), push_rlc)) in prefix
+                    .iter()
+                    .zip(prefix_is_code.iter())
+                    .zip(prefix_push_rlc.iter())
+                    .enumerate()
+                {
+                    cb.bytecode_lookup(
+                        code_hash.expr(),
+                        index.expr(),
+                        is_code.expr(),
+                        byte.expr(),
+                        push_rlc.expr(),
+                    );
+                }
+            });
+        });
+        cb.require_equal(
+            "is_delegated == has_code && code is 0xef0100 || delegate",
+            is_delegated.expr(),
+            and::expr([
+                has_code,
+                is_designator_size.expr(),
+                is_designator_prefix.expr(),
+            ]),
+        );
+
+        #[cfg(feature = "scroll")]
+        let keccak_code_hash = cb.query_cell_phase2();
+        cb.condition(is_delegated.expr(), |cb| {
+            #[cfg(feature = "scroll")]
+            cb.account_read(
+                address.expr(),
+                AccountFieldTag::KeccakCodeHash,
+                keccak_code_hash.expr(),
+            );
+
+            cb.keccak_table_lookup(
+                Self::designator_keccak_rlc(cb, delegate.expr()),
+                DELEGATION_CODE_SIZE.expr(),
+                #[cfg(feature = "scroll")]
+                keccak_code_hash.expr(),
+                #[cfg(not(feature = "scroll"))]
+                code_hash.expr(),
+            );
+        });
+        cb.condition(not::expr(is_delegated.expr()), |cb| {
+            cb.require_zero("delegate is 0 if not delegated", delegate.expr());
+        });
+
+        Self {
+            code_size,
+            is_designator_size,
+            prefix,
+            prefix_is_code,
+            prefix_push_rlc,
+            is_designator_prefix,
+            is_delegated,
+            delegate,
+            #[cfg(feature = "scroll")]
+            keccak_code_hash,
+        }
+    }
+
+    /// Keccak RLC of the designator `0xef0100 || delegate`, from the keccak RLC of the delegate.
+    pub(crate) fn designator_keccak_rlc(
+        cb: &EVMConstraintBuilder<F>,
+        delegate_keccak_rlc: Expression<F>,
+    ) -> Expression<F> {
+        let powers_of_randomness = cb.challenges().keccak_powers_of_randomness::<22>();
+        DELEGATION_PREFIX
+            .iter()
+            .zip(
+                powers_of_randomness[N_BYTES_ACCOUNT_ADDRESS - 1..]
+                    .iter()
+                    .rev(),
+            )
+            .fold(delegate_keccak_rlc, |acc, (byte, power)| {
+                acc + byte.expr() * power.clone()
+            })
+    }
+
+    /// Assign the gadget for an account with code hash `code_hash`, which is zero if the
+    /// account does not exist, consuming its rw operations from `rws`. Return the delegate if
+    /// the code is a delegation designator.
+    #[cfg_attr(not(feature = "scroll"), allow(unused_variables))]
+    pub(crate) fn assign_from_rws(
+        &self,
+        region: &mut CachedRegion<'_, '_, F>,
+        offset: usize,
+        block: &Block,
+        code_hash: U256,
+        rws: &mut StepRws,
+    ) -> Result<Option<Address>, Error> {
+        let code = block
+            .bytecodes
+            .get(&code_hash)
+            .map(|bytecode| bytecode.bytes.as_slice())
+            .unwrap_or_default();
+        let code_size = code.len() as u64;
+        self.code_size
+            .assign(region, offset, Value::known(F::from(code_size)))?;
+        self.is_designator_size.assign(
+            region,
+            offset,
+            F::from(code_size),
+            F::from(DELEGATION_CODE_SIZE as u64),
+        )?;
+
+        let is_designator_size = code.len() == DELEGATION_CODE_SIZE;
+        let mut prefix_value = 0;
+        for (index, ((byte, is_code), push_rlc)) in self
+            .prefix
+            .iter()
+            .zip(self.prefix_is_code.iter())
+            .zip(self.prefix_push_rlc.iter())
+            .enumerate()
+        {
+            let (value, value_is_code, value_push_rlc) = if is_designator_size {
+                block.bytecodes[&code_hash].get_byte_row(index, region.challenges())
+            } else {
+                (0, false, Value::known(F::zero()))
+            };
+            prefix_value = (prefix_value << 8) + value as u64;
+            byte.assign(region, offset, Value::known(F::from(value as u64)))?;
+            is_code.assign(region, offset, Value::known(F::from(value_is_code as u64)))?;
+            push_rlc.assign(region, offset, value_push_rlc)?;
+        }
+        self.is_designator_prefix.assign(
+            region,
+            offset,
+            F::from(prefix_value),
+            from_bytes::value(&[
+                DELEGATION_PREFIX[2],
+                DELEGATION_PREFIX[1],
+                DELEGATION_PREFIX[0],
+            ]),
+        )?;
+
+        let delegate = parse_delegation(code);
+        self.is_delegated.assign(
+            region,
+            offset,
+            Value::known(F::from(delegate.is_some() as u64)),
+        )?;
+        let mut delegate_bytes = delegate.unwrap_or_default().to_fixed_bytes();
+        delegate_bytes.reverse();
+        self.delegate.assign(region, offset, Some(delegate_bytes))?;
+        #[cfg(feature = "scroll")]
+        self.keccak_code_hash.assign(
+            region,
+            offset,
+            region.word_rlc(if delegate.is_some() {
+                rws.next().account_keccak_codehash_pair().0
+            } else {
+                U256::zero()
+            }),
+        )?;
+
+        Ok(delegate)
+    }
+
+    pub(crate) fn is_delegated(&self) -> Expression<F> {
+        self.is_delegated.expr()
+    }
+
+    /// Delegate address, zero if the code is not a delegation designator.
+    pub(crate) fn delegate(&self) -> Expression<F> {
+        from_bytes::expr(&self.delegate.cells)
+    }
+
+    /// Number of rw lookups done by the gadget.
+    pub(crate) fn rw_delta(&self) -> Expression<F> {
+        if cfg!(feature = "scroll") {
+            self.is_delegated.expr()
+        } else {
+            0.expr()
+        }
+    }
+}
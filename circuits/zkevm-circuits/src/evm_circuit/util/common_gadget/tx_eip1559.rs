@@ -1,5 +1,6 @@
 //! TxEip1559Gadget is used to check sender balance before fee and value
-//! transfer for EIP-1559 transactions.
+//! transfer for EIP-1559 (and EIP-4844, which shares its fee market fields)
+//! transactions.
 //! Reference the geth code as:
 //! <https://github.com/ethereum/go-ethereum/blob/master/core/state_transition.go#L234>
 //! <https://github.com/scroll-tech/go-ethereum/blob/develop/core/state_transition.go#L218>
@@ -10,7 +11,7 @@ use crate::{
         util::{
             constraint_builder::{ConstrainBuilderCommon, EVMConstraintBuilder},
             math_gadget::{AddWordsGadget, IsEqualGadget, LtWordGadget, MulWordByU64Gadget},
-            sum, Expr, Word,
+            or, sum, Expr, Word,
         },
         witness::Transaction,
     },
@@ -25,6 +26,8 @@ use halo2_proofs::plonk::{Error, Expression};
 #[derive(Clone, Debug)]
 pub(crate) struct TxEip1559Gadget<F> {
     is_eip1559_tx: IsEqualGadget<F>,
+    is_eip4844_tx: IsEqualGadget<F>,
+    is_eip7702_tx: IsEqualGadget<F>,
     // MaxFeePerGas
     gas_fee_cap: Word<F>,
     // MaxPriorityFeePerGas
@@ -38,7 +41,10 @@ pub(crate) struct TxEip1559Gadget<F> {
     // block.base_fee_per_gas
     effective_gas_price_check: AddWordsGadget<F, 2, true>,
     mul_gas_fee_cap_by_gas: MulWordByU64Gadget<F>,
-    balance_check: AddWordsGadget<F, 3, true>,
+    // MaxFeePerBlobGas, zero unless EIP-4844 tx
+    blob_fee_cap: Word<F>,
+    mul_blob_fee_cap_by_blob_gas: MulWordByU64Gadget<F>,
+    balance_check: AddWordsGadget<F, 4, true>,
     // Error condition
     // <https://github.com/ethereum/go-ethereum/blob/master/core/state_transition.go#L241>
     is_insufficient_balance: LtWordGadget<F>,
@@ -50,6 +56,9 @@ pub(crate) struct TxEip1559Gadget<F> {
     // Error condition
     // <https://github.com/ethereum/go-ethereum/blob/master/core/state_transition.go#L316>
     gas_fee_cap_lt_base_fee: LtWordGadget<F>,
+    // Error condition
+    // <https://github.com/ethereum/go-ethereum/blob/master/core/state_transition.go>
+    blob_fee_cap_lt_blob_base_fee: LtWordGadget<F>,
 }
 
 impl<F: Field> TxEip1559Gadget<F> {
@@ -64,15 +73,22 @@ impl<F: Field> TxEip1559Gadget<F> {
         tx_l1_fee: &Word<F>,
         value: &Word<F>,
         sender_balance: &Word<F>,
+        // blob_base_fee is looked up from BlockTable in begin_tx gadget.
+        blob_base_fee: &Word<F>,
+        blob_gas: Expression<F>,
     ) -> Self {
-        let is_eip1559_tx = IsEqualGadget::construct(cb, tx_type, (TxType::Eip1559 as u64).expr());
+        let [is_eip1559_tx, is_eip4844_tx, is_eip7702_tx] =
+            [TxType::Eip1559, TxType::Eip4844, TxType::Eip7702]
+                .map(|val| IsEqualGadget::construct(cb, tx_type.expr(), (val as u64).expr()));
 
         let [gas_fee_cap, gas_tip_cap] =
             [TxFieldTag::MaxFeePerGas, TxFieldTag::MaxPriorityFeePerGas]
                 .map(|field_tag| cb.tx_context_as_word(tx_id.expr(), field_tag, None));
+        let blob_fee_cap = cb.tx_context_as_word(tx_id.expr(), TxFieldTag::MaxFeePerBlobGas, None);
 
         let (
             mul_gas_fee_cap_by_gas,
+            mul_blob_fee_cap_by_blob_gas,
             balance_check,
             is_insufficient_balance,
             gas_fee_cap_lt_gas_tip_cap,
@@ -81,9 +97,12 @@ impl<F: Field> TxEip1559Gadget<F> {
             gas_tip_cap_lt_gas_fee_cap_minus_base_fee,
             gas_sub_base_fee,
             effective_gas_price_check,
-        ) = cb.condition(is_eip1559_tx.expr(), |cb| {
+            blob_fee_cap_lt_blob_base_fee,
+        ) = cb.condition(or::expr([is_eip1559_tx.expr(), is_eip4844_tx.expr(), is_eip7702_tx.expr()]), |cb| {
             let mul_gas_fee_cap_by_gas =
                 MulWordByU64Gadget::construct(cb, gas_fee_cap.clone(), tx_gas);
+            let mul_blob_fee_cap_by_blob_gas =
+                MulWordByU64Gadget::construct(cb, blob_fee_cap.clone(), blob_gas);
 
             let min_balance = cb.query_word_rlc();
             let balance_check = AddWordsGadget::construct(
@@ -92,6 +111,7 @@ impl<F: Field> TxEip1559Gadget<F> {
                     mul_gas_fee_cap_by_gas.product().clone(),
                     value.clone(),
                     tx_l1_fee.clone(),
+                    mul_blob_fee_cap_by_blob_gas.product().clone(),
                 ],
                 min_balance.clone(),
             );
@@ -121,18 +141,23 @@ impl<F: Field> TxEip1559Gadget<F> {
                 gas_fee_cap_minus_base_fee_per_gas.expr()));
             // constrain tx_gas_price = effective_gas_price within below `AddWordsGadget`.
             let effective_gas_price_check = AddWordsGadget::construct(cb, [base_fee.clone(), priority_fee_per_gas], tx_gas_price.clone());
+            // constrain BlobFeeCap not less than BlobBaseFee for EIP-4844 tx
+            let blob_fee_cap_lt_blob_base_fee =
+                LtWordGadget::construct(cb, &blob_fee_cap, blob_base_fee);
 
             cb.require_zero(
-                "Sender balance must be sufficient, and gas_fee_cap >= gas_tip_cap, and gas_fee_cap >= base_fee",
+                "Sender balance must be sufficient, and gas_fee_cap >= gas_tip_cap, and gas_fee_cap >= base_fee, and blob_fee_cap >= blob_base_fee",
                 sum::expr([
                     is_insufficient_balance.expr(),
                     gas_fee_cap_lt_gas_tip_cap.expr(),
                     gas_fee_cap_lt_base_fee.expr(),
+                    is_eip4844_tx.expr() * blob_fee_cap_lt_blob_base_fee.expr(),
                 ]),
             );
 
             (
                 mul_gas_fee_cap_by_gas,
+                mul_blob_fee_cap_by_blob_gas,
                 balance_check,
                 is_insufficient_balance,
                 gas_fee_cap_lt_gas_tip_cap,
@@ -141,25 +166,32 @@ impl<F: Field> TxEip1559Gadget<F> {
                 tip_comparator,
                 gas_fee_cap_minus_base_fee_per_gas_check,
                 effective_gas_price_check,
+                blob_fee_cap_lt_blob_base_fee,
             )
         });
 
         Self {
             is_eip1559_tx,
+            is_eip4844_tx,
+            is_eip7702_tx,
             gas_fee_cap,
             gas_tip_cap,
             gas_tip_cap_lt_gas_fee_cap_minus_base_fee,
             gas_sub_base_fee,
             effective_gas_price_check,
             mul_gas_fee_cap_by_gas,
+            blob_fee_cap,
+            mul_blob_fee_cap_by_blob_gas,
             balance_check,
             is_insufficient_balance,
             gas_fee_cap_lt_gas_tip_cap,
             base_fee,
             gas_fee_cap_lt_base_fee,
+            blob_fee_cap_lt_blob_base_fee,
         }
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub(crate) fn assign(
         &self,
         region: &mut CachedRegion<'_, '_, F>,
@@ -168,6 +200,7 @@ impl<F: Field> TxEip1559Gadget<F> {
         tx_l1_fee: U256,
         sender_balance_prev: U256,
         base_fee: U256,
+        blob_base_fee: U256,
     ) -> Result<(), Error> {
         self.is_eip1559_tx.assign(
             region,
@@ -175,6 +208,18 @@ impl<F: Field> TxEip1559Gadget<F> {
             F::from(tx.tx_type as u64),
             F::from(TxType::Eip1559 as u64),
         )?;
+        self.is_eip4844_tx.assign(
+            region,
+            offset,
+            F::from(tx.tx_type as u64),
+            F::from(TxType::Eip4844 as u64),
+        )?;
+        self.is_eip7702_tx.assign(
+            region,
+            offset,
+            F::from(tx.tx_type as u64),
+            F::from(TxType::Eip7702 as u64),
+        )?;
         self.gas_fee_cap
             .assign(region, offset, Some(tx.max_fee_per_gas.to_le_bytes()))?;
         self.gas_tip_cap.assign(
@@ -212,11 +257,27 @@ impl<F: Field> TxEip1559Gadget<F> {
             tx.gas,
             mul_gas_fee_cap_by_gas,
         )?;
-        let min_balance = mul_gas_fee_cap_by_gas + tx.value + tx_l1_fee;
+        self.blob_fee_cap
+            .assign(region, offset, Some(tx.max_fee_per_blob_gas.to_le_bytes()))?;
+        let mul_blob_fee_cap_by_blob_gas = tx.max_fee_per_blob_gas * tx.blob_gas();
+        self.mul_blob_fee_cap_by_blob_gas.assign(
+            region,
+            offset,
+            tx.max_fee_per_blob_gas,
+            tx.blob_gas(),
+            mul_blob_fee_cap_by_blob_gas,
+        )?;
+        let min_balance =
+            mul_gas_fee_cap_by_gas + tx.value + tx_l1_fee + mul_blob_fee_cap_by_blob_gas;
         self.balance_check.assign(
             region,
             offset,
-            [mul_gas_fee_cap_by_gas, tx.value, tx_l1_fee],
+            [
+                mul_gas_fee_cap_by_gas,
+                tx.value,
+                tx_l1_fee,
+                mul_blob_fee_cap_by_blob_gas,
+            ],
             min_balance,
         )?;
         self.is_insufficient_balance
@@ -230,14 +291,20 @@ impl<F: Field> TxEip1559Gadget<F> {
             tx.max_priority_fee_per_gas,
         )?;
         self.gas_fee_cap_lt_base_fee
-            .assign(region, offset, tx.max_fee_per_gas, base_fee)
+            .assign(region, offset, tx.max_fee_per_gas, base_fee)?;
+        self.blob_fee_cap_lt_blob_base_fee.assign(
+            region,
+            offset,
+            tx.max_fee_per_blob_gas,
+            blob_base_fee,
+        )
     }
 }
 
 #[cfg(test)]
 mod test {
     use crate::test_util::CircuitTestBuilder;
-    use eth_types::{Error, Word};
+    use eth_types::{Error, Word, H256};
     use ethers_signers::Signer;
     use mock::{eth, gwei, TestContext, MOCK_ACCOUNTS, MOCK_WALLETS};
 
@@ -314,6 +381,38 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_eip4844_tx_charges_blob_fee() {
+        // The sender balance must cover gas * max_fee_per_gas + value + l1 fee +
+        // blob_gas * max_fee_per_blob_gas, and blob_base_fee * blob_gas is burned.
+        // versioned hashes start with the KZG version byte 0x01
+        let mut blob_hash = [0u8; 32];
+        blob_hash[0] = 0x01;
+        let ctx = TestContext::<2, 1>::new(
+            None,
+            |accs| {
+                accs[0].address(MOCK_WALLETS[0].address()).balance(eth(1));
+                accs[1].address(MOCK_ACCOUNTS[0]).balance(eth(1));
+            },
+            |mut txs, _accs| {
+                txs[0]
+                    .from(MOCK_WALLETS[0].clone())
+                    .to(MOCK_ACCOUNTS[0])
+                    .gas(30_000.into())
+                    .value(gwei(20_000))
+                    .max_fee_per_gas(gwei(2))
+                    .max_priority_fee_per_gas(gwei(2))
+                    .max_fee_per_blob_gas(gwei(1))
+                    .blob_versioned_hashes(vec![H256(blob_hash)])
+                    .transaction_type(3); // Set tx type to EIP-4844.
+            },
+            |block, _tx| block.number(0xcafeu64),
+        )
+        .unwrap();
+
+        CircuitTestBuilder::new_from_test_ctx(ctx).run();
+    }
+
     fn build_ctx(
         sender_balance: Word,
         max_fee_per_gas: Word,
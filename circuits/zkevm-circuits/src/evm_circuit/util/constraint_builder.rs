@@ -1543,6 +1543,26 @@ impl<'a, F: Field> EVMConstraintBuilder<'a, F> {
         );
     }
 
+    // BLS12-381 Table
+
+    pub(crate) fn bls12_381_table_lookup(
+        &mut self,
+        op_type: Expression<F>,
+        is_valid: Expression<F>,
+        input_rlc: Expression<F>,
+        output_rlc: Expression<F>,
+    ) {
+        self.add_lookup(
+            "bls12-381 table",
+            Lookup::Bls12381Table {
+                op_type,
+                is_valid,
+                input_rlc,
+                output_rlc,
+            },
+        );
+    }
+
     // Power of Randomness Table
 
     pub(crate) fn pow_of_rand_lookup(
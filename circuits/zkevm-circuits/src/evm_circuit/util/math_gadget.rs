@@ -44,7 +44,7 @@ pub(crate) use mul_add_words512::MulAddWords512Gadget;
 pub(crate) use mul_word_u64::MulWordByU64Gadget;
 pub(crate) use pair_select::PairSelectGadget;
 pub(crate) use range_check::RangeCheckGadget;
-pub(crate) use rlp::ContractCreateGadget;
+pub(crate) use rlp::{AuthorizationMessageGadget, ContractCreateGadget};
 
 // This function generates a Lagrange polynomial in the range [start, end) which
 // will be evaluated to 1 when `exp == value`, otherwise 0
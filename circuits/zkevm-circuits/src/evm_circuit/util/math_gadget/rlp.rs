@@ -421,11 +421,109 @@ impl<F: Field, const IS_CREATE2: bool> ContractCreateGadget<F, IS_CREATE2> {
     }
 }
 
+/// Gadget for the message signed by the authority of an EIP-7702 authorization, i.e.
+/// `0x05 || rlp([chain_id, address, nonce])`.
+#[derive(Clone, Debug)]
+pub struct AuthorizationMessageGadget<F> {
+    /// Chain id of the authorization.
+    chain_id: RlpU64Gadget<F>,
+    /// Address of the delegate.
+    address: RandomLinearCombination<F, N_BYTES_ACCOUNT_ADDRESS>,
+    /// Nonce of the authority.
+    nonce: RlpU64Gadget<F>,
+}
+
+impl<F: Field> AuthorizationMessageGadget<F> {
+    /// Configure and construct the gadget.
+    pub(crate) fn construct(cb: &mut EVMConstraintBuilder<F>) -> Self {
+        let chain_id = RlpU64Gadget::construct(cb);
+        let address = cb.query_keccak_rlc();
+        let nonce = RlpU64Gadget::construct(cb);
+
+        Self {
+            chain_id,
+            address,
+            nonce,
+        }
+    }
+
+    /// Assign witness data to the AuthorizationMessage gadget.
+    pub(crate) fn assign(
+        &self,
+        region: &mut CachedRegion<'_, '_, F>,
+        offset: usize,
+        chain_id: u64,
+        address: Address,
+        nonce: u64,
+    ) -> Result<(), Error> {
+        self.chain_id.assign(region, offset, chain_id)?;
+        let mut address_bytes = address.to_fixed_bytes();
+        address_bytes.reverse();
+        self.address.assign(region, offset, Some(address_bytes))?;
+        self.nonce.assign(region, offset, nonce)?;
+
+        Ok(())
+    }
+
+    /// Chain id's value.
+    pub(crate) fn chain_id(&self) -> Expression<F> {
+        self.chain_id.value()
+    }
+
+    /// Delegate address' value.
+    pub(crate) fn address(&self) -> Expression<F> {
+        expr_from_bytes(&self.address.cells)
+    }
+
+    /// Delegate address' RLC value.
+    pub(crate) fn address_keccak_rlc(&self) -> Expression<F> {
+        self.address.expr()
+    }
+
+    /// Authority nonce's value.
+    pub(crate) fn nonce(&self) -> Expression<F> {
+        self.nonce.value()
+    }
+
+    /// Length of the RLP list payload.
+    fn payload_length(&self) -> Expression<F> {
+        self.chain_id.rlp_length() + 21.expr() + self.nonce.rlp_length()
+    }
+
+    /// Length of the input data to the keccak hash function.
+    pub(crate) fn input_length(&self) -> Expression<F> {
+        // | 0x05 | list-prefix | chain-id-bytes       | addr-prefix | addr | nonce-bytes       |
+        // |------|-------------|----------------------|-------------|------|-------------------|
+        // | 1    | 1           | rlp_length(chain_id) | 1           | 20   | rlp_length(nonce) |
+        2.expr() + self.payload_length()
+    }
+
+    /// RLC for the input data.
+    pub(crate) fn input_rlc(&self, cb: &EVMConstraintBuilder<F>) -> Expression<F> {
+        let challenges = cb.challenges().keccak_powers_of_randomness::<21>();
+        let challenge = challenges[0].clone();
+        let challenge_power_20 = challenges[19].clone();
+        let challenge_power_21 = challenges[20].clone();
+
+        // RLC(0x05 || RLP([chain_id]))
+        let prefix_rlc = ((0x05.expr() * challenge) + 0xc0.expr() + self.payload_length())
+            * self.chain_id.challenge_power_rlp_length(cb)
+            + self.chain_id.rlp_rlc(cb);
+        ((prefix_rlc * challenge_power_21)
+            + (148.expr() * challenge_power_20)
+            + self.address.expr())
+            * self.nonce.challenge_power_rlp_length(cb)
+            + self.nonce.rlp_rlc(cb)
+    }
+}
+
 #[cfg(test)]
 mod test {
-    use super::{super::test_util::*, ContractCreateGadget};
+    use super::{super::test_util::*, AuthorizationMessageGadget, ContractCreateGadget};
     use crate::util::Field;
-    use eth_types::{state_db::CodeDB, ToAddress, ToLittleEndian, ToWord, Word};
+    use eth_types::{
+        geth_types::SetCodeAuthorization, state_db::CodeDB, ToAddress, ToLittleEndian, ToWord, Word,
+    };
     use ethers_core::utils::keccak256;
     use gadgets::util::{not, Expr};
     use halo2_proofs::halo2curves::bn256::Fr;
@@ -613,4 +711,109 @@ mod test {
             true
         )
     }
+
+    #[derive(Clone)]
+    struct AuthorizationMessageGadgetContainer<F> {
+        message_gadget: AuthorizationMessageGadget<F>,
+        input_len_expected: Cell<F>,
+        input_rlc_expected: [Cell<F>; 64],
+    }
+
+    impl<F: Field> MathGadgetContainer<F> for AuthorizationMessageGadgetContainer<F> {
+        fn configure_gadget_container(cb: &mut EVMConstraintBuilder<F>) -> Self {
+            let message_gadget = AuthorizationMessageGadget::construct(cb);
+            let input_len_expected = cb.query_cell();
+            let input_rlc_expected = array_init::array_init(|_| cb.query_byte());
+            cb.require_equal(
+                "message length correct",
+                input_len_expected.expr(),
+                message_gadget.input_length(),
+            );
+            cb.require_equal(
+                "message encoding correct",
+                cb.keccak_rlc::<64>(
+                    input_rlc_expected
+                        .iter()
+                        .map(Expr::expr)
+                        .collect::<Vec<_>>()
+                        .try_into()
+                        .unwrap(),
+                ),
+                message_gadget.input_rlc(cb),
+            );
+
+            Self {
+                message_gadget,
+                input_len_expected,
+                input_rlc_expected,
+            }
+        }
+
+        fn assign_gadget_container(
+            &self,
+            witnesses: &[Word],
+            region: &mut CachedRegion<'_, '_, F>,
+        ) -> Result<(), halo2_proofs::plonk::Error> {
+            let offset = 0;
+            self.message_gadget.assign(
+                region,
+                offset,
+                witnesses[0].as_u64(),
+                witnesses[1].to_address(),
+                witnesses[2].as_u64(),
+            )?;
+            self.input_len_expected.assign(
+                region,
+                offset,
+                Value::known(F::from(witnesses[3].as_u64())),
+            )?;
+            // the message is split into its last 32 bytes and the bytes before them
+            for (c, v) in self
+                .input_rlc_expected
+                .iter()
+                .zip([witnesses[5].to_le_bytes(), witnesses[4].to_le_bytes()].concat())
+            {
+                c.assign(region, offset, Value::known(F::from(v as u64)))?;
+            }
+
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn authorization_message() {
+        for (chain_id, address, nonce) in [
+            (0x00u64, mock::MOCK_ACCOUNTS[0], 0x00u64),
+            (0x01u64, mock::MOCK_ACCOUNTS[1], 0x7fu64),
+            (0x7fu64, mock::MOCK_ACCOUNTS[2], 0x80u64),
+            (0x80u64, mock::MOCK_ACCOUNTS[3], 0xffffu64),
+            (0x82750u64, mock::MOCK_ACCOUNTS[4], 0xffffffffffu64),
+            (
+                0xffffffffffffffffu64,
+                mock::MOCK_ACCOUNTS[0],
+                0xffffffffffffffffu64,
+            ),
+        ] {
+            let message = SetCodeAuthorization {
+                chain_id: Word::from(chain_id),
+                address,
+                nonce: nonce.into(),
+                ..Default::default()
+            }
+            .signing_message();
+            let (message_hi, message_lo) = message.split_at(message.len().saturating_sub(32));
+            try_test!(
+                AuthorizationMessageGadgetContainer<Fr>,
+                [
+                    Word::from(chain_id),
+                    address.to_word(),
+                    Word::from(nonce),
+                    Word::from(message.len()),
+                    Word::from_big_endian(message_hi),
+                    Word::from_big_endian(message_lo),
+                ],
+                true
+            );
+        }
+    }
 }
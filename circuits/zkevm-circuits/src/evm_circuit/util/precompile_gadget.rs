@@ -49,6 +49,8 @@ impl<F: Field> PrecompileGadget<F> {
         constrain_next_state!(cb, Bn128Mul, PrecompileBn256ScalarMul);
         constrain_next_state!(cb, Bn128Pairing, PrecompileBn256Pairing);
         constrain_next_state!(cb, Blake2F, PrecompileBlake2f);
+        constrain_next_state!(cb, PointEvaluation, PrecompilePointEvaluation);
+        constrain_next_state!(cb, Bls12G1Add, PrecompileBls12G1Add);
 
         // Without constraining the next step's state, only constrain the first two Phase2 cells,
         // i.e. RLC(input_bytes) and RLC(return_bytes)
@@ -27,7 +27,9 @@
 #[cfg(not(target_pointer_width = "64"))]
 compile_error!("This program requires a 64-bit target architecture.");
 
+pub mod bls12_381_circuit;
 pub mod bytecode_circuit;
+pub mod ciphertext_circuit;
 pub mod copy_circuit;
 pub mod ecc_circuit;
 pub mod evm_circuit;
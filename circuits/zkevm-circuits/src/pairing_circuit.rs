@@ -22,7 +22,7 @@ use halo2_ecc::{
         fp::{FpConfig, FpStrategy},
         fp12::Fp12Chip,
         fp2::Fp2Chip,
-        FieldChip, FieldExtPoint, FieldExtConstructor,
+        FieldChip, FieldExtPoint, FieldExtConstructor, Selectable,
     },
 };
 use halo2_proofs::{
@@ -40,6 +40,7 @@ use snark_verifier::util::arithmetic::PrimeCurveAffine;
 use snark_verifier_sdk::CircuitExt;
 
 use crate::{
+    ciphertext_circuit::LsssPolicy,
     evm_circuit::{param::N_BYTES_WORD, EvmCircuit},
     keccak_circuit::KeccakCircuit,
     table::{EccTable, LookupTable},
@@ -48,21 +49,40 @@ use crate::{
 };
 use ark_std::start_timer;
 
+mod batch;
+pub(crate) mod commitment;
 mod util;
 mod dev;
 mod test;
 
+pub use batch::{BatchTask, MyBatchEccCircuit};
+pub use commitment::{
+    poseidon_commitment, poseidon_pi_commitment, KeccakCommitment, PiCommitment,
+    PiCommitmentMode, PoseidonCommitment,
+};
+use commitment::{
+    assign_messages, assign_pi_messages, g1_words, word_limbs, AssignedMessage,
+    PoseidonCommitmentConfig,
+};
+
 use util::{
     EcAddAssigned, EcAddDecomposed, EcMulAssigned, EcMulDecomposed, EcOpsAssigned,
     EcPairingAssigned, EcPairingDecomposed, G1Assigned, G1Decomposed, G2Decomposed, ScalarAssigned,
     LOG_TOTAL_NUM_ROWS,
 };
 
+/// Number of limbs to represent Fp.
+pub(crate) const NUM_LIMBS: usize = 3;
+/// Number of bits per limb.
+pub(crate) const LIMB_BITS: usize = 88;
+
 /// Arguments accepted to configure the MyEccCircuitConfig.
 #[derive(Clone, Debug)]
 pub struct MyEccCircuitConfigArgs<F: Field> {
     /// zkEVM challenge API.
     pub challenges: Challenges<Expression<F>>,
+    /// How the public inputs are committed to.
+    pub pi_commitment: PiCommitmentMode,
 }
 
 /// Config for the my ECC circuit.
@@ -75,8 +95,11 @@ pub struct MyEccCircuitConfig<F: Field> {
     /// Number of bits per limb.
     limb_bits: usize,
 
+    /// Public input hash, or the commitments looked up in the poseidon table.
     hash: Column<Advice>,
     instance: Column<Instance>,
+    /// Poseidon table lookups in [`PiCommitmentMode::Poseidon`].
+    poseidon_commitment: Option<PoseidonCommitmentConfig<F>>,
 
     _marker: PhantomData<F>,
 }
@@ -88,10 +111,11 @@ impl<F: Field> SubCircuitConfig<F> for MyEccCircuitConfig<F> {
         meta: &mut ConstraintSystem<F>,
         Self::ConfigArgs {
             challenges: _,
+            pi_commitment,
         }: Self::ConfigArgs,
     ) -> Self {
-        let num_limbs = 3;
-        let limb_bits = 88;
+        let num_limbs = NUM_LIMBS;
+        let limb_bits = LIMB_BITS;
         // #[cfg(feature = "onephase")]
         let num_advice = [35];
         // #[cfg(not(feature = "onephase"))]
@@ -117,12 +141,18 @@ impl<F: Field> SubCircuitConfig<F> for MyEccCircuitConfig<F> {
         meta.enable_equality(hash);
         meta.enable_equality(instance);
 
+        let poseidon_commitment = match pi_commitment {
+            PiCommitmentMode::Keccak => None,
+            PiCommitmentMode::Poseidon => Some(PoseidonCommitmentConfig::configure(meta, hash)),
+        };
+
         Self {
             fp_config,
             num_limbs,
             limb_bits,
             hash,
             instance,
+            poseidon_commitment,
             _marker: PhantomData,
         }
     }
@@ -132,13 +162,21 @@ impl<F: Field> SubCircuitConfig<F> for MyEccCircuitConfig<F> {
 // ti = e(ci^wi, L) * e(di^wi, ri)
 // n = 10   T = t1 * t2 * .. * t10
 #[derive(Clone, Debug, Default)]
-pub struct MyEccCircuit<F: Field, const XI_0: i64> {
-    ///c_i d_i
+pub struct MyEccCircuit<F: Field, const XI_0: i64, P: PiCommitment = PoseidonCommitment> {
+    ///c_i d_i, of every row of the policy when there is one
     pub p1s: Vec<(U256, U256)>,
     ///R_i
     pub p2s: Vec<(U256, U256, U256, U256)>,
     /// w_i 
     pub ws: Vec<Fr>,
+    /// Access policy of the ciphertext, which w_i must reconstruct the secret of. Required, and
+    /// bound into the public input in both commitment modes.
+    pub policy: LsssPolicy,
+    /// Row of the policy that each w_i and R_i is for, i.e. the attribute selection
+    pub rows: Vec<usize>,
+    /// H(x) of the attribute x each R_i is issued for, committed to with the transformation key.
+    /// Row i must be labelled with it, i.e. H(ρ(rows[i])) = H(x_i).
+    pub key_attributes: Vec<G1Affine>,
     /// L
     pub p4: (U256, U256, U256, U256),
     /// C'
@@ -148,10 +186,10 @@ pub struct MyEccCircuit<F: Field, const XI_0: i64> {
     /// test
     pub ti: Fq12,
     /// test
-    pub _marker: PhantomData<F>,
+    pub _marker: PhantomData<(F, P)>,
 }
 
-impl<F: Field, const XI_0: i64> MyEccCircuit<F, XI_0>{
+impl<F: Field, const XI_0: i64, P: PiCommitment> MyEccCircuit<F, XI_0, P>{
     /// Return the minimum number of rows required to prove an input of a
     /// particular size.
     pub fn min_num_rows() -> usize {
@@ -166,6 +204,69 @@ impl<F: Field, const XI_0: i64> MyEccCircuit<F, XI_0>{
         (1 << 20) - (max_blinding_factor + 3)
     }
 
+    /// Field elements of the ciphertext committed to in [`PiCommitmentMode::Poseidon`]: the CRT
+    /// limbs of C_i and D_i, followed by those of C'. This is the message the
+    /// [`CiphertextCircuit`](crate::ciphertext_circuit::CiphertextCircuit) commits to.
+    pub fn ciphertext_message(&self) -> Vec<Fr> {
+        self.p1s
+            .iter()
+            .chain(iter::once(&self.ct))
+            .flat_map(|p1| [p1.0, p1.1])
+            .flat_map(word_limbs)
+            .collect()
+    }
+
+    /// Field elements of the transformation key committed to in [`PiCommitmentMode::Poseidon`]:
+    /// the CRT limbs of w_i, L, R_i, R' and the H(x_i) of the key attributes, in the order the
+    /// circuit loads them. G2 coordinates are taken as (x.c0, x.c1, y.c0, y.c1).
+    pub fn key_message(&self) -> Vec<Fr> {
+        let g2_words = |p: &(U256, U256, U256, U256)| [p.1, p.0, p.3, p.2];
+        iter::empty()
+            .chain(self.ws.iter().map(|w| U256::from_little_endian(&w.to_bytes())))
+            .chain(g2_words(&self.p4))
+            .chain(self.p2s.iter().flat_map(g2_words))
+            .chain(g2_words(&self.tk))
+            .chain(self.key_attributes.iter().flat_map(g1_words))
+            .flat_map(word_limbs)
+            .collect()
+    }
+
+    /// Field elements of the result committed to in [`PiCommitmentMode::Poseidon`]: the CRT
+    /// limbs of the coefficients of T.
+    pub fn result_message(&self) -> Vec<Fr> {
+        self.ti
+            .coeffs()
+            .iter()
+            .flat_map(|coeff| word_limbs(U256::from_little_endian(&coeff.to_bytes())))
+            .collect()
+    }
+
+    /// Commitment to the ciphertext.
+    pub fn ciphertext_commitment(&self) -> Fr {
+        poseidon_commitment(&self.ciphertext_message())
+    }
+
+    /// Commitment to the transformation key.
+    pub fn key_commitment(&self) -> Fr {
+        poseidon_commitment(&self.key_message())
+    }
+
+    /// Commitment to the task data, the ciphertext and the transformation key, which the contract
+    /// stores when the task is created.
+    pub fn data_commitment(&self) -> Fr {
+        poseidon_commitment(&[self.ciphertext_commitment(), self.key_commitment()])
+    }
+
+    /// Commitment to the access policy, which the contract stores when the task is created.
+    pub fn policy_commitment(&self) -> Fr {
+        self.policy.commitment()
+    }
+
+    /// Commitment to the result, which the contract stores when the result is submitted.
+    pub fn result_commitment(&self) -> Fr {
+        poseidon_commitment(&self.result_message())
+    }
+
     /// Assign witness from the ecXX ops to the circuit.
     pub(crate) fn assign(
         &self,
@@ -173,6 +274,11 @@ impl<F: Field, const XI_0: i64> MyEccCircuit<F, XI_0>{
         config: &<Self as SubCircuit<F>>::Config,
         challenges: &Challenges<Value<F>>,
     ) -> Result<(), Error> {
+        // the keccak digest is computed off-circuit, so it cannot bind the policy and the result
+        if P::MODE == PiCommitmentMode::Keccak {
+            error!("keccak public input is not constrained, use the poseidon commitment");
+            return Err(Error::Synthesis);
+        }
 
         // keccak powers of randomness.
         let keccak_powers = std::iter::successors(Some(Value::known(F::one())), |coeff| {
@@ -202,7 +308,7 @@ impl<F: Field, const XI_0: i64> MyEccCircuit<F, XI_0>{
 
         println!("begin assign");
 
-        let assign_ecc = layouter.assign_region(
+        let pi_commitment = layouter.assign_region(
             || "ecc circuit",
             |mut region| {
 
@@ -215,6 +321,10 @@ impl<F: Field, const XI_0: i64> MyEccCircuit<F, XI_0>{
                 let mut ws = vec![];
                 
                 let mut d_g1_points_mul_w = vec![];
+                // CRT limbs of the ciphertext and of the transformation key, in the order of
+                // `Self::ciphertext_message` and `Self::key_message`
+                let mut ciphertext_limbs = vec![];
+                let mut key_limbs = vec![];
                 
                 //process C_i  D_i
                 for i in 0..(self.p1s.len()/2){
@@ -223,6 +333,7 @@ impl<F: Field, const XI_0: i64> MyEccCircuit<F, XI_0>{
                         self.precheck_fq(&mut ctx, &ecc_chip,self.p1s[ci].0 , powers_of_256.as_slice());
                     let (g1y, g1y_cells, g1y_valid, g1y_is_zero) =
                         self.precheck_fq(&mut ctx, &ecc_chip, self.p1s[ci].1, powers_of_256.as_slice());
+                    ciphertext_limbs.extend(g1x.truncation.limbs.iter().chain(&g1y.truncation.limbs).cloned());
                     let c_g1_point = EcPoint::<F, CRTInteger<F>>::construct(g1x, g1y);
                     c_g1_points.push(c_g1_point);
 
@@ -231,15 +342,33 @@ impl<F: Field, const XI_0: i64> MyEccCircuit<F, XI_0>{
                         self.precheck_fq(&mut ctx, &ecc_chip,self.p1s[di].0 , powers_of_256.as_slice());
                     let (g1y, g1y_cells, g1y_valid, g1y_is_zero) =
                         self.precheck_fq(&mut ctx, &ecc_chip, self.p1s[di].1, powers_of_256.as_slice());
+                    ciphertext_limbs.extend(g1x.truncation.limbs.iter().chain(&g1y.truncation.limbs).cloned());
                     let d_g1_point = EcPoint::<F, CRTInteger<F>>::construct(g1x, g1y);
                     d_g1_points.push(d_g1_point);
                 }
 
                 //process w_i
+                let mut w_scalars = vec![];
                 for w_i in self.ws.iter() {
-                    ws.push(self.handle_fr(&mut ctx, &fr_chip, *w_i).scalar.limbs().to_vec());
+                    let w_scalar = self.handle_fr(&mut ctx, &fr_chip, *w_i).scalar;
+                    let w_limbs = w_scalar.limbs().to_vec();
+                    key_limbs.extend(w_limbs.iter().cloned());
+                    ws.push(w_limbs);
+                    w_scalars.push(w_scalar);
                 }
 
+                // C_i and D_i are those of the rows the w_i are for
+                let key_attributes = self.load_key_attributes(&mut ctx, &ecc_chip);
+                let (c_g1_points, d_g1_points, policy_limbs) = self.assign_policy(
+                    &mut ctx,
+                    &ecc_chip,
+                    &fr_chip,
+                    &w_scalars,
+                    &key_attributes,
+                    &c_g1_points,
+                    &d_g1_points,
+                )?;
+
                 // let zero = G1Affine::from(G1::identity());
                 // let (g1x, g1x_cells, g1x_valid, g1x_is_zero) =
                 //     self.precheck_fq(&mut ctx, &ecc_chip,U256::from_little_endian(&zero.x.to_bytes()), powers_of_256.as_slice());
@@ -284,6 +413,9 @@ impl<F: Field, const XI_0: i64> MyEccCircuit<F, XI_0>{
                     self.precheck_fq(&mut ctx, &ecc_chip, self.p4.3, powers_of_256.as_slice());
                 let (g2y1, g2y1_cells, g2y1_valid, g2y1_is_zero) =
                     self.precheck_fq(&mut ctx, &ecc_chip, self.p4.2, powers_of_256.as_slice());
+                for coordinate in [&g2x0, &g2x1, &g2y0, &g2y1] {
+                    key_limbs.extend(coordinate.truncation.limbs.iter().cloned());
+                }
                 let L_point = EcPoint::<F, FieldExtPoint<CRTInteger<F>>>::construct(
                     FieldExtPoint::construct(vec![g2x0, g2x1]),
                     FieldExtPoint::construct(vec![g2y0, g2y1]),
@@ -300,6 +432,9 @@ impl<F: Field, const XI_0: i64> MyEccCircuit<F, XI_0>{
                         self.precheck_fq(&mut ctx, &ecc_chip, p2.3, powers_of_256.as_slice());
                     let (g2y1, g2y1_cells, g2y1_valid, g2y1_is_zero) =
                         self.precheck_fq(&mut ctx, &ecc_chip, p2.2, powers_of_256.as_slice());
+                    for coordinate in [&g2x0, &g2x1, &g2y0, &g2y1] {
+                        key_limbs.extend(coordinate.truncation.limbs.iter().cloned());
+                    }
                     let g2_point = EcPoint::<F, FieldExtPoint<CRTInteger<F>>>::construct(
                         FieldExtPoint::construct(vec![g2x0, g2x1]),
                         FieldExtPoint::construct(vec![g2y0, g2y1]),
@@ -322,6 +457,7 @@ impl<F: Field, const XI_0: i64> MyEccCircuit<F, XI_0>{
                     self.precheck_fq(&mut ctx, &ecc_chip, self.ct.0, powers_of_256.as_slice());
                 let (g1y, g1y_cells, g1y_valid, g1y_is_zero) =
                     self.precheck_fq(&mut ctx, &ecc_chip, self.ct.1, powers_of_256.as_slice());
+                ciphertext_limbs.extend(g1x.truncation.limbs.iter().chain(&g1y.truncation.limbs).cloned());
                 let C_p_point = EcPoint::<F, CRTInteger<F>>::construct(g1x, g1y);
 
 
@@ -333,14 +469,20 @@ impl<F: Field, const XI_0: i64> MyEccCircuit<F, XI_0>{
                     self.precheck_fq(&mut ctx, &ecc_chip, self.tk.3, powers_of_256.as_slice());
                 let (g2y1, g2y1_cells, g2y1_valid, g2y1_is_zero) =
                     self.precheck_fq(&mut ctx, &ecc_chip, self.tk.2, powers_of_256.as_slice());
+                for coordinate in [&g2x0, &g2x1, &g2y0, &g2y1] {
+                    key_limbs.extend(coordinate.truncation.limbs.iter().cloned());
+                }
                 let Rp_point = EcPoint::<F, FieldExtPoint<CRTInteger<F>>>::construct(
                     FieldExtPoint::construct(vec![g2x0, g2x1]),
                     FieldExtPoint::construct(vec![g2y0, g2y1]),
                 );
+                for h in key_attributes.iter() {
+                    key_limbs.extend(h.x.truncation.limbs.iter().chain(&h.y.truncation.limbs).cloned());
+                }
 
                 pairs.push((&C_p_point, &Rp_point));
                 // let pairs = vec![(&g1_point, &g2_point)];
-                let success = {
+                let result_limbs = {
                     let gt = {
                         let gt = pairing_chip.multi_miller_loop(&mut ctx, pairs);
                         pairing_chip.final_exp(&mut ctx, &gt)
@@ -367,7 +509,16 @@ impl<F: Field, const XI_0: i64> MyEccCircuit<F, XI_0>{
                     //     println!("recover gt i:{} :{:?}", i, gt.coeffs[i].truncation.limbs[2]);
                     // }
                     
-                    let ti = fp12_chip.load_constant(&mut ctx, self.ti);
+                    let ti = match P::MODE {
+                        PiCommitmentMode::Keccak => fp12_chip.load_constant(&mut ctx, self.ti),
+                        // the result is committed to in the public input instead
+                        PiCommitmentMode::Poseidon => fp12_chip.load_private(
+                            &mut ctx,
+                            Fp12Chip::<F, FpConfig<F, Fq>, Fq12, XI_0>::fe_to_witness(
+                                &Value::known(self.ti),
+                            ),
+                        ),
+                    };
 
                     
 
@@ -377,44 +528,244 @@ impl<F: Field, const XI_0: i64> MyEccCircuit<F, XI_0>{
                     //     println!("real gt i:{} :{:?}", i, ti.coeffs[i].truncation.limbs[1]);
                     //     println!("real gt i:{} :{:?}", i, ti.coeffs[i].truncation.limbs[2]);
                     // }
-                    fp12_chip.assert_equal(&mut ctx, &gt, &ti)
+                    fp12_chip.assert_equal(&mut ctx, &gt, &ti);
+                    ti.coeffs
+                        .iter()
+                        .flat_map(|coeff| coeff.truncation.limbs.iter().cloned())
+                        .collect_vec()
                 };                
+                let pi_commitment = match P::MODE {
+                    PiCommitmentMode::Keccak => None,
+                    PiCommitmentMode::Poseidon => Some(self.assign_pi_commitment(
+                        &mut ctx,
+                        &ecc_chip,
+                        ciphertext_limbs,
+                        key_limbs,
+                        policy_limbs,
+                        result_limbs,
+                    )),
+                };
                 ctx.print_stats(&["EccCircuit: FpConfig Full Context"]);
-                
-                Ok(success)
-            });
 
-            println!("begin assign hash");
+                Ok(pi_commitment)
+            })?;
 
-            let cells = layouter.assign_region(
-                || "expose pi hash",
-                |mut region| {
+            let (messages, pi) = pi_commitment.ok_or(Error::Synthesis)?;
+            config
+                .poseidon_commitment
+                .as_ref()
+                .expect("poseidon commitment configured")
+                .assign(layouter, &messages)?;
+            layouter.constrain_instance(pi.cell, config.instance, 0)
+    }
 
-                    let mut cells = vec![];
-                    let instance = self.instance()[0].clone();
-                    let mut offset = 0;
+    /// Assign the commitments to the ciphertext, the transformation key, the policy and the
+    /// result, returning the messages to look up in the poseidon table along with the public input.
+    fn assign_pi_commitment(
+        &self,
+        ctx: &mut Context<F>,
+        ecc_chip: &EccChip<F, FpConfig<F, Fq>>,
+        ciphertext_limbs: Vec<AssignedValue<F>>,
+        key_limbs: Vec<AssignedValue<F>>,
+        policy_limbs: Vec<AssignedValue<F>>,
+        result_limbs: Vec<AssignedValue<F>>,
+    ) -> (Vec<AssignedMessage<F>>, AssignedValue<F>) {
+        let gate = ecc_chip.field_chip().range().gate();
+        let mut messages = assign_messages(
+            ctx,
+            gate,
+            vec![
+                (ciphertext_limbs, self.ciphertext_message()),
+                (key_limbs, self.key_message()),
+                (policy_limbs, self.policy.message()),
+                (result_limbs, self.result_message()),
+            ],
+        );
 
-                    for i in 0..instance.len() {
+        let data_message = assign_messages(
+            ctx,
+            gate,
+            vec![(
+                vec![
+                    messages[0].commitment.clone(),
+                    messages[1].commitment.clone(),
+                ],
+                vec![self.ciphertext_commitment(), self.key_commitment()],
+            )],
+        )
+        .remove(0);
+        let data = data_message.commitment.clone();
+        let policy = messages[2].commitment.clone();
+        let result = messages[3].commitment.clone();
+        messages.push(data_message);
+        let (pi_messages, pi) = assign_pi_messages(
+            ctx,
+            gate,
+            (data, self.data_commitment()),
+            (policy, self.policy_commitment()),
+            (result, self.result_commitment()),
+        );
+        messages.extend(pi_messages);
 
-                        let cell = region.assign_advice(
-                            ||"assign pi hash", 
-                            config.hash, 
-                            offset, 
-                            || Value::known(instance[i])
-                        )?;
-                        cells.push(cell);
-                        offset += 1;
-                    }
-                    Ok(cells)          
-                },
-            )?;
-            println!("end assign hash");
-            // for i in 0..cells.len() {
-            //     println!("index{:?}", i);
-            //     layouter.constrain_instance(cells[i].cell(), config.instance, i)?;
-            // }
-
-            Ok(())
+        (messages, pi)
+    }
+
+    /// Check that the policy and the attribute selection are well formed: a non-empty M with at
+    /// least one column and an H(ρ(i)), C_i and D_i for each row, and a row, R_i and H(x_i) for
+    /// each of at least one w_i.
+    fn check_policy(&self) -> Result<(), Error> {
+        let (num_rows, num_columns) = (self.policy.num_rows(), self.policy.num_columns());
+        if num_rows == 0 || num_columns == 0 {
+            error!("policy has {num_rows} rows and {num_columns} columns");
+            return Err(Error::Synthesis);
+        }
+        if self
+            .policy
+            .matrix
+            .iter()
+            .any(|row| row.len() != num_columns)
+            || self.policy.attributes.len() != num_rows
+            || self.p1s.len() != 2 * num_rows
+        {
+            error!(
+                "policy rows are not all of {num_columns} entries with an attribute, C_i and D_i"
+            );
+            return Err(Error::Synthesis);
+        }
+        let num_ws = self.ws.len();
+        if num_ws == 0
+            || self.rows.len() != num_ws
+            || self.p2s.len() != num_ws
+            || self.key_attributes.len() != num_ws
+        {
+            error!(
+                "{num_ws} w_i with {} rows, {} R_i and {} key attributes",
+                self.rows.len(),
+                self.p2s.len(),
+                self.key_attributes.len(),
+            );
+            return Err(Error::Synthesis);
+        }
+        if let Some(row) = self.rows.iter().find(|row| **row >= num_rows) {
+            error!("row {row} is not one of the {num_rows} rows of the policy");
+            return Err(Error::Synthesis);
+        }
+        Ok(())
+    }
+
+    /// Load the H(x_i) of the attributes the R_i are issued for.
+    fn load_key_attributes(
+        &self,
+        ctx: &mut Context<F>,
+        ecc_chip: &EccChip<F, FpConfig<F, Fq>>,
+    ) -> Vec<EcPoint<F, CRTInteger<F>>> {
+        self.key_attributes
+            .iter()
+            .map(|h| ecc_chip.load_private(ctx, (Value::known(h.x), Value::known(h.y))))
+            .collect()
+    }
+
+    /// Constrain w_i to reconstruct the secret under the policy, i.e. Σ w_i·M_rows[i] =
+    /// (1, 0, ..., 0) over Fr, and select C_i and D_i of the rows used. The rows are witnesses
+    /// selected by indicator, so the circuit does not depend on the attribute selection, and row i
+    /// is bound to R_i by its attribute: H(ρ(rows[i])) = H(x_i) of the key. Returns the selected
+    /// points along with the CRT limbs of the policy, in the order of [`LsssPolicy::message`].
+    #[allow(clippy::type_complexity, clippy::too_many_arguments)]
+    fn assign_policy(
+        &self,
+        ctx: &mut Context<F>,
+        ecc_chip: &EccChip<F, FpConfig<F, Fq>>,
+        fr_chip: &FpConfig<F, Fr>,
+        ws: &[CRTInteger<F>],
+        key_attributes: &[EcPoint<F, CRTInteger<F>>],
+        c_points: &[EcPoint<F, CRTInteger<F>>],
+        d_points: &[EcPoint<F, CRTInteger<F>>],
+    ) -> Result<
+        (
+            Vec<EcPoint<F, CRTInteger<F>>>,
+            Vec<EcPoint<F, CRTInteger<F>>>,
+            Vec<AssignedValue<F>>,
+        ),
+        Error,
+    > {
+        self.check_policy()?;
+        let gate = ecc_chip.field_chip().range().gate();
+
+        let matrix = self
+            .policy
+            .matrix
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|m| self.handle_fr(ctx, fr_chip, *m).scalar)
+                    .collect_vec()
+            })
+            .collect_vec();
+        let attributes = self
+            .policy
+            .attributes
+            .iter()
+            .map(|h| ecc_chip.load_private(ctx, (Value::known(h.x), Value::known(h.y))))
+            .collect_vec();
+        let policy_limbs = matrix
+            .iter()
+            .flatten()
+            .flat_map(|m| m.truncation.limbs.iter().cloned())
+            .chain(attributes.iter().flat_map(|h| {
+                h.x.truncation
+                    .limbs
+                    .iter()
+                    .chain(&h.y.truncation.limbs)
+                    .cloned()
+            }))
+            .collect_vec();
+
+        // Σ w_i·M_rows[i], column by column
+        let mut sums: Vec<Option<CRTInteger<F>>> = vec![None; self.policy.num_columns()];
+        let mut c_selected = vec![];
+        let mut d_selected = vec![];
+        for ((w, row), key_attribute) in ws.iter().zip_eq(&self.rows).zip_eq(key_attributes) {
+            let row = gate
+                .assign_witnesses(ctx, vec![Value::known(F::from(*row as u64))])
+                .remove(0);
+            let indicator =
+                gate.idx_to_indicator(ctx, QuantumCell::Existing(row), self.policy.num_rows());
+            // the row is one of the policy
+            let is_row = gate.sum(
+                ctx,
+                indicator.iter().map(|bit| QuantumCell::Existing(bit.clone())),
+            );
+            gate.assert_is_const(ctx, &is_row, F::one());
+
+            for (j, sum) in sums.iter_mut().enumerate() {
+                let column = matrix.iter().map(|row| row[j].clone()).collect_vec();
+                let m = fr_chip.select_by_indicator(ctx, &column, &indicator);
+                let term = fr_chip.mul_no_carry(ctx, w, &m);
+                *sum = Some(match sum.take() {
+                    None => term,
+                    Some(sum) => fr_chip.add_no_carry(ctx, &sum, &term),
+                });
+            }
+            // the row is labelled with the attribute R_i is issued for
+            let attribute = ecc_chip.select_by_indicator(ctx, &attributes, &indicator);
+            ecc_chip.assert_equal(ctx, &attribute, key_attribute);
+            c_selected.push(ecc_chip.select_by_indicator(ctx, c_points, &indicator));
+            d_selected.push(ecc_chip.select_by_indicator(ctx, d_points, &indicator));
+        }
+        for (j, sum) in sums.into_iter().enumerate() {
+            // checked to be there by `check_policy`
+            let Some(sum) = sum else {
+                return Err(Error::Synthesis);
+            };
+            let diff = if j == 0 {
+                fr_chip.add_constant_no_carry(ctx, &sum, -Fr::one())
+            } else {
+                sum
+            };
+            fr_chip.check_carry_mod_to_zero(ctx, &diff);
+        }
+
+        Ok((c_selected, d_selected, policy_limbs))
     }
 
     /// Return an assigned value that indicates whether the given point is on curve G1 or identity
@@ -608,7 +959,7 @@ impl<F: Field, const XI_0: i64> MyEccCircuit<F, XI_0>{
         }
 }
 
-impl<F: Field, const XI_0: i64> SubCircuit<F> for MyEccCircuit<F, XI_0> {
+impl<F: Field, const XI_0: i64, P: PiCommitment> SubCircuit<F> for MyEccCircuit<F, XI_0, P> {
     type Config = MyEccCircuitConfig<F>;
     
 
@@ -655,6 +1006,15 @@ impl<F: Field, const XI_0: i64> SubCircuit<F> for MyEccCircuit<F, XI_0> {
 
     /// Compute the public inputs for this circuit.
     fn instance(&self) -> Vec<Vec<F>> {
+        if P::MODE == PiCommitmentMode::Poseidon {
+            let pi = poseidon_pi_commitment(
+                self.data_commitment(),
+                self.policy_commitment(),
+                self.result_commitment(),
+            );
+            return vec![vec![F::from(pi)]];
+        }
+
         let mut bytes = Vec::with_capacity(4000);
         
         for i in 0..self.p1s.len() {
@@ -686,6 +1046,15 @@ impl<F: Field, const XI_0: i64> SubCircuit<F> for MyEccCircuit<F, XI_0> {
         bytes.extend_from_slice(&self.tk.2.to_le_bytes().as_slice());
         bytes.extend_from_slice(&self.tk.3.to_le_bytes().as_slice());
 
+        // the policy, and the attributes of the key its rows are checked against
+        for m in self.policy.matrix.iter().flatten() {
+            bytes.extend_from_slice(&m.to_bytes().as_slice());
+        }
+        for h in self.policy.attributes.iter().chain(&self.key_attributes) {
+            bytes.extend_from_slice(&h.x.to_bytes().as_slice());
+            bytes.extend_from_slice(&h.y.to_bytes().as_slice());
+        }
+
         let mut result_bytes = Vec::new();
         for i in self.ti.coeffs().iter() {
             result_bytes.extend_from_slice(i.to_bytes().as_slice());
@@ -750,8 +1119,8 @@ impl<F: Field, const XI_0: i64> SubCircuit<F> for MyEccCircuit<F, XI_0> {
     }
 }
 
-impl<const XI_0: i64> CircuitExt<Fr> for  MyEccCircuit<Fr, XI_0>  {
-    /// 32 elements from digest
+impl<const XI_0: i64, P: PiCommitment> CircuitExt<Fr> for  MyEccCircuit<Fr, XI_0, P>  {
+    /// 32 elements from digest, or the poseidon commitment
     fn num_instance(&self) -> Vec<usize> {
         self.instances().iter().map(|l| l.len()).collect_vec()
     }
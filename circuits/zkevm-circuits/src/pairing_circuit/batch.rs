@@ -0,0 +1,636 @@
+//! Batched pairing circuit, proving the results of k decryption tasks under one transformation
+//! key (L, R_i, R') in a single proof.
+//!
+//! The result of task j, with coefficients w_{j,i}, is
+//! T_j = e(Σ_i w_{j,i}·C_{j,i}, L) · Π_i e(w_{j,i}·D_{j,i}, R_i) · e(C'_j, R').
+//! Each T_j is checked to lie in GT, and the k equations are combined with the powers of a
+//! challenge ρ, the low 128 bits of the Poseidon commitment to the public inputs:
+//! Π_j T_j^(ρ^j) = e(Σ_j ρ^j Σ_i w_{j,i}·C_{j,i}, L) · Π_i e(Σ_j ρ^j w_{j,i}·D_{j,i}, R_i)
+//! · e(Σ_j ρ^j·C'_j, R').
+//! The key is loaded once, and the right hand side is a single multi Miller loop over n + 2 pairs
+//! with a single final exponentiation, whatever k.
+//!
+//! Task j is committed to as in [`PiCommitmentMode::Poseidon`](super::PiCommitmentMode), and its
+//! public input is exposed on row j of the instance, so the contract checks each against its own
+//! task.
+use std::iter;
+
+use eth_types::U256;
+use halo2_base::{
+    gates::{GateInstructions, RangeInstructions},
+    utils::{fe_to_biguint, modulus},
+    AssignedValue, Context, QuantumCell, SKIP_FIRST_PASS,
+};
+use halo2_ecc::{
+    bigint::{CRTInteger, OverflowInteger},
+    bn254::pairing::PairingChip,
+    ecc::{EcPoint, EccChip},
+    fields::{fp::FpConfig, fp12::Fp12Chip, fp2::Fp2Chip, FieldChip, FieldExtPoint, Selectable},
+};
+use halo2_proofs::{
+    circuit::{Layouter, Value},
+    halo2curves::{
+        bn256::{Fq, Fq12, Fq2, Fr},
+        group::ff::PrimeField,
+    },
+    plonk::Error,
+};
+use itertools::Itertools;
+use log::error;
+use num_bigint::BigInt;
+use snark_verifier_sdk::CircuitExt;
+
+use crate::{
+    evm_circuit::param::N_BYTES_WORD,
+    util::{Challenges, Field, SubCircuit},
+    witness::Block,
+};
+
+use super::{
+    assign_messages, poseidon_commitment, poseidon_pi_commitment, MyEccCircuit, MyEccCircuitConfig,
+    PoseidonCommitment,
+};
+
+/// ξ = 9 + u of the BN254 Fq12 tower.
+const XI_0: i64 = 9;
+/// Number of bits of the challenge ρ, spread over the low CRT limbs of the scalar.
+const CHALLENGE_BITS: usize = 128;
+/// 6u² for the BN254 parameter u, as LE u64 words. p = r + 6u², so T^p = T^(6u²) iff T^r = 1.
+const SIX_U_SQUARED: [u64; 2] = [17887900258952609094, 8020209761171036667];
+/// Window bits of the variable base scalar multiplications.
+const WINDOW_BITS: usize = 4;
+
+/// A decryption task of a batch.
+pub type BatchTask<F> = MyEccCircuit<F, XI_0, PoseidonCommitment>;
+
+type Fp12Point<F> = FieldExtPoint<CRTInteger<F>>;
+
+/// Batched ECC Circuit proving the results of decryption tasks sharing a transformation key.
+#[derive(Clone, Debug, Default)]
+pub struct MyBatchEccCircuit<F: Field> {
+    /// Decryption tasks, which all have the same L, R_i and R'
+    pub tasks: Vec<BatchTask<F>>,
+}
+
+impl<F: Field> MyBatchEccCircuit<F> {
+    /// Batch the given tasks, which must share the transformation key.
+    pub fn new(tasks: Vec<BatchTask<F>>) -> Result<Self, Error> {
+        let batch = Self { tasks };
+        batch.check()?;
+        Ok(batch)
+    }
+
+    /// Check that the batch has at least one task, that every task is well formed with a w_i for
+    /// each R_i, and that the tasks share the transformation key.
+    fn check(&self) -> Result<(), Error> {
+        let Some(key) = self.tasks.first() else {
+            error!("a batch has at least one task");
+            return Err(Error::Synthesis);
+        };
+        for (j, task) in self.tasks.iter().enumerate() {
+            // which includes a w_i for each R_i
+            task.check_policy()?;
+            if task.p4 != key.p4
+                || task.p2s != key.p2s
+                || task.tk != key.tk
+                || task.key_attributes != key.key_attributes
+            {
+                error!("task {j} does not share the transformation key of the batch");
+                return Err(Error::Synthesis);
+            }
+        }
+        Ok(())
+    }
+
+    /// Return the minimum number of rows required to prove an input of a
+    /// particular size.
+    pub fn min_num_rows() -> usize {
+        BatchTask::<F>::min_num_rows()
+    }
+
+    /// The public input of each task, which commits to its inputs and result.
+    pub fn public_inputs(&self) -> Vec<Fr> {
+        self.tasks
+            .iter()
+            .map(|task| {
+                poseidon_pi_commitment(
+                    task.data_commitment(),
+                    task.policy_commitment(),
+                    task.result_commitment(),
+                )
+            })
+            .collect()
+    }
+
+    /// The challenge ρ the tasks are combined with: the low [`CHALLENGE_BITS`] bits of the
+    /// poseidon commitment to the public inputs of the batch.
+    pub fn challenge(&self) -> Fr {
+        let seed = poseidon_commitment(&self.public_inputs());
+        let mut low = [0; CHALLENGE_BITS / 8];
+        low.copy_from_slice(&seed.to_bytes()[..CHALLENGE_BITS / 8]);
+        Fr::from_u128(u128::from_le_bytes(low))
+    }
+
+    /// Assign the batch and the commitments to each task to the circuit.
+    pub(crate) fn assign(
+        &self,
+        layouter: &mut impl Layouter<F>,
+        config: &MyEccCircuitConfig<F>,
+    ) -> Result<(), Error> {
+        self.check()?;
+        let powers_of_256 = iter::successors(Some(F::one()), |coeff| Some(F::from(256) * coeff))
+            .take(N_BYTES_WORD)
+            .map(QuantumCell::Constant)
+            .collect_vec();
+        let powers_of_2 = iter::successors(Some(F::one()), |coeff| Some(F::from(2) * coeff))
+            .take(CHALLENGE_BITS)
+            .map(QuantumCell::Constant)
+            .collect_vec();
+
+        let ecc_chip = EccChip::<F, FpConfig<F, Fq>>::construct(config.fp_config.clone());
+        let fr_chip = FpConfig::<F, Fr>::construct(
+            config.fp_config.range.clone(),
+            config.limb_bits,
+            config.num_limbs,
+            modulus::<Fr>(),
+        );
+        let pairing_chip = PairingChip::construct(config.fp_config.clone());
+        let fp2_chip = Fp2Chip::<F, FpConfig<F, Fq>, Fq2>::construct(pairing_chip.fp_chip.clone());
+        let fp12_chip =
+            Fp12Chip::<F, FpConfig<F, Fq>, Fq12, XI_0>::construct(config.fp_config.clone());
+        let public_inputs = self.public_inputs();
+        let challenge = self.challenge();
+
+        let mut first_pass = SKIP_FIRST_PASS;
+
+        let pi_commitments = layouter.assign_region(
+            || "batch ecc circuit",
+            |region| {
+                if first_pass {
+                    first_pass = false;
+                    return Ok(None);
+                }
+
+                let mut ctx = config.fp_config.new_context(region);
+                let gate = ecc_chip.field_chip().range().gate();
+                let key = &self.tasks[0];
+
+                // L, R_i, R' and the key attributes, loaded once for the batch
+                let l_point = self.load_g2(&mut ctx, &ecc_chip, &fp2_chip, key.p4, &powers_of_256);
+                let r_points = key
+                    .p2s
+                    .iter()
+                    .map(|p2| self.load_g2(&mut ctx, &ecc_chip, &fp2_chip, *p2, &powers_of_256))
+                    .collect_vec();
+                let r_p_point =
+                    self.load_g2(&mut ctx, &ecc_chip, &fp2_chip, key.tk, &powers_of_256);
+                let key_attributes = key.load_key_attributes(&mut ctx, &ecc_chip);
+                // CRT limbs of the key points, in the order of `MyEccCircuit::key_message`
+                let key_point_limbs = iter::once(&l_point)
+                    .chain(&r_points)
+                    .chain(iter::once(&r_p_point))
+                    .flat_map(|point| point.x.coeffs.iter().chain(&point.y.coeffs))
+                    .flat_map(|coordinate| coordinate.truncation.limbs.iter().cloned())
+                    .chain(key_attributes.iter().flat_map(point_limbs))
+                    .collect_vec();
+
+                let mut messages = vec![];
+                let mut pis = vec![];
+                let mut tasks = vec![];
+                for task in &self.tasks {
+                    // C_i, D_i and C'
+                    let mut ciphertext_limbs = vec![];
+                    let mut c_points = vec![];
+                    let mut d_points = vec![];
+                    for (i, p1) in task.p1s.iter().enumerate() {
+                        let point = self.load_g1(&mut ctx, &ecc_chip, *p1, &powers_of_256);
+                        ciphertext_limbs.extend(point_limbs(&point));
+                        if i % 2 == 0 {
+                            c_points.push(point);
+                        } else {
+                            d_points.push(point);
+                        }
+                    }
+                    let c_p_point = self.load_g1(&mut ctx, &ecc_chip, task.ct, &powers_of_256);
+                    ciphertext_limbs.extend(point_limbs(&c_p_point));
+
+                    // w_i, reconstructing the secret under the policy
+                    let ws = task
+                        .ws
+                        .iter()
+                        .map(|w| task.handle_fr(&mut ctx, &fr_chip, *w).scalar)
+                        .collect_vec();
+                    let key_limbs = ws
+                        .iter()
+                        .flat_map(|w| w.truncation.limbs.iter().cloned())
+                        .chain(key_point_limbs.iter().cloned())
+                        .collect_vec();
+                    let (c_points, d_points, policy_limbs) = task.assign_policy(
+                        &mut ctx,
+                        &ecc_chip,
+                        &fr_chip,
+                        &ws,
+                        &key_attributes,
+                        &c_points,
+                        &d_points,
+                    )?;
+
+                    // T, which is in GT: T^p = T^(6u²)
+                    let ti = fp12_chip.load_private(
+                        &mut ctx,
+                        Fp12Chip::<F, FpConfig<F, Fq>, Fq12, XI_0>::fe_to_witness(&Value::known(
+                            task.ti,
+                        )),
+                    );
+                    let ti_p = fp12_chip.frobenius_map(&mut ctx, &ti, 1);
+                    let ti_6u2 = fp12_chip.pow(&mut ctx, &ti, SIX_U_SQUARED.to_vec());
+                    fp12_chip.assert_equal(&mut ctx, &ti_p, &ti_6u2);
+                    let result_limbs = ti
+                        .coeffs
+                        .iter()
+                        .flat_map(|coeff| coeff.truncation.limbs.iter().cloned())
+                        .collect_vec();
+
+                    let (task_messages, pi) = task.assign_pi_commitment(
+                        &mut ctx,
+                        &ecc_chip,
+                        ciphertext_limbs,
+                        key_limbs,
+                        policy_limbs,
+                        result_limbs,
+                    );
+                    messages.extend(task_messages);
+                    pis.push(pi);
+                    tasks.push((c_points, d_points, c_p_point, ws, ti));
+                }
+
+                // ρ, the low bits of the poseidon commitment to the public inputs, whose bits
+                // are spread over the limbs of the scalar
+                let seed =
+                    assign_messages(&mut ctx, gate, vec![(pis.clone(), public_inputs.clone())])
+                        .remove(0);
+                let mut rho_bits = canonical_bits(gate, &mut ctx, &seed.commitment);
+                messages.push(seed);
+                rho_bits.truncate(CHALLENGE_BITS);
+                let mut rho_limbs = rho_bits
+                    .chunks(fr_chip.limb_bits)
+                    .map(|bits| {
+                        gate.inner_product(
+                            &mut ctx,
+                            bits.iter()
+                                .map(|bit| QuantumCell::Existing(bit.clone()))
+                                .collect_vec(),
+                            powers_of_2[..bits.len()].to_vec(),
+                        )
+                    })
+                    .collect_vec();
+                rho_limbs.resize_with(fr_chip.num_limbs, || gate.load_zero(&mut ctx));
+                let rho_native = OverflowInteger::evaluate(
+                    gate,
+                    &mut ctx,
+                    &rho_limbs,
+                    fr_chip.limb_bases.iter().cloned(),
+                );
+                let rho = CRTInteger::construct(
+                    OverflowInteger::construct(rho_limbs, fr_chip.limb_bits),
+                    rho_native,
+                    Value::known(BigInt::from(fe_to_biguint(&challenge))),
+                );
+
+                // Π_j T_j^(ρ^j), by Horner's rule from the last task
+                let one = fp12_chip.load_constant(&mut ctx, Fq12::one());
+                let mut lhs = tasks.last().ok_or(Error::Synthesis)?.4.clone();
+                for (_, _, _, _, ti) in tasks.iter().rev().skip(1) {
+                    let lhs_rho = pow_bits(
+                        &fp12_chip,
+                        ecc_chip.field_chip(),
+                        &mut ctx,
+                        &lhs,
+                        &rho_bits,
+                        &one,
+                    );
+                    lhs = fp12_chip.mul(&mut ctx, &lhs_rho, ti);
+                }
+
+                // the G1 points of each pair, combined with the powers of ρ
+                let mut c_sum = None;
+                let mut d_sums = vec![None; r_points.len()];
+                let mut c_p_sum = None;
+                // ρ^j, none for the first task
+                let mut power: Option<CRTInteger<F>> = None;
+                for (c_points, d_points, c_p_point, ws, _) in &tasks {
+                    let scalars = match &power {
+                        None => ws.clone(),
+                        Some(power) => ws
+                            .iter()
+                            .map(|w| fr_chip.mul(&mut ctx, w, power))
+                            .collect_vec(),
+                    };
+                    for (((c, d), s), d_sum) in c_points
+                        .iter()
+                        .zip_eq(d_points)
+                        .zip_eq(&scalars)
+                        .zip_eq(d_sums.iter_mut())
+                    {
+                        let c_s = ecc_chip.scalar_mult(
+                            &mut ctx,
+                            c,
+                            &s.truncation.limbs,
+                            fr_chip.limb_bits,
+                            WINDOW_BITS,
+                        );
+                        c_sum = Some(accumulate(&ecc_chip, &mut ctx, c_sum, c_s));
+                        let d_s = ecc_chip.scalar_mult(
+                            &mut ctx,
+                            d,
+                            &s.truncation.limbs,
+                            fr_chip.limb_bits,
+                            WINDOW_BITS,
+                        );
+                        *d_sum = Some(accumulate(&ecc_chip, &mut ctx, d_sum.take(), d_s));
+                    }
+                    let c_p_s = match &power {
+                        None => c_p_point.clone(),
+                        Some(power) => ecc_chip.scalar_mult(
+                            &mut ctx,
+                            c_p_point,
+                            &power.truncation.limbs,
+                            fr_chip.limb_bits,
+                            WINDOW_BITS,
+                        ),
+                    };
+                    c_p_sum = Some(accumulate(&ecc_chip, &mut ctx, c_p_sum, c_p_s));
+
+                    power = Some(match power {
+                        None => rho.clone(),
+                        Some(power) => fr_chip.mul(&mut ctx, &power, &rho),
+                    });
+                }
+                // there is a task and a w_i, checked by `Self::check`
+                let c_sum = c_sum.ok_or(Error::Synthesis)?;
+                let d_sums = d_sums
+                    .into_iter()
+                    .map(|d_sum| d_sum.ok_or(Error::Synthesis))
+                    .collect::<Result<Vec<_>, _>>()?;
+                let c_p_sum = c_p_sum.ok_or(Error::Synthesis)?;
+
+                // (ΣC, L) (ΣD_i, R_i) (ΣC', R')
+                let pairs = iter::once((&c_sum, &l_point))
+                    .chain(d_sums.iter().zip_eq(&r_points))
+                    .chain(iter::once((&c_p_sum, &r_p_point)))
+                    .collect_vec();
+                let gt = {
+                    let gt = pairing_chip.multi_miller_loop(&mut ctx, pairs);
+                    pairing_chip.final_exp(&mut ctx, &gt)
+                };
+                fp12_chip.assert_equal(&mut ctx, &gt, &lhs);
+
+                // Finalize the Fp config always at the end of assignment.
+                let lookup_cells = config.fp_config.finalize(&mut ctx);
+                log::info!("total number of lookup cells: {}", lookup_cells);
+                ctx.print_stats(&["MyBatchEccCircuit: FpConfig context"]);
+
+                Ok(Some((messages, pis)))
+            },
+        )?;
+
+        if let Some((messages, pis)) = pi_commitments {
+            config
+                .poseidon_commitment
+                .as_ref()
+                .expect("poseidon commitment configured")
+                .assign(layouter, &messages)?;
+            for (row, pi) in pis.iter().enumerate() {
+                layouter.constrain_instance(pi.cell, config.instance, row)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Load a G1 point given by the LE words of its coordinates, asserting that it is on the
+    /// curve and not the point at infinity.
+    fn load_g1(
+        &self,
+        ctx: &mut Context<F>,
+        ecc_chip: &EccChip<F, FpConfig<F, Fq>>,
+        (x, y): (U256, U256),
+        powers_of_256: &[QuantumCell<F>],
+    ) -> EcPoint<F, CRTInteger<F>> {
+        let gate = ecc_chip.field_chip().range().gate();
+        let (x, x_is_zero) = self.load_fq(ctx, ecc_chip, x, powers_of_256);
+        let (y, y_is_zero) = self.load_fq(ctx, ecc_chip, y, powers_of_256);
+        let is_on_curve_or_infinity = self.tasks[0].is_on_curveg1_or_infinity(
+            ctx,
+            ecc_chip,
+            &x,
+            x_is_zero.clone(),
+            &y,
+            y_is_zero.clone(),
+        );
+        assert_on_curve(gate, ctx, is_on_curve_or_infinity, x_is_zero, y_is_zero);
+        EcPoint::construct(x, y)
+    }
+
+    /// Load a G2 point given by the LE words of its coordinates, as (x.c1, x.c0, y.c1, y.c0),
+    /// asserting that it is on the curve and not the point at infinity.
+    fn load_g2(
+        &self,
+        ctx: &mut Context<F>,
+        ecc_chip: &EccChip<F, FpConfig<F, Fq>>,
+        fp2_chip: &Fp2Chip<F, FpConfig<F, Fq>, Fq2>,
+        (x1, x0, y1, y0): (U256, U256, U256, U256),
+        powers_of_256: &[QuantumCell<F>],
+    ) -> EcPoint<F, FieldExtPoint<CRTInteger<F>>> {
+        let gate = ecc_chip.field_chip().range().gate();
+        let [(x0, x0_is_zero), (x1, x1_is_zero), (y0, y0_is_zero), (y1, y1_is_zero)] =
+            [x0, x1, y0, y1].map(|word| self.load_fq(ctx, ecc_chip, word, powers_of_256));
+        let x_is_zero = gate.and(
+            ctx,
+            QuantumCell::Existing(x0_is_zero),
+            QuantumCell::Existing(x1_is_zero),
+        );
+        let y_is_zero = gate.and(
+            ctx,
+            QuantumCell::Existing(y0_is_zero),
+            QuantumCell::Existing(y1_is_zero),
+        );
+        let x = FieldExtPoint::construct(vec![x0, x1]);
+        let y = FieldExtPoint::construct(vec![y0, y1]);
+        let is_on_curve_or_infinity = self.tasks[0].is_on_curveg2_or_infinity(
+            ctx,
+            fp2_chip,
+            &x,
+            x_is_zero.clone(),
+            &y,
+            y_is_zero.clone(),
+        );
+        assert_on_curve(gate, ctx, is_on_curve_or_infinity, x_is_zero, y_is_zero);
+        EcPoint::construct(x, y)
+    }
+
+    /// Load an Fq element given by its LE word, asserting that it is less than the modulus, and
+    /// return it along with whether it is zero.
+    fn load_fq(
+        &self,
+        ctx: &mut Context<F>,
+        ecc_chip: &EccChip<F, FpConfig<F, Fq>>,
+        word: U256,
+        powers_of_256: &[QuantumCell<F>],
+    ) -> (CRTInteger<F>, AssignedValue<F>) {
+        let fp_chip = ecc_chip.field_chip();
+        let (value, _, is_valid, is_zero) =
+            self.tasks[0].precheck_fq(ctx, ecc_chip, word, powers_of_256);
+        // the comparison with the modulus needs the limbs to be in range
+        fp_chip.range_check(ctx, &value, Fq::NUM_BITS as usize);
+        fp_chip
+            .range()
+            .gate()
+            .assert_is_const(ctx, &is_valid, F::one());
+        (value, is_zero)
+    }
+}
+
+/// Assert that a point is on the curve, given whether it is on the curve or (0, 0) and whether
+/// its coordinates are zero.
+fn assert_on_curve<F: Field>(
+    gate: &impl GateInstructions<F>,
+    ctx: &mut Context<F>,
+    is_on_curve_or_infinity: AssignedValue<F>,
+    x_is_zero: AssignedValue<F>,
+    y_is_zero: AssignedValue<F>,
+) {
+    let is_infinity = gate.and(
+        ctx,
+        QuantumCell::Existing(x_is_zero),
+        QuantumCell::Existing(y_is_zero),
+    );
+    gate.assert_is_const(ctx, &is_on_curve_or_infinity, F::one());
+    gate.assert_is_const(ctx, &is_infinity, F::zero());
+}
+
+/// LE bits of an assigned value, constrained to be its canonical decomposition, i.e. less than the
+/// modulus, so that the prover has no choice of bits.
+fn canonical_bits<F: Field>(
+    gate: &impl GateInstructions<F>,
+    ctx: &mut Context<F>,
+    a: &AssignedValue<F>,
+) -> Vec<AssignedValue<F>> {
+    let bits = gate.num_to_bits(ctx, a, F::NUM_BITS as usize);
+    let modulus_bits = modulus::<F>().to_radix_le(2);
+
+    // from the most significant bit, whether the bits so far are equal to or less than those of
+    // the modulus
+    let mut is_eq = QuantumCell::Constant(F::one());
+    let mut is_lt = QuantumCell::Constant(F::zero());
+    for (i, bit) in bits.iter().enumerate().rev() {
+        let not_bit = gate.not(ctx, QuantumCell::Existing(bit.clone()));
+        if modulus_bits.get(i) == Some(&1) {
+            let is_lt_here = gate.and(ctx, is_eq.clone(), QuantumCell::Existing(not_bit));
+            is_lt = QuantumCell::Existing(gate.or(ctx, is_lt, QuantumCell::Existing(is_lt_here)));
+            is_eq = QuantumCell::Existing(gate.and(ctx, is_eq, QuantumCell::Existing(bit.clone())));
+        } else {
+            is_eq = QuantumCell::Existing(gate.and(ctx, is_eq, QuantumCell::Existing(not_bit)));
+        }
+    }
+    gate.assert_equal(ctx, is_lt, QuantumCell::Constant(F::one()));
+    bits
+}
+
+/// CRT limbs of the coordinates of an assigned G1 point.
+fn point_limbs<F: Field>(
+    point: &EcPoint<F, CRTInteger<F>>,
+) -> impl Iterator<Item = AssignedValue<F>> + '_ {
+    point
+        .x
+        .truncation
+        .limbs
+        .iter()
+        .chain(&point.y.truncation.limbs)
+        .cloned()
+}
+
+/// Add a point to a running sum, which starts empty.
+fn accumulate<F: Field>(
+    ecc_chip: &EccChip<F, FpConfig<F, Fq>>,
+    ctx: &mut Context<F>,
+    sum: Option<EcPoint<F, CRTInteger<F>>>,
+    point: EcPoint<F, CRTInteger<F>>,
+) -> EcPoint<F, CRTInteger<F>> {
+    match sum {
+        None => point,
+        Some(sum) => ecc_chip.add_unequal(ctx, &sum, &point, false),
+    }
+}
+
+/// a^e in Fq12 for the exponent e given by its LE bits, by square and multiply.
+fn pow_bits<F: Field>(
+    fp12_chip: &Fp12Chip<F, FpConfig<F, Fq>, Fq12, XI_0>,
+    fp_chip: &FpConfig<F, Fq>,
+    ctx: &mut Context<F>,
+    a: &Fp12Point<F>,
+    bits: &[AssignedValue<F>],
+    one: &Fp12Point<F>,
+) -> Fp12Point<F> {
+    let mut acc = one.clone();
+    for bit in bits.iter().rev() {
+        acc = fp12_chip.mul(ctx, &acc, &acc);
+        let acc_a = fp12_chip.mul(ctx, &acc, a);
+        acc = FieldExtPoint::construct(
+            acc_a
+                .coeffs
+                .iter()
+                .zip_eq(&acc.coeffs)
+                .map(|(acc_a, acc)| fp_chip.select(ctx, acc_a, acc, bit))
+                .collect(),
+        );
+    }
+    acc
+}
+
+impl<F: Field> SubCircuit<F> for MyBatchEccCircuit<F> {
+    type Config = MyEccCircuitConfig<F>;
+
+    fn new_from_block(_block: &Block) -> Self {
+        unimplemented!("the batch ecc circuit is built from decryption tasks, not from a block")
+    }
+
+    /// Returns number of unusable rows of the SubCircuit, which should be
+    /// `meta.blinding_factors() + 1`.
+    fn unusable_rows() -> usize {
+        BatchTask::<F>::unusable_rows()
+    }
+
+    /// Compute the public inputs for this circuit, that of each task in turn.
+    fn instance(&self) -> Vec<Vec<F>> {
+        vec![self
+            .tasks
+            .iter()
+            .map(|task| task.instance()[0][0])
+            .collect()]
+    }
+
+    fn synthesize_sub(
+        &self,
+        config: &Self::Config,
+        _challenges: &Challenges<Value<F>>,
+        layouter: &mut impl Layouter<F>,
+    ) -> Result<(), Error> {
+        config.fp_config.range.load_lookup_table(layouter)?;
+        self.assign(layouter, config)
+    }
+
+    fn min_num_rows_block(_block: &Block) -> (usize, usize) {
+        (Self::min_num_rows(), Self::min_num_rows())
+    }
+}
+
+impl CircuitExt<Fr> for MyBatchEccCircuit<Fr> {
+    /// The public input of each task
+    fn num_instance(&self) -> Vec<usize> {
+        vec![self.tasks.len()]
+    }
+
+    fn instances(&self) -> Vec<Vec<Fr>> {
+        self.instance()
+    }
+}
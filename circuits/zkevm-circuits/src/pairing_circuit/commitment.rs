@@ -0,0 +1,313 @@
+//! Public input commitment of the pairing circuit.
+//!
+//! In [`PiCommitmentMode::Poseidon`] the ciphertext (C_i, D_i, C'), the transformation key
+//! (w_i, L, R_i, R'), the access policy (M, ρ) and the result T are each committed by streaming
+//! their CRT limbs through the poseidon table, the same way the bytecode circuit commits to code.
+//! The contract stores the data commitment, the hash of the ciphertext and key commitments, and
+//! the policy commitment when the task is created and the result commitment when the result is
+//! submitted. The single instance hashes the three together, see [`poseidon_pi_commitment`],
+//! which the contract computes with its poseidon hasher when the proof is checked. The ciphertext
+//! commitment is shared with the
+//! [`CiphertextCircuit`](crate::ciphertext_circuit::CiphertextCircuit), which is how the contract
+//! links a well-formedness proof to the task.
+use std::{fmt::Debug, iter};
+
+use eth_types::U256;
+use halo2_base::{gates::GateInstructions, AssignedValue, Context};
+use halo2_proofs::{
+    circuit::{Layouter, Value},
+    halo2curves::{
+        bn256::{Fr, G1Affine},
+        group::ff::PrimeField,
+    },
+    plonk::{Advice, Column, ConstraintSystem, Error, Fixed, Selector},
+    poly::Rotation,
+};
+use hash_circuit::hash::{PoseidonHashChip, PoseidonHashTable};
+use itertools::Itertools;
+use poseidon_base::hash::{MessageHashable, HASHABLE_DOMAIN_SPEC};
+
+use crate::{
+    bytecode_circuit::bytecode_unroller::HASHBLOCK_BYTES_IN_FIELD,
+    poseidon_circuit::{PoseidonCircuitConfig, PoseidonCircuitConfigArgs, HASH_BLOCK_STEP_SIZE},
+    table::{LookupTable, PoseidonTable},
+    util::{Expr, Field, SubCircuitConfig},
+};
+
+use super::{LIMB_BITS, NUM_LIMBS};
+
+/// How the public inputs of a [`MyEccCircuit`](super::MyEccCircuit) are committed to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PiCommitmentMode {
+    /// keccak over the LE bytes of all inputs, exposed as 32 byte-sized instances. The digest is
+    /// only computed off-circuit, so it binds neither the policy nor the result, and proving in
+    /// this mode is an error.
+    Keccak,
+    /// Poseidon over the CRT limbs of all inputs, exposed as a single instance.
+    Poseidon,
+}
+
+/// Selects the [`PiCommitmentMode`] of a circuit type, since the mode changes its configuration.
+pub trait PiCommitment: Clone + Debug + Default + Send + Sync + 'static {
+    /// The commitment mode.
+    const MODE: PiCommitmentMode;
+}
+
+/// [`PiCommitmentMode::Keccak`]
+#[derive(Clone, Debug, Default)]
+pub struct KeccakCommitment;
+
+impl PiCommitment for KeccakCommitment {
+    const MODE: PiCommitmentMode = PiCommitmentMode::Keccak;
+}
+
+/// [`PiCommitmentMode::Poseidon`]
+#[derive(Clone, Debug, Default)]
+pub struct PoseidonCommitment;
+
+impl PiCommitment for PoseidonCommitment {
+    const MODE: PiCommitmentMode = PiCommitmentMode::Poseidon;
+}
+
+/// Split a 256-bit word into the CRT limbs the circuit assigns for it.
+pub(crate) fn word_limbs(word: U256) -> [Fr; NUM_LIMBS] {
+    let mask = (U256::one() << LIMB_BITS) - 1;
+    std::array::from_fn(|i| Fr::from_u128(((word >> (LIMB_BITS * i)) & mask).low_u128()))
+}
+
+/// LE words of the coordinates of a G1 point.
+pub(crate) fn g1_words(point: &G1Affine) -> [U256; 2] {
+    [
+        U256::from_little_endian(&point.x.to_bytes()),
+        U256::from_little_endian(&point.y.to_bytes()),
+    ]
+}
+
+/// Poseidon commitment to a message of field elements, as looked up in the poseidon table.
+pub fn poseidon_commitment(message: &[Fr]) -> Fr {
+    Fr::hash_msg(
+        message,
+        Some((message.len() * HASHBLOCK_BYTES_IN_FIELD) as u128 * HASHABLE_DOMAIN_SPEC),
+    )
+}
+
+/// Public input of a proof in [`PiCommitmentMode::Poseidon`], from the commitment to the task
+/// data, the commitment to the access policy (zero without one) and the commitment to the result:
+/// Poseidon(Poseidon(data, policy), result), with the two-element hashes of
+/// [`poseidon_commitment`], so that no part can be moved from one commitment to another.
+pub fn poseidon_pi_commitment(
+    data_commitment: Fr,
+    policy_commitment: Fr,
+    result_commitment: Fr,
+) -> Fr {
+    let data_policy = poseidon_commitment(&[data_commitment, policy_commitment]);
+    poseidon_commitment(&[data_policy, result_commitment])
+}
+
+/// A message assigned in the halo2-base context, along with its commitment.
+pub(crate) struct AssignedMessage<F: Field> {
+    /// Poseidon commitment to the message.
+    pub commitment: AssignedValue<F>,
+    /// Field elements of the message, padded with a zero to the poseidon input width.
+    pub inputs: Vec<AssignedValue<F>>,
+    /// Field elements of the message, as known to the prover.
+    pub message: Vec<F>,
+}
+
+/// Assign the commitments to the given messages, each made of its assigned CRT limbs and the
+/// field elements known to the prover, returning the messages to look up in the poseidon table.
+pub(crate) fn assign_messages<F: Field>(
+    ctx: &mut Context<F>,
+    gate: &impl GateInstructions<F>,
+    messages: Vec<(Vec<AssignedValue<F>>, Vec<Fr>)>,
+) -> Vec<AssignedMessage<F>> {
+    messages
+        .into_iter()
+        .map(|(mut inputs, message)| {
+            debug_assert_eq!(inputs.len(), message.len());
+            if inputs.len() % PoseidonTable::INPUT_WIDTH != 0 {
+                inputs.push(gate.load_zero(ctx));
+            }
+            let commitment = gate
+                .assign_witnesses(
+                    ctx,
+                    vec![Value::known(F::from(poseidon_commitment(&message)))],
+                )
+                .remove(0);
+            AssignedMessage {
+                commitment,
+                inputs,
+                message: message.into_iter().map(F::from).collect(),
+            }
+        })
+        .collect_vec()
+}
+
+/// Assign the public input of [`poseidon_pi_commitment`] from the assigned data, policy and result
+/// commitments along with their values, returning the two messages hashing them to look up in the
+/// poseidon table along with the public input.
+pub(crate) fn assign_pi_messages<F: Field>(
+    ctx: &mut Context<F>,
+    gate: &impl GateInstructions<F>,
+    data: (AssignedValue<F>, Fr),
+    policy: (AssignedValue<F>, Fr),
+    result: (AssignedValue<F>, Fr),
+) -> (Vec<AssignedMessage<F>>, AssignedValue<F>) {
+    let data_policy = poseidon_commitment(&[data.1, policy.1]);
+    let mut messages = assign_messages(
+        ctx,
+        gate,
+        vec![(vec![data.0, policy.0], vec![data.1, policy.1])],
+    );
+    let data_policy_cell = messages[0].commitment.clone();
+    messages.extend(assign_messages(
+        ctx,
+        gate,
+        vec![(
+            vec![data_policy_cell, result.0],
+            vec![data_policy, result.1],
+        )],
+    ));
+    let pi = messages[1].commitment.clone();
+
+    (messages, pi)
+}
+
+/// Config looking up the assigned messages in a poseidon table, along with the poseidon circuit
+/// proving the table.
+#[derive(Clone, Debug)]
+pub(crate) struct PoseidonCommitmentConfig<F: Field> {
+    q_enable: Selector,
+    commitment: Column<Advice>,
+    input0: Column<Advice>,
+    input1: Column<Advice>,
+    control: Column<Fixed>,
+    heading_mark: Column<Fixed>,
+    poseidon: PoseidonCircuitConfig<F>,
+}
+
+impl<F: Field> PoseidonCommitmentConfig<F> {
+    pub(crate) fn configure(meta: &mut ConstraintSystem<F>, commitment: Column<Advice>) -> Self {
+        let q_enable = meta.complex_selector();
+        let input0 = meta.advice_column();
+        let input1 = meta.advice_column();
+        let control = meta.fixed_column();
+        let heading_mark = meta.fixed_column();
+        let poseidon_table = PoseidonTable::construct(meta);
+
+        meta.enable_equality(input0);
+        meta.enable_equality(input1);
+
+        meta.lookup_any("pi commitment in poseidon table", |meta| {
+            // Layout, one row per permutation of a message:
+            // | q_enable | commitment | input0 | input1 |        control         | heading_mark |
+            // | -------- | ---------- | ------ | ------ | ---------------------- | ------------ |
+            // |     1    |     h      |   m0   |   m1   | 31 * len * DOMAIN_SPEC |      1       |
+            // |     1    |     h      |   m2   |   m3   | 31 * (len - 2) * ...   |      0       |
+            let enable = meta.query_selector(q_enable);
+            let input = [
+                1.expr(),
+                meta.query_advice(commitment, Rotation::cur()),
+                meta.query_advice(input0, Rotation::cur()),
+                meta.query_advice(input1, Rotation::cur()),
+                meta.query_fixed(control, Rotation::cur()),
+                // domain 0, as in the codehash
+                0.expr(),
+                meta.query_fixed(heading_mark, Rotation::cur()),
+            ];
+
+            input
+                .into_iter()
+                .zip_eq(poseidon_table.table_exprs(meta))
+                .map(|(input, table)| (enable.clone() * input, table))
+                .collect()
+        });
+
+        let poseidon =
+            PoseidonCircuitConfig::new(meta, PoseidonCircuitConfigArgs { poseidon_table });
+
+        Self {
+            q_enable,
+            commitment,
+            input0,
+            input1,
+            control,
+            heading_mark,
+            poseidon,
+        }
+    }
+
+    /// Copy the messages into the lookup rows and prove their hashes in the poseidon table.
+    pub(crate) fn assign(
+        &self,
+        layouter: &mut impl Layouter<F>,
+        messages: &[AssignedMessage<F>],
+    ) -> Result<(), Error> {
+        layouter.assign_region(
+            || "pi commitment",
+            |mut region| {
+                let mut offset = 0;
+                for message in messages {
+                    let mut control_len = message.message.len() * HASHBLOCK_BYTES_IN_FIELD;
+                    for (row, inputs) in message
+                        .inputs
+                        .chunks(PoseidonTable::INPUT_WIDTH)
+                        .enumerate()
+                    {
+                        self.q_enable.enable(&mut region, offset)?;
+                        message
+                            .commitment
+                            .copy_advice(&mut region, self.commitment, offset);
+                        inputs[0].copy_advice(&mut region, self.input0, offset);
+                        inputs[1].copy_advice(&mut region, self.input1, offset);
+                        region.assign_fixed(
+                            || "pi commitment control",
+                            self.control,
+                            offset,
+                            || {
+                                Value::known(F::from_u128(
+                                    HASHABLE_DOMAIN_SPEC * control_len as u128,
+                                ))
+                            },
+                        )?;
+                        region.assign_fixed(
+                            || "pi commitment heading mark",
+                            self.heading_mark,
+                            offset,
+                            || Value::known(if row == 0 { F::one() } else { F::zero() }),
+                        )?;
+                        control_len = control_len.saturating_sub(HASH_BLOCK_STEP_SIZE);
+                        offset += 1;
+                    }
+                }
+                Ok(())
+            },
+        )?;
+
+        let mut hash_table = PoseidonHashTable::default();
+        let mut num_hashes = 0;
+        for message in messages {
+            let inputs = message
+                .message
+                .iter()
+                .copied()
+                .chain(iter::repeat(F::zero()))
+                .take(message.inputs.len())
+                .tuples()
+                .map(|(m0, m1)| [m0, m1])
+                .collect_vec();
+            num_hashes += inputs.len();
+            hash_table.stream_inputs(
+                &inputs,
+                (message.message.len() * HASHBLOCK_BYTES_IN_FIELD) as u64,
+                HASH_BLOCK_STEP_SIZE,
+            );
+        }
+        PoseidonHashChip::<_, HASH_BLOCK_STEP_SIZE>::construct(
+            self.poseidon.0.clone(),
+            &hash_table,
+            num_hashes + 1,
+        )
+        .load(layouter)
+    }
+}
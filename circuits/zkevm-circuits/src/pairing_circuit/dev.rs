@@ -10,9 +10,45 @@ use crate::{
     util::{Challenges, SubCircuit, SubCircuitConfig},
 };
 
-use super::{MyEccCircuit, MyEccCircuitConfig, MyEccCircuitConfigArgs};
+use super::{
+    MyBatchEccCircuit, MyEccCircuit, MyEccCircuitConfig, MyEccCircuitConfigArgs, PiCommitment,
+    PiCommitmentMode,
+};
+
+impl<F: Field, const XI_0: i64, P: PiCommitment> Circuit<F> for MyEccCircuit<F, XI_0, P> {
+    type Config = (MyEccCircuitConfig<F>, Challenges<Challenge>);
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self::default()
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        let challenges = Challenges::construct(meta);
+        let challenge_exprs = challenges.exprs(meta);
+        (
+            MyEccCircuitConfig::new(
+                meta,
+                MyEccCircuitConfigArgs {
+                    challenges: challenge_exprs,
+                    pi_commitment: P::MODE,
+                },
+            ),
+            challenges,
+        )
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let challenge_values = config.1.values(&layouter);
+        self.synthesize_sub(&config.0, &challenge_values, &mut layouter)
+    }
+}
 
-impl<F: Field, const XI_0: i64> Circuit<F> for MyEccCircuit<F, XI_0> {
+impl<F: Field> Circuit<F> for MyBatchEccCircuit<F> {
     type Config = (MyEccCircuitConfig<F>, Challenges<Challenge>);
     type FloorPlanner = SimpleFloorPlanner;
 
@@ -28,6 +64,7 @@ impl<F: Field, const XI_0: i64> Circuit<F> for MyEccCircuit<F, XI_0> {
                 meta,
                 MyEccCircuitConfigArgs {
                     challenges: challenge_exprs,
+                    pi_commitment: PiCommitmentMode::Poseidon,
                 },
             ),
             challenges,
@@ -1,4 +1,4 @@
-use std::{marker::PhantomData, ops::Mul};
+use std::{iter, marker::PhantomData, ops::Mul};
 
 use bus_mapping::circuit_input_builder::EcPairingPair;
 use eth_types::U256;
@@ -32,14 +32,281 @@ use snark_verifier_sdk::{gen_snark_shplonk, Snark};
 
 use crate::util::SubCircuit;
 
-use super::MyEccCircuit;
+use super::{
+    poseidon_commitment, poseidon_pi_commitment, KeccakCommitment, LsssPolicy, MyBatchEccCircuit,
+    MyEccCircuit, PiCommitment, PoseidonCommitment,
+};
+
+/// A random transformation key (R_i, L, R') for `n` attributes, along with the H(x_i) of the
+/// attributes.
+fn random_key(n: usize) -> (Vec<G2Affine>, G2Affine, G2Affine, Vec<G1Affine>) {
+    (
+        (0..n)
+            .map(|_| G2Affine::from(G2::random(&mut OsRng)))
+            .collect(),
+        G2Affine::from(G2::random(&mut OsRng)),
+        G2Affine::from(G2::random(&mut OsRng)),
+        (0..n).map(|_| G1Affine::random(&mut OsRng)).collect(),
+    )
+}
+
+/// A circuit over `n` of the `n + 1` rows of a random policy, with the result computed natively.
+fn random_circuit<P: PiCommitment>(n: usize) -> MyEccCircuit<Fr, 9, P> {
+    random_circuit_with_key(&random_key(n))
+}
+
+/// A random circuit as [`random_circuit`], under the given transformation key.
+fn random_circuit_with_key<P: PiCommitment>(
+    (r_points, l_point, r_p_point, key_attributes): &(
+        Vec<G2Affine>,
+        G2Affine,
+        G2Affine,
+        Vec<G1Affine>,
+    ),
+) -> MyEccCircuit<Fr, 9, P> {
+    let n = r_points.len();
+    let g1_words = |p: &G1Affine| {
+        (
+            U256::from_little_endian(&p.x.to_bytes()),
+            U256::from_little_endian(&p.y.to_bytes()),
+        )
+    };
+    let g2_words = |p: &G2Affine| {
+        (
+            U256::from_little_endian(&p.x.c1.to_bytes()),
+            U256::from_little_endian(&p.x.c0.to_bytes()),
+            U256::from_little_endian(&p.y.c1.to_bytes()),
+            U256::from_little_endian(&p.y.c0.to_bytes()),
+        )
+    };
+
+    let ws = (0..n).map(|_| Fr::random(&mut OsRng)).collect::<Vec<_>>();
+    // the user holds the attributes of rows 1..=n, and the last of these rows is chosen so that
+    // Σ w_i·M_i = (1, 0, ..., 0)
+    let rows = (1..=n).collect::<Vec<_>>();
+    let mut matrix = (0..=n)
+        .map(|_| (0..n).map(|_| Fr::random(&mut OsRng)).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    let mut last = (0..n)
+        .map(|j| if j == 0 { Fr::one() } else { Fr::zero() })
+        .collect::<Vec<_>>();
+    for (w, row) in ws.iter().zip(&rows).take(n - 1) {
+        for (last, m) in last.iter_mut().zip(&matrix[*row]) {
+            *last -= *w * m;
+        }
+    }
+    let w_inv = ws[n - 1].invert().unwrap();
+    matrix[n] = last.into_iter().map(|m| m * w_inv).collect();
+    let policy = LsssPolicy {
+        matrix,
+        attributes: iter::once(G1Affine::random(&mut OsRng))
+            .chain(key_attributes.iter().cloned())
+            .collect(),
+    };
+
+    let c_points = (0..=n)
+        .map(|_| G1Affine::random(&mut OsRng))
+        .collect::<Vec<_>>();
+    let d_points = (0..=n)
+        .map(|_| G1Affine::random(&mut OsRng))
+        .collect::<Vec<_>>();
+    let c_p_point = G1Affine::random(&mut OsRng);
+
+    let mut result = pairing(&c_p_point, r_p_point);
+    for i in 0..n {
+        result += pairing(&G1Affine::from(c_points[rows[i]].mul(ws[i])), l_point)
+            + pairing(&G1Affine::from(d_points[rows[i]].mul(ws[i])), &r_points[i]);
+    }
+
+    MyEccCircuit {
+        p1s: c_points
+            .iter()
+            .zip(d_points.iter())
+            .flat_map(|(c, d)| [g1_words(c), g1_words(d)])
+            .collect(),
+        p2s: r_points.iter().map(g2_words).collect(),
+        ws,
+        policy,
+        rows,
+        key_attributes: key_attributes.clone(),
+        p4: g2_words(l_point),
+        ct: g1_words(&c_p_point),
+        tk: g2_words(r_p_point),
+        ti: result.0,
+        _marker: PhantomData,
+    }
+}
+
+#[test]
+fn test_pairing_circuit_poseidon_commitment() {
+    let circuit = random_circuit::<PoseidonCommitment>(3);
+
+    let (data, policy, result) = (
+        circuit.data_commitment(),
+        circuit.policy.commitment(),
+        circuit.result_commitment(),
+    );
+    assert_eq!(
+        data,
+        poseidon_commitment(&[circuit.ciphertext_commitment(), circuit.key_commitment()])
+    );
+    let instance = circuit.instance();
+    assert_eq!(
+        instance,
+        vec![vec![poseidon_commitment(&[
+            poseidon_commitment(&[data, policy]),
+            result
+        ])]]
+    );
+    // moving a part of one commitment to another changes the public input
+    assert_ne!(
+        poseidon_pi_commitment(data + Fr::one(), policy - Fr::one(), result),
+        instance[0][0]
+    );
+
+    let prover = match MockProver::<Fr>::run(22, &circuit, instance) {
+        Ok(prover) => prover,
+        Err(e) => panic!("{e:#?}"),
+    };
+    assert_eq!(prover.verify(), Ok(()));
+
+    // a result that differs from the committed one is rejected
+    let mut wrong_result = circuit.clone();
+    wrong_result.ti = circuit.ti.square();
+    let prover = MockProver::<Fr>::run(22, &wrong_result, circuit.instance()).unwrap();
+    assert!(prover.verify().is_err());
+}
+
+#[test]
+fn test_pairing_circuit_policy() {
+    let circuit = random_circuit::<PoseidonCommitment>(2);
+
+    // w_i that do not reconstruct the secret under the committed policy are rejected
+    let mut wrong_policy = circuit.clone();
+    wrong_policy.policy.matrix[1][0] += Fr::one();
+    let prover = MockProver::<Fr>::run(22, &wrong_policy, wrong_policy.instance()).unwrap();
+    assert!(prover.verify().is_err());
+
+    // as are rows of attributes the user does not hold
+    let mut wrong_rows = circuit.clone();
+    wrong_rows.rows[0] = 0;
+    let prover = MockProver::<Fr>::run(22, &wrong_rows, wrong_rows.instance()).unwrap();
+    assert!(prover.verify().is_err());
+
+    // and R_i paired with the row of another attribute, even when the w_i still reconstruct the
+    // secret
+    let mut swapped_rows = circuit.clone();
+    swapped_rows.rows.swap(0, 1);
+    swapped_rows.ws.swap(0, 1);
+    let prover = MockProver::<Fr>::run(22, &swapped_rows, swapped_rows.instance()).unwrap();
+    assert!(prover.verify().is_err());
+
+    // a key attribute that does not label the row of its R_i is rejected
+    let mut wrong_attribute = circuit.clone();
+    wrong_attribute.key_attributes[0] = G1Affine::random(&mut OsRng);
+    let prover = MockProver::<Fr>::run(22, &wrong_attribute, wrong_attribute.instance()).unwrap();
+    assert!(prover.verify().is_err());
+
+    // a missing policy or w_i is an error rather than a panic
+    let mut no_policy = circuit.clone();
+    no_policy.policy = LsssPolicy::default();
+    assert!(MockProver::<Fr>::run(22, &no_policy, circuit.instance()).is_err());
+    let mut no_columns = circuit.clone();
+    no_columns.policy.matrix.iter_mut().for_each(Vec::clear);
+    assert!(MockProver::<Fr>::run(22, &no_columns, circuit.instance()).is_err());
+    let mut no_ws = circuit.clone();
+    no_ws.ws.clear();
+    no_ws.rows.clear();
+    assert!(MockProver::<Fr>::run(22, &no_ws, circuit.instance()).is_err());
+}
+
+#[test]
+fn test_pairing_circuit_keccak_commitment() {
+    // the keccak public input is not constrained in-circuit, so it cannot bind the policy
+    let circuit = random_circuit::<KeccakCommitment>(2);
+    assert!(MockProver::<Fr>::run(22, &circuit, circuit.instance()).is_err());
+}
+
+#[test]
+fn test_batch_pairing_circuit() {
+    let key = random_key(2);
+    let circuit = MyBatchEccCircuit::new(vec![
+        random_circuit_with_key(&key),
+        random_circuit_with_key(&key),
+    ])
+    .unwrap();
+
+    let instance = circuit.instance();
+    assert_eq!(
+        instance,
+        vec![circuit
+            .tasks
+            .iter()
+            .map(|task| task.instance()[0][0])
+            .collect::<Vec<_>>()]
+    );
+
+    let prover = match MockProver::<Fr>::run(22, &circuit, instance) {
+        Ok(prover) => prover,
+        Err(e) => panic!("{e:#?}"),
+    };
+    assert_eq!(prover.verify(), Ok(()));
+
+    // results swapped between the tasks are rejected
+    let mut swapped = circuit.clone();
+    let ti = swapped.tasks[0].ti;
+    swapped.tasks[0].ti = swapped.tasks[1].ti;
+    swapped.tasks[1].ti = ti;
+    let prover = MockProver::<Fr>::run(22, &swapped, circuit.instance()).unwrap();
+    assert!(prover.verify().is_err());
+
+    // as is a wrong result, even when committed to
+    let mut wrong_result = circuit.clone();
+    wrong_result.tasks[1].ti = wrong_result.tasks[1].ti.square();
+    let prover = MockProver::<Fr>::run(22, &wrong_result, wrong_result.instance()).unwrap();
+    assert!(prover.verify().is_err());
+
+    // and a key whose attributes do not label the rows of its R_i
+    let mut wrong_attributes = circuit.clone();
+    for task in wrong_attributes.tasks.iter_mut() {
+        task.key_attributes.swap(0, 1);
+    }
+    let prover = MockProver::<Fr>::run(22, &wrong_attributes, wrong_attributes.instance()).unwrap();
+    assert!(prover.verify().is_err());
+
+    // and a C_i that is not on the curve
+    let mut off_curve = circuit;
+    off_curve.tasks[1].p1s[0].1 += U256::one();
+    let prover = MockProver::<Fr>::run(22, &off_curve, off_curve.instance()).unwrap();
+    assert!(prover.verify().is_err());
+}
+
+#[test]
+fn test_batch_pairing_circuit_malformed() {
+    let key = random_key(2);
+
+    // a batch has at least one task
+    assert!(MyBatchEccCircuit::<Fr>::new(vec![]).is_err());
+
+    // which all share the transformation key
+    assert!(MyBatchEccCircuit::new(vec![
+        random_circuit_with_key(&key),
+        random_circuit_with_key(&random_key(2)),
+    ])
+    .is_err());
+
+    // with a w_i for each R_i
+    let mut missing_w = random_circuit_with_key(&key);
+    missing_w.ws.pop();
+    assert!(MyBatchEccCircuit::new(vec![random_circuit_with_key(&key), missing_w]).is_err());
+}
 
 #[test]
 fn test_pairing_circuit() {
-    // let alpha = Fr::random(OsRng);
-    // let beta = Fr::random(OsRng);
+    // let alpha = Fr::random(&mut OsRng);
+    // let beta = Fr::random(&mut OsRng);
 
-    // let random_fq12 = Fq12::random(OsRng);
+    // let random_fq12 = Fq12::random(&mut OsRng);
 
     // let point_p = G1Affine::from(G1Affine::generator() * alpha);
 
@@ -52,12 +319,15 @@ fn test_pairing_circuit() {
     let n = 2;
 
     let mut ws = vec![];
+    let mut attributes = vec![];
     let mut c_points = vec![];
     let mut d_points = vec![];
     let mut r_points = vec![];
 
+    // a policy requiring all n attributes, under which every w_i is 1
     for i in 0..n {
-        ws.push(Fr::random(&mut OsRng));
+        ws.push(Fr::one());
+        attributes.push(G1Affine::random(&mut OsRng));
         c_points.push(G1Affine::random(&mut OsRng));
         d_points.push(G1Affine::random(&mut OsRng));
         r_points.push(G2Affine::from(G2::random(&mut OsRng)));
@@ -131,6 +401,9 @@ fn test_pairing_circuit() {
         p1s,
         p2s,
         ws,
+        policy: LsssPolicy::and(attributes.clone()),
+        rows: (0..n).collect(),
+        key_attributes: attributes,
         p4,
         ct,
         tk,
@@ -225,12 +498,15 @@ fn test_e2e() {
     let n = 10;
 
     let mut ws = vec![];
+    let mut attributes = vec![];
     let mut c_points = vec![];
     let mut d_points = vec![];
     let mut r_points = vec![];
 
+    // a policy requiring all n attributes, under which every w_i is 1
     for i in 0..n {
-        ws.push(Fr::random(&mut OsRng));
+        ws.push(Fr::one());
+        attributes.push(G1Affine::random(&mut OsRng));
         c_points.push(G1Affine::random(&mut OsRng));
         d_points.push(G1Affine::random(&mut OsRng));
         r_points.push(G2Affine::from(G2::random(&mut OsRng)));
@@ -304,6 +580,9 @@ fn test_e2e() {
         p1s,
         p2s,
         ws,
+        policy: LsssPolicy::and(attributes.clone()),
+        rows: (0..n).collect(),
+        key_attributes: attributes,
         p4,
         ct,
         tk,
@@ -50,12 +50,14 @@ use halo2_proofs::{
 
 use crate::{
     evm_circuit::param::{N_BYTES_ACCOUNT_ADDRESS, N_BYTES_U64, N_BYTES_WORD},
-    pi_circuit::param::{COINBASE_OFFSET, DIFFICULTY_OFFSET, NUM_ALL_TXS_OFFSET},
+    pi_circuit::param::{
+        BLOB_BASE_FEE_OFFSET, COINBASE_OFFSET, DIFFICULTY_OFFSET, NUM_ALL_TXS_OFFSET,
+    },
     table::{
         BlockContextFieldTag,
         BlockContextFieldTag::{
-            BaseFee, ChainId, Coinbase, CumNumTxs, Difficulty, GasLimit, NumAllTxs, NumTxs, Number,
-            Timestamp,
+            BaseFee, BlobBaseFee, ChainId, Coinbase, CumNumTxs, Difficulty, GasLimit, NumAllTxs,
+            NumTxs, Number, Timestamp,
         },
     },
     util::rlc_be_bytes,
@@ -168,6 +170,7 @@ impl PublicData {
                     .chain(block.base_fee.to_be_bytes())
                     .chain(block.gas_limit.to_be_bytes())
                     .chain(num_all_txs.to_be_bytes())
+                    .chain(block.blob_base_fee.to_be_bytes())
             }))
             // Tx Hashes
             .chain(
@@ -190,7 +193,9 @@ impl PublicData {
     }
 
     /// Obtain the l2 tx (not padding; right now padding txs are l2 txs by default) bytes in the
-    /// chunk
+    /// chunk.
+    /// The signed RLP of an EIP-4844 tx carries its blob versioned hashes, which the tx circuit
+    /// binds to the tx table, so these bytes also commit the blob hashes to the public input.
     fn chunk_txbytes(&self) -> Vec<u8> {
         let mut result: Vec<u8> = vec![];
         let chunk_txs_iter = self.transactions.iter().filter(|&tx| tx.is_chunk_l2_tx());
@@ -335,6 +340,7 @@ impl BlockContext {
             number: Default::default(),
             timestamp: Default::default(),
             base_fee: Default::default(),
+            blob_base_fee: Default::default(),
             history_hashes: vec![],
             parent_hash: Default::default(),
             state_root: Default::default(),
@@ -794,9 +800,10 @@ impl<F: Field> PiCircuitConfig<F> {
     /// |          | block\[0\].base_fee    |                          |
     /// |          | block\[0\].gas_limit   |                          |
     /// |          | block\[0\].num_all_txs |                          |
+    /// |          | block\[0\].blob_base_fee |                        |
     /// |          | block\[1\].number      | <- q_block_context == 1  |
     /// | *PART 1* | ...                    |                          |
-    /// |          | block\[n\].num_all_txs |                          |
+    /// |          | block\[n\].blob_base_fee |                        |
     /// | ASSIGN   | PADDING                |                          |
     /// | DATA     | ...                    |                          |
     /// | BYTES    | PADDING                |                          |
@@ -978,6 +985,11 @@ impl<F: Field> PiCircuitConfig<F> {
                 (block.gas_limit.to_be_bytes().to_vec(), GAS_LIMIT_OFFSET),
                 // num txs in block
                 (num_all_txs.to_be_bytes().to_vec(), NUM_ALL_TXS_OFFSET),
+                // blob base fee
+                (
+                    block.blob_base_fee.to_be_bytes().to_vec(),
+                    BLOB_BASE_FEE_OFFSET,
+                ),
             ] {
                 let (tmp_offset, tmp_rpi_rlc_acc, tmp_rpi_length, cells) = self.assign_field(
                     region,
@@ -1667,8 +1679,17 @@ impl<F: Field> PiCircuitConfig<F> {
                 .cloned()
                 .unwrap_or(0);
             let tag = [
-                Coinbase, Timestamp, Number, Difficulty, GasLimit, BaseFee, ChainId, NumTxs,
-                CumNumTxs, NumAllTxs,
+                Coinbase,
+                Timestamp,
+                Number,
+                Difficulty,
+                GasLimit,
+                BaseFee,
+                ChainId,
+                NumTxs,
+                CumNumTxs,
+                NumAllTxs,
+                BlobBaseFee,
             ];
 
             // index_cells of same block are equal to block_number.
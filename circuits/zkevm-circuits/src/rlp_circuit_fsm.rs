@@ -17,7 +17,10 @@ use crate::{
         Block, DataTable, Format, RlpFsmWitnessGen, RlpFsmWitnessRow, RlpTag, RomTableRow, State,
         State::{DecodeTagStart, End},
         Tag,
-        Tag::{AccessListAddress, AccessListStorageKey, BeginObject, EndObject, EndVector, TxType},
+        Tag::{
+            AccessListAddress, AccessListStorageKey, AuthChainId, BeginObject, BlobVersionedHash,
+            EndObject, EndVector, TxType,
+        },
         Transaction,
     },
 };
@@ -309,13 +312,15 @@ pub struct RlpCircuitConfig<F> {
     /// Indicates the start of another new access list item
     is_new_access_list_address: Column<Advice>,
     /// Boolean to reduce the circuit's degree
-    /// Indicates the start of another new storage key for an access list address
+    /// Indicates the start of another new storage key for an access list address, or of another
+    /// new blob versioned hash (EIP-4844), both of which are indexed by storage_key_idx
     is_new_access_list_storage_key: Column<Advice>,
     /// Boolean to reduce the circuit's degree
     /// Indicates the end of access list
     is_access_list_end: Column<Advice>,
     /// Boolean to reduce the circuit's degree
-    /// Indicates the end of storage key list in a particular access list item
+    /// Indicates the end of storage key list in a particular access list item, or the end of
+    /// blob versioned hashes
     is_storage_key_list_end: Column<Advice>,
     /// Decoding table id change
     /// id = (tx_id, format, depth, al_idx, sk_idx)
@@ -500,6 +505,8 @@ impl<F: Field> RlpCircuitConfig<F> {
         is_tag!(is_tag_end_vector, EndVector);
         is_tag!(is_access_list_address, AccessListAddress);
         is_tag!(is_access_list_storage_key, AccessListStorageKey);
+        is_tag!(is_blob_versioned_hash, BlobVersionedHash);
+        is_tag!(is_auth_chain_id, AuthChainId);
 
         //////////////////////////////////////////////////////////
         //////////// data table checks. //////////////////////////
@@ -1203,6 +1210,24 @@ impl<F: Field> RlpCircuitConfig<F> {
                         constrain_unchanged_fields!(meta, cb; rlp_table.tx_id, rlp_table.format);
                     },
                 );
+                // the end of a list at depth 2 emits the number of its items, i.e. the last
+                // access_list_idx. The tag that follows the list tells the lists of a tx apart,
+                // e.g. the authorization list of an EIP-7702 tx is the only one followed by
+                // EndObject (signing) or SigV (hashing) in its formats.
+                cb.condition(
+                    meta.query_advice(is_access_list_end, Rotation::cur()),
+                    |cb| {
+                        emit_rlp_tag!(meta, cb, EndVector, false);
+                        constrain_eq!(
+                            meta,
+                            cb,
+                            rlp_table.tag_value,
+                            meta.query_advice(rlp_table.access_list_idx, Rotation::prev())
+                        );
+                        constrain_eq!(meta, cb, rlp_table.tag_bytes_rlc, tag_next_expr(meta));
+                        constrain_eq!(meta, cb, rlp_table.tag_length, 0);
+                    },
+                );
 
                 cb.gate(and::expr([
                     meta.query_fixed(q_enabled, Rotation::cur()),
@@ -1540,12 +1565,22 @@ impl<F: Field> RlpCircuitConfig<F> {
             cb.require_equal(
                 "is_new_access_list_address",
                 meta.query_advice(is_new_access_list_address, Rotation::cur()),
-                and::expr([is_access_list_address(meta), is_decode_tag_start(meta)]),
+                and::expr([
+                    // authorization tuples are indexed by access_list_idx as well
+                    sum::expr([is_access_list_address(meta), is_auth_chain_id(meta)]),
+                    is_decode_tag_start(meta),
+                ]),
             );
             cb.require_equal(
                 "is_new_access_list_storage_key",
                 meta.query_advice(is_new_access_list_storage_key, Rotation::cur()),
-                and::expr([is_access_list_storage_key(meta), is_decode_tag_start(meta)]),
+                and::expr([
+                    sum::expr([
+                        is_access_list_storage_key(meta),
+                        is_blob_versioned_hash(meta),
+                    ]),
+                    is_decode_tag_start(meta),
+                ]),
             );
             cb.require_equal(
                 "is_access_list_end",
@@ -1559,7 +1594,11 @@ impl<F: Field> RlpCircuitConfig<F> {
                 "is_storage_key_list_end",
                 meta.query_advice(is_storage_key_list_end, Rotation::cur()),
                 and::expr([
-                    depth_eq_four.is_equal_expression.expr(),
+                    // blob versioned hashes are a list at depth 2
+                    sum::expr([
+                        depth_eq_four.is_equal_expression.expr(),
+                        depth_eq_two.is_equal_expression.expr(),
+                    ]),
                     is_tag_end_vector(meta),
                 ]),
             );
@@ -2746,7 +2785,8 @@ impl<F: Field> RlpCircuitConfig<F> {
         )?;
 
         let is_new_access_list_address = witness.state_machine.state == DecodeTagStart
-            && witness.state_machine.tag == AccessListAddress;
+            && (witness.state_machine.tag == AccessListAddress
+                || witness.state_machine.tag == AuthChainId);
         region.assign_advice(
             || "is_new_access_list_address",
             self.is_new_access_list_address,
@@ -2754,7 +2794,8 @@ impl<F: Field> RlpCircuitConfig<F> {
             || Value::known(F::from(is_new_access_list_address as u64)),
         )?;
         let is_new_access_list_storage_key = witness.state_machine.state == DecodeTagStart
-            && witness.state_machine.tag == AccessListStorageKey;
+            && (witness.state_machine.tag == AccessListStorageKey
+                || witness.state_machine.tag == BlobVersionedHash);
         region.assign_advice(
             || "is_new_access_list_storage_key",
             self.is_new_access_list_storage_key,
@@ -2769,8 +2810,8 @@ impl<F: Field> RlpCircuitConfig<F> {
             row,
             || Value::known(F::from(is_access_list_end as u64)),
         )?;
-        let is_storage_key_list_end =
-            witness.state_machine.tag == EndVector && witness.state_machine.depth == 4;
+        let is_storage_key_list_end = witness.state_machine.tag == EndVector
+            && (witness.state_machine.depth == 4 || witness.state_machine.depth == 2);
         region.assign_advice(
             || "is_storage_key_list_end",
             self.is_storage_key_list_end,
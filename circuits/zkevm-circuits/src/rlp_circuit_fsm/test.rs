@@ -1,6 +1,9 @@
 #![allow(unused_imports)]
 use crate::{rlp_circuit_fsm::RlpCircuit, witness::Transaction};
-use eth_types::{geth_types::TxType, word, Address};
+use eth_types::{
+    geth_types::{get_rlp_signed, get_rlp_unsigned, TxType},
+    word, Address, Transaction as GethTransaction,
+};
 use ethers_core::{
     types::{
         transaction::eip2718::TypedTransaction, Eip1559TransactionRequest,
@@ -10,7 +13,9 @@ use ethers_core::{
 };
 use ethers_signers::Wallet;
 use halo2_proofs::{dev::MockProver, halo2curves::bn256::Fr};
-use mock::{eth, MOCK_CHAIN_ID};
+use mock::{
+    eth, gwei, sign_authorization, MockTransaction, MOCK_ACCOUNTS, MOCK_CHAIN_ID, MOCK_WALLETS,
+};
 use rand::rngs::OsRng;
 
 #[cfg(test)]
@@ -192,3 +197,44 @@ fn test_eip2930_tx() {
 
     mock_prover.assert_satisfied_par();
 }
+
+#[test]
+fn test_eip7702_tx() {
+    // the second authorization has a zero chain id and a multi-byte nonce
+    let authorization_list = vec![
+        sign_authorization(&MOCK_WALLETS[1], MOCK_CHAIN_ID, MOCK_ACCOUNTS[0], 0),
+        sign_authorization(&MOCK_WALLETS[2], 0, MOCK_ACCOUNTS[1], 0x1234),
+    ];
+    let mock_tx = MockTransaction::default()
+        .from(MOCK_WALLETS[0].clone())
+        .to(MOCK_ACCOUNTS[2])
+        .value(eth(1))
+        .max_fee_per_gas(gwei(2))
+        .max_priority_fee_per_gas(gwei(2))
+        .authorization_list(authorization_list)
+        .transaction_type(4)
+        .build_7702();
+    let eth_tx = GethTransaction::from(mock_tx);
+
+    let tx = Transaction::new_from_rlp_bytes(
+        1,
+        TxType::Eip7702,
+        get_rlp_signed(&eth_tx),
+        get_rlp_unsigned(&eth_tx),
+    );
+    let rlp_circuit = RlpCircuit::<Fr, Transaction> {
+        txs: vec![tx],
+        max_txs: 10,
+        size: 1000,
+        _marker: Default::default(),
+    };
+
+    let mock_prover = MockProver::run(14, &rlp_circuit, vec![]);
+    assert!(mock_prover.is_ok());
+    let mock_prover = mock_prover.unwrap();
+    if let Err(errors) = mock_prover.verify_par() {
+        log::debug!("errors.len() = {}", errors.len());
+    }
+
+    mock_prover.assert_satisfied_par();
+}
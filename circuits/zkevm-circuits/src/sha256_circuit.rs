@@ -7,6 +7,8 @@ use halo2_proofs::{
 };
 
 mod circuit;
+#[cfg(any(feature = "test", test, feature = "test-circuits"))]
+mod dev;
 #[cfg(test)]
 mod test;
 
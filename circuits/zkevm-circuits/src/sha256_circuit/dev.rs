@@ -0,0 +1,41 @@
+use super::*;
+use halo2_proofs::{
+    circuit::SimpleFloorPlanner,
+    plonk::{Challenge, Circuit},
+};
+
+impl Circuit<Fr> for SHA256Circuit<Fr> {
+    type Config = (CircuitConfig, Challenges<Challenge>);
+    type FloorPlanner = SimpleFloorPlanner;
+    #[cfg(feature = "circuit-params")]
+    type Params = ();
+
+    fn without_witnesses(&self) -> Self {
+        Self(Vec::new(), self.1, Default::default())
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Fr>) -> Self::Config {
+        let sha256_table = SHA256Table::construct(meta);
+        let challenges = Challenges::construct(meta);
+        let challenge_exprs = challenges.exprs(meta);
+        (
+            <CircuitConfig as SubCircuitConfig<Fr>>::new(
+                meta,
+                CircuitConfigArgs {
+                    sha256_table,
+                    challenges: challenge_exprs,
+                },
+            ),
+            challenges,
+        )
+    }
+
+    fn synthesize(
+        &self,
+        (config, challenges): Self::Config,
+        mut layouter: impl Layouter<Fr>,
+    ) -> Result<(), Error> {
+        let challenges = challenges.values(&layouter);
+        <Self as SubCircuit<Fr>>::synthesize_sub(self, &config, &challenges, &mut layouter)
+    }
+}
@@ -54,6 +54,8 @@ pub(crate) mod eip1559_2930;
 /// Mainnet Super circuit params
 pub mod params;
 pub(crate) mod precompile_block_trace;
+/// Named capacity profiles
+pub mod profile;
 #[cfg(any(feature = "test", test))]
 pub(crate) mod test;
 
@@ -64,6 +66,7 @@ use crate::bytecode_circuit::circuit::to_poseidon_hash::{
 #[cfg(not(feature = "poseidon-codehash"))]
 use crate::bytecode_circuit::circuit::BytecodeCircuitConfig;
 use crate::{
+    bls12_381_circuit::{Bls12381Circuit, Bls12381CircuitConfig, Bls12381CircuitConfigArgs},
     bytecode_circuit::circuit::{BytecodeCircuit, BytecodeCircuitConfigArgs},
     copy_circuit::{CopyCircuit, CopyCircuitConfig, CopyCircuitConfigArgs},
     ecc_circuit::{EccCircuit, EccCircuitConfig, EccCircuitConfigArgs},
@@ -84,9 +87,9 @@ use crate::{
     sig_circuit::{SigCircuit, SigCircuitConfig, SigCircuitConfigArgs},
     state_circuit::{StateCircuit, StateCircuitConfig, StateCircuitConfigArgs},
     table::{
-        BlockTable, BytecodeTable, CopyTable, EccTable, ExpTable, KeccakTable, ModExpTable,
-        MptTable, PoseidonTable, PowOfRandTable, RlpFsmRlpTable as RlpTable, RwTable, SHA256Table,
-        SigTable, TxTable, U16Table, U8Table,
+        BlockTable, Bls12381Table, BytecodeTable, CopyTable, EccTable, ExpTable, KeccakTable,
+        ModExpTable, MptTable, PoseidonTable, PowOfRandTable, RlpFsmRlpTable as RlpTable, RwTable,
+        SHA256Table, SigTable, TxTable, U16Table, U8Table,
     },
     tx_circuit::{TxCircuit, TxCircuitConfig, TxCircuitConfigArgs},
     util::{circuit_stats, log2_ceil, Challenges, SubCircuit, SubCircuitConfig},
@@ -123,6 +126,7 @@ pub struct SuperCircuitConfig<F: Field> {
     sig_circuit: SigCircuitConfig<F>,
     modexp_circuit: ModExpCircuitConfig,
     ecc_circuit: EccCircuitConfig<F>,
+    bls12_381_circuit: Bls12381CircuitConfig<F>,
     sha256_circuit: SHA256CircuitConfig,
     #[cfg(not(feature = "poseidon-codehash"))]
     bytecode_circuit: BytecodeCircuitConfig<F>,
@@ -204,6 +208,8 @@ impl SubCircuitConfig<Fr> for SuperCircuitConfig<Fr> {
         log_circuit_info(meta, "modexp table");
         let ecc_table = EccTable::construct(meta);
         log_circuit_info(meta, "ecc table");
+        let bls12_381_table = Bls12381Table::construct(meta);
+        log_circuit_info(meta, "bls12-381 table");
         let pow_of_rand_table = PowOfRandTable::construct(meta, &challenges_expr);
         log_circuit_info(meta, "power of randomness table");
 
@@ -357,12 +363,13 @@ impl SubCircuitConfig<Fr> for SuperCircuitConfig<Fr> {
                 sig_table,
                 modexp_table,
                 ecc_table,
+                bls12_381_table,
                 pow_of_rand_table,
             },
         );
         log_circuit_info(meta, "evm circuit");
 
-        // Sig Circuit and ECC Circuit use halo2-lib's vertifcal assignments gates
+        // Sig Circuit, ECC Circuit and BLS12-381 Circuit use halo2-lib's vertifcal assignments gates
         // and need to be configured after Circuits with higher counts of unique rotation queries
         // (ex. Keccak, EVM) to avoid assigning advice values into blinding area.
         let sig_circuit = SigCircuitConfig::new(
@@ -379,11 +386,20 @@ impl SubCircuitConfig<Fr> for SuperCircuitConfig<Fr> {
             meta,
             EccCircuitConfigArgs {
                 ecc_table,
-                challenges: challenges_expr,
+                challenges: challenges_expr.clone(),
             },
         );
         log_circuit_info(meta, "ecc circuit");
 
+        let bls12_381_circuit = Bls12381CircuitConfig::new(
+            meta,
+            Bls12381CircuitConfigArgs {
+                bls12_381_table,
+                challenges: challenges_expr,
+            },
+        );
+        log_circuit_info(meta, "bls12-381 circuit");
+
         #[cfg(feature = "onephase")]
         if meta.max_phase() != 0 {
             log::warn!("max_phase: {}", meta.max_phase());
@@ -403,6 +419,7 @@ impl SubCircuitConfig<Fr> for SuperCircuitConfig<Fr> {
             sig_circuit,
             modexp_circuit,
             ecc_circuit,
+            bls12_381_circuit,
             sha256_circuit,
             bytecode_circuit,
             copy_circuit,
@@ -464,6 +481,8 @@ pub struct SuperCircuit<
     pub modexp_circuit: ModExpCircuit<F>,
     /// Ecc Circuit
     pub ecc_circuit: EccCircuit<F, 9>,
+    /// BLS12-381 Circuit
+    pub bls12_381_circuit: Bls12381Circuit<F>,
     /// Rlp Circuit
     pub rlp_circuit: RlpCircuit<F, Transaction>,
     /// Mpt Circuit
@@ -538,6 +557,8 @@ impl<
         push("sig", sig);
         let ecc = EccCircuit::<Fr, 9>::min_num_rows_block(block);
         push("ecc", ecc);
+        let bls12_381 = Bls12381Circuit::<Fr>::min_num_rows_block(block);
+        push("bls12_381", bls12_381);
         #[cfg(feature = "zktrie")]
         {
             let mpt = MptCircuit::<Fr>::min_num_rows_block(block);
@@ -609,6 +630,7 @@ impl<
         let rlp_circuit = RlpCircuit::new_from_block(block);
         let sig_circuit = SigCircuit::new_from_block(block);
         let ecc_circuit = EccCircuit::new_from_block(block);
+        let bls12_381_circuit = Bls12381Circuit::new_from_block(block);
         #[cfg(feature = "zktrie")]
         let mpt_circuit = MptCircuit::new_from_block(block);
         SuperCircuit::<Fr, MAX_TXS, MAX_CALLDATA, MAX_INNER_BLOCKS, MOCK_RANDOMNESS> {
@@ -626,6 +648,7 @@ impl<
             sig_circuit,
             modexp_circuit,
             ecc_circuit,
+            bls12_381_circuit,
             #[cfg(feature = "zktrie")]
             mpt_circuit,
             circuit_params: block.circuits_params,
@@ -701,6 +724,9 @@ impl<
         log::debug!("assigning ecc_circuit");
         self.ecc_circuit
             .synthesize_sub(&config.ecc_circuit, challenges, layouter)?;
+        log::debug!("assigning bls12_381_circuit");
+        self.bls12_381_circuit
+            .synthesize_sub(&config.bls12_381_circuit, challenges, layouter)?;
         log::debug!("assigning modexp_circuit");
         self.modexp_circuit
             .synthesize_sub(&config.modexp_circuit, challenges, layouter)?;
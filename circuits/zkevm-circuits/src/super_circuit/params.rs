@@ -1,9 +1,8 @@
 #![allow(missing_docs)]
-use crate::exp_circuit::param::OFFSET_INCREMENT;
 use bus_mapping::circuit_input_builder::{CircuitsParams, PrecompileEcParams};
 use halo2_proofs::halo2curves::bn256::Fr;
 
-use super::SuperCircuit;
+use super::{profile::CircuitsProfile, SuperCircuit};
 
 ////// params for Super Circuit of degree = 20 ////////////
 pub const MAX_TXS: usize = 100;
@@ -21,6 +20,7 @@ pub const MAX_RWS: usize = 1_000_000;
 pub const MAX_PRECOMPILE_EC_ADD: usize = 50;
 pub const MAX_PRECOMPILE_EC_MUL: usize = 50;
 pub const MAX_PRECOMPILE_EC_PAIRING: usize = 2;
+pub const MAX_PRECOMPILE_BLS12_G1_ADD: usize = 10;
 
 /// default params for super circuit
 pub fn get_super_circuit_params() -> CircuitsParams {
@@ -42,6 +42,7 @@ pub fn get_super_circuit_params() -> CircuitsParams {
             ec_add: MAX_PRECOMPILE_EC_ADD,
             ec_mul: MAX_PRECOMPILE_EC_MUL,
             ec_pairing: MAX_PRECOMPILE_EC_PAIRING,
+            bls12_g1_add: MAX_PRECOMPILE_BLS12_G1_ADD,
         },
     }
 }
@@ -51,25 +52,5 @@ pub type ScrollSuperCircuit = SuperCircuit<Fr, MAX_TXS, MAX_CALLDATA, MAX_INNER_
 
 /// Capacity for each subcircuit
 pub fn get_sub_circuit_limit_and_confidence() -> Vec<(usize, f64)> {
-    // Change it to 0.99?
-    let default_confidence = 0.95;
-    [
-        (MAX_RWS, default_confidence),                          // evm
-        (MAX_RWS, default_confidence),                          // state
-        (MAX_BYTECODE, default_confidence),                     // bytecode
-        (MAX_RWS, default_confidence),                          // copy
-        (MAX_KECCAK_ROWS, default_confidence),                  // keccak
-        (MAX_KECCAK_ROWS, default_confidence),                  // sha256
-        (MAX_VERTICAL_ROWS, default_confidence),                // tx
-        (MAX_CALLDATA, default_confidence),                     // rlp
-        (OFFSET_INCREMENT * MAX_EXP_STEPS, default_confidence), // exp
-        (MAX_KECCAK_ROWS, default_confidence),                  // modexp
-        (MAX_RWS, default_confidence),                          // pi
-        (MAX_POSEIDON_ROWS, default_confidence),                // poseidon
-        (MAX_VERTICAL_ROWS, default_confidence),                // sig
-        (MAX_VERTICAL_ROWS, 1.0),                               // ecc
-        #[cfg(feature = "scroll")]
-        (MAX_MPT_ROWS, default_confidence), // mpt
-    ]
-    .to_vec()
+    CircuitsProfile::mainnet_chunk().sub_circuit_limit_and_confidence()
 }
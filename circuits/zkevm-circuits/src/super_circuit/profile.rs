@@ -0,0 +1,394 @@
+//! Named capacity profiles for the Super Circuit.
+//!
+//! A profile pairs a [`CircuitsParams`] with the circuit degree it was sized for. Profiles can be
+//! taken from the built-in set (see [`CircuitsProfile::builtin`]) or loaded from a TOML/JSON file,
+//! so capacities can be retargeted without recompiling:
+//!
+//! ```toml
+//! name = "my-chunk"
+//! degree = 20
+//!
+//! [params]
+//! max_rws = 1000000
+//! max_txs = 100
+//! # ...
+//!
+//! [params.max_ec_ops]
+//! ec_add = 50
+//! ec_mul = 50
+//! ec_pairing = 2
+//! bls12_g1_add = 10
+//! ```
+use super::params::{
+    get_super_circuit_params, ScrollSuperCircuit, MAX_CALLDATA, MAX_INNER_BLOCKS, MAX_TXS,
+};
+use crate::{exp_circuit::param::OFFSET_INCREMENT, util::SubCircuit, witness::Block};
+use bus_mapping::circuit_input_builder::{CircuitsParams, PrecompileEcParams};
+use halo2_proofs::halo2curves::bn256::Fr;
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+use std::{fmt, fs, path::Path};
+
+/// Name of the profile used by the small unit tests.
+pub const TINY_TEST: &str = "tiny-test";
+/// Name of the profile used for mainnet chunk proving.
+pub const MAINNET_CHUNK: &str = "mainnet-chunk";
+/// Name of the profile used when only the OABE pairing circuit is proven.
+pub const OABE_ONLY: &str = "oabe-only";
+
+/// Confidence applied to every sub-circuit limit, except for the ECC circuit whose row usage is
+/// already reported as a share of its capacity.
+const DEFAULT_CONFIDENCE: f64 = 0.95;
+
+/// Error raised while loading or validating a [`CircuitsProfile`].
+#[derive(Debug)]
+pub enum ProfileError {
+    /// The profile file could not be read.
+    Io(std::io::Error),
+    /// The profile file could not be parsed.
+    Parse(String),
+    /// The profile file extension is neither `toml` nor `json`.
+    UnknownFormat(String),
+    /// A row capacity doesn't fit in the usable rows of the chosen degree.
+    CapacityOverflow {
+        /// Name of the offending capacity knob.
+        field: &'static str,
+        /// Requested number of rows.
+        rows: usize,
+        /// Usable rows for the profile degree.
+        usable_rows: usize,
+    },
+    /// A capacity that other circuits divide by is zero.
+    ZeroCapacity(&'static str),
+    /// A capacity exceeds the const generics `ScrollSuperCircuit` is instantiated with.
+    ExceedsSuperCircuit {
+        /// Name of the offending capacity knob.
+        field: &'static str,
+        /// Requested capacity.
+        value: usize,
+        /// Capacity compiled into `ScrollSuperCircuit`.
+        max: usize,
+    },
+}
+
+impl fmt::Display for ProfileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "failed to read profile: {err}"),
+            Self::Parse(err) => write!(f, "failed to parse profile: {err}"),
+            Self::UnknownFormat(path) => {
+                write!(
+                    f,
+                    "unknown profile format of {path}, expected .toml or .json"
+                )
+            }
+            Self::CapacityOverflow {
+                field,
+                rows,
+                usable_rows,
+            } => write!(
+                f,
+                "{field} needs {rows} rows, but only {usable_rows} rows are usable"
+            ),
+            Self::ZeroCapacity(field) => write!(f, "{field} must not be zero"),
+            Self::ExceedsSuperCircuit { field, value, max } => {
+                write!(
+                    f,
+                    "{field} is {value}, but the super circuit supports at most {max}"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for ProfileError {}
+
+impl From<std::io::Error> for ProfileError {
+    fn from(err: std::io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+/// Row usage of one sub-circuit measured against the capacity of a profile.
+#[derive(Clone, Debug)]
+pub struct ProfileRowUsage {
+    /// Sub-circuit name
+    pub name: String,
+    /// Rows used by the block, without padding
+    pub row_num_real: usize,
+    /// Rows available in the profile, after applying the confidence factor
+    pub capacity: usize,
+}
+
+impl ProfileRowUsage {
+    /// Whether the sub-circuit fits in the profile capacity.
+    pub fn is_ok(&self) -> bool {
+        self.row_num_real <= self.capacity
+    }
+}
+
+/// A named set of circuit capacities sized for a given degree.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CircuitsProfile {
+    /// Profile name
+    pub name: String,
+    /// Degree of the circuit the capacities are sized for
+    pub degree: u32,
+    /// Capacities of the sub-circuits
+    pub params: CircuitsParams,
+}
+
+impl CircuitsProfile {
+    /// Profile matching `CircuitsParams::default()`, used by most unit tests.
+    pub fn tiny_test() -> Self {
+        Self {
+            name: TINY_TEST.to_string(),
+            degree: 18,
+            params: CircuitsParams::default(),
+        }
+    }
+
+    /// Profile used for mainnet chunk proving, see [`get_super_circuit_params`].
+    pub fn mainnet_chunk() -> Self {
+        Self {
+            name: MAINNET_CHUNK.to_string(),
+            degree: 20,
+            params: get_super_circuit_params(),
+        }
+    }
+
+    /// Profile for proving OABE decryption only: the vertical circuits get the whole degree-19
+    /// budget while every EVM-facing capacity is kept minimal.
+    pub fn oabe_only() -> Self {
+        Self {
+            name: OABE_ONLY.to_string(),
+            degree: 19,
+            params: CircuitsParams {
+                max_rws: 1000,
+                max_txs: 1,
+                max_calldata: 256,
+                max_rlp_rows: 1000,
+                max_copy_rows: 1000,
+                max_inner_blocks: 1,
+                max_exp_steps: 100,
+                max_bytecode: 512,
+                max_evm_rows: 0,
+                max_mpt_rows: 1000,
+                max_keccak_rows: 0,
+                max_poseidon_rows: 0,
+                max_ec_ops: PrecompileEcParams {
+                    ec_add: 1,
+                    ec_mul: 1,
+                    ec_pairing: 1,
+                    bls12_g1_add: 1,
+                },
+                max_vertical_circuit_rows: 500_000,
+            },
+        }
+    }
+
+    /// Look up a built-in profile by name.
+    pub fn builtin(name: &str) -> Option<Self> {
+        match name {
+            TINY_TEST => Some(Self::tiny_test()),
+            MAINNET_CHUNK => Some(Self::mainnet_chunk()),
+            OABE_ONLY => Some(Self::oabe_only()),
+            _ => None,
+        }
+    }
+
+    /// Load a profile from a `.toml` or `.json` file.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, ProfileError> {
+        let path = path.as_ref();
+        let content = fs::read_to_string(path)?;
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("toml") => {
+                toml::from_str(&content).map_err(|err| ProfileError::Parse(err.to_string()))
+            }
+            Some("json") => {
+                serde_json::from_str(&content).map_err(|err| ProfileError::Parse(err.to_string()))
+            }
+            _ => Err(ProfileError::UnknownFormat(path.display().to_string())),
+        }
+    }
+
+    /// Resolve a profile either by built-in name or by file path, and validate it.
+    pub fn load(name_or_path: &str) -> Result<Self, ProfileError> {
+        let profile = match Self::builtin(name_or_path) {
+            Some(profile) => profile,
+            None => Self::from_file(name_or_path)?,
+        };
+        profile.validate()?;
+        Ok(profile)
+    }
+
+    /// Number of rows usable by the sub-circuits at the profile degree.
+    pub fn usable_rows(&self) -> usize {
+        (1 << self.degree) - <ScrollSuperCircuit as SubCircuit<Fr>>::unusable_rows()
+    }
+
+    /// Check that every row capacity fits in the profile degree, and that the capacities other
+    /// circuits divide by are non-zero. A zero row capacity means "dynamic" and is skipped.
+    pub fn validate(&self) -> Result<(), ProfileError> {
+        let params = &self.params;
+        for (field, value, max) in [
+            ("max_txs", params.max_txs, MAX_TXS),
+            ("max_calldata", params.max_calldata, MAX_CALLDATA),
+            (
+                "max_inner_blocks",
+                params.max_inner_blocks,
+                MAX_INNER_BLOCKS,
+            ),
+        ] {
+            if value > max {
+                return Err(ProfileError::ExceedsSuperCircuit { field, value, max });
+            }
+        }
+        for (field, value) in [
+            ("max_txs", params.max_txs),
+            ("max_inner_blocks", params.max_inner_blocks),
+            ("max_ec_ops.ec_add", params.max_ec_ops.ec_add),
+            ("max_ec_ops.ec_mul", params.max_ec_ops.ec_mul),
+            ("max_ec_ops.ec_pairing", params.max_ec_ops.ec_pairing),
+            ("max_ec_ops.bls12_g1_add", params.max_ec_ops.bls12_g1_add),
+        ] {
+            if value == 0 {
+                return Err(ProfileError::ZeroCapacity(field));
+            }
+        }
+
+        let usable_rows = self.usable_rows();
+        for (field, rows) in [
+            ("max_rws", params.max_rws),
+            ("max_evm_rows", params.max_evm_rows),
+            ("max_copy_rows", params.max_copy_rows),
+            ("max_bytecode", params.max_bytecode),
+            ("max_rlp_rows", params.max_rlp_rows),
+            ("max_exp_steps", OFFSET_INCREMENT * params.max_exp_steps),
+            ("max_mpt_rows", params.max_mpt_rows),
+            ("max_keccak_rows", params.max_keccak_rows),
+            ("max_poseidon_rows", params.max_poseidon_rows),
+            (
+                "max_vertical_circuit_rows",
+                params.max_vertical_circuit_rows,
+            ),
+        ] {
+            if rows > usable_rows {
+                return Err(ProfileError::CapacityOverflow {
+                    field,
+                    rows,
+                    usable_rows,
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Capacity and confidence of each sub-circuit, in the order reported by
+    /// [`ScrollSuperCircuit::min_num_rows_block_subcircuits`]. A dynamic (zero) capacity is
+    /// resolved to the usable rows of the profile degree, so every limit is non-zero.
+    pub fn sub_circuit_limit_and_confidence(&self) -> Vec<(usize, f64)> {
+        let params = &self.params;
+        let usable_rows = self.usable_rows();
+        [
+            (params.max_evm_rows, DEFAULT_CONFIDENCE),    // evm
+            (params.max_rws, DEFAULT_CONFIDENCE),         // state
+            (params.max_bytecode, DEFAULT_CONFIDENCE),    // bytecode
+            (params.max_copy_rows, DEFAULT_CONFIDENCE),   // copy
+            (params.max_keccak_rows, DEFAULT_CONFIDENCE), // keccak
+            (params.max_keccak_rows, DEFAULT_CONFIDENCE), // sha256
+            (params.max_vertical_circuit_rows, DEFAULT_CONFIDENCE), // tx
+            (params.max_calldata, DEFAULT_CONFIDENCE),    // rlp
+            (OFFSET_INCREMENT * params.max_exp_steps, DEFAULT_CONFIDENCE), // exp
+            (params.max_keccak_rows, DEFAULT_CONFIDENCE), // modexp
+            (params.max_rws, DEFAULT_CONFIDENCE),         // pi
+            (params.max_poseidon_rows, DEFAULT_CONFIDENCE), // poseidon
+            (params.max_vertical_circuit_rows, DEFAULT_CONFIDENCE), // sig
+            (params.max_vertical_circuit_rows, 1.0),      // ecc
+            (params.max_vertical_circuit_rows, 1.0),      // bls12_381
+            #[cfg(feature = "scroll")]
+            (params.max_mpt_rows, DEFAULT_CONFIDENCE), // mpt
+        ]
+        .into_iter()
+        .map(|(rows, confidence)| match rows {
+            0 => (usable_rows, confidence),
+            rows => (rows, confidence),
+        })
+        .collect()
+    }
+
+    /// Report the row usage of every sub-circuit for `block` against the profile capacities.
+    pub fn row_usage(&self, block: &Block) -> Vec<ProfileRowUsage> {
+        if block.circuits_params != self.params {
+            log::warn!(
+                "block built with circuits_params {:?}, reporting against profile {}",
+                block.circuits_params,
+                self.name
+            );
+        }
+        ScrollSuperCircuit::min_num_rows_block_subcircuits(block)
+            .into_iter()
+            .zip_eq(self.sub_circuit_limit_and_confidence())
+            .map(|(usage, (limit, confidence))| ProfileRowUsage {
+                name: usage.name,
+                row_num_real: usage.row_num_real,
+                capacity: (limit as f64 * confidence) as usize,
+            })
+            .collect()
+    }
+}
+
+impl Default for CircuitsProfile {
+    fn default() -> Self {
+        Self::mainnet_chunk()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builtin_profiles_are_valid() {
+        for name in [TINY_TEST, MAINNET_CHUNK, OABE_ONLY] {
+            let profile = CircuitsProfile::builtin(name).unwrap();
+            assert_eq!(profile.name, name);
+            profile.validate().unwrap();
+        }
+    }
+
+    #[test]
+    fn profile_toml_roundtrip() {
+        let profile = CircuitsProfile::oabe_only();
+        let content = toml::to_string(&profile).unwrap();
+        assert_eq!(
+            toml::from_str::<CircuitsProfile>(&content).unwrap(),
+            profile
+        );
+    }
+
+    #[test]
+    fn dynamic_limits_are_resolved() {
+        for name in [TINY_TEST, MAINNET_CHUNK, OABE_ONLY] {
+            let profile = CircuitsProfile::builtin(name).unwrap();
+            for (limit, confidence) in profile.sub_circuit_limit_and_confidence() {
+                assert!((limit as f64 * confidence) as usize > 0, "{name}");
+            }
+        }
+    }
+
+    #[test]
+    fn capacity_overflow_is_rejected() {
+        let mut profile = CircuitsProfile::tiny_test();
+        profile.degree = 10;
+        profile.params.max_rws = 1 << 10;
+        assert!(matches!(
+            profile.validate(),
+            Err(ProfileError::CapacityOverflow {
+                field: "max_rws",
+                ..
+            })
+        ));
+    }
+}
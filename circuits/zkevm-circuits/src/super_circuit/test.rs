@@ -80,7 +80,7 @@ fn test_super_circuit<
         .expect("could not finalize building block");
 
     let mut block = block_convert(&builder.block, &builder.code_db).unwrap();
-    block.apply_mpt_updates(&builder.mpt_init_state.expect("used non-light mode"));
+    block.apply_mpt_updates(&mut builder.mpt_init_state.expect("used non-light mode"));
 
     let active_row_num =SuperCircuit::<
         Fr,
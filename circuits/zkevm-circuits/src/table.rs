@@ -16,8 +16,8 @@ use crate::{
 };
 use bus_mapping::{
     circuit_input_builder::{
-        BigModExp, CopyDataType, CopyEvent, CopyStep, EcAddOp, EcMulOp, EcPairingOp, ExpEvent,
-        PrecompileEcParams,
+        BigModExp, Bls12G1AddOp, CopyDataType, CopyEvent, CopyStep, EcAddOp, EcMulOp, EcPairingOp,
+        ExpEvent, PrecompileEcParams,
     },
     precompile::PrecompileCalls,
 };
@@ -190,6 +190,26 @@ pub enum TxFieldTag {
     MaxPriorityFeePerGas,
     /// Max Fee Per Gas (EIP1559)
     MaxFeePerGas,
+    /// Max Fee Per Blob Gas (EIP4844)
+    MaxFeePerBlobGas,
+    /// Blob versioned hash count (EIP4844)
+    BlobVersionedHashesLen,
+    /// Blob versioned hash (EIP4844)
+    BlobVersionedHash,
+    /// Authorization count (EIP7702)
+    AuthorizationListLen,
+    /// Chain ID of an authorization tuple (EIP7702)
+    AuthorizationChainId,
+    /// Delegate address of an authorization tuple (EIP7702)
+    AuthorizationAddress,
+    /// Authority nonce of an authorization tuple (EIP7702)
+    AuthorizationNonce,
+    /// Signature y_parity of an authorization tuple (EIP7702)
+    AuthorizationYParity,
+    /// Signature r of an authorization tuple (EIP7702)
+    AuthorizationR,
+    /// Signature s of an authorization tuple (EIP7702)
+    AuthorizationS,
 }
 impl_expr!(TxFieldTag);
 
@@ -371,7 +391,7 @@ impl TxTable {
                     || chunk_txbytes_hash_rlc,
                 )?);
 
-                // Assign dynamic calldata and access list section
+                // Assign dynamic calldata, access list and blob hash section
                 for tx in txs.iter().chain(padding_txs.iter()) {
                     for row in tx.table_assignments_dyn(*challenges).into_iter() {
                         assign_row(
@@ -400,6 +420,36 @@ impl TxTable {
                         )?;
                         offset += 1;
                     }
+                    for row in tx
+                        .table_assignments_blob_hashes_dyn(*challenges)
+                        .into_iter()
+                    {
+                        assign_row(
+                            &mut region,
+                            offset,
+                            self.q_enable,
+                            &advice_columns,
+                            &self.tag,
+                            &row,
+                            "",
+                        )?;
+                        offset += 1;
+                    }
+                    for row in tx
+                        .table_assignments_authorizations_dyn(*challenges)
+                        .into_iter()
+                    {
+                        assign_row(
+                            &mut region,
+                            offset,
+                            self.q_enable,
+                            &advice_columns,
+                            &self.tag,
+                            &row,
+                            "",
+                        )?;
+                        offset += 1;
+                    }
                 }
 
                 Ok(tx_value_cells)
@@ -1330,6 +1380,8 @@ pub enum BlockContextFieldTag {
     /// included in this block which also taking skipped l1 msgs into account.
     /// This could possibly be larger than NumTxs.
     NumAllTxs,
+    /// Blob base fee derived from the excess blob gas of the block (EIP-4844)
+    BlobBaseFee,
 }
 impl_expr!(BlockContextFieldTag);
 
@@ -2851,6 +2903,110 @@ impl EccTable {
     }
 }
 
+/// Lookup table within the BLS12-381 circuit, for the EIP-2537 precompiles it verifies.
+///
+/// Each row maps the RLC of the input bytes of a precompile call to whether or not the input was
+/// valid and the RLC of the output bytes, which are empty for an invalid input:
+///
+/// - Bls12G1Add: input_rlc = rlc(P.x | P.y | Q.x | Q.y), output_rlc = rlc(R.x | R.y)
+///
+/// where each co-ordinate is in the 64-bytes EIP-2537 encoding.
+#[derive(Clone, Copy, Debug)]
+pub struct Bls12381Table {
+    /// The BLS12-381 circuit reserves a fixed number of rows per op, so the `op_type` of each row
+    /// is known in advance.
+    pub op_type: Column<Fixed>,
+    /// Indicates whether or not the EVM inputs were valid.
+    pub is_valid: Column<Advice>,
+    /// Advice column for RLC of all input bytes.
+    pub input_rlc: Column<Advice>,
+    /// Advice column for RLC of all output bytes.
+    pub output_rlc: Column<Advice>,
+}
+
+impl<F: Field> LookupTable<F> for Bls12381Table {
+    fn columns(&self) -> Vec<Column<Any>> {
+        vec![
+            self.op_type.into(),
+            self.is_valid.into(),
+            self.input_rlc.into(),
+            self.output_rlc.into(),
+        ]
+    }
+
+    fn annotations(&self) -> Vec<String> {
+        vec![
+            String::from("op_type"),
+            String::from("is_valid"),
+            String::from("input_rlc"),
+            String::from("output_rlc"),
+        ]
+    }
+}
+
+impl Bls12381Table {
+    /// Construct the BLS12-381 table.
+    pub(crate) fn construct<F: Field>(meta: &mut ConstraintSystem<F>) -> Self {
+        Self {
+            op_type: meta.fixed_column(),
+            is_valid: meta.advice_column(),
+            input_rlc: meta.advice_column_in(SecondPhase),
+            output_rlc: meta.advice_column_in(SecondPhase),
+        }
+    }
+
+    /// Load witness in the BLS12-381 table. Note: for dev purposes.
+    pub fn dev_load<F: Field>(
+        &self,
+        layouter: &mut impl Layouter<F>,
+        params: PrecompileEcParams,
+        g1_add_ops: &[Bls12G1AddOp],
+        challenges: &Challenges<Value<F>>,
+    ) -> Result<(), Error> {
+        let keccak_rand = challenges.keccak_input();
+        let assignments = g1_add_ops
+            .iter()
+            .chain(repeat(&Bls12G1AddOp::default()))
+            .take(params.bls12_g1_add)
+            .map(|op| {
+                [
+                    Value::known(F::from(u64::from(PrecompileCalls::Bls12G1Add))),
+                    Value::known(F::from(op.is_valid() as u64)),
+                    keccak_rand.map(|r| rlc::value(op.input.iter().rev(), r)),
+                    keccak_rand.map(|r| rlc::value(op.output.iter().rev(), r)),
+                ]
+            })
+            .collect_vec();
+
+        layouter.assign_region(
+            || "bls12-381 table dev load",
+            |mut region| {
+                for (i, row) in assignments.iter().enumerate() {
+                    region.assign_fixed(
+                        || format!("bls12-381 table row = {i}, op_type"),
+                        self.op_type,
+                        i,
+                        || row[0],
+                    )?;
+                    for (&column, &value) in <Bls12381Table as LookupTable<F>>::advice_columns(self)
+                        .iter()
+                        .zip_eq(row.iter().skip(1))
+                    {
+                        region.assign_advice(
+                            || format!("bls12-381 table row = {i}, column = {column:?}"),
+                            column,
+                            i,
+                            || value,
+                        )?;
+                    }
+                }
+
+                Ok(())
+            },
+        )
+    }
+}
+
 /// Lookup table embedded in the modexp circuit for precompile.
 #[derive(Clone, Copy, Debug)]
 pub struct ModExpTable {
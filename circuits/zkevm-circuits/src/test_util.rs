@@ -230,7 +230,7 @@ impl<const NACC: usize, const NTX: usize> CircuitTestBuilder<NACC, NTX> {
                         .expect("could not finalize building block");
                     let mut block =
                         crate::witness::block_convert(&builder.block, &builder.code_db).unwrap();
-                    block.apply_mpt_updates(&builder.mpt_init_state.unwrap());
+                    block.apply_mpt_updates(&mut builder.mpt_init_state.unwrap());
                     block
                 }
 
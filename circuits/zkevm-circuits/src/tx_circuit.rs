@@ -20,10 +20,13 @@ use crate::{
         BlockTable, KeccakTable, LookupTable, PowOfRandTable, RlpFsmRlpTable as RlpTable, SigTable,
         TxFieldTag,
         TxFieldTag::{
-            AccessListAddressesLen, AccessListRLC, AccessListStorageKeysLen, BlockNumber, CallData,
-            CallDataGasCost, CallDataLength, CallDataRLC, CalleeAddress, CallerAddress, ChainID,
-            Gas, GasPrice, IsCreate, MaxFeePerGas, MaxPriorityFeePerGas, Nonce, SigR, SigS, SigV,
-            TxDataGasCost, TxHashLength, TxHashRLC, TxSignHash, TxSignLength, TxSignRLC,
+            AccessListAddressesLen, AccessListRLC, AccessListStorageKeysLen, AuthorizationAddress,
+            AuthorizationChainId, AuthorizationListLen, AuthorizationNonce, AuthorizationR,
+            AuthorizationS, AuthorizationYParity, BlobVersionedHash, BlobVersionedHashesLen,
+            BlockNumber, CallData, CallDataGasCost, CallDataLength, CallDataRLC, CalleeAddress,
+            CallerAddress, ChainID, Gas, GasPrice, IsCreate, MaxFeePerBlobGas, MaxFeePerGas,
+            MaxPriorityFeePerGas, Nonce, SigR, SigS, SigV, TxDataGasCost, TxHashLength, TxHashRLC,
+            TxSignHash, TxSignLength, TxSignRLC,
         },
         TxTable, U16Table, U8Table,
     },
@@ -35,8 +38,9 @@ use crate::{
     witness::{
         rlp_fsm::{Tag, ValueTagLength},
         Format::{
-            L1MsgHash, TxHashEip155, TxHashEip1559, TxHashEip2930, TxHashPreEip155, TxSignEip155,
-            TxSignEip1559, TxSignEip2930, TxSignPreEip155,
+            L1MsgHash, TxHashEip155, TxHashEip1559, TxHashEip2930, TxHashEip4844, TxHashEip7702,
+            TxHashPreEip155, TxSignEip155, TxSignEip1559, TxSignEip2930, TxSignEip4844,
+            TxSignEip7702, TxSignPreEip155,
         },
         RlpTag,
         RlpTag::{GasCost, Len, Null, RLC},
@@ -48,7 +52,7 @@ use crate::{util::Field, witness::keccak::keccak_inputs_sign_verify};
 use eth_types::{
     geth_types::{
         access_list_size, TxType,
-        TxType::{Eip155, Eip1559, Eip2930, L1Msg, PreEip155},
+        TxType::{Eip155, Eip1559, Eip2930, Eip4844, Eip7702, L1Msg, PreEip155},
     },
     sign_types::SignData,
     AccessList, Address, ToAddress, ToBigEndian,
@@ -85,7 +89,7 @@ use halo2_proofs::plonk::{Any, Fixed};
 use itertools::Itertools;
 
 /// Number of rows of one tx occupies in the fixed part of tx table
-pub const TX_LEN: usize = 28;
+pub const TX_LEN: usize = 31;
 /// Offset of TxHash tag in the tx table
 pub const TX_HASH_OFFSET: usize = 21;
 /// Offset of CallerAddress in the tx table
@@ -118,6 +122,10 @@ enum LookupCondition {
     Keccak,
     // lookup into dynamic access list section of tx table
     TxAccessList,
+    // lookup into dynamic blob versioned hash section of tx table
+    TxBlobHashes,
+    // lookup into dynamic authorization section of tx table
+    TxAuthorizations,
 }
 
 #[derive(Clone, Debug)]
@@ -175,7 +183,8 @@ pub struct TxRomTable {
     /// This indicator only applies to the dynamic section
     pub is_final: Column<Fixed>,
     /// Indicator for the end of the fixed section
-    /// The last tag of the fixed section should transition into either calldata or access_list
+    /// The last tag of the fixed section should transition into either calldata, access_list or
+    /// blob versioned hashes
     pub is_next_dynamic_first: Column<Fixed>,
 }
 
@@ -247,13 +256,20 @@ impl TxRomTable {
                     (AccessListStorageKeysLen, AccessListRLC, 1, 1, 0),
                     (AccessListRLC, MaxFeePerGas, 1, 1, 0),
                     (MaxFeePerGas, MaxPriorityFeePerGas, 1, 1, 0),
-                    (MaxPriorityFeePerGas, BlockNumber, 1, 1, 0),
+                    (MaxPriorityFeePerGas, MaxFeePerBlobGas, 1, 1, 0),
+                    (MaxFeePerBlobGas, BlobVersionedHashesLen, 1, 1, 0),
+                    (BlobVersionedHashesLen, AuthorizationListLen, 1, 1, 0),
+                    (AuthorizationListLen, BlockNumber, 1, 1, 0),
                     // Transition into dynamic section of tx_table
                     (BlockNumber, Nonce, 0, 1, 0),
                     (BlockNumber, CallData, 1, 1, 1),
                     (BlockNumber, CallData, 0, 1, 1),
                     (BlockNumber, TxFieldTag::AccessListAddress, 1, 1, 1),
                     (BlockNumber, TxFieldTag::AccessListAddress, 0, 1, 1),
+                    (BlockNumber, BlobVersionedHash, 1, 1, 1),
+                    (BlockNumber, BlobVersionedHash, 0, 1, 1),
+                    (BlockNumber, AuthorizationChainId, 1, 1, 1),
+                    (BlockNumber, AuthorizationChainId, 0, 1, 1),
                     // Transition between dynamic tags of tx_table
                     (CallData, CallData, 1, 0, 0),
                     (CallData, CallData, 0, 1, 0),
@@ -303,6 +319,48 @@ impl TxRomTable {
                     ),
                     (TxFieldTag::AccessListAddress, CallData, 0, 1, 0),
                     (TxFieldTag::AccessListStorageKey, CallData, 0, 1, 0),
+                    // Blob versioned hashes are the last part of a tx's dynamic section
+                    (CallData, BlobVersionedHash, 1, 1, 0),
+                    (CallData, BlobVersionedHash, 0, 1, 0),
+                    (TxFieldTag::AccessListAddress, BlobVersionedHash, 1, 1, 0),
+                    (TxFieldTag::AccessListAddress, BlobVersionedHash, 0, 1, 0),
+                    (TxFieldTag::AccessListStorageKey, BlobVersionedHash, 1, 1, 0),
+                    (TxFieldTag::AccessListStorageKey, BlobVersionedHash, 0, 1, 0),
+                    (BlobVersionedHash, BlobVersionedHash, 1, 0, 0),
+                    (BlobVersionedHash, BlobVersionedHash, 0, 1, 0),
+                    (BlobVersionedHash, CallData, 0, 1, 0),
+                    (BlobVersionedHash, TxFieldTag::AccessListAddress, 0, 1, 0),
+                    (BlobVersionedHash, AuthorizationChainId, 0, 1, 0),
+                    // Authorization tuples are the last part of a tx's dynamic section, each tuple
+                    // takes six rows
+                    (CallData, AuthorizationChainId, 1, 1, 0),
+                    (CallData, AuthorizationChainId, 0, 1, 0),
+                    (TxFieldTag::AccessListAddress, AuthorizationChainId, 1, 1, 0),
+                    (TxFieldTag::AccessListAddress, AuthorizationChainId, 0, 1, 0),
+                    (
+                        TxFieldTag::AccessListStorageKey,
+                        AuthorizationChainId,
+                        1,
+                        1,
+                        0,
+                    ),
+                    (
+                        TxFieldTag::AccessListStorageKey,
+                        AuthorizationChainId,
+                        0,
+                        1,
+                        0,
+                    ),
+                    (AuthorizationChainId, AuthorizationAddress, 1, 0, 0),
+                    (AuthorizationAddress, AuthorizationNonce, 1, 0, 0),
+                    (AuthorizationNonce, AuthorizationYParity, 1, 0, 0),
+                    (AuthorizationYParity, AuthorizationR, 1, 0, 0),
+                    (AuthorizationR, AuthorizationS, 1, 0, 0),
+                    (AuthorizationS, AuthorizationChainId, 1, 0, 0),
+                    (AuthorizationS, CallData, 0, 1, 0),
+                    (AuthorizationS, TxFieldTag::AccessListAddress, 0, 1, 0),
+                    (AuthorizationS, BlobVersionedHash, 0, 1, 0),
+                    (AuthorizationS, AuthorizationChainId, 0, 1, 0),
                     // Continue padding. Padding has the Calldata tag
                     (CallData, CallData, 1, 1, 0),
                 ];
@@ -338,7 +396,7 @@ pub struct TxCircuitConfig<F: Field> {
     // A selector which is enabled at 1st row
     q_first: Column<Fixed>,
     tx_table: TxTable,
-    tx_tag_bits: BinaryNumberConfig<TxFieldTag, 5>,
+    tx_tag_bits: BinaryNumberConfig<TxFieldTag, 6>,
 
     tx_type: Column<Advice>,
     tx_type_bits: BinaryNumberConfig<TxType, 3>,
@@ -363,12 +421,15 @@ pub struct TxCircuitConfig<F: Field> {
 
     /// Columns used to reduce degree
     is_tag_block_num: Column<Advice>,
+    is_tag_nonce: Column<Advice>,
     is_calldata: Column<Advice>,
     is_caller_address: Column<Advice>,
     is_row_hash_rlc: Column<Advice>,
     is_l1_msg: Column<Advice>,
     is_eip2930: Column<Advice>,
     is_eip1559: Column<Advice>,
+    is_eip4844: Column<Advice>,
+    is_eip7702: Column<Advice>,
     is_chain_id: Column<Advice>,
     is_tx_id_zero: Column<Advice>,
     lookup_conditions: HashMap<LookupCondition, Column<Advice>>,
@@ -424,6 +485,12 @@ pub struct TxCircuitConfig<F: Field> {
     // access list tag denoter, reduces degree
     is_access_list_address: Column<Advice>,
     is_access_list_storage_key: Column<Advice>,
+    // section denoter for blob versioned hashes (EIP-4844), reduces degree
+    is_blob_hash: Column<Advice>,
+    // section denoter for authorization tuples (EIP-7702), reduces degree
+    is_authorization: Column<Advice>,
+    // the last field of an authorization tuple, reduces degree
+    is_tag_authorization_s: Column<Advice>,
     // field_rlc holds tag rlc from RLP FSM
     // works together with section_rlc to ensure
     // no ommittance in access list dynamic section
@@ -536,12 +603,15 @@ impl<F: Field> SubCircuitConfig<F> for TxCircuitConfig<F> {
         let is_l1_msg = meta.advice_column();
         let is_eip2930 = meta.advice_column();
         let is_eip1559 = meta.advice_column();
+        let is_eip4844 = meta.advice_column();
+        let is_eip7702 = meta.advice_column();
         let is_calldata = meta.advice_column();
         let is_tx_id_zero = meta.advice_column();
         let is_caller_address = meta.advice_column();
         let is_row_hash_rlc = meta.advice_column();
         let is_chain_id = meta.advice_column();
         let is_tag_block_num = meta.advice_column();
+        let is_tag_nonce = meta.advice_column();
         let lookup_conditions = [
             LookupCondition::TxCalldata,
             LookupCondition::L1MsgHash,
@@ -549,6 +619,8 @@ impl<F: Field> SubCircuitConfig<F> for TxCircuitConfig<F> {
             LookupCondition::RlpHashTag,
             LookupCondition::Keccak,
             LookupCondition::TxAccessList,
+            LookupCondition::TxBlobHashes,
+            LookupCondition::TxAuthorizations,
         ]
         .into_iter()
         .map(|condition| (condition, meta.advice_column()))
@@ -563,6 +635,13 @@ impl<F: Field> SubCircuitConfig<F> for TxCircuitConfig<F> {
         let is_access_list_storage_key = meta.advice_column();
         let field_rlc = meta.advice_column_in(SecondPhase);
 
+        // blob versioned hash columns
+        let is_blob_hash = meta.advice_column();
+
+        // authorization columns
+        let is_authorization = meta.advice_column();
+        let is_tag_authorization_s = meta.advice_column();
+
         // Chunk bytes accumulator
         let is_chunk_bytes = meta.advice_column();
         let chunk_bytes_len = meta.advice_column();
@@ -635,26 +714,29 @@ impl<F: Field> SubCircuitConfig<F> for TxCircuitConfig<F> {
         is_tx_tag!(is_tag_access_list_storage_key, AccessListStorageKey);
         is_tx_tag!(is_max_fee_per_gas, MaxFeePerGas);
         is_tx_tag!(is_max_priority_fee_per_gas, MaxPriorityFeePerGas);
-
-        // testing if value is zero for tags
+        is_tx_tag!(is_max_fee_per_blob_gas, MaxFeePerBlobGas);
+        is_tx_tag!(is_blob_hashes_len, BlobVersionedHashesLen);
+        is_tx_tag!(is_authorization_list_len, AuthorizationListLen);
+        is_tx_tag!(is_tag_blob_hash, BlobVersionedHash);
+        is_tx_tag!(is_auth_chain_id, AuthorizationChainId);
+        is_tx_tag!(is_auth_address, AuthorizationAddress);
+        is_tx_tag!(is_auth_nonce, AuthorizationNonce);
+        is_tx_tag!(is_auth_y_parity, AuthorizationYParity);
+        is_tx_tag!(is_auth_r, AuthorizationR);
+        is_tx_tag!(is_auth_s, AuthorizationS);
+
+        // testing if value is zero for tags. It is enabled on all rows (and the witness is
+        // assigned on all rows) to keep the degree low. It is used by these tags:
+        // - if caller_address is zero, then skip the sig verify.
+        // - if call_data_length is zero, then skip lookup to tx table for call data
+        // - if call data byte is zero, then gas_cost = 4 (16 otherwise)
+        // - if access_list_addresses_len is zero, then access_list_storage_keys_len = 0 and
+        //   access_list_rlc = 0
+        // - if blob_versioned_hashes_len is zero, then skip lookup to tx table for blob hashes
+        // - if authorization_list_len is zero, then skip lookup to tx table for authorizations
         let value_is_zero = IsZeroChip::configure(
             meta,
-            |meta| {
-                and::expr(vec![
-                    meta.query_fixed(q_enable, Rotation::cur()),
-                    sum::expr(vec![
-                        // if caller_address is zero, then skip the sig verify.
-                        is_caller_addr(meta),
-                        // if call_data_length is zero, then skip lookup to tx table for call data
-                        is_data_length(meta),
-                        // if call data byte is zero, then gas_cost = 4 (16 otherwise)
-                        is_data(meta),
-                        // if access_list_addresses_len is zero, then access_list_storage_keys_len
-                        // = 0 and access_list_rlc = 0
-                        is_access_list_addresses_len(meta),
-                    ]),
-                ])
-            },
+            |meta| meta.query_fixed(q_enable, Rotation::cur()),
             tx_table.value,
             |meta| meta.advice_column_in(SecondPhase), // value is at 2nd phase
         );
@@ -677,7 +759,7 @@ impl<F: Field> SubCircuitConfig<F> for TxCircuitConfig<F> {
             let mut cb = BaseConstraintBuilder::default();
 
             // if tag_next == Nonce, then tx_id' = tx_id + 1
-            cb.condition(tag_bits.value_equals(Nonce, Rotation::next())(meta), |cb| {
+            cb.condition(meta.query_advice(is_tag_nonce, Rotation::next()), |cb| {
                 cb.require_equal(
                     "tx_id increments",
                     meta.query_advice(tx_table.tx_id, Rotation::next()),
@@ -686,7 +768,7 @@ impl<F: Field> SubCircuitConfig<F> for TxCircuitConfig<F> {
             });
             // if tag_next != Nonce, then tx_id' = tx_id, tx_type' = tx_type
             cb.condition(
-                not::expr(tag_bits.value_equals(Nonce, Rotation::next())(meta)),
+                not::expr(meta.query_advice(is_tag_nonce, Rotation::next())),
                 |cb| {
                     cb.require_equal(
                         "tx_id does not change",
@@ -709,7 +791,7 @@ impl<F: Field> SubCircuitConfig<F> for TxCircuitConfig<F> {
 
                         // these do not need to spread out as they are related to tx_table.tag
                         // (which is fixed col) is_chain_id,
-                        // is_caller_address, is_tag_block_num, is_calldata
+                        // is_caller_address, is_tag_block_num, is_tag_nonce, is_calldata
                     ];
                     for (col_name, meta_info) in tx_meta_info_fields {
                         cb.require_equal(
@@ -724,10 +806,14 @@ impl<F: Field> SubCircuitConfig<F> for TxCircuitConfig<F> {
             let is_tag_dynamic = sum::expr([
                 meta.query_advice(is_calldata, Rotation::cur()),
                 meta.query_advice(is_access_list, Rotation::cur()),
+                meta.query_advice(is_blob_hash, Rotation::cur()),
+                meta.query_advice(is_authorization, Rotation::cur()),
             ]);
             let is_next_tag_dynamic = sum::expr([
                 meta.query_advice(is_calldata, Rotation::next()),
                 meta.query_advice(is_access_list, Rotation::next()),
+                meta.query_advice(is_blob_hash, Rotation::next()),
+                meta.query_advice(is_authorization, Rotation::next()),
             ]);
             cb.gate(and::expr([
                 meta.query_fixed(q_enable, Rotation::cur()),
@@ -760,6 +846,8 @@ impl<F: Field> SubCircuitConfig<F> for TxCircuitConfig<F> {
                     sum::expr([
                         meta.query_advice(is_calldata, Rotation::cur()),
                         meta.query_advice(is_access_list, Rotation::cur()),
+                        meta.query_advice(is_blob_hash, Rotation::cur()),
+                        meta.query_advice(is_authorization, Rotation::cur()),
                     ]),
                     meta.query_advice(is_final, Rotation::cur()),
                     1.expr(),
@@ -805,6 +893,15 @@ impl<F: Field> SubCircuitConfig<F> for TxCircuitConfig<F> {
                     is_max_priority_fee_per_gas(meta),
                     Tag::MaxPriorityFeePerGas.into(),
                 ),
+                (is_max_fee_per_blob_gas(meta), Tag::MaxFeePerBlobGas.into()),
+                (is_tag_blob_hash(meta), Tag::BlobVersionedHash.into()),
+                (is_auth_chain_id(meta), Tag::AuthChainId.into()),
+                (is_auth_address(meta), Tag::AuthAddress.into()),
+                (is_auth_nonce(meta), Tag::AuthNonce.into()),
+                // the signature of an authorization reuses the tags of the tx signature
+                (is_auth_y_parity(meta), Tag::SigV.into()),
+                (is_auth_r(meta), Tag::SigR.into()),
+                (is_auth_s(meta), Tag::SigS.into()),
                 // tx tags which correspond to Null
                 (is_null(meta), Null),
                 (is_create(meta), Null),
@@ -819,6 +916,8 @@ impl<F: Field> SubCircuitConfig<F> for TxCircuitConfig<F> {
                 (is_access_list_addresses_len(meta), Null),
                 (is_access_list_storage_keys_len(meta), Null),
                 (is_access_list_rlc(meta), RLC),
+                (is_blob_hashes_len(meta), Null),
+                (is_authorization_list_len(meta), Null),
             ];
 
             cb.require_boolean(
@@ -835,6 +934,8 @@ impl<F: Field> SubCircuitConfig<F> for TxCircuitConfig<F> {
                     usize::from(L1Msg).expr(),
                     usize::from(Eip2930).expr(),
                     usize::from(Eip1559).expr(),
+                    usize::from(Eip4844).expr(),
+                    usize::from(Eip7702).expr(),
                 ],
             );
 
@@ -893,12 +994,17 @@ impl<F: Field> SubCircuitConfig<F> for TxCircuitConfig<F> {
             });
 
             // CallData is not none => CallDataLength != 0
+            // (the calldata lookup condition on the CallDataLength row is 1 iff its value != 0)
             cb.condition(
                 and::expr([is_data_rlc(meta), not::expr(is_none_expr)]),
                 |cb| {
-                    cb.require_zero(
+                    cb.require_equal(
                         "CallDataLength != 0",
-                        value_is_zero.expr(Rotation::next())(meta),
+                        meta.query_advice(
+                            lookup_conditions[&LookupCondition::TxCalldata],
+                            Rotation::next(),
+                        ),
+                        1.expr(),
                     );
                 },
             );
@@ -924,6 +1030,34 @@ impl<F: Field> SubCircuitConfig<F> for TxCircuitConfig<F> {
                 },
             );
 
+            // Only EIP-4844 txs have max_fee_per_blob_gas and blob_versioned_hashes
+            cb.condition(
+                and::expr([
+                    not::expr(meta.query_advice(is_eip4844, Rotation::cur())),
+                    sum::expr([is_max_fee_per_blob_gas(meta), is_blob_hashes_len(meta)]),
+                ]),
+                |cb| {
+                    cb.require_zero(
+                        "MaxFeePerBlobGas = 0 and BlobVersionedHashesLen = 0 for non-EIP4844 tx",
+                        meta.query_advice(tx_table.value, Rotation::cur()),
+                    );
+                },
+            );
+
+            // Only EIP-7702 txs have authorizations, and they must have at least one
+            cb.condition(is_authorization_list_len(meta), |cb| {
+                cb.require_zero(
+                    "AuthorizationListLen = 0 for non-EIP7702 tx",
+                    (1.expr() - meta.query_advice(is_eip7702, Rotation::cur()))
+                        * meta.query_advice(tx_table.value, Rotation::cur()),
+                );
+                cb.require_zero(
+                    "AuthorizationListLen != 0 for EIP7702 tx",
+                    meta.query_advice(is_eip7702, Rotation::cur())
+                        * value_is_zero.expr(Rotation::cur())(meta),
+                );
+            });
+
             cb.gate(meta.query_fixed(q_enable, Rotation::cur()))
         });
 
@@ -1018,6 +1152,70 @@ impl<F: Field> SubCircuitConfig<F> for TxCircuitConfig<F> {
             },
         );
 
+        meta.create_gate("is_blob_hash", |meta| {
+            let mut cb = BaseConstraintBuilder::default();
+
+            cb.require_equal(
+                "is_blob_hash",
+                is_tag_blob_hash(meta),
+                meta.query_advice(is_blob_hash, Rotation::cur()),
+            );
+
+            // Ensure continuity of is_blob_hash when is_final is false
+            cb.condition(
+                and::expr([
+                    meta.query_advice(is_blob_hash, Rotation::cur()),
+                    not::expr(meta.query_advice(is_final, Rotation::cur())),
+                ]),
+                |cb| {
+                    cb.require_zero(
+                        "is_blob_hash is continuous when is_final is false",
+                        meta.query_advice(is_blob_hash, Rotation::next()) - 1.expr(),
+                    )
+                },
+            );
+
+            cb.gate(meta.query_fixed(q_enable, Rotation::cur()))
+        });
+
+        meta.create_gate("is_authorization", |meta| {
+            let mut cb = BaseConstraintBuilder::default();
+
+            cb.require_equal(
+                "is_authorization",
+                sum::expr([
+                    is_auth_chain_id(meta),
+                    is_auth_address(meta),
+                    is_auth_nonce(meta),
+                    is_auth_y_parity(meta),
+                    is_auth_r(meta),
+                    is_auth_s(meta),
+                ]),
+                meta.query_advice(is_authorization, Rotation::cur()),
+            );
+            cb.require_equal(
+                "is_tag_authorization_s",
+                is_auth_s(meta),
+                meta.query_advice(is_tag_authorization_s, Rotation::cur()),
+            );
+
+            // Ensure continuity of is_authorization when is_final is false
+            cb.condition(
+                and::expr([
+                    meta.query_advice(is_authorization, Rotation::cur()),
+                    not::expr(meta.query_advice(is_final, Rotation::cur())),
+                ]),
+                |cb| {
+                    cb.require_zero(
+                        "is_authorization is continuous when is_final is false",
+                        meta.query_advice(is_authorization, Rotation::next()) - 1.expr(),
+                    )
+                },
+            );
+
+            cb.gate(meta.query_fixed(q_enable, Rotation::cur()))
+        });
+
         meta.create_gate("is_caller_address", |meta| {
             let mut cb = BaseConstraintBuilder::default();
 
@@ -1066,8 +1264,20 @@ impl<F: Field> SubCircuitConfig<F> for TxCircuitConfig<F> {
             cb.gate(meta.query_fixed(q_enable, Rotation::cur()))
         });
 
+        meta.create_gate("is_tag_nonce", |meta| {
+            let mut cb = BaseConstraintBuilder::default();
+
+            cb.require_equal(
+                "is_tag_nonce = (tag == Nonce)",
+                is_nonce(meta),
+                meta.query_advice(is_tag_nonce, Rotation::cur()),
+            );
+
+            cb.gate(meta.query_fixed(q_enable, Rotation::cur()))
+        });
+
         meta.create_gate(
-            "distinguish tx type: is_l1_msg, is_eip2930, is_eip1559",
+            "distinguish tx type: is_l1_msg, is_eip2930, is_eip1559, is_eip4844, is_eip7702",
             |meta| {
                 let mut cb = BaseConstraintBuilder::default();
 
@@ -1089,6 +1299,18 @@ impl<F: Field> SubCircuitConfig<F> for TxCircuitConfig<F> {
                     tx_type_bits.value_equals(Eip1559, Rotation::cur())(meta),
                 );
 
+                cb.require_equal(
+                    "is_eip4844 = (tx_type == Eip4844)",
+                    meta.query_advice(is_eip4844, Rotation::cur()),
+                    tx_type_bits.value_equals(Eip4844, Rotation::cur())(meta),
+                );
+
+                cb.require_equal(
+                    "is_eip7702 = (tx_type == Eip7702)",
+                    meta.query_advice(is_eip7702, Rotation::cur()),
+                    tx_type_bits.value_equals(Eip7702, Rotation::cur())(meta),
+                );
+
                 cb.gate(meta.query_fixed(q_enable, Rotation::cur()))
             },
         );
@@ -1129,13 +1351,56 @@ impl<F: Field> SubCircuitConfig<F> for TxCircuitConfig<F> {
             cb.gate(meta.query_fixed(q_enable, Rotation::cur()))
         });
 
+        meta.create_gate("lookup to blob hashes dynamic section condition", |meta| {
+            let mut cb = BaseConstraintBuilder::default();
+
+            cb.require_equal(
+                "condition",
+                and::expr([
+                    is_blob_hashes_len(meta),
+                    not::expr(value_is_zero.expr(Rotation::cur())(meta)),
+                ]),
+                meta.query_advice(
+                    lookup_conditions[&LookupCondition::TxBlobHashes],
+                    Rotation::cur(),
+                ),
+            );
+
+            cb.gate(meta.query_fixed(q_enable, Rotation::cur()))
+        });
+
+        meta.create_gate(
+            "lookup to authorizations dynamic section condition",
+            |meta| {
+                let mut cb = BaseConstraintBuilder::default();
+
+                cb.require_equal(
+                    "condition",
+                    and::expr([
+                        is_authorization_list_len(meta),
+                        not::expr(value_is_zero.expr(Rotation::cur())(meta)),
+                    ]),
+                    meta.query_advice(
+                        lookup_conditions[&LookupCondition::TxAuthorizations],
+                        Rotation::cur(),
+                    ),
+                );
+
+                cb.gate(meta.query_fixed(q_enable, Rotation::cur()))
+            },
+        );
+
         meta.create_gate("sign tag lookup into RLP table condition", |meta| {
             let mut cb = BaseConstraintBuilder::default();
 
             let is_tag_in_tx_sign = sum::expr([
                 is_nonce(meta),
                 and::expr([
-                    not::expr(meta.query_advice(is_eip1559, Rotation::cur())),
+                    not::expr(sum::expr([
+                        meta.query_advice(is_eip1559, Rotation::cur()),
+                        meta.query_advice(is_eip4844, Rotation::cur()),
+                        meta.query_advice(is_eip7702, Rotation::cur()),
+                    ])),
                     is_gas_price(meta),
                 ]),
                 is_gas(meta),
@@ -1148,16 +1413,30 @@ impl<F: Field> SubCircuitConfig<F> for TxCircuitConfig<F> {
                         tx_type_bits.value_equals(Eip155, Rotation::cur())(meta),
                         meta.query_advice(is_eip2930, Rotation::cur()),
                         meta.query_advice(is_eip1559, Rotation::cur()),
+                        meta.query_advice(is_eip4844, Rotation::cur()),
+                        meta.query_advice(is_eip7702, Rotation::cur()),
                     ]),
                 ]),
                 and::expr([
-                    meta.query_advice(is_eip1559, Rotation::cur()),
+                    sum::expr([
+                        meta.query_advice(is_eip1559, Rotation::cur()),
+                        meta.query_advice(is_eip4844, Rotation::cur()),
+                        meta.query_advice(is_eip7702, Rotation::cur()),
+                    ]),
                     is_max_fee_per_gas(meta),
                 ]),
                 and::expr([
-                    meta.query_advice(is_eip1559, Rotation::cur()),
+                    sum::expr([
+                        meta.query_advice(is_eip1559, Rotation::cur()),
+                        meta.query_advice(is_eip4844, Rotation::cur()),
+                        meta.query_advice(is_eip7702, Rotation::cur()),
+                    ]),
                     is_max_priority_fee_per_gas(meta),
                 ]),
+                and::expr([
+                    meta.query_advice(is_eip4844, Rotation::cur()),
+                    is_max_fee_per_blob_gas(meta),
+                ]),
                 is_sign_length(meta),
                 is_sign_rlc(meta),
             ]);
@@ -1183,7 +1462,11 @@ impl<F: Field> SubCircuitConfig<F> for TxCircuitConfig<F> {
             let is_tag_in_tx_hash = sum::expr([
                 is_nonce(meta),
                 and::expr([
-                    not::expr(meta.query_advice(is_eip1559, Rotation::cur())),
+                    not::expr(sum::expr([
+                        meta.query_advice(is_eip1559, Rotation::cur()),
+                        meta.query_advice(is_eip4844, Rotation::cur()),
+                        meta.query_advice(is_eip7702, Rotation::cur()),
+                    ])),
                     is_gas_price(meta),
                 ]),
                 is_gas(meta),
@@ -1197,13 +1480,25 @@ impl<F: Field> SubCircuitConfig<F> for TxCircuitConfig<F> {
                 is_hash_length(meta),
                 is_hash_rlc(meta),
                 and::expr([
-                    meta.query_advice(is_eip1559, Rotation::cur()),
+                    sum::expr([
+                        meta.query_advice(is_eip1559, Rotation::cur()),
+                        meta.query_advice(is_eip4844, Rotation::cur()),
+                        meta.query_advice(is_eip7702, Rotation::cur()),
+                    ]),
                     is_max_fee_per_gas(meta),
                 ]),
                 and::expr([
-                    meta.query_advice(is_eip1559, Rotation::cur()),
+                    sum::expr([
+                        meta.query_advice(is_eip1559, Rotation::cur()),
+                        meta.query_advice(is_eip4844, Rotation::cur()),
+                        meta.query_advice(is_eip7702, Rotation::cur()),
+                    ]),
                     is_max_priority_fee_per_gas(meta),
                 ]),
+                and::expr([
+                    meta.query_advice(is_eip4844, Rotation::cur()),
+                    is_max_fee_per_blob_gas(meta),
+                ]),
             ]);
 
             cb.require_equal(
@@ -1301,6 +1596,8 @@ impl<F: Field> SubCircuitConfig<F> for TxCircuitConfig<F> {
             is_l1_msg,
             is_eip2930,
             is_eip1559,
+            is_eip4844,
+            is_eip7702,
             sv_address,
             calldata_gas_cost_acc,
             section_rlc,
@@ -1312,6 +1609,9 @@ impl<F: Field> SubCircuitConfig<F> for TxCircuitConfig<F> {
             is_access_list,
             is_access_list_address,
             is_access_list_storage_key,
+            is_blob_hash,
+            is_authorization,
+            is_tag_authorization_s,
             al_idx,
             sk_idx,
             sks_acc,
@@ -1388,6 +1688,8 @@ impl<F: Field> SubCircuitConfig<F> for TxCircuitConfig<F> {
             let is_next_tag_dynamic = sum::expr([
                 meta.query_advice(is_calldata, Rotation::next()),
                 meta.query_advice(is_access_list, Rotation::next()),
+                meta.query_advice(is_blob_hash, Rotation::next()),
+                meta.query_advice(is_authorization, Rotation::next()),
             ]);
 
             let lookup_condition = and::expr([
@@ -1411,10 +1713,14 @@ impl<F: Field> SubCircuitConfig<F> for TxCircuitConfig<F> {
             let is_tag_dynamic = sum::expr([
                 meta.query_advice(is_calldata, Rotation::cur()),
                 meta.query_advice(is_access_list, Rotation::cur()),
+                meta.query_advice(is_blob_hash, Rotation::cur()),
+                meta.query_advice(is_authorization, Rotation::cur()),
             ]);
             let is_next_tag_dynamic = sum::expr([
                 meta.query_advice(is_calldata, Rotation::next()),
                 meta.query_advice(is_access_list, Rotation::next()),
+                meta.query_advice(is_blob_hash, Rotation::next()),
+                meta.query_advice(is_authorization, Rotation::next()),
             ]);
 
             // first tx in tx table
@@ -1570,6 +1876,8 @@ impl<F: Field> SubCircuitConfig<F> for TxCircuitConfig<F> {
                     not::expr(sum::expr([
                         meta.query_advice(is_calldata, Rotation::next()),
                         meta.query_advice(is_access_list, Rotation::next()),
+                        meta.query_advice(is_blob_hash, Rotation::next()),
+                        meta.query_advice(is_authorization, Rotation::next()),
                     ])),
                 ]),
                 |cb| {
@@ -1636,7 +1944,7 @@ impl<F: Field> SubCircuitConfig<F> for TxCircuitConfig<F> {
             let mut cb = BaseConstraintBuilder::default();
 
             let (lt_expr, eq_expr) = tx_id_cmp_cum_num_txs.expr(meta);
-            cb.condition(is_block_num(meta), |cb| {
+            cb.condition(meta.query_advice(is_tag_block_num, Rotation::cur()), |cb| {
                 cb.require_equal("lt or eq", sum::expr([lt_expr, eq_expr]), true.expr());
             });
 
@@ -1761,11 +2069,16 @@ impl<F: Field> SubCircuitConfig<F> for TxCircuitConfig<F> {
                 );
             });
 
-            // on the final call data byte, if there's no access list, tx_id must change.
+            // on the final call data byte, if there's no access list or blob versioned hashes,
+            // tx_id must change.
             cb.condition(
                 and::expr([
                     is_final_cur.expr(),
-                    not::expr(meta.query_advice(is_access_list, Rotation::next())),
+                    not::expr(sum::expr([
+                        meta.query_advice(is_access_list, Rotation::next()),
+                        meta.query_advice(is_blob_hash, Rotation::next()),
+                        meta.query_advice(is_authorization, Rotation::next()),
+                    ])),
                 ]),
                 |cb| {
                     cb.require_zero(
@@ -1839,6 +2152,38 @@ impl<F: Field> SubCircuitConfig<F> for TxCircuitConfig<F> {
             ]))
         });
 
+        meta.create_gate("Dynamic section init with blob versioned hashes", |meta| {
+            let mut cb = BaseConstraintBuilder::default();
+
+            cb.require_equal(
+                "index starts with 1",
+                meta.query_advice(tx_table.index, Rotation::cur()),
+                1.expr(),
+            );
+
+            cb.gate(and::expr([
+                meta.query_fixed(q_dynamic_first, Rotation::cur()),
+                not::expr(tx_id_is_zero.expr(Rotation::cur())(meta)),
+                meta.query_advice(is_blob_hash, Rotation::cur()),
+            ]))
+        });
+
+        meta.create_gate("Dynamic section init with authorizations", |meta| {
+            let mut cb = BaseConstraintBuilder::default();
+
+            cb.require_equal(
+                "index starts with 1",
+                meta.query_advice(tx_table.index, Rotation::cur()),
+                1.expr(),
+            );
+
+            cb.gate(and::expr([
+                meta.query_fixed(q_dynamic_first, Rotation::cur()),
+                not::expr(tx_id_is_zero.expr(Rotation::cur())(meta)),
+                meta.query_advice(is_authorization, Rotation::cur()),
+            ]))
+        });
+
         meta.create_gate("Dynamic section transitions", |meta| {
             let mut cb = BaseConstraintBuilder::default();
             let is_final_cur = meta.query_advice(is_final, Rotation::cur());
@@ -1886,11 +2231,34 @@ impl<F: Field> SubCircuitConfig<F> for TxCircuitConfig<F> {
                 );
             });
 
+            // Dynamic section transition #3: into blob versioned hashes
+            cb.condition(meta.query_advice(is_blob_hash, Rotation::next()), |cb| {
+                cb.require_equal(
+                    "index' starts with 1",
+                    meta.query_advice(tx_table.index, Rotation::next()),
+                    1.expr(),
+                );
+            });
+
+            // Dynamic section transition #4: into authorizations
+            cb.condition(
+                meta.query_advice(is_authorization, Rotation::next()),
+                |cb| {
+                    cb.require_equal(
+                        "index' starts with 1",
+                        meta.query_advice(tx_table.index, Rotation::next()),
+                        1.expr(),
+                    );
+                },
+            );
+
             cb.gate(and::expr([
                 meta.query_fixed(q_enable, Rotation::cur()),
                 sum::expr([
                     meta.query_advice(is_access_list, Rotation::cur()),
                     meta.query_advice(is_calldata, Rotation::cur()),
+                    meta.query_advice(is_blob_hash, Rotation::cur()),
+                    meta.query_advice(is_authorization, Rotation::cur()),
                 ]),
                 not::expr(meta.query_advice(is_tx_id_zero, Rotation::cur())),
                 not::expr(meta.query_advice(is_tx_id_zero, Rotation::next())),
@@ -2049,11 +2417,14 @@ impl<F: Field> SubCircuitConfig<F> for TxCircuitConfig<F> {
                 },
             );
 
-            // When is_final_cur is true, the tx_id must change for the next dynamic section
+            // When is_final_cur is true, the tx_id must change for the next dynamic section,
+            // unless the tx's blob versioned hashes follow.
             cb.condition(
                 and::expr([
                     is_final_cur.clone(),
-                    not::expr(tx_id_is_zero.expr(Rotation::next())(meta)),
+                    not::expr(meta.query_advice(is_tx_id_zero, Rotation::next())),
+                    not::expr(meta.query_advice(is_blob_hash, Rotation::next())),
+                    not::expr(meta.query_advice(is_authorization, Rotation::next())),
                 ]),
                 |cb| {
                     cb.require_zero(
@@ -2071,35 +2442,130 @@ impl<F: Field> SubCircuitConfig<F> for TxCircuitConfig<F> {
         });
 
         ////////////////////////////////////////////////////////////////////////
-        ///////////   SignVerify recover CallerAddress    //////////////////////
+        ////////  Blob Versioned Hashes Constraints (EIP-4844 tx only)  ////////
         ////////////////////////////////////////////////////////////////////////
-        meta.create_gate("tx signature v", |meta| {
+        // Note: the number of hashes is bound to BlobVersionedHashesLen by the lookups below, and
+        // each hash is bound to the RLP table by its index. Unlike the access list there is no
+        // RLC over the whole section.
+        meta.create_gate("tx blob versioned hashes", |meta| {
             let mut cb = BaseConstraintBuilder::default();
-            let is_chain_id = meta.query_advice(is_chain_id, Rotation::cur());
 
-            //  1. eip155 tx: v Є {chain_id*2 + 35, chain_id*2 + 36}
+            let is_final_cur = meta.query_advice(is_final, Rotation::cur());
+            cb.require_boolean("is_final is boolean", is_final_cur.clone());
+
+            // checks for any row, except the final blob versioned hash.
+            cb.condition(not::expr(is_final_cur.clone()), |cb| {
+                cb.require_equal(
+                    "index::next == index::cur + 1",
+                    meta.query_advice(tx_table.index, Rotation::next()),
+                    meta.query_advice(tx_table.index, Rotation::cur()) + 1.expr(),
+                );
+                cb.require_equal(
+                    "tx_id::next == tx_id::cur",
+                    tx_id_unchanged.is_equal_expression.clone(),
+                    1.expr(),
+                );
+            });
+
+            // blob versioned hashes are the last part of the tx's dynamic section.
             cb.condition(
                 and::expr([
-                    is_chain_id.expr(),
-                    tx_type_bits.value_equals(Eip155, Rotation::cur())(meta),
+                    is_final_cur,
+                    not::expr(meta.query_advice(is_tx_id_zero, Rotation::next())),
                 ]),
                 |cb| {
-                    // we rely on the assumption that SigV is on the next of ChainID
-                    let v = meta.query_advice(tx_table.value, Rotation::next());
-                    let chain_id = meta.query_advice(tx_table.value, Rotation::cur());
-
-                    cb.require_boolean(
-                        "V - (chain_id * 2 + 35) Є {0, 1}",
-                        v - chain_id * 2.expr() - 35.expr(),
+                    cb.require_zero(
+                        "tx_id changes at is_final == 1",
+                        tx_id_unchanged.is_equal_expression.clone(),
                     );
                 },
             );
 
-            //  2. pre-eip155 tx: v Є {27, 28}
+            cb.gate(and::expr(vec![
+                meta.query_fixed(q_enable, Rotation::cur()),
+                meta.query_advice(is_blob_hash, Rotation::cur()),
+                not::expr(meta.query_advice(is_tx_id_zero, Rotation::cur())),
+            ]))
+        });
+
+        ////////////////////////////////////////////////////////////////////////
+        ////////////  Authorization Constraints (EIP-7702 tx only)  ////////////
+        ////////////////////////////////////////////////////////////////////////
+        // Each authorization tuple takes six rows sharing the same index. The number of tuples is
+        // bound to AuthorizationListLen by the lookups below, and each field is bound to the RLP
+        // table by its index.
+        meta.create_gate("tx authorizations", |meta| {
+            let mut cb = BaseConstraintBuilder::default();
+
+            let is_final_cur = meta.query_advice(is_final, Rotation::cur());
+            cb.require_boolean("is_final is boolean", is_final_cur.clone());
+
+            // checks for any row, except the final field of the last tuple.
+            cb.condition(not::expr(is_final_cur.clone()), |cb| {
+                cb.require_equal(
+                    "index::next == index::cur + is_tag_authorization_s",
+                    meta.query_advice(tx_table.index, Rotation::next()),
+                    meta.query_advice(tx_table.index, Rotation::cur())
+                        + meta.query_advice(is_tag_authorization_s, Rotation::cur()),
+                );
+                cb.require_equal(
+                    "tx_id::next == tx_id::cur",
+                    tx_id_unchanged.is_equal_expression.clone(),
+                    1.expr(),
+                );
+            });
+
+            // authorizations are the last part of the tx's dynamic section.
             cb.condition(
                 and::expr([
-                    is_chain_id.expr(),
-                    tx_type_bits.value_equals(PreEip155, Rotation::cur())(meta),
+                    is_final_cur,
+                    not::expr(meta.query_advice(is_tx_id_zero, Rotation::next())),
+                ]),
+                |cb| {
+                    cb.require_zero(
+                        "tx_id changes at is_final == 1",
+                        tx_id_unchanged.is_equal_expression.clone(),
+                    );
+                },
+            );
+
+            cb.gate(and::expr(vec![
+                meta.query_fixed(q_enable, Rotation::cur()),
+                meta.query_advice(is_authorization, Rotation::cur()),
+                not::expr(meta.query_advice(is_tx_id_zero, Rotation::cur())),
+            ]))
+        });
+
+        ////////////////////////////////////////////////////////////////////////
+        ///////////   SignVerify recover CallerAddress    //////////////////////
+        ////////////////////////////////////////////////////////////////////////
+        meta.create_gate("tx signature v", |meta| {
+            let mut cb = BaseConstraintBuilder::default();
+            let is_chain_id = meta.query_advice(is_chain_id, Rotation::cur());
+
+            //  1. eip155 tx: v Є {chain_id*2 + 35, chain_id*2 + 36}
+            cb.condition(
+                and::expr([
+                    is_chain_id.expr(),
+                    tx_type_bits.value_equals(Eip155, Rotation::cur())(meta),
+                ]),
+                |cb| {
+                    // we rely on the assumption that SigV is on the next of ChainID
+                    let v = meta.query_advice(tx_table.value, Rotation::next());
+                    let chain_id = meta.query_advice(tx_table.value, Rotation::cur());
+
+                    cb.require_boolean(
+                        "V - (chain_id * 2 + 35) Є {0, 1}",
+                        v - chain_id * 2.expr() - 35.expr(),
+                    );
+                },
+            );
+
+            //  2. pre-eip155 tx: v Є {27, 28}
+            cb.condition(
+                and::expr([
+                    is_chain_id.expr(),
+                    tx_type_bits.value_equals(PreEip155, Rotation::cur())(meta),
                 ]),
                 |cb| {
                     let v = meta.query_advice(tx_table.value, Rotation::next());
@@ -2119,13 +2585,15 @@ impl<F: Field> SubCircuitConfig<F> for TxCircuitConfig<F> {
                 },
             );
 
-            // 4. EPI1559/2930: v Є {0, 1}
+            // 4. EPI1559/2930/4844/7702: v Є {0, 1}
             cb.condition(
                 and::expr([
                     is_chain_id.expr(),
                     sum::expr([
                         tx_type_bits.value_equals(Eip1559, Rotation::cur())(meta),
                         tx_type_bits.value_equals(Eip2930, Rotation::cur())(meta),
+                        tx_type_bits.value_equals(Eip4844, Rotation::cur())(meta),
+                        tx_type_bits.value_equals(Eip7702, Rotation::cur())(meta),
                     ]),
                 ]),
                 |cb| {
@@ -2178,6 +2646,8 @@ impl<F: Field> SubCircuitConfig<F> for TxCircuitConfig<F> {
                 not::expr(meta.query_fixed(q_first, Rotation::cur())),
                 not::expr(meta.query_advice(is_calldata, Rotation::cur())),
                 not::expr(meta.query_advice(is_access_list, Rotation::cur())),
+                not::expr(meta.query_advice(is_blob_hash, Rotation::cur())),
+                not::expr(meta.query_advice(is_authorization, Rotation::cur())),
             ]))
         });
 
@@ -2231,7 +2701,7 @@ impl<F: Field> SubCircuitConfig<F> for TxCircuitConfig<F> {
                 meta.query_fixed(q_enable, Rotation::cur()),
                 // Only l2 signed bytes are accumulated
                 meta.query_advice(is_chunk_bytes, Rotation::cur()),
-                is_hash_rlc(meta),
+                meta.query_advice(is_row_hash_rlc, Rotation::cur()),
             ]))
         });
 
@@ -2257,7 +2727,7 @@ impl<F: Field> SubCircuitConfig<F> for TxCircuitConfig<F> {
                 cb.gate(and::expr([
                     meta.query_fixed(q_enable, Rotation::cur()),
                     not::expr(meta.query_advice(is_chunk_bytes, Rotation::cur())),
-                    is_hash_rlc(meta),
+                    meta.query_advice(is_row_hash_rlc, Rotation::cur()),
                 ]))
             },
         );
@@ -2289,6 +2759,8 @@ impl<F: Field> SubCircuitConfig<F> for TxCircuitConfig<F> {
             let is_tag_dynamic = sum::expr([
                 meta.query_advice(is_calldata, Rotation::cur()),
                 meta.query_advice(is_access_list, Rotation::cur()),
+                meta.query_advice(is_blob_hash, Rotation::cur()),
+                meta.query_advice(is_authorization, Rotation::cur()),
             ]);
 
             // chunk_txbytes_len_acc, chunk_txbytes_rlc and pow_of_rand stay the same for the same tx
@@ -2311,7 +2783,7 @@ impl<F: Field> SubCircuitConfig<F> for TxCircuitConfig<F> {
             cb.gate(and::expr([
                 meta.query_fixed(q_enable, Rotation::cur()),
                 not::expr(meta.query_fixed(q_first, Rotation::cur())),
-                not::expr(is_nonce(meta)),
+                not::expr(meta.query_advice(is_tag_nonce, Rotation::cur())),
                 // we're in the fixed section
                 not::expr(is_tag_dynamic),
             ]))
@@ -2351,6 +2823,8 @@ impl<F: Field> SubCircuitConfig<F> for TxCircuitConfig<F> {
             is_l1_msg,
             is_eip2930,
             is_eip1559,
+            is_eip4844,
+            is_eip7702,
             is_row_hash_rlc,
             is_chain_id,
             is_final,
@@ -2365,12 +2839,16 @@ impl<F: Field> SubCircuitConfig<F> for TxCircuitConfig<F> {
             rlp_table,
             pow_of_rand_table,
             is_tag_block_num,
+            is_tag_nonce,
             al_idx,
             sk_idx,
             sks_acc,
             is_access_list,
             is_access_list_address,
             is_access_list_storage_key,
+            is_blob_hash,
+            is_authorization,
+            is_tag_authorization_s,
             field_rlc,
             is_chunk_bytes,
             chunk_bytes_len,
@@ -2404,6 +2882,8 @@ impl<F: Field> TxCircuitConfig<F> {
         is_l1_msg_col: Column<Advice>,
         is_eip2930: Column<Advice>,
         is_eip1559: Column<Advice>,
+        is_eip4844: Column<Advice>,
+        is_eip7702: Column<Advice>,
         sv_address: Column<Advice>,
         calldata_gas_cost_acc: Column<Advice>,
         section_rlc: Column<Advice>,
@@ -2415,6 +2895,9 @@ impl<F: Field> TxCircuitConfig<F> {
         is_access_list: Column<Advice>,
         is_access_list_address: Column<Advice>,
         is_access_list_storage_key: Column<Advice>,
+        is_blob_hash: Column<Advice>,
+        is_authorization: Column<Advice>,
+        is_tag_authorization_s: Column<Advice>,
         al_idx: Column<Advice>,
         sk_idx: Column<Advice>,
         sks_acc: Column<Advice>,
@@ -2628,6 +3111,116 @@ impl<F: Field> TxCircuitConfig<F> {
                 .collect()
         });
 
+        meta.lookup_any("lookup BlobVersionedHashesLen in the TxTable", |meta| {
+            let enable = and::expr([
+                meta.query_fixed(q_enable, Rotation::cur()),
+                meta.query_advice(is_blob_hash, Rotation::cur()),
+                meta.query_advice(is_final, Rotation::cur()),
+            ]);
+
+            let input_exprs = vec![
+                meta.query_advice(tx_table.tx_id, Rotation::cur()),
+                BlobVersionedHashesLen.expr(),
+                meta.query_advice(tx_table.index, Rotation::cur()),
+            ];
+            let table_exprs = vec![
+                meta.query_advice(tx_table.tx_id, Rotation::cur()),
+                meta.query_advice(tx_table.tag, Rotation::cur()),
+                meta.query_advice(tx_table.value, Rotation::cur()),
+            ];
+
+            input_exprs
+                .into_iter()
+                .zip(table_exprs)
+                .map(|(input, table)| (input * enable.expr(), table))
+                .collect()
+        });
+
+        meta.lookup_any(
+            "is_final blob versioned hash row should be present",
+            |meta| {
+                let enable = and::expr(vec![
+                    meta.query_fixed(q_enable, Rotation::cur()),
+                    meta.query_advice(
+                        lookup_conditions[&LookupCondition::TxBlobHashes],
+                        Rotation::cur(),
+                    ),
+                ]);
+                let input_exprs = vec![
+                    meta.query_advice(tx_table.tx_id, Rotation::cur()),
+                    1.expr(),
+                    1.expr(),
+                    meta.query_advice(tx_table.value, Rotation::cur()), /* blob versioned hashes
+                                                                         * len */
+                ];
+                let table_exprs = vec![
+                    meta.query_advice(tx_table.tx_id, Rotation::cur()),
+                    meta.query_advice(is_blob_hash, Rotation::cur()),
+                    meta.query_advice(is_final, Rotation::cur()),
+                    meta.query_advice(tx_table.index, Rotation::cur()),
+                ];
+
+                input_exprs
+                    .into_iter()
+                    .zip(table_exprs)
+                    .map(|(input, table)| (input * enable.expr(), table))
+                    .collect()
+            },
+        );
+
+        meta.lookup_any("lookup AuthorizationListLen in the TxTable", |meta| {
+            let enable = and::expr([
+                meta.query_fixed(q_enable, Rotation::cur()),
+                meta.query_advice(is_authorization, Rotation::cur()),
+                meta.query_advice(is_final, Rotation::cur()),
+            ]);
+
+            let input_exprs = vec![
+                meta.query_advice(tx_table.tx_id, Rotation::cur()),
+                AuthorizationListLen.expr(),
+                meta.query_advice(tx_table.index, Rotation::cur()),
+            ];
+            let table_exprs = vec![
+                meta.query_advice(tx_table.tx_id, Rotation::cur()),
+                meta.query_advice(tx_table.tag, Rotation::cur()),
+                meta.query_advice(tx_table.value, Rotation::cur()),
+            ];
+
+            input_exprs
+                .into_iter()
+                .zip(table_exprs)
+                .map(|(input, table)| (input * enable.expr(), table))
+                .collect()
+        });
+
+        meta.lookup_any("is_final authorization row should be present", |meta| {
+            let enable = and::expr(vec![
+                meta.query_fixed(q_enable, Rotation::cur()),
+                meta.query_advice(
+                    lookup_conditions[&LookupCondition::TxAuthorizations],
+                    Rotation::cur(),
+                ),
+            ]);
+            let input_exprs = vec![
+                meta.query_advice(tx_table.tx_id, Rotation::cur()),
+                1.expr(),
+                1.expr(),
+                meta.query_advice(tx_table.value, Rotation::cur()), // authorization list len
+            ];
+            let table_exprs = vec![
+                meta.query_advice(tx_table.tx_id, Rotation::cur()),
+                meta.query_advice(is_authorization, Rotation::cur()),
+                meta.query_advice(is_final, Rotation::cur()),
+                meta.query_advice(tx_table.index, Rotation::cur()),
+            ];
+
+            input_exprs
+                .into_iter()
+                .zip(table_exprs)
+                .map(|(input, table)| (input * enable.expr(), table))
+                .collect()
+        });
+
         /////////////////////////////////////////////////////////////////
         /////////////////    RLP table lookups     //////////////////////
         ///////////////// ////////////////////////////////////////////////
@@ -2679,7 +3272,9 @@ impl<F: Field> TxCircuitConfig<F> {
             let sign_format = is_pre_eip155(meta) * TxSignPreEip155.expr()
                 + is_eip155(meta) * TxSignEip155.expr()
                 + meta.query_advice(is_eip2930, Rotation::cur()) * TxSignEip2930.expr()
-                + meta.query_advice(is_eip1559, Rotation::cur()) * TxSignEip1559.expr();
+                + meta.query_advice(is_eip1559, Rotation::cur()) * TxSignEip1559.expr()
+                + meta.query_advice(is_eip4844, Rotation::cur()) * TxSignEip4844.expr()
+                + meta.query_advice(is_eip7702, Rotation::cur()) * TxSignEip7702.expr();
 
             // q_enable, tx_id, format, rlp_tag, tag_value, is_output, is_none
             vec![
@@ -2722,7 +3317,9 @@ impl<F: Field> TxCircuitConfig<F> {
                 + is_eip155(meta) * TxHashEip155.expr()
                 + is_l1_msg(meta) * L1MsgHash.expr()
                 + meta.query_advice(is_eip2930, Rotation::cur()) * TxHashEip2930.expr()
-                + meta.query_advice(is_eip1559, Rotation::cur()) * TxHashEip1559.expr();
+                + meta.query_advice(is_eip1559, Rotation::cur()) * TxHashEip1559.expr()
+                + meta.query_advice(is_eip4844, Rotation::cur()) * TxHashEip4844.expr()
+                + meta.query_advice(is_eip7702, Rotation::cur()) * TxHashEip7702.expr();
 
             vec![
                 1.expr(), // q_enable = true
@@ -2754,10 +3351,12 @@ impl<F: Field> TxCircuitConfig<F> {
                     meta.query_advice(is_access_list_address, Rotation::cur()),
                 ]);
 
-                // only eip2930 and eip1559 contains an access list
+                // only eip2930, eip1559, eip4844 and eip7702 contain an access list
                 let sign_format = meta.query_advice(is_eip2930, Rotation::cur())
                     * TxSignEip2930.expr()
-                    + meta.query_advice(is_eip1559, Rotation::cur()) * TxSignEip1559.expr();
+                    + meta.query_advice(is_eip1559, Rotation::cur()) * TxSignEip1559.expr()
+                    + meta.query_advice(is_eip4844, Rotation::cur()) * TxSignEip4844.expr()
+                    + meta.query_advice(is_eip7702, Rotation::cur()) * TxSignEip7702.expr();
 
                 vec![
                     1.expr(), // q_enable = true
@@ -2787,10 +3386,12 @@ impl<F: Field> TxCircuitConfig<F> {
                     meta.query_advice(is_access_list_address, Rotation::cur()),
                 ]);
 
-                // only eip2930 and eip1559 contains an access list
+                // only eip2930, eip1559, eip4844 and eip7702 contain an access list
                 let hash_format = meta.query_advice(is_eip2930, Rotation::cur())
                     * TxHashEip2930.expr()
-                    + meta.query_advice(is_eip1559, Rotation::cur()) * TxHashEip1559.expr();
+                    + meta.query_advice(is_eip1559, Rotation::cur()) * TxHashEip1559.expr()
+                    + meta.query_advice(is_eip4844, Rotation::cur()) * TxHashEip4844.expr()
+                    + meta.query_advice(is_eip7702, Rotation::cur()) * TxHashEip7702.expr();
 
                 vec![
                     1.expr(), // q_enable = true
@@ -2823,10 +3424,12 @@ impl<F: Field> TxCircuitConfig<F> {
                     meta.query_advice(is_access_list_storage_key, Rotation::cur()),
                 ]);
 
-                // only eip2930 and eip1559 contains an access list
+                // only eip2930, eip1559, eip4844 and eip7702 contain an access list
                 let sign_format = meta.query_advice(is_eip2930, Rotation::cur())
                     * TxSignEip2930.expr()
-                    + meta.query_advice(is_eip1559, Rotation::cur()) * TxSignEip1559.expr();
+                    + meta.query_advice(is_eip1559, Rotation::cur()) * TxSignEip1559.expr()
+                    + meta.query_advice(is_eip4844, Rotation::cur()) * TxSignEip4844.expr()
+                    + meta.query_advice(is_eip7702, Rotation::cur()) * TxSignEip7702.expr();
 
                 vec![
                     1.expr(), // q_enable = true
@@ -2856,10 +3459,12 @@ impl<F: Field> TxCircuitConfig<F> {
                     meta.query_advice(is_access_list_storage_key, Rotation::cur()),
                 ]);
 
-                // only eip2930 and eip1559 contains an access list
+                // only eip2930, eip1559, eip4844 and eip7702 contain an access list
                 let hash_format = meta.query_advice(is_eip2930, Rotation::cur())
                     * TxHashEip2930.expr()
-                    + meta.query_advice(is_eip1559, Rotation::cur()) * TxHashEip1559.expr();
+                    + meta.query_advice(is_eip1559, Rotation::cur()) * TxHashEip1559.expr()
+                    + meta.query_advice(is_eip4844, Rotation::cur()) * TxHashEip4844.expr()
+                    + meta.query_advice(is_eip7702, Rotation::cur()) * TxHashEip7702.expr();
 
                 vec![
                     1.expr(), // q_enable = true
@@ -2881,6 +3486,174 @@ impl<F: Field> TxCircuitConfig<F> {
             },
         );
 
+        // lookup blob versioned hash in RLP table
+        // 1. ensure field_rlc is correct
+        // 2. ensure value of blob versioned hash is correct
+        // The index of a blob versioned hash is stored in the storage_key_idx of RLP table.
+        meta.lookup_any(
+            "Lookup blob versioned hash in RLP Table from tx circuit dynamic section (Signing)",
+            |meta| {
+                let enable = and::expr(vec![
+                    meta.query_fixed(q_enable, Rotation::cur()),
+                    meta.query_advice(is_blob_hash, Rotation::cur()),
+                ]);
+
+                // only eip4844 contains blob versioned hashes
+                let sign_format =
+                    meta.query_advice(is_eip4844, Rotation::cur()) * TxSignEip4844.expr();
+
+                vec![
+                    1.expr(), // q_enable = true
+                    meta.query_advice(tx_table.tx_id, Rotation::cur()),
+                    sign_format,
+                    meta.query_advice(rlp_tag, Rotation::cur()),
+                    meta.query_advice(tx_table.value, Rotation::cur()),
+                    meta.query_advice(field_rlc, Rotation::cur()),
+                    32.expr(), // 32 bytes for blob versioned hashes
+                    1.expr(),  // is_output = true
+                    0.expr(),  // is_none = false. must have value
+                    0.expr(),  // access_list_idx
+                    meta.query_advice(tx_table.index, Rotation::cur()), // storage_key_idx
+                ]
+                .into_iter()
+                .zip_eq(rlp_table.table_exprs(meta))
+                .map(|(arg, table)| (enable.clone() * arg, table))
+                .collect()
+            },
+        );
+
+        meta.lookup_any(
+            "Lookup blob versioned hash in RLP Table from tx circuit dynamic section (Hashing)",
+            |meta| {
+                let enable = and::expr(vec![
+                    meta.query_fixed(q_enable, Rotation::cur()),
+                    meta.query_advice(is_blob_hash, Rotation::cur()),
+                ]);
+
+                // only eip4844 contains blob versioned hashes
+                let hash_format =
+                    meta.query_advice(is_eip4844, Rotation::cur()) * TxHashEip4844.expr();
+
+                vec![
+                    1.expr(), // q_enable = true
+                    meta.query_advice(tx_table.tx_id, Rotation::cur()),
+                    hash_format,
+                    meta.query_advice(rlp_tag, Rotation::cur()),
+                    meta.query_advice(tx_table.value, Rotation::cur()),
+                    meta.query_advice(field_rlc, Rotation::cur()),
+                    32.expr(), // 32 bytes for blob versioned hashes
+                    1.expr(),  // is_output = true
+                    0.expr(),  // is_none = false. must have value
+                    0.expr(),  // access_list_idx
+                    meta.query_advice(tx_table.index, Rotation::cur()), // storage_key_idx
+                ]
+                .into_iter()
+                .zip_eq(rlp_table.table_exprs(meta))
+                .map(|(arg, table)| (enable.clone() * arg, table))
+                .collect()
+            },
+        );
+
+        // lookup authorization fields in RLP table
+        // The index of an authorization tuple is stored in the access_list_idx of RLP table.
+        meta.lookup_any(
+            "Lookup authorization in RLP Table from tx circuit dynamic section (Signing)",
+            |meta| {
+                let enable = and::expr(vec![
+                    meta.query_fixed(q_enable, Rotation::cur()),
+                    meta.query_advice(is_authorization, Rotation::cur()),
+                ]);
+
+                // only eip7702 contains authorizations
+                let sign_format =
+                    meta.query_advice(is_eip7702, Rotation::cur()) * TxSignEip7702.expr();
+
+                vec![
+                    1.expr(), // q_enable = true
+                    meta.query_advice(tx_table.tx_id, Rotation::cur()),
+                    sign_format,
+                    meta.query_advice(rlp_tag, Rotation::cur()),
+                    meta.query_advice(tx_table.value, Rotation::cur()),
+                    meta.query_advice(tx_value_rlc, Rotation::cur()),
+                    meta.query_advice(tx_value_length, Rotation::cur()),
+                    1.expr(), // is_output = true
+                    meta.query_advice(is_none, Rotation::cur()),
+                    meta.query_advice(tx_table.index, Rotation::cur()), // access_list_idx
+                    0.expr(),                                           // storage_key_idx
+                ]
+                .into_iter()
+                .zip_eq(rlp_table.table_exprs(meta))
+                .map(|(arg, table)| (enable.clone() * arg, table))
+                .collect()
+            },
+        );
+
+        meta.lookup_any(
+            "Lookup authorization in RLP Table from tx circuit dynamic section (Hashing)",
+            |meta| {
+                let enable = and::expr(vec![
+                    meta.query_fixed(q_enable, Rotation::cur()),
+                    meta.query_advice(is_authorization, Rotation::cur()),
+                ]);
+
+                // only eip7702 contains authorizations
+                let hash_format =
+                    meta.query_advice(is_eip7702, Rotation::cur()) * TxHashEip7702.expr();
+
+                vec![
+                    1.expr(), // q_enable = true
+                    meta.query_advice(tx_table.tx_id, Rotation::cur()),
+                    hash_format,
+                    meta.query_advice(rlp_tag, Rotation::cur()),
+                    meta.query_advice(tx_table.value, Rotation::cur()),
+                    meta.query_advice(tx_value_rlc, Rotation::cur()),
+                    meta.query_advice(tx_value_length, Rotation::cur()),
+                    1.expr(), // is_output = true
+                    meta.query_advice(is_none, Rotation::cur()),
+                    meta.query_advice(tx_table.index, Rotation::cur()), // access_list_idx
+                    0.expr(),                                           // storage_key_idx
+                ]
+                .into_iter()
+                .zip_eq(rlp_table.table_exprs(meta))
+                .map(|(arg, table)| (enable.clone() * arg, table))
+                .collect()
+            },
+        );
+
+        // The RLP circuit emits the number of items at the end of the authorization list, which
+        // is the only list end followed by EndObject in the signing format of an EIP-7702 tx.
+        // Only EIP-7702 txs have a non-zero AuthorizationListLen.
+        meta.lookup_any(
+            "Lookup AuthorizationListLen in RLP Table from tx circuit fixed section (Signing)",
+            |meta| {
+                let enable = and::expr(vec![
+                    meta.query_fixed(q_enable, Rotation::cur()),
+                    meta.query_advice(
+                        lookup_conditions[&LookupCondition::TxAuthorizations],
+                        Rotation::cur(),
+                    ),
+                ]);
+
+                vec![
+                    1.expr(), // q_enable = true
+                    meta.query_advice(tx_table.tx_id, Rotation::cur()),
+                    TxSignEip7702.expr(),
+                    Tag::EndVector.expr(),
+                    meta.query_advice(tx_table.value, Rotation::cur()),
+                    Tag::EndObject.expr(), // the tag following the list
+                    0.expr(),              // tag_length
+                    1.expr(),              // is_output = true
+                    0.expr(),              // is_none = false
+                    0.expr(),              // access_list_idx
+                    0.expr(),              // storage_key_idx
+                ]
+                .into_iter()
+                .zip_eq(rlp_table.table_exprs(meta))
+                .map(|(arg, table)| (enable.clone() * arg, table))
+                .collect()
+            },
+        );
+
         ////////////////////////////////////////////////////////////////////
         /////////////////    Sig table lookups     //////////////////////
         ///////////////// //////////////////////////////////////////////////
@@ -2901,12 +3674,14 @@ impl<F: Field> TxCircuitConfig<F> {
             let sig_s = meta.query_advice(tx_table.value, Rotation(3));
             let sv_address = meta.query_advice(sv_address, Rotation::cur());
 
-            // include eip1559 and eip2930 type tx, sig_v is 0 or 1.
+            // include eip1559, eip2930, eip4844 and eip7702 type tx, sig_v is 0 or 1.
 
             let v = is_eip155(meta) * (sig_v.expr() - 2.expr() * chain_id - 35.expr())
                 + is_pre_eip155(meta) * (sig_v.expr() - 27.expr())
                 + meta.query_advice(is_eip1559, Rotation::cur()) * sig_v.expr()
-                + meta.query_advice(is_eip2930, Rotation::cur()) * sig_v.expr();
+                + meta.query_advice(is_eip2930, Rotation::cur()) * sig_v.expr()
+                + meta.query_advice(is_eip4844, Rotation::cur()) * sig_v.expr()
+                + meta.query_advice(is_eip7702, Rotation::cur()) * sig_v.expr();
 
             let input_exprs = vec![
                 1.expr(),     // q_enable = true
@@ -3315,6 +4090,29 @@ impl<F: Field> TxCircuitConfig<F> {
                 }),
                 rlc_be_bytes(&tx.max_priority_fee_per_gas.to_be_bytes(), evm_word),
             ),
+            (
+                MaxFeePerBlobGas,
+                Some(RlpTableInputValue {
+                    tag: Tag::MaxFeePerBlobGas.into(),
+                    is_none: tx.max_fee_per_blob_gas.is_zero(),
+                    be_bytes_len: tx.max_fee_per_blob_gas.tag_length(),
+                    be_bytes_rlc: rlc_be_bytes(
+                        &tx.max_fee_per_blob_gas.to_be_bytes(),
+                        keccak_input,
+                    ),
+                }),
+                rlc_be_bytes(&tx.max_fee_per_blob_gas.to_be_bytes(), evm_word),
+            ),
+            (
+                BlobVersionedHashesLen,
+                None,
+                Value::known(F::from(tx.blob_versioned_hashes.len() as u64)),
+            ),
+            (
+                AuthorizationListLen,
+                None,
+                Value::known(F::from(tx.authorization_list.len() as u64)),
+            ),
             (BlockNumber, None, Value::known(F::from(tx.block_number))),
         ];
         for (tx_tag, rlp_input, tx_value) in fixed_rows {
@@ -3471,7 +4269,17 @@ impl<F: Field> TxCircuitConfig<F> {
                     F::zero()
                 }
             });
-            // 3. lookup to RLP table for signing (non L1 msg)
+            // 3. lookup to ensure the final row in the blob hashes dynamic section is present.
+            conditions.insert(LookupCondition::TxBlobHashes, {
+                let tag_enable = tx_tag == BlobVersionedHashesLen;
+                F::from((tag_enable && !tx.blob_versioned_hashes.is_empty()) as u64)
+            });
+            // lookup to ensure the final row in the authorizations dynamic section is present.
+            conditions.insert(LookupCondition::TxAuthorizations, {
+                let tag_enable = tx_tag == AuthorizationListLen;
+                F::from((tag_enable && !tx.authorization_list.is_empty()) as u64)
+            });
+            // 4. lookup to RLP table for signing (non L1 msg)
             conditions.insert(LookupCondition::RlpSignTag, {
                 let sign_set = [
                     Nonce,
@@ -3485,12 +4293,15 @@ impl<F: Field> TxCircuitConfig<F> {
                 let is_tag_in_set = sign_set.into_iter().filter(|tag| tx_tag == *tag).count() == 1;
                 let case1 = is_tag_in_set && !is_l1_msg;
                 let case2 = !tx.tx_type.is_pre_eip155() && !is_l1_msg && (tx_tag == ChainID);
-                let case3 = !tx.tx_type.is_eip1559() && !is_l1_msg && (tx_tag == GasPrice);
-                let case4 = tx.tx_type.is_eip1559()
-                    && (tx_tag == MaxFeePerGas || tx_tag == MaxPriorityFeePerGas);
-                F::from((case1 || case2 || case3 || case4) as u64)
+                let is_fee_market =
+                    tx.tx_type.is_eip1559() || tx.tx_type.is_eip4844() || tx.tx_type.is_eip7702();
+                let case3 = !is_fee_market && !is_l1_msg && (tx_tag == GasPrice);
+                let case4 =
+                    is_fee_market && (tx_tag == MaxFeePerGas || tx_tag == MaxPriorityFeePerGas);
+                let case5 = tx.tx_type.is_eip4844() && tx_tag == MaxFeePerBlobGas;
+                F::from((case1 || case2 || case3 || case4 || case5) as u64)
             });
-            // 4. lookup to RLP table for hashing (non L1 msg)
+            // 5. lookup to RLP table for hashing (non L1 msg)
             conditions.insert(LookupCondition::RlpHashTag, {
                 let hash_set = [
                     Nonce,
@@ -3507,12 +4318,15 @@ impl<F: Field> TxCircuitConfig<F> {
                 ];
                 let is_tag_in_set = hash_set.into_iter().filter(|tag| tx_tag == *tag).count() == 1;
                 let case1 = is_tag_in_set && !is_l1_msg;
-                let case2 = !tx.tx_type.is_eip1559() && !is_l1_msg && (tx_tag == GasPrice);
-                let case3 = tx.tx_type.is_eip1559()
-                    && (tx_tag == MaxFeePerGas || tx_tag == MaxPriorityFeePerGas);
-                F::from((case1 || case2 || case3) as u64)
+                let is_fee_market =
+                    tx.tx_type.is_eip1559() || tx.tx_type.is_eip4844() || tx.tx_type.is_eip7702();
+                let case2 = !is_fee_market && !is_l1_msg && (tx_tag == GasPrice);
+                let case3 =
+                    is_fee_market && (tx_tag == MaxFeePerGas || tx_tag == MaxPriorityFeePerGas);
+                let case4 = tx.tx_type.is_eip4844() && tx_tag == MaxFeePerBlobGas;
+                F::from((case1 || case2 || case3 || case4) as u64)
             });
-            // 5. lookup to RLP table for hashing (L1 msg)
+            // 6. lookup to RLP table for hashing (L1 msg)
             conditions.insert(LookupCondition::L1MsgHash, {
                 let hash_set = [
                     Nonce,
@@ -3528,7 +4342,7 @@ impl<F: Field> TxCircuitConfig<F> {
                 let is_tag_in_set = hash_set.into_iter().filter(|tag| tx_tag == *tag).count() == 1;
                 F::from((is_l1_msg && is_tag_in_set) as u64)
             });
-            // 6. lookup to Keccak table for tx_sign_hash and l1 tx_hash
+            // 7. lookup to Keccak table for tx_sign_hash and l1 tx_hash
             conditions.insert(LookupCondition::Keccak, {
                 let case1 = (tx_tag == TxSignLength) && !is_l1_msg;
                 let case2 = (tx_tag == TxHashLength) && is_l1_msg;
@@ -3781,6 +4595,193 @@ impl<F: Field> TxCircuitConfig<F> {
         Ok(())
     }
 
+    /// Assign blob versioned hash rows of each tx
+    fn assign_blob_hash_rows(
+        &self,
+        region: &mut Region<'_, F>,
+        offset: &mut usize,
+        tx: &Transaction,
+        next_tx: Option<&Transaction>,
+        challenges: &Challenges<Value<F>>,
+    ) -> Result<(), Error> {
+        for (idx, hash) in tx.blob_versioned_hashes.iter().enumerate() {
+            let is_final = idx == (tx.blob_versioned_hashes.len() - 1);
+            // the tx id of next row
+            let tx_id_next = if !is_final {
+                tx.id
+            } else {
+                next_tx.map_or(0, |tx| tx.id)
+            };
+
+            self.assign_common_part(
+                region,
+                *offset,
+                Some(tx),
+                tx_id_next,
+                BlobVersionedHash,
+                (idx + 1) as u64,
+                rlc_be_bytes(&hash.to_fixed_bytes(), challenges.evm_word()),
+                Value::known(F::zero()),
+            )?;
+
+            // 1st phase columns
+            for (col_anno, col, col_val) in [
+                ("block_num", self.block_num, F::from(tx.block_number)),
+                (
+                    "rlp_tag",
+                    self.rlp_tag,
+                    F::from(usize::from(Tag::BlobVersionedHash) as u64),
+                ),
+                ("is_final", self.is_final, F::from(is_final as u64)),
+            ] {
+                region.assign_advice(|| col_anno, col, *offset, || Value::known(col_val))?;
+            }
+
+            let field_rlc = rlc_be_bytes(&hash.to_fixed_bytes(), challenges.keccak_input());
+            region.assign_advice(|| "field_rlc", self.field_rlc, *offset, || field_rlc)?;
+
+            *offset += 1;
+        }
+
+        Ok(())
+    }
+
+    /// Assign authorization rows of each tx, six rows per authorization tuple
+    fn assign_authorization_rows(
+        &self,
+        region: &mut Region<'_, F>,
+        offset: &mut usize,
+        tx: &Transaction,
+        next_tx: Option<&Transaction>,
+        challenges: &Challenges<Value<F>>,
+    ) -> Result<(), Error> {
+        let keccak_input = challenges.keccak_input();
+        let evm_word = challenges.evm_word();
+
+        for (idx, auth) in tx.authorization_list.iter().enumerate() {
+            let is_last = idx == (tx.authorization_list.len() - 1);
+            // the RLP circuit limits the chain id to 8 bytes
+            let chain_id = auth.chain_id.low_u64();
+            let nonce = auth.nonce.as_u64();
+            let y_parity = auth.y_parity.as_u64();
+
+            let rows = [
+                (
+                    AuthorizationChainId,
+                    RlpTableInputValue {
+                        tag: Tag::AuthChainId.into(),
+                        is_none: chain_id == 0,
+                        be_bytes_len: chain_id.tag_length(),
+                        be_bytes_rlc: rlc_be_bytes(&chain_id.to_be_bytes(), keccak_input),
+                    },
+                    Value::known(F::from(chain_id)),
+                ),
+                (
+                    AuthorizationAddress,
+                    RlpTableInputValue {
+                        tag: Tag::AuthAddress.into(),
+                        is_none: false,
+                        be_bytes_len: auth.address.tag_length(),
+                        be_bytes_rlc: rlc_be_bytes(&auth.address.to_fixed_bytes(), keccak_input),
+                    },
+                    Value::known(auth.address.to_scalar().expect("address too big")),
+                ),
+                (
+                    AuthorizationNonce,
+                    RlpTableInputValue {
+                        tag: Tag::AuthNonce.into(),
+                        is_none: nonce == 0,
+                        be_bytes_len: nonce.tag_length(),
+                        be_bytes_rlc: rlc_be_bytes(&nonce.to_be_bytes(), keccak_input),
+                    },
+                    Value::known(F::from(nonce)),
+                ),
+                (
+                    AuthorizationYParity,
+                    RlpTableInputValue {
+                        tag: Tag::SigV.into(),
+                        is_none: y_parity == 0,
+                        be_bytes_len: y_parity.tag_length(),
+                        be_bytes_rlc: rlc_be_bytes(&y_parity.to_be_bytes(), keccak_input),
+                    },
+                    Value::known(F::from(y_parity)),
+                ),
+                (
+                    AuthorizationR,
+                    RlpTableInputValue {
+                        tag: Tag::SigR.into(),
+                        is_none: auth.r.is_zero(),
+                        be_bytes_len: auth.r.tag_length(),
+                        be_bytes_rlc: rlc_be_bytes(&auth.r.to_be_bytes(), keccak_input),
+                    },
+                    rlc_be_bytes(&auth.r.to_be_bytes(), evm_word),
+                ),
+                (
+                    AuthorizationS,
+                    RlpTableInputValue {
+                        tag: Tag::SigS.into(),
+                        is_none: auth.s.is_zero(),
+                        be_bytes_len: auth.s.tag_length(),
+                        be_bytes_rlc: rlc_be_bytes(&auth.s.to_be_bytes(), keccak_input),
+                    },
+                    rlc_be_bytes(&auth.s.to_be_bytes(), evm_word),
+                ),
+            ];
+
+            for (tx_tag, rlp_input, tx_value) in rows {
+                let is_final = is_last && tx_tag == AuthorizationS;
+                // the tx id of next row
+                let tx_id_next = if !is_final {
+                    tx.id
+                } else {
+                    next_tx.map_or(0, |tx| tx.id)
+                };
+
+                self.assign_common_part(
+                    region,
+                    *offset,
+                    Some(tx),
+                    tx_id_next,
+                    tx_tag,
+                    (idx + 1) as u64,
+                    tx_value,
+                    Value::known(F::zero()),
+                )?;
+
+                // 1st phase columns
+                for (col_anno, col, col_val) in [
+                    ("block_num", self.block_num, F::from(tx.block_number)),
+                    (
+                        "rlp_tag",
+                        self.rlp_tag,
+                        F::from(usize::from(rlp_input.tag) as u64),
+                    ),
+                    ("is_none", self.is_none, F::from(rlp_input.is_none as u64)),
+                    (
+                        "tx_value_length",
+                        self.tx_value_length,
+                        F::from(rlp_input.be_bytes_len as u64),
+                    ),
+                    ("is_final", self.is_final, F::from(is_final as u64)),
+                ] {
+                    region.assign_advice(|| col_anno, col, *offset, || Value::known(col_val))?;
+                }
+
+                // 2nd phase columns
+                region.assign_advice(
+                    || "tx_value_rlc",
+                    self.tx_value_rlc,
+                    *offset,
+                    || rlp_input.be_bytes_rlc,
+                )?;
+
+                *offset += 1;
+            }
+        }
+
+        Ok(())
+    }
+
     // Assigns to common columns in different parts of tx circuit
     // 1. 1st all zero row
     // 2. fixed rows of each tx
@@ -3858,6 +4859,44 @@ impl<F: Field> TxCircuitConfig<F> {
                 self.is_eip1559,
                 F::from(tx_type.is_eip1559() as u64),
             ),
+            (
+                "is_eip4844",
+                self.is_eip4844,
+                F::from(tx_type.is_eip4844() as u64),
+            ),
+            (
+                "is_eip7702",
+                self.is_eip7702,
+                F::from(tx_type.is_eip7702() as u64),
+            ),
+            (
+                "is_tag_nonce",
+                self.is_tag_nonce,
+                F::from((tag == Nonce) as u64),
+            ),
+            (
+                "is_blob_hash",
+                self.is_blob_hash,
+                F::from((tag == BlobVersionedHash) as u64),
+            ),
+            (
+                "is_authorization",
+                self.is_authorization,
+                F::from(matches!(
+                    tag,
+                    AuthorizationChainId
+                        | AuthorizationAddress
+                        | AuthorizationNonce
+                        | AuthorizationYParity
+                        | AuthorizationR
+                        | AuthorizationS
+                ) as u64),
+            ),
+            (
+                "is_tag_authorization_s",
+                self.is_tag_authorization_s,
+                F::from((tag == AuthorizationS) as u64),
+            ),
             (
                 "is_tx_id_zero",
                 self.is_tx_id_zero,
@@ -4262,7 +5301,7 @@ impl<F: Field> TxCircuit<F> {
                     }
                     let is_last_tx = i == (sigs.len() - 1);
                     let next_tx = if is_last_tx {
-                        self.txs.iter().find(|tx| !tx.call_data.is_empty() || (tx.access_list.as_ref().map_or(false, |al| !al.0.is_empty())))
+                        self.txs.iter().find(|tx| !tx.call_data.is_empty() || (tx.access_list.as_ref().map_or(false, |al| !al.0.is_empty())) || !tx.blob_versioned_hashes.is_empty() || !tx.authorization_list.is_empty())
                     } else {
                         Some(get_tx(i+1))
                     };
@@ -4313,7 +5352,7 @@ impl<F: Field> TxCircuit<F> {
                         .txs
                         .iter()
                         .skip(i + 1)
-                        .find(|tx| !tx.call_data.is_empty() || (tx.access_list.as_ref().map_or(false, |al| !al.0.is_empty())));
+                        .find(|tx| !tx.call_data.is_empty() || (tx.access_list.as_ref().map_or(false, |al| !al.0.is_empty())) || !tx.blob_versioned_hashes.is_empty() || !tx.authorization_list.is_empty());
                     config.assign_calldata_rows(
                         &mut region,
                         &mut offset,
@@ -4328,6 +5367,20 @@ impl<F: Field> TxCircuit<F> {
                         next_tx,
                         challenges,
                     )?;
+                    config.assign_blob_hash_rows(
+                        &mut region,
+                        &mut offset,
+                        tx,
+                        next_tx,
+                        challenges,
+                    )?;
+                    config.assign_authorization_rows(
+                        &mut region,
+                        &mut offset,
+                        tx,
+                        next_tx,
+                        challenges,
+                    )?;
                 }
                 assert!(offset <= calldata_last_row, "{offset}, {calldata_last_row}");
                 // 3.2 pad calldata with zeros
@@ -4413,18 +5466,31 @@ impl<F: Field> SubCircuit<F> for TxCircuit<F> {
                 }
             })
             .sum::<usize>();
+        let sum_blob_hashes_len = block
+            .txs
+            .iter()
+            .map(|tx| tx.blob_versioned_hashes.len())
+            .sum::<usize>();
+        // each authorization tuple takes six rows
+        let sum_authorizations_len = block
+            .txs
+            .iter()
+            .map(|tx| tx.authorization_list.len() * 6)
+            .sum::<usize>();
 
         // With the introduction of access list, the max_calldata circuit parameter now has to share
         // capacity between calldata and access list rows TODO: The max_calldata parameter
         // should be renamed later to max_dynamic
         let max_dynamic_data = if block.circuits_params.max_calldata == 0 {
             // input-specific max_dynamic
-            sum_calldata_len + sum_access_list_len
+            sum_calldata_len + sum_access_list_len + sum_blob_hashes_len + sum_authorizations_len
         } else {
             block.circuits_params.max_calldata
         };
         let dynamic_usage =
-            (sum_calldata_len + sum_access_list_len) as f32 / max_dynamic_data as f32;
+            (sum_calldata_len + sum_access_list_len + sum_blob_hashes_len + sum_authorizations_len)
+                as f32
+                / max_dynamic_data as f32;
 
         // Get the highest usage fraction out of all capacities
         let highest_usage = ([blob_usage, dynamic_usage])
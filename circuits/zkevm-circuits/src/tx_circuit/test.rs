@@ -165,6 +165,68 @@ fn build_eip1559_tx(id: usize) -> Transaction {
     tx
 }
 
+#[cfg(test)]
+fn build_eip4844_tx(id: usize) -> Transaction {
+    use eth_types::geth_types::{get_rlp_signed, get_rlp_unsigned};
+
+    let blob_versioned_hashes = (1..=2u8)
+        .map(|i| {
+            // versioned hashes start with the KZG version byte 0x01
+            let mut hash = [0u8; 32];
+            hash[0] = 0x01;
+            hash[31] = i;
+            H256(hash)
+        })
+        .collect::<Vec<_>>();
+    let mock_tx = MockTransaction::default()
+        .from(mock::MOCK_WALLETS[0].clone())
+        .to(mock::MOCK_ACCOUNTS[0])
+        .nonce(word!("0x1"))
+        .gas(word!("0x5208"))
+        .value(word!("0x3e8"))
+        .input(vec![0x01, 0x00, 0x02].into())
+        .max_fee_per_gas(word!("0x77359400"))
+        .max_priority_fee_per_gas(word!("0x3b9aca00"))
+        .max_fee_per_blob_gas(word!("0x3b9aca00"))
+        .blob_versioned_hashes(blob_versioned_hashes.clone())
+        .transaction_type(3)
+        .build();
+    let eth_tx = eth_types::Transaction::from(mock_tx);
+
+    let mut tx = Transaction::new_from_rlp_bytes(
+        1,
+        TxType::Eip4844,
+        get_rlp_signed(&eth_tx),
+        get_rlp_unsigned(&eth_tx),
+    );
+
+    tx.hash = eth_tx.hash;
+    tx.block_number = 1;
+    tx.id = id;
+    tx.chain_id = mock::MOCK_CHAIN_ID;
+    tx.nonce = eth_tx.nonce.as_u64();
+    tx.value = eth_tx.value;
+    tx.gas = eth_tx.gas.as_u64();
+    tx.max_fee_per_gas = eth_tx.max_fee_per_gas.unwrap_or_default();
+    tx.max_priority_fee_per_gas = eth_tx.max_priority_fee_per_gas.unwrap_or_default();
+    tx.max_fee_per_blob_gas = word!("0x3b9aca00");
+    tx.blob_versioned_hashes = blob_versioned_hashes;
+    tx.call_data = eth_tx.input.to_vec();
+    tx.callee_address = eth_tx.to;
+    tx.caller_address = eth_tx.from;
+    tx.is_create = false;
+    tx.call_data_length = tx.call_data.len();
+    tx.call_data_gas_cost = tx_data_gas_cost(&tx.call_data);
+    tx.access_list = eth_tx.access_list.clone();
+    tx.access_list_gas_cost = tx_access_list_gas_cost(&eth_tx.access_list);
+    tx.tx_data_gas_cost = tx_data_gas_cost(&tx.rlp_signed);
+    tx.v = eth_tx.v.as_u64();
+    tx.r = eth_tx.r;
+    tx.s = eth_tx.s;
+
+    tx
+}
+
 fn run<F: Field>(
     txs: Vec<Transaction>,
     chain_id: u64,
@@ -226,6 +288,41 @@ fn tx_circuit_2tx_2max_tx_eip1559() {
     );
 }
 
+#[test]
+#[cfg(feature = "scroll")]
+fn tx_circuit_1tx_2max_eip4844() {
+    const MAX_TXS: usize = 2;
+    const MAX_CALLDATA: usize = 320;
+
+    let tx = build_eip4844_tx(1);
+
+    assert_eq!(
+        run::<Fr>(vec![tx], mock::MOCK_CHAIN_ID, MAX_TXS, MAX_CALLDATA, 0),
+        Ok(())
+    );
+}
+
+#[test]
+#[cfg(feature = "scroll")]
+fn tx_circuit_2tx_2max_tx_eip4844_and_eip1559() {
+    const MAX_TXS: usize = 2;
+    const MAX_CALLDATA: usize = 6400;
+
+    let tx1 = build_eip4844_tx(1);
+    let tx2 = build_eip1559_tx(2);
+
+    assert_eq!(
+        run::<Fr>(
+            vec![tx1, tx2],
+            mock::MOCK_CHAIN_ID,
+            MAX_TXS,
+            MAX_CALLDATA,
+            0
+        ),
+        Ok(())
+    );
+}
+
 #[test]
 #[cfg(feature = "scroll")]
 fn tx_circuit_2tx_2max_tx() {
@@ -18,6 +18,8 @@ use eth_types::{ToAddress, Word};
 pub use ethers_core::types::{Address, U256};
 pub use gadgets::util::Expr;
 
+/// Layout and cost report of a circuit
+pub mod circuit_report;
 /// A wrapper of is_zero in gadgets which gives is_zero at any rotation
 pub mod is_zero;
 
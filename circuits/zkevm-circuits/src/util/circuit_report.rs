@@ -0,0 +1,633 @@
+//! Layout and cost report of a circuit: columns by type, gates and lookups with their degrees,
+//! permutation size, and the rows used by each region when a circuit instance is synthesized.
+use std::{
+    collections::{BTreeMap, HashSet},
+    fmt,
+    ops::Range,
+};
+
+use halo2_proofs::{
+    circuit::Value,
+    plonk::{
+        Advice, Any, Assigned, Assignment, Challenge, Circuit, Column, ConstraintSystem, Error,
+        Expression, Fixed, FloorPlanner, Instance, Selector,
+    },
+};
+use serde::Serialize;
+
+use super::{log2_ceil, Field, SubCircuit};
+use crate::witness;
+
+/// Number of columns of each type
+#[derive(Clone, Debug, Serialize)]
+pub struct ColumnReport {
+    /// Advice columns by phase
+    pub advice: Vec<usize>,
+    /// Fixed columns, not counting the selectors
+    pub fixed: usize,
+    /// Instance columns
+    pub instance: usize,
+    /// Selectors, before they are combined into fixed columns
+    pub selectors: usize,
+    /// Simple selectors, which can be combined with each other
+    pub simple_selectors: usize,
+    /// Challenges
+    pub challenges: usize,
+}
+
+/// Gate and the degree of each of its constraints
+#[derive(Clone, Debug, Serialize)]
+pub struct GateReport {
+    /// Name of the gate
+    pub name: String,
+    /// Name and degree of the constraints
+    pub constraints: Vec<(String, usize)>,
+}
+
+impl GateReport {
+    /// Highest degree of the constraints of the gate
+    pub fn degree(&self) -> usize {
+        self.constraints
+            .iter()
+            .map(|(_, degree)| *degree)
+            .max()
+            .unwrap_or_default()
+    }
+}
+
+/// Lookup argument and its degree
+#[derive(Clone, Debug, Serialize)]
+pub struct LookupReport {
+    /// Name of the lookup
+    pub name: String,
+    /// Number of looked up expressions
+    pub inputs: usize,
+    /// Degree of the argument, i.e. `max(4, 2 + input degree + table degree)`
+    pub degree: usize,
+}
+
+/// Permutation argument
+#[derive(Clone, Debug, Serialize)]
+pub struct PermutationReport {
+    /// Columns with equality enabled
+    pub columns: usize,
+    /// Grand product columns the columns are split into for the circuit degree
+    pub products: usize,
+    /// Copy constraints, known once an instance is synthesized
+    pub copies: Option<usize>,
+}
+
+/// Rows and columns used by a region
+#[derive(Clone, Debug, Serialize)]
+pub struct RegionReport {
+    /// Name of the region
+    pub name: String,
+    /// First row assigned in the region
+    pub offset: usize,
+    /// Rows from the first to the last one assigned
+    pub rows: usize,
+    /// Advice columns assigned
+    pub advice_columns: usize,
+    /// Fixed columns assigned
+    pub fixed_columns: usize,
+    /// Advice cells assigned
+    pub advice_cells: usize,
+}
+
+/// Layout and cost report of a circuit.
+///
+/// [`CircuitReport::configure`] only looks at the constraint system, while
+/// [`CircuitReport::synthesize`] also records the regions assigned by a circuit instance.
+#[derive(Clone, Debug, Serialize)]
+pub struct CircuitReport {
+    /// Name of the circuit
+    pub name: String,
+    /// Degree of the constraint system
+    pub degree: usize,
+    /// Rows reserved for blinding
+    pub blinding_factors: usize,
+    /// Columns by type
+    pub columns: ColumnReport,
+    /// Gates
+    pub gates: Vec<GateReport>,
+    /// Lookups
+    pub lookups: Vec<LookupReport>,
+    /// Permutation argument
+    pub permutation: PermutationReport,
+    /// Regions in the order they are assigned
+    pub regions: Vec<RegionReport>,
+    /// Advice rows used, i.e. the last advice row assigned plus one
+    pub advice_rows: Option<usize>,
+    /// Rows required by the block the sub-circuit is built from, without and with padding
+    pub min_num_rows: Option<(usize, usize)>,
+    /// Smallest `k` fitting the rows assigned and the unusable rows
+    pub k: Option<u32>,
+}
+
+impl CircuitReport {
+    /// Report of the configuration of circuit `C`, without any region
+    pub fn configure<F: Field, C: Circuit<F>>(name: impl Into<String>) -> Self {
+        let mut meta = ConstraintSystem::default();
+        C::configure(&mut meta);
+        Self::from_meta(name.into(), &meta)
+    }
+
+    /// Report of `circuit`, with the regions it assigns
+    pub fn synthesize<F: Field, C: Circuit<F>>(
+        name: impl Into<String>,
+        circuit: &C,
+    ) -> Result<Self, Error> {
+        let mut meta = ConstraintSystem::default();
+        let config = C::configure(&mut meta);
+        let mut recorder = RegionRecorder::default();
+        C::FloorPlanner::synthesize(&mut recorder, circuit, config, meta.constants().clone())?;
+
+        let mut report = Self::from_meta(name.into(), &meta);
+        report.permutation.copies = Some(recorder.copies);
+        report.advice_rows = Some(recorder.advice_rows);
+        report.k = Some(log2_ceil(recorder.rows + meta.blinding_factors() + 1));
+        report.regions = recorder.regions;
+        Ok(report)
+    }
+
+    /// Report of the sub-circuit `C` built from `block`, with the rows the block requires
+    pub fn from_block<F: Field, C: SubCircuit<F> + Circuit<F>>(
+        name: impl Into<String>,
+        block: &witness::Block,
+    ) -> Result<Self, Error> {
+        let circuit = C::new_from_block(block);
+        let mut report = Self::synthesize(name, &circuit)?;
+        let (rows, padded_rows) = C::min_num_rows_block(block);
+        report.min_num_rows = Some((rows, padded_rows));
+        report.k = report
+            .k
+            .max(Some(log2_ceil(padded_rows + C::unusable_rows())));
+        Ok(report)
+    }
+
+    fn from_meta<F: Field>(name: String, meta: &ConstraintSystem<F>) -> Self {
+        let degree = meta.degree();
+        let mut advice = vec![0; meta.max_phase() as usize + 1];
+        for phase in meta.advice_column_phase() {
+            advice[phase as usize] += 1;
+        }
+        let gates = meta
+            .gates()
+            .iter()
+            .map(|gate| GateReport {
+                name: gate.name().to_string(),
+                constraints: gate
+                    .polynomials()
+                    .iter()
+                    .enumerate()
+                    .map(|(i, poly)| (gate.constraint_name(i).to_string(), poly.degree()))
+                    .collect(),
+            })
+            .collect();
+        let lookups = meta
+            .lookups
+            .iter()
+            .map(|lookup| {
+                let max_degree = |exprs: &[Expression<F>]| {
+                    exprs.iter().map(Expression::degree).fold(1, usize::max)
+                };
+                LookupReport {
+                    name: lookup.name().to_string(),
+                    inputs: lookup.input_expressions().len(),
+                    degree: 4.max(
+                        2 + max_degree(lookup.input_expressions())
+                            + max_degree(lookup.table_expressions()),
+                    ),
+                }
+            })
+            .collect();
+        let permutation_columns = meta.permutation.columns.len();
+
+        Self {
+            name,
+            degree,
+            blinding_factors: meta.blinding_factors(),
+            columns: ColumnReport {
+                advice,
+                fixed: meta.num_fixed_columns,
+                instance: meta.num_instance_columns,
+                selectors: meta.num_selectors,
+                simple_selectors: meta.num_simple_selectors,
+                challenges: meta.num_challenges(),
+            },
+            gates,
+            lookups,
+            permutation: PermutationReport {
+                columns: permutation_columns,
+                products: (permutation_columns + degree - 3) / (degree - 2).max(1),
+                copies: None,
+            },
+            regions: vec![],
+            advice_rows: None,
+            min_num_rows: None,
+            k: None,
+        }
+    }
+
+    /// Gates by name, with the number of constraints and the degree, which is stable across
+    /// witnesses and meant to be diffed when reviewing constraint changes
+    pub fn gate_summary(&self) -> BTreeMap<&str, (usize, usize)> {
+        let mut summary = BTreeMap::new();
+        for gate in &self.gates {
+            let entry = summary.entry(gate.name.as_str()).or_insert((0, 0));
+            entry.0 += gate.constraints.len();
+            entry.1 = entry.1.max(gate.degree());
+        }
+        summary
+    }
+}
+
+impl fmt::Display for CircuitReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "# {}", self.name)?;
+        writeln!(f)?;
+        writeln!(f, "- degree: {}", self.degree)?;
+        writeln!(f, "- blinding factors: {}", self.blinding_factors)?;
+        if let Some(k) = self.k {
+            writeln!(f, "- k: {k}")?;
+        }
+        if let Some(rows) = self.advice_rows {
+            writeln!(f, "- advice rows: {rows}")?;
+        }
+        if let Some((rows, padded_rows)) = self.min_num_rows {
+            writeln!(f, "- block rows: {rows} ({padded_rows} padded)")?;
+        }
+
+        let columns = &self.columns;
+        writeln!(f, "\n## Columns\n")?;
+        writeln!(f, "| type | count |\n| --- | --- |")?;
+        for (phase, advice) in columns.advice.iter().enumerate() {
+            writeln!(f, "| advice (phase {phase}) | {advice} |")?;
+        }
+        writeln!(f, "| fixed | {} |", columns.fixed)?;
+        writeln!(f, "| instance | {} |", columns.instance)?;
+        writeln!(
+            f,
+            "| selector | {} ({} simple) |",
+            columns.selectors, columns.simple_selectors
+        )?;
+        writeln!(f, "| challenge | {} |", columns.challenges)?;
+
+        writeln!(f, "\n## Gates\n")?;
+        writeln!(f, "| gate | constraints | degree |\n| --- | --- | --- |")?;
+        for (name, (constraints, degree)) in self.gate_summary() {
+            writeln!(f, "| {name} | {constraints} | {degree} |")?;
+        }
+
+        writeln!(f, "\n## Lookups\n")?;
+        writeln!(f, "| lookup | inputs | degree |\n| --- | --- | --- |")?;
+        for lookup in &self.lookups {
+            writeln!(
+                f,
+                "| {} | {} | {} |",
+                lookup.name, lookup.inputs, lookup.degree
+            )?;
+        }
+
+        let permutation = &self.permutation;
+        writeln!(f, "\n## Permutation\n")?;
+        writeln!(f, "- columns: {}", permutation.columns)?;
+        writeln!(f, "- grand products: {}", permutation.products)?;
+        if let Some(copies) = permutation.copies {
+            writeln!(f, "- copies: {copies}")?;
+        }
+
+        if !self.regions.is_empty() {
+            writeln!(f, "\n## Regions\n")?;
+            writeln!(
+                f,
+                "| region | offset | rows | advice columns | fixed columns | advice cells |"
+            )?;
+            writeln!(f, "| --- | --- | --- | --- | --- | --- |")?;
+            for region in &self.regions {
+                writeln!(
+                    f,
+                    "| {} | {} | {} | {} | {} | {} |",
+                    region.name,
+                    region.offset,
+                    region.rows,
+                    region.advice_columns,
+                    region.fixed_columns,
+                    region.advice_cells
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Render the column and region layout of `circuit` at degree `k` to the image at `path`
+#[cfg(feature = "dev-graph")]
+pub fn render_layout<F: Field, C: Circuit<F>>(
+    k: u32,
+    circuit: &C,
+    title: &str,
+    path: impl AsRef<std::path::Path>,
+    size: (u32, u32),
+) -> Result<(), Box<dyn std::error::Error>> {
+    use plotters::prelude::*;
+
+    let root = BitMapBackend::new(path.as_ref(), size).into_drawing_area();
+    root.fill(&WHITE)?;
+    let root = root.titled(title, ("sans-serif", 60))?;
+    halo2_proofs::dev::CircuitLayout::default()
+        .show_labels(true)
+        .render(k, circuit, &root)?;
+    root.present()?;
+    Ok(())
+}
+
+#[derive(Default)]
+struct RegionUsage {
+    name: String,
+    rows: Option<Range<usize>>,
+    advice_columns: HashSet<usize>,
+    fixed_columns: HashSet<usize>,
+    advice_cells: usize,
+}
+
+impl RegionUsage {
+    fn use_row(&mut self, row: usize) {
+        self.rows = Some(match self.rows.take() {
+            Some(rows) => rows.start.min(row)..rows.end.max(row + 1),
+            None => row..row + 1,
+        });
+    }
+}
+
+impl From<RegionUsage> for RegionReport {
+    fn from(usage: RegionUsage) -> Self {
+        let rows = usage.rows.unwrap_or_default();
+        Self {
+            name: usage.name,
+            offset: rows.start,
+            rows: rows.len(),
+            advice_columns: usage.advice_columns.len(),
+            fixed_columns: usage.fixed_columns.len(),
+            advice_cells: usage.advice_cells,
+        }
+    }
+}
+
+/// Assignment recording where the regions are laid out, ignoring the values
+#[derive(Default)]
+struct RegionRecorder {
+    regions: Vec<RegionReport>,
+    current: Option<RegionUsage>,
+    copies: usize,
+    advice_rows: usize,
+    rows: usize,
+}
+
+impl<F: Field> Assignment<F> for RegionRecorder {
+    #[cfg(feature = "parallel_syn")]
+    fn fork(&mut self, ranges: &[Range<usize>]) -> Result<Vec<Self>, Error> {
+        Ok(ranges.iter().map(|_| Self::default()).collect())
+    }
+
+    #[cfg(feature = "parallel_syn")]
+    fn merge(&mut self, sub_cs: Vec<Self>) -> Result<(), Error> {
+        for sub_cs in sub_cs {
+            self.regions.extend(sub_cs.regions);
+            self.copies += sub_cs.copies;
+            self.advice_rows = self.advice_rows.max(sub_cs.advice_rows);
+            self.rows = self.rows.max(sub_cs.rows);
+        }
+        Ok(())
+    }
+
+    fn enter_region<NR, N>(&mut self, name_fn: N)
+    where
+        NR: Into<String>,
+        N: FnOnce() -> NR,
+    {
+        self.current = Some(RegionUsage {
+            name: name_fn().into(),
+            ..Default::default()
+        });
+    }
+
+    fn exit_region(&mut self) {
+        if let Some(usage) = self.current.take() {
+            self.regions.push(usage.into());
+        }
+    }
+
+    fn enable_selector<A, AR>(&mut self, _: A, _: &Selector, row: usize) -> Result<(), Error>
+    where
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+    {
+        if let Some(usage) = &mut self.current {
+            usage.use_row(row);
+        }
+        Ok(())
+    }
+
+    fn query_instance(&self, _: Column<Instance>, _: usize) -> Result<Value<F>, Error> {
+        Ok(Value::unknown())
+    }
+
+    fn assign_advice<V, VR, A, AR>(
+        &mut self,
+        _: A,
+        column: Column<Advice>,
+        row: usize,
+        _: V,
+    ) -> Result<(), Error>
+    where
+        V: FnOnce() -> Value<VR>,
+        VR: Into<Assigned<F>>,
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+    {
+        self.advice_rows = self.advice_rows.max(row + 1);
+        self.rows = self.rows.max(row + 1);
+        if let Some(usage) = &mut self.current {
+            usage.use_row(row);
+            usage.advice_columns.insert(column.index());
+            usage.advice_cells += 1;
+        }
+        Ok(())
+    }
+
+    fn assign_fixed<V, VR, A, AR>(
+        &mut self,
+        _: A,
+        column: Column<Fixed>,
+        row: usize,
+        _: V,
+    ) -> Result<(), Error>
+    where
+        V: FnOnce() -> Value<VR>,
+        VR: Into<Assigned<F>>,
+        A: FnOnce() -> AR,
+        AR: Into<String>,
+    {
+        self.rows = self.rows.max(row + 1);
+        if let Some(usage) = &mut self.current {
+            usage.use_row(row);
+            usage.fixed_columns.insert(column.index());
+        }
+        Ok(())
+    }
+
+    fn copy(&mut self, _: Column<Any>, _: usize, _: Column<Any>, _: usize) -> Result<(), Error> {
+        self.copies += 1;
+        Ok(())
+    }
+
+    fn fill_from_row(
+        &mut self,
+        _: Column<Fixed>,
+        _: usize,
+        _: Value<Assigned<F>>,
+    ) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn get_challenge(&self, challenge: Challenge) -> Value<F> {
+        // Arbitrary but known, so that witness generation depending on challenges still runs
+        Value::known(F::from(0x100 + challenge.index() as u64))
+    }
+
+    fn push_namespace<NR, N>(&mut self, _: N)
+    where
+        NR: Into<String>,
+        N: FnOnce() -> NR,
+    {
+    }
+
+    fn pop_namespace(&mut self, _: Option<String>) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use halo2_proofs::{
+        circuit::{Layouter, SimpleFloorPlanner},
+        halo2curves::bn256::Fr,
+        plonk::TableColumn,
+        poly::Rotation,
+    };
+
+    #[derive(Clone)]
+    struct SquareConfig {
+        q_enable: Selector,
+        value: Column<Advice>,
+        square: Column<Advice>,
+        range: TableColumn,
+    }
+
+    #[derive(Default)]
+    struct SquareCircuit(Vec<u64>);
+
+    impl Circuit<Fr> for SquareCircuit {
+        type Config = SquareConfig;
+        type FloorPlanner = SimpleFloorPlanner;
+
+        fn without_witnesses(&self) -> Self {
+            Self::default()
+        }
+
+        fn configure(meta: &mut ConstraintSystem<Fr>) -> Self::Config {
+            let q_enable = meta.complex_selector();
+            let value = meta.advice_column();
+            let square = meta.advice_column();
+            let range = meta.lookup_table_column();
+            meta.enable_equality(value);
+
+            meta.create_gate("square", |meta| {
+                let q_enable = meta.query_selector(q_enable);
+                let value = meta.query_advice(value, Rotation::cur());
+                let square = meta.query_advice(square, Rotation::cur());
+                vec![q_enable * (square - value.clone() * value)]
+            });
+            meta.lookup("value range", |meta| {
+                let q_enable = meta.query_selector(q_enable);
+                let value = meta.query_advice(value, Rotation::cur());
+                vec![(q_enable * value, range)]
+            });
+
+            SquareConfig {
+                q_enable,
+                value,
+                square,
+                range,
+            }
+        }
+
+        fn synthesize(
+            &self,
+            config: Self::Config,
+            mut layouter: impl Layouter<Fr>,
+        ) -> Result<(), Error> {
+            layouter.assign_table(
+                || "range",
+                |mut table| {
+                    for i in 0..16 {
+                        table.assign_cell(
+                            || "range",
+                            config.range,
+                            i,
+                            || Value::known(Fr::from(i as u64)),
+                        )?;
+                    }
+                    Ok(())
+                },
+            )?;
+            layouter.assign_region(
+                || "squares",
+                |mut region| {
+                    let mut cells = vec![];
+                    for (offset, value) in self.0.iter().enumerate() {
+                        config.q_enable.enable(&mut region, offset)?;
+                        cells.push(region.assign_advice(
+                            || "value",
+                            config.value,
+                            offset,
+                            || Value::known(Fr::from(*value)),
+                        )?);
+                        region.assign_advice(
+                            || "square",
+                            config.square,
+                            offset,
+                            || Value::known(Fr::from(value * value)),
+                        )?;
+                    }
+                    for pair in cells.windows(2) {
+                        region.constrain_equal(pair[0].cell(), pair[1].cell())?;
+                    }
+                    Ok(())
+                },
+            )
+        }
+    }
+
+    #[test]
+    fn circuit_report_of_squares() {
+        let report = CircuitReport::synthesize("squares", &SquareCircuit(vec![3; 5])).unwrap();
+
+        assert_eq!(report.columns.advice, vec![2]);
+        assert_eq!(report.columns.selectors, 1);
+        assert_eq!(report.gate_summary().get("square"), Some(&(1, 3)));
+        assert_eq!(report.lookups.len(), 1);
+        assert_eq!(report.lookups[0].degree, 5);
+        assert_eq!(report.degree, 5);
+        assert_eq!(report.permutation.columns, 1);
+        assert_eq!(report.permutation.copies, Some(4));
+        assert_eq!(report.advice_rows, Some(5));
+        // the range table spans 16 rows
+        assert_eq!(report.k, Some(log2_ceil(16 + report.blinding_factors + 1)));
+
+        let squares = report.regions.iter().find(|r| r.name == "squares").unwrap();
+        assert_eq!(squares.rows, 5);
+        assert_eq!(squares.advice_columns, 2);
+        assert_eq!(squares.advice_cells, 10);
+    }
+}
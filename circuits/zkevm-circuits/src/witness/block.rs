@@ -13,8 +13,8 @@ use crate::{
 };
 use bus_mapping::{
     circuit_input_builder::{
-        self, BigModExp, CircuitsParams, CopyEvent, EcAddOp, EcMulOp, EcPairingOp, ExpEvent,
-        PrecompileEvents, SHA256,
+        self, BigModExp, Bls12G1AddOp, CircuitsParams, CopyEvent, EcAddOp, EcMulOp, EcPairingOp,
+        ExpEvent, PrecompileEvents, SHA256,
     },
     Error,
 };
@@ -111,9 +111,18 @@ impl Block {
         }
         post_state_root_in_trie
     }
-    /// Replay mpt updates to generate mpt witness
-    pub fn apply_mpt_updates(&mut self, mpt_state: &MptState) {
-        self.mpt_updates.fill_state_roots(mpt_state);
+    /// Replay mpt updates to generate mpt witness, adding the trie nodes created by the updates
+    /// to `mpt_state` so that it can switch to the post state root
+    pub fn apply_mpt_updates(&mut self, mpt_state: &mut MptState) {
+        let nodes = self.mpt_updates.fill_state_roots(mpt_state);
+        mpt_state.add_nodes(nodes.iter().map(Vec::as_slice));
+    }
+    /// Replay mpt updates on a mock trie holding their old values, for tests and benchmarks which
+    /// have no zktrie state for the block
+    #[cfg(any(feature = "test", test))]
+    #[allow(deprecated)]
+    pub fn apply_mpt_updates_on_mock_prestate(&mut self) {
+        self.mpt_updates.build_prestate_trie();
     }
     /// For each tx, for each step, print the rwc at the beginning of the step,
     /// and all the rw operations of the step.
@@ -139,6 +148,7 @@ impl Block {
             .map(|tx| tx.sign_data())
             .filter_map(|res| res.ok())
             .collect::<Vec<SignData>>();
+        signatures.extend(self.get_authorization_sign_data());
         signatures.extend_from_slice(&self.precompile_events.get_ecrecover_events());
         if padding && self.txs.len() < self.circuits_params.max_txs {
             // padding tx's sign data
@@ -147,6 +157,16 @@ impl Block {
         signatures
     }
 
+    /// Get signatures of the EIP7702 authorizations in this block whose signature values are in
+    /// range, as looked up by the TxAuthorization steps.
+    pub(crate) fn get_authorization_sign_data(&self) -> Vec<SignData> {
+        self.txs
+            .iter()
+            .flat_map(|tx| tx.authorization_list.iter())
+            .filter_map(|authorization| authorization.sign_data())
+            .collect()
+    }
+
     /// Get EcAdd operations from all precompiled contract calls in this block.
     pub(crate) fn get_ec_add_ops(&self) -> Vec<EcAddOp> {
         self.precompile_events.get_ec_add_events()
@@ -162,6 +182,11 @@ impl Block {
         self.precompile_events.get_ec_pairing_events()
     }
 
+    /// Get BLS12-381 G1Add operations from all precompiled contract calls in this block.
+    pub(crate) fn get_bls12_g1_add_ops(&self) -> Vec<Bls12G1AddOp> {
+        self.precompile_events.get_bls12_g1_add_events()
+    }
+
     /// Get BigModexp operations from all precompiled contract calls in this block.
     pub(crate) fn get_big_modexp(&self) -> Vec<BigModExp> {
         self.precompile_events.get_modexp_events()
@@ -351,6 +376,8 @@ pub struct BlockContext {
     pub difficulty: Word,
     /// The base fee, the minimum amount of gas fee for a transaction
     pub base_fee: Word,
+    /// The blob base fee, derived from the excess blob gas (EIP-4844)
+    pub blob_base_fee: Word,
     /// The hash of previous blocks
     pub history_hashes: Vec<Word>,
     /// The chain id
@@ -425,6 +452,13 @@ impl BlockContext {
                     Value::known(current_block_number),
                     Value::known(F::from(num_all_txs)),
                 ],
+                [
+                    Value::known(F::from(BlockContextFieldTag::BlobBaseFee as u64)),
+                    Value::known(current_block_number),
+                    randomness.map(|randomness| {
+                        rlc::value(&self.blob_base_fee.to_le_bytes(), randomness)
+                    }),
+                ],
             ],
             self.block_hash_assignments(randomness),
         ]
@@ -479,6 +513,7 @@ impl From<&circuit_input_builder::Blocks> for BlockContexts {
                             timestamp: block.timestamp,
                             difficulty: block.difficulty,
                             base_fee: block.base_fee,
+                            blob_base_fee: block.blob_base_fee,
                             history_hashes: block.history_hashes.clone(),
                             chain_id: block.chain_id,
                             parent_hash: block.parent_hash,
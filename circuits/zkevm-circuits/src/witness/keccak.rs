@@ -27,6 +27,10 @@ pub fn keccak_inputs(block: &Block) -> Result<Vec<Vec<u8>>, Error> {
         "keccak total len after ecrecover: {}",
         keccak_inputs.iter().map(|i| i.len()).sum::<usize>()
     );
+    // EIP7702 authorizations
+    keccak_inputs.extend_from_slice(&keccak_inputs_sign_verify(
+        &block.get_authorization_sign_data(),
+    ));
     // PI circuit
     keccak_inputs.extend(keccak_inputs_pi_circuit(
         block.chain_id,
@@ -130,6 +134,7 @@ fn keccak_inputs_pi_circuit(
                 .chain(block.base_fee.to_be_bytes())
                 .chain(block.gas_limit.to_be_bytes())
                 .chain(num_txs.to_be_bytes())
+                .chain(block.blob_base_fee.to_be_bytes())
         }))
         // Tx Hashes
         .chain(
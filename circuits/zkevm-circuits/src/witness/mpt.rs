@@ -11,7 +11,7 @@ use mpt_circuits::{serde::SMTTrace, MPTProofType};
 use mpt_zktrie::{state, state::builder::init_hash_scheme};
 use serde::{Deserialize, Serialize};
 pub use state::ZktrieState;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 
 #[cfg(test)]
 mod test;
@@ -173,7 +173,9 @@ impl MptUpdates {
         self.pretty_print();
     }
 
-    pub(crate) fn fill_state_roots(&mut self, init_trie: &ZktrieState) {
+    /// Replay the updates on `init_trie`, returning the trie nodes on the paths of the updated
+    /// keys afterwards, which include every node created by the updates.
+    pub(crate) fn fill_state_roots(&mut self, init_trie: &ZktrieState) -> Vec<Vec<u8>> {
         let root_pair = (self.old_root, self.new_root);
         self.old_root = U256::from_big_endian(init_trie.root());
         log::trace!("fill_state_roots init {:?}", init_trie.root());
@@ -223,6 +225,37 @@ impl MptUpdates {
         }
         log::debug!("fill_state_roots done");
         self.pretty_print();
+
+        self.updated_nodes(&wit_gen)
+    }
+
+    fn updated_nodes(&self, wit_gen: &WitnessGenerator) -> Vec<Vec<u8>> {
+        let mut addresses = BTreeSet::new();
+        let mut storage_keys = BTreeSet::new();
+        for key in self.updates.keys() {
+            match *key {
+                Key::Account { address, .. } => {
+                    addresses.insert(address);
+                }
+                Key::AccountStorage {
+                    address,
+                    storage_key,
+                    ..
+                } => {
+                    addresses.insert(address);
+                    storage_keys.insert((address, storage_key));
+                }
+            }
+        }
+        addresses
+            .into_iter()
+            .flat_map(|address| wit_gen.account_proof(address))
+            .chain(
+                storage_keys
+                    .into_iter()
+                    .flat_map(|(address, key)| wit_gen.storage_proof(address, key)),
+            )
+            .collect()
     }
 
     fn fill_state_roots_from_generator(
@@ -45,7 +45,7 @@ pub struct WitnessGenerator {
 impl From<&ZktrieState> for WitnessGenerator {
     fn from(state: &ZktrieState) -> Self {
         Self {
-            trie: state.zk_db.borrow_mut().new_trie(&state.trie_root).unwrap(),
+            trie: state.zk_db().new_trie(&state.trie_root).unwrap(),
             storages_cache: HashMap::new(),
         }
     }
@@ -136,6 +136,23 @@ pub enum Tag {
     // L1MsgHash
     /// Sender
     Sender,
+
+    // EIP-4844
+    /// Max fee per blob gas
+    MaxFeePerBlobGas,
+    /// Versioned hash in blob_versioned_hashes
+    BlobVersionedHash,
+
+    // EIP-7702
+    // The y_parity, r and s of an authorization tuple reuse the SigV, SigR and SigS tags, and
+    // are told apart from the tx signature by a non-zero access_list_idx. This keeps the number
+    // of tags within the 5 bits of the tag column.
+    /// Chain ID of an authorization tuple
+    AuthChainId,
+    /// Delegate address of an authorization tuple
+    AuthAddress,
+    /// Authority nonce of an authorization tuple
+    AuthNonce,
 }
 
 impl From<Tag> for usize {
@@ -178,6 +195,16 @@ impl Tag {
     pub fn is_access_list_storage_key(&self) -> bool {
         matches!(self, Self::AccessListStorageKey)
     }
+
+    /// If the tag is BlobVersionedHash
+    pub fn is_blob_versioned_hash(&self) -> bool {
+        matches!(self, Self::BlobVersionedHash)
+    }
+
+    /// If the tag is AuthChainId, i.e. the first field of an authorization tuple
+    pub fn is_auth_chain_id(&self) -> bool {
+        matches!(self, Self::AuthChainId)
+    }
 }
 
 /// RLP tags
@@ -219,13 +246,15 @@ use crate::{
     witness::{
         l1_msg,
         Format::{
-            TxHashEip155, TxHashEip1559, TxHashEip2930, TxHashPreEip155, TxSignEip155,
-            TxSignEip1559, TxSignEip2930, TxSignPreEip155,
+            TxHashEip155, TxHashEip1559, TxHashEip2930, TxHashEip4844, TxHashEip7702,
+            TxHashPreEip155, TxSignEip155, TxSignEip1559, TxSignEip2930, TxSignEip4844,
+            TxSignEip7702, TxSignPreEip155,
         },
         Tag::{
-            AccessListAddress, AccessListStorageKey, BeginObject, BeginVector, ChainId, Data,
-            EndObject, EndVector, Gas, GasPrice, MaxFeePerGas, MaxPriorityFeePerGas, Nonce, SigR,
-            SigS, SigV, To, TxType, Value as TxValue, Zero1, Zero2,
+            AccessListAddress, AccessListStorageKey, AuthAddress, AuthChainId, AuthNonce,
+            BeginObject, BeginVector, BlobVersionedHash, ChainId, Data, EndObject, EndVector, Gas,
+            GasPrice, MaxFeePerBlobGas, MaxFeePerGas, MaxPriorityFeePerGas, Nonce, SigR, SigS,
+            SigV, To, TxType, Value as TxValue, Zero1, Zero2,
         },
     },
 };
@@ -545,6 +574,198 @@ pub fn eip1559_tx_sign_rom_table_rows() -> Vec<RomTableRow> {
         .collect()
 }
 
+/// The fields shared by the sign and hash formats of EIP-4844 tx, which are
+/// the EIP-1559 fields followed by `max_fee_per_blob_gas` and `blob_versioned_hashes`.
+fn eip4844_tx_common_rom_table_rows() -> Vec<(Tag, Tag, usize, Vec<usize>)> {
+    vec![
+        (TxType, BeginObject, 1, vec![1]),
+        (BeginObject, ChainId, MAX_TAG_LENGTH_OF_LIST, vec![2]),
+        (ChainId, Nonce, N_BYTES_U64, vec![3]),
+        (Nonce, MaxPriorityFeePerGas, N_BYTES_U64, vec![4]),
+        (MaxPriorityFeePerGas, MaxFeePerGas, N_BYTES_WORD, vec![5]),
+        (MaxFeePerGas, Gas, N_BYTES_WORD, vec![6]),
+        (Gas, To, N_BYTES_U64, vec![7]),
+        (To, TxValue, N_BYTES_ACCOUNT_ADDRESS, vec![8]),
+        (TxValue, Data, N_BYTES_WORD, vec![9]),
+        (Data, BeginVector, N_BYTES_CALLDATA, vec![10, 11]),
+        (BeginVector, EndVector, MAX_TAG_LENGTH_OF_LIST, vec![21]), // access_list is none
+        (BeginVector, BeginObject, MAX_TAG_LENGTH_OF_LIST, vec![12]),
+        (
+            BeginObject,
+            AccessListAddress,
+            MAX_TAG_LENGTH_OF_LIST,
+            vec![13],
+        ),
+        (
+            AccessListAddress,
+            BeginVector,
+            N_BYTES_ACCOUNT_ADDRESS,
+            vec![14, 15],
+        ),
+        (BeginVector, EndVector, MAX_TAG_LENGTH_OF_LIST, vec![18]), /* access_list.storage_keys
+                                                                     * is none */
+        (
+            BeginVector,
+            AccessListStorageKey,
+            MAX_TAG_LENGTH_OF_LIST,
+            vec![16, 17],
+        ),
+        (AccessListStorageKey, EndVector, N_BYTES_WORD, vec![18]), // finished parsing storage keys
+        (
+            AccessListStorageKey,
+            AccessListStorageKey,
+            N_BYTES_WORD,
+            vec![16, 17],
+        ), // keep parsing storage_keys
+        (EndVector, EndObject, 0, vec![19, 20]),
+        (EndObject, EndVector, 0, vec![21]), // finished parsing access_list
+        (EndObject, BeginObject, 0, vec![12]), // parse another access_list entry
+        (EndVector, MaxFeePerBlobGas, 0, vec![22]),
+        (MaxFeePerBlobGas, BeginVector, N_BYTES_WORD, vec![23, 24]),
+        // blob_versioned_hashes is none
+        (BeginVector, EndVector, MAX_TAG_LENGTH_OF_LIST, vec![27]),
+        (
+            BeginVector,
+            BlobVersionedHash,
+            MAX_TAG_LENGTH_OF_LIST,
+            vec![25, 26],
+        ),
+        (BlobVersionedHash, EndVector, N_BYTES_WORD, vec![27]), // finished parsing blob hashes
+        // keep parsing blob hashes
+        (
+            BlobVersionedHash,
+            BlobVersionedHash,
+            N_BYTES_WORD,
+            vec![25, 26],
+        ),
+    ]
+}
+
+pub fn eip4844_tx_sign_rom_table_rows() -> Vec<RomTableRow> {
+    let mut rows = eip4844_tx_common_rom_table_rows();
+    rows.extend([
+        (EndVector, EndObject, 0, vec![28]),
+        (EndObject, EndObject, 0, vec![29]),
+        // used to emit TxGasCostInL1
+        (EndObject, BeginObject, 0, vec![]),
+    ]);
+
+    rows.into_iter()
+        .map(|row| (row.0, row.1, row.2, TxSignEip4844, row.3).into())
+        .collect()
+}
+
+pub fn eip4844_tx_hash_rom_table_rows() -> Vec<RomTableRow> {
+    let mut rows = eip4844_tx_common_rom_table_rows();
+    rows.extend([
+        (EndVector, SigV, 0, vec![28]),
+        (SigV, SigR, N_BYTES_U64, vec![29]),
+        (SigR, SigS, N_BYTES_WORD, vec![30]),
+        (SigS, EndObject, N_BYTES_WORD, vec![31]),
+        (EndObject, EndObject, 0, vec![32]),
+        // used to exit TxGasCostInL1
+        (EndObject, BeginObject, 0, vec![]),
+    ]);
+
+    rows.into_iter()
+        .map(|row| (row.0, row.1, row.2, TxHashEip4844, row.3).into())
+        .collect()
+}
+
+/// The fields shared by the sign and hash formats of EIP-7702 tx, which are
+/// the EIP-1559 fields followed by `authorization_list`. Each authorization
+/// tuple is `[chain_id, address, nonce, y_parity, r, s]`, where the chain id
+/// is limited to 8 bytes.
+fn eip7702_tx_common_rom_table_rows() -> Vec<(Tag, Tag, usize, Vec<usize>)> {
+    vec![
+        (TxType, BeginObject, 1, vec![1]),
+        (BeginObject, ChainId, MAX_TAG_LENGTH_OF_LIST, vec![2]),
+        (ChainId, Nonce, N_BYTES_U64, vec![3]),
+        (Nonce, MaxPriorityFeePerGas, N_BYTES_U64, vec![4]),
+        (MaxPriorityFeePerGas, MaxFeePerGas, N_BYTES_WORD, vec![5]),
+        (MaxFeePerGas, Gas, N_BYTES_WORD, vec![6]),
+        (Gas, To, N_BYTES_U64, vec![7]),
+        (To, TxValue, N_BYTES_ACCOUNT_ADDRESS, vec![8]),
+        (TxValue, Data, N_BYTES_WORD, vec![9]),
+        (Data, BeginVector, N_BYTES_CALLDATA, vec![10, 11]),
+        (BeginVector, EndVector, MAX_TAG_LENGTH_OF_LIST, vec![21]), // access_list is none
+        (BeginVector, BeginObject, MAX_TAG_LENGTH_OF_LIST, vec![12]),
+        (
+            BeginObject,
+            AccessListAddress,
+            MAX_TAG_LENGTH_OF_LIST,
+            vec![13],
+        ),
+        (
+            AccessListAddress,
+            BeginVector,
+            N_BYTES_ACCOUNT_ADDRESS,
+            vec![14, 15],
+        ),
+        (BeginVector, EndVector, MAX_TAG_LENGTH_OF_LIST, vec![18]), /* access_list.storage_keys
+                                                                     * is none */
+        (
+            BeginVector,
+            AccessListStorageKey,
+            MAX_TAG_LENGTH_OF_LIST,
+            vec![16, 17],
+        ),
+        (AccessListStorageKey, EndVector, N_BYTES_WORD, vec![18]), // finished parsing storage keys
+        (
+            AccessListStorageKey,
+            AccessListStorageKey,
+            N_BYTES_WORD,
+            vec![16, 17],
+        ), // keep parsing storage_keys
+        (EndVector, EndObject, 0, vec![19, 20]),
+        (EndObject, EndVector, 0, vec![21]), // finished parsing access_list
+        (EndObject, BeginObject, 0, vec![12]), // parse another access_list entry
+        (EndVector, BeginVector, 0, vec![22, 23]),
+        (BeginVector, EndVector, MAX_TAG_LENGTH_OF_LIST, vec![33]), // authorization_list is none
+        (BeginVector, BeginObject, MAX_TAG_LENGTH_OF_LIST, vec![24]),
+        (BeginObject, AuthChainId, MAX_TAG_LENGTH_OF_LIST, vec![25]),
+        (AuthChainId, AuthAddress, N_BYTES_U64, vec![26]),
+        (AuthAddress, AuthNonce, N_BYTES_ACCOUNT_ADDRESS, vec![27]),
+        (AuthNonce, SigV, N_BYTES_U64, vec![28]),
+        (SigV, SigR, N_BYTES_U64, vec![29]),
+        (SigR, SigS, N_BYTES_WORD, vec![30]),
+        (SigS, EndObject, N_BYTES_WORD, vec![31, 32]),
+        (EndObject, EndVector, 0, vec![33]), // finished parsing authorization_list
+        (EndObject, BeginObject, 0, vec![24]), // parse another authorization tuple
+    ]
+}
+
+pub fn eip7702_tx_sign_rom_table_rows() -> Vec<RomTableRow> {
+    let mut rows = eip7702_tx_common_rom_table_rows();
+    rows.extend([
+        (EndVector, EndObject, 0, vec![34]),
+        (EndObject, EndObject, 0, vec![35]),
+        // used to emit TxGasCostInL1
+        (EndObject, BeginObject, 0, vec![]),
+    ]);
+
+    rows.into_iter()
+        .map(|row| (row.0, row.1, row.2, TxSignEip7702, row.3).into())
+        .collect()
+}
+
+pub fn eip7702_tx_hash_rom_table_rows() -> Vec<RomTableRow> {
+    let mut rows = eip7702_tx_common_rom_table_rows();
+    rows.extend([
+        (EndVector, SigV, 0, vec![34]),
+        (SigV, SigR, N_BYTES_U64, vec![35]),
+        (SigR, SigS, N_BYTES_WORD, vec![36]),
+        (SigS, EndObject, N_BYTES_WORD, vec![37]),
+        (EndObject, EndObject, 0, vec![38]),
+        // used to exit TxGasCostInL1
+        (EndObject, BeginObject, 0, vec![]),
+    ]);
+
+    rows.into_iter()
+        .map(|row| (row.0, row.1, row.2, TxHashEip7702, row.3).into())
+        .collect()
+}
+
 /// Read-only Memory table row.
 #[derive(Debug, Clone)]
 pub struct RomTableRow {
@@ -603,6 +824,14 @@ pub enum Format {
     TxHashEip2930,
     /// L1 Msg
     L1MsgHash,
+    /// Sign for EIP4844 tx
+    TxSignEip4844,
+    /// Hash for EIP4844 tx
+    TxHashEip4844,
+    /// Sign for EIP7702 tx
+    TxSignEip7702,
+    /// Hash for EIP7702 tx
+    TxHashEip7702,
 }
 
 impl From<Format> for usize {
@@ -629,6 +858,10 @@ impl Format {
             TxSignEip2930 => eip2930_tx_sign_rom_table_rows(),
             TxHashEip2930 => eip2930_tx_hash_rom_table_rows(),
             Self::L1MsgHash => l1_msg::rom_table_rows(),
+            TxSignEip4844 => eip4844_tx_sign_rom_table_rows(),
+            TxHashEip4844 => eip4844_tx_hash_rom_table_rows(),
+            TxSignEip7702 => eip7702_tx_sign_rom_table_rows(),
+            TxHashEip7702 => eip7702_tx_hash_rom_table_rows(),
         }
     }
 }
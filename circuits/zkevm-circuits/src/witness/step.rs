@@ -196,6 +196,8 @@ impl From<&circuit_input_builder::ExecStep> for ExecutionState {
                     OpcodeId::GAS => ExecutionState::GAS,
                     OpcodeId::SAR => ExecutionState::SAR,
                     OpcodeId::SELFBALANCE => ExecutionState::SELFBALANCE,
+                    OpcodeId::BLOBHASH => ExecutionState::BLOBHASH,
+                    OpcodeId::BLOBBASEFEE => ExecutionState::BLOBBASEFEE,
                     OpcodeId::SHA3 => ExecutionState::SHA3,
                     OpcodeId::SHL | OpcodeId::SHR => ExecutionState::SHL_SHR,
                     OpcodeId::SLOAD => ExecutionState::SLOAD,
@@ -235,8 +237,11 @@ impl From<&circuit_input_builder::ExecStep> for ExecutionState {
                 PrecompileCalls::Bn128Mul => ExecutionState::PrecompileBn256ScalarMul,
                 PrecompileCalls::Bn128Pairing => ExecutionState::PrecompileBn256Pairing,
                 PrecompileCalls::Blake2F => ExecutionState::PrecompileBlake2f,
+                PrecompileCalls::PointEvaluation => ExecutionState::PrecompilePointEvaluation,
+                PrecompileCalls::Bls12G1Add => ExecutionState::PrecompileBls12G1Add,
             },
             circuit_input_builder::ExecState::BeginTx => ExecutionState::BeginTx,
+            circuit_input_builder::ExecState::TxAuthorization => ExecutionState::TxAuthorization,
             circuit_input_builder::ExecState::EndTx => ExecutionState::EndTx,
             circuit_input_builder::ExecState::EndBlock => ExecutionState::EndBlock,
             circuit_input_builder::ExecState::Padding => ExecutionState::Padding,
@@ -6,8 +6,9 @@ use crate::{
         rlp_fsm::{RlpStackOp, SmState},
         DataTable, Format,
         Format::{
-            L1MsgHash, TxHashEip155, TxHashEip1559, TxHashEip2930, TxHashPreEip155, TxSignEip155,
-            TxSignEip1559, TxSignEip2930, TxSignPreEip155,
+            L1MsgHash, TxHashEip155, TxHashEip1559, TxHashEip2930, TxHashEip4844, TxHashEip7702,
+            TxHashPreEip155, TxSignEip155, TxSignEip1559, TxSignEip2930, TxSignEip4844,
+            TxSignEip7702, TxSignPreEip155,
         },
         RlpFsmWitnessGen, RlpFsmWitnessRow, RlpTable, RlpTag, State,
         State::DecodeTagStart,
@@ -17,8 +18,11 @@ use crate::{
 };
 use bus_mapping::circuit_input_builder::{self, get_dummy_tx_hash, TxL1Fee};
 use eth_types::{
-    evm_types::gas_utils::{tx_access_list_gas_cost, tx_data_gas_cost},
-    geth_types::{access_list_size, TxType, TxType::PreEip155},
+    evm_types::{
+        block_utils::GAS_PER_BLOB,
+        gas_utils::{tx_access_list_gas_cost, tx_data_gas_cost},
+    },
+    geth_types::{access_list_size, SetCodeAuthorization, TxType, TxType::PreEip155},
     sign_types::{
         biguint_to_32bytes_le, ct_option_ok_or, get_dummy_tx, recover_pk2, SignData, SECP256K1_Q,
     },
@@ -97,6 +101,12 @@ pub struct Transaction {
     pub l1_fee_committed: TxL1Fee,
     /// Optional access list for EIP-2930
     pub access_list: Option<AccessList>,
+    /// Max fee per blob gas (EIP-4844)
+    pub max_fee_per_blob_gas: Word,
+    /// Versioned hashes of the blobs (EIP-4844)
+    pub blob_versioned_hashes: Vec<H256>,
+    /// Authorization list (EIP-7702)
+    pub authorization_list: Vec<SetCodeAuthorization>,
     /// The calls made in the transaction
     pub calls: Vec<Call>,
     /// The steps executioned in the transaction
@@ -131,6 +141,11 @@ impl Transaction {
         }
     }
 
+    /// Blob gas of the transaction, zero unless it is an EIP-4844 tx
+    pub fn blob_gas(&self) -> u64 {
+        GAS_PER_BLOB * self.blob_versioned_hashes.len() as u64
+    }
+
     /// Return whether the transaction is included in the chunk txbytes (not l1Msg and not padding)
     pub fn is_chunk_l2_tx(&self) -> bool {
         self.tx_type != TxType::L1Msg && !self.caller_address.is_zero()
@@ -385,6 +400,29 @@ impl Transaction {
                 }),
                 Value::known(F::zero()),
             ],
+            [
+                Value::known(F::from(self.id as u64)),
+                Value::known(F::from(TxContextFieldTag::MaxFeePerBlobGas as u64)),
+                Value::known(F::zero()),
+                challenges.evm_word().map(|challenge| {
+                    rlc::value(&self.max_fee_per_blob_gas.to_le_bytes(), challenge)
+                }),
+                Value::known(F::zero()),
+            ],
+            [
+                Value::known(F::from(self.id as u64)),
+                Value::known(F::from(TxContextFieldTag::BlobVersionedHashesLen as u64)),
+                Value::known(F::zero()),
+                Value::known(F::from(self.blob_versioned_hashes.len() as u64)),
+                Value::known(F::zero()),
+            ],
+            [
+                Value::known(F::from(self.id as u64)),
+                Value::known(F::from(TxContextFieldTag::AuthorizationListLen as u64)),
+                Value::known(F::zero()),
+                Value::known(F::from(self.authorization_list.len() as u64)),
+                Value::known(F::zero()),
+            ],
             [
                 Value::known(F::from(self.id as u64)),
                 Value::known(F::from(TxContextFieldTag::BlockNumber as u64)),
@@ -451,6 +489,77 @@ impl Transaction {
         assignments
     }
 
+    /// Assignments for tx table blob versioned hashes
+    pub fn table_assignments_blob_hashes_dyn<F: Field>(
+        &self,
+        challenges: Challenges<Value<F>>,
+    ) -> Vec<[Value<F>; 5]> {
+        self.blob_versioned_hashes
+            .iter()
+            .enumerate()
+            .map(|(idx, hash)| {
+                [
+                    Value::known(F::from(self.id as u64)),
+                    Value::known(F::from(TxContextFieldTag::BlobVersionedHash as u64)),
+                    // Blob versioned hash index starts from 1 in tx-table.
+                    Value::known(F::from((idx + 1) as u64)),
+                    rlc_be_bytes(&hash.to_fixed_bytes(), challenges.evm_word()),
+                    Value::known(F::zero()),
+                ]
+            })
+            .collect()
+    }
+
+    /// Assignments for tx table authorization tuples, six rows per tuple
+    pub fn table_assignments_authorizations_dyn<F: Field>(
+        &self,
+        challenges: Challenges<Value<F>>,
+    ) -> Vec<[Value<F>; 5]> {
+        self.authorization_list
+            .iter()
+            .enumerate()
+            .flat_map(|(idx, auth)| {
+                [
+                    (
+                        TxContextFieldTag::AuthorizationChainId,
+                        // the RLP circuit limits the chain id to 8 bytes
+                        Value::known(F::from(auth.chain_id.low_u64())),
+                    ),
+                    (
+                        TxContextFieldTag::AuthorizationAddress,
+                        Value::known(auth.address.to_scalar().unwrap()),
+                    ),
+                    (
+                        TxContextFieldTag::AuthorizationNonce,
+                        Value::known(F::from(auth.nonce.as_u64())),
+                    ),
+                    (
+                        TxContextFieldTag::AuthorizationYParity,
+                        Value::known(F::from(auth.y_parity.as_u64())),
+                    ),
+                    (
+                        TxContextFieldTag::AuthorizationR,
+                        rlc_be_bytes(&auth.r.to_be_bytes(), challenges.evm_word()),
+                    ),
+                    (
+                        TxContextFieldTag::AuthorizationS,
+                        rlc_be_bytes(&auth.s.to_be_bytes(), challenges.evm_word()),
+                    ),
+                ]
+                .map(|(tag, value)| {
+                    [
+                        Value::known(F::from(self.id as u64)),
+                        Value::known(F::from(tag as u64)),
+                        // Authorization index starts from 1 in tx-table.
+                        Value::known(F::from((idx + 1) as u64)),
+                        value,
+                        Value::known(F::zero()),
+                    ]
+                })
+            })
+            .collect()
+    }
+
     pub(crate) fn gen_rlp_witness<F: Field>(
         &self,
         is_hash: bool,
@@ -465,6 +574,8 @@ impl Transaction {
                     TxType::Eip1559 => TxHashEip1559,
                     TxType::L1Msg => L1MsgHash,
                     TxType::Eip2930 => TxHashEip2930,
+                    TxType::Eip4844 => TxHashEip4844,
+                    TxType::Eip7702 => TxHashEip7702,
                 },
             )
         } else {
@@ -475,7 +586,9 @@ impl Transaction {
                     TxType::PreEip155 => TxSignPreEip155,
                     TxType::Eip1559 => TxSignEip1559,
                     TxType::Eip2930 => TxSignEip2930,
-                    _ => unreachable!("tx type {:?} not supported", self.tx_type),
+                    TxType::Eip4844 => TxSignEip4844,
+                    TxType::Eip7702 => TxSignEip7702,
+                    TxType::L1Msg => unreachable!("tx type {:?} not supported", self.tx_type),
                 },
             )
         };
@@ -544,17 +657,23 @@ impl Transaction {
         let mut is_output;
         let mut is_none;
         let mut rlp_tag;
+        // the number of items of a list at depth 2, emitted at its end
+        let mut list_len;
         let mut lb_len = 0;
         // These two variables keep track
         // unique identifier of addresses and storage keys included in access list
         let mut access_list_idx: u64 = 0;
         let mut storage_key_idx: u64 = 0;
+        // The index of blob versioned hashes. They are all at depth 2, so it does not take
+        // part in the stack ops and only shares the storage_key_idx column of the rlp table.
+        let mut blob_hash_idx: u64 = 0;
 
         loop {
             // default behavior
             is_none = false;
             is_output = false;
             rlp_tag = RlpTag::Tag(cur.tag);
+            list_len = None;
 
             let mut next = cur.clone();
             match cur.state {
@@ -585,7 +704,13 @@ impl Transaction {
                             // note: depth alone currently is sufficient to ascertain
                             // the end of an access list as there's no other nested
                             // structure at depth 2 specified in EIP standards
+                            // (an authorization list is indexed like an access list).
+                            // The end of the list emits its number of items.
+                            is_output = true;
+                            list_len = Some(access_list_idx);
                             access_list_idx = 0;
+                            // or the end of blob versioned hashes
+                            blob_hash_idx = 0;
                         } else if cur.depth == 0 {
                             // emit GasCost
                             is_output = true;
@@ -626,6 +751,15 @@ impl Transaction {
                             if cur.tag.is_access_list_storage_key() {
                                 storage_key_idx += 1;
                             }
+                            // detect start of blob versioned hash
+                            if cur.tag.is_blob_versioned_hash() {
+                                blob_hash_idx += 1;
+                            }
+                        }
+                        // detect start of authorization tuple, whose chain id may be encoded
+                        // in a single byte
+                        if cur.tag.is_auth_chain_id() {
+                            access_list_idx += 1;
                         }
 
                         if let Some(rem) = remaining_bytes.last_mut() {
@@ -939,13 +1073,19 @@ impl Transaction {
                 RlpTag::Len => cur.tag_value_acc + Value::known(F::from((cur.byte_idx + 1) as u64)),
                 RlpTag::RLC => bytes_rlc,
                 RlpTag::GasCost => gas_cost_acc,
-                RlpTag::Tag(_) => cur.tag_value_acc,
+                RlpTag::Tag(_) => {
+                    list_len.map_or(cur.tag_value_acc, |len| Value::known(F::from(len)))
+                }
                 RlpTag::Null => unreachable!("Null is not used"),
             };
             let (tag_bytes_rlc, tag_length) = match rlp_tag {
                 // Len | RLC | GasCost are just meta-info extracted from keccak input bytes
                 RlpTag::Len => (Value::known(F::zero()), cur.tag_length),
                 RlpTag::RLC | RlpTag::GasCost => (Value::known(F::zero()), 0),
+                // the end of a list at depth 2 is told apart by the tag that follows it
+                RlpTag::Tag(_) if list_len.is_some() => {
+                    (Value::known(F::from(usize::from(next.tag) as u64)), 0)
+                }
                 RlpTag::Tag(_) => (cur.tag_bytes_rlc, cur.tag_length),
                 RlpTag::Null => unreachable!("Null is not used"),
             };
@@ -961,7 +1101,8 @@ impl Transaction {
                     is_output,
                     is_none,
                     access_list_idx,
-                    storage_key_idx,
+                    // at most one of them is non-zero
+                    storage_key_idx: storage_key_idx + blob_hash_idx,
                 },
                 state_machine: StateMachine {
                     state: cur.state,
@@ -1105,7 +1246,9 @@ impl<F: Field> RlpFsmWitnessGen<F> for Transaction {
             TxType::PreEip155 => (TxHashPreEip155, Some(TxSignPreEip155)),
             TxType::Eip1559 => (TxHashEip1559, Some(TxSignEip1559)),
             TxType::Eip2930 => (TxHashEip2930, Some(TxSignEip2930)),
+            TxType::Eip4844 => (TxHashEip4844, Some(TxSignEip4844)),
             TxType::L1Msg => (L1MsgHash, None),
+            TxType::Eip7702 => (TxHashEip7702, Some(TxSignEip7702)),
         };
 
         let get_table = |rlp_bytes: &Vec<u8>, format: Format| {
@@ -1200,6 +1343,9 @@ impl From<MockTransaction> for Transaction {
             l1_fee: Default::default(),
             l1_fee_committed: Default::default(),
             access_list,
+            max_fee_per_blob_gas: Word::zero(),
+            blob_versioned_hashes: vec![],
+            authorization_list: vec![],
             calls: vec![],
             steps: vec![],
         }
@@ -1227,6 +1373,9 @@ pub(super) fn tx_convert(
         tx_data_gas_cost(&tx.rlp_bytes)
     };
 
+    let is_fee_market =
+        tx.tx_type.is_eip1559() || tx.tx_type.is_eip4844() || tx.tx_type.is_eip7702();
+
     Transaction {
         block_number: tx.block_num,
         id,
@@ -1235,12 +1384,12 @@ pub(super) fn tx_convert(
         nonce: tx.nonce,
         gas: tx.gas,
         gas_price: tx.gas_price,
-        max_fee_per_gas: if tx.tx_type.is_eip1559() {
+        max_fee_per_gas: if is_fee_market {
             tx.gas_fee_cap
         } else {
             tx.gas_price
         },
-        max_priority_fee_per_gas: if tx.tx_type.is_eip1559() {
+        max_priority_fee_per_gas: if is_fee_market {
             tx.gas_tip_cap
         } else {
             tx.gas_price
@@ -1263,6 +1412,9 @@ pub(super) fn tx_convert(
         l1_fee: tx.l1_fee,
         l1_fee_committed: tx.l1_fee_committed,
         access_list: tx.access_list.clone(),
+        max_fee_per_blob_gas: tx.max_fee_per_blob_gas,
+        blob_versioned_hashes: tx.blob_versioned_hashes.clone(),
+        authorization_list: tx.authorization_list.clone(),
         calls: tx
             .calls()
             .iter()
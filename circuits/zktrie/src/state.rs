@@ -6,21 +6,34 @@ pub use zktrie::{Hash as ZkTrieHash, ZkMemoryDb, ZkTrie, ZkTrieNode};
 
 pub mod builder;
 pub use builder::{AccountData, StorageData};
+mod nodes;
+pub use nodes::ZkTrieNodes;
+mod store;
+pub use store::{FileNodeStore, MemoryNodeStore, NodeStore};
 
-use std::{cell::RefCell, fmt, rc::Rc};
+use std::{fmt, rc::Rc, sync::Arc};
 
 /// represent a storage state being applied in specified block
+///
+/// the state is `Send + Sync` and cloning it is cheap, so a snapshot can be
+/// taken for each block and read on another thread, then merged back; the
+/// blocks of a chunk are still applied in order, as bus-mapping's `StateDB`
+/// is sequential
 #[derive(Clone)]
 pub struct ZktrieState {
-    /// The underlying db
-    pub zk_db: RefCell<Rc<ZkMemoryDb>>,
+    /// The trie nodes known by the state
+    pub nodes: ZkTrieNodes,
     /// Trie root
     pub trie_root: ZkTrieHash,
     addr_cache: HashSet<Address>,
     storage_cache: HashSet<(Address, H256)>,
 }
 
-//unsafe impl Send for ZktrieState {}
+// states are snapshotted per block and sent to other threads
+const _: fn() = || {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<ZktrieState>();
+};
 
 impl fmt::Debug for ZktrieState {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
@@ -49,13 +62,30 @@ impl ZktrieState {
         builder::init_hash_scheme();
 
         Self {
-            zk_db: RefCell::new(ZkMemoryDb::new()),
+            nodes: Default::default(),
             trie_root: state_root.0,
             addr_cache: HashSet::new(),
             storage_cache: HashSet::new(),
         }
     }
 
+    /// construct from external data, with the nodes backed by a store kept
+    /// across states: the state starts with the nodes of the store, so the
+    /// tries it has can be switched to, the ones met again in the traces are
+    /// not hashed again and the db reading the tries is shared with the other
+    /// states of the store
+    pub fn construct_with_node_store(state_root: Hash, store: Arc<dyn NodeStore>) -> Self {
+        Self {
+            nodes: ZkTrieNodes::with_store(store),
+            ..Self::construct(state_root)
+        }
+    }
+
+    /// write the nodes of the state to its node store, if any
+    pub fn persist_nodes(&self) -> Result<(), Error> {
+        self.nodes.persist()
+    }
+
     /// prepare to switch to another root state (trie snapshot)
     /// it is ok that even the db is not ready for this state
     /// cache is cleared so user can fill db with new storage traces
@@ -72,8 +102,7 @@ impl ZktrieState {
     /// new snapshot since we consider it is not need to send more nodes data
     /// from storage trace for the updated leaves
     pub fn switch_to(&mut self, new_root: ZkTrieHash) -> bool {
-        let test_trie = self.zk_db.borrow_mut().new_trie(&new_root);
-        if test_trie.is_none() {
+        if new_root != ZkTrieHash::default() && !self.nodes.contains(&new_root) {
             return false;
         }
         self.trie_root = new_root;
@@ -144,10 +173,22 @@ impl ZktrieState {
                     .flat_map(|(_, _, bytes)| bytes),
             )
             .chain(additional_proofs);
-        let mut zk_db = self.zk_db.borrow_mut();
-        for bytes in proofs {
-            zk_db.add_node_bytes(bytes).unwrap();
-        }
+        self.nodes.extend(proofs);
+    }
+
+    /// add trie nodes to the state, e.g. the ones created by applying the
+    /// updates of a block
+    pub fn add_nodes<'d>(&mut self, nodes: impl IntoIterator<Item = &'d [u8]>) {
+        self.nodes.extend(nodes);
+    }
+
+    /// merge the nodes and cached keys of another state, usually a snapshot
+    /// of this one which has been updated on another thread; the root is kept
+    pub fn merge(&mut self, other: &Self) {
+        self.nodes.merge(&other.nodes);
+        self.addr_cache.extend(other.addr_cache.iter().copied());
+        self.storage_cache
+            .extend(other.storage_cache.iter().copied());
     }
 
     /// construct from external data, with additional proofs (trie node) can be
@@ -170,8 +211,10 @@ impl ZktrieState {
         Ok(state)
     }
 
-    /// get the inner zk memory db
-    pub fn into_inner(self) -> Rc<ZkMemoryDb> {
-        self.zk_db.into_inner()
+    /// zk memory db with all the nodes of the state, local to the calling
+    /// thread; it is cached there so only the nodes added since the last call
+    /// are hashed again
+    pub fn zk_db(&self) -> Rc<ZkMemoryDb> {
+        self.nodes.to_zk_db()
     }
 }
@@ -0,0 +1,196 @@
+//! Thread-safe store of the trie nodes of a state
+use super::{NodeStore, ZkTrieHash};
+use rayon::prelude::*;
+use std::{
+    cell::RefCell,
+    collections::{HashMap, HashSet},
+    fmt, io,
+    rc::Rc,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+};
+use zktrie::{ZkMemoryDb, ZkTrieNode};
+
+/// Serialized trie nodes by their hash.
+///
+/// The store is copy-on-write: clones share the nodes until one of them is updated, so that a
+/// state can be snapshotted per block and handed to other threads. The tries are read through a
+/// [`ZkMemoryDb`] built from the store, which stays on the thread building it and is kept there
+/// for the next call, so that a node is only added (and hashed by the db) once per thread.
+///
+/// The nodes can be backed by a [`NodeStore`] kept across states: they start with the nodes of
+/// the store, nodes met in the traces which it already has are not parsed and hashed again, and
+/// the new ones are written to it by [`ZkTrieNodes::persist`]. The states of a store share its
+/// db, so the nodes of the store are only added to it once per thread, not once per state.
+#[derive(Clone)]
+pub struct ZkTrieNodes {
+    /// Shared by the clones, whose nodes only grow, so a db built for one of them can be reused
+    /// for the others. Nodes backed by a store share the db of the store instead.
+    id: u64,
+    nodes: Arc<HashMap<ZkTrieHash, Arc<[u8]>>>,
+    store: Option<Arc<dyn NodeStore>>,
+}
+
+static NEXT_NODES_ID: AtomicU64 = AtomicU64::new(0);
+
+/// What a db is built for: nodes and their clones, or all the states of a store. A db holding
+/// more nodes than needed still reads the tries of its states, since nodes are looked up by hash.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum ZkDbKey {
+    Nodes(u64),
+    Store(*const ()),
+}
+
+/// Last db built on a thread, with the hashes of the nodes added to it
+struct CachedZkDb {
+    key: ZkDbKey,
+    zk_db: Rc<ZkMemoryDb>,
+    hashes: HashSet<ZkTrieHash>,
+}
+
+thread_local! {
+    static ZK_DB: RefCell<Option<CachedZkDb>> = const { RefCell::new(None) };
+}
+
+impl Default for ZkTrieNodes {
+    fn default() -> Self {
+        Self {
+            id: NEXT_NODES_ID.fetch_add(1, Ordering::Relaxed),
+            nodes: Default::default(),
+            store: None,
+        }
+    }
+}
+
+impl fmt::Debug for ZkTrieNodes {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "ZkTrieNodes: {} nodes", self.len())?;
+        if let Some(store) = &self.store {
+            write!(f, ", backed by {store:?}")?;
+        }
+        Ok(())
+    }
+}
+
+impl ZkTrieNodes {
+    /// Nodes backed by `store`, starting with the ones it has
+    pub fn with_store(store: Arc<dyn NodeStore>) -> Self {
+        let nodes = store.nodes().into_iter().collect::<HashMap<_, _>>();
+        log::debug!("load {} trie nodes from {store:?}", nodes.len());
+        Self {
+            nodes: Arc::new(nodes),
+            store: Some(store),
+            ..Default::default()
+        }
+    }
+
+    /// The store backing the nodes
+    pub fn store(&self) -> Option<&Arc<dyn NodeStore>> {
+        self.store.as_ref()
+    }
+
+    /// Number of nodes, the ones loaded from the backing store included
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Return if no node has been added
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Return if the node of hash `hash` has been added
+    pub fn contains(&self, hash: &ZkTrieHash) -> bool {
+        self.nodes.contains_key(hash)
+    }
+
+    /// Add serialized nodes, which are parsed and hashed in parallel unless the backing store
+    /// already has them. Bytes which are not a node, like the magic bytes ending a proof, are
+    /// skipped.
+    pub fn extend<'d>(&mut self, nodes: impl IntoIterator<Item = &'d [u8]>) {
+        let store = self.store.as_deref();
+        let nodes = nodes
+            .into_iter()
+            .collect::<Vec<_>>()
+            .into_par_iter()
+            .filter_map(|bytes| {
+                let hash = match store.and_then(|store| store.node_hash(bytes)) {
+                    Some(hash) => hash,
+                    None => ZkTrieNode::parse(bytes).ok()?.node_hash(),
+                };
+                Some((hash, Arc::from(bytes)))
+            })
+            .collect::<Vec<_>>();
+        if !nodes.is_empty() {
+            Arc::make_mut(&mut self.nodes).extend(nodes);
+        }
+    }
+
+    /// Write the added nodes to the backing store, if any
+    pub fn persist(&self) -> io::Result<()> {
+        let Some(store) = &self.store else {
+            return Ok(());
+        };
+        let nodes = self
+            .nodes
+            .iter()
+            .filter(|(hash, _)| !store.contains(hash))
+            .map(|(hash, bytes)| (*hash, bytes.clone()))
+            .collect::<Vec<_>>();
+        log::debug!("persist {} new trie nodes", nodes.len());
+        store.insert(&nodes)
+    }
+
+    /// Add the nodes of `other`, e.g. a snapshot of this store which has been updated since.
+    pub fn merge(&mut self, other: &Self) {
+        if Arc::ptr_eq(&self.nodes, &other.nodes) {
+            return;
+        }
+        let nodes = Arc::make_mut(&mut self.nodes);
+        for (hash, bytes) in other.nodes.iter() {
+            nodes.entry(*hash).or_insert_with(|| bytes.clone());
+        }
+    }
+
+    fn zk_db_key(&self) -> ZkDbKey {
+        match &self.store {
+            Some(store) => ZkDbKey::Store(Arc::as_ptr(store) as *const ()),
+            None => ZkDbKey::Nodes(self.id),
+        }
+    }
+
+    /// In-memory db with the added nodes, i.e. every node met in the traces of the state so far.
+    ///
+    /// The db last built on the calling thread is reused if it was built for these nodes, one of
+    /// their clones or, for nodes backed by a store, any state of the store, and only the nodes it
+    /// misses are added. It is built again if it misses nodes while a trie still holds it, since
+    /// nodes can only be added to a db nothing else refers to.
+    pub fn to_zk_db(&self) -> Rc<ZkMemoryDb> {
+        let key = self.zk_db_key();
+        ZK_DB.with(|cache| {
+            let mut cache = cache.borrow_mut();
+            let cached = match &mut *cache {
+                Some(cached) if cached.key == key => cached,
+                cache => cache.insert(CachedZkDb {
+                    key,
+                    zk_db: ZkMemoryDb::new(),
+                    hashes: HashSet::new(),
+                }),
+            };
+            if self.nodes.keys().any(|hash| !cached.hashes.contains(hash)) {
+                if Rc::strong_count(&cached.zk_db) > 1 {
+                    cached.zk_db = ZkMemoryDb::new();
+                    cached.hashes.clear();
+                }
+                for (hash, bytes) in self.nodes.iter() {
+                    if cached.hashes.insert(*hash) {
+                        cached.zk_db.add_node_bytes(bytes).unwrap();
+                    }
+                }
+            }
+            cached.zk_db.clone()
+        })
+    }
+}
@@ -0,0 +1,395 @@
+//! Stores keeping the trie nodes across states
+use super::{builder, ZkTrieHash, ZkTrieNode};
+use std::{
+    collections::{HashMap, VecDeque},
+    fmt,
+    fs::{self, File, OpenOptions},
+    io::{self, BufWriter, Read, Write},
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex, RwLock},
+};
+
+/// Store of trie nodes shared by the states built on it. A state built on the store starts with
+/// its nodes, so that the tries of earlier blocks can be read and the nodes met again in the
+/// traces of later blocks do not have to be hashed again.
+pub trait NodeStore: fmt::Debug + Send + Sync {
+    /// Hash of the node serialized as `bytes`, if the store has it
+    fn node_hash(&self, bytes: &[u8]) -> Option<ZkTrieHash>;
+    /// Return if the store has the node of hash `hash`
+    fn contains(&self, hash: &ZkTrieHash) -> bool;
+    /// Every node of the store
+    fn nodes(&self) -> Vec<(ZkTrieHash, Arc<[u8]>)>;
+    /// Add nodes to the store, the ones it already has are skipped
+    fn insert(&self, nodes: &[(ZkTrieHash, Arc<[u8]>)]) -> io::Result<()>;
+}
+
+/// Nodes of a store in insertion order, keeping at most `max_nodes` of them: the oldest ones are
+/// evicted first, and will be taken from the traces again when they are met.
+#[derive(Debug)]
+struct NodeIndex {
+    nodes: HashMap<ZkTrieHash, Arc<[u8]>>,
+    by_bytes: HashMap<Arc<[u8]>, ZkTrieHash>,
+    order: VecDeque<ZkTrieHash>,
+    max_nodes: usize,
+}
+
+impl NodeIndex {
+    fn new(max_nodes: usize) -> Self {
+        Self {
+            nodes: Default::default(),
+            by_bytes: Default::default(),
+            order: Default::default(),
+            max_nodes,
+        }
+    }
+
+    /// Add a node, return the number of nodes evicted
+    fn insert(&mut self, hash: ZkTrieHash, bytes: Arc<[u8]>) -> usize {
+        if self.nodes.contains_key(&hash) {
+            return 0;
+        }
+        self.nodes.insert(hash, bytes.clone());
+        self.by_bytes.insert(bytes, hash);
+        self.order.push_back(hash);
+
+        let mut evicted = 0;
+        while self.order.len() > self.max_nodes {
+            let hash = self.order.pop_front().unwrap();
+            let bytes = self.nodes.remove(&hash).unwrap();
+            self.by_bytes.remove(&bytes);
+            evicted += 1;
+        }
+        evicted
+    }
+
+    fn nodes(&self) -> Vec<(ZkTrieHash, Arc<[u8]>)> {
+        self.order
+            .iter()
+            .map(|hash| (*hash, self.nodes[hash].clone()))
+            .collect()
+    }
+}
+
+/// Node store living in memory, which can be kept by a long-running service between chunks
+#[derive(Debug)]
+pub struct MemoryNodeStore(RwLock<NodeIndex>);
+
+impl Default for MemoryNodeStore {
+    fn default() -> Self {
+        Self::with_max_nodes(usize::MAX)
+    }
+}
+
+impl MemoryNodeStore {
+    /// Empty store keeping at most `max_nodes` nodes, the oldest ones being evicted first
+    pub fn with_max_nodes(max_nodes: usize) -> Self {
+        Self(RwLock::new(NodeIndex::new(max_nodes)))
+    }
+}
+
+impl NodeStore for MemoryNodeStore {
+    fn node_hash(&self, bytes: &[u8]) -> Option<ZkTrieHash> {
+        self.0.read().unwrap().by_bytes.get(bytes).copied()
+    }
+
+    fn contains(&self, hash: &ZkTrieHash) -> bool {
+        self.0.read().unwrap().nodes.contains_key(hash)
+    }
+
+    fn nodes(&self) -> Vec<(ZkTrieHash, Arc<[u8]>)> {
+        self.0.read().unwrap().nodes()
+    }
+
+    fn insert(&self, nodes: &[(ZkTrieHash, Arc<[u8]>)]) -> io::Result<()> {
+        let mut index = self.0.write().unwrap();
+        for (hash, bytes) in nodes {
+            index.insert(*hash, bytes.clone());
+        }
+        Ok(())
+    }
+}
+
+/// Node store persisted in an append-only file, made of records `hash | len (u32 le) | bytes`.
+///
+/// The file is indexed in memory when opened. Every record is hashed again, and the ones which
+/// are torn by a crash, are not a node or do not match their hash are dropped. The store keeps at
+/// most `max_nodes` nodes: the oldest ones are evicted first, and the file is compacted once it
+/// holds twice as many records.
+pub struct FileNodeStore {
+    path: PathBuf,
+    index: RwLock<NodeIndex>,
+    file: Mutex<FileState>,
+}
+
+struct FileState {
+    writer: BufWriter<File>,
+    /// Number of records in the file, evicted ones included
+    records: usize,
+}
+
+impl fmt::Debug for FileNodeStore {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "FileNodeStore: {}", self.path.display())
+    }
+}
+
+impl FileNodeStore {
+    /// Open the store at `path`, creating it if it does not exist
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::open_with_max_nodes(path, usize::MAX)
+    }
+
+    /// Open the store at `path`, creating it if it does not exist, keeping at most `max_nodes`
+    /// nodes
+    pub fn open_with_max_nodes(path: impl AsRef<Path>, max_nodes: usize) -> io::Result<Self> {
+        builder::init_hash_scheme();
+        let path = path.as_ref().to_path_buf();
+        let mut file = OpenOptions::new()
+            .read(true)
+            .append(true)
+            .create(true)
+            .open(&path)?;
+        let mut data = Vec::new();
+        file.read_to_end(&mut data)?;
+
+        let mut index = NodeIndex::new(max_nodes);
+        let mut offset = 0;
+        let mut records = 0;
+        let mut dropped = 0;
+        while let Some((hash, bytes, len)) = Self::read_record(&data[offset..]) {
+            match ZkTrieNode::parse(bytes) {
+                Ok(node) if node.node_hash() == hash => {
+                    index.insert(hash, Arc::from(bytes));
+                }
+                _ => dropped += 1,
+            }
+            offset += len;
+            records += 1;
+        }
+        if offset < data.len() {
+            log::warn!(
+                "drop {} bytes of torn record at the end of {}",
+                data.len() - offset,
+                path.display()
+            );
+            file.set_len(offset as u64)?;
+        }
+        if dropped > 0 {
+            log::warn!(
+                "drop {} records not matching their hash in {}",
+                dropped,
+                path.display()
+            );
+        }
+        log::debug!(
+            "open node store {} with {} nodes",
+            path.display(),
+            index.nodes.len()
+        );
+
+        let store = Self {
+            path,
+            index: RwLock::new(index),
+            file: Mutex::new(FileState {
+                writer: BufWriter::new(file),
+                records,
+            }),
+        };
+        if records > store.index.read().unwrap().nodes.len() {
+            store.compact(&mut store.file.lock().unwrap())?;
+        }
+        Ok(store)
+    }
+
+    fn read_record(data: &[u8]) -> Option<(ZkTrieHash, &[u8], usize)> {
+        let hash: ZkTrieHash = data.get(..32)?.try_into().unwrap();
+        let len = u32::from_le_bytes(data.get(32..36)?.try_into().unwrap()) as usize;
+        let bytes = data.get(36..36 + len)?;
+        Some((hash, bytes, 36 + len))
+    }
+
+    fn write_record(writer: &mut impl Write, hash: &ZkTrieHash, bytes: &[u8]) -> io::Result<()> {
+        writer.write_all(hash)?;
+        writer.write_all(&(bytes.len() as u32).to_le_bytes())?;
+        writer.write_all(bytes)
+    }
+
+    /// Rewrite the file with the nodes of the index only
+    fn compact(&self, file: &mut FileState) -> io::Result<()> {
+        let nodes = self.index.read().unwrap().nodes();
+        let tmp_path = self.path.with_extension("compact");
+        let mut writer = BufWriter::new(File::create(&tmp_path)?);
+        for (hash, bytes) in &nodes {
+            Self::write_record(&mut writer, hash, bytes)?;
+        }
+        writer.flush()?;
+        writer.get_ref().sync_data()?;
+        fs::rename(&tmp_path, &self.path)?;
+
+        let file_handle = OpenOptions::new().append(true).open(&self.path)?;
+        *file = FileState {
+            writer: BufWriter::new(file_handle),
+            records: nodes.len(),
+        };
+        log::debug!(
+            "compact node store {} to {} nodes",
+            self.path.display(),
+            nodes.len()
+        );
+        Ok(())
+    }
+}
+
+impl NodeStore for FileNodeStore {
+    fn node_hash(&self, bytes: &[u8]) -> Option<ZkTrieHash> {
+        self.index.read().unwrap().by_bytes.get(bytes).copied()
+    }
+
+    fn contains(&self, hash: &ZkTrieHash) -> bool {
+        self.index.read().unwrap().nodes.contains_key(hash)
+    }
+
+    fn nodes(&self) -> Vec<(ZkTrieHash, Arc<[u8]>)> {
+        self.index.read().unwrap().nodes()
+    }
+
+    fn insert(&self, nodes: &[(ZkTrieHash, Arc<[u8]>)]) -> io::Result<()> {
+        let mut file = self.file.lock().unwrap();
+        let max_nodes = {
+            let mut index = self.index.write().unwrap();
+            for (hash, bytes) in nodes {
+                if index.nodes.contains_key(hash) {
+                    continue;
+                }
+                Self::write_record(&mut file.writer, hash, bytes)?;
+                file.records += 1;
+                index.insert(*hash, bytes.clone());
+            }
+            index.max_nodes
+        };
+        file.writer.flush()?;
+        file.writer.get_ref().sync_data()?;
+        if file.records / 2 >= max_nodes {
+            self.compact(&mut file)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::ZkTrieNodes;
+    use std::rc::Rc;
+
+    // branch and leaf nodes of an account proof
+    const NODES: [&str; 3] = [
+        "0917e72849d9c0d67bb31746101cf4895de34892b24d1486daa024a660abc37d860ddffa0c24af819b6e3c1a8b94699fedcdc77656184edc5a39eb81ca0bed790a",
+        "0927fb0f5d23170a387eba5ab2e6d4353fd2ec8ab81022f981548d9acdc07c637a2048ec88c007fbe8be0b597adcb2ce40b5f4581e0cc058d67e8e12528d3e6917",
+        "041822829dca763241624d1f8dd4cf59018fc5f69931d579f8e8a4c3addd6633e605080000000000000000000000000000000000000000000000000000000000000000002d007fffffffffffffffffffffffffffffffffffffffffc078f7390f013506e29d0000000000000000000000000000000000000000000000000000000000000000c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a4702098f5fb9e239eab3ceac3f27b81e481dc3124d55ffed523a839ee8446b64864201c5a77d9fa7ef466951b2f01f724bca3a5820b63000000000000000000000000",
+    ];
+
+    fn nodes() -> Vec<(ZkTrieHash, Arc<[u8]>)> {
+        builder::init_hash_scheme();
+        NODES
+            .iter()
+            .map(|node| {
+                let bytes = hex::decode(node).unwrap();
+                let hash = ZkTrieNode::parse(&bytes).unwrap().node_hash();
+                (hash, Arc::from(bytes))
+            })
+            .collect()
+    }
+
+    fn temp_path(name: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("zktrie-{name}-{}", std::process::id()));
+        let _ = fs::remove_file(&path);
+        path
+    }
+
+    #[test]
+    fn file_node_store_reopen() {
+        let path = temp_path("reopen");
+        let nodes = nodes();
+        {
+            let store = FileNodeStore::open(&path).unwrap();
+            store.insert(&nodes[..2]).unwrap();
+            store.insert(&nodes[..1]).unwrap();
+        }
+        // tear the last record
+        let data = fs::read(&path).unwrap();
+        assert_eq!(data.len(), 2 * 36 + nodes[0].1.len() + nodes[1].1.len());
+        fs::write(&path, &data[..data.len() - 1]).unwrap();
+
+        let store = FileNodeStore::open(&path).unwrap();
+        assert!(store.contains(&nodes[0].0));
+        assert!(!store.contains(&nodes[1].0));
+        assert_eq!(store.node_hash(&nodes[0].1), Some(nodes[0].0));
+        store.insert(&nodes).unwrap();
+        drop(store);
+
+        let store = FileNodeStore::open(&path).unwrap();
+        assert_eq!(store.nodes(), nodes);
+        assert_eq!(store.node_hash(&nodes[2].1), Some(nodes[2].0));
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn file_node_store_drops_wrong_hash() {
+        let path = temp_path("wrong-hash");
+        let nodes = nodes();
+        {
+            let store = FileNodeStore::open(&path).unwrap();
+            store.insert(&nodes[..1]).unwrap();
+            // a record whose hash is not the hash of its node
+            store.insert(&[([1; 32], nodes[1].1.clone())]).unwrap();
+        }
+
+        let store = FileNodeStore::open(&path).unwrap();
+        assert_eq!(store.nodes(), nodes[..1]);
+        assert_eq!(store.node_hash(&nodes[1].1), None);
+        drop(store);
+        // the record is dropped from the file too
+        let data = fs::read(&path).unwrap();
+        assert_eq!(data.len(), 36 + nodes[0].1.len());
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn file_node_store_max_nodes() {
+        let path = temp_path("max-nodes");
+        let nodes = nodes();
+        {
+            let store = FileNodeStore::open_with_max_nodes(&path, 1).unwrap();
+            store.insert(&nodes).unwrap();
+            assert_eq!(store.nodes(), nodes[2..]);
+        }
+        // compacted to the newest node
+        let data = fs::read(&path).unwrap();
+        assert_eq!(data.len(), 36 + nodes[2].1.len());
+
+        let store = FileNodeStore::open_with_max_nodes(&path, 1).unwrap();
+        assert_eq!(store.nodes(), nodes[2..]);
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn memory_node_store_max_nodes() {
+        let nodes = nodes();
+        let store = MemoryNodeStore::with_max_nodes(2);
+        store.insert(&nodes).unwrap();
+        assert!(!store.contains(&nodes[0].0));
+        assert_eq!(store.node_hash(&nodes[0].1), None);
+        assert_eq!(store.nodes(), nodes[1..]);
+    }
+
+    #[test]
+    fn states_of_a_store_share_the_db() {
+        let store: Arc<dyn NodeStore> = Arc::new(MemoryNodeStore::default());
+        store.insert(&nodes()).unwrap();
+        let zk_db = ZkTrieNodes::with_store(store.clone()).to_zk_db();
+        // the nodes of the store are not added to another db
+        let other = ZkTrieNodes::with_store(store).to_zk_db();
+        assert!(Rc::ptr_eq(&zk_db, &other));
+    }
+}